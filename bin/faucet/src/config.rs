@@ -1,6 +1,7 @@
 use std::{
     fmt::{Display, Formatter},
     path::PathBuf,
+    time::Duration,
 };
 
 use miden_node_utils::config::Endpoint;
@@ -25,6 +26,14 @@ pub struct FaucetConfig {
     pub decimals: u8,
     /// Maximum supply of the generated fungible asset
     pub max_supply: u64,
+    /// Account file (`.mac`) of a faucet account created by the node's `make-genesis` command. If
+    /// set, assets are minted from this account instead of a newly created one, and
+    /// `token_symbol`, `decimals` and `max_supply` are ignored
+    #[serde(default)]
+    pub faucet_account_path: Option<PathBuf>,
+    /// Limits the number of requests accepted from a single IP address
+    #[serde(default)]
+    pub rate_limit: RateLimitConfig,
 }
 
 impl FaucetConfig {
@@ -36,8 +45,40 @@ impl FaucetConfig {
 impl Display for FaucetConfig {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.write_fmt(format_args!(
-            "{{ endpoint: \"{}\",  database_filepath: {:?}, asset_amount: {}, token_symbol: {}, decimals: {}, max_supply: {} }}",
-            self.endpoint, self.database_filepath, self.asset_amount, self.token_symbol, self.decimals, self.max_supply
+            "{{ endpoint: \"{}\",  database_filepath: {:?}, asset_amount: {}, token_symbol: {}, decimals: {}, max_supply: {}, faucet_account_path: {:?}, rate_limit: {} }}",
+            self.endpoint, self.database_filepath, self.asset_amount, self.token_symbol, self.decimals, self.max_supply, self.faucet_account_path, self.rate_limit
+        ))
+    }
+}
+
+// Rate limit config
+// ================================================================================================
+
+/// Each IP address can request tokens at most `max_requests` times every `period_secs` seconds.
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RateLimitConfig {
+    pub max_requests: usize,
+    pub period_secs: u64,
+}
+
+impl RateLimitConfig {
+    pub fn period(&self) -> Duration {
+        Duration::from_secs(self.period_secs)
+    }
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self { max_requests: 5, period_secs: 3600 }
+    }
+}
+
+impl Display for RateLimitConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!(
+            "{{ max_requests: {}, period_secs: {} }}",
+            self.max_requests, self.period_secs
         ))
     }
 }
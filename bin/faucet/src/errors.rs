@@ -28,6 +28,9 @@ pub enum FaucetError {
 
     #[error("Failed to create Miden account: {0}")]
     AccountCreationError(String),
+
+    #[error("Too many requests, retry in {0} seconds")]
+    TooManyRequests(u64),
 }
 
 impl error::ResponseError for FaucetError {
@@ -40,6 +43,7 @@ impl error::ResponseError for FaucetError {
             FaucetError::SyncError(msg) => msg.to_string(),
             FaucetError::AccountCreationError(msg) => msg.to_string(),
             FaucetError::DatabaseError(msg) => msg.to_string(),
+            FaucetError::TooManyRequests(_) => self.to_string(),
         };
 
         HttpResponse::build(self.status_code())
@@ -50,6 +54,7 @@ impl error::ResponseError for FaucetError {
     fn status_code(&self) -> actix_web::http::StatusCode {
         match *self {
             FaucetError::BadRequest(_) => StatusCode::BAD_REQUEST,
+            FaucetError::TooManyRequests(_) => StatusCode::TOO_MANY_REQUESTS,
             _ => StatusCode::INTERNAL_SERVER_ERROR,
         }
     }
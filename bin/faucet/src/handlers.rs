@@ -1,4 +1,6 @@
-use actix_web::{get, http::header, post, web, HttpResponse, Result};
+use std::time::Instant;
+
+use actix_web::{get, http::header, post, web, HttpRequest, HttpResponse, Result};
 use miden_client::{
     client::transactions::transaction_request::TransactionTemplate, store::InputNoteRecord,
 };
@@ -36,11 +38,19 @@ pub async fn get_metadata(state: web::Data<FaucetState>) -> HttpResponse {
 
 #[post("/get_tokens")]
 pub async fn get_tokens(
+    http_req: HttpRequest,
     req: web::Json<FaucetRequest>,
     state: web::Data<FaucetState>,
 ) -> Result<HttpResponse> {
     info!("Received a request with account_id: {}", req.account_id);
 
+    // Limit the requests per IP address, the peer address is used since forwarding headers can
+    // be set by the client
+    let peer_addr = http_req.peer_addr().ok_or_else(|| {
+        FaucetError::BadRequest("Failed to read the client's address".to_string())
+    })?;
+    state.rate_limiter.check(peer_addr.ip(), Instant::now())?;
+
     let client = state.client.clone();
 
     // Receive and hex user account id
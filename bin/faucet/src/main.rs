@@ -1,6 +1,7 @@
 mod config;
 mod errors;
 mod handlers;
+mod rate_limit;
 mod utils;
 
 use std::path::PathBuf;
@@ -0,0 +1,81 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    net::IpAddr,
+    sync::Mutex,
+    time::Instant,
+};
+
+use crate::{config::RateLimitConfig, errors::FaucetError};
+
+/// Tracks the requests received from each IP address over a sliding window.
+pub struct RateLimiter {
+    config: RateLimitConfig,
+    requests: Mutex<HashMap<IpAddr, VecDeque<Instant>>>,
+}
+
+impl RateLimiter {
+    pub fn new(config: RateLimitConfig) -> Self {
+        Self {
+            config,
+            requests: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Records a request from `ip` received at `now`, or returns an error if `ip` already reached
+    /// the maximum number of requests in the current period.
+    pub fn check(&self, ip: IpAddr, now: Instant) -> Result<(), FaucetError> {
+        let period = self.config.period();
+        let mut requests = self.requests.lock().expect("lock is not poisoned");
+
+        // forget the requests which are out of the window
+        requests.retain(|_, times| {
+            while times.front().is_some_and(|time| now.duration_since(*time) >= period) {
+                times.pop_front();
+            }
+            !times.is_empty()
+        });
+
+        let times = requests.entry(ip).or_default();
+        if times.len() >= self.config.max_requests {
+            let retry_in = times
+                .front()
+                .map(|oldest| period.saturating_sub(now.duration_since(*oldest)))
+                .unwrap_or(period);
+            return Err(FaucetError::TooManyRequests(retry_in.as_secs().max(1)));
+        }
+
+        times.push_back(now);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        net::{IpAddr, Ipv4Addr},
+        time::{Duration, Instant},
+    };
+
+    use super::RateLimiter;
+    use crate::{config::RateLimitConfig, errors::FaucetError};
+
+    #[test]
+    fn requests_are_limited_per_ip() {
+        let limiter = RateLimiter::new(RateLimitConfig { max_requests: 2, period_secs: 60 });
+        let ip = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1));
+        let other_ip = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 2));
+        let start = Instant::now();
+
+        assert!(limiter.check(ip, start).is_ok());
+        assert!(limiter.check(ip, start + Duration::from_secs(10)).is_ok());
+        assert!(matches!(
+            limiter.check(ip, start + Duration::from_secs(20)),
+            Err(FaucetError::TooManyRequests(40))
+        ));
+        assert!(limiter.check(other_ip, start + Duration::from_secs(20)).is_ok());
+
+        // the first request leaves the window
+        assert!(limiter.check(ip, start + Duration::from_secs(60)).is_ok());
+    }
+}
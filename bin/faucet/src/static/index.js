@@ -9,7 +9,7 @@ document.addEventListener('DOMContentLoaded', function () {
     button.addEventListener('click', handleButtonClick);
 
     function fetchMetadata() {
-        fetch('http://localhost:8080/get_metadata')
+        fetch('/get_metadata')
             .then(response => response.json())
             .then(data => {
                 faucetIdElem.textContent = data.id;
@@ -35,12 +35,18 @@ document.addEventListener('DOMContentLoaded', function () {
 
         button.textContent = 'Loading...';
         try {
-            const response = await fetch('http://localhost:8080/get_tokens', {
+            const response = await fetch('/get_tokens', {
                 method: 'POST',
                 headers: { 'Content-Type': 'application/json' },
                 body: JSON.stringify({ account_id: accountId })
             });
 
+            if (response.status === 429) {
+                errorMessage.textContent = await response.text();
+                errorMessage.style.display = 'block';
+                return;
+            }
+
             if (!response.ok) {
                 throw new Error(`HTTP error! Status: ${response.status}`);
             }
@@ -1,4 +1,7 @@
-use std::{path::PathBuf, sync::Arc};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
 use async_mutex::Mutex;
 use miden_client::{
@@ -8,7 +11,7 @@ use miden_client::{
 };
 use miden_lib::{accounts::faucets::create_basic_fungible_faucet, AuthScheme};
 use miden_objects::{
-    accounts::{Account, AccountId, AccountStorageType},
+    accounts::{Account, AccountData, AccountId, AccountStorageType, AccountType},
     assets::TokenSymbol,
     crypto::{dsa::rpo_falcon512::SecretKey, rand::RpoRandomCoin},
     Felt,
@@ -16,7 +19,7 @@ use miden_objects::{
 use rand_chacha::{rand_core::SeedableRng, ChaCha20Rng};
 use tracing::info;
 
-use crate::{config::FaucetConfig, errors::FaucetError};
+use crate::{config::FaucetConfig, errors::FaucetError, rate_limit::RateLimiter};
 
 pub type FaucetClient = Client<TonicRpcClient, RpoRandomCoin, SqliteStore>;
 
@@ -25,18 +28,22 @@ pub struct FaucetState {
     pub id: AccountId,
     pub asset_amount: u64,
     pub client: Arc<Mutex<FaucetClient>>,
+    pub rate_limiter: Arc<RateLimiter>,
 }
 
 /// Instatiantes the Miden faucet
 pub async fn build_faucet_state(config: FaucetConfig) -> Result<FaucetState, FaucetError> {
     let mut client = build_client(config.database_filepath.clone(), config.node_url.clone())?;
 
-    let faucet_account = create_fungible_faucet(
-        &config.token_symbol,
-        &config.decimals,
-        &config.max_supply,
-        &mut client,
-    )?;
+    let faucet_account = match &config.faucet_account_path {
+        Some(path) => import_fungible_faucet(path, &mut client)?,
+        None => create_fungible_faucet(
+            &config.token_symbol,
+            &config.decimals,
+            &config.max_supply,
+            &mut client,
+        )?,
+    };
 
     // Sync client
     client.sync_state().await.map_err(FaucetError::SyncError)?;
@@ -47,6 +54,7 @@ pub async fn build_faucet_state(config: FaucetConfig) -> Result<FaucetState, Fau
         id: faucet_account.id(),
         asset_amount: config.asset_amount,
         client: Arc::new(Mutex::new(client)),
+        rate_limiter: Arc::new(RateLimiter::new(config.rate_limit)),
     })
 }
 
@@ -127,3 +135,37 @@ pub fn create_fungible_faucet(
 
     Ok(account)
 }
+
+/// Imports the fungible faucet account from an account file created by `make-genesis`.
+///
+/// The account is imported only once, later calls use the state of the account in the client's
+/// store.
+pub fn import_fungible_faucet(
+    path: &Path,
+    client: &mut FaucetClient,
+) -> Result<Account, FaucetError> {
+    let account_data = AccountData::read(path).map_err(|err| {
+        FaucetError::ConfigurationError(format!(
+            "Failed to read faucet account file {}: {err}",
+            path.display()
+        ))
+    })?;
+
+    let account_id = account_data.account.id();
+    if account_id.account_type() != AccountType::FungibleFaucet {
+        return Err(FaucetError::ConfigurationError(format!(
+            "Account {account_id} is not a fungible faucet"
+        )));
+    }
+
+    if let Ok((account, _)) = client.get_account(account_id) {
+        return Ok(account);
+    }
+
+    let account = account_data.account.clone();
+    client
+        .import_account(account_data)
+        .map_err(|err| FaucetError::AccountCreationError(err.to_string()))?;
+
+    Ok(account)
+}
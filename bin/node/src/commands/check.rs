@@ -0,0 +1,332 @@
+use std::{collections::BTreeSet, fs, path::Path, time::Duration};
+
+use anyhow::{anyhow, Result};
+use miden_node_proto::generated::{
+    block_producer::api_client as block_producer_client,
+    digest::Digest,
+    requests::{
+        GetBlockHeaderByNumberRequest, GetBlockProducerStatusRequest, GetNodeStatusRequest,
+        GetStoreStatusRequest,
+    },
+    rpc::api_client as rpc_client,
+    store::api_client as store_client,
+};
+use miden_node_store::genesis::GenesisState;
+use miden_objects::{utils::Deserializable, BlockHeader};
+use tonic::transport::{Channel, Endpoint};
+
+use crate::config::{NodeConfig, Transport};
+
+// SMOKE CHECK
+// ===================================================================================================
+
+/// Outcome of a check, the details of what was checked or the diagnosis of the failure.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckResult {
+    pub name: String,
+    pub outcome: Result<String, String>,
+}
+
+impl CheckResult {
+    fn new(name: impl Into<String>, outcome: Result<String, String>) -> Self {
+        Self { name: name.into(), outcome }
+    }
+}
+
+/// Checks the node of `config` is deployed correctly and prints the outcome of each check, failing
+/// if any of them failed.
+///
+/// Each request to the components must complete within `timeout`.
+pub async fn check(config: &NodeConfig, timeout: Duration) -> Result<()> {
+    let results = run_checks(config, timeout).await;
+
+    for result in &results {
+        match &result.outcome {
+            Ok(details) => println!("[ok]     {}: {details}", result.name),
+            Err(diagnosis) => println!("[failed] {}: {diagnosis}", result.name),
+        }
+    }
+
+    let failed = results.iter().filter(|result| result.outcome.is_err()).count();
+    if failed != 0 {
+        return Err(anyhow!("{failed} of {} checks failed", results.len()));
+    }
+
+    Ok(())
+}
+
+/// Runs the checks of the node of `config`, all of them even if some fail:
+///
+/// - the configuration is valid,
+/// - the genesis file can be read,
+/// - the stores answer their status and a read of the genesis block header,
+/// - the block producers answer their status, as a no-op ping,
+/// - the RPC answers the status of the node and the genesis block header,
+/// - the followed node, for a replica, answers the status of its store,
+///
+/// and the genesis block is the same for all the components and the genesis file.
+///
+/// With the in-process transport, the store and the block producer are only reached through the
+/// RPC, which reports their status.
+pub async fn run_checks(config: &NodeConfig, timeout: Duration) -> Vec<CheckResult> {
+    let mut results = Vec::new();
+
+    results.push(CheckResult::new(
+        "configuration",
+        config.validate().map(|_| "valid".to_string()).map_err(|err| err.to_string()),
+    ));
+
+    let mut genesis = GenesisCheck::default();
+    if let Some(store) = &config.store {
+        let outcome = read_genesis_hash(&store.genesis_filepath).map(|genesis_hash| {
+            let details = format!("genesis hash {genesis_hash}");
+            genesis.expected = Some((genesis_hash, "the genesis file".to_string()));
+            details
+        });
+        results.push(CheckResult::new("genesis file", outcome));
+    }
+
+    for store_url in store_urls(config) {
+        let outcome = check_store(&store_url, timeout, &mut genesis).await;
+        results.push(CheckResult::new(format!("store at {store_url}"), outcome));
+    }
+
+    for block_producer_url in block_producer_urls(config) {
+        let outcome = check_block_producer(&block_producer_url, timeout).await;
+        results.push(CheckResult::new(format!("block producer at {block_producer_url}"), outcome));
+    }
+
+    if let Some(rpc) = &config.rpc {
+        let rpc_url = rpc.endpoint.to_string();
+        let outcome = check_rpc(&rpc_url, timeout, &mut genesis).await;
+        results.push(CheckResult::new(format!("rpc at {rpc_url}"), outcome));
+    }
+
+    if let Some(follow) = config.store.as_ref().and_then(|store| store.follow.as_ref()) {
+        let outcome = check_followed_node(&follow.rpc_url, timeout, &mut genesis).await;
+        results.push(CheckResult::new(format!("followed node at {}", follow.rpc_url), outcome));
+    }
+
+    results
+}
+
+// COMPONENTS
+// ---------------------------------------------------------------------------------------------------
+
+/// Returns the URLs the store is reached at: its endpoint and the store URLs of the other
+/// components, if they don't run in-process.
+fn store_urls(config: &NodeConfig) -> BTreeSet<String> {
+    if config.transport == Transport::InProcess {
+        return BTreeSet::new();
+    }
+
+    let endpoint = config.store.as_ref().map(|store| store.endpoint.to_string());
+    let block_producer = config.block_producer.as_ref().map(|config| config.store_url.clone());
+    let rpc = config.rpc.as_ref().map(|rpc| rpc.store_url.clone());
+
+    [endpoint, block_producer, rpc].into_iter().flatten().collect()
+}
+
+/// Returns the URLs the block producer is reached at: its endpoint and the block producer URL of
+/// the RPC, if they don't run in-process.
+fn block_producer_urls(config: &NodeConfig) -> BTreeSet<String> {
+    if config.transport == Transport::InProcess {
+        return BTreeSet::new();
+    }
+
+    let endpoint = config.block_producer.as_ref().map(|config| config.endpoint.to_string());
+    // Replicas forward the transactions to the sequencer's block producer
+    let rpc = config.rpc.as_ref().map(|rpc| rpc.block_producer_url.clone());
+
+    [endpoint, rpc].into_iter().flatten().collect()
+}
+
+async fn check_store(url: &str, timeout: Duration, genesis: &mut GenesisCheck) -> CheckOutcome {
+    let mut client = store_client::ApiClient::new(connect(url, timeout).await?);
+
+    let status = client
+        .get_status(GetStoreStatusRequest {})
+        .await
+        .map_err(|status| format!("status request failed: {}", status.message()))?
+        .into_inner();
+    let genesis_hash = status.genesis_hash.ok_or("the store didn't report its genesis hash")?;
+    genesis.check(&genesis_hash, &format!("the store at {url}"))?;
+
+    let genesis_header = client
+        .get_block_header_by_number(GetBlockHeaderByNumberRequest { block_num: Some(0) })
+        .await
+        .map_err(|status| format!("read of the genesis block failed: {}", status.message()))?
+        .into_inner()
+        .block_header
+        .ok_or("the store has no genesis block")?;
+    let genesis_header = BlockHeader::try_from(genesis_header)
+        .map_err(|err| format!("the store returned an invalid genesis block header: {err}"))?;
+    if Digest::from(genesis_header.hash()) != genesis_hash {
+        return Err("the genesis block header doesn't match the genesis hash of the store".into());
+    }
+
+    Ok(format!(
+        "version {}, chain tip {}, genesis hash {genesis_hash}",
+        status.version, status.chain_tip
+    ))
+}
+
+async fn check_block_producer(url: &str, timeout: Duration) -> CheckOutcome {
+    let mut client = block_producer_client::ApiClient::new(connect(url, timeout).await?);
+
+    let status = client
+        .get_status(GetBlockProducerStatusRequest {})
+        .await
+        .map_err(|status| format!("status request failed: {}", status.message()))?
+        .into_inner();
+
+    Ok(format!(
+        "version {}, mempool depth {}, block production {}{}",
+        status.version,
+        status.mempool_depth,
+        if status.block_production_paused {
+            "paused"
+        } else {
+            "running"
+        },
+        if status.simulation { ", in simulation mode" } else { "" }
+    ))
+}
+
+async fn check_rpc(url: &str, timeout: Duration, genesis: &mut GenesisCheck) -> CheckOutcome {
+    let mut client = rpc_client::ApiClient::new(connect(url, timeout).await?);
+
+    let status = client
+        .get_node_status(GetNodeStatusRequest {})
+        .await
+        .map_err(|status| format!("status request failed: {}", status.message()))?
+        .into_inner();
+    let store = status.store.ok_or("the rpc can't reach its store")?;
+    let genesis_hash = store.genesis_hash.ok_or("the store didn't report its genesis hash")?;
+    genesis.check(&genesis_hash, &format!("the store of the rpc at {url}"))?;
+
+    client
+        .get_block_header_by_number(GetBlockHeaderByNumberRequest { block_num: Some(0) })
+        .await
+        .map_err(|status| format!("read of the genesis block failed: {}", status.message()))?;
+
+    Ok(format!(
+        "version {}, chain tip {}, block producer {}",
+        status.version,
+        store.chain_tip,
+        if status.block_producer.is_some() {
+            "reachable"
+        } else {
+            "unreachable"
+        }
+    ))
+}
+
+async fn check_followed_node(
+    url: &str,
+    timeout: Duration,
+    genesis: &mut GenesisCheck,
+) -> CheckOutcome {
+    let mut client = rpc_client::ApiClient::new(connect(url, timeout).await?);
+
+    let status = client
+        .get_node_status(GetNodeStatusRequest {})
+        .await
+        .map_err(|status| format!("status request failed: {}", status.message()))?
+        .into_inner();
+    let store = status.store.ok_or("the followed node can't reach its store")?;
+    let genesis_hash = store.genesis_hash.ok_or("the store didn't report its genesis hash")?;
+    genesis.check(&genesis_hash, &format!("the followed node at {url}"))?;
+
+    Ok(format!("version {}, chain tip {}", status.version, store.chain_tip))
+}
+
+// HELPERS
+// ---------------------------------------------------------------------------------------------------
+
+/// Details of a successful check, or the diagnosis of its failure.
+type CheckOutcome = Result<String, String>;
+
+/// Genesis hash all the components must agree on, the hash of the genesis file if it can be read,
+/// otherwise the first one reported by a component.
+#[derive(Default)]
+struct GenesisCheck {
+    /// The genesis hash and the component which reported it
+    expected: Option<(Digest, String)>,
+}
+
+impl GenesisCheck {
+    /// Checks `genesis_hash`, reported by `component`, is the expected one.
+    fn check(&mut self, genesis_hash: &Digest, component: &str) -> Result<(), String> {
+        match &self.expected {
+            Some((expected, source)) if expected != genesis_hash => Err(format!(
+                "genesis hash {genesis_hash} of {component} doesn't match the genesis hash \
+                 {expected} of {source}, the components belong to different chains"
+            )),
+            Some(_) => Ok(()),
+            None => {
+                self.expected = Some((genesis_hash.clone(), component.to_string()));
+                Ok(())
+            },
+        }
+    }
+}
+
+/// Returns the hash of the genesis block of the genesis file at `path`.
+fn read_genesis_hash(path: &Path) -> Result<Digest, String> {
+    let bytes = fs::read(path)
+        .map_err(|err| format!("failed to read the genesis file {}: {err}", path.display()))?;
+    let genesis_state = GenesisState::read_from_bytes(&bytes)
+        .map_err(|err| format!("invalid genesis file {}: {err}", path.display()))?;
+    let (block_header, ..) = genesis_state
+        .into_block_parts()
+        .map_err(|err| format!("malformed genesis state in {}: {err}", path.display()))?;
+
+    Ok(block_header.hash().into())
+}
+
+/// Connects to the component at `url`, its requests timing out after `timeout`.
+async fn connect(url: &str, timeout: Duration) -> Result<Channel, String> {
+    Endpoint::from_shared(url.to_string())
+        .map_err(|err| format!("invalid URL: {err}"))?
+        .connect_timeout(timeout)
+        .timeout(timeout)
+        .connect()
+        .await
+        .map_err(|err| format!("failed to connect: {err}, is the component running?"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use miden_node_store::config::StoreConfig;
+    use miden_node_utils::config::Endpoint;
+
+    use super::run_checks;
+    use crate::config::{NodeConfig, Transport};
+
+    #[tokio::test]
+    async fn test_checks_diagnose_unreachable_components() {
+        let config = NodeConfig {
+            transport: Transport::Grpc,
+            block_producer: None,
+            rpc: None,
+            store: Some(StoreConfig::new(
+                Endpoint { host: "127.0.0.1".to_string(), port: 1 },
+                "miden-store.sqlite3".into(),
+                "missing-genesis.dat".into(),
+            )),
+        };
+
+        let results = run_checks(&config, Duration::from_secs(1)).await;
+        let names: Vec<_> = results.iter().map(|result| result.name.as_str()).collect();
+        assert_eq!(names, ["configuration", "genesis file", "store at http://127.0.0.1:1"]);
+
+        // The missing genesis file is reported by the validation and by the genesis check, and the
+        // store isn't running
+        assert!(results.iter().all(|result| result.outcome.is_err()));
+        let diagnosis = results[2].outcome.as_ref().unwrap_err();
+        assert!(diagnosis.starts_with("failed to connect"), "{diagnosis}");
+    }
+}
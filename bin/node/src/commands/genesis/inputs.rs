@@ -1,3 +1,4 @@
+use miden_node_utils::chain_params::BLOCK_OUTPUT_NOTES_TREE_DEPTH;
 use serde::Deserialize;
 
 // INPUT HELPER STRUCTS
@@ -11,6 +12,12 @@ pub struct GenesisInput {
     pub version: u64,
     pub timestamp: u64,
     pub accounts: Vec<AccountInput>,
+    #[serde(default)]
+    pub notes: Vec<NoteInput>,
+    #[serde(default)]
+    pub network: Option<NetworkInput>,
+    #[serde(default)]
+    pub parameters: Option<ParametersInput>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -37,7 +44,74 @@ pub struct BasicFungibleFaucetInputs {
     pub max_supply: u64,
 }
 
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type")]
+pub enum NoteInput {
+    P2ID(P2IdNoteInputs),
+}
+
+/// A public P2ID note paying an asset issued by a genesis faucet to a genesis account.
+#[derive(Debug, Clone, Deserialize)]
+pub struct P2IdNoteInputs {
+    /// Index in `accounts` of the faucet issuing the asset, also the sender of the note
+    pub faucet: usize,
+    /// Index in `accounts` of the account which can consume the note
+    pub target: usize,
+    pub amount: u64,
+    pub serial_seed: String,
+}
+
 #[derive(Debug, Clone, Copy, Deserialize)]
 pub enum AuthSchemeInput {
     RpoFalcon512,
 }
+
+/// Test network of nodes sharing the genesis block, a configuration file is generated per node.
+#[derive(Debug, Clone, Deserialize)]
+pub struct NetworkInput {
+    /// Host the endpoints of the nodes listen on, and at which the nodes reach each other
+    pub host: String,
+    /// Ports of the n-th node start from `base_port + 10 * n`
+    pub base_port: u16,
+    /// Names of the nodes, also the directories of their files. The first node is the sequencer,
+    /// the others are replicas following it.
+    pub nodes: Vec<String>,
+    /// Testnet faucet served by the sequencer's RPC, disabled if not set
+    #[serde(default)]
+    pub faucet: Option<NetworkFaucetInput>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct NetworkFaucetInput {
+    /// Index in `accounts` of the faucet minting the asset
+    pub account: usize,
+    /// Amount of the asset minted per request
+    pub asset_amount: u64,
+}
+
+/// Parameters of the chain, committed to by the genesis block header
+#[derive(Debug, Clone, Deserialize)]
+pub struct ParametersInput {
+    pub chain_id: u32,
+    /// Maximum number of transactions in a batch
+    pub max_batch_size: u32,
+    /// Depth of the note tree of the blocks, the one supported by the node if not set
+    #[serde(default = "default_note_tree_depth")]
+    pub note_tree_depth: u8,
+    /// Fees charged for the transactions, no fees are charged if not set
+    #[serde(default)]
+    pub fees: Option<FeesInput>,
+}
+
+fn default_note_tree_depth() -> u8 {
+    BLOCK_OUTPUT_NOTES_TREE_DEPTH
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct FeesInput {
+    /// Index in `accounts` of the account the fees are paid to
+    pub operator: usize,
+    /// Index in `accounts` of the faucet of the fungible asset the fees are paid in
+    pub faucet: usize,
+    pub min_amount: u64,
+}
@@ -0,0 +1,340 @@
+use std::{collections::BTreeSet, fs, path::Path};
+
+use anyhow::{anyhow, Result};
+use clap::ValueEnum;
+use miden_objects::{
+    accounts::{AccountData, AccountId},
+    crypto::dsa::rpo_falcon512::SecretKey,
+    utils::serde::Serializable,
+    Digest, Word,
+};
+use rand::RngCore;
+use rand_chacha::{rand_core::SeedableRng, ChaCha20Rng};
+use serde::{Deserialize, Serialize};
+use sha3::{Digest as _, Sha3_256};
+
+/// Iterations of the key derivation of the keystores.
+const KDF_ITERATIONS: u32 = 262_144;
+
+/// Length of the key derived from the password, the first half encrypts the secret key and the
+/// second half authenticates the ciphertext.
+const DERIVED_KEY_LEN: usize = 64;
+
+/// Block size of SHA3-256, used by HMAC.
+const SHA3_256_BLOCK_LEN: usize = 136;
+
+// KEY EXPORT
+// ================================================================================================
+
+/// Format of the exported authentication keys of the genesis accounts.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+pub enum KeyFormat {
+    /// The account file, `account<n>.mac`, with the seed of the key, importable by the CLI wallet
+    Wallet,
+    /// The serialized secret key as hex, `account<n>.hex`
+    Hex,
+    /// A JSON keystore encrypting the secret key with a password, `account<n>.keystore.json`
+    Keystore,
+}
+
+/// Formats in which the authentication keys of the genesis accounts are exported.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeyExport {
+    formats: BTreeSet<KeyFormat>,
+    keystore_password: Option<String>,
+}
+
+impl KeyExport {
+    /// Returns the export of the keys in `formats`, the keystores being encrypted with the password
+    /// read from `keystore_password_file`.
+    pub fn new(
+        formats: impl IntoIterator<Item = KeyFormat>,
+        keystore_password_file: Option<&Path>,
+    ) -> Result<Self> {
+        let formats: BTreeSet<KeyFormat> = formats.into_iter().collect();
+        let keystore_password = match (formats.contains(&KeyFormat::Keystore), keystore_password_file)
+        {
+            (true, Some(path)) => {
+                let password = fs::read_to_string(path).map_err(|err| {
+                    anyhow!("Failed to read keystore password file {}: {err}", path.display())
+                })?;
+                let password = password.trim_end_matches(['\n', '\r']).to_string();
+                if password.is_empty() {
+                    return Err(anyhow!("The keystore password file {} is empty.", path.display()));
+                }
+                Some(password)
+            },
+            (true, None) => {
+                return Err(anyhow!(
+                    "The keystore format requires a password. Use the --keystore-password-file flag to pass in the password file."
+                ))
+            },
+            (false, _) => None,
+        };
+
+        Ok(Self { formats, keystore_password })
+    }
+
+    /// Returns `true` if the keys are exported in `format`.
+    pub fn contains(&self, format: KeyFormat) -> bool {
+        self.formats.contains(&format)
+    }
+
+    /// Writes the keys of the account `account_data`, whose secret key is `secret_key`, to
+    /// `accounts_path` in each format, the files being named after the account's `index`.
+    pub fn write(
+        &self,
+        accounts_path: &Path,
+        index: usize,
+        account_data: &AccountData,
+        secret_key: &SecretKey,
+        force: bool,
+    ) -> Result<()> {
+        for format in &self.formats {
+            let path = match format {
+                KeyFormat::Wallet => accounts_path.join(format!("account{index}.mac")),
+                KeyFormat::Hex => accounts_path.join(format!("account{index}.hex")),
+                KeyFormat::Keystore => accounts_path.join(format!("account{index}.keystore.json")),
+            };
+            check_overwrite(&path, force)?;
+
+            match format {
+                KeyFormat::Wallet => account_data.write(&path)?,
+                KeyFormat::Hex => fs::write(&path, hex::encode(secret_key.to_bytes()) + "\n")?,
+                KeyFormat::Keystore => {
+                    let password =
+                        self.keystore_password.as_ref().expect("checked by KeyExport::new");
+                    let keystore = Keystore::encrypt(
+                        account_data.account.id(),
+                        secret_key,
+                        password,
+                        KDF_ITERATIONS,
+                    );
+                    fs::write(&path, serde_json::to_string_pretty(&keystore)? + "\n")?;
+                },
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for KeyExport {
+    fn default() -> Self {
+        Self {
+            formats: [KeyFormat::Wallet].into(),
+            keystore_password: None,
+        }
+    }
+}
+
+fn check_overwrite(path: &Path, force: bool) -> Result<()> {
+    if let Ok(path_exists) = path.try_exists() {
+        if path_exists && !force {
+            return Err(anyhow!("Failed to generate account file {} because it already exists. Use the --force flag to overwrite.", path.display()));
+        }
+    }
+
+    Ok(())
+}
+
+// KEYSTORE
+// ================================================================================================
+
+/// JSON keystore of the secret key of an account, in the layout of the Web3 secret storage.
+///
+/// The key is derived from the password with PBKDF2 over HMAC-SHA3-256, its first half encrypts the
+/// serialized secret key with ChaCha20, and the MAC is the SHA3-256 hash of its second half and
+/// the ciphertext. The binary fields are hex encoded.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Keystore {
+    pub version: u32,
+    pub account_id: String,
+    pub auth_scheme: String,
+    pub public_key: String,
+    pub crypto: KeystoreCrypto,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeystoreCrypto {
+    pub cipher: String,
+    pub cipherparams: CipherParams,
+    pub ciphertext: String,
+    pub kdf: String,
+    pub kdfparams: KdfParams,
+    pub mac: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CipherParams {
+    pub nonce: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KdfParams {
+    pub prf: String,
+    pub c: u32,
+    pub dklen: usize,
+    pub salt: String,
+}
+
+impl Keystore {
+    /// Encrypts `secret_key`, the key of the account `account_id`, with `password`, deriving the
+    /// encryption key with `iterations` of PBKDF2.
+    pub fn encrypt(
+        account_id: AccountId,
+        secret_key: &SecretKey,
+        password: &str,
+        iterations: u32,
+    ) -> Self {
+        let mut salt = [0; 32];
+        rand::thread_rng().fill_bytes(&mut salt);
+        let nonce = rand::thread_rng().next_u64();
+
+        let derived_key = derive_key(password, &salt, iterations);
+        let mut ciphertext = secret_key.to_bytes();
+        apply_keystream(&derived_key, nonce, &mut ciphertext);
+
+        Self {
+            version: 1,
+            account_id: account_id.to_hex(),
+            auth_scheme: "RpoFalcon512".to_string(),
+            public_key: Digest::from(Word::from(secret_key.public_key())).to_hex(),
+            crypto: KeystoreCrypto {
+                cipher: "chacha20".to_string(),
+                cipherparams: CipherParams { nonce: hex::encode(nonce.to_be_bytes()) },
+                ciphertext: hex::encode(&ciphertext),
+                kdf: "pbkdf2".to_string(),
+                kdfparams: KdfParams {
+                    prf: "hmac-sha3-256".to_string(),
+                    c: iterations,
+                    dklen: DERIVED_KEY_LEN,
+                    salt: hex::encode(salt),
+                },
+                mac: hex::encode(mac(&derived_key, &ciphertext)),
+            },
+        }
+    }
+}
+
+/// Derives the key of a keystore from `password` with PBKDF2 over HMAC-SHA3-256.
+fn derive_key(password: &str, salt: &[u8], iterations: u32) -> [u8; DERIVED_KEY_LEN] {
+    let hmac = HmacSha3::new(password.as_bytes());
+    let mut derived_key = [0; DERIVED_KEY_LEN];
+    for (index, block) in derived_key.chunks_mut(32).enumerate() {
+        let block_index = (index as u32 + 1).to_be_bytes();
+        let mut u = hmac.mac(&[salt, &block_index]);
+        let mut t = u;
+        for _ in 1..iterations {
+            u = hmac.mac(&[&u]);
+            t.iter_mut().zip(u).for_each(|(t, u)| *t ^= u);
+        }
+        block.copy_from_slice(&t);
+    }
+
+    derived_key
+}
+
+/// HMAC-SHA3-256 keyed once, the PBKDF2 iterations only hashing their message.
+struct HmacSha3 {
+    inner: Sha3_256,
+    outer: Sha3_256,
+}
+
+impl HmacSha3 {
+    fn new(key: &[u8]) -> Self {
+        let mut block_key = [0; SHA3_256_BLOCK_LEN];
+        if key.len() > SHA3_256_BLOCK_LEN {
+            block_key[..32].copy_from_slice(&Sha3_256::digest(key));
+        } else {
+            block_key[..key.len()].copy_from_slice(key);
+        }
+
+        let mut inner = Sha3_256::new();
+        inner.update(block_key.map(|byte| byte ^ 0x36));
+        let mut outer = Sha3_256::new();
+        outer.update(block_key.map(|byte| byte ^ 0x5c));
+
+        Self { inner, outer }
+    }
+
+    fn mac(&self, message: &[&[u8]]) -> [u8; 32] {
+        let mut inner = self.inner.clone();
+        message.iter().for_each(|part| inner.update(part));
+
+        let mut outer = self.outer.clone();
+        outer.update(inner.finalize());
+        outer.finalize().into()
+    }
+}
+
+/// Encrypts or decrypts `data` with the ChaCha20 keystream of the first half of `derived_key`.
+fn apply_keystream(derived_key: &[u8; DERIVED_KEY_LEN], nonce: u64, data: &mut [u8]) {
+    let mut key = [0; 32];
+    key.copy_from_slice(&derived_key[..32]);
+    let mut chacha = ChaCha20Rng::from_seed(key);
+    chacha.set_stream(nonce);
+
+    let mut keystream = vec![0; data.len()];
+    chacha.fill_bytes(&mut keystream);
+    data.iter_mut().zip(keystream).for_each(|(byte, key)| *byte ^= key);
+}
+
+fn mac(derived_key: &[u8; DERIVED_KEY_LEN], ciphertext: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha3_256::new();
+    hasher.update(&derived_key[32..]);
+    hasher.update(ciphertext);
+    hasher.finalize().into()
+}
+
+#[cfg(test)]
+mod tests {
+    use miden_objects::{utils::serde::Deserializable, Felt};
+
+    use super::*;
+
+    /// Decrypts the secret key of `keystore` with `password`, failing if the password is wrong.
+    fn decrypt(keystore: &Keystore, password: &str) -> Result<SecretKey> {
+        let KeystoreCrypto { cipherparams, ciphertext, kdfparams, .. } = &keystore.crypto;
+        let salt = hex::decode(&kdfparams.salt)?;
+        let nonce = u64::from_be_bytes(
+            hex::decode(&cipherparams.nonce)?
+                .try_into()
+                .map_err(|_| anyhow!("Invalid keystore nonce"))?,
+        );
+        let mut secret_key = hex::decode(ciphertext)?;
+
+        let derived_key = derive_key(password, &salt, kdfparams.c);
+        if hex::encode(mac(&derived_key, &secret_key)) != keystore.crypto.mac {
+            return Err(anyhow!("Wrong keystore password"));
+        }
+        apply_keystream(&derived_key, nonce, &mut secret_key);
+
+        SecretKey::read_from_bytes(&secret_key).map_err(|err| anyhow!("{err}"))
+    }
+
+    #[test]
+    fn hmac_sha3_matches_test_vector() {
+        // RFC 4231 test case 2, with the SHA3-256 result from the NIST examples
+        let mac = HmacSha3::new(b"Jefe").mac(&[b"what do ya want ", b"for nothing?"]);
+        assert_eq!(
+            hex::encode(mac),
+            "c7d4072e788877ae3596bbb0da73b887c9171f93095b294ae857fbe2645e1ba5"
+        );
+    }
+
+    #[test]
+    fn keystore_round_trip() {
+        let secret_key = SecretKey::with_rng(&mut ChaCha20Rng::from_seed([7; 32]));
+        let account_id = AccountId::new_unchecked(Felt::new(0x200000000000001f));
+
+        let keystore = Keystore::encrypt(account_id, &secret_key, "correct horse", 16);
+        let json = serde_json::to_string(&keystore).unwrap();
+        let keystore: Keystore = serde_json::from_str(&json).unwrap();
+        assert_eq!(keystore.account_id, account_id.to_hex());
+
+        let decrypted = decrypt(&keystore, "correct horse").unwrap();
+        assert_eq!(decrypted.to_bytes(), secret_key.to_bytes());
+        assert!(decrypt(&keystore, "wrong horse").is_err());
+    }
+}
@@ -4,25 +4,35 @@ use std::{
 };
 
 use anyhow::{anyhow, Result};
-use inputs::{AccountInput, AuthSchemeInput, GenesisInput};
+use inputs::{AccountInput, AuthSchemeInput, GenesisInput, NoteInput, ParametersInput};
+pub use keys::{KeyExport, KeyFormat};
 use miden_lib::{
     accounts::{faucets::create_basic_fungible_faucet, wallets::create_basic_wallet},
+    notes::create_p2id_note,
     AuthScheme,
 };
 use miden_node_store::genesis::GenesisState;
-use miden_node_utils::config::load_config;
+use miden_node_utils::{
+    chain_params::{ChainFees, ChainParameters},
+    config::load_config,
+};
 use miden_objects::{
     accounts::{Account, AccountData, AccountStorageType, AccountType, AuthData},
-    assets::TokenSymbol,
+    assets::{FungibleAsset, TokenSymbol},
     crypto::{
         dsa::rpo_falcon512::SecretKey,
+        rand::RpoRandomCoin,
         utils::{hex_to_bytes, Serializable},
     },
-    Felt, ONE,
+    notes::{Note, NoteType},
+    Felt, Hasher, ONE,
 };
+use network::write_network_configs;
 use rand_chacha::{rand_core::SeedableRng, ChaCha20Rng};
 
 mod inputs;
+mod keys;
+mod network;
 
 const DEFAULT_ACCOUNTS_DIR: &str = "accounts/";
 
@@ -31,18 +41,27 @@ const DEFAULT_ACCOUNTS_DIR: &str = "accounts/";
 
 /// Generates a genesis file and associated account files based on a specified genesis input
 ///
+/// If the inputs define a test `network`, the configuration files of its nodes are also generated,
+/// see [write_network_configs].
+///
 /// # Arguments
 ///
 /// * `output_path` - A `PathBuf` reference to the path where the genesis file will be created.
 /// * `force` - A boolean flag to determine if an existing genesis file should be overwritten.
 /// * `inputs_path` - A `PathBuf` reference to the genesis inputs file's path.
+/// * `keys` - The formats in which the authentication keys of the accounts are exported.
 ///
 /// # Returns
 ///
 /// This function returns a `Result` type. On successful creation of the genesis file, it returns
 /// `Ok(())`. If it fails at any point, due to issues like file existence checks or read/write
 /// operations, it returns an `Err` with a detailed error message.
-pub fn make_genesis(inputs_path: &PathBuf, output_path: &PathBuf, force: &bool) -> Result<()> {
+pub fn make_genesis(
+    inputs_path: &PathBuf,
+    output_path: &PathBuf,
+    force: &bool,
+    keys: &KeyExport,
+) -> Result<()> {
     let inputs_path = Path::new(inputs_path);
     let output_path = Path::new(output_path);
 
@@ -82,29 +101,61 @@ pub fn make_genesis(inputs_path: &PathBuf, output_path: &PathBuf, force: &bool)
     })?;
     println!("Genesis input file: {} has successfully been loaded.", output_path.display());
 
-    let accounts = create_accounts(&genesis_input.accounts, parent_path, force)?;
+    let serves_faucet =
+        genesis_input.network.as_ref().is_some_and(|network| network.faucet.is_some());
+    if serves_faucet && !keys.contains(KeyFormat::Wallet) {
+        return Err(anyhow!(
+            "The network faucet is loaded from its account file. Add the wallet format to the --key-formats flag."
+        ));
+    }
+
+    let accounts = create_accounts(&genesis_input.accounts, parent_path, force, keys)?;
     println!(
         "Accounts have successfully been created at: {}/{}",
         parent_path.display(),
         DEFAULT_ACCOUNTS_DIR
     );
 
-    let genesis_state = GenesisState::new(accounts, genesis_input.version, genesis_input.timestamp);
+    let notes = create_notes(&genesis_input.notes, &accounts)?;
+
+    let mut genesis_state =
+        GenesisState::new(accounts.clone(), notes, genesis_input.version, genesis_input.timestamp);
+    if let Some(parameters) = &genesis_input.parameters {
+        let parameters = create_parameters(parameters, &accounts)?;
+        println!("Chain parameters: {parameters}");
+        genesis_state = genesis_state.with_parameters(parameters);
+    }
     fs::write(output_path, genesis_state.to_bytes()).unwrap_or_else(|_| {
         panic!("Failed to write genesis state to output file {}", output_path.display())
     });
     println!("Miden node genesis successful: {} has been created", output_path.display());
 
+    if let Some(network) = &genesis_input.network {
+        let (genesis_header, ..) = genesis_state
+            .into_block_parts()
+            .map_err(|err| anyhow!("Failed to build the genesis block: {err}"))?;
+        write_network_configs(
+            network,
+            &accounts,
+            parent_path,
+            output_path,
+            genesis_header.hash(),
+            *force,
+        )?;
+    }
+
     Ok(())
 }
 
 /// Converts the provided list of account inputs into [Account] objects.
 ///
-/// This function also writes the account data files into the default accounts directory.
+/// This function also writes the account data files, and the authentication keys in the other
+/// formats of `keys`, into the default accounts directory.
 fn create_accounts(
     accounts: &[AccountInput],
     parent_path: &Path,
     force: &bool,
+    keys: &KeyExport,
 ) -> Result<Vec<Account>> {
     let mut accounts_path = PathBuf::from(&parent_path);
     accounts_path.push(DEFAULT_ACCOUNTS_DIR);
@@ -118,12 +169,12 @@ fn create_accounts(
 
     for account in accounts {
         // build offchain account data from account inputs
-        let mut account_data = match account {
+        let (mut account_data, secret_key) = match account {
             AccountInput::BasicWallet(inputs) => {
                 print!("Creating basic wallet account...");
                 let init_seed = hex_to_bytes(&inputs.init_seed)?;
 
-                let (auth_scheme, auth_info) =
+                let (auth_scheme, auth_info, secret_key) =
                     parse_auth_inputs(inputs.auth_scheme, &inputs.auth_seed)?;
 
                 let (account, account_seed) = create_basic_wallet(
@@ -133,13 +184,13 @@ fn create_accounts(
                     AccountStorageType::OffChain,
                 )?;
 
-                AccountData::new(account, Some(account_seed), auth_info)
+                (AccountData::new(account, Some(account_seed), auth_info), secret_key)
             },
             AccountInput::BasicFungibleFaucet(inputs) => {
                 println!("Creating fungible faucet account...");
                 let init_seed = hex_to_bytes(&inputs.init_seed)?;
 
-                let (auth_scheme, auth_info) =
+                let (auth_scheme, auth_info, secret_key) =
                     parse_auth_inputs(inputs.auth_scheme, &inputs.auth_seed)?;
 
                 let (account, account_seed) = create_basic_fungible_faucet(
@@ -152,23 +203,14 @@ fn create_accounts(
                     auth_scheme,
                 )?;
 
-                AccountData::new(account, Some(account_seed), auth_info)
+                (AccountData::new(account, Some(account_seed), auth_info), secret_key)
             },
         };
 
-        // write account data to file
-        let path = format!("{}/account{}.mac", accounts_path.display(), final_accounts.len());
-        let path = Path::new(&path);
-
-        if let Ok(path_exists) = path.try_exists() {
-            if path_exists && !force {
-                return Err(anyhow!("Failed to generate account file {} because it already exists. Use the --force flag to overwrite.", path.display()));
-            }
-        }
-
         account_data.account.set_nonce(ONE)?;
 
-        account_data.write(path)?;
+        // write account data and keys to files
+        keys.write(&accounts_path, final_accounts.len(), &account_data, &secret_key, *force)?;
 
         final_accounts.push(account_data.account);
     }
@@ -176,10 +218,78 @@ fn create_accounts(
     Ok(final_accounts)
 }
 
+/// Converts the provided list of note inputs into [Note] objects created by the genesis block.
+///
+/// The assets of the notes are not recorded as issued by their faucets.
+fn create_notes(notes: &[NoteInput], accounts: &[Account]) -> Result<Vec<Note>> {
+    let account = |index: usize| {
+        accounts
+            .get(index)
+            .ok_or_else(|| anyhow!("Note refers to account {index}, which doesn't exist"))
+    };
+
+    notes
+        .iter()
+        .map(|note| match note {
+            NoteInput::P2ID(inputs) => {
+                let faucet = account(inputs.faucet)?;
+                let target = account(inputs.target)?;
+                let asset = FungibleAsset::new(faucet.id(), inputs.amount)?;
+
+                let serial_seed: [u8; 32] = hex_to_bytes(&inputs.serial_seed)?;
+                let rng = RpoRandomCoin::new(Hasher::hash(&serial_seed).into());
+
+                Ok(create_p2id_note(
+                    faucet.id(),
+                    target.id(),
+                    vec![asset.into()],
+                    NoteType::Public,
+                    rng,
+                )?)
+            },
+        })
+        .collect()
+}
+
+/// Converts the parameters input into the [ChainParameters] committed to by the genesis block.
+fn create_parameters(
+    parameters: &ParametersInput,
+    accounts: &[Account],
+) -> Result<ChainParameters> {
+    let account = |index: usize| {
+        accounts
+            .get(index)
+            .map(Account::id)
+            .ok_or_else(|| anyhow!("Fees refer to account {index}, which doesn't exist"))
+    };
+
+    let fees = parameters
+        .fees
+        .as_ref()
+        .map(|fees| {
+            Ok::<_, anyhow::Error>(ChainFees {
+                operator: account(fees.operator)?,
+                faucet_id: account(fees.faucet)?,
+                min_amount: fees.min_amount,
+            })
+        })
+        .transpose()?;
+
+    let parameters = ChainParameters {
+        chain_id: parameters.chain_id,
+        max_batch_size: parameters.max_batch_size,
+        note_tree_depth: parameters.note_tree_depth,
+        fees,
+    };
+    parameters.validate()?;
+
+    Ok(parameters)
+}
+
 fn parse_auth_inputs(
     auth_scheme_input: AuthSchemeInput,
     auth_seed: &str,
-) -> Result<(AuthScheme, AuthData)> {
+) -> Result<(AuthScheme, AuthData, SecretKey)> {
     match auth_scheme_input {
         AuthSchemeInput::RpoFalcon512 => {
             let auth_seed: [u8; 32] = hex_to_bytes(auth_seed)?;
@@ -189,7 +299,7 @@ fn parse_auth_inputs(
             let auth_scheme = AuthScheme::RpoFalcon512 { pub_key: secret.public_key() };
             let auth_info = AuthData::RpoFalcon512Seed(auth_seed);
 
-            Ok((auth_scheme, auth_info))
+            Ok((auth_scheme, auth_info, secret))
         },
     }
 }
@@ -202,11 +312,22 @@ mod tests {
     use std::{fs, path::PathBuf};
 
     use figment::Jail;
-    use miden_node_store::genesis::GenesisState;
-    use miden_objects::{accounts::AccountData, utils::serde::Deserializable};
+    use miden_node_block_producer::signer::BlockSigner;
+    use miden_node_store::{config::FollowConfig, genesis::GenesisState};
+    use miden_node_utils::{
+        chain_params::{ChainFees, ChainParameters, BLOCK_OUTPUT_NOTES_TREE_DEPTH},
+        config::load_config,
+    };
+    use miden_objects::{
+        accounts::{AccountData, AuthData},
+        assets::FungibleAsset,
+        crypto::dsa::rpo_falcon512::SecretKey,
+        utils::serde::{Deserializable, Serializable},
+    };
+    use rand_chacha::{rand_core::SeedableRng, ChaCha20Rng};
 
-    use super::make_genesis;
-    use crate::DEFAULT_GENESIS_FILE_PATH;
+    use super::{make_genesis, KeyExport, KeyFormat};
+    use crate::{config::NodeConfig, DEFAULT_GENESIS_FILE_PATH, NODE_CONFIG_FILE_PATH};
 
     #[test]
     fn test_make_genesis() {
@@ -234,13 +355,31 @@ mod tests {
                 token_symbol = "POL"
                 decimals = 12
                 max_supply = 1000000
+
+                [[notes]]
+                type = "P2ID"
+                faucet = 1
+                target = 0
+                amount = 1000
+                serial_seed = "0xe123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef"
+
+                [parameters]
+                chain_id = 7
+                max_batch_size = 4
+                fees = { operator = 0, faucet = 1, min_amount = 5 }
             "#,
             )?;
 
             let genesis_dat_file_path = PathBuf::from(DEFAULT_GENESIS_FILE_PATH);
 
             //  run make_genesis to generate genesis.dat and accounts folder and files
-            make_genesis(&genesis_inputs_file_path, &genesis_dat_file_path, &true).unwrap();
+            make_genesis(
+                &genesis_inputs_file_path,
+                &genesis_dat_file_path,
+                &true,
+                &KeyExport::new([KeyFormat::Wallet, KeyFormat::Hex], None).unwrap(),
+            )
+            .unwrap();
 
             let a0_file_path = PathBuf::from("accounts/account0.mac");
             let a1_file_path = PathBuf::from("accounts/account1.mac");
@@ -254,16 +393,143 @@ mod tests {
             let a0 = AccountData::read(a0_file_path).unwrap();
             let a1 = AccountData::read(a1_file_path).unwrap();
 
+            // the hex key is the key of the seed in the account file
+            let AuthData::RpoFalcon512Seed(auth_seed) = a0.auth;
+            let hex_key = fs::read_to_string("accounts/account0.hex").unwrap();
+            assert_eq!(
+                hex::decode(hex_key.trim()).unwrap(),
+                SecretKey::with_rng(&mut ChaCha20Rng::from_seed(auth_seed)).to_bytes()
+            );
+
             let genesis_file_contents = fs::read(genesis_dat_file_path).unwrap();
             let genesis_state = GenesisState::read_from_bytes(&genesis_file_contents).unwrap();
 
+            // the note pays the faucet's asset to the wallet
+            assert_eq!(genesis_state.notes.len(), 1);
+            let note = &genesis_state.notes[0];
+            assert_eq!(note.metadata().sender(), a1.account.id());
+            assert_eq!(
+                note.assets().iter().collect::<Vec<_>>(),
+                [&FungibleAsset::new(a1.account.id(), 1000).unwrap().into()]
+            );
+
+            // the fees are paid to the wallet in the faucet's asset
+            let parameters = ChainParameters {
+                chain_id: 7,
+                max_batch_size: 4,
+                note_tree_depth: BLOCK_OUTPUT_NOTES_TREE_DEPTH,
+                fees: Some(ChainFees {
+                    operator: a0.account.id(),
+                    faucet_id: a1.account.id(),
+                    min_amount: 5,
+                }),
+            };
+
             // build supposed genesis_state
-            let supposed_genesis_state =
-                GenesisState::new(vec![a0.account, a1.account], 1, 1672531200);
+            let supposed_genesis_state = GenesisState::new(
+                vec![a0.account, a1.account],
+                genesis_state.notes.clone(),
+                1,
+                1672531200,
+            )
+            .with_parameters(parameters);
 
             // assert that both genesis_state(s) are eq
             assert_eq!(genesis_state, supposed_genesis_state);
 
+            // the genesis block header commits to the parameters
+            let (header, ..) = genesis_state.into_block_parts().unwrap();
+            assert_eq!(header.proof_hash(), parameters.commitment());
+
+            Ok(())
+        });
+    }
+    #[test]
+    fn test_make_genesis_network() {
+        let genesis_inputs_file_path = PathBuf::from("genesis.toml");
+
+        Jail::expect_with(|jail| {
+            jail.create_file(
+                genesis_inputs_file_path.as_path(),
+                r#"
+                version = 1
+                timestamp = 1672531200
+
+                [[accounts]]
+                type = "BasicFungibleFaucet"
+                init_seed = "0xc123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef"
+                auth_scheme = "RpoFalcon512"
+                auth_seed = "0xd123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef"
+                token_symbol = "POL"
+                decimals = 12
+                max_supply = 1000000
+
+                [network]
+                host = "localhost"
+                base_port = 50000
+                nodes = ["sequencer", "replica-1", "replica-2"]
+                faucet = { account = 0, asset_amount = 100 }
+            "#,
+            )?;
+
+            let genesis_dat_file_path = PathBuf::from(DEFAULT_GENESIS_FILE_PATH);
+            make_genesis(
+                &genesis_inputs_file_path,
+                &genesis_dat_file_path,
+                &true,
+                &KeyExport::default(),
+            )
+            .unwrap();
+
+            let load = |node: &str| -> NodeConfig {
+                load_config(PathBuf::from(node).join(NODE_CONFIG_FILE_PATH).as_path())
+                    .extract()
+                    .unwrap()
+            };
+            let sequencer = load("sequencer");
+            let replicas = [load("replica-1"), load("replica-2")];
+
+            // the sequencer runs all the components, and serves the faucet
+            let sequencer_rpc = sequencer.rpc.unwrap();
+            let sequencer_store = sequencer.store.unwrap();
+            let block_producer = sequencer.block_producer.unwrap();
+            assert_eq!(sequencer_rpc.endpoint.port, 50000);
+            assert_eq!(sequencer_store.genesis_filepath, PathBuf::from("../genesis.dat"));
+            assert_eq!(sequencer_store.follow, None);
+            assert_eq!(block_producer.store_url, sequencer_store.endpoint.to_string());
+            let faucet = sequencer_rpc.testnet_faucet.unwrap();
+            assert_eq!(faucet.faucet_account_filepath, PathBuf::from("../accounts/account0.mac"));
+
+            // the replicas follow the sequencer and check its signatures
+            let signer =
+                BlockSigner::load_or_generate(&PathBuf::from("sequencer/sequencer.key")).unwrap();
+            for (index, replica) in replicas.into_iter().enumerate() {
+                let rpc = replica.rpc.unwrap();
+                let store = replica.store.unwrap();
+                assert!(replica.block_producer.is_none());
+                assert_eq!(rpc.endpoint.port, 50010 + 10 * index as u16);
+                assert_eq!(rpc.block_producer_url, block_producer.endpoint.to_string());
+                assert_eq!(rpc.testnet_faucet, None);
+                assert_eq!(
+                    store.follow,
+                    Some(FollowConfig {
+                        rpc_url: sequencer_rpc.endpoint.to_string(),
+                        sequencer_public_key: Some(signer.public_key_hex()),
+                    })
+                );
+            }
+
+            // the sequencer's key isn't overwritten without `force`
+            fs::remove_dir_all("accounts").unwrap();
+            let err = make_genesis(
+                &genesis_inputs_file_path,
+                &PathBuf::from("other-genesis.dat"),
+                &false,
+                &KeyExport::default(),
+            )
+            .unwrap_err();
+            assert!(err.to_string().contains("sequencer.key"));
+
             Ok(())
         });
     }
@@ -0,0 +1,220 @@
+use std::{
+    collections::BTreeSet,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, Result};
+use miden_node_block_producer::{config::BlockProducerConfig, signer::BlockSigner};
+use miden_node_rpc::config::{QuotaConfig, RpcConfig, TestnetFaucetConfig};
+use miden_node_store::config::{FollowConfig, StoreConfig};
+use miden_node_utils::config::Endpoint;
+use miden_objects::{accounts::Account, Digest};
+
+use super::{
+    inputs::{NetworkFaucetInput, NetworkInput},
+    DEFAULT_ACCOUNTS_DIR,
+};
+use crate::{
+    config::{NodeConfig, Transport},
+    NODE_CONFIG_FILE_PATH,
+};
+
+/// Files of a node, in its directory
+const DATABASE_FILE_PATH: &str = "miden-store.sqlite3";
+const SIGNING_KEY_FILE_PATH: &str = "sequencer.key";
+
+/// Number of ports reserved per node, and offsets of the ports of its components
+const PORTS_PER_NODE: u16 = 10;
+const RPC_PORT_OFFSET: u16 = 0;
+const STORE_PORT_OFFSET: u16 = 1;
+const BLOCK_PRODUCER_PORT_OFFSET: u16 = 2;
+
+/// Quota of the testnet faucet, per account and per IP address
+const FAUCET_QUOTA: QuotaConfig = QuotaConfig { max_requests: 100, period_secs: 3600 };
+
+// TEST NETWORK
+// ================================================================================================
+
+/// Writes the configuration file of each node of the test `network` to
+/// `<output_dir>/<node>/miden-node.toml`, and the signing key of the sequencer to its directory.
+///
+/// The first node is the sequencer, running all the components. The other nodes are replicas
+/// running only the store, following the sequencer and checking its signatures, and the RPC,
+/// forwarding the transactions to the sequencer's block producer. The paths in the configuration
+/// files are relative to the node's directory, in which the node must be started. The block
+/// producer and the RPCs are pinned to the genesis block `genesis_hash`.
+pub fn write_network_configs(
+    network: &NetworkInput,
+    accounts: &[Account],
+    output_dir: &Path,
+    genesis_path: &Path,
+    genesis_hash: Digest,
+    force: bool,
+) -> Result<()> {
+    validate_nodes(network)?;
+    let testnet_faucet = network
+        .faucet
+        .as_ref()
+        .map(|faucet| faucet_config(faucet, accounts))
+        .transpose()?;
+
+    let genesis_filepath = Path::new("..").join(
+        genesis_path
+            .file_name()
+            .ok_or_else(|| anyhow!("Invalid genesis file path {}", genesis_path.display()))?,
+    );
+    let genesis_hash = genesis_hash.to_hex();
+    let sequencer_rpc = endpoint(network, 0, RPC_PORT_OFFSET)?;
+    let sequencer_block_producer = endpoint(network, 0, BLOCK_PRODUCER_PORT_OFFSET)?;
+
+    let mut sequencer_public_key = None;
+    for (index, name) in network.nodes.iter().enumerate() {
+        let node_dir = output_dir.join(name);
+        fs::create_dir_all(&node_dir).map_err(|err| {
+            anyhow!("Failed to create node directory {}: {err}", node_dir.display())
+        })?;
+
+        let store_endpoint = endpoint(network, index, STORE_PORT_OFFSET)?;
+        let block_producer = if index == 0 {
+            let key_path = node_dir.join(SIGNING_KEY_FILE_PATH);
+            check_overwrite(&key_path, force)?;
+            let signer = BlockSigner::load_or_generate(&key_path)
+                .map_err(|err| anyhow!("Failed to generate the sequencer key: {err}"))?;
+            sequencer_public_key = Some(signer.public_key_hex());
+
+            Some(BlockProducerConfig {
+                endpoint: sequencer_block_producer.clone(),
+                store_url: store_endpoint.to_string(),
+                store_client: Default::default(),
+                genesis_hash: Some(genesis_hash.clone()),
+                verify_tx_proofs: true,
+                batch_sealing: Default::default(),
+                batch_proving: Default::default(),
+                block_production: Default::default(),
+                clock: Default::default(),
+                signing_key_filepath: Some(PathBuf::from(SIGNING_KEY_FILE_PATH)),
+                fees: None,
+                submission_policy: Default::default(),
+                simulation: None,
+                access_log: None,
+            })
+        } else {
+            None
+        };
+
+        let config = NodeConfig {
+            transport: Transport::Grpc,
+            block_producer,
+            rpc: Some(RpcConfig {
+                endpoint: endpoint(network, index, RPC_PORT_OFFSET)?,
+                store_url: store_endpoint.to_string(),
+                block_producer_url: sequencer_block_producer.to_string(),
+                client: Default::default(),
+                genesis_hash: Some(genesis_hash.clone()),
+                timeouts: Default::default(),
+                cors: Default::default(),
+                admin: None,
+                audit: None,
+                load_shedding: None,
+                access_control: None,
+                testnet_faucet: if index == 0 { testnet_faucet.clone() } else { None },
+                access_log: None,
+                usage: None,
+                store_retry: None,
+            }),
+            store: Some(StoreConfig {
+                follow: (index > 0).then(|| FollowConfig {
+                    rpc_url: sequencer_rpc.to_string(),
+                    sequencer_public_key: sequencer_public_key.clone(),
+                }),
+                ..StoreConfig::new(
+                    store_endpoint,
+                    PathBuf::from(DATABASE_FILE_PATH),
+                    genesis_filepath.clone(),
+                )
+            }),
+        };
+
+        let config_path = node_dir.join(NODE_CONFIG_FILE_PATH);
+        check_overwrite(&config_path, force)?;
+        fs::write(&config_path, toml::to_string_pretty(&config)?).map_err(|err| {
+            anyhow!("Failed to write node configuration {}: {err}", config_path.display())
+        })?;
+        println!("Configuration of node {name} written to {}", config_path.display());
+    }
+
+    Ok(())
+}
+
+/// Checks the node names can be used as directory names, and the ports of all the nodes are valid.
+fn validate_nodes(network: &NetworkInput) -> Result<()> {
+    if network.nodes.is_empty() {
+        return Err(anyhow!("The network has no nodes"));
+    }
+
+    let mut names = BTreeSet::new();
+    for name in &network.nodes {
+        if name.is_empty() || name.starts_with('.') || name.contains(['/', '\\']) {
+            return Err(anyhow!("Invalid node name `{name}`, it must be a directory name"));
+        }
+        if !names.insert(name) {
+            return Err(anyhow!("Duplicate node name `{name}`"));
+        }
+    }
+
+    endpoint(network, network.nodes.len() - 1, PORTS_PER_NODE - 1)?;
+
+    Ok(())
+}
+
+/// Returns the endpoint of the component at `offset` of the node `index`.
+fn endpoint(network: &NetworkInput, index: usize, offset: u16) -> Result<Endpoint> {
+    let port = u16::try_from(index)
+        .ok()
+        .and_then(|index| index.checked_mul(PORTS_PER_NODE))
+        .and_then(|node_port| network.base_port.checked_add(node_port))
+        .and_then(|node_port| node_port.checked_add(offset))
+        .ok_or_else(|| anyhow!("The ports of node {index} exceed {}", u16::MAX))?;
+
+    Ok(Endpoint { host: network.host.clone(), port })
+}
+
+/// Returns the configuration of the sequencer's testnet faucet, minting the asset of the genesis
+/// faucet `faucet.account`.
+fn faucet_config(faucet: &NetworkFaucetInput, accounts: &[Account]) -> Result<TestnetFaucetConfig> {
+    let account = accounts.get(faucet.account).ok_or_else(|| {
+        anyhow!("Network faucet refers to account {}, which doesn't exist", faucet.account)
+    })?;
+    if !account.id().is_faucet() {
+        return Err(anyhow!(
+            "Network faucet refers to account {}, which isn't a faucet",
+            faucet.account
+        ));
+    }
+
+    Ok(TestnetFaucetConfig {
+        faucet_account_filepath: Path::new("..")
+            .join(DEFAULT_ACCOUNTS_DIR)
+            .join(format!("account{}.mac", faucet.account)),
+        asset_amount: faucet.asset_amount,
+        per_account: FAUCET_QUOTA,
+        per_ip: FAUCET_QUOTA,
+        verification_url: None,
+    })
+}
+
+/// Fails if `path` exists, unless `force` is set, in which case the file is removed.
+fn check_overwrite(path: &Path, force: bool) -> Result<()> {
+    if path.try_exists()? {
+        if !force {
+            return Err(anyhow!(
+                "Failed to generate {} because it already exists. Use the --force flag to overwrite.",
+                path.display()
+            ));
+        }
+        fs::remove_file(path)?;
+    }
+
+    Ok(())
+}
@@ -1,3 +1,6 @@
+pub mod check;
 mod genesis;
 pub mod start;
-pub use genesis::make_genesis;
+pub mod status;
+pub mod store;
+pub use genesis::{make_genesis, KeyExport, KeyFormat};
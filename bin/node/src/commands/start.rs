@@ -4,28 +4,57 @@ use anyhow::{anyhow, Context, Result};
 use miden_node_block_producer::{config::BlockProducerConfig, server as block_producer_server};
 use miden_node_rpc::{config::RpcConfig, server as rpc_server};
 use miden_node_store::{config::StoreConfig, db::Db, server as store_server};
+use miden_node_utils::{config::Endpoint, transport::ComponentChannel};
 use tokio::task::JoinSet;
 
-use crate::config::NodeConfig;
+use crate::{
+    config::{NodeConfig, Transport},
+    service::Shutdown,
+};
 
 // START
 // ===================================================================================================
 
-pub async fn start_node(config: NodeConfig) -> Result<()> {
+/// Returns the endpoints the components of `config` listen on, only the RPC's one with the
+/// in-process transport.
+pub fn endpoints(config: &NodeConfig) -> Vec<Endpoint> {
+    let mut endpoints = Vec::new();
+    if config.transport != Transport::InProcess {
+        endpoints.extend(config.store.as_ref().map(|store| store.endpoint.clone()));
+        endpoints.extend(config.block_producer.as_ref().map(|config| config.endpoint.clone()));
+    }
+    endpoints.extend(config.rpc.as_ref().map(|rpc| rpc.endpoint.clone()));
+
+    endpoints
+}
+
+/// Starts all the components, stopping them once `shutdown` completes.
+///
+/// The store completes its in-flight requests and flushes the database before stopping, the other
+/// components stop right away.
+pub async fn start_node(config: NodeConfig, shutdown: Shutdown) -> Result<()> {
+    if config.transport == Transport::InProcess {
+        return start_node_in_process(config, shutdown).await;
+    }
+
     let mut join_set = JoinSet::new();
 
     // Start store
-    join_set.spawn(start_store(config.store.context("Missing store configuration.")?));
+    join_set.spawn(start_store(
+        config.store.context("Missing store configuration.")?,
+        shutdown.clone(),
+    ));
 
     // Wait for store to start & start block-producer
     tokio::time::sleep(Duration::from_secs(1)).await;
     join_set.spawn(start_block_producer(
         config.block_producer.context("Missing block-producer configuration.")?,
+        shutdown.clone(),
     ));
 
     // Wait for block-producer to start & start rpc
     tokio::time::sleep(Duration::from_secs(1)).await;
-    join_set.spawn(start_rpc(config.rpc.context("Missing rpc configuration.")?));
+    join_set.spawn(start_rpc(config.rpc.context("Missing rpc configuration.")?, shutdown));
 
     // block on all tasks
     while let Some(res) = join_set.join_next().await {
@@ -36,28 +65,68 @@ pub async fn start_node(config: NodeConfig) -> Result<()> {
     Ok(())
 }
 
-pub async fn start_block_producer(config: BlockProducerConfig) -> Result<()> {
-    block_producer_server::serve(config)
+/// Starts all the components in the current process, the store and the block producer are reached
+/// without going through the network and only the RPC component listens on its endpoint.
+async fn start_node_in_process(config: NodeConfig, shutdown: Shutdown) -> Result<()> {
+    let store_config = config.store.context("Missing store configuration.")?;
+    let db = Db::setup(store_config.clone())
         .await
-        .map_err(|err| anyhow!("Failed to serve block-producer: {}", err))?;
+        .map_err(|err| anyhow!("Failed to setup database: {}", err))?;
+    let chain_parameters = db.chain_parameters();
+    let store = store_server::init(&store_config, db)
+        .await
+        .map_err(|err| anyhow!("Failed to initialize store: {}", err))?;
+    let store = ComponentChannel::in_process(store);
+
+    let block_producer_config = block_producer_server::apply_chain_parameters(
+        config.block_producer.context("Missing block-producer configuration.")?,
+        chain_parameters,
+    );
+    let block_producer = block_producer_server::init(&block_producer_config, store.clone())
+        .map_err(|err| anyhow!("Failed to initialize block-producer: {}", err))?;
+    let block_producer = ComponentChannel::in_process(block_producer);
+
+    let rpc = rpc_server::serve_with(
+        config.rpc.context("Missing rpc configuration.")?,
+        store,
+        block_producer,
+    );
+    tokio::select! {
+        result = rpc => result.map_err(|err| anyhow!("Failed to serve rpc: {}", err))?,
+        () = shutdown.requested() => {},
+    }
 
     Ok(())
 }
 
-pub async fn start_rpc(config: RpcConfig) -> Result<()> {
-    rpc_server::serve(config)
-        .await
-        .map_err(|err| anyhow!("Failed to serve rpc: {}", err))?;
+pub async fn start_block_producer(config: BlockProducerConfig, shutdown: Shutdown) -> Result<()> {
+    tokio::select! {
+        result = block_producer_server::serve(config) => {
+            result.map_err(|err| anyhow!("Failed to serve block-producer: {}", err))?
+        },
+        () = shutdown.requested() => {},
+    }
+
+    Ok(())
+}
+
+pub async fn start_rpc(config: RpcConfig, shutdown: Shutdown) -> Result<()> {
+    tokio::select! {
+        result = rpc_server::serve(config) => {
+            result.map_err(|err| anyhow!("Failed to serve rpc: {}", err))?
+        },
+        () = shutdown.requested() => {},
+    }
 
     Ok(())
 }
 
-pub async fn start_store(config: StoreConfig) -> Result<()> {
+pub async fn start_store(config: StoreConfig, shutdown: Shutdown) -> Result<()> {
     let db = Db::setup(config.clone())
         .await
         .map_err(|err| anyhow!("Failed to setup database: {}", err))?;
 
-    store_server::serve(config, db)
+    store_server::serve(config, db, shutdown.requested())
         .await
         .map_err(|err| anyhow!("Failed to serve store: {}", err))?;
 
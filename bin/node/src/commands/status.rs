@@ -0,0 +1,190 @@
+use anyhow::{anyhow, Result};
+use miden_node_proto::generated::{
+    requests::GetNodeStatusRequest,
+    responses::{
+        GetBlockProducerStatusResponse, GetNodeStatusResponse, GetStoreStatusResponse,
+        RuntimeMetrics,
+    },
+    rpc::api_client as rpc_client,
+};
+use miden_node_utils::formatting::format_opt;
+use serde_json::{json, Value};
+
+// NODE STATUS
+// ===================================================================================================
+
+/// Queries the status of the node whose RPC is at `rpc_url` and prints it as text or, if `json` is
+/// set, as JSON.
+pub async fn status(rpc_url: &str, json: bool) -> Result<()> {
+    let mut client = rpc_client::ApiClient::connect(rpc_url.to_string())
+        .await
+        .map_err(|err| anyhow!("failed to connect to the node at `{rpc_url}`: {err}"))?;
+
+    let status = client
+        .get_node_status(GetNodeStatusRequest {})
+        .await
+        .map_err(|status| anyhow!("failed to get the node status: {}", status.message()))?
+        .into_inner();
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&status_json(&status))?);
+    } else {
+        println!("{}", status_text(&status));
+    }
+
+    Ok(())
+}
+
+// TEXT
+// ---------------------------------------------------------------------------------------------------
+
+fn status_text(status: &GetNodeStatusResponse) -> String {
+    let mut text = format!("RPC version: {}", status.version);
+    if let Some(runtime) = &status.runtime {
+        text.push_str(&format!("\nRPC runtime:\n{}", runtime_text(runtime)));
+    }
+
+    match &status.store {
+        Some(store) => text.push_str(&format!("\nStore:\n{}", store_text(store))),
+        None => text.push_str("\nStore: unknown"),
+    }
+
+    match &status.block_producer {
+        Some(block_producer) => {
+            text.push_str(&format!("\nBlock producer:\n{}", block_producer_text(block_producer)))
+        },
+        None => text.push_str("\nBlock producer: unreachable"),
+    }
+
+    text
+}
+
+fn store_text(store: &GetStoreStatusResponse) -> String {
+    let mut text = format!(
+        "  version: {}\n  genesis hash: {}\n  chain tip: {}\n  last block timestamp: {}\n  \
+         database size: {} bytes",
+        store.version,
+        format_opt(store.genesis_hash.as_ref()),
+        store.chain_tip,
+        store.last_block_timestamp,
+        store.database_size_bytes,
+    );
+    if let Some(follower) = &store.follower {
+        text.push_str(&format!(
+            "\n  sync lag: {} blocks (followed node's chain tip: {}, {})",
+            follower.sync_lag,
+            follower.upstream_chain_tip,
+            if follower.connected {
+                "connected"
+            } else {
+                "disconnected"
+            },
+        ));
+    }
+    if let Some(runtime) = &store.runtime {
+        text.push_str(&format!("\n  runtime:\n{}", runtime_text(runtime)));
+    }
+    for fee in &store.collected_fees {
+        text.push_str(&format!(
+            "\n  collected fees: {} of faucet {}",
+            fee.amount,
+            format_opt(fee.faucet_id.as_ref()),
+        ));
+    }
+
+    text
+}
+
+fn block_producer_text(block_producer: &GetBlockProducerStatusResponse) -> String {
+    let mut text = format!(
+        "  version: {}\n  mempool depth: {} transactions\n  in-flight batches: {}\n  block \
+         production: {}",
+        block_producer.version,
+        block_producer.mempool_depth,
+        block_producer.in_flight_batches,
+        if block_producer.block_production_paused {
+            "paused"
+        } else {
+            "running"
+        },
+    );
+    if block_producer.simulation {
+        text.push_str("\n  simulation mode: transaction proofs aren't verified");
+    }
+    if let Some(runtime) = &block_producer.runtime {
+        text.push_str(&format!("\n  runtime:\n{}", runtime_text(runtime)));
+    }
+
+    text
+}
+
+fn runtime_text(runtime: &RuntimeMetrics) -> String {
+    format!(
+        "    workers: {} ({}% busy)\n    queued tasks: {} global, {} local\n    blocking \
+         threads: {} busy, {} idle, {} queued tasks\n    active tasks: {}",
+        runtime.workers,
+        runtime.worker_utilization_percent,
+        runtime.global_queue_depth,
+        runtime.local_queue_depth,
+        runtime.busy_blocking_threads,
+        runtime.idle_blocking_threads,
+        runtime.blocking_queue_depth,
+        runtime.active_tasks,
+    )
+}
+
+// JSON
+// ---------------------------------------------------------------------------------------------------
+
+fn status_json(status: &GetNodeStatusResponse) -> Value {
+    json!({
+        "version": status.version,
+        "runtime": status.runtime.as_ref().map(runtime_json),
+        "store": status.store.as_ref().map(store_json),
+        "block_producer": status.block_producer.as_ref().map(block_producer_json),
+    })
+}
+
+fn store_json(store: &GetStoreStatusResponse) -> Value {
+    json!({
+        "version": store.version,
+        "genesis_hash": store.genesis_hash.as_ref().map(ToString::to_string),
+        "chain_tip": store.chain_tip,
+        "last_block_timestamp": store.last_block_timestamp,
+        "database_size_bytes": store.database_size_bytes,
+        "follower": store.follower.as_ref().map(|follower| json!({
+            "upstream_chain_tip": follower.upstream_chain_tip,
+            "sync_lag": follower.sync_lag,
+            "connected": follower.connected,
+        })),
+        "collected_fees": store.collected_fees.iter().map(|fee| json!({
+            "faucet_id": fee.faucet_id.as_ref().map(ToString::to_string),
+            "amount": fee.amount,
+        })).collect::<Vec<_>>(),
+        "runtime": store.runtime.as_ref().map(runtime_json),
+    })
+}
+
+fn block_producer_json(block_producer: &GetBlockProducerStatusResponse) -> Value {
+    json!({
+        "version": block_producer.version,
+        "mempool_depth": block_producer.mempool_depth,
+        "in_flight_batches": block_producer.in_flight_batches,
+        "block_production_paused": block_producer.block_production_paused,
+        "simulation": block_producer.simulation,
+        "runtime": block_producer.runtime.as_ref().map(runtime_json),
+    })
+}
+
+fn runtime_json(runtime: &RuntimeMetrics) -> Value {
+    json!({
+        "workers": runtime.workers,
+        "worker_utilization_percent": runtime.worker_utilization_percent,
+        "global_queue_depth": runtime.global_queue_depth,
+        "local_queue_depth": runtime.local_queue_depth,
+        "busy_blocking_threads": runtime.busy_blocking_threads,
+        "idle_blocking_threads": runtime.idle_blocking_threads,
+        "blocking_queue_depth": runtime.blocking_queue_depth,
+        "active_tasks": runtime.active_tasks,
+    })
+}
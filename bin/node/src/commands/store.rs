@@ -0,0 +1,524 @@
+use std::{
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+use anyhow::{anyhow, bail, Context, Result};
+use clap::ValueEnum;
+use miden_node_proto::{
+    domain::accounts::{AccountLookup, AccountSummary},
+    generated::{requests::GetDatabaseSchemaRequest, store::api_client as store_client},
+};
+use miden_node_store::{
+    config::StoreConfig,
+    db::{epoch_ranges, DatabaseSchema, DbInspector, DbStats, Note, StateDelta},
+    types::{AccountId, BlockNumber},
+};
+use miden_node_utils::formatting::format_opt;
+use miden_objects::{
+    accounts::AccountId as AccountIdObject,
+    crypto::dsa::rpo_falcon512::Signature,
+    notes::{NoteId, Nullifier},
+    BlockHeader,
+};
+use serde_json::{json, Value};
+
+use crate::QueryCommand;
+
+// STORE BACKFILL
+// ===================================================================================================
+
+/// Rebuilds the store database into a new database at `output`, replaying its blocks, see
+/// [miden_node_store::backfill::backfill].
+pub async fn backfill(config: StoreConfig, output: &Path) -> Result<()> {
+    let source = config.database_filepath.clone();
+    let report = miden_node_store::backfill::backfill(config, output)
+        .await
+        .map_err(|err| anyhow!("failed to backfill the database `{}`: {err}", source.display()))?;
+
+    println!(
+        "Replayed {} blocks up to block {} from `{}` into `{}`",
+        report.blocks,
+        report.chain_tip,
+        source.display(),
+        report.output.display()
+    );
+
+    Ok(())
+}
+
+// STORE SCHEMA
+// ===================================================================================================
+
+/// Format of the printed database schema.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum SchemaFormat {
+    /// The statements creating the tables, indexes and views
+    Sql,
+    /// The tables with their columns and indexes, and the views, as JSON
+    Json,
+}
+
+/// Prints the schema of the store database at `database_filepath`, opened in read-only mode.
+pub fn schema(database_filepath: &Path, format: SchemaFormat) -> Result<()> {
+    let mut inspector = DbInspector::open(database_filepath).map_err(|err| {
+        anyhow!("failed to open the database `{}`: {err}", database_filepath.display())
+    })?;
+
+    print_schema(&inspector.schema()?, format)
+}
+
+/// Prints the schema of the database of the running store whose API is at `store_url`.
+pub async fn store_schema(store_url: &str, format: SchemaFormat) -> Result<()> {
+    let mut client = store_client::ApiClient::connect(store_url.to_string())
+        .await
+        .map_err(|err| anyhow!("failed to connect to the store at `{store_url}`: {err}"))?;
+
+    let schema = client
+        .get_database_schema(GetDatabaseSchemaRequest {})
+        .await
+        .map_err(|status| anyhow!("failed to get the database schema: {}", status.message()))?
+        .into_inner()
+        .schema
+        .context("the store returned no schema")?;
+
+    print_schema(&schema.into(), format)
+}
+
+fn print_schema(schema: &DatabaseSchema, format: SchemaFormat) -> Result<()> {
+    match format {
+        SchemaFormat::Sql => print!("{}", schema.to_sql()),
+        SchemaFormat::Json => println!("{}", serde_json::to_string_pretty(&schema_json(schema))?),
+    }
+
+    Ok(())
+}
+
+fn schema_json(schema: &DatabaseSchema) -> Value {
+    json!({
+        "version": schema.version,
+        "tables": schema.tables.iter().map(|table| json!({
+            "name": table.name,
+            "sql": table.sql,
+            "columns": table.columns.iter().map(|column| json!({
+                "name": column.name,
+                "type": column.column_type,
+                "not_null": column.not_null,
+                "primary_key": column.primary_key,
+                "default_value": column.default_value,
+            })).collect::<Vec<_>>(),
+            "indexes": table.indexes.iter().map(|index| json!({
+                "name": index.name,
+                "unique": index.unique,
+                "columns": index.columns,
+                "sql": index.sql,
+            })).collect::<Vec<_>>(),
+            "partitions": table.partitions,
+        })).collect::<Vec<_>>(),
+        "views": schema.views.iter().map(|view| json!({
+            "name": view.name,
+            "sql": view.sql,
+        })).collect::<Vec<_>>(),
+    })
+}
+
+// STORE EXPORT DELTA
+// ===================================================================================================
+
+/// Exports the accounts, notes and nullifiers changed by the blocks after `since`, up to the chain
+/// tip, from the store database at `database_filepath` opened in read-only mode.
+///
+/// The records are written to `output`, or to the standard output, as newline-delimited JSON
+/// ordered by block. Without `since`, the export resumes after the block recorded in `bookmark`,
+/// or starts from the genesis block if the bookmark doesn't exist yet. The bookmark is set to the
+/// exported chain tip once all the records are written.
+pub fn export_delta(
+    database_filepath: &Path,
+    since: Option<u32>,
+    bookmark: Option<&Path>,
+    output: Option<&Path>,
+) -> Result<()> {
+    let from = match (since, bookmark) {
+        (Some(since), _) => BlockNumber::from(since).child(),
+        (None, Some(bookmark)) => match read_bookmark(bookmark)? {
+            Some(block_num) => block_num.child(),
+            None => Some(BlockNumber::GENESIS),
+        },
+        (None, None) => bail!("the first exported block requires `--since` or `--bookmark`"),
+    };
+
+    let mut inspector = DbInspector::open(database_filepath).map_err(|err| {
+        anyhow!("failed to open the database `{}`: {err}", database_filepath.display())
+    })?;
+    let Some((chain_tip, _)) = inspector.block_header(None)? else {
+        bail!("the database `{}` has no blocks", database_filepath.display());
+    };
+    let chain_tip = BlockNumber::from(chain_tip.block_num());
+    // No block follows the last block number
+    let Some(from) = from.filter(|from| *from <= chain_tip) else {
+        eprintln!("No blocks after block {chain_tip}, the chain tip");
+        return Ok(());
+    };
+
+    let mut writer: BufWriter<Box<dyn Write>> = BufWriter::new(match output {
+        Some(output) => Box::new(
+            File::create(output)
+                .with_context(|| format!("failed to create `{}`", output.display()))?,
+        ),
+        None => Box::new(io::stdout().lock()),
+    });
+
+    // The blocks applied during the export are exported by the next one. The changes are read one
+    // epoch at a time, so each partition of the nullifiers is read once
+    let mut counts = (0, 0, 0);
+    for block_range in epoch_ranges(from..=chain_tip) {
+        let delta = inspector.delta(block_range)?;
+        counts.0 += delta.accounts.len();
+        counts.1 += delta.notes.len();
+        counts.2 += delta.nullifiers.len();
+        for record in delta_records(delta) {
+            serde_json::to_writer(&mut writer, &record)?;
+            writer.write_all(b"\n")?;
+        }
+    }
+    writer.flush()?;
+    drop(writer);
+
+    if let Some(bookmark) = bookmark {
+        write_bookmark(bookmark, chain_tip)?;
+    }
+    eprintln!(
+        "Exported {} accounts, {} notes and {} nullifiers of blocks {from} to {chain_tip}",
+        counts.0, counts.1, counts.2
+    );
+
+    Ok(())
+}
+
+/// Returns the JSON records of the changes of `delta`, the accounts, notes and nullifiers of each
+/// block being ordered by block.
+fn delta_records(delta: StateDelta) -> Vec<Value> {
+    let accounts = delta.accounts.into_iter().map(|account| {
+        let record = json!({
+            "type": "account",
+            "block_num": account.block_num,
+            "account_id": AccountId::from(account.account_id).to_string(),
+            "hash": account.account_hash.to_hex(),
+            "nonce": account.nonce,
+        });
+        (account.block_num, record)
+    });
+    let notes = delta.notes.into_iter().map(|(block_num, note)| {
+        let record = json!({
+            "type": "note",
+            "block_num": block_num.as_u32(),
+            "batch_index": note.batch_index,
+            "note_index": note.note_index,
+            "note_id": note.note_id.to_hex(),
+            "note_type": format!("{:?}", note.note_type),
+            "sender": note.sender.to_string(),
+            "tag": note.tag,
+            "aux": note.aux,
+        });
+        (block_num.as_u32(), record)
+    });
+    let nullifiers = delta.nullifiers.into_iter().map(|nullifier| {
+        let record = json!({
+            "type": "nullifier",
+            "block_num": nullifier.block_num.as_u32(),
+            "block_timestamp": nullifier.block_timestamp,
+            "nullifier": nullifier.nullifier.to_hex(),
+        });
+        (nullifier.block_num.as_u32(), record)
+    });
+
+    // The sort is stable, keeping the order of the records of a block
+    let mut records: Vec<_> = accounts.chain(notes).chain(nullifiers).collect();
+    records.sort_by_key(|(block_num, _)| *block_num);
+    records.into_iter().map(|(_, record)| record).collect()
+}
+
+/// Reads the block recorded in `bookmark`, [None] if the bookmark doesn't exist.
+fn read_bookmark(bookmark: &Path) -> Result<Option<BlockNumber>> {
+    let content = match fs::read_to_string(bookmark) {
+        Ok(content) => content,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => bail!("failed to read the bookmark `{}`: {err}", bookmark.display()),
+    };
+    let block_num = content
+        .trim()
+        .parse::<u32>()
+        .with_context(|| format!("invalid bookmark `{}`", bookmark.display()))?;
+
+    Ok(Some(block_num.into()))
+}
+
+/// Records `block_num` in `bookmark`, replacing the bookmark at once so an interrupted write
+/// doesn't corrupt it.
+fn write_bookmark(bookmark: &Path, block_num: BlockNumber) -> Result<()> {
+    let tmp = bookmark.with_extension("tmp");
+    fs::write(&tmp, format!("{block_num}\n"))
+        .and_then(|_| fs::rename(&tmp, bookmark))
+        .with_context(|| format!("failed to write the bookmark `{}`", bookmark.display()))
+}
+
+// STORE QUERY
+// ===================================================================================================
+
+/// Runs the query `command` against the store database at `database_filepath`, opened in
+/// read-only mode, and prints the result as text or, if `json` is set, as JSON.
+pub fn query(database_filepath: &Path, command: &QueryCommand, json: bool) -> Result<()> {
+    let mut inspector = DbInspector::open(database_filepath).map_err(|err| {
+        anyhow!("failed to open the database `{}`: {err}", database_filepath.display())
+    })?;
+
+    let output = match command {
+        QueryCommand::Account { id } => {
+            let account_id: AccountId = AccountIdObject::from_hex(id)
+                .map_err(|err| anyhow!("invalid account id `{id}`: {err}"))?
+                .into();
+            let lookup = inspector.account(account_id)?;
+            Output::new(account_text(account_id, &lookup), account_json(account_id, &lookup))
+        },
+        QueryCommand::Note { id } => {
+            let note_id =
+                NoteId::try_from_hex(id).map_err(|err| anyhow!("invalid note id `{id}`: {err}"))?;
+            let note = inspector.note(note_id)?;
+            Output::new(note_text(note_id, note.as_ref()), note_json(note_id, note.as_ref()))
+        },
+        QueryCommand::Nullifier { nullifier } => {
+            let nullifier = Nullifier::from_hex(nullifier)
+                .map_err(|err| anyhow!("invalid nullifier `{nullifier}`: {err}"))?;
+            let block_num = inspector.nullifier(&nullifier)?;
+            Output::new(
+                match block_num {
+                    Some(block_num) => {
+                        format!("Nullifier {} consumed in block {block_num}", nullifier.to_hex())
+                    },
+                    None => format!("Nullifier {} not consumed", nullifier.to_hex()),
+                },
+                json!({
+                    "nullifier": nullifier.to_hex(),
+                    "block_num": block_num.map(BlockNumber::as_u32),
+                }),
+            )
+        },
+        QueryCommand::Header { block_num } => {
+            let block_num = block_num.map(BlockNumber::from);
+            let header = inspector.block_header(block_num)?;
+            Output::new(header_text(block_num, header.as_ref()), header_json(header.as_ref()))
+        },
+        QueryCommand::Stats => {
+            let stats = inspector.stats()?;
+            Output::new(stats_text(&stats), stats_json(&stats))
+        },
+    };
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&output.json)?);
+    } else {
+        println!("{}", output.text);
+    }
+
+    Ok(())
+}
+
+/// Human-readable and JSON renderings of a query result.
+struct Output {
+    text: String,
+    json: Value,
+}
+
+impl Output {
+    fn new(text: String, json: Value) -> Self {
+        Self { text, json }
+    }
+}
+
+// ACCOUNT
+// ---------------------------------------------------------------------------------------------------
+
+fn account_text(account_id: AccountId, lookup: &AccountLookup) -> String {
+    match lookup {
+        AccountLookup::NotFound => format!("Account {account_id} not found"),
+        AccountLookup::PrivateOnChain { summary } => {
+            format!("Account {account_id} (private)\n{}", summary_text(summary))
+        },
+        AccountLookup::Public { summary, account } => format!(
+            "Account {account_id} (public)\n{}\n  code root: {}\n  storage root: {}\n  vault root: {}",
+            summary_text(summary),
+            account.code().root(),
+            account.storage().root(),
+            account.vault().commitment(),
+        ),
+    }
+}
+
+fn summary_text(summary: &AccountSummary) -> String {
+    format!(
+        "  hash: {}\n  nonce: {}\n  last updated in block: {}",
+        summary.account_hash,
+        format_opt(summary.nonce.as_ref()),
+        summary.block_num
+    )
+}
+
+fn account_json(account_id: AccountId, lookup: &AccountLookup) -> Value {
+    let (summary, account) = match lookup {
+        AccountLookup::NotFound => return json!({ "account_id": account_id.to_string() }),
+        AccountLookup::PrivateOnChain { summary } => (summary, None),
+        AccountLookup::Public { summary, account } => (summary, Some(account)),
+    };
+
+    json!({
+        "account_id": account_id.to_string(),
+        "hash": summary.account_hash.to_hex(),
+        "block_num": summary.block_num,
+        "public": account.is_some(),
+        "nonce": summary.nonce,
+        "code_root": account.map(|account| account.code().root().to_hex()),
+        "storage_root": account.map(|account| account.storage().root().to_hex()),
+        "vault_root": account.map(|account| account.vault().commitment().to_hex()),
+    })
+}
+
+// NOTE
+// ---------------------------------------------------------------------------------------------------
+
+fn note_text(note_id: NoteId, note: Option<&Note>) -> String {
+    let Some(note) = note else {
+        return format!("Note {} not found", note_id.inner());
+    };
+    let created = &note.note_created;
+
+    format!(
+        "Note {}\n  block: {}\n  batch index: {}\n  note index: {}\n  type: {:?}\n  sender: {}\n  \
+         tag: {}\n  details: {}",
+        note_id.inner(),
+        note.block_num,
+        created.batch_index,
+        created.note_index,
+        created.note_type,
+        created.sender,
+        created.tag,
+        format_opt(
+            created
+                .details
+                .as_ref()
+                .map(|details| format!("{} bytes", details.len()))
+                .as_ref()
+        ),
+    )
+}
+
+fn note_json(note_id: NoteId, note: Option<&Note>) -> Value {
+    let Some(note) = note else {
+        return json!({ "note_id": note_id.inner().to_hex() });
+    };
+    let created = &note.note_created;
+
+    json!({
+        "note_id": note_id.inner().to_hex(),
+        "block_num": note.block_num.as_u32(),
+        "batch_index": created.batch_index,
+        "note_index": created.note_index,
+        "note_type": format!("{:?}", created.note_type),
+        "sender": created.sender.to_string(),
+        "tag": created.tag,
+        "details_len": created.details.as_ref().map(Vec::len),
+    })
+}
+
+// BLOCK HEADER
+// ---------------------------------------------------------------------------------------------------
+
+fn header_text(
+    block_num: Option<BlockNumber>,
+    header: Option<&(BlockHeader, Option<Signature>)>,
+) -> String {
+    let Some((header, signature)) = header else {
+        return match block_num {
+            Some(block_num) => format!("Block {block_num} not found"),
+            None => "The database has no blocks".to_string(),
+        };
+    };
+
+    format!(
+        "Block {}\n  hash: {}\n  prev hash: {}\n  chain root: {}\n  account root: {}\n  nullifier \
+         root: {}\n  note root: {}\n  batch root: {}\n  proof hash: {}\n  version: {}\n  timestamp: \
+         {}\n  signed: {}",
+        header.block_num(),
+        header.hash(),
+        header.prev_hash(),
+        header.chain_root(),
+        header.account_root(),
+        header.nullifier_root(),
+        header.note_root(),
+        header.batch_root(),
+        header.proof_hash(),
+        header.version(),
+        header.timestamp(),
+        signature.is_some(),
+    )
+}
+
+fn header_json(header: Option<&(BlockHeader, Option<Signature>)>) -> Value {
+    let Some((header, signature)) = header else {
+        return Value::Null;
+    };
+
+    json!({
+        "block_num": header.block_num(),
+        "hash": header.hash().to_hex(),
+        "prev_hash": header.prev_hash().to_hex(),
+        "chain_root": header.chain_root().to_hex(),
+        "account_root": header.account_root().to_hex(),
+        "nullifier_root": header.nullifier_root().to_hex(),
+        "note_root": header.note_root().to_hex(),
+        "batch_root": header.batch_root().to_hex(),
+        "proof_hash": header.proof_hash().to_hex(),
+        "version": header.version().as_int(),
+        "timestamp": header.timestamp().as_int(),
+        "signed": signature.is_some(),
+    })
+}
+
+// STATS
+// ---------------------------------------------------------------------------------------------------
+
+fn stats_text(stats: &DbStats) -> String {
+    let mut text = format!(
+        "Chain tip: {}\nRows:\n  block headers: {}\n  accounts: {}\n  notes: {}\n  nullifiers: \
+         {}\n  transactions: {}\nSize: {} bytes",
+        format_opt(stats.chain_tip.as_ref()),
+        stats.rows.block_headers,
+        stats.rows.accounts,
+        stats.rows.notes,
+        stats.rows.nullifiers,
+        stats.rows.transactions,
+        stats.size.total_bytes,
+    );
+    for (table, bytes) in stats.size.tables.iter() {
+        text.push_str(&format!("\n  {table}: {bytes} bytes"));
+    }
+
+    text
+}
+
+fn stats_json(stats: &DbStats) -> Value {
+    json!({
+        "chain_tip": stats.chain_tip.map(BlockNumber::as_u32),
+        "rows": {
+            "block_headers": stats.rows.block_headers,
+            "accounts": stats.rows.accounts,
+            "notes": stats.rows.notes,
+            "nullifiers": stats.rows.nullifiers,
+            "transactions": stats.rows.transactions,
+        },
+        "size": {
+            "total_bytes": stats.size.total_bytes,
+            "tables": stats.size.tables,
+        },
+    })
+}
@@ -1,49 +1,321 @@
+use std::{
+    env,
+    net::IpAddr,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, Result};
+use clap::Args;
 use miden_node_block_producer::config::BlockProducerConfig;
 use miden_node_rpc::config::RpcConfig;
 use miden_node_store::config::StoreConfig;
+use miden_node_utils::config::{load_layered_config, validate_url, Endpoint};
 use serde::{Deserialize, Serialize};
 
+use crate::NODE_CONFIG_FILE_PATH;
+
+/// Prefix of the environment variables overriding the configuration file, e.g.
+/// `MIDEN_NODE_STORE__ENDPOINT__PORT` for `store.endpoint.port`.
+pub const ENV_PREFIX: &str = "MIDEN_NODE_";
+
+// CONFIG ARGUMENTS
+// ================================================================================================
+
+/// Command line arguments locating the configuration file and overriding its values.
+#[derive(Args, Clone, Debug)]
+pub struct ConfigArgs {
+    #[arg(short, long, value_name = "FILE", default_value = NODE_CONFIG_FILE_PATH)]
+    pub config: PathBuf,
+
+    /// Override a configuration value, e.g. `--set store.endpoint.port=28943`. Takes precedence
+    /// over the configuration file and the `MIDEN_NODE_*` environment variables
+    #[arg(long = "set", value_name = "KEY=VALUE", value_parser = parse_override)]
+    pub overrides: Vec<(String, String)>,
+}
+
+impl ConfigArgs {
+    /// Loads the configuration, see [NodeConfig::load].
+    pub fn load(&self) -> Result<NodeConfig> {
+        NodeConfig::load(&self.config, &self.overrides)
+    }
+}
+
+/// Parses a `KEY=VALUE` override.
+fn parse_override(arg: &str) -> Result<(String, String), String> {
+    match arg.split_once('=') {
+        Some((key, value)) if !key.is_empty() => Ok((key.to_string(), value.to_string())),
+        _ => Err(format!("invalid override `{arg}`, expected `KEY=VALUE`")),
+    }
+}
+
+// NODE CONFIG
+// ================================================================================================
+
 /// Node top-level configuration.
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize)]
 pub struct NodeConfig {
+    /// How the components communicate when they are started together with `start node`.
+    #[serde(default)]
+    pub transport: Transport,
     pub block_producer: Option<BlockProducerConfig>,
     pub rpc: Option<RpcConfig>,
     pub store: Option<StoreConfig>,
 }
 
+impl NodeConfig {
+    /// Loads the configuration from `config_file`, overridden by the `MIDEN_NODE_*` environment
+    /// variables, themselves overridden by `overrides`.
+    pub fn load(config_file: &Path, overrides: &[(String, String)]) -> Result<Self> {
+        if !config_file_exists(config_file) {
+            return Err(anyhow!(
+                "config file `{}` not found, pass its path with `--config`",
+                config_file.display()
+            ));
+        }
+
+        load_layered_config(config_file, ENV_PREFIX, overrides)
+            .extract()
+            .map_err(|err| anyhow!("failed to load config file `{}`: {err}", config_file.display()))
+    }
+
+    /// Checks the configuration of the components, failing with the list of all the problems
+    /// found: invalid URLs, endpoints listening on the same port and missing input files.
+    pub fn validate(&self) -> Result<()> {
+        let problems = self.problems();
+        if problems.is_empty() {
+            return Ok(());
+        }
+
+        let mut message = "invalid configuration:".to_string();
+        for problem in problems {
+            message.push_str("\n  - ");
+            message.push_str(&problem);
+        }
+        Err(anyhow!(message))
+    }
+
+    /// Returns the configuration with the API keys replaced by placeholders, for printing.
+    pub fn redacted(mut self) -> Self {
+        if let Some(access_control) = self.rpc.as_mut().and_then(|rpc| rpc.access_control.as_mut())
+        {
+            access_control.api_keys = std::mem::take(&mut access_control.api_keys)
+                .into_values()
+                .enumerate()
+                .map(|(index, role)| (format!("<redacted-{index}>"), role))
+                .collect();
+        }
+        self
+    }
+
+    fn problems(&self) -> Vec<String> {
+        let grpc = self.transport == Transport::Grpc;
+        let mut problems = Vec::new();
+        let mut urls = Vec::new();
+        let mut listeners = Vec::new();
+
+        if let Some(block_producer) = &self.block_producer {
+            if grpc {
+                listeners.push(("block_producer.endpoint", &block_producer.endpoint));
+                urls.push(("block_producer.store_url", &block_producer.store_url));
+            }
+            if block_producer.batch_sealing.max_delay_ms == 0 {
+                problems.push(
+                    "block_producer.batch_sealing.max_delay_ms: must be at least 1 millisecond"
+                        .to_string(),
+                );
+            }
+        }
+
+        if let Some(rpc) = &self.rpc {
+            listeners.push(("rpc.endpoint", &rpc.endpoint));
+            if let Some(admin) = &rpc.admin {
+                listeners.push(("rpc.admin.endpoint", &admin.endpoint));
+            }
+            if grpc {
+                urls.push(("rpc.store_url", &rpc.store_url));
+                urls.push(("rpc.block_producer_url", &rpc.block_producer_url));
+            }
+            if let Some(faucet) = &rpc.testnet_faucet {
+                if let Some(verification_url) = &faucet.verification_url {
+                    urls.push(("rpc.testnet_faucet.verification_url", verification_url));
+                }
+                if !faucet.faucet_account_filepath.exists() {
+                    problems.push(format!(
+                        "rpc.testnet_faucet.faucet_account_filepath: account file `{}` doesn't \
+                         exist, it is written by `miden-node make-genesis`",
+                        faucet.faucet_account_filepath.display()
+                    ));
+                }
+            }
+        }
+
+        if let Some(store) = &self.store {
+            if grpc {
+                listeners.push(("store.endpoint", &store.endpoint));
+            }
+            if let Some(follow) = &store.follow {
+                urls.push(("store.follow.rpc_url", &follow.rpc_url));
+                if store.read_only {
+                    problems.push(
+                        "store.read_only: a read-only store can't follow another node, unset \
+                         store.follow"
+                            .to_string(),
+                    );
+                }
+            }
+            if let Some(backup) = &store.backup {
+                if backup.interval_blocks == 0 && backup.interval().is_none() {
+                    problems.push(
+                        "store.backup: no backup is ever taken, set interval_blocks or interval_s"
+                            .to_string(),
+                    );
+                }
+            }
+            if !store.genesis_filepath.exists() {
+                problems.push(format!(
+                    "store.genesis_filepath: genesis file `{}` doesn't exist, generate it with \
+                     `miden-node make-genesis`",
+                    store.genesis_filepath.display()
+                ));
+            }
+        }
+
+        for (key, url) in urls {
+            if let Err(err) = validate_url(url) {
+                problems.push(format!("{key}: {err}, e.g. `http://localhost:57291`"));
+            }
+        }
+
+        for (index, (key, endpoint)) in listeners.iter().enumerate() {
+            for (other_key, other_endpoint) in &listeners[..index] {
+                if endpoints_overlap(endpoint, other_endpoint) {
+                    problems.push(format!(
+                        "{key}: `{endpoint}` listens on the same port as {other_key} \
+                         `{other_endpoint}`, use distinct ports"
+                    ));
+                }
+            }
+        }
+
+        problems
+    }
+}
+
+/// Returns true if the file exists, a relative path being searched in the current directory and
+/// its parents like [load_layered_config] does.
+fn config_file_exists(config_file: &Path) -> bool {
+    if config_file.is_absolute() {
+        return config_file.exists();
+    }
+    env::current_dir().is_ok_and(|dir| dir.ancestors().any(|dir| dir.join(config_file).exists()))
+}
+
+/// Returns true if both endpoints listen on the same port of the same interface, an unspecified
+/// address like `0.0.0.0` listening on all of them.
+fn endpoints_overlap(endpoint: &Endpoint, other: &Endpoint) -> bool {
+    let is_unspecified =
+        |host: &str| host.parse::<IpAddr>().is_ok_and(|address| address.is_unspecified());
+
+    endpoint.port != 0
+        && endpoint.port == other.port
+        && (endpoint.host == other.host
+            || is_unspecified(&endpoint.host)
+            || is_unspecified(&other.host))
+}
+
+/// Transport used between the components of a node running in a single process.
+#[derive(
+    Clone, Copy, Default, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum Transport {
+    /// Each component serves its gRPC API on its endpoint, and the other components connect to it
+    /// over the network.
+    #[default]
+    Grpc,
+    /// Requests are handed directly to the other components. Only the RPC component listens on
+    /// its endpoint, the store and block producer endpoints and URLs are ignored.
+    InProcess,
+}
+
 #[cfg(test)]
 mod tests {
-    use std::path::PathBuf;
+    use std::{path::PathBuf, time::Duration};
 
     use figment::Jail;
-    use miden_node_block_producer::config::BlockProducerConfig;
-    use miden_node_rpc::config::RpcConfig;
-    use miden_node_store::config::StoreConfig;
-    use miden_node_utils::config::{load_config, Endpoint};
+    use miden_node_block_producer::config::{
+        AdaptiveBatchSize, BatchProvingConfig, BatchSealingPolicy, BlockProducerConfig,
+        BlockProductionConfig, ClockConfig, SimulationConfig,
+    };
+    use miden_node_rpc::config::{
+        AccessControlConfig, AdminConfig, AuditConfig, CorsConfig, LoadSheddingConfig, QuotaConfig,
+        RequestTimeouts, Role, RpcConfig, StoreRetryConfig, TestnetFaucetConfig, UsageConfig,
+    };
+    use miden_node_store::config::{
+        BackupConfig, BlockProofsConfig, CacheConfig, DatabaseConfig, DatabaseSizeConfig,
+        FollowConfig, StoreConfig, SyncFiltersConfig,
+    };
+    use miden_node_utils::config::{load_config, AccessLogConfig, ClientConfig, Endpoint};
 
-    use super::NodeConfig;
+    use super::{NodeConfig, Transport};
     use crate::NODE_CONFIG_FILE_PATH;
 
+    const STORE_CONFIG: &str = r#"
+        [store]
+        endpoint = { host = "127.0.0.1",  port = 8080 }
+        database_filepath = "local.sqlite3"
+        genesis_filepath = "genesis.dat"
+    "#;
+
     #[test]
     fn test_node_config() {
         Jail::expect_with(|jail| {
             jail.create_file(
                 NODE_CONFIG_FILE_PATH,
                 r#"
+                    transport = "in_process"
+
                     [block_producer]
                     endpoint = { host = "127.0.0.1",  port = 8080 }
                     store_url = "http://store:8000"
+                    store_client = { keep_alive_interval_ms = 10000, health_check_interval_ms = 0 }
+                    genesis_hash = "0x02"
                     verify_tx_proofs = true
+                    batch_sealing = { max_txs = 4, max_notes = 1024, max_delay_ms = 500, adaptive = { min_txs = 1, target_latency_ms = 20000 } }
+                    batch_proving = { max_in_flight_batches = 8 }
+                    block_production = { interval_ms = 5000, max_cycles = 1048576, priority_lane_capacity = 8 }
+                    clock = { type = "ntp", server = "pool.ntp.org:123", max_drift_ms = 1000 }
+                    signing_key_filepath = "sequencer.key"
+                    simulation = { block_proof_size = 2048 }
 
                     [rpc]
                     endpoint = { host = "127.0.0.1",  port = 8080 }
                     store_url = "http://store:8000"
                     block_producer_url = "http://block_producer:8001"
+                    client = { connect_timeout_ms = 1000 }
+                    timeouts = { default_ms = 5000, endpoints = { SyncState = 1000 } }
+                    cors = { allowed_origins = ["https://wallet.example.com"] }
+                    admin = { endpoint = { host = "127.0.0.1", port = 8090 } }
+                    audit = { database_filepath = "audit.sqlite3" }
+                    load_shedding = { max_in_flight_cost = 500, priority_endpoints = ["SubmitProvenTransaction", "GetChainTip"] }
+                    access_control = { api_keys = { partner-key = "submitter" }, peers = { "10.0.0.1" = "admin" } }
+                    testnet_faucet = { faucet_account_filepath = "accounts/account1.mac", asset_amount = 100, per_account = { max_requests = 1, period_secs = 86400 }, per_ip = { max_requests = 5, period_secs = 3600 }, verification_url = "http://captcha:8080/verify" }
+                    access_log = { sample_one_in = 100 }
+                    usage = { export_interval_s = 300, endpoint_costs = { SyncState = 20 } }
+                    store_retry = { max_attempts = 2, hedge_delay_ms = 50 }
 
                     [store]
                     endpoint = { host = "127.0.0.1",  port = 8080 }
                     database_filepath = "local.sqlite3"
                     genesis_filepath = "genesis.dat"
+                    cache = { block_headers = 100, notes = 1000 }
+                    database = { busy_timeout_ms = 2000, slow_apply_block_ms = 500, slow_query_ms = 200 }
+                    database_size = { sample_interval_s = 30, max_size_mb = 10240, warn_percent = 90 }
+                    follow = { rpc_url = "http://sequencer:57291", sequencer_public_key = "0x01" }
+                    index_account_keys = true
+                    sync_filters = { max_account_ids = 100, max_note_tags = 200, max_nullifier_prefixes = 300, chunk_size = 50 }
+                    backup = { directory = "backups", interval_blocks = 100, interval_s = 3600, keep_last = 24, upload_command = ["aws", "s3", "cp", "{path}", "s3://bucket/"] }
+                    block_proofs = { keep_last_blocks = 1000 }
                 "#,
             )?;
 
@@ -53,13 +325,44 @@ mod tests {
             assert_eq!(
                 config,
                 NodeConfig {
+                    transport: Transport::InProcess,
                     block_producer: Some(BlockProducerConfig {
                         endpoint: Endpoint {
                             host: "127.0.0.1".to_string(),
                             port: 8080,
                         },
                         store_url: "http://store:8000".to_string(),
-                        verify_tx_proofs: true
+                        store_client: ClientConfig {
+                            keep_alive_interval_ms: 10000,
+                            health_check_interval_ms: 0,
+                            ..Default::default()
+                        },
+                        genesis_hash: Some("0x02".to_string()),
+                        verify_tx_proofs: true,
+                        batch_sealing: BatchSealingPolicy {
+                            max_txs: 4,
+                            max_notes: 1024,
+                            max_delay_ms: 500,
+                            adaptive: Some(AdaptiveBatchSize {
+                                min_txs: 1,
+                                target_latency_ms: 20000,
+                            }),
+                        },
+                        batch_proving: BatchProvingConfig { max_in_flight_batches: 8 },
+                        block_production: BlockProductionConfig {
+                            interval_ms: 5000,
+                            max_cycles: Some(1 << 20),
+                            priority_lane_capacity: 8,
+                        },
+                        clock: ClockConfig::Ntp {
+                            server: "pool.ntp.org:123".to_string(),
+                            max_drift_ms: 1000,
+                        },
+                        signing_key_filepath: Some(PathBuf::from("sequencer.key")),
+                        fees: None,
+                        submission_policy: Default::default(),
+                        simulation: Some(SimulationConfig { block_proof_size: 2048 }),
+                        access_log: None,
                     }),
                     rpc: Some(RpcConfig {
                         endpoint: Endpoint {
@@ -68,6 +371,62 @@ mod tests {
                         },
                         store_url: "http://store:8000".to_string(),
                         block_producer_url: "http://block_producer:8001".to_string(),
+                        client: ClientConfig {
+                            connect_timeout_ms: 1000,
+                            ..Default::default()
+                        },
+                        genesis_hash: None,
+                        timeouts: RequestTimeouts {
+                            default_ms: 5000,
+                            endpoints: [("SyncState".to_string(), 1000)].into(),
+                        },
+                        cors: CorsConfig {
+                            allowed_origins: vec!["https://wallet.example.com".to_string()],
+                        },
+                        admin: Some(AdminConfig {
+                            endpoint: Endpoint {
+                                host: "127.0.0.1".to_string(),
+                                port: 8090,
+                            },
+                        }),
+                        audit: Some(AuditConfig {
+                            database_filepath: "audit.sqlite3".into(),
+                        }),
+                        load_shedding: Some(LoadSheddingConfig {
+                            max_in_flight_cost: 500,
+                            priority_endpoints: [
+                                "SubmitProvenTransaction".to_string(),
+                                "GetChainTip".to_string(),
+                            ]
+                            .into(),
+                            ..Default::default()
+                        }),
+                        access_control: Some(AccessControlConfig {
+                            api_keys: [("partner-key".to_string(), Role::Submitter)].into(),
+                            peers: [([10, 0, 0, 1].into(), Role::Admin)].into(),
+                            ..Default::default()
+                        }),
+                        testnet_faucet: Some(TestnetFaucetConfig {
+                            faucet_account_filepath: "accounts/account1.mac".into(),
+                            asset_amount: 100,
+                            per_account: QuotaConfig { max_requests: 1, period_secs: 86400 },
+                            per_ip: QuotaConfig { max_requests: 5, period_secs: 3600 },
+                            verification_url: Some("http://captcha:8080/verify".to_string()),
+                        }),
+                        access_log: Some(AccessLogConfig {
+                            sample_one_in: 100,
+                            ..Default::default()
+                        }),
+                        usage: Some(UsageConfig {
+                            export_interval_s: 300,
+                            endpoint_costs: [("SyncState".to_string(), 20)].into(),
+                            ..Default::default()
+                        }),
+                        store_retry: Some(StoreRetryConfig {
+                            max_attempts: 2,
+                            hedge_delay_ms: 50,
+                            ..Default::default()
+                        }),
                     }),
                     store: Some(StoreConfig {
                         endpoint: Endpoint {
@@ -75,7 +434,41 @@ mod tests {
                             port: 8080,
                         },
                         database_filepath: "local.sqlite3".into(),
-                        genesis_filepath: "genesis.dat".into()
+                        genesis_filepath: "genesis.dat".into(),
+                        cache: CacheConfig { block_headers: 100, notes: 1000 },
+                        database: DatabaseConfig {
+                            busy_timeout_ms: 2000,
+                            slow_apply_block_ms: 500,
+                            slow_query_ms: 200,
+                        },
+                        database_size: DatabaseSizeConfig {
+                            sample_interval_s: 30,
+                            max_size_mb: 10240,
+                            warn_percent: 90,
+                        },
+                        follow: Some(FollowConfig {
+                            rpc_url: "http://sequencer:57291".to_string(),
+                            sequencer_public_key: Some("0x01".to_string()),
+                        }),
+                        read_only: false,
+                        index_account_keys: true,
+                        sync_filters: SyncFiltersConfig {
+                            max_account_ids: 100,
+                            max_note_tags: 200,
+                            max_nullifier_prefixes: 300,
+                            chunk_size: 50,
+                        },
+                        backup: Some(BackupConfig {
+                            directory: "backups".into(),
+                            interval_blocks: 100,
+                            interval_s: 3600,
+                            keep_last: 24,
+                            upload_command: ["aws", "s3", "cp", "{path}", "s3://bucket/"]
+                                .map(String::from)
+                                .into(),
+                        }),
+                        block_proofs: BlockProofsConfig { keep_last_blocks: 1000 },
+                        access_log: None,
                     }),
                 }
             );
@@ -83,4 +476,117 @@ mod tests {
             Ok(())
         });
     }
+
+    #[test]
+    fn test_node_config_overrides() {
+        Jail::expect_with(|jail| {
+            jail.create_file(NODE_CONFIG_FILE_PATH, STORE_CONFIG)?;
+            jail.set_env("MIDEN_NODE_STORE__ENDPOINT__HOST", "0.0.0.0");
+            jail.set_env("MIDEN_NODE_STORE__ENDPOINT__PORT", 8081);
+            jail.set_env("MIDEN_NODE_STORE__FOLLOW__RPC_URL", "http://sequencer:57291");
+
+            let overrides = [
+                ("store.endpoint.port".to_string(), "8082".to_string()),
+                ("store.index_account_keys".to_string(), "true".to_string()),
+            ];
+            let config = NodeConfig::load(NODE_CONFIG_FILE_PATH.as_ref(), &overrides)
+                .map_err(|err| err.to_string())?;
+            let store = config.store.unwrap();
+
+            // The environment overrides the file, and the command line overrides both
+            assert_eq!(store.endpoint, Endpoint { host: "0.0.0.0".to_string(), port: 8082 });
+            assert_eq!(store.database_filepath, PathBuf::from("local.sqlite3"));
+            assert!(store.index_account_keys);
+            assert_eq!(
+                store.follow,
+                Some(FollowConfig {
+                    rpc_url: "http://sequencer:57291".to_string(),
+                    sequencer_public_key: None,
+                })
+            );
+
+            let err = NodeConfig::load("missing.toml".as_ref(), &[]).unwrap_err();
+            assert!(err.to_string().contains("`missing.toml` not found"));
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_node_config_validation() {
+        Jail::expect_with(|jail| {
+            jail.create_file(
+                NODE_CONFIG_FILE_PATH,
+                &format!(
+                    r#"
+                        [rpc]
+                        endpoint = {{ host = "0.0.0.0",  port = 8080 }}
+                        store_url = "store:8080"
+                        block_producer_url = "http://block-producer:8081"
+                        {STORE_CONFIG}
+                    "#
+                ),
+            )?;
+            let config = NodeConfig::load(NODE_CONFIG_FILE_PATH.as_ref(), &[])
+                .map_err(|err| err.to_string())?;
+
+            let err = config.validate().unwrap_err().to_string();
+            assert!(err.contains("rpc.store_url: `store:8080` must start with `http://`"), "{err}");
+            assert!(err.contains("store.endpoint: `http://127.0.0.1:8080` listens on the same port as rpc.endpoint"), "{err}");
+            assert!(
+                err.contains("store.genesis_filepath: genesis file `genesis.dat` doesn't exist"),
+                "{err}"
+            );
+            assert!(!err.contains("rpc.block_producer_url"), "{err}");
+
+            // Only the RPC listens when the components are started in a single process
+            jail.create_file("genesis.dat", "")?;
+            let config = NodeConfig {
+                transport: Transport::InProcess,
+                ..config
+            };
+            assert!(config.validate().is_ok());
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_node_config_batch_sealing_validation() {
+        Jail::expect_with(|jail| {
+            jail.create_file(
+                NODE_CONFIG_FILE_PATH,
+                r#"
+                    transport = "in_process"
+
+                    [block_producer]
+                    endpoint = { host = "127.0.0.1",  port = 8080 }
+                    store_url = "http://store:8000"
+                    verify_tx_proofs = true
+                    batch_sealing = { max_delay_ms = 0 }
+                "#,
+            )?;
+            let config = NodeConfig::load(NODE_CONFIG_FILE_PATH.as_ref(), &[])
+                .map_err(|err| err.to_string())?;
+
+            let err = config.validate().unwrap_err().to_string();
+            assert!(
+                err.contains("block_producer.batch_sealing.max_delay_ms: must be at least 1"),
+                "{err}"
+            );
+
+            // The queue is still checked every millisecond if the configuration isn't validated
+            let block_producer = config.block_producer.unwrap();
+            assert_eq!(block_producer.batch_sealing.max_delay(), Duration::from_millis(1));
+
+            let config = NodeConfig::load(
+                NODE_CONFIG_FILE_PATH.as_ref(),
+                &[("block_producer.batch_sealing.max_delay_ms".to_string(), "1".to_string())],
+            )
+            .map_err(|err| err.to_string())?;
+            assert!(config.validate().is_ok());
+
+            Ok(())
+        });
+    }
 }
@@ -1,13 +1,18 @@
-use std::path::PathBuf;
+use std::{path::PathBuf, time::Duration};
 
-use anyhow::{anyhow, Context};
+use anyhow::Context;
 use clap::{Parser, Subcommand};
-use commands::start::{start_block_producer, start_node, start_rpc, start_store};
-use config::NodeConfig;
-use miden_node_utils::config::load_config;
+use commands::{
+    start::{endpoints, start_block_producer, start_node, start_rpc, start_store},
+    store::SchemaFormat,
+    KeyExport, KeyFormat,
+};
+use config::{ConfigArgs, NodeConfig};
+use service::run_service;
 
 mod commands;
 mod config;
+mod service;
 
 // CONSTANTS
 // ================================================================================================
@@ -17,6 +22,8 @@ const NODE_CONFIG_FILE_PATH: &str = "miden-node.toml";
 const DEFAULT_GENESIS_FILE_PATH: &str = "genesis.dat";
 const DEFAULT_GENESIS_INPUTS_PATH: &str = "genesis.toml";
 
+const DEFAULT_CHECK_TIMEOUT_MS: u64 = 5_000;
+
 // COMMANDS
 // ================================================================================================
 
@@ -34,8 +41,8 @@ pub enum Command {
         #[command(subcommand)]
         command: StartCommand,
 
-        #[arg(short, long, value_name = "FILE", default_value = NODE_CONFIG_FILE_PATH)]
-        config: PathBuf,
+        #[command(flatten)]
+        config: ConfigArgs,
     },
 
     /// Generates a genesis file and associated account files based on a specified genesis input
@@ -55,6 +62,59 @@ pub enum Command {
         /// Generate the output file even if a file already exists
         #[arg(short, long)]
         force: bool,
+
+        /// Export the authentication keys of the accounts in these formats, comma separated
+        #[arg(long, value_enum, value_delimiter = ',', default_value = "wallet")]
+        key_formats: Vec<KeyFormat>,
+
+        /// Encrypt the keystores with the password read from this file
+        #[arg(long, value_name = "FILE")]
+        keystore_password_file: Option<PathBuf>,
+    },
+
+    /// Inspect the store database
+    Store {
+        #[command(subcommand)]
+        command: StoreCommand,
+
+        #[command(flatten)]
+        config: ConfigArgs,
+    },
+
+    /// Print the status of a running node: the versions of its components, the chain tip, the
+    /// pending transactions, the database size and, for replicas, the sync lag
+    Status {
+        /// Query the node whose RPC is at this URL instead of the RPC of `config`
+        #[arg(long, value_name = "URL")]
+        rpc_url: Option<String>,
+
+        /// Print the status as JSON
+        #[arg(long)]
+        json: bool,
+
+        #[command(flatten)]
+        config: ConfigArgs,
+    },
+
+    /// Check the components of the node are reachable and agree on the genesis block, e.g. after a
+    /// deployment, exiting with an error if any check fails
+    Check {
+        /// Maximum time to connect to a component and to answer a request, in milliseconds
+        #[arg(long, value_name = "MS", default_value_t = DEFAULT_CHECK_TIMEOUT_MS)]
+        timeout_ms: u64,
+
+        #[command(flatten)]
+        config: ConfigArgs,
+    },
+
+    /// Inspect the configuration, resolved from the configuration file, the `MIDEN_NODE_*`
+    /// environment variables and the `--set` overrides
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
+
+        #[command(flatten)]
+        config: ConfigArgs,
     },
 }
 
@@ -63,7 +123,93 @@ pub enum StartCommand {
     Node,
     BlockProducer,
     Rpc,
-    Store,
+    Store {
+        /// Run the store as a replica of the node whose RPC is at this URL, applying its blocks
+        /// instead of the blocks of a local block producer
+        #[arg(long, value_name = "URL")]
+        sync_from: Option<String>,
+
+        /// Serve the reads of the existing database without writing to it, rejecting the blocks
+        #[arg(long, conflicts_with = "sync_from")]
+        read_only: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ConfigCommand {
+    /// Validate the configuration and print the effective configuration, API keys redacted
+    Check,
+}
+
+#[derive(Subcommand)]
+pub enum StoreCommand {
+    /// Query the database at `store.database_filepath`, opened in read-only mode
+    Query {
+        #[command(subcommand)]
+        command: QueryCommand,
+
+        /// Print the result as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Rebuild the database at `store.database_filepath` into a new database, re-deriving the
+    /// notes, nullifiers, accounts and transactions of each stored block and verifying its roots.
+    /// The source database is only read
+    Backfill {
+        /// Write the rebuilt database to this location, which must not exist
+        #[arg(long, value_name = "FILE")]
+        output: PathBuf,
+    },
+    /// Print the tables, indexes and views of the database at `store.database_filepath`, opened in
+    /// read-only mode, and the version of its schema
+    Schema {
+        /// Print the schema in this format
+        #[arg(long, value_enum, default_value = "sql")]
+        format: SchemaFormat,
+
+        /// Query the running store whose API is at this URL instead of reading the database file
+        #[arg(long, value_name = "URL")]
+        store_url: Option<String>,
+    },
+    /// Export the accounts, notes and nullifiers changed after a block as newline-delimited JSON,
+    /// reading the database at `store.database_filepath` in read-only mode
+    ExportDelta {
+        /// Export the changes of the blocks after this block, the block of the bookmark by default
+        #[arg(long, value_name = "BLOCK")]
+        since: Option<u32>,
+
+        /// Resume from the block recorded in this file, which is set to the exported chain tip
+        /// once the export completes
+        #[arg(long, value_name = "FILE")]
+        bookmark: Option<PathBuf>,
+
+        /// Write the records to this file instead of the standard output
+        #[arg(long, value_name = "FILE")]
+        output: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum QueryCommand {
+    /// Print the state of an account
+    Account {
+        /// Hex-encoded account id, e.g. `0x8a65fc5a39e4cd3f`
+        id: String,
+    },
+    /// Print a note created by the chain
+    Note {
+        /// Hex-encoded note id
+        id: String,
+    },
+    /// Print the block which consumed a nullifier
+    Nullifier {
+        /// Hex-encoded nullifier
+        nullifier: String,
+    },
+    /// Print a block header, the latest one if no block number is given
+    Header { block_num: Option<u32> },
+    /// Print the chain tip, the number of rows and the size of the tables
+    Stats,
 }
 
 #[tokio::main]
@@ -73,28 +219,113 @@ async fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
 
     match &cli.command {
-        Command::Start { command, config } => {
-            let config: NodeConfig = load_config(config).extract().map_err(|err| {
-                anyhow!("failed to load config file `{}`: {err}", config.display())
-            })?;
+        Command::Start { command, config: args } => match command {
+            StartCommand::Node => {
+                let config = args.load()?;
+                config.validate()?;
+                run_service(endpoints(&config), |shutdown| start_node(config, shutdown)).await
+            },
+            StartCommand::BlockProducer => {
+                let config = NodeConfig { rpc: None, store: None, ..args.load()? };
+                config.validate()?;
+                let endpoints = endpoints(&config);
+                let config =
+                    config.block_producer.context("Missing block-producer configuration.")?;
+                run_service(endpoints, |shutdown| start_block_producer(config, shutdown)).await
+            },
+            StartCommand::Rpc => {
+                let config = NodeConfig {
+                    block_producer: None,
+                    store: None,
+                    ..args.load()?
+                };
+                config.validate()?;
+                let endpoints = endpoints(&config);
+                let config = config.rpc.context("Missing rpc configuration.")?;
+                run_service(endpoints, |shutdown| start_rpc(config, shutdown)).await
+            },
+            StartCommand::Store { sync_from, read_only } => {
+                // The sequencer's public key is kept from the configuration, if set
+                let mut overrides = args.overrides.clone();
+                if let Some(rpc_url) = sync_from {
+                    overrides.push(("store.follow.rpc_url".to_string(), rpc_url.clone()));
+                }
+                if *read_only {
+                    overrides.push(("store.read_only".to_string(), "true".to_string()));
+                }
+                let config = NodeConfig {
+                    block_producer: None,
+                    rpc: None,
+                    ..NodeConfig::load(&args.config, &overrides)?
+                };
+                config.validate()?;
+                let endpoints = endpoints(&config);
+                let config = config.store.context("Missing store configuration.")?;
+                run_service(endpoints, |shutdown| start_store(config, shutdown)).await
+            },
+        },
+        Command::MakeGenesis {
+            output_path,
+            force,
+            inputs_path,
+            key_formats,
+            keystore_password_file,
+        } => {
+            let keys =
+                KeyExport::new(key_formats.iter().copied(), keystore_password_file.as_deref())?;
+            commands::make_genesis(inputs_path, output_path, force, &keys)
+        },
+        Command::Store { command, config } => {
+            // The schema of a running store doesn't need the configuration of the store
+            if let StoreCommand::Schema { format, store_url: Some(store_url) } = command {
+                return commands::store::store_schema(store_url, *format).await;
+            }
+
+            let store_config = config.load()?.store.context("Missing store configuration.")?;
             match command {
-                StartCommand::Node => start_node(config).await,
-                StartCommand::BlockProducer => {
-                    start_block_producer(
-                        config.block_producer.context("Missing block-producer configuration.")?,
-                    )
-                    .await
+                StoreCommand::Query { command, json } => {
+                    commands::store::query(&store_config.database_filepath, command, *json)
+                },
+                StoreCommand::Backfill { output } => {
+                    commands::store::backfill(store_config, output).await
                 },
-                StartCommand::Rpc => {
-                    start_rpc(config.rpc.context("Missing rpc configuration.")?).await
+                StoreCommand::Schema { format, .. } => {
+                    commands::store::schema(&store_config.database_filepath, *format)
                 },
-                StartCommand::Store => {
-                    start_store(config.store.context("Missing store configuration.")?).await
+                StoreCommand::ExportDelta { since, bookmark, output } => {
+                    commands::store::export_delta(
+                        &store_config.database_filepath,
+                        *since,
+                        bookmark.as_deref(),
+                        output.as_deref(),
+                    )
                 },
             }
         },
-        Command::MakeGenesis { output_path, force, inputs_path } => {
-            commands::make_genesis(inputs_path, output_path, force)
+        Command::Status { rpc_url, json, config } => {
+            let rpc_url = match rpc_url {
+                Some(rpc_url) => rpc_url.clone(),
+                None => {
+                    config.load()?.rpc.context("Missing rpc configuration.")?.endpoint.to_string()
+                },
+            };
+            commands::status::status(&rpc_url, *json).await
+        },
+        Command::Check { timeout_ms, config } => {
+            commands::check::check(&config.load()?, Duration::from_millis(*timeout_ms)).await
+        },
+        Command::Config {
+            command: ConfigCommand::Check,
+            config: args,
+        } => {
+            let config = args.load()?;
+            config.validate()?;
+            println!(
+                "# Effective configuration of `{}`, API keys redacted\n\n{}",
+                args.config.display(),
+                toml::to_string_pretty(&config.redacted())?
+            );
+            Ok(())
         },
     }
 }
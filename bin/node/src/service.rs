@@ -0,0 +1,306 @@
+//! Integration with the service manager, following the `sd_notify` protocol of systemd.
+//!
+//! When the node runs as a `Type=notify` service, systemd passes the path of its notification
+//! socket in `NOTIFY_SOCKET` and the node reports:
+//!
+//! - `READY=1` once its components accept connections on their endpoints,
+//! - `WATCHDOG=1` at half the interval of `WatchdogSec=`, as long as the endpoints still accept
+//!   connections,
+//! - `STOPPING=1` when it receives `SIGTERM` or `SIGINT`, then `EXTEND_TIMEOUT_USEC=` while the
+//!   components complete their shutdown,
+//!
+//! along with a `STATUS=` describing what the node is doing. Without `NOTIFY_SOCKET` nothing is
+//! sent, and the node still stops gracefully on these signals.
+use std::{env, future::Future, time::Duration};
+
+use anyhow::Result;
+use miden_node_utils::config::Endpoint;
+use tokio::{
+    net::TcpStream,
+    sync::watch,
+    time::{interval, interval_at, sleep, timeout, Instant},
+};
+use tracing::{info, warn};
+
+const COMPONENT: &str = "miden-node";
+
+/// Interval between two attempts to connect to the endpoints of the starting components.
+const READINESS_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Maximum time to connect to the endpoint of a component, when checking it is serving.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Interval between two progress notifications while the components are stopping, each extending
+/// the stop timeout of the service manager by twice this interval.
+const SHUTDOWN_PROGRESS_INTERVAL: Duration = Duration::from_secs(5);
+
+// SERVICE
+// ================================================================================================
+
+/// Completes once the node is asked to stop, the components passed it must then stop.
+#[derive(Clone)]
+pub struct Shutdown(watch::Receiver<bool>);
+
+impl Shutdown {
+    pub async fn requested(mut self) {
+        // The sender is only dropped once the components stopped
+        let _ = self.0.wait_for(|&stop| stop).await;
+    }
+}
+
+/// Runs the components started by `start` until they fail or the node receives `SIGTERM` or
+/// `SIGINT`, notifying the service manager of their state.
+///
+/// The components are ready once all of `endpoints` accept connections. On a signal, the
+/// [Shutdown] passed to `start` completes and the components are waited for.
+pub async fn run_service<F, Fut>(endpoints: Vec<Endpoint>, start: F) -> Result<()>
+where
+    F: FnOnce(Shutdown) -> Fut,
+    Fut: Future<Output = Result<()>>,
+{
+    supervise(Notifier::from_env(), endpoints, start, stop_signal()).await
+}
+
+/// Runs the components started by `start` until they fail or `stop_signal` completes, see
+/// [run_service].
+async fn supervise<F, Fut>(
+    notifier: Notifier,
+    endpoints: Vec<Endpoint>,
+    start: F,
+    stop_signal: impl Future<Output = ()>,
+) -> Result<()>
+where
+    F: FnOnce(Shutdown) -> Fut,
+    Fut: Future<Output = Result<()>>,
+{
+    let (stop, stop_requested) = watch::channel(false);
+
+    let components = start(Shutdown(stop_requested));
+    tokio::pin!(components, stop_signal);
+
+    notifier.notify("STATUS=Starting");
+    let started = tokio::select! {
+        result = &mut components => return result,
+        () = wait_until_serving(&endpoints) => true,
+        () = &mut stop_signal => false,
+    };
+
+    if started {
+        info!(target: COMPONENT, "Node is ready");
+        notifier.notify("READY=1\nSTATUS=Serving");
+
+        let watchdog = async {
+            let Some(period) = Notifier::watchdog_interval() else {
+                return std::future::pending().await;
+            };
+            let mut ticks = interval(period);
+            loop {
+                ticks.tick().await;
+                // A missed ping makes the service manager restart the node
+                if is_serving(&endpoints).await {
+                    notifier.notify("WATCHDOG=1");
+                } else {
+                    warn!(target: COMPONENT, "Components stopped accepting connections");
+                }
+            }
+        };
+
+        tokio::select! {
+            result = &mut components => return result,
+            () = watchdog => {},
+            () = &mut stop_signal => {},
+        }
+    }
+
+    info!(target: COMPONENT, "Stopping the node");
+    notifier.notify("STOPPING=1\nSTATUS=Stopping");
+    let _ = stop.send(true);
+
+    let stopping_since = Instant::now();
+    let mut progress =
+        interval_at(stopping_since + SHUTDOWN_PROGRESS_INTERVAL, SHUTDOWN_PROGRESS_INTERVAL);
+    loop {
+        tokio::select! {
+            result = &mut components => {
+                info!(target: COMPONENT, "Node stopped");
+                return result;
+            },
+            _ = progress.tick() => {
+                let elapsed = stopping_since.elapsed().as_secs();
+                info!(target: COMPONENT, elapsed, "Waiting for the components to stop");
+                notifier.notify(&format!(
+                    "EXTEND_TIMEOUT_USEC={}\nSTATUS=Stopping for {elapsed}s",
+                    2 * SHUTDOWN_PROGRESS_INTERVAL.as_micros()
+                ));
+            },
+        }
+    }
+}
+
+/// Completes when the node receives `SIGTERM` or `SIGINT`.
+async fn stop_signal() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        let mut terminate =
+            signal(SignalKind::terminate()).expect("Failed to install the SIGTERM handler");
+        tokio::select! {
+            _ = terminate.recv() => {},
+            _ = tokio::signal::ctrl_c() => {},
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = tokio::signal::ctrl_c().await;
+    }
+}
+
+async fn wait_until_serving(endpoints: &[Endpoint]) {
+    while !is_serving(endpoints).await {
+        sleep(READINESS_POLL_INTERVAL).await;
+    }
+}
+
+/// Returns whether all of `endpoints` accept connections.
+async fn is_serving(endpoints: &[Endpoint]) -> bool {
+    for endpoint in endpoints {
+        let connect = TcpStream::connect((endpoint.host.as_str(), endpoint.port));
+        if !matches!(timeout(CONNECT_TIMEOUT, connect).await, Ok(Ok(_))) {
+            return false;
+        }
+    }
+
+    true
+}
+
+// NOTIFIER
+// ================================================================================================
+
+/// Sends the notifications to the socket of the service manager, if the node runs under one.
+struct Notifier {
+    #[cfg(unix)]
+    socket: Option<(std::os::unix::net::UnixDatagram, std::os::unix::net::SocketAddr)>,
+}
+
+impl Notifier {
+    #[cfg(unix)]
+    fn from_env() -> Self {
+        match env::var_os("NOTIFY_SOCKET") {
+            Some(path) => Self::new(&path),
+            None => Self { socket: None },
+        }
+    }
+
+    /// Returns a notifier sending to the socket at `path`.
+    #[cfg(unix)]
+    fn new(path: &std::ffi::OsStr) -> Self {
+        use std::os::unix::net::{SocketAddr, UnixDatagram};
+
+        // Paths starting with `@` are in the abstract namespace of Linux
+        let address = match path.to_str().and_then(|path| path.strip_prefix('@')) {
+            #[cfg(target_os = "linux")]
+            Some(name) => {
+                use std::os::linux::net::SocketAddrExt;
+                SocketAddr::from_abstract_name(name)
+            },
+            #[cfg(not(target_os = "linux"))]
+            Some(_) => Err(std::io::ErrorKind::Unsupported.into()),
+            None => SocketAddr::from_pathname(path),
+        };
+
+        match address.and_then(|address| Ok((UnixDatagram::unbound()?, address))) {
+            Ok(socket) => Self { socket: Some(socket) },
+            Err(err) => {
+                warn!(target: COMPONENT, ?path, %err, "Invalid notification socket");
+                Self { socket: None }
+            },
+        }
+    }
+
+    #[cfg(not(unix))]
+    fn from_env() -> Self {
+        Self {}
+    }
+
+    /// Returns the interval of the watchdog pings, half the watchdog timeout of the service
+    /// manager, [None] if its watchdog is disabled or watches another process.
+    fn watchdog_interval() -> Option<Duration> {
+        if let Ok(pid) = env::var("WATCHDOG_PID") {
+            if pid.parse() != Ok(std::process::id()) {
+                return None;
+            }
+        }
+
+        let timeout = env::var("WATCHDOG_USEC").ok()?.parse().ok().filter(|&usec| usec != 0)?;
+        Some(Duration::from_micros(timeout) / 2)
+    }
+
+    /// Sends `state`, newline separated `KEY=VALUE` assignments, to the service manager.
+    fn notify(&self, state: &str) {
+        #[cfg(unix)]
+        if let Some((socket, address)) = &self.socket {
+            if let Err(err) = socket.send_to_addr(state.as_bytes(), address) {
+                warn!(target: COMPONENT, %err, state, "Failed to notify the service manager");
+            }
+        }
+        #[cfg(not(unix))]
+        let _ = state;
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::{fs, net::Ipv4Addr};
+
+    use tokio::{
+        net::{TcpListener, UnixDatagram},
+        sync::oneshot,
+    };
+
+    use super::*;
+
+    async fn receive(socket: &UnixDatagram) -> String {
+        let mut buf = [0; 256];
+        let len = timeout(Duration::from_secs(5), socket.recv(&mut buf)).await.unwrap().unwrap();
+        String::from_utf8(buf[..len].to_vec()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn notifies_the_readiness_and_the_shutdown() {
+        let dir = env::temp_dir().join(format!("miden-node-notify-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("notify.sock");
+        let _ = fs::remove_file(&path);
+        let socket = UnixDatagram::bind(&path).unwrap();
+
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).await.unwrap();
+        let endpoint = Endpoint {
+            host: Ipv4Addr::LOCALHOST.to_string(),
+            port: listener.local_addr().unwrap().port(),
+        };
+
+        let (stop, stop_signal) = oneshot::channel::<()>();
+        let service = tokio::spawn(supervise(
+            Notifier::new(path.as_os_str()),
+            vec![endpoint],
+            |shutdown| async move {
+                let _listener = listener;
+                shutdown.requested().await;
+                Ok(())
+            },
+            async {
+                let _ = stop_signal.await;
+            },
+        ));
+
+        assert_eq!(receive(&socket).await, "STATUS=Starting");
+        assert_eq!(receive(&socket).await, "READY=1\nSTATUS=Serving");
+
+        stop.send(()).unwrap();
+        assert_eq!(receive(&socket).await, "STOPPING=1\nSTATUS=Stopping");
+        service.await.unwrap().unwrap();
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
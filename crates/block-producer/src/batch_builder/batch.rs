@@ -1,25 +1,26 @@
-use std::collections::BTreeMap;
+use std::collections::{btree_map::Entry, BTreeMap};
 
-use miden_node_proto::domain::accounts::AccountUpdateDetails;
+use miden_node_proto::domain::{accounts::AccountUpdateDetails, transactions::TransactionSummary};
+use miden_node_utils::chain_params::MAX_NOTES_PER_BATCH;
 use miden_objects::{
     accounts::AccountId,
     batches::BatchNoteTree,
     crypto::hash::blake::{Blake3Digest, Blake3_256},
     notes::Nullifier,
     transaction::{AccountDetails, OutputNote},
-    Digest, MAX_NOTES_PER_BATCH,
+    Digest,
 };
 use tracing::instrument;
 
-use crate::{errors::BuildBatchError, ProvenTransaction};
+use crate::{errors::BuildBatchError, metering::transaction_cycles, ProvenTransaction};
 
 pub type BatchId = Blake3Digest<32>;
 
 // TRANSACTION BATCH
 // ================================================================================================
 
-/// A batch of transactions that share a common proof. For any given account, at most 1 transaction
-/// in the batch must be addressing that account (issue: #186).
+/// A batch of transactions that share a common proof. The transactions modifying the same account
+/// are chained, each starting from the final state of the previous one.
 ///
 /// Note: Until recursive proofs are available in the Miden VM, we don't include the common proof.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -29,6 +30,9 @@ pub struct TransactionBatch {
     produced_nullifiers: Vec<Nullifier>,
     created_notes_smt: BatchNoteTree,
     created_notes: Vec<OutputNote>,
+    transactions: Vec<TransactionSummary>,
+    /// Estimated cycles of each transaction, in the order of `transactions`
+    transaction_cycles: Vec<u64>,
 }
 
 impl TransactionBatch {
@@ -40,25 +44,40 @@ impl TransactionBatch {
     /// # Errors
     /// Returns an error if:
     /// - The number of created notes across all transactions exceeds 4096.
+    /// - A transaction doesn't start from the final state of the previous transaction modifying
+    ///   the same account.
     ///
     /// TODO: enforce limit on the number of created nullifiers.
     #[instrument(target = "miden-block-producer", name = "new_batch", skip_all, err)]
     pub fn new(txs: Vec<ProvenTransaction>) -> Result<Self, BuildBatchError> {
         let id = Self::compute_id(&txs);
-
-        let updated_accounts = txs
-            .iter()
-            .map(|tx| {
-                (
-                    tx.account_id(),
-                    AccountStates {
+        let transactions = txs.iter().map(TransactionSummary::from).collect();
+        let transaction_cycles = txs.iter().map(transaction_cycles).collect();
+
+        let mut updated_accounts = BTreeMap::<AccountId, AccountStates>::new();
+        for tx in &txs {
+            let update = (tx.final_account_hash(), tx.account_details().cloned());
+            match updated_accounts.entry(tx.account_id()) {
+                Entry::Vacant(entry) => {
+                    entry.insert(AccountStates {
                         initial_state: tx.initial_account_hash(),
                         final_state: tx.final_account_hash(),
-                        details: tx.account_details().cloned(),
-                    },
-                )
-            })
-            .collect();
+                        updates: vec![update],
+                    });
+                },
+                Entry::Occupied(mut entry) => {
+                    let states = entry.get_mut();
+                    if states.final_state != tx.initial_account_hash() {
+                        return Err(BuildBatchError::InconsistentAccountStates(
+                            tx.account_id(),
+                            txs,
+                        ));
+                    }
+                    states.final_state = tx.final_account_hash();
+                    states.updates.push(update);
+                },
+            }
+        }
 
         let produced_nullifiers =
             txs.iter().flat_map(|tx| tx.input_notes().iter()).copied().collect();
@@ -82,6 +101,8 @@ impl TransactionBatch {
             produced_nullifiers,
             created_notes_smt,
             created_notes,
+            transactions,
+            transaction_cycles,
         })
     }
 
@@ -94,23 +115,33 @@ impl TransactionBatch {
     }
 
     /// Returns an iterator over (account_id, init_state_hash) tuples for accounts that were
-    /// modified in this transaction batch.
+    /// modified in this transaction batch, the state before the first transaction modifying it.
     pub fn account_initial_states(&self) -> impl Iterator<Item = (AccountId, Digest)> + '_ {
         self.updated_accounts
             .iter()
             .map(|(account_id, account_states)| (*account_id, account_states.initial_state))
     }
 
-    /// Returns an iterator over (account_id, details, new_state_hash) tuples for accounts that were
-    /// modified in this transaction batch.
-    pub fn updated_accounts(&self) -> impl Iterator<Item = AccountUpdateDetails> + '_ {
+    /// Returns an iterator over (account_id, final_state_hash) tuples for accounts that were
+    /// modified in this transaction batch, the state after the last transaction modifying it.
+    pub fn account_final_states(&self) -> impl Iterator<Item = (AccountId, Digest)> + '_ {
         self.updated_accounts
             .iter()
-            .map(|(&account_id, account_states)| AccountUpdateDetails {
-                account_id,
-                final_state_hash: account_states.final_state,
-                details: account_states.details.clone(),
+            .map(|(account_id, account_states)| (*account_id, account_states.final_state))
+    }
+
+    /// Returns an iterator over the account updates of this transaction batch, one per
+    /// transaction, in the order of the transactions for each account.
+    pub fn updated_accounts(&self) -> impl Iterator<Item = AccountUpdateDetails> + '_ {
+        self.updated_accounts.iter().flat_map(|(&account_id, account_states)| {
+            account_states.updates.iter().map(move |(final_state_hash, details)| {
+                AccountUpdateDetails {
+                    account_id,
+                    final_state_hash: *final_state_hash,
+                    details: details.clone(),
+                }
             })
+        })
     }
 
     /// Returns an iterator over produced nullifiers for all consumed notes.
@@ -128,10 +159,26 @@ impl TransactionBatch {
         &self.created_notes
     }
 
+    /// Returns the effects of each transaction in the batch.
+    pub fn transactions(&self) -> &[TransactionSummary] {
+        &self.transactions
+    }
+
+    /// Returns the estimated cycles of each transaction in the batch, in the order of
+    /// [Self::transactions].
+    pub fn transaction_cycles(&self) -> &[u64] {
+        &self.transaction_cycles
+    }
+
+    /// Returns the estimated cycles of all transactions in the batch.
+    pub fn cycles(&self) -> u64 {
+        self.transaction_cycles.iter().sum()
+    }
+
     // HELPER FUNCTIONS
     // --------------------------------------------------------------------------------------------
 
-    fn compute_id(txs: &[ProvenTransaction]) -> BatchId {
+    pub(crate) fn compute_id(txs: &[ProvenTransaction]) -> BatchId {
         let mut buf = Vec::with_capacity(32 * txs.len());
         for tx in txs {
             buf.extend_from_slice(&tx.id().as_bytes());
@@ -140,13 +187,13 @@ impl TransactionBatch {
     }
 }
 
-/// Stores the initial state (before the transaction) and final state (after the transaction) of an
-/// account.
+/// Stores the initial state (before the first transaction) and final state (after the last
+/// transaction) of an account, and the final state and details of each transaction modifying it.
 ///
 /// TODO: should this be moved into domain objects?
 #[derive(Debug, Clone, PartialEq, Eq)]
 struct AccountStates {
     initial_state: Digest,
     final_state: Digest,
-    details: Option<AccountDetails>,
+    updates: Vec<(Digest, Option<AccountDetails>)>,
 }
@@ -1,17 +1,34 @@
-use std::{cmp::min, sync::Arc, time::Duration};
+use std::{
+    collections::BTreeMap,
+    panic,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use async_trait::async_trait;
-use tokio::{sync::RwLock, time};
-use tracing::{debug, info, instrument, Span};
+use tokio::{
+    sync::{Mutex, RwLock, Semaphore},
+    time,
+};
+use tracing::{debug, error, info, instrument, Span};
 
-use crate::{block_builder::BlockBuilder, ProvenTransaction, SharedRwVec, COMPONENT};
+use crate::{block_builder::BlockBuilder, txqueue::Lane, ProvenTransaction, COMPONENT};
 
 #[cfg(test)]
 mod tests;
 
 pub mod batch;
+use batch::BatchId;
+
+pub mod sizing;
 pub use batch::TransactionBatch;
+use miden_node_proto::{
+    domain::transactions::TransactionSummary,
+    generated::responses::{BatchTransaction, GetBatchResponse},
+};
 use miden_node_utils::formatting::{format_array, format_blake3_digest};
+use miden_objects::Digest;
+use sizing::BatchSizing;
 
 use crate::errors::BuildBatchError;
 
@@ -31,6 +48,15 @@ use crate::errors::BuildBatchError;
 pub trait BatchBuilder: Send + Sync + 'static {
     /// Start proving of a new batch.
     async fn build_batch(&self, txs: Vec<ProvenTransaction>) -> Result<(), BuildBatchError>;
+
+    /// Start proving of a new batch of the priority lane, to be included in the next block ahead
+    /// of the other batches. Proven as any other batch by default.
+    async fn build_priority_batch(
+        &self,
+        txs: Vec<ProvenTransaction>,
+    ) -> Result<(), BuildBatchError> {
+        self.build_batch(txs).await
+    }
 }
 
 // DEFAULT BATCH BUILDER
@@ -43,14 +69,29 @@ pub struct DefaultBatchBuilderOptions {
 
     /// Maximum number of batches in any given block
     pub max_batches_per_block: usize,
+
+    /// Maximum number of batches proven concurrently, further batches wait for a proving slot
+    pub max_in_flight_batches: usize,
+
+    /// Maximum estimated cycles of the transactions in any given block, unlimited if [None]
+    pub max_cycles_per_block: Option<u64>,
 }
 
 pub struct DefaultBatchBuilder<BB> {
-    /// Batches ready to be included in a block
-    ready_batches: SharedRwVec<TransactionBatch>,
+    /// Batches being proven or ready to be included in a block, in dispatch order
+    pipeline: Arc<RwLock<BatchPipeline>>,
+
+    /// Limits the number of batches proven concurrently
+    proving_slots: Semaphore,
+
+    /// Allows the operator to pause the production of blocks
+    production: Arc<BlockProduction>,
 
     block_builder: Arc<BB>,
 
+    /// Measures the latencies the batches are sized from
+    batch_sizing: Arc<BatchSizing>,
+
     options: DefaultBatchBuilderOptions,
 }
 
@@ -67,12 +108,32 @@ where
     /// specified options.
     pub fn new(block_builder: Arc<BB>, options: DefaultBatchBuilderOptions) -> Self {
         Self {
-            ready_batches: Arc::new(RwLock::new(Vec::new())),
+            pipeline: Arc::new(RwLock::new(BatchPipeline::default())),
+            proving_slots: Semaphore::new(options.max_in_flight_batches.max(1)),
+            production: Arc::new(BlockProduction::default()),
             block_builder,
+            batch_sizing: Arc::new(BatchSizing::new(Default::default())),
             options,
         }
     }
 
+    /// Records the latencies of the batches and blocks in `batch_sizing`, shared with the
+    /// transaction queue sealing the batches.
+    pub fn with_batch_sizing(mut self, batch_sizing: Arc<BatchSizing>) -> Self {
+        self.batch_sizing = batch_sizing;
+        self
+    }
+
+    /// Returns the handle used to pause and resume the production of blocks.
+    pub fn production(&self) -> Arc<BlockProduction> {
+        self.production.clone()
+    }
+
+    /// Returns the handle used to look up the batches in the pipeline.
+    pub fn lookup(&self) -> BatchLookup {
+        BatchLookup { pipeline: self.pipeline.clone() }
+    }
+
     // BATCH BUILDER STARTER
     // --------------------------------------------------------------------------------------------
     pub async fn run(self: Arc<Self>) {
@@ -89,29 +150,95 @@ where
     // HELPER METHODS
     // --------------------------------------------------------------------------------------------
 
-    /// Note that we call `build_block()` regardless of whether the `ready_batches` queue is empty.
-    /// A call to an empty `build_block()` indicates that an empty block should be created.
+    /// Note that we call `build_block()` regardless of whether any batch is ready. A call to an
+    /// empty `build_block()` indicates that an empty block should be created.
     #[instrument(target = "miden-block-producer", skip_all)]
     async fn try_build_block(&self) {
-        let mut batches_in_block: Vec<TransactionBatch> = {
-            let mut locked_ready_batches = self.ready_batches.write().await;
-
-            let num_batches_in_block =
-                min(self.options.max_batches_per_block, locked_ready_batches.len());
+        // Held while the block is built, so that pausing waits for the block to be applied
+        let mut paused = self.production.paused.lock().await;
+        if *paused {
+            debug!(target: COMPONENT, "Block production is paused");
+            return;
+        }
 
-            locked_ready_batches.drain(..num_batches_in_block).collect()
+        let (ready_batches, full) = {
+            let mut pipeline = self.pipeline.write().await;
+            let ready_batches = pipeline
+                .take_ready(self.options.max_batches_per_block, self.options.max_cycles_per_block);
+            // The block is full if it left out proven batches
+            (ready_batches, pipeline.num_ready() > 0)
         };
+        let (keys, batches_in_block): (Vec<_>, Vec<_>) = ready_batches.into_iter().unzip();
 
         match self.block_builder.build_block(&batches_in_block).await {
             Ok(_) => {
-                // block successfully built, do nothing
+                let latencies = self.pipeline.write().await.commit(&keys);
+                self.batch_sizing.record_block(latencies, full);
             },
-            Err(_) => {
-                // Block building failed; add back the batches at the end of the queue
-                self.ready_batches.write().await.append(&mut batches_in_block);
+            Err(err) => {
+                // Block building failed; put the batches back at their place in the pipeline
+                self.pipeline.write().await.restore(keys.into_iter().zip(batches_in_block));
+
+                // The block is built again at the next period, unless the store's failure can't be
+                // fixed by retrying, the operator then resumes the production once it is fixed
+                if err.halts_production() {
+                    error!(target: COMPONENT, %err, "Block production halted");
+                    *paused = true;
+                }
             },
         }
     }
+
+    /// Proves the batch of `txs` and adds it to the pipeline in `lane`.
+    #[instrument(target = "miden-block-producer", skip_all, err, fields(batch_id, ?lane))]
+    async fn build_lane_batch(
+        &self,
+        txs: Vec<ProvenTransaction>,
+        lane: Lane,
+    ) -> Result<(), BuildBatchError> {
+        let num_txs = txs.len();
+
+        info!(target: COMPONENT, num_txs, "Building a transaction batch");
+        debug!(target: COMPONENT, txs = %format_array(txs.iter().map(|tx| tx.id().to_hex())));
+
+        // The semaphore is fair, so the batches are dispatched in the order they were submitted.
+        // The batches of the priority lane, bounded by its capacity, don't wait for a slot.
+        let _permit = match lane {
+            Lane::Priority => None,
+            Lane::User => {
+                Some(self.proving_slots.acquire().await.expect("proving slots are never closed"))
+            },
+        };
+        let key = self.pipeline.write().await.dispatch(lane, PendingBatch::new(&txs));
+
+        // Proving is CPU bound, it runs on a blocking thread to keep serving requests meanwhile
+        let proving_started = Instant::now();
+        let batch = tokio::task::spawn_blocking(move || TransactionBatch::new(txs))
+            .await
+            .unwrap_or_else(|err| panic::resume_unwind(err.into_panic()));
+        self.batch_sizing.record_proving(proving_started.elapsed());
+
+        let batch = match batch {
+            Ok(batch) => batch,
+            Err(err) => {
+                self.pipeline.write().await.cancel(key);
+                return Err(err);
+            },
+        };
+
+        info!(target: COMPONENT, sequence_number = key.1, "Transaction batch built");
+        Span::current().record("batch_id", format_blake3_digest(batch.id()));
+
+        let num_ready = {
+            let mut pipeline = self.pipeline.write().await;
+            pipeline.complete(key, batch);
+            pipeline.num_ready()
+        };
+
+        info!(target: COMPONENT, num_ready, "Transaction batch added to the batch pipeline");
+
+        Ok(())
+    }
 }
 
 // FIXME: remove the allow when the upstream clippy issue is fixed:
@@ -122,26 +249,297 @@ impl<BB> BatchBuilder for DefaultBatchBuilder<BB>
 where
     BB: BlockBuilder,
 {
-    #[instrument(target = "miden-block-producer", skip_all, err, fields(batch_id))]
     async fn build_batch(&self, txs: Vec<ProvenTransaction>) -> Result<(), BuildBatchError> {
-        let num_txs = txs.len();
+        self.build_lane_batch(txs, Lane::User).await
+    }
 
-        info!(target: COMPONENT, num_txs, "Building a transaction batch");
-        debug!(target: COMPONENT, txs = %format_array(txs.iter().map(|tx| tx.id().to_hex())));
+    async fn build_priority_batch(
+        &self,
+        txs: Vec<ProvenTransaction>,
+    ) -> Result<(), BuildBatchError> {
+        self.build_lane_batch(txs, Lane::Priority).await
+    }
+}
 
-        let batch = TransactionBatch::new(txs)?;
+// BLOCK PRODUCTION
+// ================================================================================================
 
-        info!(target: COMPONENT, "Transaction batch built");
-        Span::current().record("batch_id", format_blake3_digest(batch.id()));
+/// Pauses and resumes the production of blocks.
+///
+/// While paused, transactions are still accepted and batches are still proven, they are included
+/// in blocks once the production is resumed.
+#[derive(Debug, Default)]
+pub struct BlockProduction {
+    paused: Mutex<bool>,
+}
+
+impl BlockProduction {
+    /// Pauses the production of blocks.
+    ///
+    /// Waits for the block being built, if any, so no block is applied to the store once this
+    /// returns.
+    pub async fn pause(&self) {
+        *self.paused.lock().await = true;
+    }
+
+    /// Resumes the production of blocks, starting at the next block period.
+    pub async fn resume(&self) {
+        *self.paused.lock().await = false;
+    }
+
+    /// Returns `true` if the production of blocks is paused.
+    pub async fn is_paused(&self) -> bool {
+        *self.paused.lock().await
+    }
+}
+
+// BATCH LOOKUP
+// ================================================================================================
+
+/// Looks up the batches being proven or waiting to be included in a block, e.g. to find out why a
+/// batch is stuck.
+#[derive(Debug, Clone)]
+pub struct BatchLookup {
+    pipeline: Arc<RwLock<BatchPipeline>>,
+}
 
-        let num_batches = {
-            let mut write_guard = self.ready_batches.write().await;
-            write_guard.push(batch);
-            write_guard.len()
+impl BatchLookup {
+    /// Returns the batch `batch_id`, [None] if it isn't in the pipeline, i.e. it is unknown, its
+    /// proving failed or it was included in a block.
+    pub async fn get(&self, batch_id: BatchId) -> Option<BatchInfo> {
+        self.pipeline.read().await.get(batch_id)
+    }
+}
+
+/// State of a batch in the pipeline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BatchStatus {
+    /// The batch is being proven, or waiting for a proving slot
+    Proving,
+    /// The batch is proven and waiting to be included in a block
+    Proven,
+}
+
+/// A batch being proven or waiting to be included in a block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchInfo {
+    pub id: BatchId,
+    pub status: BatchStatus,
+    /// The effects of each transaction in the batch
+    pub transactions: Vec<TransactionSummary>,
+    /// Root of the tree of the notes created by the batch, [None] until the batch is proven
+    pub note_root: Option<Digest>,
+    /// Number of batches dispatched before this one and not yet included in a block. The batch
+    /// is included in a block once all of them are.
+    pub batches_ahead: usize,
+}
+
+impl From<BatchInfo> for GetBatchResponse {
+    fn from(batch: BatchInfo) -> Self {
+        Self {
+            proven: batch.status == BatchStatus::Proven,
+            transactions: batch
+                .transactions
+                .iter()
+                .map(|tx| BatchTransaction {
+                    transaction_id: Some(tx.id.into()),
+                    account_id: Some(tx.account_id.into()),
+                    initial_account_hash: Some(tx.initial_account_hash.into()),
+                    final_account_hash: Some(tx.final_account_hash.into()),
+                    num_output_notes: tx.output_notes.len() as u32,
+                    num_input_notes: tx.input_nullifiers.len() as u32,
+                })
+                .collect(),
+            note_root: batch.note_root.map(Into::into),
+            batches_ahead: batch.batches_ahead as u32,
+        }
+    }
+}
+
+// BATCH PIPELINE
+// ================================================================================================
+
+/// Position of a batch in the pipeline, its lane and the order in which it was dispatched.
+type PipelineKey = (Lane, u64);
+
+/// Batches dispatched for proving, keyed by their lane and the order in which they were
+/// dispatched.
+///
+/// Batches can finish proving in any order, but they are handed to the block builder in dispatch
+/// order: a batch is only taken once all the batches of its lane dispatched before it have been
+/// taken. The batches of the priority lane are taken ahead of the user batches.
+#[derive(Debug, Default)]
+struct BatchPipeline {
+    next_sequence_number: u64,
+
+    batches: BTreeMap<PipelineKey, PipelineBatch>,
+
+    /// Time each batch was dispatched, until its block is committed
+    dispatched_at: BTreeMap<PipelineKey, Instant>,
+}
+
+#[derive(Debug)]
+enum PipelineBatch {
+    Proving(PendingBatch),
+    Proven(TransactionBatch),
+}
+
+impl PipelineBatch {
+    fn id(&self) -> BatchId {
+        match self {
+            Self::Proving(batch) => batch.id,
+            Self::Proven(batch) => batch.id(),
+        }
+    }
+}
+
+/// A batch being proven, described by its transactions.
+#[derive(Debug)]
+struct PendingBatch {
+    id: BatchId,
+    transactions: Vec<TransactionSummary>,
+}
+
+impl PendingBatch {
+    fn new(txs: &[ProvenTransaction]) -> Self {
+        Self {
+            id: TransactionBatch::compute_id(txs),
+            transactions: txs.iter().map(TransactionSummary::from).collect(),
+        }
+    }
+}
+
+impl BatchPipeline {
+    /// Registers a new batch of `lane` being proven, and returns its key.
+    fn dispatch(&mut self, lane: Lane, batch: PendingBatch) -> PipelineKey {
+        let key = (lane, self.next_sequence_number);
+        self.next_sequence_number += 1;
+        self.batches.insert(key, PipelineBatch::Proving(batch));
+        self.dispatched_at.insert(key, Instant::now());
+
+        key
+    }
+
+    /// Marks the batch `key` as proven.
+    fn complete(&mut self, key: PipelineKey, batch: TransactionBatch) {
+        self.batches.insert(key, PipelineBatch::Proven(batch));
+    }
+
+    /// Removes the batch `key`, whose proving failed.
+    fn cancel(&mut self, key: PipelineKey) {
+        self.batches.remove(&key);
+        self.dispatched_at.remove(&key);
+    }
+
+    /// Forgets the batches `keys` taken by [Self::take_ready], whose block was committed, and
+    /// returns the time elapsed since each was dispatched.
+    fn commit(&mut self, keys: &[PipelineKey]) -> Vec<Duration> {
+        keys.iter()
+            .filter_map(|key| self.dispatched_at.remove(key))
+            .map(|dispatched_at| dispatched_at.elapsed())
+            .collect()
+    }
+
+    /// Removes and returns up to `max_batches` proven batches, the priority batches first, then the
+    /// user batches whose estimated cycles add up to at most `max_cycles`, each lane in dispatch
+    /// order.
+    ///
+    /// Stops at the first batch of a lane still being proven, so that no batch is taken before an
+    /// earlier one of its lane. The user batches dispatched after a priority batch being proven
+    /// wait for it, as they may build on its transactions. A batch above `max_cycles` is taken
+    /// alone, so that it doesn't hold back the pipeline.
+    fn take_ready(
+        &mut self,
+        max_batches: usize,
+        max_cycles: Option<u64>,
+    ) -> Vec<(PipelineKey, TransactionBatch)> {
+        let mut keys = Vec::new();
+        let mut cycles = 0;
+        // Sequence number of the first priority batch still being proven
+        let mut proving_priority = None;
+        for (&key, batch) in &self.batches {
+            if keys.len() == max_batches {
+                break;
+            }
+
+            let (lane, sequence_number) = key;
+            match (lane, batch) {
+                (Lane::Priority, PipelineBatch::Proving(_)) => {
+                    proving_priority.get_or_insert(sequence_number);
+                },
+                (Lane::Priority, PipelineBatch::Proven(batch)) => {
+                    if proving_priority.is_none() {
+                        cycles += batch.cycles();
+                        keys.push(key);
+                    }
+                },
+                (Lane::User, PipelineBatch::Proving(_)) => break,
+                (Lane::User, PipelineBatch::Proven(batch)) => {
+                    if proving_priority.is_some_and(|proving| proving < sequence_number) {
+                        break;
+                    }
+                    let batch_cycles = batch.cycles();
+                    if !keys.is_empty() && max_cycles.is_some_and(|max| cycles + batch_cycles > max)
+                    {
+                        break;
+                    }
+                    cycles += batch_cycles;
+                    keys.push(key);
+                },
+            }
+        }
+
+        keys.into_iter()
+            .map(|key| match self.batches.remove(&key) {
+                Some(PipelineBatch::Proven(batch)) => (key, batch),
+                _ => unreachable!("only proven batches are taken"),
+            })
+            .collect()
+    }
+
+    /// Puts back batches previously returned by [Self::take_ready].
+    fn restore(&mut self, batches: impl IntoIterator<Item = (PipelineKey, TransactionBatch)>) {
+        for (key, batch) in batches {
+            self.batches.insert(key, PipelineBatch::Proven(batch));
+        }
+    }
+
+    /// Returns the number of proven batches, including the ones waiting for an earlier batch.
+    fn num_ready(&self) -> usize {
+        self.batches
+            .values()
+            .filter(|batch| matches!(batch, PipelineBatch::Proven(_)))
+            .count()
+    }
+
+    /// Returns the batch `batch_id`, [None] if it isn't in the pipeline.
+    fn get(&self, batch_id: BatchId) -> Option<BatchInfo> {
+        let (batches_ahead, batch) =
+            self.batches.values().enumerate().find(|(_, batch)| batch.id() == batch_id)?;
+
+        let info = match batch {
+            PipelineBatch::Proving(batch) => BatchInfo {
+                id: batch.id,
+                status: BatchStatus::Proving,
+                transactions: batch.transactions.clone(),
+                note_root: None,
+                batches_ahead,
+            },
+            PipelineBatch::Proven(batch) => BatchInfo {
+                id: batch.id(),
+                status: BatchStatus::Proven,
+                transactions: batch.transactions().to_vec(),
+                note_root: Some(batch.created_notes_root()),
+                batches_ahead,
+            },
         };
 
-        info!(target: COMPONENT, num_batches, "Transaction batch added to the batch queue");
+        Some(info)
+    }
 
-        Ok(())
+    /// Returns the number of batches in the pipeline, proven or not.
+    #[cfg(test)]
+    fn len(&self) -> usize {
+        self.batches.len()
     }
 }
@@ -0,0 +1,242 @@
+//! Adjusts the number of transactions the batches are sealed at to the load, see
+//! [AdaptiveBatchSize].
+use std::{sync::Mutex, time::Duration};
+
+use tracing::info;
+
+use crate::{
+    config::{AdaptiveBatchSize, BatchSealingPolicy},
+    COMPONENT,
+};
+
+/// Weight of the latest measurement in the moving averages of the latencies.
+const LATENCY_SMOOTHING: f64 = 0.2;
+
+/// Sizes the batches sealed by the transaction queue, from the latencies measured by the batch
+/// builder.
+///
+/// The batches are sealed at the `max_txs` transactions of the [BatchSealingPolicy] unless it is
+/// adaptive, the latencies are measured either way.
+#[derive(Debug)]
+pub struct BatchSizing {
+    policy: BatchSealingPolicy,
+    state: Mutex<SizingState>,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct SizingState {
+    target_txs: usize,
+    /// Moving average of the time the transactions wait in the queue before being batched
+    queue_wait: Option<Duration>,
+    /// Moving average of the time a batch takes to be proven
+    proving: Option<Duration>,
+    /// Moving average of the time from the dispatch of a batch to the commit of its block
+    pipeline: Option<Duration>,
+}
+
+/// Controller state reported by the block producer's status.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct BatchSizingStatus {
+    /// Number of transactions the batches are currently sealed at
+    pub target_txs: usize,
+    /// Moving average of the time a batch takes to be proven
+    pub proving_latency: Duration,
+    /// Estimated time from the acceptance of a transaction to the commit of its block
+    pub confirmation_latency: Duration,
+}
+
+impl BatchSizing {
+    /// Returns a controller sealing the batches according to `policy`, starting at its `max_txs`.
+    pub fn new(policy: BatchSealingPolicy) -> Self {
+        let state = SizingState {
+            target_txs: policy.max_txs.max(1),
+            ..Default::default()
+        };
+
+        Self { policy, state: Mutex::new(state) }
+    }
+
+    /// Returns the sealing policy of the next batches, sealed at the current target size.
+    pub fn sealing_policy(&self) -> BatchSealingPolicy {
+        BatchSealingPolicy {
+            max_txs: self.target_batch_size(),
+            ..self.policy
+        }
+    }
+
+    /// Returns the number of transactions the batches are currently sealed at.
+    pub fn target_batch_size(&self) -> usize {
+        self.state().target_txs
+    }
+
+    /// Records that the transactions of a batch waited up to `wait` in the queue.
+    pub fn record_queue_wait(&self, wait: Duration) {
+        let mut state = self.state.lock().expect("batch sizing lock poisoned");
+        state.queue_wait = Some(smooth(state.queue_wait, wait));
+    }
+
+    /// Records that a batch took `latency` to be proven.
+    pub fn record_proving(&self, latency: Duration) {
+        let mut state = self.state.lock().expect("batch sizing lock poisoned");
+        state.proving = Some(smooth(state.proving, latency));
+    }
+
+    /// Records the commit of a block whose batches were dispatched `latencies` ago, and adjusts
+    /// the target size of the batches.
+    ///
+    /// `full` is set if proven batches were left out of the block by its limits.
+    pub fn record_block(&self, latencies: impl IntoIterator<Item = Duration>, full: bool) {
+        let mut state = self.state.lock().expect("batch sizing lock poisoned");
+
+        let mut latencies = latencies.into_iter().peekable();
+        // Empty blocks tell nothing about the load
+        if latencies.peek().is_none() {
+            return;
+        }
+        for latency in latencies {
+            state.pipeline = Some(smooth(state.pipeline, latency));
+        }
+
+        let Some(adaptive) = self.policy.adaptive else {
+            return;
+        };
+
+        let target_txs = adjust(&state, &adaptive, self.policy.max_txs, full);
+        if target_txs != state.target_txs {
+            info!(
+                target: COMPONENT,
+                from = state.target_txs,
+                to = target_txs,
+                full,
+                confirmation_latency_ms = state.confirmation_latency().as_millis(),
+                "Batch size adjusted"
+            );
+            state.target_txs = target_txs;
+        }
+    }
+
+    /// Returns the state of the controller.
+    pub fn status(&self) -> BatchSizingStatus {
+        let state = self.state();
+
+        BatchSizingStatus {
+            target_txs: state.target_txs,
+            proving_latency: state.proving.unwrap_or_default(),
+            confirmation_latency: state.confirmation_latency(),
+        }
+    }
+
+    fn state(&self) -> SizingState {
+        *self.state.lock().expect("batch sizing lock poisoned")
+    }
+}
+
+impl SizingState {
+    fn confirmation_latency(&self) -> Duration {
+        self.queue_wait.unwrap_or_default() + self.pipeline.unwrap_or_default()
+    }
+}
+
+/// Returns the target size of the batches after a block was committed.
+///
+/// The batches grow by a quarter while the blocks are full and the batches are proven within the
+/// target latency, and shrink by a quarter while the transactions are confirmed later than the
+/// target, between `adaptive.min_txs` and `max_txs`.
+fn adjust(state: &SizingState, adaptive: &AdaptiveBatchSize, max_txs: usize, full: bool) -> usize {
+    let max_txs = max_txs.max(1);
+    let min_txs = adaptive.min_txs.clamp(1, max_txs);
+    let step = (state.target_txs / 4).max(1);
+    let target_latency = adaptive.target_latency();
+
+    let target_txs = if full && state.proving.unwrap_or_default() < target_latency {
+        state.target_txs + step
+    } else if state.confirmation_latency() > target_latency {
+        state.target_txs.saturating_sub(step)
+    } else {
+        state.target_txs
+    };
+
+    target_txs.clamp(min_txs, max_txs)
+}
+
+/// Returns the moving average `average` updated with `sample`.
+fn smooth(average: Option<Duration>, sample: Duration) -> Duration {
+    match average {
+        Some(average) => {
+            average.mul_f64(1.0 - LATENCY_SMOOTHING) + sample.mul_f64(LATENCY_SMOOTHING)
+        },
+        None => sample,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn adaptive_policy(
+        min_txs: usize,
+        max_txs: usize,
+        target_latency_ms: u64,
+    ) -> BatchSealingPolicy {
+        BatchSealingPolicy {
+            max_txs,
+            adaptive: Some(AdaptiveBatchSize { min_txs, target_latency_ms }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn batches_shrink_while_the_confirmation_is_late() {
+        let sizing = BatchSizing::new(adaptive_policy(2, 16, 1000));
+        assert_eq!(sizing.target_batch_size(), 16);
+
+        sizing.record_queue_wait(Duration::from_millis(500));
+        sizing.record_proving(Duration::from_millis(800));
+        sizing.record_block([Duration::from_millis(1500)], false);
+        assert_eq!(sizing.target_batch_size(), 12);
+        assert_eq!(sizing.sealing_policy().max_txs, 12);
+
+        let status = sizing.status();
+        assert_eq!(status.proving_latency, Duration::from_millis(800));
+        assert_eq!(status.confirmation_latency, Duration::from_millis(2000));
+
+        for _ in 0..20 {
+            sizing.record_block([Duration::from_millis(1500)], false);
+        }
+        assert_eq!(sizing.target_batch_size(), 2);
+    }
+
+    #[test]
+    fn batches_grow_while_the_blocks_are_full() {
+        let sizing = BatchSizing::new(adaptive_policy(1, 8, 1000));
+        sizing.record_queue_wait(Duration::from_millis(2000));
+        for _ in 0..10 {
+            sizing.record_block([Duration::from_millis(2000)], false);
+        }
+        assert_eq!(sizing.target_batch_size(), 1);
+
+        // Full blocks while the batches are proven quickly
+        sizing.record_proving(Duration::from_millis(100));
+        sizing.record_block([Duration::from_millis(2000)], true);
+        assert_eq!(sizing.target_batch_size(), 2);
+        for _ in 0..10 {
+            sizing.record_block([Duration::from_millis(2000)], true);
+        }
+        assert_eq!(sizing.target_batch_size(), 8);
+
+        // Empty blocks don't change the size
+        sizing.record_queue_wait(Duration::from_secs(60));
+        sizing.record_block([], false);
+        assert_eq!(sizing.target_batch_size(), 8);
+    }
+
+    #[test]
+    fn batches_keep_their_size_if_not_adaptive() {
+        let sizing = BatchSizing::new(BatchSealingPolicy { max_txs: 4, ..Default::default() });
+        sizing.record_queue_wait(Duration::from_secs(10));
+        sizing.record_block([Duration::from_secs(10)], false);
+
+        assert_eq!(sizing.target_batch_size(), 4);
+        assert_eq!(sizing.status().confirmation_latency, Duration::from_secs(20));
+    }
+}
@@ -1,5 +1,9 @@
 use super::*;
-use crate::{errors::BuildBlockError, test_utils::MockProvenTxBuilder};
+use crate::{
+    errors::{BuildBlockError, StoreClientError},
+    test_utils::MockProvenTxBuilder,
+    SharedRwVec,
+};
 
 // STRUCTS
 // ================================================================================================
@@ -33,6 +37,22 @@ impl BlockBuilder for BlockBuilderFailure {
     }
 }
 
+/// Fails to build the blocks as if the store rejected them, counting the attempts
+#[derive(Default)]
+struct BlockBuilderRejected {
+    num_attempts: Arc<RwLock<usize>>,
+}
+
+#[async_trait]
+impl BlockBuilder for BlockBuilderRejected {
+    async fn build_block(&self, _batches: &[TransactionBatch]) -> Result<(), BuildBlockError> {
+        *self.num_attempts.write().await += 1;
+        Err(BuildBlockError::ApplyBlockFailed(
+            StoreClientError::Rejected("invalid block".to_string()).into(),
+        ))
+    }
+}
+
 // TESTS
 // ================================================================================================
 
@@ -47,15 +67,19 @@ async fn test_block_size_doesnt_exceed_limit() {
 
     let batch_builder = Arc::new(DefaultBatchBuilder::new(
         block_builder.clone(),
-        DefaultBatchBuilderOptions { block_frequency, max_batches_per_block },
+        DefaultBatchBuilderOptions {
+            block_frequency,
+            max_batches_per_block,
+            max_in_flight_batches: 2,
+            max_cycles_per_block: None,
+        },
     ));
 
     // Add 3 batches in internal queue (remember: 2 batches/block)
     {
-        let mut batch_group =
-            vec![dummy_tx_batch(0, 2), dummy_tx_batch(10, 2), dummy_tx_batch(20, 2)];
+        let batch_group = vec![dummy_tx_batch(0, 2), dummy_tx_batch(10, 2), dummy_tx_batch(20, 2)];
 
-        batch_builder.ready_batches.write().await.append(&mut batch_group);
+        add_ready_batches(&mut *batch_builder.pipeline.write().await, batch_group);
     }
 
     // start batch builder
@@ -85,7 +109,12 @@ async fn test_build_block_called_when_no_batches() {
 
     let batch_builder = Arc::new(DefaultBatchBuilder::new(
         block_builder.clone(),
-        DefaultBatchBuilderOptions { block_frequency, max_batches_per_block },
+        DefaultBatchBuilderOptions {
+            block_frequency,
+            max_batches_per_block,
+            max_in_flight_batches: 2,
+            max_cycles_per_block: None,
+        },
     ));
 
     // start batch builder
@@ -110,17 +139,21 @@ async fn test_batches_added_back_to_queue_on_block_build_failure() {
 
     let batch_builder = Arc::new(DefaultBatchBuilder::new(
         block_builder.clone(),
-        DefaultBatchBuilderOptions { block_frequency, max_batches_per_block },
+        DefaultBatchBuilderOptions {
+            block_frequency,
+            max_batches_per_block,
+            max_in_flight_batches: 2,
+            max_cycles_per_block: None,
+        },
     ));
 
-    let internal_ready_batches = batch_builder.ready_batches.clone();
+    let internal_pipeline = batch_builder.pipeline.clone();
 
     // Add 3 batches in internal queue
     {
-        let mut batch_group =
-            vec![dummy_tx_batch(0, 2), dummy_tx_batch(10, 2), dummy_tx_batch(20, 2)];
+        let batch_group = vec![dummy_tx_batch(0, 2), dummy_tx_batch(10, 2), dummy_tx_batch(20, 2)];
 
-        batch_builder.ready_batches.write().await.append(&mut batch_group);
+        add_ready_batches(&mut *batch_builder.pipeline.write().await, batch_group);
     }
 
     // start batch builder
@@ -130,12 +163,251 @@ async fn test_batches_added_back_to_queue_on_block_build_failure() {
     time::sleep(block_frequency * 2 + (block_frequency / 2)).await;
 
     // Ensure the transaction batches are all still on the queue
-    assert_eq!(internal_ready_batches.read().await.len(), 3);
+    assert_eq!(internal_pipeline.read().await.len(), 3);
+}
+
+/// Tests that the production of blocks halts when the store rejects a block, keeping the batches
+#[tokio::test]
+#[miden_node_test_macro::enable_logging]
+async fn test_block_production_halts_when_store_rejects_block() {
+    let block_frequency = Duration::from_millis(20);
+
+    let block_builder = Arc::new(BlockBuilderRejected::default());
+
+    let batch_builder = Arc::new(DefaultBatchBuilder::new(
+        block_builder.clone(),
+        DefaultBatchBuilderOptions {
+            block_frequency,
+            max_batches_per_block: 2,
+            max_in_flight_batches: 2,
+            max_cycles_per_block: None,
+        },
+    ));
+    let production = batch_builder.production();
+
+    add_ready_batches(&mut *batch_builder.pipeline.write().await, vec![dummy_tx_batch(0, 2)]);
+
+    tokio::spawn(batch_builder.clone().run());
+    time::sleep(block_frequency * 3).await;
+
+    assert!(production.is_paused().await);
+    assert_eq!(*block_builder.num_attempts.read().await, 1);
+    assert_eq!(batch_builder.pipeline.read().await.len(), 1);
+}
+
+/// Tests that no block is built while the production is paused, and that the batches are kept
+#[tokio::test]
+#[miden_node_test_macro::enable_logging]
+async fn test_no_block_built_while_paused() {
+    let block_frequency = Duration::from_millis(20);
+
+    let block_builder = Arc::new(BlockBuilderSuccess::default());
+
+    let batch_builder = Arc::new(DefaultBatchBuilder::new(
+        block_builder.clone(),
+        DefaultBatchBuilderOptions {
+            block_frequency,
+            max_batches_per_block: 2,
+            max_in_flight_batches: 2,
+            max_cycles_per_block: None,
+        },
+    ));
+    let production = batch_builder.production();
+    production.pause().await;
+
+    add_ready_batches(&mut *batch_builder.pipeline.write().await, vec![dummy_tx_batch(0, 2)]);
+
+    tokio::spawn(batch_builder.clone().run());
+    time::sleep(block_frequency * 3).await;
+
+    assert!(block_builder.batch_groups.read().await.is_empty());
+    assert_eq!(*block_builder.num_empty_batches_received.read().await, 0);
+    assert_eq!(batch_builder.pipeline.read().await.len(), 1);
+
+    // The batches are included in a block once the production is resumed
+    production.resume().await;
+    time::sleep(block_frequency * 2).await;
+
+    assert_eq!(block_builder.batch_groups.read().await.len(), 1);
+}
+
+/// Tests that batches completing out of order are handed to the block builder in dispatch order
+#[test]
+fn test_pipeline_takes_batches_in_dispatch_order() {
+    let mut pipeline = BatchPipeline::default();
+    let batches = [dummy_tx_batch(0, 1), dummy_tx_batch(10, 1), dummy_tx_batch(20, 1)];
+    let sequence_numbers =
+        batches.each_ref().map(|batch| pipeline.dispatch(Lane::User, pending(batch)));
+
+    // The later batches are proven first, they wait for the first batch
+    pipeline.complete(sequence_numbers[2], batches[2].clone());
+    pipeline.complete(sequence_numbers[1], batches[1].clone());
+    assert_eq!(pipeline.num_ready(), 2);
+    assert!(pipeline.take_ready(3, None).is_empty());
+
+    pipeline.complete(sequence_numbers[0], batches[0].clone());
+    let taken = pipeline.take_ready(2, None);
+    assert_eq!(batch_ids(&taken), [batches[0].id(), batches[1].id()]);
+
+    // Batches put back after a failed block are taken again first
+    pipeline.restore(taken);
+    let taken = pipeline.take_ready(3, None);
+    assert_eq!(batch_ids(&taken), [batches[0].id(), batches[1].id(), batches[2].id()]);
+    assert_eq!(pipeline.len(), 0);
+}
+
+/// Tests that a batch whose proving failed doesn't hold back the following batches
+#[test]
+fn test_pipeline_skips_cancelled_batches() {
+    let mut pipeline = BatchPipeline::default();
+    let batch = dummy_tx_batch(0, 1);
+    let failed = pipeline.dispatch(Lane::User, pending(&dummy_tx_batch(10, 1)));
+    let proven = pipeline.dispatch(Lane::User, pending(&batch));
+
+    pipeline.complete(proven, batch.clone());
+    assert!(pipeline.take_ready(1, None).is_empty());
+
+    pipeline.cancel(failed);
+    assert_eq!(batch_ids(&pipeline.take_ready(1, None)), [batch.id()]);
+}
+
+/// Tests that the batches taken into a block stay within its cycle budget
+#[test]
+fn test_pipeline_takes_batches_within_cycle_budget() {
+    let mut pipeline = BatchPipeline::default();
+    let batches = vec![dummy_tx_batch(0, 2), dummy_tx_batch(10, 1), dummy_tx_batch(20, 3)];
+    let ids: Vec<_> = batches.iter().map(TransactionBatch::id).collect();
+    let tx_cycles = batches[1].cycles();
+    assert_eq!(batches[0].cycles(), 2 * tx_cycles);
+    add_ready_batches(&mut pipeline, batches);
+
+    // The second batch would take the block over its budget
+    let taken = pipeline.take_ready(3, Some(2 * tx_cycles));
+    assert_eq!(batch_ids(&taken), [ids[0]]);
+
+    let taken = pipeline.take_ready(3, Some(3 * tx_cycles));
+    assert_eq!(batch_ids(&taken), [ids[1]]);
+
+    // A batch above the budget is taken alone
+    let taken = pipeline.take_ready(3, Some(tx_cycles));
+    assert_eq!(batch_ids(&taken), [ids[2]]);
+}
+
+/// Tests that the priority batches are taken ahead of the user batches, the user batches
+/// dispatched after a priority batch waiting for it
+#[test]
+fn test_pipeline_takes_priority_batches_first() {
+    let mut pipeline = BatchPipeline::default();
+    let batches = [
+        dummy_tx_batch(0, 1),
+        dummy_tx_batch(10, 1),
+        dummy_tx_batch(20, 1),
+        dummy_tx_batch(30, 1),
+    ];
+    let user = pipeline.dispatch(Lane::User, pending(&batches[0]));
+    let priority = pipeline.dispatch(Lane::Priority, pending(&batches[1]));
+    let late_user = pipeline.dispatch(Lane::User, pending(&batches[2]));
+    pipeline.complete(user, batches[0].clone());
+    pipeline.complete(late_user, batches[2].clone());
+
+    // The user batch dispatched after the priority batch being proven may build on it
+    assert_eq!(batch_ids(&pipeline.take_ready(3, None)), [batches[0].id()]);
+
+    pipeline.complete(priority, batches[1].clone());
+    let late_priority = pipeline.dispatch(Lane::Priority, pending(&batches[3]));
+    pipeline.complete(late_priority, batches[3].clone());
+    assert_eq!(pipeline.get(batches[3].id()).unwrap().batches_ahead, 1);
+
+    // The priority batches are taken regardless of the cycle budget, the user batch waits
+    let taken = pipeline.take_ready(3, Some(batches[1].cycles()));
+    assert_eq!(batch_ids(&taken), [batches[1].id(), batches[3].id()]);
+    assert_eq!(batch_ids(&pipeline.take_ready(3, None)), [batches[2].id()]);
+}
+
+/// Tests that the batches are looked up until they are taken into a block
+#[test]
+fn test_pipeline_looks_up_batches() {
+    let mut pipeline = BatchPipeline::default();
+    let batches = [dummy_tx_batch(0, 2), dummy_tx_batch(10, 1)];
+    let sequence_numbers =
+        batches.each_ref().map(|batch| pipeline.dispatch(Lane::User, pending(batch)));
+
+    let proving = pipeline.get(batches[0].id()).unwrap();
+    assert_eq!(proving.status, BatchStatus::Proving);
+    assert_eq!(proving.transactions, batches[0].transactions());
+    assert_eq!(proving.note_root, None);
+    assert_eq!(proving.batches_ahead, 0);
+
+    pipeline.complete(sequence_numbers[1], batches[1].clone());
+    let proven = pipeline.get(batches[1].id()).unwrap();
+    assert_eq!(proven.status, BatchStatus::Proven);
+    assert_eq!(proven.note_root, Some(batches[1].created_notes_root()));
+    assert_eq!(proven.batches_ahead, 1);
+
+    pipeline.complete(sequence_numbers[0], batches[0].clone());
+    pipeline.take_ready(1, None);
+    assert_eq!(pipeline.get(batches[0].id()), None);
+    assert_eq!(pipeline.get(batches[1].id()).unwrap().batches_ahead, 0);
+}
+
+/// Tests that batches built concurrently are all added to the pipeline, in submission order
+#[tokio::test]
+#[miden_node_test_macro::enable_logging]
+async fn test_concurrent_batches_are_added_in_order() {
+    let batch_builder = Arc::new(DefaultBatchBuilder::new(
+        Arc::new(BlockBuilderSuccess::default()),
+        DefaultBatchBuilderOptions {
+            block_frequency: Duration::from_millis(20),
+            max_batches_per_block: 4,
+            max_in_flight_batches: 2,
+            max_cycles_per_block: None,
+        },
+    ));
+
+    let txs: Vec<_> = (0..4)
+        .map(|index| vec![MockProvenTxBuilder::with_account_index(index).build()])
+        .collect();
+    let expected: Vec<_> =
+        txs.iter().map(|txs| TransactionBatch::new(txs.clone()).unwrap().id()).collect();
+
+    let [txs0, txs1, txs2, txs3] = <[_; 4]>::try_from(txs).unwrap();
+    let results = tokio::join!(
+        batch_builder.build_batch(txs0),
+        batch_builder.build_batch(txs1),
+        batch_builder.build_batch(txs2),
+        batch_builder.build_batch(txs3),
+    );
+    results.0.unwrap();
+    results.1.unwrap();
+    results.2.unwrap();
+    results.3.unwrap();
+
+    let taken = batch_builder.pipeline.write().await.take_ready(4, None);
+    assert_eq!(batch_ids(&taken), expected);
+    assert_eq!(batch_builder.proving_slots.available_permits(), 2);
 }
 
 // HELPERS
 // ================================================================================================
 
+fn add_ready_batches(pipeline: &mut BatchPipeline, batches: Vec<TransactionBatch>) {
+    for batch in batches {
+        let sequence_number = pipeline.dispatch(Lane::User, pending(&batch));
+        pipeline.complete(sequence_number, batch);
+    }
+}
+
+fn pending(batch: &TransactionBatch) -> PendingBatch {
+    PendingBatch {
+        id: batch.id(),
+        transactions: batch.transactions().to_vec(),
+    }
+}
+
+fn batch_ids(batches: &[(PipelineKey, TransactionBatch)]) -> Vec<batch::BatchId> {
+    batches.iter().map(|(_, batch)| batch.id()).collect()
+}
+
 fn dummy_tx_batch(starting_account_index: u32, num_txs_in_batch: usize) -> TransactionBatch {
     let txs = (0..num_txs_in_batch)
         .map(|index| {
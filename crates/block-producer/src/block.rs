@@ -1,14 +1,17 @@
 use std::collections::BTreeMap;
 
 use miden_node_proto::{
-    domain::accounts::AccountUpdateDetails,
+    domain::{accounts::AccountUpdateDetails, transactions::TransactionReceipt},
     errors::{ConversionError, MissingFieldHelper},
     generated::responses::GetBlockInputsResponse,
     AccountInputRecord, NullifierWitness,
 };
 use miden_objects::{
     accounts::AccountId,
-    crypto::merkle::{MerklePath, MmrPeaks, SmtProof},
+    crypto::{
+        dsa::rpo_falcon512::Signature,
+        merkle::{MerklePath, MmrPeaks, SmtProof},
+    },
     notes::Nullifier,
     transaction::OutputNote,
     BlockHeader, Digest,
@@ -24,9 +27,14 @@ pub struct Block {
     pub updated_accounts: Vec<AccountUpdateDetails>,
     pub created_notes: Vec<NoteBatch>,
     pub produced_nullifiers: Vec<Nullifier>,
+    pub transactions: Vec<TransactionReceipt>,
+    /// Signature of the header's hash by the sequencer, [None] if the block producer doesn't sign
+    /// its blocks.
+    pub signature: Option<Signature>,
+    /// Validity proof of the block, [None] as the block producer doesn't prove its blocks yet.
+    pub proof: Option<Vec<u8>>,
     // TODO:
     // - full states for created public notes
-    // - zk proof
 }
 
 impl Block {
@@ -1,15 +1,21 @@
-use std::sync::Arc;
+use std::{borrow::Cow, sync::Arc};
 
 use async_trait::async_trait;
+use miden_node_proto::domain::transactions::TransactionReceipt;
 use miden_node_utils::formatting::{format_array, format_blake3_digest};
-use miden_objects::notes::Nullifier;
-use tracing::{debug, info, instrument};
+use miden_objects::{notes::Nullifier, BlockHeader};
+use tracing::{debug, info, instrument, warn};
 
 use crate::{
     batch_builder::batch::TransactionBatch,
     block::Block,
+    clock::TimeProvider,
     errors::BuildBlockError,
+    fees::Fees,
+    metering::BlockMetering,
+    signer::BlockSigner,
     store::{ApplyBlock, Store},
+    timeline::{BlockStage, BlockTimelines},
     COMPONENT,
 };
 
@@ -38,6 +44,13 @@ pub struct DefaultBlockBuilder<S, A> {
     store: Arc<S>,
     state_view: Arc<A>,
     block_kernel: BlockProver,
+    signer: Option<Arc<BlockSigner>>,
+    fees: Option<Arc<Fees>>,
+    metering: Option<Arc<BlockMetering>>,
+    timelines: Arc<BlockTimelines>,
+    /// Size of the proofs fabricated for the built blocks in simulation mode, [None] if the blocks
+    /// aren't proven
+    simulated_proof_size: Option<usize>,
 }
 
 impl<S, A> DefaultBlockBuilder<S, A>
@@ -50,8 +63,50 @@ where
             store,
             state_view,
             block_kernel: BlockProver::new(),
+            signer: None,
+            fees: None,
+            metering: None,
+            timelines: Default::default(),
+            simulated_proof_size: None,
         }
     }
+
+    /// Sets the source of the block timestamps, defaults to the system clock.
+    pub fn with_time_provider(mut self, time_provider: Arc<dyn TimeProvider>) -> Self {
+        self.block_kernel = self.block_kernel.with_time_provider(time_provider);
+        self
+    }
+
+    /// Signs the headers of the built blocks with `signer`, the blocks are unsigned by default.
+    pub fn with_signer(mut self, signer: Arc<BlockSigner>) -> Self {
+        self.signer = Some(signer);
+        self
+    }
+
+    /// Records the fees paid by the transactions, from `fees`, in their receipts.
+    pub fn with_fees(mut self, fees: Arc<Fees>) -> Self {
+        self.fees = Some(fees);
+        self
+    }
+
+    /// Records the estimated cycles of the built blocks to `metering`.
+    pub fn with_metering(mut self, metering: Arc<BlockMetering>) -> Self {
+        self.metering = Some(metering);
+        self
+    }
+
+    /// Records the stages reached by the built blocks to `timelines`.
+    pub fn with_timelines(mut self, timelines: Arc<BlockTimelines>) -> Self {
+        self.timelines = timelines;
+        self
+    }
+
+    /// Attaches a fabricated proof of `block_proof_size` bytes to the built blocks, to simulate
+    /// the load of the block proofs on the pipeline.
+    pub fn with_simulated_proofs(mut self, block_proof_size: usize) -> Self {
+        self.simulated_proof_size = Some(block_proof_size);
+        self
+    }
 }
 
 // FIXME: remove the allow when the upstream clippy issue is fixed:
@@ -63,14 +118,71 @@ where
     S: Store,
     A: ApplyBlock,
 {
+    /// Builds a block from `batches` and applies it.
+    ///
+    /// The batches conflicting with the state of the store, e.g. consuming notes already consumed,
+    /// can never be included in a block: they are excluded and the block is built again without
+    /// them. Their transactions are discarded once the block is applied.
     #[instrument(target = "miden-block-producer", skip_all, err)]
     async fn build_block(&self, batches: &[TransactionBatch]) -> Result<(), BuildBlockError> {
+        let mut included = Cow::Borrowed(batches);
+        let mut excluded = Vec::new();
+
+        while let Err(err) = self.try_build_block(&included).await {
+            let (offending, remaining): (Vec<_>, Vec<_>) =
+                included.iter().cloned().partition(|batch| is_offending(&err, batch));
+            if offending.is_empty() {
+                return Err(err);
+            }
+
+            warn!(
+                target: COMPONENT,
+                %err,
+                batches = %format_array(offending.iter().map(|batch| format_blake3_digest(batch.id()))),
+                "Building the block again without the offending batches"
+            );
+            excluded.extend(offending);
+            included = Cow::Owned(remaining);
+        }
+
+        if !excluded.is_empty() {
+            let tx_ids: Vec<_> = excluded
+                .iter()
+                .flat_map(|batch| batch.transactions().iter().map(|tx| tx.id))
+                .collect();
+            warn!(
+                target: COMPONENT,
+                transactions = %format_array(tx_ids.iter().map(|tx_id| tx_id.to_hex())),
+                "Transactions discarded"
+            );
+
+            self.state_view.discard_batches(&excluded).await;
+            if let Some(fees) = &self.fees {
+                fees.remove(tx_ids.iter());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<S, A> DefaultBlockBuilder<S, A>
+where
+    S: Store,
+    A: ApplyBlock,
+{
+    /// Builds a block from `batches` and applies it, fails if any of the batches can't be
+    /// included.
+    async fn try_build_block(&self, batches: &[TransactionBatch]) -> Result<(), BuildBlockError> {
         info!(
             target: COMPONENT,
             num_batches = batches.len(),
             batches = %format_array(batches.iter().map(|batch| format_blake3_digest(batch.id()))),
         );
 
+        let num_transactions = batches.iter().map(|batch| batch.transactions().len()).sum();
+        let mut timeline = self.timelines.start(batches.len(), num_transactions);
+
         let updated_accounts: Vec<_> =
             batches.iter().flat_map(TransactionBatch::updated_accounts).collect();
 
@@ -86,30 +198,103 @@ where
                 produced_nullifiers.iter(),
             )
             .await?;
+        timeline.set_block_num(block_inputs.block_header.block_num() + 1);
+        timeline.record(BlockStage::InputsFetched);
 
-        let block_header_witness = BlockWitness::new(block_inputs, batches)?;
+        let block_header_witness =
+            BlockWitness::new(block_inputs, batches).inspect_err(|err| timeline.fail(err))?;
 
-        let new_block_header = self.block_kernel.prove(block_header_witness)?;
+        let new_block_header = self
+            .block_kernel
+            .prove(block_header_witness)
+            .inspect_err(|err| timeline.fail(err))?;
+        timeline.record(BlockStage::BlockBuilt);
 
         let block_num = new_block_header.block_num();
 
+        let fees = self.fees.as_deref();
+        let transactions: Vec<_> = batches
+            .iter()
+            .enumerate()
+            .flat_map(|(batch_index, batch)| {
+                batch.transactions().iter().zip(batch.transaction_cycles()).map(
+                    move |(transaction, &cycles)| TransactionReceipt {
+                        transaction: transaction.clone(),
+                        block_num,
+                        batch_index: batch_index as u32,
+                        fee: fees.and_then(|fees| fees.paid_by(&transaction.id)),
+                        cycles,
+                    },
+                )
+            })
+            .collect();
+        let cycles: u64 = batches.iter().map(TransactionBatch::cycles).sum();
+
+        let signature = self.signer.as_ref().map(|signer| signer.sign(&new_block_header));
+
+        let proof = self
+            .simulated_proof_size
+            .map(|size| simulated_block_proof(&new_block_header, size));
+
         let block = Block {
             header: new_block_header,
             updated_accounts,
             created_notes,
             produced_nullifiers,
+            transactions,
+            signature,
+            proof,
         };
+        timeline.record(BlockStage::ProofDone);
 
         // TODO: Change to block.hash(), once it implemented
         let block_hash = block.header.hash();
 
-        info!(target: COMPONENT, block_num, %block_hash, "block built");
+        info!(target: COMPONENT, block_num, %block_hash, cycles, "block built");
         debug!(target: COMPONENT, ?block);
 
-        self.state_view.apply_block(&block).await?;
+        self.state_view
+            .apply_block(&block)
+            .await
+            .inspect_err(|err| timeline.fail(err))?;
+        timeline.record(BlockStage::Committed);
 
         info!(target: COMPONENT, block_num, %block_hash, "block committed");
 
+        if let Some(utilization_percent) =
+            self.metering.as_ref().and_then(|metering| metering.record(cycles))
+        {
+            info!(target: COMPONENT, block_num, cycles, utilization_percent, "block cycle budget used");
+        }
+
+        if let Some(fees) = &self.fees {
+            let collected = block.transactions.iter().filter_map(|receipt| receipt.fee);
+            let amount: u64 = collected.map(|fee| fee.amount).sum();
+            let operator = fees.operator();
+            info!(target: COMPONENT, block_num, %operator, amount, "fees collected");
+            fees.remove(block.transactions.iter().map(|receipt| &receipt.transaction.id));
+        }
+
         Ok(())
     }
 }
+
+/// Returns `true` if `err` blames `batch` for the failure of the block, i.e. the batch modifies
+/// an account from a state other than its state in the store, or consumes a note already consumed.
+fn is_offending(err: &BuildBlockError, batch: &TransactionBatch) -> bool {
+    match err {
+        BuildBlockError::InconsistentAccountStates(account_ids) => batch
+            .account_initial_states()
+            .any(|(account_id, _)| account_ids.contains(&account_id)),
+        BuildBlockError::NullifiersAlreadyConsumed(nullifiers) => {
+            batch.produced_nullifiers().any(|nullifier| nullifiers.contains(&nullifier))
+        },
+        _ => false,
+    }
+}
+
+/// Returns a fabricated proof of the block `header`, of `size` bytes: the hash of the header
+/// repeated, so that the proofs of different blocks differ.
+fn simulated_block_proof(header: &BlockHeader, size: usize) -> Vec<u8> {
+    header.hash().as_bytes().into_iter().cycle().take(size).collect()
+}
@@ -1,12 +1,12 @@
 use std::collections::{BTreeMap, BTreeSet};
 
-use miden_node_proto::domain::accounts::AccountUpdateDetails;
+use miden_node_utils::chain_params::{BLOCK_OUTPUT_NOTES_TREE_DEPTH, MAX_BATCHES_PER_BLOCK};
 use miden_objects::{
     accounts::AccountId,
     crypto::merkle::{EmptySubtreeRoots, MerklePath, MerkleStore, MmrPeaks, SmtProof},
     notes::Nullifier,
     vm::{AdviceInputs, StackInputs},
-    BlockHeader, Digest, Felt, BLOCK_OUTPUT_NOTES_TREE_DEPTH, MAX_BATCHES_PER_BLOCK, ZERO,
+    BlockHeader, Digest, Felt, EMPTY_WORD, ZERO,
 };
 
 use crate::{
@@ -37,8 +37,16 @@ impl BlockWitness {
         Self::validate_inputs(&block_inputs, batches)?;
 
         let updated_accounts = {
-            let mut account_initial_states: BTreeMap<AccountId, Digest> =
-                batches.iter().flat_map(TransactionBatch::account_initial_states).collect();
+            // An account modified in several batches starts from its state before the first one
+            // and ends in its state after the last one
+            let mut account_initial_states = BTreeMap::new();
+            for (account_id, initial_state_hash) in
+                batches.iter().flat_map(TransactionBatch::account_initial_states)
+            {
+                account_initial_states.entry(account_id).or_insert(initial_state_hash);
+            }
+            let account_final_states: BTreeMap<AccountId, Digest> =
+                batches.iter().flat_map(TransactionBatch::account_final_states).collect();
 
             let mut account_merkle_proofs: BTreeMap<AccountId, MerklePath> = block_inputs
                 .accounts
@@ -46,10 +54,9 @@ impl BlockWitness {
                 .map(|(account_id, witness)| (account_id, witness.proof))
                 .collect();
 
-            batches
-                .iter()
-                .flat_map(TransactionBatch::updated_accounts)
-                .map(|AccountUpdateDetails { account_id, final_state_hash, .. }| {
+            account_final_states
+                .into_iter()
+                .map(|(account_id, final_state_hash)| {
                     let initial_state_hash = account_initial_states
                         .remove(&account_id)
                         .expect("already validated that key exists");
@@ -113,37 +120,43 @@ impl BlockWitness {
     }
 
     /// Validates that initial account states coming from the batches are the same as the account
-    /// states returned from the store
+    /// states returned from the store, or as the final states of the previous batches modifying
+    /// the same accounts
     fn validate_account_states(
         block_inputs: &BlockInputs,
         batches: &[TransactionBatch],
     ) -> Result<(), BuildBlockError> {
-        let batches_initial_states: BTreeMap<AccountId, Digest> =
-            batches.iter().flat_map(|batch| batch.account_initial_states()).collect();
-
-        let accounts_in_batches: BTreeSet<AccountId> =
-            batches_initial_states.keys().cloned().collect();
+        let accounts_in_batches: BTreeSet<AccountId> = batches
+            .iter()
+            .flat_map(|batch| batch.account_initial_states())
+            .map(|(account_id, _)| account_id)
+            .collect();
         let accounts_in_store: BTreeSet<AccountId> =
             block_inputs.accounts.keys().copied().collect();
 
         if accounts_in_batches == accounts_in_store {
-            let accounts_with_different_hashes: Vec<AccountId> = block_inputs
+            let mut current_states: BTreeMap<AccountId, Digest> = block_inputs
                 .accounts
                 .iter()
-                .filter_map(|(account_id, witness)| {
-                    let hash_in_store = witness.hash;
-                    let hash_in_batches = batches_initial_states
-                        .get(account_id)
-                        .expect("we already verified that account id is contained in batches");
-
-                    if hash_in_store == *hash_in_batches {
-                        None
-                    } else {
-                        Some(*account_id)
-                    }
-                })
+                .map(|(account_id, witness)| (*account_id, witness.hash))
                 .collect();
 
+            let mut accounts_with_different_hashes = BTreeSet::new();
+            for batch in batches {
+                for (account_id, hash_in_batch) in batch.account_initial_states() {
+                    let current_hash = current_states
+                        .get(&account_id)
+                        .expect("we already verified that account id is contained in store");
+
+                    if *current_hash != hash_in_batch {
+                        accounts_with_different_hashes.insert(account_id);
+                    }
+                }
+                current_states.extend(batch.account_final_states());
+            }
+            let accounts_with_different_hashes: Vec<AccountId> =
+                accounts_with_different_hashes.into_iter().collect();
+
             if accounts_with_different_hashes.is_empty() {
                 Ok(())
             } else {
@@ -162,8 +175,9 @@ impl BlockWitness {
         }
     }
 
-    /// Validates that the nullifiers returned from the store are the same the produced nullifiers in the batches.
-    /// Note that validation that the value of the nullifiers is `0` will be done in MASM.
+    /// Validates that the nullifiers returned from the store are the same the produced nullifiers
+    /// in the batches, and that the store didn't record them as consumed. The value of the
+    /// nullifiers is validated again in MASM.
     fn validate_nullifiers(
         block_inputs: &BlockInputs,
         batches: &[TransactionBatch],
@@ -174,15 +188,28 @@ impl BlockWitness {
         let produced_nullifiers_from_batches: BTreeSet<Nullifier> =
             batches.iter().flat_map(|batch| batch.produced_nullifiers()).collect();
 
-        if produced_nullifiers_from_store == produced_nullifiers_from_batches {
-            Ok(())
-        } else {
+        if produced_nullifiers_from_store != produced_nullifiers_from_batches {
             let differing_nullifiers: Vec<Nullifier> = produced_nullifiers_from_store
                 .symmetric_difference(&produced_nullifiers_from_batches)
                 .copied()
                 .collect();
 
-            Err(BuildBlockError::InconsistentNullifiers(differing_nullifiers))
+            return Err(BuildBlockError::InconsistentNullifiers(differing_nullifiers));
+        }
+
+        let consumed_nullifiers: Vec<Nullifier> = block_inputs
+            .nullifiers
+            .iter()
+            .filter(|(nullifier, proof)| {
+                proof.get(&nullifier.inner()).is_some_and(|value| value != EMPTY_WORD)
+            })
+            .map(|(nullifier, _)| *nullifier)
+            .collect();
+
+        if consumed_nullifiers.is_empty() {
+            Ok(())
+        } else {
+            Err(BuildBlockError::NullifiersAlreadyConsumed(consumed_nullifiers))
         }
     }
 
@@ -1,11 +1,15 @@
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::sync::Arc;
 
+use miden_node_utils::chain_params::ACCOUNT_TREE_DEPTH;
 use miden_objects::{assembly::Assembler, BlockHeader, Digest, Felt};
 use miden_processor::{execute, DefaultHost, ExecutionOptions, MemAdviceProvider, Program};
 use miden_stdlib::StdLibrary;
 
 use self::block_witness::BlockWitness;
-use crate::errors::{BlockProverError, BuildBlockError};
+use crate::{
+    clock::{SystemClock, TimeProvider},
+    errors::{BlockProverError, BuildBlockError},
+};
 
 /// The index of the word at which the account root is stored on the output stack.
 pub const ACCOUNT_ROOT_WORD_IDX: usize = 0;
@@ -24,16 +28,22 @@ pub mod block_witness;
 #[cfg(test)]
 mod tests;
 
+/// Returns the source of the block kernel, with the constants set from the chain parameters.
+///
+/// The imports must precede the constants, they are not part of [BLOCK_KERNEL_MASM].
+fn block_kernel_source() -> String {
+    format!(
+        "use.std::collections::smt\nuse.std::collections::mmr\n\n\
+         const.ACCOUNT_TREE_DEPTH={ACCOUNT_TREE_DEPTH}\n{BLOCK_KERNEL_MASM}"
+    )
+}
+
 /// Note: For now, the "block kernel" only computes the account root. Eventually, it will compute
 /// the entire block header.
 ///
 /// Stack inputs: [num_accounts_updated, OLD_ACCOUNT_ROOT, NEW_ACCOUNT_HASH_0, account_id_0, ... ,
 /// NEW_ACCOUNT_HASH_n, account_id_n]
 const BLOCK_KERNEL_MASM: &str = "
-use.std::collections::smt
-use.std::collections::mmr
-
-const.ACCOUNT_TREE_DEPTH=64
 const.CHAIN_MMR_PTR=1000
 
 #! Compute the account root
@@ -189,6 +199,7 @@ end
 #[derive(Debug)]
 pub(crate) struct BlockProver {
     kernel: Program,
+    time_provider: Arc<dyn TimeProvider>,
 }
 
 impl BlockProver {
@@ -199,11 +210,20 @@ impl BlockProver {
                 .expect("failed to load std-lib");
 
             assembler
-                .compile(BLOCK_KERNEL_MASM)
+                .compile(block_kernel_source())
                 .expect("failed to load account update program")
         };
 
-        Self { kernel: account_program }
+        Self {
+            kernel: account_program,
+            time_provider: Arc::new(SystemClock),
+        }
+    }
+
+    /// Sets the source of the block timestamps, defaults to the system clock.
+    pub fn with_time_provider(mut self, time_provider: Arc<dyn TimeProvider>) -> Self {
+        self.time_provider = time_provider;
+        self
     }
 
     // Note: this will eventually all be done in the VM, and also return an `ExecutionProof`
@@ -211,17 +231,12 @@ impl BlockProver {
         let prev_hash = witness.prev_header.hash();
         let block_num = witness.prev_header.block_num() + 1;
         let version = witness.prev_header.version();
+        let timestamp = self.next_timestamp(witness.prev_header.timestamp().as_int())?;
 
         let (account_root, note_root, nullifier_root, chain_root) = self.compute_roots(witness)?;
 
         let batch_root = Digest::default();
         let proof_hash = Digest::default();
-        let timestamp: Felt = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .expect("today is expected to be after 1970")
-            .as_millis()
-            .try_into()
-            .expect("timestamp is greater than or equal to the field modulus");
 
         Ok(BlockHeader::new(
             prev_hash,
@@ -237,6 +252,19 @@ impl BlockProver {
         ))
     }
 
+    /// Returns the timestamp of the new block, which must be strictly greater than the timestamp
+    /// of the previous block.
+    fn next_timestamp(&self, previous: u64) -> Result<Felt, BuildBlockError> {
+        let current = self.time_provider.now_ms()?;
+        if current <= previous {
+            return Err(BuildBlockError::TimestampNotMonotonic { previous, current });
+        }
+
+        Ok(current
+            .try_into()
+            .expect("timestamp is greater than or equal to the field modulus"))
+    }
+
     fn compute_roots(
         &self,
         witness: BlockWitness,
@@ -1,26 +1,28 @@
-use std::{collections::BTreeMap, iter};
+use std::{collections::BTreeMap, iter, sync::Arc};
 
 use miden_node_proto::domain::accounts::AccountUpdateDetails;
+use miden_node_utils::chain_params::{BLOCK_OUTPUT_NOTES_TREE_DEPTH, NULLIFIER_TREE_DEPTH};
 use miden_objects::{
     accounts::{
         AccountId, ACCOUNT_ID_OFF_CHAIN_SENDER, ACCOUNT_ID_REGULAR_ACCOUNT_UPDATABLE_CODE_OFF_CHAIN,
     },
     crypto::merkle::{
         EmptySubtreeRoots, LeafIndex, MerklePath, Mmr, MmrPeaks, SimpleSmt, Smt, SmtLeaf, SmtProof,
-        SMT_DEPTH,
     },
     notes::{NoteEnvelope, NoteMetadata, NoteType},
     transaction::OutputNote,
-    BLOCK_OUTPUT_NOTES_TREE_DEPTH, ONE, ZERO,
+    ONE, ZERO,
 };
 
 use super::*;
 use crate::{
     block::{AccountWitness, BlockInputs},
+    clock::FixedStepClock,
+    errors::BuildBatchError,
     store::Store,
     test_utils::{
         block::{build_actual_block_header, build_expected_block_header, MockBlockBuilder},
-        MockProvenTxBuilder, MockStoreSuccessBuilder,
+        MockPrivateAccount, MockProvenTxBuilder, MockStoreSuccessBuilder,
     },
     TransactionBatch,
 };
@@ -154,6 +156,61 @@ fn test_block_witness_validation_inconsistent_account_hashes() {
     );
 }
 
+/// Tests that `BlockWitness` chains the states of an account modified by several transactions
+/// across batches, and fails if a batch doesn't start from the final state of the previous one.
+#[test]
+fn test_block_witness_validation_chained_account_states() {
+    let account: MockPrivateAccount<4> = MockPrivateAccount::from(0);
+
+    let block_inputs_from_store = || BlockInputs {
+        block_header: BlockHeader::mock(0, None, None, &[]),
+        chain_peaks: MmrPeaks::new(0, Vec::new()).unwrap(),
+        accounts: BTreeMap::from_iter(vec![(
+            account.id,
+            AccountWitness {
+                hash: account.states[0],
+                proof: Default::default(),
+            },
+        )]),
+        nullifiers: Default::default(),
+    };
+    let tx = |initial_state: usize, final_state: usize| {
+        MockProvenTxBuilder::with_account(
+            account.id,
+            account.states[initial_state],
+            account.states[final_state],
+        )
+        .build()
+    };
+
+    // The transactions of a batch are chained
+    assert!(matches!(
+        TransactionBatch::new(vec![tx(0, 1), tx(0, 2)]),
+        Err(BuildBatchError::InconsistentAccountStates(account_id, _)) if account_id == account.id
+    ));
+
+    let batches = vec![
+        TransactionBatch::new(vec![tx(0, 1)]).unwrap(),
+        TransactionBatch::new(vec![tx(1, 2), tx(2, 3)]).unwrap(),
+    ];
+    assert_eq!(batches[1].updated_accounts().count(), 2);
+
+    let block_witness = BlockWitness::new(block_inputs_from_store(), &batches).unwrap();
+    let account_update = &block_witness.updated_accounts[&account.id];
+    assert_eq!(account_update.initial_state_hash, account.states[0]);
+    assert_eq!(account_update.final_state_hash, account.states[3]);
+
+    // The second batch doesn't start from the state after the first one
+    let batches = vec![
+        TransactionBatch::new(vec![tx(0, 1)]).unwrap(),
+        TransactionBatch::new(vec![tx(2, 3)]).unwrap(),
+    ];
+    assert_eq!(
+        BlockWitness::new(block_inputs_from_store(), &batches),
+        Err(BuildBlockError::InconsistentAccountStates(vec![account.id]))
+    );
+}
+
 // ACCOUNT ROOT TESTS
 // =================================================================================================
 
@@ -504,7 +561,7 @@ fn test_block_witness_validation_inconsistent_nullifiers() {
             (
                 nullifier_2,
                 SmtProof::new(
-                    MerklePath::new(vec![Digest::default(); SMT_DEPTH as usize]),
+                    MerklePath::new(vec![Digest::default(); NULLIFIER_TREE_DEPTH as usize]),
                     SmtLeaf::new_empty(LeafIndex::new_max_depth(
                         nullifier_2.most_significant_felt().into(),
                     )),
@@ -514,7 +571,7 @@ fn test_block_witness_validation_inconsistent_nullifiers() {
             (
                 nullifier_3,
                 SmtProof::new(
-                    MerklePath::new(vec![Digest::default(); SMT_DEPTH as usize]),
+                    MerklePath::new(vec![Digest::default(); NULLIFIER_TREE_DEPTH as usize]),
                     SmtLeaf::new_empty(LeafIndex::new_max_depth(
                         nullifier_3.most_significant_felt().into(),
                     )),
@@ -539,6 +596,49 @@ fn test_block_witness_validation_inconsistent_nullifiers() {
     );
 }
 
+/// Tests that `BlockWitness` constructor fails if the store already consumed a nullifier of the
+/// transaction batches.
+#[test]
+fn test_block_witness_validation_consumed_nullifiers() {
+    let batches: Vec<TransactionBatch> = (0..2)
+        .map(|index| {
+            let tx = MockProvenTxBuilder::with_account_index(index)
+                .nullifiers_range(index as u64..index as u64 + 1)
+                .build();
+
+            TransactionBatch::new(vec![tx]).unwrap()
+        })
+        .collect();
+
+    let consumed = batches[1].produced_nullifiers().next().unwrap();
+    let nullifier_smt =
+        Smt::with_entries(iter::once((consumed.inner(), [ONE, ZERO, ZERO, ZERO]))).unwrap();
+
+    let block_inputs_from_store = BlockInputs {
+        block_header: BlockHeader::mock(0, None, None, &[]),
+        chain_peaks: MmrPeaks::new(0, Vec::new()).unwrap(),
+        accounts: batches
+            .iter()
+            .flat_map(|batch| batch.account_initial_states())
+            .map(|(account_id, hash)| {
+                (account_id, AccountWitness { hash, proof: MerklePath::default() })
+            })
+            .collect(),
+        nullifiers: batches
+            .iter()
+            .flat_map(TransactionBatch::produced_nullifiers)
+            .map(|nullifier| (nullifier, nullifier_smt.open(&nullifier.inner())))
+            .collect(),
+    };
+
+    let block_witness_result = BlockWitness::new(block_inputs_from_store, &batches);
+
+    assert_eq!(
+        block_witness_result,
+        Err(BuildBlockError::NullifiersAlreadyConsumed(vec![consumed]))
+    );
+}
+
 /// Tests that the block kernel returns the expected nullifier tree when no nullifiers are present in the transaction
 #[tokio::test]
 async fn test_compute_nullifier_root_empty_success() {
@@ -715,3 +815,43 @@ async fn test_compute_chain_mmr_root_mmr_17_peaks() {
 
     assert_eq!(actual_block_header.chain_root(), expected_block_header.chain_root());
 }
+
+// TIMESTAMP TESTS
+// =================================================================================================
+
+/// Test that the block timestamp is read from the injected time provider
+#[tokio::test]
+#[miden_node_test_macro::enable_logging]
+async fn test_block_timestamp_from_time_provider() {
+    let store = MockStoreSuccessBuilder::from_batches(iter::empty()).build();
+
+    let block_inputs_from_store: BlockInputs =
+        store.get_block_inputs(iter::empty(), iter::empty()).await.unwrap();
+    let block_witness = BlockWitness::new(block_inputs_from_store, &[]).unwrap();
+
+    let block_prover =
+        BlockProver::new().with_time_provider(Arc::new(FixedStepClock::new(1000, 10)));
+    let block_header = block_prover.prove(block_witness).unwrap();
+
+    assert_eq!(block_header.timestamp(), Felt::new(1000));
+}
+
+/// Test that a block whose timestamp is not after the previous block's timestamp is rejected
+#[tokio::test]
+#[miden_node_test_macro::enable_logging]
+async fn test_block_timestamp_not_monotonic() {
+    let store = MockStoreSuccessBuilder::from_batches(iter::empty()).build();
+
+    let block_inputs_from_store: BlockInputs =
+        store.get_block_inputs(iter::empty(), iter::empty()).await.unwrap();
+    let previous = block_inputs_from_store.block_header.timestamp().as_int();
+    let block_witness = BlockWitness::new(block_inputs_from_store, &[]).unwrap();
+
+    let block_prover =
+        BlockProver::new().with_time_provider(Arc::new(FixedStepClock::new(previous, 10)));
+
+    assert_eq!(
+        block_prover.prove(block_witness),
+        Err(BuildBlockError::TimestampNotMonotonic { previous, current: previous })
+    );
+}
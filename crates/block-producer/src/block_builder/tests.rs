@@ -5,13 +5,14 @@ use std::sync::Arc;
 
 use miden_objects::{
     accounts::{AccountId, ACCOUNT_ID_OFF_CHAIN_SENDER},
-    Digest, Felt,
+    BlockHeader, Digest, Felt,
 };
 
 use crate::{
     batch_builder::TransactionBatch,
-    block_builder::{BlockBuilder, BuildBlockError, DefaultBlockBuilder},
+    block_builder::{simulated_block_proof, BlockBuilder, BuildBlockError, DefaultBlockBuilder},
     test_utils::{MockProvenTxBuilder, MockStoreFailure, MockStoreSuccessBuilder},
+    timeline::{BlockStage, BlockTimelines},
 };
 
 /// Tests that `build_block()` succeeds when the transaction batches are not empty
@@ -67,6 +68,34 @@ async fn test_apply_block_called_empty_batches() {
     assert_eq!(*store.num_apply_block_called.read().await, 1);
 }
 
+/// Tests that `build_block()` records the stages reached by the block
+#[tokio::test]
+#[miden_node_test_macro::enable_logging]
+async fn test_build_block_records_its_timeline() {
+    let store = Arc::new(MockStoreSuccessBuilder::from_batches(std::iter::empty()).build());
+    let chain_tip = store.last_block_header.read().await.block_num();
+    let timelines = Arc::new(BlockTimelines::default());
+
+    let block_builder =
+        DefaultBlockBuilder::new(store.clone(), store.clone()).with_timelines(timelines.clone());
+
+    block_builder.build_block(&Vec::new()).await.unwrap();
+
+    let timeline = timelines.get(None).unwrap();
+    assert_eq!(timeline.block_num, chain_tip + 1);
+    let stages: Vec<_> = timeline.events.into_iter().map(|event| event.stage).collect();
+    assert_eq!(
+        stages,
+        vec![
+            BlockStage::BatchesSelected,
+            BlockStage::InputsFetched,
+            BlockStage::BlockBuilt,
+            BlockStage::ProofDone,
+            BlockStage::Committed,
+        ]
+    );
+}
+
 /// Tests that `build_block()` fails when `get_block_inputs()` fails
 #[tokio::test]
 #[miden_node_test_macro::enable_logging]
@@ -80,3 +109,17 @@ async fn test_build_block_failure() {
     // Ensure that the store's `apply_block()` was called
     assert!(matches!(result, Err(BuildBlockError::GetBlockInputsFailed(_))));
 }
+
+/// Tests that the proofs fabricated in simulation mode have the requested size and differ between
+/// blocks
+#[test]
+fn test_simulated_block_proof() {
+    let header_1 = BlockHeader::mock(1, None, None, &[]);
+    let header_2 = BlockHeader::mock(2, None, None, &[]);
+
+    let proof_1 = simulated_block_proof(&header_1, 100);
+    assert_eq!(proof_1.len(), 100);
+    assert_eq!(&proof_1[..32], &header_1.hash().as_bytes());
+    assert_ne!(proof_1, simulated_block_proof(&header_2, 100));
+    assert!(simulated_block_proof(&header_1, 0).is_empty());
+}
@@ -0,0 +1,285 @@
+use std::{
+    fmt::Debug,
+    io,
+    net::UdpSocket,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, RwLock, Weak,
+    },
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use miden_node_utils::runtime::spawn_named;
+use tokio::time::MissedTickBehavior;
+use tracing::{info, warn};
+
+use crate::{errors::TimeProviderError, COMPONENT};
+
+// TIME PROVIDER
+// =================================================================================================
+
+/// Source of the timestamps of the produced blocks.
+pub trait TimeProvider: Debug + Send + Sync + 'static {
+    /// Returns the current time, in milliseconds since the UNIX epoch.
+    fn now_ms(&self) -> Result<u64, TimeProviderError>;
+}
+
+// SYSTEM CLOCK
+// =================================================================================================
+
+/// Reads the time from the system clock.
+#[derive(Debug, Default)]
+pub struct SystemClock;
+
+impl TimeProvider for SystemClock {
+    fn now_ms(&self) -> Result<u64, TimeProviderError> {
+        system_time_ms()
+    }
+}
+
+fn system_time_ms() -> Result<u64, TimeProviderError> {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|_| TimeProviderError::BeforeUnixEpoch)?;
+
+    Ok(now.as_millis() as u64)
+}
+
+// FIXED STEP CLOCK
+// =================================================================================================
+
+/// A clock which starts at `start_ms` and advances by `step_ms` every time it is read.
+///
+/// Produces reproducible timestamps, intended for testing.
+#[derive(Debug)]
+pub struct FixedStepClock {
+    next_ms: AtomicU64,
+    step_ms: u64,
+}
+
+impl FixedStepClock {
+    pub fn new(start_ms: u64, step_ms: u64) -> Self {
+        Self {
+            next_ms: AtomicU64::new(start_ms),
+            step_ms,
+        }
+    }
+}
+
+impl TimeProvider for FixedStepClock {
+    fn now_ms(&self) -> Result<u64, TimeProviderError> {
+        Ok(self.next_ms.fetch_add(self.step_ms, Ordering::SeqCst))
+    }
+}
+
+// NTP VALIDATED CLOCK
+// =================================================================================================
+
+/// Time between two validations of the system clock against the NTP server.
+const NTP_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Maximum time to wait for the response of the NTP server.
+const NTP_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Seconds between the NTP epoch (1900-01-01) and the UNIX epoch (1970-01-01).
+const NTP_UNIX_EPOCH_OFFSET_SECS: u64 = 2_208_988_800;
+
+/// Reads the time from the system clock, and periodically validates it against an NTP server.
+///
+/// The NTP server is queried by a background task, see [NtpValidatedClock::spawn], reading the time
+/// only looks up the result of the last check. Returns an error if the system clock drifted from
+/// the NTP server's clock by more than `max_drift_ms`. If the NTP server can't be reached, the
+/// system clock is used without validation until the next check.
+#[derive(Debug)]
+pub struct NtpValidatedClock {
+    server: String,
+    max_drift_ms: u64,
+    /// Offset of the system clock measured by the last check, [None] if the NTP server couldn't be
+    /// queried or hasn't been yet.
+    offset_ms: RwLock<Option<i64>>,
+}
+
+impl NtpValidatedClock {
+    /// Returns a new clock validated against the NTP server `server`, in the format `host:port`.
+    ///
+    /// The clock is not validated until [NtpValidatedClock::check] is called.
+    pub fn new(server: String, max_drift_ms: u64) -> Self {
+        Self {
+            server,
+            max_drift_ms,
+            offset_ms: RwLock::new(None),
+        }
+    }
+
+    /// Returns a new clock validated against the NTP server `server`, and spawns the task checking
+    /// it every [NTP_CHECK_INTERVAL]. The task stops once the clock is dropped.
+    ///
+    /// Must be called from within a tokio runtime.
+    pub fn spawn(server: String, max_drift_ms: u64) -> Arc<Self> {
+        let clock = Arc::new(Self::new(server, max_drift_ms));
+        spawn_named("ntp-clock-check", Self::check_periodically(Arc::downgrade(&clock)));
+        clock
+    }
+
+    async fn check_periodically(clock: Weak<Self>) {
+        let mut interval = tokio::time::interval(NTP_CHECK_INTERVAL);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            interval.tick().await;
+            let Some(clock) = clock.upgrade() else {
+                return;
+            };
+            if let Err(err) = tokio::task::spawn_blocking(move || clock.check()).await {
+                warn!(target: COMPONENT, %err, "NTP check task failed");
+            }
+        }
+    }
+
+    /// Queries the NTP server and records the offset of the system clock, blocking until the
+    /// server responds or [NTP_TIMEOUT] elapses.
+    pub fn check(&self) {
+        let offset_ms = self.query_offset_ms();
+        self.record_check(offset_ms);
+    }
+
+    fn record_check(&self, offset_ms: io::Result<i64>) {
+        let offset_ms = match offset_ms {
+            Ok(offset_ms) => {
+                info!(target: COMPONENT, offset_ms, server = self.server, "System clock checked");
+                Some(offset_ms)
+            },
+            Err(err) => {
+                warn!(target: COMPONENT, %err, server = self.server, "Failed to query NTP server");
+                None
+            },
+        };
+
+        *self.offset_ms.write().expect("lock is not poisoned") = offset_ms;
+    }
+
+    /// Returns the offset of the system clock in relation to the NTP server, in milliseconds.
+    fn query_offset_ms(&self) -> io::Result<i64> {
+        let socket = UdpSocket::bind("0.0.0.0:0")?;
+        socket.set_read_timeout(Some(NTP_TIMEOUT))?;
+        socket.connect(&self.server)?;
+
+        // LI = 0 (no warning), VN = 4, Mode = 3 (client)
+        let mut request = [0u8; 48];
+        request[0] = 0b00_100_011;
+
+        let sent_ms = system_time_ms().map_err(io::Error::other)?;
+        socket.send(&request)?;
+
+        let mut response = [0u8; 48];
+        let len = socket.recv(&mut response)?;
+        let received_ms = system_time_ms().map_err(io::Error::other)?;
+
+        if len < response.len() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "NTP response is too short"));
+        }
+
+        Ok(ntp_offset_ms(&response, sent_ms, received_ms))
+    }
+}
+
+impl TimeProvider for NtpValidatedClock {
+    fn now_ms(&self) -> Result<u64, TimeProviderError> {
+        let offset_ms = *self.offset_ms.read().expect("lock is not poisoned");
+
+        if let Some(offset_ms) = offset_ms {
+            if offset_ms.unsigned_abs() > self.max_drift_ms {
+                return Err(TimeProviderError::ClockDrift {
+                    drift_ms: offset_ms,
+                    max_drift_ms: self.max_drift_ms,
+                });
+            }
+        }
+
+        system_time_ms()
+    }
+}
+
+/// Computes the clock offset from an NTP server `response`, see RFC 5905.
+///
+/// `sent_ms` and `received_ms` are the local times at which the request was sent and the response
+/// received, in milliseconds since the UNIX epoch.
+fn ntp_offset_ms(response: &[u8; 48], sent_ms: u64, received_ms: u64) -> i64 {
+    let server_received_ms = ntp_timestamp_ms(&response[32..40]) as i64;
+    let server_sent_ms = ntp_timestamp_ms(&response[40..48]) as i64;
+
+    ((server_received_ms - sent_ms as i64) + (server_sent_ms - received_ms as i64)) / 2
+}
+
+/// Converts an NTP timestamp, 32 bits of seconds since the NTP epoch followed by 32 bits of
+/// fraction, into milliseconds since the UNIX epoch.
+fn ntp_timestamp_ms(timestamp: &[u8]) -> u64 {
+    let secs = u32::from_be_bytes(timestamp[0..4].try_into().expect("slice has 4 bytes")) as u64;
+    let fraction =
+        u32::from_be_bytes(timestamp[4..8].try_into().expect("slice has 4 bytes")) as u64;
+
+    secs.saturating_sub(NTP_UNIX_EPOCH_OFFSET_SECS) * 1000 + ((fraction * 1000) >> 32)
+}
+
+// TESTS
+// =================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Encodes `ms` since the UNIX epoch as an NTP timestamp
+    fn ntp_timestamp(ms: u64) -> [u8; 8] {
+        let secs = (ms / 1000 + NTP_UNIX_EPOCH_OFFSET_SECS) as u32;
+        let fraction = (((ms % 1000) << 32) / 1000) as u32;
+
+        let mut timestamp = [0u8; 8];
+        timestamp[0..4].copy_from_slice(&secs.to_be_bytes());
+        timestamp[4..8].copy_from_slice(&fraction.to_be_bytes());
+        timestamp
+    }
+
+    #[test]
+    fn test_fixed_step_clock() {
+        let clock = FixedStepClock::new(1000, 10);
+
+        assert_eq!(clock.now_ms(), Ok(1000));
+        assert_eq!(clock.now_ms(), Ok(1010));
+        assert_eq!(clock.now_ms(), Ok(1020));
+    }
+
+    #[test]
+    fn test_ntp_validated_clock() {
+        let clock = NtpValidatedClock::new("localhost:123".to_string(), 1000);
+        assert!(clock.now_ms().is_ok());
+
+        clock.record_check(Ok(1500));
+        assert_eq!(
+            clock.now_ms(),
+            Err(TimeProviderError::ClockDrift { drift_ms: 1500, max_drift_ms: 1000 })
+        );
+
+        // A failed check leaves the clock unvalidated, it doesn't keep the previous drift
+        clock.record_check(Err(io::ErrorKind::TimedOut.into()));
+        assert!(clock.now_ms().is_ok());
+
+        clock.record_check(Ok(-500));
+        assert!(clock.now_ms().is_ok());
+    }
+
+    #[test]
+    fn test_ntp_offset() {
+        let local_ms = 1_700_000_000_000;
+
+        // The server's clock is 500ms ahead, the request takes 20ms each way and the server takes
+        // 10ms to respond
+        let mut response = [0u8; 48];
+        response[32..40].copy_from_slice(&ntp_timestamp(local_ms + 20 + 500));
+        response[40..48].copy_from_slice(&ntp_timestamp(local_ms + 30 + 500));
+
+        let offset = ntp_offset_ms(&response, local_ms, local_ms + 50);
+
+        // The fraction of the NTP timestamp loses precision, allow for 1ms of error
+        assert!((499..=501).contains(&offset), "offset: {offset}");
+    }
+}
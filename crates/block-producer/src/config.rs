@@ -1,8 +1,24 @@
-use std::fmt::{Display, Formatter};
+use std::{
+    fmt::{Display, Formatter},
+    path::PathBuf,
+    sync::Arc,
+    time::Duration,
+};
 
-use miden_node_utils::config::Endpoint;
+use miden_node_utils::{
+    chain_params::{ChainFees, ChainParameters, MAX_NOTES_PER_BATCH},
+    config::{AccessLogConfig, ClientConfig, Endpoint},
+    formatting::{format_array, format_opt},
+};
+use miden_objects::{accounts::AccountId, utils::HexParseError, AccountError, Digest};
 use serde::{Deserialize, Serialize};
 
+use crate::{
+    clock::{NtpValidatedClock, SystemClock, TimeProvider},
+    SERVER_BATCH_SIZE, SERVER_BLOCK_FREQUENCY, SERVER_BUILD_BATCH_FREQUENCY,
+    SERVER_MAX_IN_FLIGHT_BATCHES, SERVER_PRIORITY_LANE_CAPACITY, SIMULATED_BLOCK_PROOF_SIZE,
+};
+
 // Main config
 // ================================================================================================
 
@@ -14,6 +30,15 @@ pub struct BlockProducerConfig {
     /// Store gRPC endpoint in the format `http://<host>[:<port>]`.
     pub store_url: String,
 
+    /// Keep-alive and health checks of the connection to the store.
+    #[serde(default)]
+    pub store_client: ClientConfig,
+
+    /// Hash of the genesis block of the chain, as logged by the store at startup. If set, the
+    /// block producer refuses to start with a store of another chain.
+    #[serde(default)]
+    pub genesis_hash: Option<String>,
+
     /// Enable or disable the verification of transaction proofs before they are accepted into the
     /// transaction queue.
     ///
@@ -21,19 +46,447 @@ pub struct BlockProducerConfig {
     /// verification may take ~15ms/proof. This is OK when all transactions are forwarded to the
     /// block producer from the RPC component as transaction proofs are also verified there.
     pub verify_tx_proofs: bool,
+
+    /// Policy used to decide when the transactions in the queue are sealed into a batch.
+    #[serde(default)]
+    pub batch_sealing: BatchSealingPolicy,
+
+    /// Concurrency of the batch proving.
+    #[serde(default)]
+    pub batch_proving: BatchProvingConfig,
+
+    /// Period of the block production.
+    #[serde(default)]
+    pub block_production: BlockProductionConfig,
+
+    /// Source of the block timestamps.
+    #[serde(default)]
+    pub clock: ClockConfig,
+
+    /// File holding the key used to sign the produced block headers, created with a new key if it
+    /// doesn't exist. A new key is generated on every start if not set.
+    #[serde(default)]
+    pub signing_key_filepath: Option<PathBuf>,
+
+    /// Fees charged for the submitted transactions, no fees are charged if not set.
+    #[serde(default)]
+    pub fees: Option<FeeConfig>,
+
+    /// Accounts and note tags allowed or denied on startup, all the transactions are accepted if
+    /// not set. The policy can be replaced at runtime through the admin API.
+    #[serde(default)]
+    pub submission_policy: SubmissionPolicyConfig,
+
+    /// Runs the block producer in simulation mode, to load test the pipeline independently of the
+    /// provers. Development only, must never be set on a public network.
+    #[serde(default)]
+    pub simulation: Option<SimulationConfig>,
+
+    /// Access log of the requests served, disabled if not set.
+    #[serde(default)]
+    pub access_log: Option<AccessLogConfig>,
 }
 
 impl BlockProducerConfig {
     pub fn endpoint_url(&self) -> String {
         self.endpoint.to_string()
     }
+
+    /// Parses the hex encoded `genesis_hash`.
+    pub fn genesis_hash(&self) -> Result<Option<Digest>, HexParseError> {
+        self.genesis_hash.as_deref().map(Digest::try_from).transpose()
+    }
+
+    /// Applies the parameters of the chain, which take precedence over the configuration: the
+    /// batches are capped to the maximum batch size of the chain and the fees are those of the
+    /// chain.
+    pub fn with_chain_parameters(mut self, parameters: &ChainParameters) -> Self {
+        let max_batch_size = parameters.max_batch_size.try_into().unwrap_or(usize::MAX);
+        self.batch_sealing.max_txs = self.batch_sealing.max_txs.min(max_batch_size);
+        self.fees = parameters.fees.map(Into::into);
+        self
+    }
 }
 
 impl Display for BlockProducerConfig {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.write_fmt(format_args!(
-            "{{ endpoint: \"{}\", store_url: \"{}\" }}",
-            self.endpoint, self.store_url
+            "{{ endpoint: \"{}\", store_url: \"{}\", store_client: {}, genesis_hash: {}, \
+             batch_sealing: {}, batch_proving: {}, block_production: {}, clock: {}, \
+             signing_key_filepath: {}, fees: {}, submission_policy: {}, simulation: {}, \
+             access_log: {} }}",
+            self.endpoint,
+            self.store_url,
+            self.store_client,
+            format_opt(self.genesis_hash.as_ref()),
+            self.batch_sealing,
+            self.batch_proving,
+            self.block_production,
+            self.clock,
+            format_opt(self.signing_key_filepath.as_ref().map(|path| path.display()).as_ref()),
+            format_opt(self.fees.as_ref()),
+            self.submission_policy,
+            format_opt(self.simulation.as_ref()),
+            format_opt(self.access_log.as_ref()),
+        ))
+    }
+}
+
+// Batch sealing policy
+// ================================================================================================
+
+/// Determines when queued transactions are sealed into a batch.
+///
+/// A batch is sealed as soon as the first of the following conditions is met:
+/// - it contains `max_txs` transactions,
+/// - adding the next transaction would take it over `max_notes` created notes,
+/// - `max_delay_ms` milliseconds have elapsed since the last batch was sealed.
+///
+/// Lower limits reduce the time a transaction waits for confirmation at the cost of producing
+/// smaller batches.
+///
+/// If `adaptive` is set, the number of transactions a batch is sealed at is adjusted to the load
+/// between `adaptive.min_txs` and `max_txs`, see [AdaptiveBatchSize].
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BatchSealingPolicy {
+    /// Maximum number of transactions in a batch.
+    pub max_txs: usize,
+
+    /// Maximum number of notes created by all transactions in a batch. Values above
+    /// [MAX_NOTES_PER_BATCH] are capped to it.
+    pub max_notes: usize,
+
+    /// Maximum time, in milliseconds, a transaction waits in the queue before being batched. Must
+    /// be at least 1, 0 is raised to 1.
+    pub max_delay_ms: u64,
+
+    /// Adjusts the number of transactions of the batches to the load, the batches are sealed at
+    /// `max_txs` transactions if not set.
+    pub adaptive: Option<AdaptiveBatchSize>,
+}
+
+impl BatchSealingPolicy {
+    /// Returns the maximum time a transaction waits in the queue before being batched, at least
+    /// 1ms since the queue can't be checked continuously.
+    pub fn max_delay(&self) -> Duration {
+        Duration::from_millis(self.max_delay_ms.max(1))
+    }
+
+    /// Returns the maximum number of notes in a batch, capped to the protocol limit.
+    pub fn max_notes(&self) -> usize {
+        self.max_notes.min(MAX_NOTES_PER_BATCH)
+    }
+
+    /// Returns true if a batch with `num_txs` transactions and `num_notes` created notes must be
+    /// sealed.
+    pub fn is_full(&self, num_txs: usize, num_notes: usize) -> bool {
+        num_txs >= self.max_txs.max(1) || num_notes >= self.max_notes()
+    }
+}
+
+impl Default for BatchSealingPolicy {
+    fn default() -> Self {
+        Self {
+            max_txs: SERVER_BATCH_SIZE,
+            max_notes: MAX_NOTES_PER_BATCH,
+            max_delay_ms: SERVER_BUILD_BATCH_FREQUENCY
+                .as_millis()
+                .try_into()
+                .expect("batch frequency fits in u64"),
+            adaptive: None,
+        }
+    }
+}
+
+impl Display for BatchSealingPolicy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!(
+            "{{ max_txs: {}, max_notes: {}, max_delay_ms: {}, adaptive: {} }}",
+            self.max_txs,
+            self.max_notes,
+            self.max_delay_ms,
+            format_opt(self.adaptive.as_ref())
+        ))
+    }
+}
+
+/// Adjusts the number of transactions the batches are sealed at, so that the transactions are
+/// confirmed in about `target_latency_ms` milliseconds as the load fluctuates.
+///
+/// After each block, the batches grow while the blocks are full and the batches are proven within
+/// the target, so that the blocks include more transactions, and shrink while the transactions
+/// take longer than the target to be confirmed, so that the batches are sealed and proven sooner.
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize)]
+pub struct AdaptiveBatchSize {
+    /// Minimum number of transactions the batches are sealed at.
+    pub min_txs: usize,
+
+    /// Target time, in milliseconds, from the acceptance of a transaction to the commit of its
+    /// block.
+    pub target_latency_ms: u64,
+}
+
+impl AdaptiveBatchSize {
+    /// Returns the target time from the acceptance of a transaction to the commit of its block.
+    pub fn target_latency(&self) -> Duration {
+        Duration::from_millis(self.target_latency_ms)
+    }
+}
+
+impl Display for AdaptiveBatchSize {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!(
+            "{{ min_txs: {}, target_latency_ms: {} }}",
+            self.min_txs, self.target_latency_ms
+        ))
+    }
+}
+
+// Batch proving
+// ================================================================================================
+
+/// Determines how many batches are proven concurrently.
+///
+/// Batches can finish proving in any order, they are still included in blocks in the order they
+/// were sealed. Proving more batches concurrently increases the throughput when proving dominates
+/// the latency, at the cost of CPU and memory.
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BatchProvingConfig {
+    /// Maximum number of batches proven concurrently, further batches wait for a proving slot.
+    pub max_in_flight_batches: usize,
+}
+
+impl Default for BatchProvingConfig {
+    fn default() -> Self {
+        Self {
+            max_in_flight_batches: SERVER_MAX_IN_FLIGHT_BATCHES,
+        }
+    }
+}
+
+impl Display for BatchProvingConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("{{ max_in_flight_batches: {} }}", self.max_in_flight_batches))
+    }
+}
+
+// Block production
+// ================================================================================================
+
+/// Determines how often blocks are produced, and how heavy they can be.
+///
+/// A block is produced every period, including the batches proven since the previous block, and
+/// is empty if there are none. If `max_cycles` is set, the block only includes the batches whose
+/// transactions add up to at most `max_cycles` estimated VM cycles, the next batches waiting for
+/// the following blocks, so that the proving time of the blocks stays predictable. A batch above
+/// the budget is included alone in a block.
+///
+/// The transactions submitted by the operator through the admin API skip the queue of the user
+/// transactions: their batches are included in the next block ahead of the user batches. At most
+/// `priority_lane_capacity` of them are in flight, further ones are rejected until a block
+/// includes them, and setting it to zero disables the priority lane.
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BlockProductionConfig {
+    /// Time, in milliseconds, between the production of two blocks.
+    pub interval_ms: u64,
+
+    /// Maximum estimated VM cycles of the transactions of a block, unlimited if not set.
+    pub max_cycles: Option<u64>,
+
+    /// Maximum number of operator transactions in flight, included in the next block ahead of the
+    /// user transactions.
+    pub priority_lane_capacity: usize,
+}
+
+impl BlockProductionConfig {
+    /// Returns the time between the production of two blocks.
+    pub fn interval(&self) -> Duration {
+        Duration::from_millis(self.interval_ms)
+    }
+}
+
+impl Default for BlockProductionConfig {
+    fn default() -> Self {
+        Self {
+            interval_ms: SERVER_BLOCK_FREQUENCY
+                .as_millis()
+                .try_into()
+                .expect("block frequency fits in u64"),
+            max_cycles: None,
+            priority_lane_capacity: SERVER_PRIORITY_LANE_CAPACITY,
+        }
+    }
+}
+
+impl Display for BlockProductionConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!(
+            "{{ interval_ms: {}, max_cycles: {}, priority_lane_capacity: {} }}",
+            self.interval_ms,
+            format_opt(self.max_cycles.as_ref()),
+            self.priority_lane_capacity
         ))
     }
 }
+
+// Fees
+// ================================================================================================
+
+/// Fee charged for each submitted transaction.
+///
+/// A transaction pays its fee by creating a pay-to-id note sending at least `min_amount` of the
+/// fungible asset of `faucet_id` to `operator_account_id`. Both accounts are hex encoded.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize)]
+pub struct FeeConfig {
+    /// Account the fees are paid to.
+    pub operator_account_id: String,
+
+    /// Faucet of the fungible asset the fees are paid in.
+    pub faucet_id: String,
+
+    /// Minimum amount of the fee of a transaction.
+    pub min_amount: u64,
+}
+
+impl FeeConfig {
+    /// Parses the hex encoded `operator_account_id`.
+    pub fn operator(&self) -> Result<AccountId, AccountError> {
+        AccountId::from_hex(&self.operator_account_id)
+    }
+
+    /// Parses the hex encoded `faucet_id`.
+    pub fn faucet(&self) -> Result<AccountId, AccountError> {
+        AccountId::from_hex(&self.faucet_id)
+    }
+}
+
+impl From<ChainFees> for FeeConfig {
+    fn from(fees: ChainFees) -> Self {
+        Self {
+            operator_account_id: fees.operator.to_hex(),
+            faucet_id: fees.faucet_id.to_hex(),
+            min_amount: fees.min_amount,
+        }
+    }
+}
+
+impl Display for FeeConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!(
+            "{{ operator_account_id: \"{}\", faucet_id: \"{}\", min_amount: {} }}",
+            self.operator_account_id, self.faucet_id, self.min_amount
+        ))
+    }
+}
+
+// Submission policy
+// ================================================================================================
+
+/// Accounts and note tags allowed or denied by the operator, see [crate::policy]. The accounts are
+/// hex encoded.
+///
+/// A transaction is rejected if its account is denied, or isn't allowed while `allowed_accounts`
+/// isn't empty. It is also rejected if it creates a note whose tag is denied, or isn't allowed
+/// while `allowed_note_tags` isn't empty.
+#[derive(Clone, Default, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SubmissionPolicyConfig {
+    /// Accounts whose transactions are accepted, all of them if empty.
+    pub allowed_accounts: Vec<String>,
+
+    /// Accounts whose transactions are rejected.
+    pub denied_accounts: Vec<String>,
+
+    /// Tags of the notes the transactions may create, all of them if empty.
+    pub allowed_note_tags: Vec<u32>,
+
+    /// Tags of the notes the transactions must not create.
+    pub denied_note_tags: Vec<u32>,
+}
+
+impl Display for SubmissionPolicyConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!(
+            "{{ allowed_accounts: {}, denied_accounts: {}, allowed_note_tags: {}, \
+             denied_note_tags: {} }}",
+            format_array(&self.allowed_accounts),
+            format_array(&self.denied_accounts),
+            format_array(&self.allowed_note_tags),
+            format_array(&self.denied_note_tags),
+        ))
+    }
+}
+
+// Simulation
+// ================================================================================================
+
+/// Simulation mode of the block producer, for load testing.
+///
+/// The proofs of the submitted transactions are never verified, whatever `verify_tx_proofs`, so
+/// transactions can be submitted with placeholder proofs. The produced blocks carry a fabricated
+/// proof of `block_proof_size` bytes, stored and served like a real one.
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SimulationConfig {
+    /// Size, in bytes, of the fabricated block proofs.
+    pub block_proof_size: usize,
+}
+
+impl Default for SimulationConfig {
+    fn default() -> Self {
+        Self {
+            block_proof_size: SIMULATED_BLOCK_PROOF_SIZE,
+        }
+    }
+}
+
+impl Display for SimulationConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("{{ block_proof_size: {} }}", self.block_proof_size))
+    }
+}
+
+// Clock
+// ================================================================================================
+
+/// Source of the block timestamps.
+#[derive(Clone, Default, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClockConfig {
+    /// Timestamps are read from the system clock.
+    #[default]
+    System,
+
+    /// Timestamps are read from the system clock, which is periodically validated against the NTP
+    /// server `server`, in the format `<host>:<port>`. Blocks are not produced while the system
+    /// clock has drifted by more than `max_drift_ms` milliseconds.
+    Ntp { server: String, max_drift_ms: u64 },
+}
+
+impl ClockConfig {
+    /// Returns the time provider described by this configuration.
+    ///
+    /// The NTP clock spawns its check task, so this must be called from within a tokio runtime.
+    pub fn time_provider(&self) -> Arc<dyn TimeProvider> {
+        match self {
+            Self::System => Arc::new(SystemClock),
+            Self::Ntp { server, max_drift_ms } => {
+                NtpValidatedClock::spawn(server.clone(), *max_drift_ms)
+            },
+        }
+    }
+}
+
+impl Display for ClockConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::System => f.write_str("system"),
+            Self::Ntp { server, max_drift_ms } => {
+                f.write_fmt(format_args!("{{ ntp: \"{server}\", max_drift_ms: {max_drift_ms} }}"))
+            },
+        }
+    }
+}
@@ -1,42 +1,40 @@
-use miden_node_proto::errors::ConversionError;
-use miden_node_utils::formatting::format_opt;
+use miden_node_proto::{domain::transactions::TransactionRejection, errors::ConversionError};
+use miden_node_utils::{
+    chain_params::{BLOCK_OUTPUT_NOTES_BATCH_TREE_DEPTH, MAX_NOTES_PER_BATCH},
+    formatting::format_opt,
+};
 use miden_objects::{
     accounts::AccountId,
     crypto::merkle::{MerkleError, MmrError},
-    notes::Nullifier,
+    notes::{NoteId, Nullifier},
     transaction::{InputNotes, ProvenTransaction, TransactionId},
-    Digest, TransactionInputError, BLOCK_OUTPUT_NOTES_BATCH_TREE_DEPTH, MAX_NOTES_PER_BATCH,
+    Digest, TransactionInputError,
 };
 use miden_processor::ExecutionError;
 use thiserror::Error;
+use tonic::{Code, Status};
 
 // Transaction verification errors
 // =================================================================================================
 
 #[derive(Debug, PartialEq, Eq, Error)]
 pub enum VerifyTxError {
-    /// The account that the transaction modifies has already been modified and isn't yet committed
-    /// to a block
-    #[error("Account {0} was already modified by other transaction")]
-    AccountAlreadyModifiedByOtherTx(AccountId),
-
     /// Another transaction already consumed the notes with given nullifiers
     #[error("Input notes with given nullifier were already consumed by another transaction")]
     InputNotesAlreadyConsumed(InputNotes<Nullifier>),
 
-    /// The account's initial hash did not match the current account's hash
-    #[error("Incorrect account's initial hash ({tx_initial_account_hash}, stored: {})", format_opt(.store_account_hash.as_ref()))]
+    /// The account's initial hash did not match the current account's hash, which is the final
+    /// hash of the latest in-flight transaction modifying the account, if any, or its hash in the
+    /// store
+    #[error("Incorrect account's initial hash ({tx_initial_account_hash}, current: {})", format_opt(.current_account_hash.as_ref()))]
     IncorrectAccountInitialHash {
         tx_initial_account_hash: Digest,
-        store_account_hash: Option<Digest>,
+        current_account_hash: Option<Digest>,
     },
 
     /// Failed to retrieve transaction inputs from the store
-    ///
-    /// TODO: Make this an "internal error". Q: Should we have a single `InternalError` enum for all
-    /// internal errors that can occur across the system?
     #[error("Failed to retrieve transaction inputs from the store: {0}")]
-    StoreConnectionFailed(#[from] TxInputsError),
+    StoreRequestFailed(#[from] StoreClientError),
 
     #[error("Transaction input error: {0}")]
     TransactionInputError(#[from] TransactionInputError),
@@ -44,6 +42,52 @@ pub enum VerifyTxError {
     /// Failed to verify the transaction execution proof
     #[error("Invalid transaction proof error for transaction: {0}")]
     InvalidTransactionProof(TransactionId),
+
+    /// The transaction was rejected by a policy of the operator
+    #[error("Transaction rejected by policy: {0}")]
+    PolicyViolation(String),
+
+    /// The priority transaction modifies an account modified by in-flight user transactions, it
+    /// can't be included in a block ahead of them
+    #[error("Account {0} is modified by in-flight user transactions")]
+    AccountModifiedByUserTransactions(AccountId),
+}
+
+impl VerifyTxError {
+    /// Returns the reason of the rejection reported to the client, [None] if the transaction
+    /// wasn't rejected because of its contents.
+    pub fn rejection(&self) -> Option<TransactionRejection> {
+        match self {
+            Self::InputNotesAlreadyConsumed(nullifiers) => {
+                Some(TransactionRejection::InputNotesAlreadyConsumed(
+                    nullifiers.iter().copied().collect(),
+                ))
+            },
+            Self::IncorrectAccountInitialHash {
+                tx_initial_account_hash,
+                current_account_hash,
+            } => Some(TransactionRejection::IncorrectAccountInitialHash {
+                provided: *tx_initial_account_hash,
+                expected: *current_account_hash,
+            }),
+            Self::InvalidTransactionProof(tx_id) => {
+                Some(TransactionRejection::InvalidTransactionProof(*tx_id))
+            },
+            Self::PolicyViolation(reason) => {
+                Some(TransactionRejection::PolicyViolation(reason.clone()))
+            },
+            Self::AccountModifiedByUserTransactions(account_id) => {
+                Some(TransactionRejection::AccountAlreadyModified(*account_id))
+            },
+            Self::StoreRequestFailed(_) | Self::TransactionInputError(_) => None,
+        }
+    }
+
+    /// Returns `true` if the transaction couldn't be verified because the store couldn't be
+    /// reached, verifying it again may succeed.
+    pub fn is_retriable(&self) -> bool {
+        matches!(self, Self::StoreRequestFailed(err) if err.is_retriable())
+    }
 }
 
 // Transaction adding errors
@@ -53,6 +97,13 @@ pub enum VerifyTxError {
 pub enum AddTransactionError {
     #[error("Transaction verification failed: {0}")]
     VerificationFailed(#[from] VerifyTxError),
+
+    /// The priority lane already holds its capacity of in-flight transactions
+    #[error("Priority lane is full ({capacity} transactions in flight)")]
+    PriorityLaneFull { capacity: usize },
+
+    #[error("Priority lane is disabled")]
+    PriorityLaneDisabled,
 }
 
 // Batch building errors
@@ -70,6 +121,9 @@ pub enum BuildBatchError {
 
     #[error("failed to create notes SMT: {0}")]
     NotesSmtError(MerkleError, Vec<ProvenTransaction>),
+
+    #[error("Transaction of account {0} doesn't start from the final state of the previous transaction in the batch")]
+    InconsistentAccountStates(AccountId, Vec<ProvenTransaction>),
 }
 
 impl BuildBatchError {
@@ -77,6 +131,7 @@ impl BuildBatchError {
         match self {
             BuildBatchError::TooManyNotesCreated(_, txs) => txs,
             BuildBatchError::NotesSmtError(_, txs) => txs,
+            BuildBatchError::InconsistentAccountStates(_, txs) => txs,
         }
     }
 }
@@ -104,8 +159,6 @@ pub enum BlockInputsError {
     ConversionError(#[from] ConversionError),
     #[error("MmrPeaks error: {0}")]
     MmrPeaksError(#[from] MmrError),
-    #[error("gRPC client failed with error: {0}")]
-    GrpcClientError(String),
 }
 
 // Block applying errors
@@ -113,8 +166,11 @@ pub enum BlockInputsError {
 
 #[derive(Debug, PartialEq, Eq, Error)]
 pub enum ApplyBlockError {
-    #[error("gRPC client failed with error: {0}")]
-    GrpcClientError(String),
+    /// If the store couldn't be reached, the block may or may not have been applied
+    #[error("store request failed: {0}")]
+    StoreRequestFailed(#[from] StoreClientError),
+    #[error("block {block_num} doesn't follow the store's chain tip {chain_tip}")]
+    ChainTipMismatch { block_num: u32, chain_tip: u32 },
 }
 
 // Block building errors
@@ -127,31 +183,135 @@ pub enum BuildBlockError {
     #[error("failed to apply block: {0}")]
     ApplyBlockFailed(#[from] ApplyBlockError),
     #[error("failed to get block inputs from store: {0}")]
-    GetBlockInputsFailed(#[from] BlockInputsError),
+    GetBlockInputsFailed(#[from] StoreClientError),
     #[error("transaction batches and store don't modify the same account IDs. Offending accounts: {0:?}")]
     InconsistentAccountIds(Vec<AccountId>),
-    #[error("transaction batches and store contain different hashes for some accounts. Offending accounts: {0:?}")]
+    #[error("transaction batches don't start from the account hashes of the store or of the previous batches. Offending accounts: {0:?}")]
     InconsistentAccountStates(Vec<AccountId>),
     #[error("transaction batches and store don't produce the same nullifiers. Offending nullifiers: {0:?}")]
     InconsistentNullifiers(Vec<Nullifier>),
+    #[error("transaction batches consume notes already consumed in the store. Offending nullifiers: {0:?}")]
+    NullifiersAlreadyConsumed(Vec<Nullifier>),
     #[error(
         "too many batches in block. Got: {0}, max: 2^{}",
         BLOCK_OUTPUT_NOTES_BATCH_TREE_DEPTH
     )]
     TooManyBatchesInBlock(usize),
+    #[error("failed to read the block timestamp: {0}")]
+    TimeProviderFailed(#[from] TimeProviderError),
+    #[error("block timestamp {current} is not after the previous block's timestamp {previous}")]
+    TimestampNotMonotonic { previous: u64, current: u64 },
+}
+
+impl BuildBlockError {
+    /// Returns `true` if the production of blocks must halt, i.e. the store rejected the block or
+    /// its data, or its chain diverged from the block producer's, which building the block again
+    /// can't fix.
+    pub fn halts_production(&self) -> bool {
+        match self {
+            Self::ApplyBlockFailed(ApplyBlockError::StoreRequestFailed(err))
+            | Self::GetBlockInputsFailed(err) => !err.is_retriable(),
+            Self::ApplyBlockFailed(ApplyBlockError::ChainTipMismatch { .. }) => true,
+            _ => false,
+        }
+    }
+}
+
+// Time provider errors
+// =================================================================================================
+
+#[derive(Debug, PartialEq, Eq, Error)]
+pub enum TimeProviderError {
+    #[error("system time is before the UNIX epoch")]
+    BeforeUnixEpoch,
+    #[error(
+        "system clock drifted by {drift_ms}ms from the NTP server, max drift is {max_drift_ms}ms"
+    )]
+    ClockDrift { drift_ms: i64, max_drift_ms: u64 },
 }
 
-// Transaction inputs errors
+// Store client errors
 // =================================================================================================
 
+/// Failure of a request sent to the store, classified by whether sending it again may succeed.
 #[derive(Debug, PartialEq, Eq, Error)]
-pub enum TxInputsError {
-    #[error("gRPC client failed with error: {0}")]
-    GrpcClientError(String),
+pub enum StoreClientError {
+    /// The store couldn't be reached or didn't answer in time, e.g. because it restarts. The
+    /// outcome of the request is unknown
+    #[error("store is unavailable: {0}")]
+    Unavailable(String),
+    /// The store's response couldn't be parsed or doesn't match the request
     #[error("malformed response from store: {0}")]
     MalformedResponse(String),
-    #[error("failed to parse protobuf message: {0}")]
-    ConversionError(#[from] ConversionError),
-    #[error("dummy")]
-    Dummy,
+    /// The store aborted the request without applying any of its changes, e.g. because its
+    /// database transaction failed to commit
+    #[error("store aborted the request: {0}")]
+    Aborted(String),
+    /// The store rejected the request
+    #[error("store rejected the request: {0}")]
+    Rejected(String),
+}
+
+impl StoreClientError {
+    /// Returns `true` if the request may succeed when sent again, i.e. the store couldn't be
+    /// reached or aborted the request.
+    pub fn is_retriable(&self) -> bool {
+        matches!(self, Self::Unavailable(_) | Self::Aborted(_))
+    }
+}
+
+impl From<Status> for StoreClientError {
+    fn from(status: Status) -> Self {
+        match status.code() {
+            Code::Unavailable | Code::Unknown | Code::Cancelled | Code::DeadlineExceeded => {
+                Self::Unavailable(status.message().to_string())
+            },
+            Code::Aborted => Self::Aborted(status.message().to_string()),
+            _ => Self::Rejected(status.message().to_string()),
+        }
+    }
+}
+
+impl From<ConversionError> for StoreClientError {
+    fn from(err: ConversionError) -> Self {
+        Self::MalformedResponse(err.to_string())
+    }
+}
+
+impl From<BlockInputsError> for StoreClientError {
+    fn from(err: BlockInputsError) -> Self {
+        Self::MalformedResponse(err.to_string())
+    }
+}
+
+// Signing key errors
+// =================================================================================================
+
+#[derive(Debug, Error)]
+pub enum SigningKeyError {
+    #[error("Failed to access the signing key file: {0}")]
+    IoError(#[from] std::io::Error),
+    #[error("Signing key file doesn't contain a valid key: {0}")]
+    InvalidKey(String),
+}
+
+// Fee errors
+// =================================================================================================
+
+#[derive(Debug, PartialEq, Eq, Error)]
+pub enum FeeError {
+    #[error("Transaction doesn't create a note paying its fee to the operator {0}")]
+    MissingFeeNote(AccountId),
+    #[error("Invalid fee note: {0}")]
+    InvalidFeeNote(String),
+    #[error("Fee note {0} isn't created by the transaction")]
+    FeeNoteNotCreated(NoteId),
+    #[error("Fee note {0} isn't paid to the operator {1}")]
+    WrongRecipient(NoteId, AccountId),
+    #[error("Fee of {amount} of faucet {faucet_id} is below the minimum of {min_amount}")]
+    FeeTooLow {
+        faucet_id: AccountId,
+        amount: u64,
+        min_amount: u64,
+    },
 }
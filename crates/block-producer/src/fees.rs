@@ -0,0 +1,228 @@
+use std::{collections::BTreeMap, sync::Mutex};
+
+use miden_node_proto::domain::transactions::TransactionFee;
+use miden_objects::{
+    accounts::AccountId,
+    assets::Asset,
+    notes::Note,
+    transaction::{OutputNote, ProvenTransaction, TransactionId},
+    utils::serde::Deserializable,
+    AccountError, Felt,
+};
+
+use crate::{config::FeeConfig, errors::FeeError};
+
+// FEES
+// ================================================================================================
+
+/// Verifies the fees paid by the submitted transactions and keeps track of them until their block
+/// is committed.
+///
+/// A transaction pays its fee with a pay-to-id note sending assets of the configured faucet to the
+/// operator account, the first input of such a note being the id of the account. The note is
+/// either one of the public notes created by the transaction, or a private one whose details are
+/// submitted along with the transaction.
+#[derive(Debug)]
+pub struct Fees {
+    operator: AccountId,
+    faucet_id: AccountId,
+    min_amount: u64,
+    /// Fees of the transactions submitted and not yet committed in a block.
+    paid: Mutex<BTreeMap<TransactionId, TransactionFee>>,
+}
+
+impl Fees {
+    pub fn new(operator: AccountId, faucet_id: AccountId, min_amount: u64) -> Self {
+        Self {
+            operator,
+            faucet_id,
+            min_amount,
+            paid: Default::default(),
+        }
+    }
+
+    /// Parses the accounts of `config`.
+    pub fn from_config(config: &FeeConfig) -> Result<Self, AccountError> {
+        Ok(Self::new(config.operator()?, config.faucet()?, config.min_amount))
+    }
+
+    /// Returns the account the fees are paid to.
+    pub fn operator(&self) -> AccountId {
+        self.operator
+    }
+
+    /// Returns the fee paid by `tx`.
+    ///
+    /// `fee_note` is the serialized fee note if it is private, empty otherwise.
+    pub fn verify(
+        &self,
+        tx: &ProvenTransaction,
+        fee_note: &[u8],
+    ) -> Result<TransactionFee, FeeError> {
+        let amount = if fee_note.is_empty() {
+            tx.output_notes()
+                .iter()
+                .filter_map(|note| match note {
+                    OutputNote::Public(note) if self.is_paid_to_operator(note) => {
+                        Some(self.amount(note))
+                    },
+                    _ => None,
+                })
+                .max()
+                .ok_or(FeeError::MissingFeeNote(self.operator))?
+        } else {
+            let note = Note::read_from_bytes(fee_note)
+                .map_err(|err| FeeError::InvalidFeeNote(err.to_string()))?;
+            // The metadata isn't committed to by the note id, it is compared separately
+            if !tx
+                .output_notes()
+                .iter()
+                .any(|output| output.id() == note.id() && output.metadata() == note.metadata())
+            {
+                return Err(FeeError::FeeNoteNotCreated(note.id()));
+            }
+            if !self.is_paid_to_operator(&note) {
+                return Err(FeeError::WrongRecipient(note.id(), self.operator));
+            }
+            self.amount(&note)
+        };
+
+        if amount < self.min_amount {
+            return Err(FeeError::FeeTooLow {
+                faucet_id: self.faucet_id,
+                amount,
+                min_amount: self.min_amount,
+            });
+        }
+
+        Ok(TransactionFee { faucet_id: self.faucet_id, amount })
+    }
+
+    /// Records the fee paid by the transaction `tx_id`, once it has been accepted.
+    pub fn record(&self, tx_id: TransactionId, fee: TransactionFee) {
+        self.paid.lock().expect("fees lock poisoned").insert(tx_id, fee);
+    }
+
+    /// Forgets the fee of the transaction `tx_id`, which won't be included in a block.
+    pub fn forget(&self, tx_id: &TransactionId) {
+        self.paid.lock().expect("fees lock poisoned").remove(tx_id);
+    }
+
+    /// Returns the fee paid by the transaction `tx_id`, if it has been recorded.
+    pub fn paid_by(&self, tx_id: &TransactionId) -> Option<TransactionFee> {
+        self.paid.lock().expect("fees lock poisoned").get(tx_id).copied()
+    }
+
+    /// Forgets the fees of the transactions committed in a block.
+    pub fn remove<'a>(&self, tx_ids: impl IntoIterator<Item = &'a TransactionId>) {
+        let mut paid = self.paid.lock().expect("fees lock poisoned");
+        for tx_id in tx_ids {
+            paid.remove(tx_id);
+        }
+    }
+
+    fn is_paid_to_operator(&self, note: &Note) -> bool {
+        note.inputs().values().first() == Some(&Felt::from(self.operator))
+    }
+
+    /// Returns the amount of the configured faucet's asset sent by `note`.
+    fn amount(&self, note: &Note) -> u64 {
+        note.assets()
+            .iter()
+            .filter_map(|asset| match asset {
+                Asset::Fungible(asset) if asset.faucet_id() == self.faucet_id => {
+                    Some(asset.amount())
+                },
+                _ => None,
+            })
+            .sum()
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use miden_objects::{
+        accounts::{
+            ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN, ACCOUNT_ID_REGULAR_ACCOUNT_IMMUTABLE_CODE_ON_CHAIN,
+        },
+        assembly::ProgramAst,
+        assets::FungibleAsset,
+        notes::{NoteAssets, NoteInputs, NoteMetadata, NoteRecipient, NoteScript, NoteType},
+        utils::Serializable,
+        Digest, ONE, ZERO,
+    };
+
+    use super::*;
+    use crate::test_utils::MockProvenTxBuilder;
+
+    fn fee_note(
+        sender: AccountId,
+        recipient: AccountId,
+        faucet_id: AccountId,
+        amount: u64,
+    ) -> Note {
+        let script = NoteScript::from_parts(
+            ProgramAst::parse("begin push.1 drop end").unwrap(),
+            Digest::default(),
+        );
+        let inputs = NoteInputs::new(vec![recipient.into()]).unwrap();
+        let asset = FungibleAsset::new(faucet_id, amount).unwrap();
+
+        Note::new(
+            NoteAssets::new(vec![asset.into()]).unwrap(),
+            NoteMetadata::new(sender, NoteType::OffChain, 0.into(), ZERO).unwrap(),
+            NoteRecipient::new([ONE; 4], script, inputs),
+        )
+    }
+
+    #[test]
+    fn fee_notes_are_verified() {
+        let operator =
+            AccountId::try_from(ACCOUNT_ID_REGULAR_ACCOUNT_IMMUTABLE_CODE_ON_CHAIN).unwrap();
+        let faucet_id = AccountId::try_from(ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN).unwrap();
+        let fees = Fees::new(operator, faucet_id, 10);
+
+        let sender = MockProvenTxBuilder::with_account_index(0).build().account_id();
+        let private_note = fee_note(sender, operator, faucet_id, 15);
+        let public_note = fee_note(sender, operator, faucet_id, 20);
+        let tx = MockProvenTxBuilder::with_account_index(0)
+            .notes_created(vec![
+                OutputNote::Private(private_note.clone().into()),
+                OutputNote::Public(public_note),
+            ])
+            .build();
+
+        let fee = TransactionFee { faucet_id, amount: 15 };
+        assert_eq!(fees.verify(&tx, &private_note.to_bytes()), Ok(fee));
+        assert_eq!(fees.verify(&tx, &[]), Ok(TransactionFee { faucet_id, amount: 20 }));
+
+        // the note must be created by the transaction
+        let other_note = fee_note(sender, operator, faucet_id, 16);
+        assert_eq!(
+            fees.verify(&tx, &other_note.to_bytes()),
+            Err(FeeError::FeeNoteNotCreated(other_note.id()))
+        );
+
+        // and pay at least the minimum fee
+        let low_note = fee_note(sender, operator, faucet_id, 5);
+        let tx = MockProvenTxBuilder::with_account_index(0)
+            .notes_created(vec![OutputNote::Public(low_note.clone())])
+            .build();
+        assert_eq!(
+            fees.verify(&tx, &low_note.to_bytes()),
+            Err(FeeError::FeeTooLow { faucet_id, amount: 5, min_amount: 10 })
+        );
+
+        // to the operator
+        let tx = MockProvenTxBuilder::with_account_index(0).build();
+        assert_eq!(fees.verify(&tx, &[]), Err(FeeError::MissingFeeNote(operator)));
+
+        fees.record(tx.id(), fee);
+        assert_eq!(fees.paid_by(&tx.id()), Some(fee));
+        fees.remove([&tx.id()]);
+        assert_eq!(fees.paid_by(&tx.id()), None);
+    }
+}
@@ -1,6 +1,7 @@
 use std::{sync::Arc, time::Duration};
 
 use batch_builder::batch::TransactionBatch;
+use miden_node_proto::domain::pagination::PageSize;
 use miden_objects::transaction::ProvenTransaction;
 use tokio::sync::RwLock;
 
@@ -10,13 +11,21 @@ pub mod test_utils;
 mod batch_builder;
 mod block_builder;
 mod errors;
+mod fees;
+mod mempool;
+mod metering;
 mod state_view;
 mod store;
+mod timeline;
 mod txqueue;
 
 pub mod block;
+pub mod clock;
 pub mod config;
+pub mod policy;
 pub mod server;
+pub mod signer;
+pub mod verifier;
 
 // TYPE ALIASES
 // =================================================================================================
@@ -41,3 +50,18 @@ const SERVER_BUILD_BATCH_FREQUENCY: Duration = Duration::from_secs(2);
 
 /// Maximum number of batches per block
 const SERVER_MAX_BATCHES_PER_BLOCK: usize = 4;
+
+/// Maximum number of operator transactions in the priority lane
+const SERVER_PRIORITY_LANE_CAPACITY: usize = 4;
+
+/// Number of the latest blocks whose production timeline is kept
+const SERVER_BLOCK_TIMELINES: usize = 1024;
+
+/// Maximum number of batches proven concurrently
+const SERVER_MAX_IN_FLIGHT_BATCHES: usize = 4;
+
+/// Size, in bytes, of the block proofs fabricated in simulation mode
+const SIMULATED_BLOCK_PROOF_SIZE: usize = 1024;
+
+/// Sizes of the pages of `GetMempoolContents`, in transactions
+const MEMPOOL_CONTENTS_PAGE_SIZE: PageSize = PageSize { default: 100, max: 1000 };
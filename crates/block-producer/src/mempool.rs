@@ -0,0 +1,292 @@
+use std::{
+    collections::BTreeMap,
+    time::{Duration, Instant},
+};
+
+use miden_node_proto::generated::responses::{
+    AccountPendingTransactions, GetMempoolStatsResponse, PendingTransaction as PendingTransactionPb,
+};
+use miden_objects::{accounts::AccountId, notes::Nullifier, transaction::TransactionId};
+use tokio::sync::RwLock;
+
+use crate::{txqueue::Lane, ProvenTransaction};
+
+// MEMPOOL
+// ================================================================================================
+
+/// Tracks the transactions accepted by the block producer until they are included in a block.
+///
+/// This is only used to inspect the block production pipeline, the transactions themselves are
+/// held by the [TransactionQueue](crate::txqueue::TransactionQueue) and the batch builder.
+#[derive(Debug, Default)]
+pub struct Mempool {
+    inner: RwLock<MempoolInner>,
+}
+
+#[derive(Debug, Default)]
+struct MempoolInner {
+    next_sequence_number: u64,
+    next_batch: u64,
+    transactions: BTreeMap<TransactionId, TrackedTransaction>,
+}
+
+#[derive(Debug)]
+struct TrackedTransaction {
+    /// Order in which the transaction was accepted
+    sequence_number: u64,
+    account_id: AccountId,
+    nullifiers: Vec<Nullifier>,
+    accepted_at: Instant,
+    lane: Lane,
+    /// Local number of the batch containing the transaction, [None] while it is queued
+    batch: Option<u64>,
+}
+
+impl Mempool {
+    /// Starts tracking `tx` of `lane`, waiting to be batched.
+    pub async fn add(&self, tx: &ProvenTransaction, lane: Lane) {
+        let mut inner = self.inner.write().await;
+        let tracked = TrackedTransaction {
+            sequence_number: inner.next_sequence_number,
+            account_id: tx.account_id(),
+            nullifiers: tx.input_notes().iter().cloned().collect(),
+            accepted_at: Instant::now(),
+            lane,
+            batch: None,
+        };
+
+        inner.next_sequence_number += 1;
+        inner.transactions.insert(tx.id(), tracked);
+    }
+
+    /// Marks the transactions `tx_ids` as part of a new batch, and returns the longest time one of
+    /// them waited to be batched, [None] if none is tracked.
+    pub async fn add_batch(
+        &self,
+        tx_ids: impl IntoIterator<Item = TransactionId>,
+    ) -> Option<Duration> {
+        let mut inner = self.inner.write().await;
+        let batch = inner.next_batch;
+        inner.next_batch += 1;
+
+        let mut longest_wait = None;
+        for tx_id in tx_ids {
+            if let Some(tx) = inner.transactions.get_mut(&tx_id) {
+                tx.batch = Some(batch);
+                longest_wait = longest_wait.max(Some(tx.accepted_at.elapsed()));
+            }
+        }
+
+        longest_wait
+    }
+
+    /// Marks the transactions `tx_ids`, whose batch failed, as waiting to be batched again.
+    pub async fn requeue(&self, tx_ids: impl IntoIterator<Item = TransactionId>) {
+        let mut inner = self.inner.write().await;
+        for tx_id in tx_ids {
+            if let Some(tx) = inner.transactions.get_mut(&tx_id) {
+                tx.batch = None;
+            }
+        }
+    }
+
+    /// Stops tracking the transactions `tx_ids`, included in an applied block.
+    pub async fn remove(&self, tx_ids: impl IntoIterator<Item = TransactionId>) {
+        let mut inner = self.inner.write().await;
+        for tx_id in tx_ids {
+            inner.transactions.remove(&tx_id);
+        }
+    }
+
+    /// Returns the number of tracked transactions of `lane`, batched or not.
+    pub async fn num_in_lane(&self, lane: Lane) -> usize {
+        self.inner
+            .read()
+            .await
+            .transactions
+            .values()
+            .filter(|tx| tx.lane == lane)
+            .count()
+    }
+
+    /// Returns the aggregated state of the tracked transactions.
+    pub async fn stats(&self) -> MempoolStats {
+        let inner = self.inner.read().await;
+
+        let mut stats = MempoolStats::default();
+        let mut batches = Vec::new();
+        for tx in inner.transactions.values() {
+            match tx.batch {
+                Some(batch) => {
+                    stats.batched_transactions += 1;
+                    batches.push(batch);
+                },
+                None => stats.queued_transactions += 1,
+            }
+            stats.oldest_transaction_age =
+                stats.oldest_transaction_age.max(tx.accepted_at.elapsed());
+            *stats.accounts.entry(tx.account_id).or_default() += 1;
+        }
+
+        batches.sort_unstable();
+        batches.dedup();
+        stats.in_flight_batches = batches.len();
+
+        stats
+    }
+
+    /// Returns at most `limit` tracked transactions accepted after the transaction whose sequence
+    /// number is `after`, or from the oldest one if [None], the oldest first.
+    pub async fn contents(&self, after: Option<u64>, limit: usize) -> Vec<PendingTransaction> {
+        let inner = self.inner.read().await;
+
+        let mut transactions: Vec<_> = inner
+            .transactions
+            .iter()
+            .filter(|(_, tx)| after.map_or(true, |after| tx.sequence_number > after))
+            .collect();
+        transactions.sort_by_key(|(_, tx)| tx.sequence_number);
+
+        transactions
+            .into_iter()
+            .take(limit)
+            .map(|(&id, tx)| PendingTransaction {
+                sequence_number: tx.sequence_number,
+                id,
+                account_id: tx.account_id,
+                nullifiers: tx.nullifiers.clone(),
+                age: tx.accepted_at.elapsed(),
+                batched: tx.batch.is_some(),
+                priority: tx.lane == Lane::Priority,
+            })
+            .collect()
+    }
+}
+
+// MEMPOOL STATS
+// ================================================================================================
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct MempoolStats {
+    pub queued_transactions: usize,
+    pub batched_transactions: usize,
+    pub in_flight_batches: usize,
+    /// Time elapsed since the oldest tracked transaction was accepted, zero if there is none
+    pub oldest_transaction_age: Duration,
+    /// Number of tracked transactions per account
+    pub accounts: BTreeMap<AccountId, usize>,
+}
+
+impl From<MempoolStats> for GetMempoolStatsResponse {
+    fn from(stats: MempoolStats) -> Self {
+        Self {
+            queued_transactions: stats.queued_transactions as u32,
+            batched_transactions: stats.batched_transactions as u32,
+            in_flight_batches: stats.in_flight_batches as u32,
+            oldest_transaction_age_ms: stats.oldest_transaction_age.as_millis() as u64,
+            accounts: stats
+                .accounts
+                .into_iter()
+                .map(|(account_id, num_transactions)| AccountPendingTransactions {
+                    account_id: Some(account_id.into()),
+                    num_transactions: num_transactions as u32,
+                })
+                .collect(),
+        }
+    }
+}
+
+// PENDING TRANSACTION
+// ================================================================================================
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingTransaction {
+    /// Order in which the transaction was accepted
+    pub sequence_number: u64,
+    pub id: TransactionId,
+    pub account_id: AccountId,
+    pub nullifiers: Vec<Nullifier>,
+    /// Time elapsed since the transaction was accepted
+    pub age: Duration,
+    pub batched: bool,
+    /// Whether the transaction was submitted in the priority lane
+    pub priority: bool,
+}
+
+impl From<PendingTransaction> for PendingTransactionPb {
+    fn from(tx: PendingTransaction) -> Self {
+        Self {
+            transaction_id: Some(tx.id.into()),
+            account_id: Some(tx.account_id.into()),
+            nullifiers: tx.nullifiers.into_iter().map(Into::into).collect(),
+            age_ms: tx.age.as_millis() as u64,
+            batched: tx.batched,
+            priority: tx.priority,
+        }
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::MockProvenTxBuilder;
+
+    #[tokio::test]
+    async fn test_mempool_tracks_transactions_until_applied() {
+        let mempool = Mempool::default();
+        let txs: Vec<_> = (0..3)
+            .map(|i| {
+                MockProvenTxBuilder::with_account_index(i)
+                    .nullifiers_range(i as u64..i as u64 + 1)
+                    .build()
+            })
+            .collect();
+
+        for tx in &txs[..2] {
+            mempool.add(tx, Lane::User).await;
+        }
+        mempool.add(&txs[2], Lane::Priority).await;
+        assert_eq!(mempool.num_in_lane(Lane::Priority).await, 1);
+        mempool.add_batch([txs[0].id(), txs[1].id()]).await;
+
+        let stats = mempool.stats().await;
+        assert_eq!(stats.queued_transactions, 1);
+        assert_eq!(stats.batched_transactions, 2);
+        assert_eq!(stats.in_flight_batches, 1);
+        assert_eq!(stats.accounts.len(), 3);
+        assert!(stats.accounts.values().all(|&count| count == 1));
+
+        let contents = mempool.contents(None, usize::MAX).await;
+        assert_eq!(contents.len(), 3);
+        assert_eq!(contents[0].id, txs[0].id());
+        assert!(contents[0].batched);
+        assert_eq!(
+            contents[0].nullifiers,
+            txs[0].input_notes().iter().cloned().collect::<Vec<_>>()
+        );
+        assert_eq!(contents[2].id, txs[2].id());
+        assert!(!contents[2].batched);
+        assert!(contents[2].priority && !contents[0].priority);
+
+        // The pages of the contents follow the order of acceptance
+        let page = mempool.contents(Some(contents[0].sequence_number), 1).await;
+        assert_eq!(page.len(), 1);
+        assert_eq!(page[0].id, txs[1].id());
+
+        // A failed batch puts its transactions back in the queue
+        mempool.requeue([txs[1].id()]).await;
+        mempool.add_batch([txs[2].id()]).await;
+
+        let stats = mempool.stats().await;
+        assert_eq!(stats.queued_transactions, 1);
+        assert_eq!(stats.in_flight_batches, 2);
+
+        mempool.remove(txs.iter().map(|tx| tx.id())).await;
+
+        assert_eq!(mempool.stats().await, MempoolStats::default());
+        assert!(mempool.contents(None, usize::MAX).await.is_empty());
+    }
+}
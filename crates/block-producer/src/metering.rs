@@ -0,0 +1,49 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use miden_objects::transaction::ProvenTransaction;
+
+// CYCLE METERING
+// ================================================================================================
+
+/// Returns the estimated execution cost of `tx`, in VM cycles.
+///
+/// The cost is the length of the execution trace of the transaction's proof, i.e. the cycles it
+/// executed padded to the next power of two, which is what the proving time depends on.
+pub fn transaction_cycles(tx: &ProvenTransaction) -> u64 {
+    tx.proof().stark_proof().trace_length() as u64
+}
+
+/// Utilization of the cycle budget of the blocks, recorded by the block builder and reported in
+/// the status of the block producer.
+#[derive(Debug, Default)]
+pub struct BlockMetering {
+    /// Maximum estimated cycles of a block, [None] if the blocks aren't metered
+    budget: Option<u64>,
+    last_block_cycles: AtomicU64,
+}
+
+impl BlockMetering {
+    pub fn new(budget: Option<u64>) -> Self {
+        Self {
+            budget,
+            last_block_cycles: AtomicU64::new(0),
+        }
+    }
+
+    /// Returns the maximum estimated cycles of a block, [None] if the blocks aren't metered.
+    pub fn budget(&self) -> Option<u64> {
+        self.budget
+    }
+
+    /// Records the estimated cycles of the last block, returning the percentage of the budget it
+    /// used if the blocks are metered.
+    pub fn record(&self, cycles: u64) -> Option<u64> {
+        self.last_block_cycles.store(cycles, Ordering::Relaxed);
+        self.budget.map(|budget| cycles.saturating_mul(100) / budget.max(1))
+    }
+
+    /// Returns the estimated cycles of the last block.
+    pub fn last_block_cycles(&self) -> u64 {
+        self.last_block_cycles.load(Ordering::Relaxed)
+    }
+}
@@ -0,0 +1,220 @@
+//! Submission policy of the operator, restricting the accounts which can submit transactions and
+//! the tags of the notes they can create, e.g. on permissioned test deployments.
+//!
+//! The policy is loaded from the configuration on startup and enforced as a [TransactionVerifier].
+//! It can be replaced at runtime through the admin API, without restarting the block producer.
+use std::{collections::BTreeSet, sync::RwLock};
+
+use miden_node_proto::{errors::ConversionError, generated::transaction as proto};
+use miden_objects::{accounts::AccountId, AccountError};
+
+use crate::{
+    config::SubmissionPolicyConfig,
+    verifier::{TransactionInputs, TransactionVerifier, VerifyTxError},
+    ProvenTransaction,
+};
+
+// POLICY RULES
+// ================================================================================================
+
+/// Accounts and note tags allowed or denied by the submission policy, the denylists taking
+/// precedence over the allowlists. An empty allowlist allows everything.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PolicyRules {
+    pub allowed_accounts: BTreeSet<AccountId>,
+    pub denied_accounts: BTreeSet<AccountId>,
+    pub allowed_note_tags: BTreeSet<u32>,
+    pub denied_note_tags: BTreeSet<u32>,
+}
+
+impl PolicyRules {
+    /// Parses the hex encoded accounts of `config`.
+    pub fn from_config(config: &SubmissionPolicyConfig) -> Result<Self, AccountError> {
+        let parse = |accounts: &[String]| -> Result<BTreeSet<AccountId>, AccountError> {
+            accounts.iter().map(|account_id| AccountId::from_hex(account_id)).collect()
+        };
+
+        Ok(Self {
+            allowed_accounts: parse(&config.allowed_accounts)?,
+            denied_accounts: parse(&config.denied_accounts)?,
+            allowed_note_tags: config.allowed_note_tags.iter().copied().collect(),
+            denied_note_tags: config.denied_note_tags.iter().copied().collect(),
+        })
+    }
+
+    /// Returns the reason of the rejection of `tx`, if it violates the rules.
+    fn check(&self, tx: &ProvenTransaction) -> Result<(), String> {
+        let account_id = tx.account_id();
+        if self.denied_accounts.contains(&account_id) {
+            return Err(format!("account {account_id} is denied"));
+        }
+        if !self.allowed_accounts.is_empty() && !self.allowed_accounts.contains(&account_id) {
+            return Err(format!("account {account_id} isn't allowed"));
+        }
+
+        for note in tx.output_notes().iter() {
+            let tag = u32::from(note.metadata().tag());
+            if self.denied_note_tags.contains(&tag) {
+                return Err(format!("note {} has the denied tag {tag}", note.id()));
+            }
+            if !self.allowed_note_tags.is_empty() && !self.allowed_note_tags.contains(&tag) {
+                return Err(format!("note {} has the tag {tag}, which isn't allowed", note.id()));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl From<PolicyRules> for proto::SubmissionPolicy {
+    fn from(rules: PolicyRules) -> Self {
+        Self {
+            allowed_accounts: rules.allowed_accounts.into_iter().map(Into::into).collect(),
+            denied_accounts: rules.denied_accounts.into_iter().map(Into::into).collect(),
+            allowed_note_tags: rules.allowed_note_tags.into_iter().collect(),
+            denied_note_tags: rules.denied_note_tags.into_iter().collect(),
+        }
+    }
+}
+
+impl TryFrom<proto::SubmissionPolicy> for PolicyRules {
+    type Error = ConversionError;
+
+    fn try_from(policy: proto::SubmissionPolicy) -> Result<Self, Self::Error> {
+        Ok(Self {
+            allowed_accounts: policy
+                .allowed_accounts
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()?,
+            denied_accounts: policy
+                .denied_accounts
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()?,
+            allowed_note_tags: policy.allowed_note_tags.into_iter().collect(),
+            denied_note_tags: policy.denied_note_tags.into_iter().collect(),
+        })
+    }
+}
+
+// SUBMISSION POLICY
+// ================================================================================================
+
+/// Submission policy enforced on the submitted transactions, whose rules can be replaced while
+/// the block producer runs.
+#[derive(Debug, Default)]
+pub struct SubmissionPolicy {
+    /// Rules set on startup, restored when the rules are reset
+    configured: PolicyRules,
+    rules: RwLock<PolicyRules>,
+}
+
+impl SubmissionPolicy {
+    pub fn new(rules: PolicyRules) -> Self {
+        Self {
+            configured: rules.clone(),
+            rules: RwLock::new(rules),
+        }
+    }
+
+    /// Returns the rules currently enforced.
+    pub fn rules(&self) -> PolicyRules {
+        self.rules.read().expect("policy lock poisoned").clone()
+    }
+
+    /// Replaces the rules enforced on the next transactions by `rules`, or by the rules set on
+    /// startup if [None]. Returns the replaced rules.
+    pub fn replace(&self, rules: Option<PolicyRules>) -> PolicyRules {
+        let rules = rules.unwrap_or_else(|| self.configured.clone());
+        std::mem::replace(&mut *self.rules.write().expect("policy lock poisoned"), rules)
+    }
+}
+
+impl TransactionVerifier for SubmissionPolicy {
+    fn verify(&self, tx: &ProvenTransaction, _: &TransactionInputs) -> Result<(), VerifyTxError> {
+        self.rules
+            .read()
+            .expect("policy lock poisoned")
+            .check(tx)
+            .map_err(VerifyTxError::PolicyViolation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use miden_objects::{
+        notes::{NoteEnvelope, NoteMetadata, NoteType},
+        transaction::OutputNote,
+        Digest, Felt, ONE, ZERO,
+    };
+
+    use super::*;
+    use crate::test_utils::{MockPrivateAccount, MockProvenTxBuilder};
+
+    fn tx_inputs(tx: &ProvenTransaction) -> TransactionInputs {
+        TransactionInputs {
+            account_id: tx.account_id(),
+            account_hash: None,
+            nullifiers: Default::default(),
+        }
+    }
+
+    fn tx_creating_note(account: &MockPrivateAccount<2>, tag: u32) -> ProvenTransaction {
+        let metadata = NoteMetadata::new(account.id, NoteType::OffChain, tag.into(), ONE).unwrap();
+        let note_hash: Digest = [Felt::from(tag), ZERO, ZERO, ONE].into();
+        let note = OutputNote::Private(NoteEnvelope::new(note_hash.into(), metadata).unwrap());
+        MockProvenTxBuilder::with_account(account.id, account.states[0], account.states[1])
+            .notes_created(vec![note])
+            .build()
+    }
+
+    #[test]
+    fn replaced_rules_apply_to_the_next_transactions() {
+        let allowed: MockPrivateAccount<2> = MockPrivateAccount::from(0);
+        let other: MockPrivateAccount<2> = MockPrivateAccount::from(1);
+        let policy = SubmissionPolicy::new(PolicyRules {
+            allowed_accounts: [allowed.id].into(),
+            denied_note_tags: [7].into(),
+            ..Default::default()
+        });
+
+        let tx = tx_creating_note(&allowed, 3);
+        assert_eq!(policy.verify(&tx, &tx_inputs(&tx)), Ok(()));
+
+        let tx = tx_creating_note(&other, 3);
+        assert_eq!(
+            policy.verify(&tx, &tx_inputs(&tx)),
+            Err(VerifyTxError::PolicyViolation(format!("account {} isn't allowed", other.id)))
+        );
+
+        let tx = tx_creating_note(&allowed, 7);
+        let note_id = tx.output_notes().get_note(0).id();
+        assert_eq!(
+            policy.verify(&tx, &tx_inputs(&tx)),
+            Err(VerifyTxError::PolicyViolation(format!("note {note_id} has the denied tag 7")))
+        );
+
+        // The accounts are denied even if they are allowed
+        let configured = policy.replace(Some(PolicyRules {
+            denied_accounts: [allowed.id].into(),
+            allowed_note_tags: [3].into(),
+            ..Default::default()
+        }));
+        let tx = tx_creating_note(&allowed, 3);
+        assert_eq!(
+            policy.verify(&tx, &tx_inputs(&tx)),
+            Err(VerifyTxError::PolicyViolation(format!("account {} is denied", allowed.id)))
+        );
+        let tx = tx_creating_note(&other, 3);
+        assert_eq!(policy.verify(&tx, &tx_inputs(&tx)), Ok(()));
+
+        // Resetting restores the configured rules
+        policy.replace(None);
+        assert_eq!(policy.rules(), configured);
+        assert_eq!(
+            PolicyRules::try_from(proto::SubmissionPolicy::from(configured.clone())),
+            Ok(configured)
+        );
+    }
+}
@@ -1,18 +1,50 @@
 use std::sync::Arc;
 
-use miden_node_proto::generated::{
-    block_producer::api_server, requests::SubmitProvenTransactionRequest,
-    responses::SubmitProvenTransactionResponse,
+use miden_node_proto::{
+    domain::{
+        pagination::{next_page_cursor, PageTokens},
+        version::{PROTOCOL_VERSION, PROTO_VERSION},
+    },
+    generated::{
+        block_producer::api_server,
+        requests::{
+            GetBatchRequest, GetBlockProducerStatusRequest, GetBlockTimelineRequest,
+            GetMempoolContentsRequest, GetMempoolStatsRequest, GetVersionRequest,
+            SetBlockProductionPausedRequest, SetLogFilterRequest, SetSubmissionPolicyRequest,
+            SimulateTransactionRequest, SubmitPriorityTransactionRequest,
+            SubmitProvenTransactionRequest,
+        },
+        responses::{
+            GetBatchResponse, GetBlockProducerStatusResponse, GetBlockTimelineResponse,
+            GetMempoolContentsResponse, GetMempoolStatsResponse, GetVersionResponse,
+            SetBlockProductionPausedResponse, SetLogFilterResponse, SetSubmissionPolicyResponse,
+            SimulateTransactionResponse, SubmitPriorityTransactionResponse,
+            SubmitProvenTransactionResponse,
+        },
+    },
+};
+use miden_node_utils::{
+    features::build_features,
+    formatting::{format_blake3_digest, format_input_notes, format_opt, format_output_notes},
+    logging::set_log_filter,
+    runtime::runtime_metrics,
 };
-use miden_node_utils::formatting::{format_input_notes, format_opt, format_output_notes};
 use miden_objects::{transaction::ProvenTransaction, utils::serde::Deserializable};
 use tonic::Status;
 use tracing::{debug, info, instrument};
 
 use crate::{
-    batch_builder::BatchBuilder,
+    batch_builder::{
+        batch::BatchId, sizing::BatchSizing, BatchBuilder, BatchLookup, BlockProduction,
+    },
+    errors::{AddTransactionError, VerifyTxError},
+    fees::Fees,
+    mempool::Mempool,
+    metering::BlockMetering,
+    policy::{PolicyRules, SubmissionPolicy},
+    timeline::BlockTimelines,
     txqueue::{TransactionQueue, TransactionValidator},
-    COMPONENT,
+    COMPONENT, MEMPOOL_CONTENTS_PAGE_SIZE,
 };
 
 // BLOCK PRODUCER
@@ -20,11 +52,77 @@ use crate::{
 
 pub struct BlockProducerApi<BB, TV> {
     queue: Arc<TransactionQueue<BB, TV>>,
+    production: Arc<BlockProduction>,
+    batches: BatchLookup,
+    mempool: Arc<Mempool>,
+    fees: Option<Arc<Fees>>,
+    metering: Arc<BlockMetering>,
+    batch_sizing: Arc<BatchSizing>,
+    policy: Arc<SubmissionPolicy>,
+    timelines: Arc<BlockTimelines>,
+    /// Whether the block producer runs in simulation mode, reported in its status
+    simulation: bool,
+    page_tokens: PageTokens,
 }
 
 impl<BB, TV> BlockProducerApi<BB, TV> {
-    pub fn new(queue: Arc<TransactionQueue<BB, TV>>) -> Self {
-        Self { queue }
+    pub fn new(
+        queue: Arc<TransactionQueue<BB, TV>>,
+        production: Arc<BlockProduction>,
+        batches: BatchLookup,
+        mempool: Arc<Mempool>,
+    ) -> Self {
+        Self {
+            queue,
+            production,
+            batches,
+            mempool,
+            fees: None,
+            metering: Default::default(),
+            batch_sizing: Arc::new(BatchSizing::new(Default::default())),
+            policy: Default::default(),
+            timelines: Default::default(),
+            simulation: false,
+            page_tokens: PageTokens::random(),
+        }
+    }
+
+    /// Requires the submitted transactions to pay the fees verified by `fees`.
+    pub fn with_fees(mut self, fees: Arc<Fees>) -> Self {
+        self.fees = Some(fees);
+        self
+    }
+
+    /// Reports the utilization of the cycle budget of the blocks recorded by `metering` in its
+    /// status.
+    pub fn with_metering(mut self, metering: Arc<BlockMetering>) -> Self {
+        self.metering = metering;
+        self
+    }
+
+    /// Reports the size of the batches and the latencies recorded by `batch_sizing` in its status.
+    pub fn with_batch_sizing(mut self, batch_sizing: Arc<BatchSizing>) -> Self {
+        self.batch_sizing = batch_sizing;
+        self
+    }
+
+    /// Replaces the rules of `policy` on the admin requests, `policy` being enforced by the
+    /// transaction validator.
+    pub fn with_policy(mut self, policy: Arc<SubmissionPolicy>) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Serves the timelines of the blocks recorded by the block builder to `timelines`.
+    pub fn with_timelines(mut self, timelines: Arc<BlockTimelines>) -> Self {
+        self.timelines = timelines;
+        self
+    }
+
+    /// Reports the block producer as running in simulation mode in its status.
+    pub fn with_simulation(mut self) -> Self {
+        self.simulation = true;
+        self
     }
 }
 
@@ -52,6 +150,7 @@ where
 
         let tx = ProvenTransaction::read_from_bytes(&request.transaction)
             .map_err(|_| Status::invalid_argument("Invalid transaction"))?;
+        self.queue.prefetch_tx_inputs(&tx);
 
         info!(
             target: COMPONENT,
@@ -67,11 +166,333 @@ where
         );
         debug!(target: COMPONENT, proof = ?tx.proof());
 
-        self.queue
-            .add_transaction(tx)
-            .await
-            .map_err(|err| Status::invalid_argument(format!("{:?}", err)))?;
+        let tx_id = tx.id();
+        if let Some(fees) = &self.fees {
+            let fee = fees
+                .verify(&tx, &request.fee_note)
+                .map_err(|err| Status::invalid_argument(err.to_string()))?;
+            info!(target: COMPONENT, tx_id = %tx_id.to_hex(), %fee, "Transaction fee verified");
+            // Recorded before the transaction is queued, so that it is known to the block builder
+            fees.record(tx_id, fee);
+        }
+
+        self.queue.add_transaction(tx).await.map_err(|err| {
+            if let Some(fees) = &self.fees {
+                fees.forget(&tx_id);
+            }
+            add_transaction_status(err)
+        })?;
 
         Ok(tonic::Response::new(SubmitProvenTransactionResponse {}))
     }
+
+    /// Queues an operator transaction in the priority lane, to be included in the next block
+    /// ahead of the user transactions. The transaction doesn't pay a fee.
+    #[instrument(
+        target = "miden-block-producer",
+        name = "block_producer:submit_priority_transaction",
+        skip_all,
+        err
+    )]
+    async fn submit_priority_transaction(
+        &self,
+        request: tonic::Request<SubmitPriorityTransactionRequest>,
+    ) -> Result<tonic::Response<SubmitPriorityTransactionResponse>, Status> {
+        let request = request.into_inner();
+
+        let tx = ProvenTransaction::read_from_bytes(&request.transaction)
+            .map_err(|_| Status::invalid_argument("Invalid transaction"))?;
+        self.queue.prefetch_tx_inputs(&tx);
+
+        info!(
+            target: COMPONENT,
+            tx_id = %tx.id().to_hex(),
+            account_id = %tx.account_id().to_hex(),
+            initial_account_hash = %tx.initial_account_hash(),
+            final_account_hash = %tx.final_account_hash(),
+            "Deserialized priority transaction"
+        );
+
+        self.queue.add_priority_transaction(tx).await.map_err(add_transaction_status)?;
+
+        Ok(tonic::Response::new(SubmitPriorityTransactionResponse {}))
+    }
+
+    /// Runs the verification of a submitted transaction without adding it to the queue.
+    ///
+    /// The rejection reason, if any, is returned in the response instead of an error status.
+    #[instrument(
+        target = "miden-block-producer",
+        name = "block_producer:simulate_transaction",
+        skip_all,
+        err
+    )]
+    async fn simulate_transaction(
+        &self,
+        request: tonic::Request<SimulateTransactionRequest>,
+    ) -> Result<tonic::Response<SimulateTransactionResponse>, Status> {
+        let request = request.into_inner();
+
+        let tx = ProvenTransaction::read_from_bytes(&request.transaction)
+            .map_err(|_| Status::invalid_argument("Invalid transaction"))?;
+
+        let simulation = self
+            .queue
+            .simulate_transaction(&tx)
+            .await
+            .map_err(|err| Status::internal(format!("{:?}", err)))?;
+
+        info!(
+            target: COMPONENT,
+            tx_id = %tx.id().to_hex(),
+            rejection = %format_opt(simulation.rejection.as_ref()),
+            "Simulated transaction"
+        );
+
+        Ok(tonic::Response::new(simulation.into()))
+    }
+
+    /// Pauses or resumes the production of blocks. Pausing returns once the block being built, if
+    /// any, has been applied to the store.
+    #[instrument(
+        target = "miden-block-producer",
+        name = "block_producer:set_block_production_paused",
+        skip_all,
+        err
+    )]
+    async fn set_block_production_paused(
+        &self,
+        request: tonic::Request<SetBlockProductionPausedRequest>,
+    ) -> Result<tonic::Response<SetBlockProductionPausedResponse>, Status> {
+        let paused = request.into_inner().paused;
+        if paused {
+            self.production.pause().await;
+        } else {
+            self.production.resume().await;
+        }
+
+        info!(target: COMPONENT, paused, "Block production updated");
+
+        Ok(tonic::Response::new(SetBlockProductionPausedResponse {}))
+    }
+
+    /// Returns the number of transactions and batches waiting to be included in a block.
+    #[instrument(
+        target = "miden-block-producer",
+        name = "block_producer:get_mempool_stats",
+        skip_all,
+        err
+    )]
+    async fn get_mempool_stats(
+        &self,
+        _request: tonic::Request<GetMempoolStatsRequest>,
+    ) -> Result<tonic::Response<GetMempoolStatsResponse>, Status> {
+        Ok(tonic::Response::new(self.mempool.stats().await.into()))
+    }
+
+    /// Returns a page of the transactions waiting to be included in a block, the oldest first.
+    #[instrument(
+        target = "miden-block-producer",
+        name = "block_producer:get_mempool_contents",
+        skip_all,
+        err
+    )]
+    async fn get_mempool_contents(
+        &self,
+        request: tonic::Request<GetMempoolContentsRequest>,
+    ) -> Result<tonic::Response<GetMempoolContentsResponse>, Status> {
+        // The cursor is the sequence number of the last transaction of the previous page
+        const SCOPE: &str = "GetMempoolContents";
+        let page = self
+            .page_tokens
+            .decode::<u64>(SCOPE, request.into_inner().page, MEMPOOL_CONTENTS_PAGE_SIZE)
+            .map_err(|err| Status::invalid_argument(err.to_string()))?;
+
+        // One more transaction tells whether there is a next page
+        let mut transactions = self.mempool.contents(page.cursor, page.size + 1).await;
+        let next = next_page_cursor(&mut transactions, page.size, |tx| tx.sequence_number);
+
+        Ok(tonic::Response::new(GetMempoolContentsResponse {
+            transactions: transactions.into_iter().map(Into::into).collect(),
+            page: Some(self.page_tokens.page(SCOPE, next.as_ref(), None)),
+        }))
+    }
+
+    /// Returns the transactions and the proving status of a batch being proven or waiting to be
+    /// included in a block.
+    ///
+    /// Fails with `NOT_FOUND` if the batch is unknown, its proving failed or it was already
+    /// included in a block.
+    #[instrument(
+        target = "miden-block-producer",
+        name = "block_producer:get_batch",
+        skip_all,
+        err
+    )]
+    async fn get_batch(
+        &self,
+        request: tonic::Request<GetBatchRequest>,
+    ) -> Result<tonic::Response<GetBatchResponse>, Status> {
+        let batch_id: [u8; 32] = request
+            .into_inner()
+            .batch_id
+            .try_into()
+            .map_err(|_| Status::invalid_argument("Batch id must be 32 bytes"))?;
+        let batch_id = BatchId::from(batch_id);
+
+        let batch = self.batches.get(batch_id).await.ok_or_else(|| {
+            Status::not_found(format!("Batch {} not found", format_blake3_digest(batch_id)))
+        })?;
+
+        Ok(tonic::Response::new(batch.into()))
+    }
+
+    /// Returns the stages reached by a block while it was produced, with their timestamps.
+    ///
+    /// Fails with `NOT_FOUND` if the production of the block didn't start, or if it is older than
+    /// the kept timelines.
+    #[instrument(
+        target = "miden-block-producer",
+        name = "block_producer:get_block_timeline",
+        skip_all,
+        err
+    )]
+    async fn get_block_timeline(
+        &self,
+        request: tonic::Request<GetBlockTimelineRequest>,
+    ) -> Result<tonic::Response<GetBlockTimelineResponse>, Status> {
+        let block_num = request.into_inner().block_num;
+
+        let timeline = self.timelines.get(block_num).ok_or_else(|| {
+            Status::not_found(format!("No timeline for block {}", format_opt(block_num.as_ref())))
+        })?;
+
+        Ok(tonic::Response::new(timeline.into()))
+    }
+
+    /// Returns the version of the block producer, the number of pending transactions and whether
+    /// the production of blocks is paused.
+    #[instrument(
+        target = "miden-block-producer",
+        name = "block_producer:get_status",
+        skip_all,
+        err
+    )]
+    async fn get_status(
+        &self,
+        _request: tonic::Request<GetBlockProducerStatusRequest>,
+    ) -> Result<tonic::Response<GetBlockProducerStatusResponse>, Status> {
+        let stats = self.mempool.stats().await;
+        let sizing = self.batch_sizing.status();
+
+        Ok(tonic::Response::new(GetBlockProducerStatusResponse {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            mempool_depth: (stats.queued_transactions + stats.batched_transactions) as u32,
+            in_flight_batches: stats.in_flight_batches as u32,
+            block_production_paused: self.production.is_paused().await,
+            runtime: runtime_metrics().map(Into::into),
+            simulation: self.simulation,
+            last_block_cycles: self.metering.last_block_cycles(),
+            block_cycle_budget: self.metering.budget().unwrap_or_default(),
+            target_batch_size: sizing.target_txs as u32,
+            batch_proving_latency_ms: sizing.proving_latency.as_millis() as u64,
+            confirmation_latency_ms: sizing.confirmation_latency.as_millis() as u64,
+        }))
+    }
+
+    /// Returns the versions of the block producer.
+    #[instrument(
+        target = "miden-block-producer",
+        name = "block_producer:get_version",
+        skip_all,
+        err
+    )]
+    async fn get_version(
+        &self,
+        _request: tonic::Request<GetVersionRequest>,
+    ) -> Result<tonic::Response<GetVersionResponse>, Status> {
+        Ok(tonic::Response::new(GetVersionResponse {
+            component: COMPONENT.to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            protocol_version: Some(PROTOCOL_VERSION.into()),
+            proto_version: PROTO_VERSION.to_string(),
+            genesis_hash: None,
+            features: build_features(),
+            components: Vec::new(),
+        }))
+    }
+
+    /// Replaces the log filter of the block producer, returning the replaced one.
+    #[instrument(
+        target = "miden-block-producer",
+        name = "block_producer:set_log_filter",
+        skip_all,
+        err
+    )]
+    async fn set_log_filter(
+        &self,
+        request: tonic::Request<SetLogFilterRequest>,
+    ) -> Result<tonic::Response<SetLogFilterResponse>, Status> {
+        let filter = request.into_inner().filter;
+        let previous_filter = set_log_filter(&filter)?;
+
+        info!(target: COMPONENT, filter, previous_filter, "Log filter updated");
+
+        Ok(tonic::Response::new(SetLogFilterResponse { previous_filter }))
+    }
+
+    /// Replaces the submission policy enforced on the next transactions, returning the replaced
+    /// one. Without a policy, the configured policy is restored.
+    #[instrument(
+        target = "miden-block-producer",
+        name = "block_producer:set_submission_policy",
+        skip_all,
+        err
+    )]
+    async fn set_submission_policy(
+        &self,
+        request: tonic::Request<SetSubmissionPolicyRequest>,
+    ) -> Result<tonic::Response<SetSubmissionPolicyResponse>, Status> {
+        let rules = request
+            .into_inner()
+            .policy
+            .map(PolicyRules::try_from)
+            .transpose()
+            .map_err(|err| Status::invalid_argument(format!("Invalid policy: {err}")))?;
+        let reset = rules.is_none();
+        let previous_policy = self.policy.replace(rules);
+
+        info!(target: COMPONENT, reset, "Submission policy updated");
+
+        Ok(tonic::Response::new(SetSubmissionPolicyResponse {
+            previous_policy: Some(previous_policy.into()),
+        }))
+    }
+}
+
+// HELPERS
+// ================================================================================================
+
+/// Returns the status of a transaction the queue didn't accept.
+fn add_transaction_status(err: AddTransactionError) -> Status {
+    let message = format!("{:?}", err);
+    let err = match err {
+        AddTransactionError::VerificationFailed(err) => err,
+        // The lane has room again once a block includes its transactions
+        AddTransactionError::PriorityLaneFull { .. } => {
+            return Status::resource_exhausted(message);
+        },
+        AddTransactionError::PriorityLaneDisabled => return Status::failed_precondition(message),
+    };
+    if let Some(rejection) = err.rejection() {
+        return rejection.into_status(message);
+    }
+    // The transaction can be submitted again once the store is back
+    match err {
+        VerifyTxError::StoreRequestFailed(err) if err.is_retriable() => {
+            Status::unavailable(message)
+        },
+        VerifyTxError::StoreRequestFailed(_) => Status::internal(message),
+        _ => Status::invalid_argument(message),
+    }
 }
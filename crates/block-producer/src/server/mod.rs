@@ -1,19 +1,40 @@
 use std::{net::ToSocketAddrs, sync::Arc};
 
-use miden_node_proto::generated::{block_producer::api_server, store::api_client as store_client};
-use miden_node_utils::errors::ApiError;
+use miden_node_proto::{
+    domain::version::{ProtocolVersion, PROTOCOL_VERSION},
+    generated::{
+        block_producer::api_server,
+        requests::{GetGenesisRequest, GetVersionRequest},
+        store::api_client as store_client,
+    },
+};
+use miden_node_utils::{
+    access_log::AccessLogLayer,
+    chain_params::{ChainGenesis, ChainParameters},
+    errors::ApiError,
+    formatting::format_opt,
+    runtime::{run_named, spawn_named, start_runtime_metrics_sampling},
+    transport::ComponentChannel,
+};
+use miden_objects::Digest;
 use tonic::transport::Server;
-use tracing::info;
+use tracing::{info, warn};
 
 use crate::{
-    batch_builder::{DefaultBatchBuilder, DefaultBatchBuilderOptions},
+    batch_builder::{sizing::BatchSizing, DefaultBatchBuilder, DefaultBatchBuilderOptions},
     block_builder::DefaultBlockBuilder,
-    config::BlockProducerConfig,
+    config::{BlockProducerConfig, FeeConfig},
+    fees::Fees,
+    mempool::Mempool,
+    metering::BlockMetering,
+    policy::{PolicyRules, SubmissionPolicy},
+    signer::BlockSigner,
     state_view::DefaultStateView,
     store::DefaultStore,
+    timeline::BlockTimelines,
     txqueue::{TransactionQueue, TransactionQueueOptions},
-    COMPONENT, SERVER_BATCH_SIZE, SERVER_BLOCK_FREQUENCY, SERVER_BUILD_BATCH_FREQUENCY,
-    SERVER_MAX_BATCHES_PER_BLOCK,
+    verifier::TransactionVerifier,
+    COMPONENT, SERVER_MAX_BATCHES_PER_BLOCK,
 };
 
 pub mod api;
@@ -22,37 +43,27 @@ pub mod api;
 // ================================================================================================
 
 pub async fn serve(config: BlockProducerConfig) -> Result<(), ApiError> {
-    info!(target: COMPONENT, %config, "Initializing server");
-
-    let store = Arc::new(DefaultStore::new(
-        store_client::ApiClient::connect(config.store_url.to_string())
-            .await
-            .map_err(|err| ApiError::DatabaseConnectionFailed(err.to_string()))?,
-    ));
-    let state_view = Arc::new(DefaultStateView::new(store.clone(), config.verify_tx_proofs));
-
-    let block_builder = DefaultBlockBuilder::new(store.clone(), state_view.clone());
-    let batch_builder_options = DefaultBatchBuilderOptions {
-        block_frequency: SERVER_BLOCK_FREQUENCY,
-        max_batches_per_block: SERVER_MAX_BATCHES_PER_BLOCK,
-    };
-    let batch_builder =
-        Arc::new(DefaultBatchBuilder::new(Arc::new(block_builder), batch_builder_options));
-
-    let transaction_queue_options = TransactionQueueOptions {
-        build_batch_frequency: SERVER_BUILD_BATCH_FREQUENCY,
-        batch_size: SERVER_BATCH_SIZE,
-    };
-    let queue = Arc::new(TransactionQueue::new(
-        state_view,
-        batch_builder.clone(),
-        transaction_queue_options,
-    ));
+    serve_with_verifier(config, None).await
+}
 
-    let block_producer = api_server::ApiServer::new(api::BlockProducerApi::new(queue.clone()));
+/// Serves the block producer, running `verifier` after the default verification of the submitted
+/// transactions, e.g. to enforce the operator's policies.
+pub async fn serve_with_verifier(
+    config: BlockProducerConfig,
+    verifier: Option<Arc<dyn TransactionVerifier>>,
+) -> Result<(), ApiError> {
+    info!(target: COMPONENT, %config, "Initializing server");
 
-    tokio::spawn(async move { queue.run().await });
-    tokio::spawn(async move { batch_builder.run().await });
+    let store = ComponentChannel::connect_with(config.store_url.to_string(), &config.store_client)
+        .await
+        .map_err(|err| ApiError::DatabaseConnectionFailed(err.to_string()))?;
+    check_store_version(store.clone()).await?;
+    let genesis_hash = config
+        .genesis_hash()
+        .map_err(|err| ApiError::GenesisLoadFailed(format!("invalid genesis hash: {err}")))?;
+    let genesis = load_genesis(store.clone(), genesis_hash).await?;
+    let config = apply_chain_parameters(config, genesis.parameters);
+    let block_producer = init_with_verifier(&config, store, verifier)?;
 
     info!(target: COMPONENT, "Server initialized");
 
@@ -63,11 +74,201 @@ pub async fn serve(config: BlockProducerConfig) -> Result<(), ApiError> {
         .next()
         .ok_or_else(|| ApiError::AddressResolutionFailed(config.endpoint.to_string()))?;
 
-    Server::builder()
+    let server = Server::builder()
+        .layer(AccessLogLayer::new(COMPONENT, config.access_log.as_ref()))
         .add_service(block_producer)
-        .serve(addr)
+        .serve(addr);
+    run_named("block-producer-grpc-server", server)
         .await
         .map_err(ApiError::ApiServeFailed)?;
 
     Ok(())
 }
+
+/// Refuses to work with a store implementing another major version of the protocol.
+async fn check_store_version(store: ComponentChannel) -> Result<(), ApiError> {
+    let version = store_client::ApiClient::new(store)
+        .get_version(GetVersionRequest {})
+        .await
+        .map_err(|status| ApiError::VersionHandshakeFailed("store", status.to_string()))?
+        .into_inner();
+    let protocol_version = ProtocolVersion::try_from(&version)
+        .map_err(|err| ApiError::VersionHandshakeFailed("store", err.to_string()))?;
+
+    if !PROTOCOL_VERSION.is_compatible_with(&protocol_version) {
+        return Err(ApiError::IncompatibleProtocolVersion {
+            component: "store",
+            version: protocol_version.to_string(),
+            expected: PROTOCOL_VERSION.to_string(),
+        });
+    }
+
+    info!(
+        target: COMPONENT,
+        store_version = version.version,
+        %protocol_version,
+        "Store version checked"
+    );
+
+    Ok(())
+}
+
+/// Fetches the genesis block and the parameters of the chain from the store, and verifies the
+/// genesis block commits to the parameters and is the configured genesis block if any.
+async fn load_genesis(
+    store: ComponentChannel,
+    expected_hash: Option<Digest>,
+) -> Result<ChainGenesis, ApiError> {
+    let response = store_client::ApiClient::new(store)
+        .get_genesis(GetGenesisRequest {})
+        .await
+        .map_err(|status| ApiError::GenesisLoadFailed(status.to_string()))?
+        .into_inner();
+    let genesis = ChainGenesis::try_from(response)
+        .map_err(|err| ApiError::GenesisLoadFailed(err.to_string()))?;
+    genesis
+        .verify(expected_hash)
+        .map_err(|err| ApiError::GenesisLoadFailed(err.to_string()))?;
+
+    info!(target: COMPONENT, genesis_hash = %genesis.hash(), "Genesis block loaded");
+
+    Ok(genesis)
+}
+
+/// Applies the parameters of the chain to the configuration, which is left unchanged for the
+/// chains without parameters.
+pub fn apply_chain_parameters(
+    config: BlockProducerConfig,
+    parameters: Option<ChainParameters>,
+) -> BlockProducerConfig {
+    let Some(parameters) = parameters else {
+        info!(target: COMPONENT, "The chain has no parameters, using the configured ones");
+        return config;
+    };
+
+    let fees = parameters.fees.map(FeeConfig::from);
+    if config.fees.is_some() && config.fees != fees {
+        warn!(
+            target: COMPONENT,
+            configured_fees = %format_opt(config.fees.as_ref()),
+            chain_fees = %format_opt(fees.as_ref()),
+            "The configured fees are replaced by the fees of the chain"
+        );
+    }
+    info!(target: COMPONENT, %parameters, "Chain parameters loaded");
+
+    config.with_chain_parameters(&parameters)
+}
+
+/// Builds the block producer's gRPC service, sending requests to the store over `store`, and
+/// spawns the tasks producing batches and blocks.
+pub fn init(
+    config: &BlockProducerConfig,
+    store: ComponentChannel,
+) -> Result<api_server::ApiServer<impl api_server::Api>, ApiError> {
+    init_with_verifier(config, store, None)
+}
+
+/// Like [init], running `verifier` after the default verification of the submitted transactions.
+pub fn init_with_verifier(
+    config: &BlockProducerConfig,
+    store: ComponentChannel,
+    verifier: Option<Arc<dyn TransactionVerifier>>,
+) -> Result<api_server::ApiServer<impl api_server::Api>, ApiError> {
+    let signer = match &config.signing_key_filepath {
+        Some(path) => BlockSigner::load_or_generate(path)
+            .map_err(|err| ApiError::InvalidSigningKey(err.to_string()))?,
+        None => BlockSigner::generate(),
+    };
+    info!(target: COMPONENT, public_key = %signer.public_key_hex(), "Block signing key loaded");
+
+    let mempool = Arc::new(Mempool::default());
+    let store = Arc::new(DefaultStore::new(store_client::ApiClient::new(store)));
+    if let Some(simulation) = &config.simulation {
+        warn!(
+            target: COMPONENT,
+            %simulation,
+            "Simulation mode: transaction proofs aren't verified and block proofs are fabricated"
+        );
+    }
+    // The transactions submitted in simulation mode have placeholder proofs
+    let verify_tx_proofs = config.verify_tx_proofs && config.simulation.is_none();
+    let policy = PolicyRules::from_config(&config.submission_policy)
+        .map(|rules| Arc::new(SubmissionPolicy::new(rules)))
+        .map_err(|err| ApiError::InvalidSubmissionPolicy(err.to_string()))?;
+    let mut state_view = DefaultStateView::new(store.clone(), verify_tx_proofs)
+        .with_mempool(mempool.clone())
+        .with_verifier(policy.clone());
+    if let Some(verifier) = verifier {
+        state_view = state_view.with_verifier(verifier);
+    }
+    let state_view = Arc::new(state_view);
+
+    let fees = config
+        .fees
+        .as_ref()
+        .map(|fees| {
+            Fees::from_config(fees)
+                .map(Arc::new)
+                .map_err(|err| ApiError::InvalidFeeConfig(err.to_string()))
+        })
+        .transpose()?;
+
+    let metering = Arc::new(BlockMetering::new(config.block_production.max_cycles));
+    let timelines = Arc::new(BlockTimelines::default());
+    let mut block_builder = DefaultBlockBuilder::new(store.clone(), state_view.clone())
+        .with_time_provider(config.clock.time_provider())
+        .with_signer(Arc::new(signer))
+        .with_metering(metering.clone())
+        .with_timelines(timelines.clone());
+    if let Some(fees) = &fees {
+        block_builder = block_builder.with_fees(fees.clone());
+    }
+    if let Some(simulation) = &config.simulation {
+        block_builder = block_builder.with_simulated_proofs(simulation.block_proof_size);
+    }
+    let batch_builder_options = DefaultBatchBuilderOptions {
+        block_frequency: config.block_production.interval(),
+        max_batches_per_block: SERVER_MAX_BATCHES_PER_BLOCK,
+        max_in_flight_batches: config.batch_proving.max_in_flight_batches,
+        max_cycles_per_block: config.block_production.max_cycles,
+    };
+    let batch_sizing = Arc::new(BatchSizing::new(config.batch_sealing));
+    let batch_builder = Arc::new(
+        DefaultBatchBuilder::new(Arc::new(block_builder), batch_builder_options)
+            .with_batch_sizing(batch_sizing.clone()),
+    );
+
+    let transaction_queue_options =
+        TransactionQueueOptions { sealing_policy: config.batch_sealing };
+    let queue = Arc::new(
+        TransactionQueue::new(state_view, batch_builder.clone(), transaction_queue_options)
+            .with_mempool(mempool.clone())
+            .with_batch_sizing(batch_sizing.clone())
+            .with_priority_lane(config.block_production.priority_lane_capacity),
+    );
+
+    let mut block_producer_api = api::BlockProducerApi::new(
+        queue.clone(),
+        batch_builder.production(),
+        batch_builder.lookup(),
+        mempool,
+    )
+    .with_metering(metering)
+    .with_batch_sizing(batch_sizing)
+    .with_policy(policy)
+    .with_timelines(timelines);
+    if let Some(fees) = fees {
+        block_producer_api = block_producer_api.with_fees(fees);
+    }
+    if config.simulation.is_some() {
+        block_producer_api = block_producer_api.with_simulation();
+    }
+    let block_producer = api_server::ApiServer::new(block_producer_api);
+
+    start_runtime_metrics_sampling();
+    spawn_named("batch-builder-ticker", async move { queue.run().await });
+    spawn_named("block-builder-ticker", async move { batch_builder.run().await });
+
+    Ok(block_producer)
+}
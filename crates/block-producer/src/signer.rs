@@ -0,0 +1,101 @@
+//! Identity of the sequencer, used to sign the headers of the produced blocks so the clients can
+//! authenticate them.
+use std::{
+    fmt::{Debug, Formatter},
+    fs,
+    path::Path,
+};
+
+use miden_objects::{
+    crypto::dsa::rpo_falcon512::{PublicKey, SecretKey, Signature},
+    utils::serde::{Deserializable, Serializable},
+    BlockHeader, Digest, Word,
+};
+use tracing::info;
+
+use crate::{errors::SigningKeyError, COMPONENT};
+
+// BLOCK SIGNER
+// =================================================================================================
+
+/// Signs the block headers with the sequencer's key.
+pub struct BlockSigner {
+    secret_key: SecretKey,
+}
+
+impl BlockSigner {
+    /// Returns a signer with a new random key, which is lost when the process exits.
+    pub fn generate() -> Self {
+        Self { secret_key: SecretKey::new() }
+    }
+
+    /// Loads the key stored at `path`, or generates a new key and stores it at `path` if the file
+    /// doesn't exist.
+    pub fn load_or_generate(path: &Path) -> Result<Self, SigningKeyError> {
+        if path.exists() {
+            let bytes = fs::read(path).map_err(SigningKeyError::IoError)?;
+            let secret_key = SecretKey::read_from_bytes(&bytes)
+                .map_err(|err| SigningKeyError::InvalidKey(err.to_string()))?;
+
+            return Ok(Self { secret_key });
+        }
+
+        let signer = Self::generate();
+        fs::write(path, signer.secret_key.to_bytes()).map_err(SigningKeyError::IoError)?;
+        info!(target: COMPONENT, path = %path.display(), "Generated a new signing key");
+
+        Ok(signer)
+    }
+
+    /// Returns the public key the block signatures are verified against.
+    pub fn public_key(&self) -> PublicKey {
+        self.secret_key.public_key()
+    }
+
+    /// Returns the public key as a hex string, as shared with the clients.
+    pub fn public_key_hex(&self) -> String {
+        Digest::from(Word::from(self.public_key())).to_hex()
+    }
+
+    /// Signs the hash of `header`.
+    pub fn sign(&self, header: &BlockHeader) -> Signature {
+        self.secret_key.sign(header.hash().into())
+    }
+}
+
+impl Debug for BlockSigner {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BlockSigner")
+            .field("public_key", &self.public_key_hex())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use miden_objects::{BlockHeader, Digest, ONE, ZERO};
+
+    use super::BlockSigner;
+
+    #[test]
+    fn signed_header_is_verified() {
+        let header = BlockHeader::new(
+            Digest::default(),
+            1,
+            Digest::default(),
+            Digest::default(),
+            Digest::default(),
+            Digest::default(),
+            Digest::default(),
+            Digest::default(),
+            ZERO,
+            ONE,
+        );
+        let signer = BlockSigner::generate();
+
+        let signature = signer.sign(&header);
+
+        assert!(signer.public_key().verify(header.hash().into(), &signature));
+        assert!(!BlockSigner::generate().public_key().verify(header.hash().into(), &signature));
+    }
+}
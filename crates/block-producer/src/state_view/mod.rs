@@ -1,110 +1,293 @@
-use std::{collections::BTreeSet, sync::Arc};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
 
 use async_trait::async_trait;
 use miden_node_utils::formatting::format_array;
 use miden_objects::{
-    accounts::AccountId, notes::Nullifier, transaction::InputNotes, Digest,
-    MIN_PROOF_SECURITY_LEVEL,
+    accounts::AccountId,
+    notes::Nullifier,
+    transaction::{InputNotes, TransactionId},
+    Digest,
 };
-use miden_tx::TransactionVerifier;
-use tokio::sync::RwLock;
-use tracing::{debug, instrument};
+use tokio::{sync::RwLock, time};
+use tracing::{debug, info, instrument, warn};
 
 use crate::{
+    batch_builder::batch::TransactionBatch,
     block::Block,
     errors::VerifyTxError,
+    mempool::Mempool,
     store::{ApplyBlock, ApplyBlockError, Store, TransactionInputs},
-    txqueue::TransactionValidator,
+    txqueue::{Lane, TransactionSimulation, TransactionValidator},
+    verifier::{ProofVerifier, StoreInputsVerifier, TransactionVerifier},
     ProvenTransaction, COMPONENT,
 };
 
+use self::prefetch::{InputsPrefetch, VersionedInputs};
+
+mod prefetch;
 #[cfg(test)]
 mod tests;
 
+/// Interval before the first attempt to reach the store again after it became unavailable while
+/// applying a block, doubled after each failed attempt
+const STORE_RETRY_INTERVAL_MIN: Duration = Duration::from_millis(100);
+
+/// Maximum interval between the attempts to reach the store
+const STORE_RETRY_INTERVAL_MAX: Duration = Duration::from_secs(5);
+
 pub struct DefaultStateView<S> {
     store: Arc<S>,
 
-    /// Enables or disables the verification of transaction proofs in `verify_tx`
-    verify_tx_proofs: bool,
+    /// Verifies the transactions against their inputs in the store, before they are checked
+    /// against the in-flight transactions
+    verifier: Arc<dyn TransactionVerifier>,
 
-    /// The account ID of accounts being modified by transactions currently in the block production
-    /// pipeline. We currently ensure that only 1 tx/block modifies any given account (issue: #186).
-    accounts_in_flight: Arc<RwLock<BTreeSet<AccountId>>>,
+    /// The accounts being modified by transactions currently in the block production pipeline,
+    /// with their speculative state after these transactions. A transaction modifying one of these
+    /// accounts must start from this state rather than from the state in the store.
+    accounts_in_flight: Arc<RwLock<BTreeMap<AccountId, InFlightAccount>>>,
 
     /// The nullifiers of notes consumed by transactions currently in the block production pipeline.
     nullifiers_in_flight: Arc<RwLock<BTreeSet<Nullifier>>>,
+
+    /// Tracks the transactions in the block production pipeline, for inspection
+    mempool: Option<Arc<Mempool>>,
+
+    /// The number of blocks applied, versioning the transaction inputs read from the store
+    applied_blocks: AtomicU64,
+
+    /// The inputs of the submitted transactions, read before the transactions are verified
+    prefetch: InputsPrefetch,
 }
 
 impl<S> DefaultStateView<S>
 where
     S: Store,
 {
+    /// Creates a state view verifying that the notes consumed by the transactions weren't consumed
+    /// in a block, and their proofs if `verify_tx_proofs` is set.
     pub fn new(store: Arc<S>, verify_tx_proofs: bool) -> Self {
+        let verifier: Arc<dyn TransactionVerifier> = if verify_tx_proofs {
+            Arc::new(ProofVerifier::default().and_then(StoreInputsVerifier))
+        } else {
+            Arc::new(StoreInputsVerifier)
+        };
+
         Self {
             store,
-            verify_tx_proofs,
-            accounts_in_flight: Arc::new(RwLock::new(BTreeSet::new())),
+            verifier,
+            accounts_in_flight: Arc::new(RwLock::new(BTreeMap::new())),
             nullifiers_in_flight: Arc::new(RwLock::new(BTreeSet::new())),
+            mempool: None,
+            applied_blocks: AtomicU64::new(0),
+            prefetch: InputsPrefetch::default(),
         }
     }
-}
 
-// FIXME: remove the allow when the upstream clippy issue is fixed:
-// https://github.com/rust-lang/rust-clippy/issues/12281
-#[allow(clippy::blocks_in_conditions)]
-#[async_trait]
-impl<S> TransactionValidator for DefaultStateView<S>
-where
-    S: Store,
-{
-    #[instrument(skip_all, err)]
-    async fn verify_tx(&self, candidate_tx: &ProvenTransaction) -> Result<(), VerifyTxError> {
-        if self.verify_tx_proofs {
-            // Make sure that the transaction proof is valid and meets the required security level
-            let tx_verifier = TransactionVerifier::new(MIN_PROOF_SECURITY_LEVEL);
-            tx_verifier
-                .verify(candidate_tx.clone())
-                .map_err(|_| VerifyTxError::InvalidTransactionProof(candidate_tx.id()))?;
+    /// Removes the transactions of the applied blocks from `mempool`.
+    pub fn with_mempool(mut self, mempool: Arc<Mempool>) -> Self {
+        self.mempool = Some(mempool);
+        self
+    }
+
+    /// Runs `verifier` after the default verification of the transactions, e.g. to enforce the
+    /// operator's policies.
+    pub fn with_verifier(mut self, verifier: impl TransactionVerifier) -> Self {
+        self.verifier = Arc::new(self.verifier.and_then(verifier));
+        self
+    }
+
+    /// Applies `block` to the store.
+    ///
+    /// If the store can't be reached, e.g. because it restarts, or aborted the block, waits until
+    /// it is back and compares its chain tip to the block: the block is only sent again if the
+    /// store didn't apply it before the connection was lost, so its transactions are never applied
+    /// twice.
+    async fn commit_block(&self, block: &Block) -> Result<(), ApplyBlockError> {
+        let block_num = block.header.block_num();
+        let mut retry_interval = STORE_RETRY_INTERVAL_MIN;
+        let mut result = self.store.apply_block(block).await;
+
+        loop {
+            match result {
+                Err(ApplyBlockError::StoreRequestFailed(err)) if err.is_retriable() => {
+                    warn!(
+                        target: COMPONENT,
+                        block_num,
+                        %err,
+                        ?retry_interval,
+                        "Store unavailable, retrying"
+                    );
+                },
+                result => return result,
+            }
+
+            time::sleep(retry_interval).await;
+            retry_interval = (retry_interval * 2).min(STORE_RETRY_INTERVAL_MAX);
+
+            result = match self.store.get_chain_tip().await {
+                Ok(chain_tip) if chain_tip.hash() == block.header.hash() => {
+                    info!(target: COMPONENT, block_num, "Block was applied before the store failed");
+                    Ok(())
+                },
+                Ok(chain_tip) if chain_tip.hash() == block.header.prev_hash() => {
+                    info!(target: COMPONENT, block_num, "Store is back, sending the block again");
+                    self.store.apply_block(block).await
+                },
+                Ok(chain_tip) => Err(ApplyBlockError::ChainTipMismatch {
+                    block_num,
+                    chain_tip: chain_tip.block_num(),
+                }),
+                Err(err) => Err(err.into()),
+            };
         }
+    }
 
+    /// Verifies `candidate_tx` of `lane` and registers it as in flight, see
+    /// [TransactionValidator::verify_tx].
+    async fn verify_lane_tx(
+        &self,
+        candidate_tx: &ProvenTransaction,
+        lane: Lane,
+    ) -> Result<(), VerifyTxError> {
         // Soft-check if `tx` violates in-flight requirements.
         //
         // This is a "soft" check, because we'll need to redo it at the end. We do this soft check
         // to quickly reject clearly infracting transactions before hitting the store (slow).
-        ensure_in_flight_constraints(
-            candidate_tx,
-            &*self.accounts_in_flight.read().await,
-            &*self.nullifiers_in_flight.read().await,
-        )?;
-
-        // Fetch the transaction inputs from the store, and check tx input constraints
-        let tx_inputs = self.store.get_tx_inputs(candidate_tx).await?;
-        ensure_tx_inputs_constraints(candidate_tx, tx_inputs)?;
-
-        // Re-check in-flight transaction constraints, and if verification passes, register
-        // transaction
-        //
-        // Note: We need to re-check these constraints because we dropped the locks since we last
-        // checked
         {
+            let locked_accounts_in_flight = self.accounts_in_flight.read().await;
+            ensure_in_flight_constraints(
+                candidate_tx,
+                &locked_accounts_in_flight,
+                &*self.nullifiers_in_flight.read().await,
+            )?;
+            ensure_lane_constraints(candidate_tx, lane, &locked_accounts_in_flight)?;
+        }
+
+        let mut prefetched = self.prefetch.take(candidate_tx.id()).await;
+
+        loop {
+            // Fetch the transaction inputs from the store, unless they were prefetched since the
+            // last block was applied
+            let version = self.applied_blocks.load(Ordering::SeqCst);
+            let tx_inputs = match prefetched.take() {
+                Some(VersionedInputs { version: prefetched_version, inputs })
+                    if prefetched_version == version =>
+                {
+                    debug!(target: COMPONENT, "Using the prefetched transaction inputs");
+                    inputs
+                },
+                _ => self.store.get_tx_inputs(candidate_tx).await?,
+            };
+            self.verifier.verify(candidate_tx, &tx_inputs)?;
+
+            // Re-check in-flight transaction constraints, check tx input constraints, and if
+            // verification passes, register transaction
+            //
+            // Note: We need to re-check these constraints because we dropped the locks since we
+            // last checked. The tx inputs are checked under the same locks, because the account's
+            // hash in the store is only relevant if no in-flight transaction modifies the account.
             let mut locked_accounts_in_flight = self.accounts_in_flight.write().await;
             let mut locked_nullifiers_in_flight = self.nullifiers_in_flight.write().await;
 
+            // The in-flight transactions of the blocks applied since the inputs were read are no
+            // longer tracked, the inputs must reflect these blocks
+            if self.applied_blocks.load(Ordering::SeqCst) != version {
+                debug!(target: COMPONENT, "Blocks were applied while reading the transaction inputs, reading them again");
+                continue;
+            }
+
             ensure_in_flight_constraints(
                 candidate_tx,
                 &locked_accounts_in_flight,
                 &locked_nullifiers_in_flight,
             )?;
+            ensure_lane_constraints(candidate_tx, lane, &locked_accounts_in_flight)?;
+            ensure_tx_inputs_constraints(candidate_tx, &tx_inputs, &locked_accounts_in_flight)?;
 
             // Success! Register transaction as successfully verified
-            locked_accounts_in_flight.insert(candidate_tx.account_id());
+            let account = locked_accounts_in_flight
+                .entry(candidate_tx.account_id())
+                .or_insert_with(|| InFlightAccount {
+                    hash: candidate_tx.final_account_hash(),
+                    num_txs: 0,
+                    user_txs: BTreeSet::new(),
+                });
+            account.hash = candidate_tx.final_account_hash();
+            account.num_txs += 1;
+            if lane == Lane::User {
+                account.user_txs.insert(candidate_tx.id());
+            }
 
             let mut nullifiers_in_tx: BTreeSet<_> =
                 candidate_tx.input_notes().iter().cloned().collect();
             locked_nullifiers_in_flight.append(&mut nullifiers_in_tx);
+
+            return Ok(());
         }
+    }
+}
 
-        Ok(())
+// FIXME: remove the allow when the upstream clippy issue is fixed:
+// https://github.com/rust-lang/rust-clippy/issues/12281
+#[allow(clippy::blocks_in_conditions)]
+#[async_trait]
+impl<S> TransactionValidator for DefaultStateView<S>
+where
+    S: Store,
+{
+    #[instrument(skip_all, err)]
+    async fn verify_tx(&self, candidate_tx: &ProvenTransaction) -> Result<(), VerifyTxError> {
+        self.verify_lane_tx(candidate_tx, Lane::User).await
+    }
+
+    #[instrument(skip_all, err)]
+    async fn verify_priority_tx(
+        &self,
+        candidate_tx: &ProvenTransaction,
+    ) -> Result<(), VerifyTxError> {
+        self.verify_lane_tx(candidate_tx, Lane::Priority).await
+    }
+
+    fn prefetch_tx_inputs(&self, tx: &ProvenTransaction) {
+        let version = self.applied_blocks.load(Ordering::SeqCst);
+        self.prefetch.start(self.store.clone(), tx, version);
+    }
+
+    #[instrument(skip_all, err)]
+    async fn simulate_tx(
+        &self,
+        candidate_tx: &ProvenTransaction,
+    ) -> Result<TransactionSimulation, VerifyTxError> {
+        // The inputs are fetched first, so they are reported even if the transaction is rejected
+        let tx_inputs = self.store.get_tx_inputs(candidate_tx).await?;
+
+        let verification = async {
+            self.verifier.verify(candidate_tx, &tx_inputs)?;
+
+            let locked_accounts_in_flight = self.accounts_in_flight.read().await;
+            ensure_in_flight_constraints(
+                candidate_tx,
+                &locked_accounts_in_flight,
+                &*self.nullifiers_in_flight.read().await,
+            )?;
+            ensure_tx_inputs_constraints(candidate_tx, &tx_inputs, &locked_accounts_in_flight)
+        }
+        .await;
+
+        let rejection = match verification {
+            Ok(()) => None,
+            Err(err) => Some(err.rejection().ok_or(err)?),
+        };
+
+        Ok(TransactionSimulation { tx_inputs, rejection })
     }
 }
 
@@ -118,15 +301,26 @@ where
 {
     #[instrument(target = "miden-block-producer", skip_all, err)]
     async fn apply_block(&self, block: &Block) -> Result<(), ApplyBlockError> {
-        self.store.apply_block(block).await?;
+        self.commit_block(block).await?;
 
         let mut locked_accounts_in_flight = self.accounts_in_flight.write().await;
         let mut locked_nullifiers_in_flight = self.nullifiers_in_flight.write().await;
+        self.applied_blocks.fetch_add(1, Ordering::SeqCst);
+
+        // The user transactions in block no longer hold back the priority transactions of their
+        // accounts
+        for receipt in &block.transactions {
+            if let Some(account) =
+                locked_accounts_in_flight.get_mut(&receipt.transaction.account_id)
+            {
+                account.user_txs.remove(&receipt.transaction.id);
+            }
+        }
 
-        // Remove account ids of transactions in block
+        // Remove the transactions in block from the in-flight accounts, the accounts are no longer
+        // in flight once all their transactions are in a block
         for update in &block.updated_accounts {
-            let was_in_flight = locked_accounts_in_flight.remove(&update.account_id);
-            debug_assert!(was_in_flight);
+            remove_in_flight_tx(&mut locked_accounts_in_flight, update.account_id);
         }
 
         // Remove new nullifiers of transactions in block
@@ -135,29 +329,103 @@ where
             debug_assert!(was_in_flight);
         }
 
+        if let Some(mempool) = &self.mempool {
+            mempool
+                .remove(block.transactions.iter().map(|receipt| receipt.transaction.id))
+                .await;
+        }
+
         Ok(())
     }
+
+    #[instrument(target = "miden-block-producer", skip_all)]
+    async fn discard_batches(&self, batches: &[TransactionBatch]) {
+        let mut locked_accounts_in_flight = self.accounts_in_flight.write().await;
+        let mut locked_nullifiers_in_flight = self.nullifiers_in_flight.write().await;
+
+        // Only the transactions of `batches` are removed. The later in-flight transactions of the
+        // accounts build on the discarded ones, so their batches fail to be included with
+        // inconsistent account states and are discarded when excluded from their block
+        for tx in batches.iter().flat_map(TransactionBatch::transactions) {
+            if let Some(account) = locked_accounts_in_flight.get_mut(&tx.account_id) {
+                account.user_txs.remove(&tx.id);
+            }
+            remove_in_flight_tx(&mut locked_accounts_in_flight, tx.account_id);
+
+            for nullifier in &tx.input_nullifiers {
+                locked_nullifiers_in_flight.remove(nullifier);
+            }
+        }
+
+        if let Some(mempool) = &self.mempool {
+            let tx_ids: Vec<_> = batches
+                .iter()
+                .flat_map(TransactionBatch::transactions)
+                .map(|tx| tx.id)
+                .collect();
+            mempool.remove(tx_ids).await;
+        }
+    }
+}
+
+/// Removes a transaction modifying `account_id` from the in-flight accounts, the account is no
+/// longer in flight once all its transactions are removed.
+fn remove_in_flight_tx(
+    accounts_in_flight: &mut BTreeMap<AccountId, InFlightAccount>,
+    account_id: AccountId,
+) {
+    let Some(account) = accounts_in_flight.get_mut(&account_id) else {
+        debug_assert!(false, "account {account_id} wasn't in flight");
+        return;
+    };
+
+    account.num_txs -= 1;
+    if account.num_txs == 0 {
+        accounts_in_flight.remove(&account_id);
+    }
+}
+
+// IN-FLIGHT ACCOUNT
+// -------------------------------------------------------------------------------------------------
+
+/// The speculative state of an account modified by transactions in the block production pipeline.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct InFlightAccount {
+    /// The final hash of the latest in-flight transaction modifying the account
+    hash: Digest,
+
+    /// The number of in-flight transactions modifying the account
+    num_txs: usize,
+
+    /// The in-flight user transactions modifying the account, a priority transaction can't be
+    /// included in a block ahead of them
+    user_txs: BTreeSet<TransactionId>,
 }
 
 // HELPERS
 // -------------------------------------------------------------------------------------------------
 
 /// Ensures the constraints related to in-flight transactions:
-/// - the candidate transaction doesn't modify the same account as an existing in-flight
-///   transaction (issue: #186)
+/// - if the account is modified by in-flight transactions, the candidate transaction starts from
+///   the final state of the latest of them
 /// - no consumed note's nullifier in candidate tx's consumed notes is already contained in
 ///   `already_consumed_nullifiers`
 #[instrument(target = "miden-block-producer", skip_all, err)]
 fn ensure_in_flight_constraints(
     candidate_tx: &ProvenTransaction,
-    accounts_in_flight: &BTreeSet<AccountId>,
+    accounts_in_flight: &BTreeMap<AccountId, InFlightAccount>,
     already_consumed_nullifiers: &BTreeSet<Nullifier>,
 ) -> Result<(), VerifyTxError> {
-    debug!(target: COMPONENT, accounts_in_flight = %format_array(accounts_in_flight), already_consumed_nullifiers = %format_array(already_consumed_nullifiers));
-
-    // Check account id hasn't been modified yet
-    if accounts_in_flight.contains(&candidate_tx.account_id()) {
-        return Err(VerifyTxError::AccountAlreadyModifiedByOtherTx(candidate_tx.account_id()));
+    debug!(target: COMPONENT, accounts_in_flight = %format_array(accounts_in_flight.keys()), already_consumed_nullifiers = %format_array(already_consumed_nullifiers));
+
+    // Check the transaction builds on the in-flight state of the account
+    if let Some(account) = accounts_in_flight.get(&candidate_tx.account_id()) {
+        if candidate_tx.initial_account_hash() != account.hash {
+            return Err(VerifyTxError::IncorrectAccountInitialHash {
+                tx_initial_account_hash: candidate_tx.initial_account_hash(),
+                current_account_hash: Some(account.hash),
+            });
+        }
     }
 
     // Check no consumed notes were already consumed
@@ -179,21 +447,48 @@ fn ensure_in_flight_constraints(
     Ok(())
 }
 
+/// Ensures a candidate transaction of the priority lane doesn't modify an account modified by
+/// in-flight user transactions: it would be included in a block ahead of them, before the state it
+/// starts from.
+fn ensure_lane_constraints(
+    candidate_tx: &ProvenTransaction,
+    lane: Lane,
+    accounts_in_flight: &BTreeMap<AccountId, InFlightAccount>,
+) -> Result<(), VerifyTxError> {
+    let modified_by_user_txs = accounts_in_flight
+        .get(&candidate_tx.account_id())
+        .is_some_and(|account| !account.user_txs.is_empty());
+
+    if lane == Lane::Priority && modified_by_user_txs {
+        return Err(VerifyTxError::AccountModifiedByUserTransactions(candidate_tx.account_id()));
+    }
+
+    Ok(())
+}
+
+/// Ensures the candidate transaction starts from the account's state in the store, unless the
+/// account is modified by in-flight transactions, which is checked by
+/// [ensure_in_flight_constraints].
+///
+/// The other inputs from the store are checked by the [TransactionVerifier] of the state view.
 #[instrument(target = "miden-block-producer", skip_all, err)]
 fn ensure_tx_inputs_constraints(
     candidate_tx: &ProvenTransaction,
-    tx_inputs: TransactionInputs,
+    tx_inputs: &TransactionInputs,
+    accounts_in_flight: &BTreeMap<AccountId, InFlightAccount>,
 ) -> Result<(), VerifyTxError> {
     debug!(target: COMPONENT, %tx_inputs);
 
     match tx_inputs.account_hash {
+        // the account's hash in the store is outdated by the in-flight transactions
+        _ if accounts_in_flight.contains_key(&candidate_tx.account_id()) => {},
         // if the account is present in the Store, make sure that the account state hash
         // from the received transaction is the same as the one from the Store
         Some(store_account_hash) => {
             if candidate_tx.initial_account_hash() != store_account_hash {
                 return Err(VerifyTxError::IncorrectAccountInitialHash {
                     tx_initial_account_hash: candidate_tx.initial_account_hash(),
-                    store_account_hash: Some(store_account_hash),
+                    current_account_hash: Some(store_account_hash),
                 });
             }
         },
@@ -204,24 +499,11 @@ fn ensure_tx_inputs_constraints(
             if candidate_tx.initial_account_hash() != Digest::default() {
                 return Err(VerifyTxError::IncorrectAccountInitialHash {
                     tx_initial_account_hash: candidate_tx.initial_account_hash(),
-                    store_account_hash: None,
+                    current_account_hash: None,
                 });
             }
         },
     }
 
-    let infracting_nullifiers: Vec<Nullifier> = tx_inputs
-        .nullifiers
-        .into_iter()
-        .filter(|&(_, block_num)| block_num != 0)
-        .map(|(nullifier_in_tx, _)| nullifier_in_tx)
-        .collect();
-
-    if !infracting_nullifiers.is_empty() {
-        return Err(VerifyTxError::InputNotesAlreadyConsumed(InputNotes::new(
-            infracting_nullifiers,
-        )?));
-    }
-
     Ok(())
 }
@@ -0,0 +1,73 @@
+//! Inputs of the submitted transactions, read from the store before the transactions are verified.
+//!
+//! The reads start as soon as the transactions are received, so that their inputs are usually
+//! available once they are verified. The inputs are versioned by the number of blocks applied by
+//! the state view when the read started, the inputs read before a block was applied are stale.
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use miden_objects::transaction::TransactionId;
+use tokio::sync::oneshot;
+use tracing::{info_span, Instrument};
+
+use crate::{
+    errors::StoreClientError,
+    store::{Store, TransactionInputs},
+    ProvenTransaction, COMPONENT,
+};
+
+/// Time after which the prefetched inputs which weren't taken are dropped, e.g. the inputs of the
+/// transactions rejected before their verification
+const PREFETCH_TTL: Duration = Duration::from_secs(30);
+
+/// Inputs of a transaction read from the store.
+pub(super) struct VersionedInputs {
+    /// Number of blocks applied by the state view when the read started
+    pub version: u64,
+    pub inputs: TransactionInputs,
+}
+
+struct PendingRead {
+    version: u64,
+    started_at: Instant,
+    result: oneshot::Receiver<Result<TransactionInputs, StoreClientError>>,
+}
+
+/// Reads of transaction inputs started ahead of the verification of the transactions.
+#[derive(Default)]
+pub(super) struct InputsPrefetch {
+    reads: Mutex<BTreeMap<TransactionId, PendingRead>>,
+}
+
+impl InputsPrefetch {
+    /// Starts reading the inputs of `tx` from `store`, `version` blocks being applied.
+    pub fn start<S: Store>(&self, store: Arc<S>, tx: &ProvenTransaction, version: u64) {
+        let (sender, result) = oneshot::channel();
+        let tx_id = tx.id();
+        let tx = tx.clone();
+        tokio::spawn(
+            async move {
+                // The verification may have given up on the read already
+                let _ = sender.send(store.get_tx_inputs(&tx).await);
+            }
+            .instrument(info_span!(target: COMPONENT, "prefetch_tx_inputs")),
+        );
+
+        let now = Instant::now();
+        let mut reads = self.reads.lock().expect("prefetch lock poisoned");
+        reads.retain(|_, read| now.duration_since(read.started_at) < PREFETCH_TTL);
+        reads.insert(tx_id, PendingRead { version, started_at: now, result });
+    }
+
+    /// Waits for the prefetched inputs of `tx_id`, [None] if they weren't prefetched or couldn't
+    /// be read.
+    pub async fn take(&self, tx_id: TransactionId) -> Option<VersionedInputs> {
+        let read = self.reads.lock().expect("prefetch lock poisoned").remove(&tx_id)?;
+        let inputs = read.result.await.ok()?.ok()?;
+
+        Some(VersionedInputs { version: read.version, inputs })
+    }
+}
@@ -1,15 +1,22 @@
 //! Requirements for `apply_block()`:
 //!
 //! AB1: the internal store's `apply_block` is called once
-//! AB2: All accounts modified by transactions in the block are removed from the internal state
+//! AB2: All accounts modified by transactions in the block are removed from the internal state,
+//!      unless they are also modified by in-flight transactions not in the block
 //! AB3: All consumed notes by some transaction in the block are still not consumable after `apply_block`
+//! AB4: If the store becomes unavailable, the block is applied once it is back, and only once
+//! AB5: The transactions of the batches excluded from a block because they conflict with the store
+//!      are no longer in flight once the block is applied
 
 use std::iter;
 
 use miden_node_proto::domain::accounts::AccountUpdateDetails;
 
 use super::*;
-use crate::test_utils::{block::MockBlockBuilder, MockStoreSuccessBuilder};
+use crate::{
+    block_builder::{BlockBuilder, DefaultBlockBuilder},
+    test_utils::{block::MockBlockBuilder, MockStoreSuccessBuilder},
+};
 
 /// Tests requirement AB1
 #[tokio::test]
@@ -97,7 +104,56 @@ async fn test_apply_block_ab2() {
 
     // Only the first account should still be in flight
     assert_eq!(accounts_still_in_flight.len(), 1);
-    assert!(accounts_still_in_flight.contains(&accounts[0].id));
+    assert!(accounts_still_in_flight.contains_key(&accounts[0].id));
+}
+
+/// Tests requirement AB2 for an account modified by several in-flight transactions
+#[tokio::test]
+#[miden_node_test_macro::enable_logging]
+async fn test_apply_block_ab2_chained_txs() {
+    let account: MockPrivateAccount<3> = MockPrivateAccount::from(0);
+
+    let store = Arc::new(
+        MockStoreSuccessBuilder::from_accounts(iter::once((account.id, account.states[0]))).build(),
+    );
+
+    let tx1 =
+        MockProvenTxBuilder::with_account(account.id, account.states[0], account.states[1]).build();
+    let tx2 =
+        MockProvenTxBuilder::with_account(account.id, account.states[1], account.states[2]).build();
+
+    let state_view = DefaultStateView::new(store.clone(), false);
+
+    // Verify transactions so they are tracked in state view
+    assert_eq!(state_view.verify_tx(&tx1).await, Ok(()));
+    assert_eq!(state_view.verify_tx(&tx2).await, Ok(()));
+
+    // Only the first transaction goes into the block
+    let update = |final_state_hash| AccountUpdateDetails {
+        account_id: account.id,
+        final_state_hash,
+        details: None,
+    };
+    let block = MockBlockBuilder::new(&store)
+        .await
+        .account_updates(vec![update(account.states[1])])
+        .build();
+    state_view.apply_block(&block).await.unwrap();
+
+    // The account is still in flight, in the state after the second transaction
+    {
+        let accounts_in_flight = state_view.accounts_in_flight.read().await;
+        let in_flight = &accounts_in_flight[&account.id];
+        assert_eq!((in_flight.hash, in_flight.num_txs), (account.states[2], 1));
+    }
+
+    let block = MockBlockBuilder::new(&store)
+        .await
+        .account_updates(vec![update(account.states[2])])
+        .build();
+    state_view.apply_block(&block).await.unwrap();
+
+    assert!(state_view.accounts_in_flight.read().await.is_empty());
 }
 
 /// Tests requirement AB3
@@ -158,3 +214,98 @@ async fn test_apply_block_ab3() {
         Err(VerifyTxError::InputNotesAlreadyConsumed(txs[0].input_notes().clone()))
     );
 }
+
+/// Tests requirement AB4
+#[tokio::test(start_paused = true)]
+#[miden_node_test_macro::enable_logging]
+async fn test_apply_block_ab4() {
+    let account: MockPrivateAccount<3> = MockPrivateAccount::from(0);
+    let tx =
+        MockProvenTxBuilder::with_account(account.id, account.states[0], account.states[1]).build();
+    let account_update = AccountUpdateDetails {
+        account_id: account.id,
+        final_state_hash: account.states[1],
+        details: None,
+    };
+
+    // The store is down: the block is sent again once it is back
+    let store = Arc::new(
+        MockStoreSuccessBuilder::from_accounts(iter::once((account.id, account.states[0]))).build(),
+    );
+    let state_view = DefaultStateView::new(store.clone(), false);
+    state_view.verify_tx(&tx).await.unwrap();
+    let block = MockBlockBuilder::new(&store)
+        .await
+        .account_updates(vec![account_update.clone()])
+        .build();
+
+    *store.unavailable_apply_blocks.write().await = 3;
+    assert_eq!(state_view.apply_block(&block).await, Ok(()));
+    assert_eq!(*store.num_apply_block_called.read().await, 1);
+    assert_eq!(*store.last_block_header.read().await, block.header);
+    assert!(state_view.accounts_in_flight.read().await.is_empty());
+
+    // The store applies the block but the response is lost: the block isn't sent again
+    let store = Arc::new(
+        MockStoreSuccessBuilder::from_accounts(iter::once((account.id, account.states[0]))).build(),
+    );
+    let state_view = DefaultStateView::new(store.clone(), false);
+    state_view.verify_tx(&tx).await.unwrap();
+    let block = MockBlockBuilder::new(&store)
+        .await
+        .account_updates(vec![account_update])
+        .build();
+
+    *store.lost_apply_block_responses.write().await = 1;
+    assert_eq!(state_view.apply_block(&block).await, Ok(()));
+    assert_eq!(*store.num_apply_block_called.read().await, 1);
+    assert!(state_view.accounts_in_flight.read().await.is_empty());
+}
+
+/// Tests requirement AB5
+#[tokio::test]
+#[miden_node_test_macro::enable_logging]
+async fn test_apply_block_ab5() {
+    let (txs, accounts): (Vec<_>, Vec<_>) = get_txs_and_accounts(0, 2).unzip();
+
+    let store = Arc::new(
+        MockStoreSuccessBuilder::from_accounts(
+            accounts.iter().map(|mock_account| (mock_account.id, mock_account.states[0])),
+        )
+        .build(),
+    );
+    let state_view = Arc::new(DefaultStateView::new(store.clone(), false));
+    for tx in &txs {
+        assert_eq!(state_view.verify_tx(tx).await, Ok(()));
+    }
+
+    // The note consumed by the second transaction is consumed in the store after the transaction
+    // was verified
+    let spent = txs[1].input_notes().iter().next().unwrap();
+    store
+        .produced_nullifiers
+        .write()
+        .await
+        .insert(spent.inner(), [1u32.into(), ZERO, ZERO, ZERO]);
+
+    let batches: Vec<_> =
+        txs.iter().map(|tx| TransactionBatch::new(vec![tx.clone()]).unwrap()).collect();
+    let block_builder = DefaultBlockBuilder::new(store.clone(), state_view.clone());
+    block_builder.build_block(&batches).await.unwrap();
+
+    // The block only includes the first batch
+    assert_eq!(*store.num_apply_block_called.read().await, 1);
+    let locked_accounts = store.accounts.read().await;
+    assert_eq!(
+        Digest::from(locked_accounts.get_leaf(&accounts[0].id.into())),
+        accounts[0].states[1]
+    );
+    assert_eq!(
+        Digest::from(locked_accounts.get_leaf(&accounts[1].id.into())),
+        accounts[1].states[0]
+    );
+    drop(locked_accounts);
+
+    assert!(state_view.accounts_in_flight.read().await.is_empty());
+    assert!(state_view.nullifiers_in_flight.read().await.is_empty());
+}
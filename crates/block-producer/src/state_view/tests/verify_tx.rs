@@ -6,16 +6,35 @@
 //! VT3: If `tx` consumes an already-consumed note in the store, `verify_tx` must fail
 //!
 //! in-flight related requirements
-//! VT4: In each block, at most 1 transaction is allowed to modify any given account
+//! VT4: If in-flight transactions modify the account of `tx`, `tx.initial_account_hash` must match
+//!      the final account hash of the latest of them instead of the account hash in store
 //! VT5: `verify_tx(tx)` must fail if a previous transaction, not yet in the block, consumed a note
 //!      that `tx` is also consuming
+//!
+//! policy related requirements
+//! VT6: `verify_tx(tx)` must fail if `tx` is rejected by a verifier added to the state view,
+//!      without tracking `tx`
+//!
+//! prefetch related requirements
+//! VT7: The inputs of `tx` prefetched before a block was applied must be read again
+//!
+//! priority lane related requirements
+//! VT8: `verify_priority_tx(tx)` must fail if in-flight user transactions modify the account of
+//!      `tx`, until a block includes them
 
 use std::iter;
 
-use tokio::task::JoinSet;
+use miden_node_proto::domain::{
+    accounts::AccountUpdateDetails,
+    transactions::{TransactionReceipt, TransactionRejection},
+};
+use tokio::task::{self, JoinSet};
 
 use super::*;
-use crate::test_utils::MockStoreSuccessBuilder;
+use crate::{
+    test_utils::{block::MockBlockBuilder, MockStoreSuccessBuilder},
+    verifier::AccountDenylist,
+};
 
 /// Tests the happy path where 3 transactions who modify different accounts and consume different
 /// notes all verify successfully
@@ -98,7 +117,16 @@ async fn test_verify_tx_vt1() {
         verify_tx_result,
         Err(VerifyTxError::IncorrectAccountInitialHash {
             tx_initial_account_hash: account.states[1],
-            store_account_hash: Some(account.states[0]),
+            current_account_hash: Some(account.states[0]),
+        })
+    );
+
+    // The client is told which hash the store expects
+    assert_eq!(
+        verify_tx_result.unwrap_err().rejection(),
+        Some(TransactionRejection::IncorrectAccountInitialHash {
+            provided: account.states[1],
+            expected: Some(account.states[0]),
         })
     );
 }
@@ -172,20 +200,29 @@ async fn test_verify_tx_vt4() {
     let tx1 =
         MockProvenTxBuilder::with_account(account.id, account.states[0], account.states[1]).build();
 
-    // Notice: tx2 modifies the same account as tx1, even though from a different initial state,
-    // which is currently disallowed
+    // Notice: tx2 builds on the state of the account after tx1, which isn't in the store yet
     let tx2 =
         MockProvenTxBuilder::with_account(account.id, account.states[1], account.states[2]).build();
 
+    // Notice: tx3 starts from the state of the account in the store, which tx1 already modified
+    let tx3 =
+        MockProvenTxBuilder::with_account(account.id, account.states[0], account.states[2]).build();
+
     let state_view = DefaultStateView::new(store, false);
 
     let verify_tx1_result = state_view.verify_tx(&tx1).await;
     assert!(verify_tx1_result.is_ok());
 
     let verify_tx2_result = state_view.verify_tx(&tx2).await;
+    assert!(verify_tx2_result.is_ok());
+
+    let verify_tx3_result = state_view.verify_tx(&tx3).await;
     assert_eq!(
-        verify_tx2_result,
-        Err(VerifyTxError::AccountAlreadyModifiedByOtherTx(account.id))
+        verify_tx3_result,
+        Err(VerifyTxError::IncorrectAccountInitialHash {
+            tx_initial_account_hash: account.states[0],
+            current_account_hash: Some(account.states[2]),
+        })
     );
 }
 
@@ -212,10 +249,8 @@ async fn test_verify_tx_vt5() {
             .nullifiers(vec![nullifier_in_both_txs])
             .build();
 
-    // Notice: tx2 modifies the same account as tx1, even though from a different initial state,
-    // which is currently disallowed
     let tx2 =
-        MockProvenTxBuilder::with_account(account_2.id, account_2.states[1], account_2.states[2])
+        MockProvenTxBuilder::with_account(account_2.id, account_2.states[0], account_2.states[1])
             .nullifiers(vec![nullifier_in_both_txs])
             .build();
 
@@ -232,3 +267,214 @@ async fn test_verify_tx_vt5() {
         ))
     );
 }
+
+/// Tests that simulating a transaction runs the verification without tracking the transaction
+#[tokio::test]
+#[miden_node_test_macro::enable_logging]
+async fn test_simulate_tx_does_not_track_the_transaction() {
+    let account: MockPrivateAccount<3> = MockPrivateAccount::from(1);
+
+    let store = Arc::new(
+        MockStoreSuccessBuilder::from_accounts(iter::once((account.id, account.states[0]))).build(),
+    );
+
+    let tx =
+        MockProvenTxBuilder::with_account(account.id, account.states[0], account.states[1]).build();
+
+    let state_view = DefaultStateView::new(store, false);
+
+    let simulation = state_view.simulate_tx(&tx).await.unwrap();
+    assert_eq!(simulation.rejection, None);
+    assert_eq!(simulation.tx_inputs.account_hash, Some(account.states[0]));
+
+    // The simulated transaction isn't in-flight, so it can be verified
+    state_view.verify_tx(&tx).await.unwrap();
+
+    let simulation = state_view.simulate_tx(&tx).await.unwrap();
+    assert_eq!(
+        simulation.rejection,
+        Some(TransactionRejection::IncorrectAccountInitialHash {
+            provided: account.states[0],
+            expected: Some(account.states[1]),
+        })
+    );
+}
+
+/// Tests that simulating a transaction consuming a note already consumed in the store reports the
+/// rejection along with the transaction inputs
+#[tokio::test]
+#[miden_node_test_macro::enable_logging]
+async fn test_simulate_tx_reports_consumed_notes() {
+    let account: MockPrivateAccount<3> = MockPrivateAccount::from(1);
+
+    let nullifier_in_store = nullifier_by_index(0);
+
+    let store = Arc::new(
+        MockStoreSuccessBuilder::from_accounts(iter::once((account.id, account.states[0])))
+            .initial_nullifiers(BTreeSet::from_iter(iter::once(nullifier_in_store.inner())))
+            .initial_block_num(1)
+            .build(),
+    );
+
+    let tx = MockProvenTxBuilder::with_account(account.id, account.states[0], account.states[1])
+        .nullifiers(vec![nullifier_in_store])
+        .build();
+
+    let state_view = DefaultStateView::new(store, false);
+
+    let simulation = state_view.simulate_tx(&tx).await.unwrap();
+
+    assert_eq!(
+        simulation.rejection,
+        Some(TransactionRejection::InputNotesAlreadyConsumed(vec![nullifier_in_store]))
+    );
+    assert!(simulation.tx_inputs.nullifiers[&nullifier_in_store] != 0);
+}
+
+/// Tests requirement VT6
+#[tokio::test]
+#[miden_node_test_macro::enable_logging]
+async fn test_verify_tx_vt6() {
+    let denied: MockPrivateAccount<3> = MockPrivateAccount::from(1);
+    let allowed: MockPrivateAccount<3> = MockPrivateAccount::from(2);
+
+    let store = Arc::new(
+        MockStoreSuccessBuilder::from_accounts(
+            [(denied.id, denied.states[0]), (allowed.id, allowed.states[0])].into_iter(),
+        )
+        .build(),
+    );
+
+    let denied_tx =
+        MockProvenTxBuilder::with_account(denied.id, denied.states[0], denied.states[1])
+            .nullifiers_range(0..1)
+            .build();
+    let allowed_tx =
+        MockProvenTxBuilder::with_account(allowed.id, allowed.states[0], allowed.states[1])
+            .nullifiers_range(0..1)
+            .build();
+
+    let state_view =
+        DefaultStateView::new(store, false).with_verifier(AccountDenylist::new([denied.id]));
+
+    let verify_tx_result = state_view.verify_tx(&denied_tx).await;
+    assert_eq!(
+        verify_tx_result,
+        Err(VerifyTxError::PolicyViolation(format!("account {} is denied", denied.id)))
+    );
+
+    // The note consumed by the rejected transaction isn't in flight
+    state_view.verify_tx(&allowed_tx).await.unwrap();
+}
+
+/// Tests requirement VT7
+#[tokio::test]
+#[miden_node_test_macro::enable_logging]
+async fn test_verify_tx_vt7() {
+    let account_1: MockPrivateAccount<3> = MockPrivateAccount::from(1);
+    let account_2: MockPrivateAccount<3> = MockPrivateAccount::from(2);
+    let nullifier_in_both_txs = nullifier_by_index(0);
+
+    let store = Arc::new(
+        MockStoreSuccessBuilder::from_accounts(
+            [account_1, account_2]
+                .into_iter()
+                .map(|account| (account.id, account.states[0])),
+        )
+        .build(),
+    );
+
+    let tx1 =
+        MockProvenTxBuilder::with_account(account_1.id, account_1.states[0], account_1.states[1])
+            .nullifiers(vec![nullifier_in_both_txs])
+            .build();
+    let tx2 =
+        MockProvenTxBuilder::with_account(account_2.id, account_2.states[0], account_2.states[1])
+            .nullifiers(vec![nullifier_in_both_txs])
+            .build();
+
+    let state_view = DefaultStateView::new(store.clone(), false);
+    state_view.prefetch_tx_inputs(&tx1);
+    state_view.verify_tx(&tx1).await.unwrap();
+
+    // The inputs of tx2 are read while its note is only consumed by the in-flight tx1
+    state_view.prefetch_tx_inputs(&tx2);
+    task::yield_now().await;
+
+    let block = MockBlockBuilder::new(&store)
+        .await
+        .account_updates(vec![AccountUpdateDetails {
+            account_id: account_1.id,
+            final_state_hash: account_1.states[1],
+            details: None,
+        }])
+        .produced_nullifiers(vec![nullifier_in_both_txs])
+        .build();
+    state_view.apply_block(&block).await.unwrap();
+
+    // tx1 is no longer in flight, the note is consumed according to the store
+    assert_eq!(
+        state_view.verify_tx(&tx2).await,
+        Err(VerifyTxError::InputNotesAlreadyConsumed(
+            InputNotes::new(vec![nullifier_in_both_txs]).unwrap()
+        ))
+    );
+}
+
+/// Tests requirement VT8
+#[tokio::test]
+#[miden_node_test_macro::enable_logging]
+async fn test_verify_tx_vt8() {
+    let account: MockPrivateAccount<4> = MockPrivateAccount::from(1);
+
+    let store = Arc::new(
+        MockStoreSuccessBuilder::from_accounts(iter::once((account.id, account.states[0]))).build(),
+    );
+
+    let tx = |from: usize| {
+        MockProvenTxBuilder::with_account(
+            account.id,
+            account.states[from],
+            account.states[from + 1],
+        )
+        .build()
+    };
+    let (priority_tx1, user_tx, priority_tx2) = (tx(0), tx(1), tx(2));
+
+    let state_view = DefaultStateView::new(store.clone(), false);
+
+    // Priority transactions are chained as the user transactions are
+    state_view.verify_priority_tx(&priority_tx1).await.unwrap();
+    state_view.verify_tx(&user_tx).await.unwrap();
+
+    let err = state_view.verify_priority_tx(&priority_tx2).await.unwrap_err();
+    assert_eq!(err, VerifyTxError::AccountModifiedByUserTransactions(account.id));
+    assert_eq!(err.rejection(), Some(TransactionRejection::AccountAlreadyModified(account.id)));
+
+    let mut block = MockBlockBuilder::new(&store)
+        .await
+        .account_updates(
+            account.states[1..3]
+                .iter()
+                .map(|&final_state_hash| AccountUpdateDetails {
+                    account_id: account.id,
+                    final_state_hash,
+                    details: None,
+                })
+                .collect(),
+        )
+        .build();
+    block.transactions = [&priority_tx1, &user_tx]
+        .into_iter()
+        .map(|tx| TransactionReceipt {
+            transaction: tx.into(),
+            block_num: block.header.block_num(),
+            batch_index: 0,
+            fee: None,
+            cycles: 0,
+        })
+        .collect();
+    state_view.apply_block(&block).await.unwrap();
+
+    state_view.verify_priority_tx(&priority_tx2).await.unwrap();
+}
@@ -1,5 +1,5 @@
 use std::{
-    collections::BTreeMap,
+    collections::{BTreeMap, BTreeSet},
     fmt::{Display, Formatter},
 };
 
@@ -10,21 +10,31 @@ use miden_node_proto::{
     generated::{
         account, digest,
         note::NoteCreated,
-        requests::{ApplyBlockRequest, GetBlockInputsRequest, GetTransactionInputsRequest},
-        responses::{GetTransactionInputsResponse, NullifierTransactionInputRecord},
+        requests::{
+            ApplyBlockRequest, GetBlockHeaderByNumberRequest, GetBlockInputsRequest,
+            GetTransactionInputsRequest,
+        },
+        responses::{
+            GetBlockHeaderByNumberResponse, GetTransactionInputsResponse,
+            NullifierTransactionInputRecord,
+        },
         store::api_client as store_client,
     },
     AccountState,
 };
-use miden_node_utils::formatting::{format_map, format_opt};
+use miden_node_utils::{
+    formatting::{format_map, format_opt},
+    transport::ComponentChannel,
+};
 use miden_objects::{
-    accounts::AccountId, notes::Nullifier, transaction::OutputNote, utils::Serializable, Digest,
+    accounts::AccountId, notes::Nullifier, transaction::OutputNote, utils::Serializable,
+    BlockHeader, Digest,
 };
-use tonic::transport::Channel;
 use tracing::{debug, info, instrument};
 
-pub use crate::errors::{ApplyBlockError, BlockInputsError, TxInputsError};
+pub use crate::errors::{ApplyBlockError, BlockInputsError, StoreClientError};
 use crate::{
+    batch_builder::batch::TransactionBatch,
     block::{Block, BlockInputs},
     ProvenTransaction, COMPONENT,
 };
@@ -32,25 +42,41 @@ use crate::{
 // STORE TRAIT
 // ================================================================================================
 
+/// Client of the store used by the block producer.
+///
+/// The failed requests are reported as [StoreClientError]s, whose classification tells the callers
+/// whether to retry the request or to give up on it.
 #[async_trait]
 pub trait Store: ApplyBlock {
-    /// TODO: add comments
+    /// Returns the state of the account of `proven_tx` and the blocks consuming its input notes,
+    /// used to verify the transaction.
     async fn get_tx_inputs(
         &self,
         proven_tx: &ProvenTransaction,
-    ) -> Result<TransactionInputs, TxInputsError>;
+    ) -> Result<TransactionInputs, StoreClientError>;
 
-    /// TODO: add comments
+    /// Returns the latest block header, the chain peaks and the witnesses of the accounts and
+    /// nullifiers updated by a block, used to build the block.
     async fn get_block_inputs(
         &self,
         updated_accounts: impl Iterator<Item = &AccountId> + Send,
         produced_nullifiers: impl Iterator<Item = &Nullifier> + Send,
-    ) -> Result<BlockInputs, BlockInputsError>;
+    ) -> Result<BlockInputs, StoreClientError>;
+
+    /// Returns the header of the latest block applied by the store.
+    async fn get_chain_tip(&self) -> Result<BlockHeader, StoreClientError>;
 }
 
 #[async_trait]
 pub trait ApplyBlock: Send + Sync + 'static {
     async fn apply_block(&self, block: &Block) -> Result<(), ApplyBlockError>;
+
+    /// Forgets the transactions of `batches`, which were excluded from the block because they
+    /// conflict with the state of the store.
+    ///
+    /// Does nothing by default, for the implementations which don't track the transactions in
+    /// flight.
+    async fn discard_batches(&self, _batches: &[TransactionBatch]) {}
 }
 
 // TRANSACTION INPUTS
@@ -106,12 +132,12 @@ impl TryFrom<GetTransactionInputsResponse> for TransactionInputs {
 // ================================================================================================
 
 pub struct DefaultStore {
-    store: store_client::ApiClient<Channel>,
+    store: store_client::ApiClient<ComponentChannel>,
 }
 
 impl DefaultStore {
     /// TODO: this should probably take store connection string and create a connection internally
-    pub fn new(store: store_client::ApiClient<Channel>) -> Self {
+    pub fn new(store: store_client::ApiClient<ComponentChannel>) -> Self {
         Self { store }
     }
 }
@@ -143,6 +169,7 @@ impl ApplyBlock for DefaultStore {
                             note_type: note.metadata().note_type() as u32,
                             sender: Some(note.metadata().sender().into()),
                             tag: note.metadata().tag().into(),
+                            aux: note.metadata().aux().as_int(),
                             details,
                         }
                     })
@@ -155,14 +182,16 @@ impl ApplyBlock for DefaultStore {
             accounts: convert(&block.updated_accounts),
             nullifiers: convert(&block.produced_nullifiers),
             notes,
+            transactions: convert(&block.transactions),
+            signature: block.signature.as_ref().map(Serializable::to_bytes).unwrap_or_default(),
+            proof: block.proof.clone().unwrap_or_default(),
         });
 
-        let _ = self
-            .store
-            .clone()
-            .apply_block(request)
-            .await
-            .map_err(|status| ApplyBlockError::GrpcClientError(status.message().to_string()))?;
+        let response =
+            self.store.clone().apply_block(request).await.map_err(StoreClientError::from)?;
+        if response.into_inner().already_applied {
+            info!(target: COMPONENT, block_num = block.header.block_num(), "Block was already applied by the store");
+        }
 
         Ok(())
     }
@@ -177,7 +206,7 @@ impl Store for DefaultStore {
     async fn get_tx_inputs(
         &self,
         proven_tx: &ProvenTransaction,
-    ) -> Result<TransactionInputs, TxInputsError> {
+    ) -> Result<TransactionInputs, StoreClientError> {
         let message = GetTransactionInputsRequest {
             account_id: Some(proven_tx.account_id().into()),
             nullifiers: proven_tx.input_notes().iter().map(|&nullifier| nullifier.into()).collect(),
@@ -187,20 +216,14 @@ impl Store for DefaultStore {
         debug!(target: COMPONENT, ?message);
 
         let request = tonic::Request::new(message);
-        let response = self
-            .store
-            .clone()
-            .get_transaction_inputs(request)
-            .await
-            .map_err(|status| TxInputsError::GrpcClientError(status.message().to_string()))?
-            .into_inner();
+        let response = self.store.clone().get_transaction_inputs(request).await?.into_inner();
 
         debug!(target: COMPONENT, ?response);
 
         let tx_inputs: TransactionInputs = response.try_into()?;
 
         if tx_inputs.account_id != proven_tx.account_id() {
-            return Err(TxInputsError::MalformedResponse(format!(
+            return Err(StoreClientError::MalformedResponse(format!(
                 "incorrect account id returned from store. Got: {}, expected: {}",
                 tx_inputs.account_id,
                 proven_tx.account_id()
@@ -216,22 +239,35 @@ impl Store for DefaultStore {
         &self,
         updated_accounts: impl Iterator<Item = &AccountId> + Send,
         produced_nullifiers: impl Iterator<Item = &Nullifier> + Send,
-    ) -> Result<BlockInputs, BlockInputsError> {
+    ) -> Result<BlockInputs, StoreClientError> {
+        // An account updated by several batches is requested once
+        let updated_accounts: BTreeSet<_> = updated_accounts.collect();
+        let produced_nullifiers: BTreeSet<_> = produced_nullifiers.collect();
+
         let request = tonic::Request::new(GetBlockInputsRequest {
             account_ids: updated_accounts
+                .into_iter()
                 .map(|&account_id| account::AccountId::from(account_id))
                 .collect(),
-            nullifiers: produced_nullifiers.map(digest::Digest::from).collect(),
+            nullifiers: produced_nullifiers.into_iter().map(digest::Digest::from).collect(),
         });
 
-        let store_response = self
+        let store_response = self.store.clone().get_block_inputs(request).await?.into_inner();
+
+        Ok(BlockInputs::try_from(store_response)?)
+    }
+
+    async fn get_chain_tip(&self) -> Result<BlockHeader, StoreClientError> {
+        let request = GetBlockHeaderByNumberRequest { block_num: None };
+        let block_header = self
             .store
             .clone()
-            .get_block_inputs(request)
-            .await
-            .map_err(|err| BlockInputsError::GrpcClientError(err.message().to_string()))?
-            .into_inner();
+            .get_block_header_by_number(request)
+            .await?
+            .into_inner()
+            .block_header
+            .ok_or(GetBlockHeaderByNumberResponse::missing_field(stringify!(block_header)))?;
 
-        Ok(store_response.try_into()?)
+        Ok(block_header.try_into()?)
     }
 }
@@ -1,10 +1,11 @@
 use miden_node_proto::domain::accounts::AccountUpdateDetails;
+use miden_node_utils::chain_params::ACCOUNT_TREE_DEPTH;
 use miden_objects::{
     block::BlockNoteTree,
     crypto::merkle::{Mmr, SimpleSmt},
     notes::Nullifier,
     transaction::OutputNote,
-    BlockHeader, Digest, ACCOUNT_TREE_DEPTH, ONE, ZERO,
+    BlockHeader, Digest, ONE, ZERO,
 };
 
 use super::MockStoreSuccess;
@@ -146,6 +147,9 @@ impl MockBlockBuilder {
             updated_accounts: self.updated_accounts.unwrap_or_default(),
             created_notes,
             produced_nullifiers: self.produced_nullifiers.unwrap_or_default(),
+            transactions: Vec::new(),
+            signature: None,
+            proof: None,
         }
     }
 }
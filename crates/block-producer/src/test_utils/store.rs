@@ -1,21 +1,20 @@
 use std::collections::BTreeSet;
 
 use async_trait::async_trait;
+use miden_node_utils::chain_params::ACCOUNT_TREE_DEPTH;
 use miden_objects::{
     block::BlockNoteTree,
     crypto::merkle::{Mmr, SimpleSmt, Smt, ValuePath},
     notes::Nullifier,
     transaction::OutputNote,
-    BlockHeader, ACCOUNT_TREE_DEPTH, EMPTY_WORD, ONE, ZERO,
+    BlockHeader, EMPTY_WORD, ONE, ZERO,
 };
 
 use super::*;
 use crate::{
     batch_builder::TransactionBatch,
     block::{AccountWitness, Block, BlockInputs},
-    store::{
-        ApplyBlock, ApplyBlockError, BlockInputsError, Store, TransactionInputs, TxInputsError,
-    },
+    store::{ApplyBlock, ApplyBlockError, Store, StoreClientError, TransactionInputs},
     test_utils::block::{note_created_smt_from_batches, note_created_smt_from_note_batches},
     ProvenTransaction,
 };
@@ -132,6 +131,8 @@ impl MockStoreSuccessBuilder {
             chain_mmr: Arc::new(RwLock::new(chain_mmr)),
             last_block_header: Arc::new(RwLock::new(initial_block_header)),
             num_apply_block_called: Arc::new(RwLock::new(0)),
+            unavailable_apply_blocks: Arc::new(RwLock::new(0)),
+            lost_apply_block_responses: Arc::new(RwLock::new(0)),
         }
     }
 }
@@ -151,6 +152,14 @@ pub struct MockStoreSuccess {
 
     /// The number of times `apply_block()` was called
     pub num_apply_block_called: Arc<RwLock<u32>>,
+
+    /// The number of next calls to `apply_block()` failing as if the store was down, without
+    /// applying the block
+    pub unavailable_apply_blocks: Arc<RwLock<u32>>,
+
+    /// The number of next calls to `apply_block()` failing as if the connection was lost after the
+    /// block was applied
+    pub lost_apply_block_responses: Arc<RwLock<u32>>,
 }
 
 impl MockStoreSuccess {
@@ -164,6 +173,10 @@ impl MockStoreSuccess {
 #[async_trait]
 impl ApplyBlock for MockStoreSuccess {
     async fn apply_block(&self, block: &Block) -> Result<(), ApplyBlockError> {
+        if take_failure(&self.unavailable_apply_blocks).await {
+            return Err(StoreClientError::Unavailable("store is down".to_string()).into());
+        }
+
         // Intentionally, we take and hold both locks, to prevent calls to `get_tx_inputs()` from going through while we're updating the store's data structure
         let mut locked_accounts = self.accounts.write().await;
         let mut locked_produced_nullifiers = self.produced_nullifiers.write().await;
@@ -193,16 +206,31 @@ impl ApplyBlock for MockStoreSuccess {
         // update num_apply_block_called
         *self.num_apply_block_called.write().await += 1;
 
+        if take_failure(&self.lost_apply_block_responses).await {
+            return Err(StoreClientError::Unavailable("connection lost".to_string()).into());
+        }
+
         Ok(())
     }
 }
 
+/// Decrements `failures`, returns `false` if there are no failures left.
+async fn take_failure(failures: &RwLock<u32>) -> bool {
+    let mut failures = failures.write().await;
+    let Some(remaining) = failures.checked_sub(1) else {
+        return false;
+    };
+    *failures = remaining;
+
+    true
+}
+
 #[async_trait]
 impl Store for MockStoreSuccess {
     async fn get_tx_inputs(
         &self,
         proven_tx: &ProvenTransaction,
-    ) -> Result<TransactionInputs, TxInputsError> {
+    ) -> Result<TransactionInputs, StoreClientError> {
         let locked_accounts = self.accounts.read().await;
         let locked_produced_nullifiers = self.produced_nullifiers.read().await;
 
@@ -237,7 +265,7 @@ impl Store for MockStoreSuccess {
         &self,
         updated_accounts: impl Iterator<Item = &AccountId> + Send,
         produced_nullifiers: impl Iterator<Item = &Nullifier> + Send,
-    ) -> Result<BlockInputs, BlockInputsError> {
+    ) -> Result<BlockInputs, StoreClientError> {
         let locked_accounts = self.accounts.read().await;
         let locked_produced_nullifiers = self.produced_nullifiers.read().await;
 
@@ -268,6 +296,10 @@ impl Store for MockStoreSuccess {
             nullifiers,
         })
     }
+
+    async fn get_chain_tip(&self) -> Result<BlockHeader, StoreClientError> {
+        Ok(*self.last_block_header.read().await)
+    }
 }
 
 #[derive(Default)]
@@ -276,7 +308,7 @@ pub struct MockStoreFailure;
 #[async_trait]
 impl ApplyBlock for MockStoreFailure {
     async fn apply_block(&self, _block: &Block) -> Result<(), ApplyBlockError> {
-        Err(ApplyBlockError::GrpcClientError(String::new()))
+        Err(StoreClientError::Rejected(String::new()).into())
     }
 }
 
@@ -285,15 +317,19 @@ impl Store for MockStoreFailure {
     async fn get_tx_inputs(
         &self,
         _proven_tx: &ProvenTransaction,
-    ) -> Result<TransactionInputs, TxInputsError> {
-        Err(TxInputsError::Dummy)
+    ) -> Result<TransactionInputs, StoreClientError> {
+        Err(StoreClientError::Rejected(String::new()))
     }
 
     async fn get_block_inputs(
         &self,
         _updated_accounts: impl Iterator<Item = &AccountId> + Send,
         _produced_nullifiers: impl Iterator<Item = &Nullifier> + Send,
-    ) -> Result<BlockInputs, BlockInputsError> {
-        Err(BlockInputsError::GrpcClientError(String::new()))
+    ) -> Result<BlockInputs, StoreClientError> {
+        Err(StoreClientError::Rejected(String::new()))
+    }
+
+    async fn get_chain_tip(&self) -> Result<BlockHeader, StoreClientError> {
+        Err(StoreClientError::Rejected(String::new()))
     }
 }
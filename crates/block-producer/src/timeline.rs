@@ -0,0 +1,267 @@
+//! Timelines of the production of the latest blocks, recording when each block reached each stage
+//! of the pipeline, so the operator can see where a slow block spent its time.
+use std::{
+    collections::VecDeque,
+    fmt::{Display, Formatter},
+    sync::Mutex,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use miden_node_proto::generated::responses::{BlockTimelineEvent, GetBlockTimelineResponse};
+
+use crate::SERVER_BLOCK_TIMELINES;
+
+// BLOCK STAGES
+// ================================================================================================
+
+/// Stage of the production of a block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BlockStage {
+    /// The batches of the block were handed to the block builder
+    BatchesSelected,
+    /// The witnesses of the accounts and nullifiers updated by the block were fetched from the
+    /// store
+    InputsFetched,
+    /// The header of the block was computed
+    BlockBuilt,
+    /// The block was signed and its proof attached
+    ProofDone,
+    /// The block was applied by the store and its transactions are no longer in flight
+    Committed,
+    /// The production of the block failed, its batches are built into a block again
+    Failed(String),
+}
+
+impl Display for BlockStage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::BatchesSelected => "batches_selected",
+            Self::InputsFetched => "inputs_fetched",
+            Self::BlockBuilt => "block_built",
+            Self::ProofDone => "proof_done",
+            Self::Committed => "committed",
+            Self::Failed(_) => "failed",
+        })
+    }
+}
+
+/// A stage reached by a block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockEvent {
+    pub stage: BlockStage,
+    /// Time the stage was reached, in milliseconds since the UNIX epoch
+    pub timestamp_ms: u64,
+}
+
+impl BlockEvent {
+    fn now(stage: BlockStage) -> Self {
+        let timestamp_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_millis() as u64)
+            .unwrap_or_default();
+
+        Self { stage, timestamp_ms }
+    }
+}
+
+// BLOCK TIMELINES
+// ================================================================================================
+
+/// The stages reached by a block, including the failed attempts to produce it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockTimeline {
+    pub block_num: u32,
+    pub num_batches: usize,
+    pub num_transactions: usize,
+    pub events: Vec<BlockEvent>,
+}
+
+impl From<BlockTimeline> for GetBlockTimelineResponse {
+    fn from(timeline: BlockTimeline) -> Self {
+        Self {
+            block_num: timeline.block_num,
+            num_batches: timeline.num_batches as u32,
+            num_transactions: timeline.num_transactions as u32,
+            events: timeline
+                .events
+                .into_iter()
+                .map(|event| BlockTimelineEvent {
+                    stage: event.stage.to_string(),
+                    timestamp_ms: event.timestamp_ms,
+                    error: match event.stage {
+                        BlockStage::Failed(error) => Some(error),
+                        _ => None,
+                    },
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Ring buffer of the timelines of the latest blocks, the oldest timelines being dropped once
+/// `capacity` blocks are recorded.
+#[derive(Debug)]
+pub struct BlockTimelines {
+    capacity: usize,
+    timelines: Mutex<VecDeque<BlockTimeline>>,
+}
+
+impl BlockTimelines {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            timelines: Mutex::new(VecDeque::with_capacity(capacity)),
+        }
+    }
+
+    /// Returns the timeline of the block `block_num`, the latest block if [None].
+    pub fn get(&self, block_num: Option<u32>) -> Option<BlockTimeline> {
+        let timelines = self.timelines.lock().expect("timelines lock poisoned");
+        match block_num {
+            Some(block_num) => {
+                timelines.iter().rev().find(|timeline| timeline.block_num == block_num).cloned()
+            },
+            None => timelines.back().cloned(),
+        }
+    }
+
+    /// Starts recording the production of a block from `num_batches` batches of
+    /// `num_transactions` transactions, whose number is known once its inputs are fetched.
+    pub fn start(&self, num_batches: usize, num_transactions: usize) -> TimelineRecorder<'_> {
+        TimelineRecorder {
+            timelines: self,
+            block_num: None,
+            num_batches,
+            num_transactions,
+            pending: vec![BlockEvent::now(BlockStage::BatchesSelected)],
+        }
+    }
+
+    fn append(
+        &self,
+        block_num: u32,
+        num_batches: usize,
+        num_transactions: usize,
+        events: impl IntoIterator<Item = BlockEvent>,
+    ) {
+        let mut timelines = self.timelines.lock().expect("timelines lock poisoned");
+
+        // A block produced again after a failure is recorded in the timeline of the failed attempt
+        let timeline = match timelines.iter().position(|timeline| timeline.block_num == block_num) {
+            Some(index) => &mut timelines[index],
+            None => {
+                if timelines.len() == self.capacity {
+                    timelines.pop_front();
+                }
+                timelines.push_back(BlockTimeline {
+                    block_num,
+                    num_batches,
+                    num_transactions,
+                    events: Vec::new(),
+                });
+                timelines.back_mut().expect("timeline was just pushed")
+            },
+        };
+
+        timeline.num_batches = num_batches;
+        timeline.num_transactions = num_transactions;
+        timeline.events.extend(events);
+    }
+}
+
+impl Default for BlockTimelines {
+    fn default() -> Self {
+        Self::new(SERVER_BLOCK_TIMELINES)
+    }
+}
+
+/// Records the stages reached by a block while it is produced.
+///
+/// The stages reached before the number of the block is known are recorded once it is, they are
+/// dropped if the production fails before.
+pub struct TimelineRecorder<'a> {
+    timelines: &'a BlockTimelines,
+    block_num: Option<u32>,
+    num_batches: usize,
+    num_transactions: usize,
+    pending: Vec<BlockEvent>,
+}
+
+impl TimelineRecorder<'_> {
+    /// Records the stages reached so far in the timeline of the block `block_num`.
+    pub fn set_block_num(&mut self, block_num: u32) {
+        self.block_num = Some(block_num);
+        self.record_pending();
+    }
+
+    /// Records that the block reached `stage` now.
+    pub fn record(&mut self, stage: BlockStage) {
+        self.pending.push(BlockEvent::now(stage));
+        self.record_pending();
+    }
+
+    /// Records that the production of the block failed with `err`.
+    pub fn fail(&mut self, err: &impl Display) {
+        self.record(BlockStage::Failed(err.to_string()));
+    }
+
+    fn record_pending(&mut self) {
+        if let Some(block_num) = self.block_num {
+            self.timelines.append(
+                block_num,
+                self.num_batches,
+                self.num_transactions,
+                self.pending.drain(..),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stages(timeline: &BlockTimeline) -> Vec<BlockStage> {
+        timeline.events.iter().map(|event| event.stage.clone()).collect()
+    }
+
+    #[test]
+    fn timelines_are_recorded_once_the_block_number_is_known() {
+        let timelines = BlockTimelines::new(2);
+
+        let mut recorder = timelines.start(2, 5);
+        assert_eq!(timelines.get(None), None);
+        recorder.set_block_num(1);
+        recorder.record(BlockStage::InputsFetched);
+        recorder.fail(&"store unavailable");
+
+        // The block is produced again after the failure
+        let mut recorder = timelines.start(3, 6);
+        recorder.set_block_num(1);
+        recorder.record(BlockStage::Committed);
+
+        let timeline = timelines.get(Some(1)).unwrap();
+        assert_eq!((timeline.num_batches, timeline.num_transactions), (3, 6));
+        assert_eq!(
+            stages(&timeline),
+            vec![
+                BlockStage::BatchesSelected,
+                BlockStage::InputsFetched,
+                BlockStage::Failed("store unavailable".to_string()),
+                BlockStage::BatchesSelected,
+                BlockStage::Committed,
+            ]
+        );
+        assert!(timeline.events.windows(2).all(|w| w[0].timestamp_ms <= w[1].timestamp_ms));
+
+        let response = GetBlockTimelineResponse::from(timeline);
+        assert_eq!(response.events[2].stage, "failed");
+        assert_eq!(response.events[2].error.as_deref(), Some("store unavailable"));
+
+        // The oldest timeline is dropped
+        timelines.start(1, 1).set_block_num(2);
+        timelines.start(1, 1).set_block_num(3);
+        assert_eq!(timelines.get(Some(1)), None);
+        assert_eq!(timelines.get(None).unwrap().block_num, 3);
+    }
+}
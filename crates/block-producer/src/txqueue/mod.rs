@@ -1,19 +1,38 @@
-use std::{sync::Arc, time::Duration};
+use std::{mem, sync::Arc, time::Duration};
 
 use async_trait::async_trait;
-use miden_objects::MAX_NOTES_PER_BATCH;
-use tokio::{sync::RwLock, time};
-use tracing::{debug, info, info_span, instrument, Instrument};
+use miden_node_proto::{
+    domain::transactions::TransactionRejection,
+    generated::responses::{
+        AccountTransactionInputRecord, NullifierTransactionInputRecord, SimulateTransactionResponse,
+    },
+};
+use miden_node_utils::chain_params::MAX_NOTES_PER_BATCH;
+use tokio::{
+    sync::{Mutex, Notify, RwLock},
+    time,
+};
+use tracing::{debug, info, info_span, instrument, warn, Instrument};
 
 use crate::{
-    batch_builder::BatchBuilder,
+    batch_builder::{sizing::BatchSizing, BatchBuilder},
+    config::BatchSealingPolicy,
     errors::{AddTransactionError, VerifyTxError},
+    mempool::Mempool,
+    store::TransactionInputs,
     ProvenTransaction, SharedRwVec, COMPONENT,
 };
 
 #[cfg(test)]
 mod tests;
 
+/// Number of attempts to verify a transaction while the store is unavailable, before the
+/// transaction is rejected.
+const STORE_UNAVAILABLE_ATTEMPTS: u32 = 3;
+
+/// Interval between two attempts to verify a transaction while the store is unavailable.
+const STORE_UNAVAILABLE_RETRY_INTERVAL: Duration = Duration::from_millis(200);
+
 // TRANSACTION VALIDATOR
 // ================================================================================================
 
@@ -32,24 +51,100 @@ pub trait TransactionValidator: Send + Sync + 'static {
     /// - Track the necessary state of the transaction until it is committed to the `store`, to
     ///   perform the check above.
     async fn verify_tx(&self, tx: &ProvenTransaction) -> Result<(), VerifyTxError>;
+
+    /// Runs the verification of `verify_tx` without tracking the transaction.
+    ///
+    /// A rejection of the transaction is reported in the returned [TransactionSimulation], errors
+    /// are returned only when the verification couldn't be performed.
+    async fn simulate_tx(
+        &self,
+        tx: &ProvenTransaction,
+    ) -> Result<TransactionSimulation, VerifyTxError>;
+
+    /// Runs the verification of `verify_tx` for a transaction of the priority lane, included in a
+    /// block ahead of the in-flight user transactions. Verifies it as a user transaction by
+    /// default.
+    async fn verify_priority_tx(&self, tx: &ProvenTransaction) -> Result<(), VerifyTxError> {
+        self.verify_tx(tx).await
+    }
+
+    /// Starts reading the inputs `verify_tx` needs from the store as soon as `tx` is received,
+    /// so that they are available once `tx` is verified. Does nothing by default.
+    fn prefetch_tx_inputs(&self, _tx: &ProvenTransaction) {}
+}
+
+/// Outcome of a transaction verification which doesn't add the transaction to the queue.
+#[derive(Debug)]
+pub struct TransactionSimulation {
+    /// The inputs of the transaction fetched from the store
+    pub tx_inputs: TransactionInputs,
+    /// The reason the transaction would be rejected, [None] if it would be accepted
+    pub rejection: Option<TransactionRejection>,
+}
+
+impl From<TransactionSimulation> for SimulateTransactionResponse {
+    fn from(simulation: TransactionSimulation) -> Self {
+        let TransactionInputs { account_id, account_hash, nullifiers } = simulation.tx_inputs;
+
+        Self {
+            account_state: Some(AccountTransactionInputRecord {
+                account_id: Some(account_id.into()),
+                // The zero hash signifies that the account isn't in the store yet
+                account_hash: Some(account_hash.unwrap_or_default().into()),
+            }),
+            nullifiers: nullifiers
+                .into_iter()
+                .map(|(nullifier, block_num)| NullifierTransactionInputRecord {
+                    nullifier: Some(nullifier.into()),
+                    block_num,
+                })
+                .collect(),
+            rejection: simulation.rejection.map(Into::into),
+        }
+    }
+}
+
+// TRANSACTION LANES
+// ================================================================================================
+
+/// Lane of a transaction in the block production pipeline, the lanes being ordered by priority.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Lane {
+    /// Transactions of the operator, submitted through the admin API, included in the next block
+    /// ahead of the user transactions
+    Priority,
+    /// Transactions submitted by the clients
+    User,
 }
 
 // TRANSACTION QUEUE
 // ================================================================================================
 
 pub struct TransactionQueueOptions {
-    /// The frequency at which we try to build batches from transactions in the queue
-    pub build_batch_frequency: Duration,
-
-    /// The size of a batch
-    pub batch_size: usize,
+    /// Determines when the transactions in the queue are sealed into batches
+    pub sealing_policy: BatchSealingPolicy,
 }
 
 pub struct TransactionQueue<BB, TV> {
     ready_queue: SharedRwVec<ProvenTransaction>,
+    /// Operator transactions waiting to be batched, ahead of the ones of `ready_queue`
+    priority_queue: SharedRwVec<ProvenTransaction>,
     tx_validator: Arc<TV>,
     batch_builder: Arc<BB>,
     options: TransactionQueueOptions,
+    mempool: Arc<Mempool>,
+
+    /// Sizes the batches of user transactions, within the bounds of the sealing policy
+    batch_sizing: Arc<BatchSizing>,
+
+    /// Wakes up the queue when enough transactions are available to fill a batch
+    batch_full: Notify,
+
+    /// Maximum number of in-flight transactions of the priority lane, disabled if zero
+    priority_lane_capacity: usize,
+
+    /// Serializes the admission of the transactions of the priority lane
+    priority_admission: Mutex<()>,
 }
 
 impl<BB, TV> TransactionQueue<BB, TV>
@@ -64,79 +159,200 @@ where
     ) -> Self {
         Self {
             ready_queue: Arc::new(RwLock::new(Vec::new())),
+            priority_queue: Arc::new(RwLock::new(Vec::new())),
             tx_validator,
             batch_builder,
+            batch_sizing: Arc::new(BatchSizing::new(options.sealing_policy)),
             options,
+            mempool: Arc::new(Mempool::default()),
+            batch_full: Notify::new(),
+            priority_lane_capacity: 0,
+            priority_admission: Mutex::new(()),
         }
     }
 
+    /// Tracks the accepted transactions in `mempool`, instead of a mempool private to the queue.
+    pub fn with_mempool(mut self, mempool: Arc<Mempool>) -> Self {
+        self.mempool = mempool;
+        self
+    }
+
+    /// Seals the batches at the size set by `batch_sizing`, instead of the `max_txs` of the
+    /// queue's [BatchSealingPolicy].
+    ///
+    /// The time the transactions wait in the queue is recorded in `batch_sizing`.
+    pub fn with_batch_sizing(mut self, batch_sizing: Arc<BatchSizing>) -> Self {
+        self.batch_sizing = batch_sizing;
+        self
+    }
+
+    /// Accepts up to `capacity` in-flight transactions in the priority lane, which is disabled by
+    /// default.
+    ///
+    /// The transactions leave the lane once the mempool stops tracking them, so the mempool must
+    /// be the one the applied blocks are removed from.
+    pub fn with_priority_lane(mut self, capacity: usize) -> Self {
+        self.priority_lane_capacity = capacity;
+        self
+    }
+
+    /// Seals batches according to the queue's [BatchSealingPolicy].
+    ///
+    /// Full batches are sealed as soon as enough transactions are available, while the remaining
+    /// transactions are sealed into a partial batch once the maximum delay elapses.
     pub async fn run(self: Arc<Self>) {
-        let mut interval = time::interval(self.options.build_batch_frequency);
+        let mut interval = time::interval(self.options.sealing_policy.max_delay());
 
-        info!(target: COMPONENT, period_ms = interval.period().as_millis(), "Transaction queue started");
+        info!(
+            target: COMPONENT,
+            period_ms = interval.period().as_millis(),
+            policy = %self.options.sealing_policy,
+            "Transaction queue started"
+        );
 
         loop {
-            interval.tick().await;
-            self.try_build_batches().await;
+            tokio::select! {
+                _ = interval.tick() => self.try_build_batches(false).await,
+                _ = self.batch_full.notified() => self.try_build_batches(true).await,
+            }
         }
     }
 
-    /// Divides the queue in groups to be batched; those that failed are appended back on the queue
+    /// Divides the queue in groups to be batched; those that failed are appended back on the queue.
+    ///
+    /// If `full_batches_only` is set, user transactions which don't fill a batch are left in the
+    /// queue. The transactions of the priority lane are always batched, ahead of the user ones.
     #[instrument(target = "miden-block-producer", skip_all)]
-    async fn try_build_batches(&self) {
-        let mut txs: Vec<ProvenTransaction> = {
-            let mut locked_ready_queue = self.ready_queue.write().await;
-
-            // If there are no transactions in the queue, this call is a no-op. The [BatchBuilder]
-            // will produce empty blocks if necessary.
-            if locked_ready_queue.is_empty() {
-                debug!(target: COMPONENT, "Transaction queue empty");
-                return;
+    async fn try_build_batches(&self, full_batches_only: bool) {
+        let priority_txs: Vec<ProvenTransaction> =
+            self.priority_queue.write().await.drain(..).collect();
+        let txs: Vec<ProvenTransaction> = self.ready_queue.write().await.drain(..).collect();
+
+        // If there are no transactions in the queue, this call is a no-op. The [BatchBuilder]
+        // will produce empty blocks if necessary.
+        if priority_txs.is_empty() && txs.is_empty() {
+            debug!(target: COMPONENT, "Transaction queue empty");
+            return;
+        }
+
+        let (priority_batches, _) = self.split_batches(priority_txs, false);
+        let (batches, remaining) = self.split_batches(txs, full_batches_only);
+
+        if !remaining.is_empty() {
+            // Put the remaining transactions back at the front of the queue, they are batched
+            // once more transactions arrive or the timer elapses
+            self.ready_queue.write().await.splice(0..0, remaining);
+        }
+
+        for batch in priority_batches {
+            self.dispatch_batch(batch, Lane::Priority).await;
+        }
+        for batch in batches {
+            self.dispatch_batch(batch, Lane::User).await;
+        }
+    }
+
+    /// Divides `txs` in batches according to the queue's [BatchSealingPolicy], in order, the
+    /// batches being sealed at the current target size.
+    ///
+    /// If `full_batches_only` is set, the transactions which don't fill a batch are returned
+    /// apart instead of being sealed into a partial batch.
+    fn split_batches(
+        &self,
+        txs: Vec<ProvenTransaction>,
+        full_batches_only: bool,
+    ) -> (Vec<Vec<ProvenTransaction>>, Vec<ProvenTransaction>) {
+        let policy = self.batch_sizing.sealing_policy();
+        let mut batches = Vec::new();
+        let mut batch = Vec::with_capacity(policy.max_txs);
+        let mut notes_in_batch = 0;
+
+        for tx in txs {
+            let num_notes = tx.output_notes().num_notes();
+
+            debug_assert!(
+                num_notes <= MAX_NOTES_PER_BATCH,
+                "Sanity check, the number of output notes of a single transaction must never be larger than the batch maximum",
+            );
+
+            // The tx doesn't fit in the current batch, forward the current batch
+            if !batch.is_empty() && notes_in_batch + num_notes > policy.max_notes() {
+                batches.push(mem::take(&mut batch));
+                notes_in_batch = 0;
+            }
+
+            notes_in_batch += num_notes;
+            batch.push(tx);
+
+            if policy.is_full(batch.len(), notes_in_batch) {
+                batches.push(mem::take(&mut batch));
+                notes_in_batch = 0;
             }
+        }
+
+        if !batch.is_empty() && !full_batches_only {
+            batches.push(mem::take(&mut batch));
+        }
+
+        (batches, batch)
+    }
 
-            locked_ready_queue.drain(..).collect()
+    /// Hands `batch` to the batch builder in `lane`, its transactions are appended back to the
+    /// queue of the lane if the batch fails.
+    async fn dispatch_batch(&self, batch: Vec<ProvenTransaction>, lane: Lane) {
+        let queue_wait = self.mempool.add_batch(batch.iter().map(|tx| tx.id())).await;
+        if let (Lane::User, Some(queue_wait)) = (lane, queue_wait) {
+            self.batch_sizing.record_queue_wait(queue_wait);
+        }
+
+        let queue = match lane {
+            Lane::Priority => self.priority_queue.clone(),
+            Lane::User => self.ready_queue.clone(),
         };
+        let batch_builder = self.batch_builder.clone();
+        let mempool = self.mempool.clone();
 
-        while !txs.is_empty() {
-            let mut batch = Vec::with_capacity(self.options.batch_size);
-            let mut notes_in_batch = 0;
-
-            while let Some(tx) = txs.pop() {
-                notes_in_batch += tx.output_notes().num_notes();
-
-                debug_assert!(
-                    tx.output_notes().num_notes() <= MAX_NOTES_PER_BATCH,
-                    "Sanity check, the number of output notes of a single transaction must never be larger than the batch maximum",
-                );
-
-                if notes_in_batch > MAX_NOTES_PER_BATCH || batch.len() == self.options.batch_size {
-                    // Batch would be too big in number of notes or transactions. Push the tx back
-                    // to the list of available transactions and forward the current batch.
-                    txs.push(tx);
-                    break;
-                } else {
-                    // The tx fits in the current batch
-                    batch.push(tx)
+        tokio::spawn(
+            async move {
+                let result = match lane {
+                    Lane::Priority => batch_builder.build_priority_batch(batch).await,
+                    Lane::User => batch_builder.build_batch(batch).await,
+                };
+                match result {
+                    Ok(_) => {
+                        // batch was successfully built, do nothing
+                    },
+                    Err(e) => {
+                        // batch building failed, add txs back at the end of the queue
+                        let mut txs = e.into_transactions();
+                        mempool.requeue(txs.iter().map(|tx| tx.id())).await;
+                        queue.write().await.append(&mut txs);
+                    },
                 }
             }
+            .instrument(info_span!(target: COMPONENT, "batch_builder")),
+        );
+    }
 
-            let ready_queue = self.ready_queue.clone();
-            let batch_builder = self.batch_builder.clone();
-
-            tokio::spawn(
-                async move {
-                    match batch_builder.build_batch(batch).await {
-                        Ok(_) => {
-                            // batch was successfully built, do nothing
-                        },
-                        Err(e) => {
-                            // batch building failed, add txs back at the end of the queue
-                            ready_queue.write().await.append(&mut e.into_transactions());
-                        },
-                    }
-                }
-                .instrument(info_span!(target: COMPONENT, "batch_builder")),
-            );
+    /// Verifies `tx` for `lane`, again while the store can't be reached.
+    async fn verify(&self, tx: &ProvenTransaction, lane: Lane) -> Result<(), AddTransactionError> {
+        // The transaction is only verified again if the store couldn't be reached, it is dropped
+        // if the store rejected the request or the transaction is invalid
+        let mut attempts = 1;
+        loop {
+            let result = match lane {
+                Lane::Priority => self.tx_validator.verify_priority_tx(tx).await,
+                Lane::User => self.tx_validator.verify_tx(tx).await,
+            };
+            let Err(err) = result else {
+                return Ok(());
+            };
+            if !err.is_retriable() || attempts == STORE_UNAVAILABLE_ATTEMPTS {
+                return Err(AddTransactionError::VerificationFailed(err));
+            }
+            warn!(target: COMPONENT, %err, attempts, "Store unavailable, verifying the transaction again");
+            time::sleep(STORE_UNAVAILABLE_RETRY_INTERVAL).await;
+            attempts += 1;
         }
     }
 
@@ -148,19 +364,86 @@ where
     pub async fn add_transaction(&self, tx: ProvenTransaction) -> Result<(), AddTransactionError> {
         info!(target: COMPONENT, tx_id = %tx.id().to_hex(), account_id = %tx.account_id().to_hex());
 
-        self.tx_validator
-            .verify_tx(&tx)
-            .await
-            .map_err(AddTransactionError::VerificationFailed)?;
+        self.verify(&tx, Lane::User).await?;
+        self.mempool.add(&tx, Lane::User).await;
 
-        let queue_len = {
+        let (queue_len, batch_full) = {
             let mut queue_write_guard = self.ready_queue.write().await;
             queue_write_guard.push(tx);
-            queue_write_guard.len()
+
+            let num_notes = queue_write_guard.iter().map(|tx| tx.output_notes().num_notes()).sum();
+
+            (
+                queue_write_guard.len(),
+                self.batch_sizing.sealing_policy().is_full(queue_write_guard.len(), num_notes),
+            )
         };
 
         info!(target: COMPONENT, queue_len, "Transaction added to tx queue");
 
+        if batch_full {
+            self.batch_full.notify_one();
+        }
+
         Ok(())
     }
+
+    /// Queues the operator transaction `tx` in the priority lane, to be included in the next
+    /// block ahead of the user transactions.
+    ///
+    /// The transaction is rejected if the lane already holds its capacity of in-flight
+    /// transactions, or if its account is modified by in-flight user transactions, which it can't
+    /// be included ahead of.
+    #[instrument(target = "miden-block-producer", skip_all, err)]
+    pub async fn add_priority_transaction(
+        &self,
+        tx: ProvenTransaction,
+    ) -> Result<(), AddTransactionError> {
+        info!(target: COMPONENT, tx_id = %tx.id().to_hex(), account_id = %tx.account_id().to_hex());
+
+        // Held until the transaction is tracked, so concurrent submissions can't overfill the lane
+        let _admission = self.priority_admission.lock().await;
+
+        let capacity = self.priority_lane_capacity;
+        if capacity == 0 {
+            return Err(AddTransactionError::PriorityLaneDisabled);
+        }
+        if self.mempool.num_in_lane(Lane::Priority).await >= capacity {
+            return Err(AddTransactionError::PriorityLaneFull { capacity });
+        }
+
+        self.verify(&tx, Lane::Priority).await?;
+        self.mempool.add(&tx, Lane::Priority).await;
+
+        let queue_len = {
+            let mut priority_queue = self.priority_queue.write().await;
+            priority_queue.push(tx);
+            priority_queue.len()
+        };
+
+        info!(target: COMPONENT, queue_len, "Transaction added to the priority lane");
+
+        // The lane isn't waiting for full batches, so the transaction is proven in time for the
+        // next block
+        self.batch_full.notify_one();
+
+        Ok(())
+    }
+
+    /// Starts reading the inputs of `tx` ahead of [Self::add_transaction], e.g. while the
+    /// transaction's fee is verified.
+    pub fn prefetch_tx_inputs(&self, tx: &ProvenTransaction) {
+        self.tx_validator.prefetch_tx_inputs(tx);
+    }
+
+    /// Verifies `tx` as [Self::add_transaction] does, without adding it to the queue.
+    #[instrument(target = "miden-block-producer", skip_all, err)]
+    pub async fn simulate_transaction(
+        &self,
+        tx: &ProvenTransaction,
+    ) -> Result<TransactionSimulation, VerifyTxError> {
+        info!(target: COMPONENT, tx_id = %tx.id().to_hex(), account_id = %tx.account_id().to_hex());
+
+        self.tx_validator.simulate_tx(tx).await
+    }
 }
@@ -1,7 +1,16 @@
+use std::{
+    sync::atomic::{AtomicU32, Ordering},
+    time::Duration,
+};
+
 use tokio::sync::mpsc::{self, error::TryRecvError};
 
 use super::*;
-use crate::{errors::BuildBatchError, test_utils::MockProvenTxBuilder, TransactionBatch};
+use crate::{
+    errors::{BuildBatchError, StoreClientError},
+    test_utils::MockProvenTxBuilder,
+    TransactionBatch,
+};
 
 // STRUCTS
 // ================================================================================================
@@ -14,6 +23,16 @@ impl TransactionValidator for TransactionValidatorSuccess {
     async fn verify_tx(&self, _tx: &ProvenTransaction) -> Result<(), VerifyTxError> {
         Ok(())
     }
+
+    async fn simulate_tx(
+        &self,
+        tx: &ProvenTransaction,
+    ) -> Result<TransactionSimulation, VerifyTxError> {
+        Ok(TransactionSimulation {
+            tx_inputs: simulated_tx_inputs(tx),
+            rejection: None,
+        })
+    }
 }
 
 /// All transactions fail to verify
@@ -22,7 +41,60 @@ struct TransactionValidatorFailure;
 #[async_trait]
 impl TransactionValidator for TransactionValidatorFailure {
     async fn verify_tx(&self, tx: &ProvenTransaction) -> Result<(), VerifyTxError> {
-        Err(VerifyTxError::AccountAlreadyModifiedByOtherTx(tx.account_id()))
+        Err(VerifyTxError::InvalidTransactionProof(tx.id()))
+    }
+
+    async fn simulate_tx(
+        &self,
+        tx: &ProvenTransaction,
+    ) -> Result<TransactionSimulation, VerifyTxError> {
+        Ok(TransactionSimulation {
+            tx_inputs: simulated_tx_inputs(tx),
+            rejection: Some(TransactionRejection::InvalidTransactionProof(tx.id())),
+        })
+    }
+}
+
+/// Transactions fail to verify as if the store was unavailable, until `failures` attempts failed
+struct TransactionValidatorUnavailable {
+    failures: u32,
+    attempts: AtomicU32,
+}
+
+impl TransactionValidatorUnavailable {
+    fn new(failures: u32) -> Self {
+        Self { failures, attempts: Default::default() }
+    }
+}
+
+#[async_trait]
+impl TransactionValidator for TransactionValidatorUnavailable {
+    async fn verify_tx(&self, _tx: &ProvenTransaction) -> Result<(), VerifyTxError> {
+        let attempt = self.attempts.fetch_add(1, Ordering::Relaxed);
+        if attempt < self.failures {
+            return Err(StoreClientError::Unavailable("store restarting".to_string()).into());
+        }
+
+        Ok(())
+    }
+
+    async fn simulate_tx(
+        &self,
+        tx: &ProvenTransaction,
+    ) -> Result<TransactionSimulation, VerifyTxError> {
+        Ok(TransactionSimulation {
+            tx_inputs: simulated_tx_inputs(tx),
+            rejection: None,
+        })
+    }
+}
+
+/// Returns inputs of `tx` as if its account and notes were unknown to the store
+fn simulated_tx_inputs(tx: &ProvenTransaction) -> TransactionInputs {
+    TransactionInputs {
+        account_id: tx.account_id(),
+        account_hash: None,
+        nullifiers: tx.input_notes().iter().map(|nullifier| (*nullifier, 0)).collect(),
     }
 }
 
@@ -60,6 +132,16 @@ impl BatchBuilder for BatchBuilderFailure {
     }
 }
 
+/// Builds a policy sealing batches of `batch_size` transactions, or every `build_batch_frequency`
+fn sealing_policy(build_batch_frequency: Duration, batch_size: usize) -> BatchSealingPolicy {
+    BatchSealingPolicy {
+        max_txs: batch_size,
+        max_notes: MAX_NOTES_PER_BATCH,
+        max_delay_ms: build_batch_frequency.as_millis() as u64,
+        adaptive: None,
+    }
+}
+
 // TESTS
 // ================================================================================================
 
@@ -75,7 +157,9 @@ async fn test_build_batch_success() {
     let tx_queue = Arc::new(TransactionQueue::new(
         Arc::new(TransactionValidatorSuccess),
         Arc::new(BatchBuilderSuccess::new(sender)),
-        TransactionQueueOptions { build_batch_frequency, batch_size },
+        TransactionQueueOptions {
+            sealing_policy: sealing_policy(build_batch_frequency, batch_size),
+        },
     ));
 
     // Starts the transaction queue task.
@@ -109,7 +193,8 @@ async fn test_build_batch_success() {
 
     // a batch will include up to `batch_size` transactions
     let mut txs = Vec::new();
-    for _ in 0..batch_size {
+    for i in 0..batch_size {
+        let tx = MockProvenTxBuilder::with_account_index(i as u32).build();
         tx_queue
             .add_transaction(tx.clone())
             .await
@@ -128,7 +213,8 @@ async fn test_build_batch_success() {
 
     // the transaction queue eagerly produces batches
     let mut txs = Vec::new();
-    for _ in 0..(2 * batch_size + 1) {
+    for i in 0..(2 * batch_size + 1) {
+        let tx = MockProvenTxBuilder::with_account_index(i as u32).build();
         tx_queue
             .add_transaction(tx.clone())
             .await
@@ -151,6 +237,93 @@ async fn test_build_batch_success() {
     );
 }
 
+/// Tests that a batch is sealed as soon as it is full, without waiting for the timer
+#[tokio::test(start_paused = true)]
+#[miden_node_test_macro::enable_logging]
+async fn test_build_batch_when_full() {
+    let build_batch_frequency = Duration::from_secs(60);
+    let batch_size = 3;
+    let (sender, mut receiver) = mpsc::unbounded_channel::<TransactionBatch>();
+
+    let tx_queue = Arc::new(TransactionQueue::new(
+        Arc::new(TransactionValidatorSuccess),
+        Arc::new(BatchBuilderSuccess::new(sender)),
+        TransactionQueueOptions {
+            sealing_policy: sealing_policy(build_batch_frequency, batch_size),
+        },
+    ));
+
+    // Starts the transaction queue task, and lets it consume the first tick of the timer.
+    tokio::spawn(tx_queue.clone().run());
+    tokio::task::yield_now().await;
+    let start = time::Instant::now();
+
+    let mut txs = Vec::new();
+    for i in 0..(batch_size + 1) {
+        let tx = MockProvenTxBuilder::with_account_index(i as u32).build();
+        tx_queue
+            .add_transaction(tx.clone())
+            .await
+            .expect("Transaction queue is running");
+        txs.push(tx);
+    }
+
+    let batch = receiver.recv().await.expect("A full batch is sealed");
+    assert!(start.elapsed() < build_batch_frequency, "Full batches don't wait for the timer");
+    let expected = TransactionBatch::new(txs[..batch_size].to_vec()).expect("Valid transactions");
+    assert_eq!(
+        expected, batch,
+        "The batch should have the first transactions added to the queue"
+    );
+
+    let batch = receiver.recv().await.expect("The partial batch is sealed");
+    assert!(start.elapsed() >= build_batch_frequency, "Partial batches wait for the timer");
+    let expected = TransactionBatch::new(txs[batch_size..].to_vec()).expect("Valid transactions");
+    assert_eq!(expected, batch, "The batch should have the remaining transaction");
+}
+
+/// Tests that batches are sealed once the maximum number of notes is reached
+#[tokio::test(start_paused = true)]
+#[miden_node_test_macro::enable_logging]
+async fn test_build_batch_notes_limit() {
+    let build_batch_frequency = Duration::from_secs(60);
+    let (sender, mut receiver) = mpsc::unbounded_channel::<TransactionBatch>();
+
+    let sealing_policy = BatchSealingPolicy {
+        max_txs: 10,
+        max_notes: 5,
+        max_delay_ms: build_batch_frequency.as_millis() as u64,
+        adaptive: None,
+    };
+    let tx_queue = Arc::new(TransactionQueue::new(
+        Arc::new(TransactionValidatorSuccess),
+        Arc::new(BatchBuilderSuccess::new(sender)),
+        TransactionQueueOptions { sealing_policy },
+    ));
+
+    tokio::spawn(tx_queue.clone().run());
+    tokio::task::yield_now().await;
+
+    // 3 notes per transaction, the second transaction doesn't fit in the first batch
+    let txs: Vec<_> = (0..3)
+        .map(|i| {
+            MockProvenTxBuilder::with_account_index(i)
+                .private_notes_created_range(u64::from(i) * 3..u64::from(i + 1) * 3)
+                .build()
+        })
+        .collect();
+    for tx in txs.iter().cloned() {
+        tx_queue.add_transaction(tx).await.expect("Transaction queue is running");
+    }
+
+    for tx in txs {
+        let batch = receiver.recv().await.expect("One batch per transaction");
+        let expected = TransactionBatch::new(vec![tx]).expect("Valid transactions");
+        assert_eq!(expected, batch, "Each batch should have a single transaction");
+    }
+    assert_eq!(Err(TryRecvError::Empty), receiver.try_recv(), "All transactions are batched");
+}
+
 /// Tests that when transactions fail to verify, they are not added to the queue
 #[tokio::test(start_paused = true)]
 #[miden_node_test_macro::enable_logging]
@@ -164,7 +337,9 @@ async fn test_tx_verify_failure() {
     let tx_queue = Arc::new(TransactionQueue::new(
         Arc::new(TransactionValidatorFailure),
         batch_builder.clone(),
-        TransactionQueueOptions { build_batch_frequency, batch_size },
+        TransactionQueueOptions {
+            sealing_policy: sealing_policy(build_batch_frequency, batch_size),
+        },
     ));
 
     // Start the queue
@@ -185,6 +360,147 @@ async fn test_tx_verify_failure() {
     }
 }
 
+/// Tests that the transactions are verified again while the store is unavailable, and rejected
+/// once the attempts are exhausted
+#[tokio::test(start_paused = true)]
+#[miden_node_test_macro::enable_logging]
+async fn test_tx_verify_retried_while_store_unavailable() {
+    let (sender, _receiver) = mpsc::unbounded_channel::<TransactionBatch>();
+    let options = || TransactionQueueOptions {
+        sealing_policy: sealing_policy(Duration::from_millis(5), 3),
+    };
+
+    let validator = Arc::new(TransactionValidatorUnavailable::new(STORE_UNAVAILABLE_ATTEMPTS - 1));
+    let tx_queue = TransactionQueue::new(
+        validator.clone(),
+        Arc::new(BatchBuilderSuccess::new(sender.clone())),
+        options(),
+    );
+    tx_queue
+        .add_transaction(MockProvenTxBuilder::with_account_index(0).build())
+        .await
+        .unwrap();
+    assert_eq!(tx_queue.ready_queue.read().await.len(), 1);
+
+    let validator = Arc::new(TransactionValidatorUnavailable::new(u32::MAX));
+    let tx_queue = TransactionQueue::new(
+        validator.clone(),
+        Arc::new(BatchBuilderSuccess::new(sender)),
+        options(),
+    );
+    let Err(AddTransactionError::VerificationFailed(err)) = tx_queue
+        .add_transaction(MockProvenTxBuilder::with_account_index(0).build())
+        .await
+    else {
+        panic!("The transaction should fail to verify");
+    };
+    assert!(err.is_retriable());
+    assert_eq!(validator.attempts.load(Ordering::Relaxed), STORE_UNAVAILABLE_ATTEMPTS);
+    assert!(tx_queue.ready_queue.read().await.is_empty());
+}
+
+/// Tests that the priority transactions are sealed right away, ahead of the user transactions, and
+/// that the priority lane holds at most its capacity of in-flight transactions
+#[tokio::test(start_paused = true)]
+#[miden_node_test_macro::enable_logging]
+async fn test_priority_lane() {
+    let build_batch_frequency = Duration::from_secs(60);
+    let (sender, mut receiver) = mpsc::unbounded_channel::<TransactionBatch>();
+    let mempool = Arc::new(Mempool::default());
+
+    let tx_queue = Arc::new(
+        TransactionQueue::new(
+            Arc::new(TransactionValidatorSuccess),
+            Arc::new(BatchBuilderSuccess::new(sender.clone())),
+            TransactionQueueOptions {
+                sealing_policy: sealing_policy(build_batch_frequency, 3),
+            },
+        )
+        .with_mempool(mempool.clone())
+        .with_priority_lane(1),
+    );
+
+    tokio::spawn(tx_queue.clone().run());
+    tokio::task::yield_now().await;
+    let start = time::Instant::now();
+
+    let user_tx = MockProvenTxBuilder::with_account_index(0).build();
+    let priority_tx = MockProvenTxBuilder::with_account_index(1).build();
+    tx_queue.add_transaction(user_tx.clone()).await.unwrap();
+    tx_queue.add_priority_transaction(priority_tx.clone()).await.unwrap();
+
+    let batch = receiver.recv().await.expect("The priority transaction is sealed");
+    assert!(
+        start.elapsed() < build_batch_frequency,
+        "Priority batches don't wait for the timer"
+    );
+    assert_eq!(batch, TransactionBatch::new(vec![priority_tx.clone()]).unwrap());
+    assert_eq!(tx_queue.ready_queue.read().await.as_slice(), [user_tx]);
+
+    // The lane is full until a block includes the priority transaction
+    let next_priority_tx = MockProvenTxBuilder::with_account_index(2).build();
+    let err = tx_queue.add_priority_transaction(next_priority_tx.clone()).await.unwrap_err();
+    assert_eq!(err, AddTransactionError::PriorityLaneFull { capacity: 1 });
+
+    mempool.remove([priority_tx.id()]).await;
+    tx_queue.add_priority_transaction(next_priority_tx).await.unwrap();
+    let contents = mempool.contents(None, usize::MAX).await;
+    assert_eq!(contents.iter().filter(|tx| tx.priority).count(), 1);
+
+    // The lane is disabled by default
+    let tx_queue = TransactionQueue::new(
+        Arc::new(TransactionValidatorSuccess),
+        Arc::new(BatchBuilderSuccess::new(sender)),
+        TransactionQueueOptions {
+            sealing_policy: sealing_policy(build_batch_frequency, 3),
+        },
+    );
+    let err = tx_queue
+        .add_priority_transaction(MockProvenTxBuilder::with_account_index(3).build())
+        .await
+        .unwrap_err();
+    assert_eq!(err, AddTransactionError::PriorityLaneDisabled);
+}
+
+/// Tests that simulated transactions are not added to the queue
+#[tokio::test(start_paused = true)]
+#[miden_node_test_macro::enable_logging]
+async fn test_simulated_txs_are_not_queued() {
+    let build_batch_frequency = Duration::from_millis(5);
+    let batch_size = 3;
+
+    let (sender, mut receiver) = mpsc::unbounded_channel::<TransactionBatch>();
+    let batch_builder = Arc::new(BatchBuilderSuccess::new(sender));
+
+    let tx_queue = Arc::new(TransactionQueue::new(
+        Arc::new(TransactionValidatorSuccess),
+        batch_builder.clone(),
+        TransactionQueueOptions {
+            sealing_policy: sealing_policy(build_batch_frequency, batch_size),
+        },
+    ));
+
+    // Start the queue
+    tokio::spawn(tx_queue.clone().run());
+
+    for i in 0..(3 * batch_size as u32) {
+        let simulation = tx_queue
+            .simulate_transaction(&MockProvenTxBuilder::with_account_index(i).build())
+            .await
+            .unwrap();
+        assert_eq!(simulation.rejection, None);
+    }
+
+    time::sleep(build_batch_frequency * 2).await;
+
+    assert_eq!(
+        Err(TryRecvError::Empty),
+        receiver.try_recv(),
+        "Simulated transactions must not be batched"
+    );
+    assert!(tx_queue.mempool.contents(None, usize::MAX).await.is_empty());
+}
+
 /// Tests that when batch building fails, transactions are added back to the ready queue
 #[tokio::test]
 #[miden_node_test_macro::enable_logging]
@@ -197,7 +513,9 @@ async fn test_build_batch_failure() {
     let tx_queue = TransactionQueue::new(
         Arc::new(TransactionValidatorSuccess),
         batch_builder.clone(),
-        TransactionQueueOptions { build_batch_frequency, batch_size },
+        TransactionQueueOptions {
+            sealing_policy: sealing_policy(build_batch_frequency, batch_size),
+        },
     );
 
     let internal_ready_queue = tx_queue.ready_queue.clone();
@@ -0,0 +1,232 @@
+//! Verification of the submitted transactions against their inputs in the store, split into
+//! verifiers which can be chained, so that operators can layer their own policies, e.g. a denylist
+//! of accounts, on top of the default verification without forking the state view.
+//!
+//! The verifiers run before the transaction is checked against the transactions in the block
+//! production pipeline, which is done by the state view: the account's hash in the store is only
+//! checked there, as it is outdated by the in-flight transactions of the account.
+use std::{collections::BTreeSet, sync::Arc};
+
+use miden_objects::{
+    accounts::AccountId, notes::Nullifier, transaction::InputNotes, MIN_PROOF_SECURITY_LEVEL,
+};
+use miden_tx::TransactionVerifier as ProofChecker;
+
+use crate::ProvenTransaction;
+pub use crate::{errors::VerifyTxError, store::TransactionInputs};
+
+// TRANSACTION VERIFIER
+// ================================================================================================
+
+/// Verifies a candidate transaction against its inputs fetched from the store.
+pub trait TransactionVerifier: Send + Sync + 'static {
+    /// Returns an error if `tx` must be rejected.
+    fn verify(
+        &self,
+        tx: &ProvenTransaction,
+        tx_inputs: &TransactionInputs,
+    ) -> Result<(), VerifyTxError>;
+
+    /// Returns a verifier running this verifier, then `next` if this one accepts the transaction.
+    fn and_then<V>(self, next: V) -> Chain<Self, V>
+    where
+        Self: Sized,
+        V: TransactionVerifier,
+    {
+        Chain { first: self, second: next }
+    }
+}
+
+impl<V: TransactionVerifier + ?Sized> TransactionVerifier for Arc<V> {
+    fn verify(
+        &self,
+        tx: &ProvenTransaction,
+        tx_inputs: &TransactionInputs,
+    ) -> Result<(), VerifyTxError> {
+        (**self).verify(tx, tx_inputs)
+    }
+}
+
+/// Two verifiers run one after the other, built by [TransactionVerifier::and_then].
+#[derive(Debug, Clone)]
+pub struct Chain<A, B> {
+    first: A,
+    second: B,
+}
+
+impl<A, B> TransactionVerifier for Chain<A, B>
+where
+    A: TransactionVerifier,
+    B: TransactionVerifier,
+{
+    fn verify(
+        &self,
+        tx: &ProvenTransaction,
+        tx_inputs: &TransactionInputs,
+    ) -> Result<(), VerifyTxError> {
+        self.first.verify(tx, tx_inputs)?;
+        self.second.verify(tx, tx_inputs)
+    }
+}
+
+// DEFAULT VERIFIERS
+// ================================================================================================
+
+/// Makes sure that the transaction proof is valid and meets the required security level.
+#[derive(Debug, Clone, Copy)]
+pub struct ProofVerifier {
+    security_level: u32,
+}
+
+impl ProofVerifier {
+    pub fn new(security_level: u32) -> Self {
+        Self { security_level }
+    }
+}
+
+impl Default for ProofVerifier {
+    fn default() -> Self {
+        Self::new(MIN_PROOF_SECURITY_LEVEL)
+    }
+}
+
+impl TransactionVerifier for ProofVerifier {
+    fn verify(&self, tx: &ProvenTransaction, _: &TransactionInputs) -> Result<(), VerifyTxError> {
+        ProofChecker::new(self.security_level)
+            .verify(tx.clone())
+            .map_err(|_| VerifyTxError::InvalidTransactionProof(tx.id()))
+    }
+}
+
+/// Makes sure that none of the notes consumed by the transaction were consumed in a block.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StoreInputsVerifier;
+
+impl TransactionVerifier for StoreInputsVerifier {
+    fn verify(
+        &self,
+        _: &ProvenTransaction,
+        tx_inputs: &TransactionInputs,
+    ) -> Result<(), VerifyTxError> {
+        let infracting_nullifiers: Vec<Nullifier> = tx_inputs
+            .nullifiers
+            .iter()
+            .filter(|&(_, &block_num)| block_num != 0)
+            .map(|(&nullifier_in_tx, _)| nullifier_in_tx)
+            .collect();
+
+        if !infracting_nullifiers.is_empty() {
+            return Err(VerifyTxError::InputNotesAlreadyConsumed(InputNotes::new(
+                infracting_nullifiers,
+            )?));
+        }
+
+        Ok(())
+    }
+}
+
+// POLICIES
+// ================================================================================================
+
+/// Rejects the transactions of the denied accounts.
+#[derive(Debug, Clone, Default)]
+pub struct AccountDenylist {
+    accounts: BTreeSet<AccountId>,
+}
+
+impl AccountDenylist {
+    pub fn new(accounts: impl IntoIterator<Item = AccountId>) -> Self {
+        Self { accounts: accounts.into_iter().collect() }
+    }
+}
+
+impl TransactionVerifier for AccountDenylist {
+    fn verify(&self, tx: &ProvenTransaction, _: &TransactionInputs) -> Result<(), VerifyTxError> {
+        if self.accounts.contains(&tx.account_id()) {
+            return Err(VerifyTxError::PolicyViolation(format!(
+                "account {} is denied",
+                tx.account_id()
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+/// Policy implemented by a function, returning the reason of the rejection reported to the client
+/// if the transaction violates the policy, e.g. if it doesn't create a note of an allowed type.
+pub struct Policy<F>(pub F);
+
+impl<F> TransactionVerifier for Policy<F>
+where
+    F: Fn(&ProvenTransaction, &TransactionInputs) -> Result<(), String> + Send + Sync + 'static,
+{
+    fn verify(
+        &self,
+        tx: &ProvenTransaction,
+        tx_inputs: &TransactionInputs,
+    ) -> Result<(), VerifyTxError> {
+        (self.0)(tx, tx_inputs).map_err(VerifyTxError::PolicyViolation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+    use crate::test_utils::{MockPrivateAccount, MockProvenTxBuilder};
+
+    fn tx_inputs(tx: &ProvenTransaction, consumed_in_block: u32) -> TransactionInputs {
+        TransactionInputs {
+            account_id: tx.account_id(),
+            account_hash: None,
+            nullifiers: tx
+                .input_notes()
+                .iter()
+                .map(|nullifier| (*nullifier, consumed_in_block))
+                .collect::<BTreeMap<_, _>>(),
+        }
+    }
+
+    #[test]
+    fn chained_verifiers_run_in_order() {
+        let denied: MockPrivateAccount<2> = MockPrivateAccount::from(0);
+        let allowed: MockPrivateAccount<2> = MockPrivateAccount::from(1);
+        let verifier = StoreInputsVerifier.and_then(AccountDenylist::new([denied.id])).and_then(
+            Policy(|tx: &ProvenTransaction, _: &TransactionInputs| {
+                if tx.input_notes().num_notes() > 1 {
+                    return Err("too many input notes".to_string());
+                }
+                Ok(())
+            }),
+        );
+
+        let tx =
+            MockProvenTxBuilder::with_account(allowed.id, allowed.states[0], allowed.states[1])
+                .nullifiers_range(0..1)
+                .build();
+        assert_eq!(verifier.verify(&tx, &tx_inputs(&tx, 0)), Ok(()));
+
+        // The store inputs are verified before the policies
+        let consumed = verifier.verify(&tx, &tx_inputs(&tx, 3)).unwrap_err();
+        assert!(matches!(consumed, VerifyTxError::InputNotesAlreadyConsumed(_)));
+
+        let tx = MockProvenTxBuilder::with_account(denied.id, denied.states[0], denied.states[1])
+            .nullifiers_range(0..2)
+            .build();
+        assert_eq!(
+            verifier.verify(&tx, &tx_inputs(&tx, 0)),
+            Err(VerifyTxError::PolicyViolation(format!("account {} is denied", denied.id)))
+        );
+
+        let tx =
+            MockProvenTxBuilder::with_account(allowed.id, allowed.states[0], allowed.states[1])
+                .nullifiers_range(0..2)
+                .build();
+        assert_eq!(
+            verifier.verify(&tx, &tx_inputs(&tx, 0)),
+            Err(VerifyTxError::PolicyViolation("too many input notes".to_string()))
+        );
+    }
+}
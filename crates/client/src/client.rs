@@ -0,0 +1,621 @@
+use std::{future::Future, mem};
+
+use miden_node_proto::{
+    convert,
+    domain::{
+        accounts::{AccountInfo, AccountLookup},
+        transactions::{TransactionReceipt, TransactionRejection},
+    },
+    errors::{ConversionError, MissingFieldHelper},
+    generated::{
+        account::AccountInfo as AccountInfoPb,
+        page::PageRequest,
+        requests::{
+            CheckNullifiersByPrefixRequest, CheckNullifiersRequest, GetAccountDetailsRequest,
+            GetAccountsByPubKeyRequest, GetBlockHeaderByNumberRequest, GetChainTipRequest,
+            GetNoteScriptByRootRequest, GetNotesByIdRequest, GetNullifiersByPrefixRequest,
+            GetTransactionReceiptRequest, SubmitProvenTransactionRequest, SyncNotesRequest,
+            SyncStateRequest,
+        },
+        rpc::api_client::ApiClient,
+        transaction::TransactionReceipt as TransactionReceiptPb,
+    },
+    try_convert,
+};
+use miden_objects::{
+    accounts::AccountId,
+    crypto::merkle::SmtProof,
+    notes::{Note, NoteId, NoteScript, Nullifier},
+    transaction::{ProvenTransaction, TransactionId},
+    utils::{Deserializable, Serializable},
+    BlockHeader, Digest,
+};
+use tokio::time;
+use tonic::{codec::CompressionEncoding, transport::Channel, Code, Response, Status};
+use tracing::{debug, warn};
+
+use crate::{
+    domain::{
+        ChainTip, CommittedNote, NoteSyncUpdate, NullifierUpdate, SignedBlockHeader,
+        StateSyncUpdate,
+    },
+    errors::RpcClientError,
+    retry::RetryPolicy,
+    COMPONENT,
+};
+
+// RPC CLIENT
+// ================================================================================================
+
+/// Client for the node's RPC API.
+///
+/// Requests and responses are converted from and to the `miden-objects` domain types, and the
+/// read requests failing with transient errors are retried according to the client's
+/// [RetryPolicy]. The submissions of transactions are never retried.
+#[derive(Clone, Debug)]
+pub struct RpcClient {
+    inner: ApiClient<Channel>,
+    retry_policy: RetryPolicy,
+    sync_page_size: SyncPageSize,
+    include_account_details: bool,
+    compact_merkle_paths: bool,
+    min_confirmations: u32,
+}
+
+/// Maximum size of the updates requested by [RpcClient::sync_state], the node caps both values.
+///
+/// Larger pages reduce the number of requests needed to reach the chain tip.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SyncPageSize {
+    /// Maximum number of blocks with matching notes per update, zero selects a single block.
+    pub max_blocks: u32,
+    /// Maximum number of notes per update, zero selects the node's cap.
+    pub max_notes: u32,
+}
+
+impl RpcClient {
+    /// Connects to the RPC server at `url`, in the format `http://<host>[:<port>]`.
+    pub async fn connect(url: impl Into<String>) -> Result<Self, RpcClientError> {
+        let inner = ApiClient::connect(url.into()).await?;
+
+        Ok(Self::new(inner))
+    }
+
+    /// Returns a new client wrapping an already connected gRPC client.
+    pub fn new(inner: ApiClient<Channel>) -> Self {
+        Self {
+            inner,
+            retry_policy: RetryPolicy::default(),
+            sync_page_size: SyncPageSize::default(),
+            include_account_details: false,
+            compact_merkle_paths: false,
+            min_confirmations: 0,
+        }
+    }
+
+    /// Sets the policy used to retry failed requests.
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Sets the maximum size of the state sync updates.
+    pub fn with_sync_page_size(mut self, sync_page_size: SyncPageSize) -> Self {
+        self.sync_page_size = sync_page_size;
+        self
+    }
+
+    /// Sets whether the state sync updates include the details of the updated public accounts, see
+    /// [StateSyncUpdate::account_details].
+    pub fn with_account_details(mut self, include_account_details: bool) -> Self {
+        self.include_account_details = include_account_details;
+        self
+    }
+
+    /// Sets whether the state sync responses deduplicate the nodes of the notes' Merkle paths,
+    /// which share most of their nodes within a block. The paths are restored by the client, the
+    /// updates are the same either way.
+    pub fn with_compact_merkle_paths(mut self, compact_merkle_paths: bool) -> Self {
+        self.compact_merkle_paths = compact_merkle_paths;
+        self
+    }
+
+    /// Sets whether the client accepts gzip compressed responses, trading CPU time for bandwidth,
+    /// e.g. on mobile networks.
+    pub fn with_compression(mut self, compression: bool) -> Self {
+        if compression {
+            self.inner = self.inner.accept_compressed(CompressionEncoding::Gzip);
+        }
+        self
+    }
+
+    /// Sets the minimum number of confirmations of the notes, nullifiers, accounts and transaction
+    /// receipts returned by the node, the block at the chain tip having one confirmation.
+    ///
+    /// Data from more recent blocks is reported as not found, except the accounts, whose lookup
+    /// fails if they were updated since, zero disables the check.
+    pub fn with_min_confirmations(mut self, min_confirmations: u32) -> Self {
+        self.min_confirmations = min_confirmations;
+        self
+    }
+
+    /// Returns the underlying gRPC client, for requests not covered by this wrapper.
+    pub fn inner(&self) -> &ApiClient<Channel> {
+        &self.inner
+    }
+
+    // REQUESTS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the opening of each nullifier in the nullifier tree, in the same order as the
+    /// request.
+    pub async fn check_nullifiers(
+        &self,
+        nullifiers: &[Nullifier],
+    ) -> Result<Vec<SmtProof>, RpcClientError> {
+        let request = CheckNullifiersRequest {
+            nullifiers: nullifiers.iter().map(|nullifier| nullifier.inner().into()).collect(),
+            block_num: None,
+        };
+
+        let response =
+            self.call(request, |mut client, request| async move {
+                client.check_nullifiers(request).await
+            })
+            .await?;
+
+        Ok(try_convert(response.proofs)?)
+    }
+
+    /// Returns the consumed nullifiers whose `prefix_len` high bits match one of `prefixes`, see
+    /// [crate::domain::nullifier_prefix_of_len].
+    ///
+    /// The node rejects prefix lengths shorter than its configured minimum.
+    pub async fn check_nullifiers_by_prefix(
+        &self,
+        prefix_len: u32,
+        prefixes: &[u32],
+    ) -> Result<Vec<NullifierUpdate>, RpcClientError> {
+        let request = CheckNullifiersByPrefixRequest {
+            prefix_len,
+            nullifiers: prefixes.to_vec(),
+            min_confirmations: self.min_confirmations,
+        };
+
+        let response = self
+            .call(request, |mut client, request| async move {
+                client.check_nullifiers_by_prefix(request).await
+            })
+            .await?;
+
+        Ok(try_convert(response.nullifiers)?)
+    }
+
+    /// Returns the nullifiers consumed after `from_block` whose `prefix_len` high bits match one
+    /// of `prefixes`, following the pages of the node up to the chain tip.
+    ///
+    /// Returns the nullifiers ordered by block, and the last block they cover, from which the next
+    /// call continues.
+    pub async fn get_nullifiers_by_prefix(
+        &self,
+        prefix_len: u32,
+        prefixes: &[u32],
+        from_block: u32,
+    ) -> Result<(Vec<NullifierUpdate>, u32), RpcClientError> {
+        let mut request = GetNullifiersByPrefixRequest {
+            prefix_len,
+            nullifiers: prefixes.to_vec(),
+            from_block,
+            page: None,
+        };
+
+        let mut nullifiers = Vec::new();
+        loop {
+            let response = self
+                .call(request.clone(), |mut client, request| async move {
+                    client.get_nullifiers_by_prefix(request).await
+                })
+                .await?;
+            let page: Vec<NullifierUpdate> = try_convert(response.nullifiers)?;
+            nullifiers.extend(page);
+
+            debug!(target: COMPONENT, from_block, block_num = response.block_num);
+
+            let Some(next) = response.page.and_then(|page| page.next) else {
+                return Ok((nullifiers, response.block_num));
+            };
+            request.page = Some(PageRequest { token: Some(next), size: 0 });
+        }
+    }
+
+    /// Returns the number and hash of the latest block.
+    ///
+    /// Cheaper than requesting the latest block header when only the chain height is needed.
+    pub async fn get_chain_tip(&self) -> Result<ChainTip, RpcClientError> {
+        let response = self
+            .call(GetChainTipRequest {}, |mut client, request| async move {
+                client.get_chain_tip(request).await
+            })
+            .await?;
+
+        Ok(response.try_into()?)
+    }
+
+    /// Returns the header of the block `block_num`, or of the latest block if `None`.
+    pub async fn get_block_header_by_number(
+        &self,
+        block_num: Option<u32>,
+    ) -> Result<BlockHeader, RpcClientError> {
+        Ok(self.get_signed_block_header(block_num).await?.header)
+    }
+
+    /// Returns the header of the block `block_num`, or of the latest block if `None`, with the
+    /// sequencer's signature so the block can be authenticated with [SignedBlockHeader::verify].
+    pub async fn get_signed_block_header(
+        &self,
+        block_num: Option<u32>,
+    ) -> Result<SignedBlockHeader, RpcClientError> {
+        let request = GetBlockHeaderByNumberRequest { block_num };
+
+        let response = self
+            .call(request, |mut client, request| async move {
+                client.get_block_header_by_number(request).await
+            })
+            .await?;
+
+        if response.block_header.is_none() {
+            return Err(RpcClientError::RequestFailed(Status::not_found("Block not found")));
+        }
+
+        Ok(response.try_into()?)
+    }
+
+    /// Returns the committed notes with the given IDs, notes unknown to the node are omitted.
+    pub async fn get_notes_by_id(
+        &self,
+        note_ids: &[NoteId],
+    ) -> Result<Vec<CommittedNote>, RpcClientError> {
+        let request = GetNotesByIdRequest {
+            note_ids: convert(note_ids),
+            min_confirmations: self.min_confirmations,
+        };
+
+        let response =
+            self.call(request, |mut client, request| async move {
+                client.get_notes_by_id(request).await
+            })
+            .await?;
+
+        try_convert(response.notes)
+    }
+
+    /// Returns the latest state of the account, with its details if the account is public.
+    pub async fn get_account_details(
+        &self,
+        account_id: AccountId,
+    ) -> Result<AccountLookup, RpcClientError> {
+        let request = GetAccountDetailsRequest {
+            account_id: Some(account_id.into()),
+            min_confirmations: self.min_confirmations,
+        };
+
+        let result = self
+            .call(request, |mut client, request| async move {
+                client.get_account_details(request).await
+            })
+            .await;
+
+        let response = match result {
+            Ok(response) => response,
+            Err(RpcClientError::RequestFailed(status)) if status.code() == Code::NotFound => {
+                return Ok(AccountLookup::NotFound);
+            },
+            Err(err) => return Err(err),
+        };
+
+        let account = response.account.ok_or(AccountInfoPb::missing_field(stringify!(account)))?;
+        let account: AccountInfo = account.try_into()?;
+
+        Ok(account.into())
+    }
+
+    /// Returns the receipt of the transaction, or [None] if it hasn't been included in a block.
+    pub async fn get_transaction_receipt(
+        &self,
+        transaction_id: TransactionId,
+    ) -> Result<Option<TransactionReceipt>, RpcClientError> {
+        let request = GetTransactionReceiptRequest {
+            transaction_id: Some(transaction_id.into()),
+            min_confirmations: self.min_confirmations,
+        };
+
+        let result = self
+            .call(request, |mut client, request| async move {
+                client.get_transaction_receipt(request).await
+            })
+            .await;
+
+        let response = match result {
+            Ok(response) => response,
+            Err(RpcClientError::RequestFailed(status)) if status.code() == Code::NotFound => {
+                return Ok(None);
+            },
+            Err(err) => return Err(err),
+        };
+
+        let receipt = response
+            .receipt
+            .ok_or(TransactionReceiptPb::missing_field(stringify!(receipt)))?;
+
+        Ok(Some(receipt.try_into()?))
+    }
+
+    /// Returns the note script with the given root, or [None] if no public note known to the node
+    /// uses this script.
+    pub async fn get_note_script_by_root(
+        &self,
+        script_root: Digest,
+    ) -> Result<Option<NoteScript>, RpcClientError> {
+        let request = GetNoteScriptByRootRequest { script_root: Some(script_root.into()) };
+
+        let result = self
+            .call(request, |mut client, request| async move {
+                client.get_note_script_by_root(request).await
+            })
+            .await;
+
+        let response = match result {
+            Ok(response) => response,
+            Err(RpcClientError::RequestFailed(status)) if status.code() == Code::NotFound => {
+                return Ok(None);
+            },
+            Err(err) => return Err(err),
+        };
+
+        let script =
+            NoteScript::read_from_bytes(&response.script).map_err(ConversionError::from)?;
+
+        Ok(Some(script))
+    }
+
+    /// Returns the ids of the public accounts authenticated by `public_key`.
+    ///
+    /// Fails with `UNIMPLEMENTED` if the node doesn't maintain the account public key index.
+    pub async fn get_accounts_by_public_key(
+        &self,
+        public_key: Digest,
+    ) -> Result<Vec<AccountId>, RpcClientError> {
+        let request = GetAccountsByPubKeyRequest { public_key: Some(public_key.into()) };
+
+        let response = self
+            .call(request, |mut client, request| async move {
+                client.get_accounts_by_pub_key(request).await
+            })
+            .await?;
+
+        Ok(try_convert(response.account_ids)?)
+    }
+
+    /// Submits a proven transaction to the node.
+    ///
+    /// Returns [RpcClientError::TransactionRejected] when the node reports why the transaction was
+    /// rejected.
+    ///
+    /// The submission is not retried, since the node may have accepted the transaction even if
+    /// the request failed, e.g. with `DEADLINE_EXCEEDED`. Use
+    /// [RpcClient::get_transaction_receipt] to find out whether it was accepted before submitting
+    /// it again.
+    pub async fn submit_proven_transaction(
+        &self,
+        transaction: &ProvenTransaction,
+    ) -> Result<(), RpcClientError> {
+        self.submit(transaction, Vec::new()).await
+    }
+
+    /// Submits a proven transaction to a node charging fees, `fee_note` is the note created by the
+    /// transaction paying its fee to the operator.
+    ///
+    /// Returns [RpcClientError::TransactionRejected] when the node reports why the transaction was
+    /// rejected. The submission is not retried, see [RpcClient::submit_proven_transaction].
+    pub async fn submit_proven_transaction_with_fee(
+        &self,
+        transaction: &ProvenTransaction,
+        fee_note: &Note,
+    ) -> Result<(), RpcClientError> {
+        self.submit(transaction, fee_note.to_bytes()).await
+    }
+
+    async fn submit(
+        &self,
+        transaction: &ProvenTransaction,
+        fee_note: Vec<u8>,
+    ) -> Result<(), RpcClientError> {
+        let request = SubmitProvenTransactionRequest {
+            transaction: transaction.to_bytes(),
+            fee_note,
+        };
+
+        // Not retried, the transaction may have been accepted even if the request failed
+        match self.inner.clone().submit_proven_transaction(request).await {
+            Ok(_) => Ok(()),
+            Err(status) => match TransactionRejection::from_status(&status)? {
+                Some(rejection) => Err(RpcClientError::TransactionRejected(rejection)),
+                None => Err(status.into()),
+            },
+        }
+    }
+
+    /// Returns the state updates from `block_num + 1` up to the first block containing a note
+    /// matching `note_tags`, or the chain tip.
+    ///
+    /// If the client's [SyncPageSize] allows several blocks, the update covers the next blocks
+    /// containing a matching note, the blocks before the last one are returned in
+    /// [StateSyncUpdate::preceding_blocks].
+    ///
+    /// `note_tags` and `nullifiers` contain only the 16 high bits of the values the client is
+    /// interested in, see [crate::domain::nullifier_prefix].
+    pub async fn sync_state(
+        &self,
+        block_num: u32,
+        account_ids: &[AccountId],
+        note_tags: &[u32],
+        nullifiers: &[u32],
+    ) -> Result<StateSyncUpdate, RpcClientError> {
+        let mut request = SyncStateRequest {
+            block_num,
+            account_ids: convert(account_ids),
+            note_tags: note_tags.to_vec(),
+            nullifiers: nullifiers.to_vec(),
+            nullifier_prefix_len: 0,
+            max_blocks: self.sync_page_size.max_blocks,
+            max_notes: self.sync_page_size.max_notes,
+            include_account_details: self.include_account_details,
+            continuation: Vec::new(),
+            compact_merkle_paths: self.compact_merkle_paths,
+        };
+
+        // The node truncates the filters exceeding its caps, the rest is synced by continuing the
+        // request over the same blocks
+        let mut update: Option<StateSyncUpdate> = None;
+        loop {
+            let mut response = self
+                .call(request.clone(), |mut client, request| async move {
+                    client.sync_state(request).await
+                })
+                .await?;
+            let continuation = mem::take(&mut response.continuation);
+
+            let part = StateSyncUpdate::try_from(response)?;
+            match update.as_mut() {
+                Some(update) => update.extend(part),
+                None => update = Some(part),
+            }
+
+            if continuation.is_empty() {
+                return Ok(update.expect("the first response was received"));
+            }
+            debug!(target: COMPONENT, block_num, "Continuing the truncated state sync");
+            request.continuation = continuation;
+        }
+    }
+
+    /// Returns the notes of the first block after `block_num` containing a note matching
+    /// `note_tags`, or the chain tip.
+    ///
+    /// Unlike [RpcClient::sync_state], the update contains no account or nullifier updates, and the
+    /// block is authenticated by a path in the chain MMR instead of an MMR delta.
+    pub async fn sync_notes(
+        &self,
+        block_num: u32,
+        note_tags: &[u32],
+    ) -> Result<NoteSyncUpdate, RpcClientError> {
+        let request = SyncNotesRequest {
+            block_num,
+            note_tags: note_tags.to_vec(),
+            ..Default::default()
+        };
+
+        self.sync_notes_request(request).await
+    }
+
+    /// Returns the notes of the first block after `block_num` containing a note whose tag has one
+    /// of the `use_cases`, or which is executed by the network if `network_notes` is set, or the
+    /// chain tip.
+    ///
+    /// The use case of a tag is given by [crate::domain::note_tag_use_case]. The network notes are
+    /// discovered this way without knowing their tags.
+    pub async fn sync_notes_by_use_case(
+        &self,
+        block_num: u32,
+        use_cases: &[u32],
+        network_notes: bool,
+    ) -> Result<NoteSyncUpdate, RpcClientError> {
+        let request = SyncNotesRequest {
+            block_num,
+            note_use_cases: use_cases.to_vec(),
+            network_notes,
+            ..Default::default()
+        };
+
+        self.sync_notes_request(request).await
+    }
+
+    async fn sync_notes_request(
+        &self,
+        request: SyncNotesRequest,
+    ) -> Result<NoteSyncUpdate, RpcClientError> {
+        let response = self
+            .call(request, |mut client, request| async move { client.sync_notes(request).await })
+            .await?;
+
+        response.try_into()
+    }
+
+    /// Repeatedly calls [RpcClient::sync_state] until the chain tip is reached.
+    ///
+    /// Returns the updates in order, each update must be applied after the previous one since its
+    /// MMR delta starts at the previous update's block.
+    pub async fn sync_state_to_tip(
+        &self,
+        mut block_num: u32,
+        account_ids: &[AccountId],
+        note_tags: &[u32],
+        nullifiers: &[u32],
+    ) -> Result<Vec<StateSyncUpdate>, RpcClientError> {
+        let mut updates = Vec::new();
+
+        loop {
+            let update = self.sync_state(block_num, account_ids, note_tags, nullifiers).await?;
+            let received = update.block_header.block_num();
+            let is_at_chain_tip = update.is_at_chain_tip();
+
+            debug!(target: COMPONENT, block_num, received, chain_tip = update.chain_tip);
+
+            if received <= block_num && !is_at_chain_tip {
+                return Err(RpcClientError::SyncStalled { requested: block_num, received });
+            }
+
+            updates.push(update);
+            block_num = received;
+
+            if is_at_chain_tip {
+                return Ok(updates);
+            }
+        }
+    }
+
+    // HELPERS
+    // --------------------------------------------------------------------------------------------
+
+    /// Sends the request using `send`, retrying transient failures according to the retry policy.
+    async fn call<Req, Res, F, Fut>(&self, request: Req, send: F) -> Result<Res, RpcClientError>
+    where
+        Req: Clone,
+        F: Fn(ApiClient<Channel>, Req) -> Fut,
+        Fut: Future<Output = Result<Response<Res>, Status>>,
+    {
+        let mut attempt = 0;
+        loop {
+            match send(self.inner.clone(), request.clone()).await {
+                Ok(response) => return Ok(response.into_inner()),
+                Err(status)
+                    if attempt < self.retry_policy.max_retries
+                        && RetryPolicy::is_retryable(&status) =>
+                {
+                    let backoff = self.retry_policy.backoff(attempt);
+                    warn!(
+                        target: COMPONENT,
+                        %status,
+                        attempt,
+                        backoff_ms = backoff.as_millis(),
+                        "Request failed, retrying"
+                    );
+
+                    time::sleep(backoff).await;
+                    attempt += 1;
+                },
+                Err(status) => return Err(status.into()),
+            }
+        }
+    }
+}
@@ -0,0 +1,380 @@
+pub use miden_node_proto::domain::notes::{is_network_note_tag, note_tag_use_case};
+pub use miden_node_proto::domain::nullifiers::{nullifier_prefix, nullifier_prefix_of_len};
+use miden_node_proto::{
+    domain::{
+        accounts::{AccountInfo, AccountSummary},
+        merkle::expand_merkle_path,
+    },
+    errors::{ConversionError, MissingFieldHelper},
+    generated::{
+        digest::Digest as DigestPb,
+        note::{Note as NotePb, NoteSyncRecord as NoteSyncRecordPb},
+        responses::{
+            GetBlockHeaderByNumberResponse, GetChainTipResponse,
+            NullifierUpdate as NullifierUpdatePb, SyncNotesResponse, SyncStateResponse,
+            SyncedBlock as SyncedBlockPb,
+        },
+    },
+    try_convert,
+};
+use miden_objects::{
+    accounts::AccountId,
+    crypto::{
+        dsa::rpo_falcon512::{PublicKey, Signature},
+        merkle::{MerklePath, MmrDelta},
+    },
+    notes::{Note, NoteId, NoteMetadata, NoteType, Nullifier},
+    utils::Deserializable,
+    BlockHeader, Digest, Felt,
+};
+
+use crate::errors::RpcClientError;
+
+// NOTES
+// ================================================================================================
+
+/// A note committed to the chain, as returned by `GetNotesById`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommittedNote {
+    pub block_num: u32,
+    pub note_index: u32,
+    pub note_id: NoteId,
+    pub metadata: NoteMetadata,
+    pub merkle_path: MerklePath,
+    /// Present only for public (on-chain) notes.
+    pub details: Option<Note>,
+}
+
+impl TryFrom<NotePb> for CommittedNote {
+    type Error = RpcClientError;
+
+    fn try_from(value: NotePb) -> Result<Self, Self::Error> {
+        let note_id: Digest =
+            value.note_id.ok_or(NotePb::missing_field(stringify!(note_id)))?.try_into()?;
+        let sender = value.sender.ok_or(NotePb::missing_field(stringify!(sender)))?.try_into()?;
+
+        Ok(Self {
+            block_num: value.block_num,
+            note_index: value.note_index,
+            note_id: note_id.into(),
+            metadata: note_metadata(sender, value.note_type, value.tag, value.aux)?,
+            merkle_path: value
+                .merkle_path
+                .ok_or(NotePb::missing_field(stringify!(merkle_path)))?
+                .try_into()?,
+            details: value
+                .details
+                .map(|details| Note::read_from_bytes(&details))
+                .transpose()
+                .map_err(ConversionError::from)?,
+        })
+    }
+}
+
+/// A note returned by `SyncState`, the note is included in the block of the sync response.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NoteSyncRecord {
+    pub note_index: u32,
+    pub note_id: NoteId,
+    pub metadata: NoteMetadata,
+    pub merkle_path: MerklePath,
+}
+
+impl TryFrom<NoteSyncRecordPb> for NoteSyncRecord {
+    type Error = RpcClientError;
+
+    fn try_from(value: NoteSyncRecordPb) -> Result<Self, Self::Error> {
+        let note_id: Digest = value
+            .note_id
+            .ok_or(NoteSyncRecordPb::missing_field(stringify!(note_id)))?
+            .try_into()?;
+        let sender = value
+            .sender
+            .ok_or(NoteSyncRecordPb::missing_field(stringify!(sender)))?
+            .try_into()?;
+
+        Ok(Self {
+            note_index: value.note_index,
+            note_id: note_id.into(),
+            metadata: note_metadata(sender, value.note_type, value.tag, value.aux)?,
+            merkle_path: value
+                .merkle_path
+                .ok_or(NoteSyncRecordPb::missing_field(stringify!(merkle_path)))?
+                .try_into()?,
+        })
+    }
+}
+
+/// Builds the note's metadata from the fields of the RPC messages.
+fn note_metadata(
+    sender: AccountId,
+    note_type: u32,
+    tag: u32,
+    aux: u64,
+) -> Result<NoteMetadata, RpcClientError> {
+    let note_type = NoteType::try_from(note_type).map_err(RpcClientError::InvalidNoteMetadata)?;
+    let aux = Felt::try_from(aux).map_err(|_| ConversionError::NotAValidFelt)?;
+    NoteMetadata::new(sender, note_type, tag.into(), aux)
+        .map_err(RpcClientError::InvalidNoteMetadata)
+}
+
+// BLOCKS
+// ================================================================================================
+
+/// A block header with the sequencer's signature, as returned by `GetBlockHeaderByNumber`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignedBlockHeader {
+    pub header: BlockHeader,
+    /// [None] for blocks not signed by the sequencer, e.g. the genesis block.
+    pub signature: Option<Signature>,
+}
+
+impl SignedBlockHeader {
+    /// Returns true if the header is signed by the owner of `public_key`.
+    pub fn verify(&self, public_key: PublicKey) -> bool {
+        self.signature
+            .as_ref()
+            .is_some_and(|signature| public_key.verify(self.header.hash().into(), signature))
+    }
+}
+
+impl TryFrom<GetBlockHeaderByNumberResponse> for SignedBlockHeader {
+    type Error = ConversionError;
+
+    fn try_from(value: GetBlockHeaderByNumberResponse) -> Result<Self, Self::Error> {
+        let signature = if value.signature.is_empty() {
+            None
+        } else {
+            Some(Signature::read_from_bytes(&value.signature)?)
+        };
+
+        Ok(Self {
+            header: value
+                .block_header
+                .ok_or(GetBlockHeaderByNumberResponse::missing_field(stringify!(block_header)))?
+                .try_into()?,
+            signature,
+        })
+    }
+}
+
+// CHAIN TIP
+// ================================================================================================
+
+/// Number and hash of the latest block in the chain, as returned by `GetChainTip`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChainTip {
+    pub block_num: u32,
+    pub block_hash: Digest,
+}
+
+impl TryFrom<GetChainTipResponse> for ChainTip {
+    type Error = ConversionError;
+
+    fn try_from(value: GetChainTipResponse) -> Result<Self, Self::Error> {
+        Ok(Self {
+            block_num: value.block_num,
+            block_hash: value
+                .block_hash
+                .ok_or(GetChainTipResponse::missing_field(stringify!(block_hash)))?
+                .try_into()?,
+        })
+    }
+}
+
+// NULLIFIERS
+// ================================================================================================
+
+/// A nullifier consumed at `block_num`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NullifierUpdate {
+    pub nullifier: Nullifier,
+    pub block_num: u32,
+    /// Timestamp of the block `block_num`, i.e. when the note was spent.
+    pub block_timestamp: u64,
+}
+
+impl TryFrom<NullifierUpdatePb> for NullifierUpdate {
+    type Error = ConversionError;
+
+    fn try_from(value: NullifierUpdatePb) -> Result<Self, Self::Error> {
+        Ok(Self {
+            nullifier: value
+                .nullifier
+                .ok_or(NullifierUpdatePb::missing_field(stringify!(nullifier)))?
+                .try_into()?,
+            block_num: value.block_num,
+            block_timestamp: value.block_timestamp,
+        })
+    }
+}
+
+// STATE SYNC
+// ================================================================================================
+
+/// The state updates between the request's block and `block_header`.
+#[derive(Debug)]
+pub struct StateSyncUpdate {
+    /// Number of the latest block in the chain.
+    pub chain_tip: u32,
+    /// Header of the first block with a note matching the request, or of the chain tip.
+    pub block_header: BlockHeader,
+    /// Data needed to update the partial MMR up to `block_header`.
+    pub mmr_delta: MmrDelta,
+    /// Latest hashes of the requested accounts updated in the block range.
+    pub accounts: Vec<AccountSummary>,
+    /// Matching notes included in `block_header`.
+    pub notes: Vec<NoteSyncRecord>,
+    /// Matching nullifiers consumed in the block range.
+    pub nullifiers: Vec<NullifierUpdate>,
+    /// Blocks with matching notes before `block_header`, when the request allowed several blocks.
+    pub preceding_blocks: Vec<SyncedBlock>,
+    /// Latest details of the public accounts of `accounts`, when the client requested them.
+    pub account_details: Vec<AccountInfo>,
+}
+
+impl StateSyncUpdate {
+    /// Returns true if this update reaches the chain tip.
+    pub fn is_at_chain_tip(&self) -> bool {
+        self.block_header.block_num() >= self.chain_tip
+    }
+
+    /// Adds the `continuation` of this truncated update, which covers the same blocks for the
+    /// values of the filters left out of this update.
+    pub(crate) fn extend(&mut self, continuation: StateSyncUpdate) {
+        self.chain_tip = self.chain_tip.max(continuation.chain_tip);
+        self.accounts.extend(continuation.accounts);
+        self.account_details.extend(continuation.account_details);
+        self.nullifiers.extend(continuation.nullifiers);
+        self.nullifiers.sort_by_key(|nullifier| nullifier.block_num);
+
+        // A note matching both a tag and a sender of different parts of the filters is repeated
+        extend_notes(&mut self.notes, continuation.notes);
+        for block in continuation.preceding_blocks {
+            let block_num = block.block_header.block_num();
+            match self
+                .preceding_blocks
+                .iter_mut()
+                .find(|known| known.block_header.block_num() == block_num)
+            {
+                Some(known) => extend_notes(&mut known.notes, block.notes),
+                None => self.preceding_blocks.push(block),
+            }
+        }
+        self.preceding_blocks.sort_by_key(|block| block.block_header.block_num());
+    }
+}
+
+/// Adds the `new` notes of a block to its known `notes`, skipping the known ones.
+fn extend_notes(notes: &mut Vec<NoteSyncRecord>, new: Vec<NoteSyncRecord>) {
+    notes.extend(new);
+    notes.sort_by_key(|note| note.note_index);
+    notes.dedup_by_key(|note| note.note_index);
+}
+
+impl TryFrom<SyncStateResponse> for StateSyncUpdate {
+    type Error = RpcClientError;
+
+    fn try_from(value: SyncStateResponse) -> Result<Self, Self::Error> {
+        Ok(Self {
+            chain_tip: value.chain_tip,
+            block_header: value
+                .block_header
+                .ok_or(SyncStateResponse::missing_field(stringify!(block_header)))?
+                .try_into()?,
+            mmr_delta: value
+                .mmr_delta
+                .ok_or(SyncStateResponse::missing_field(stringify!(mmr_delta)))?
+                .try_into()?,
+            accounts: try_convert(value.accounts)?,
+            notes: try_convert(expand_merkle_paths(value.notes, value.merkle_path_nodes)?)?,
+            nullifiers: try_convert(value.nullifiers)?,
+            preceding_blocks: try_convert(value.preceding_blocks)?,
+            account_details: try_convert(value.account_details)?,
+        })
+    }
+}
+
+/// A block with matching notes of a multi-block state sync update, before the update's last block.
+#[derive(Debug)]
+pub struct SyncedBlock {
+    pub block_header: BlockHeader,
+    /// Path authenticating `block_header` in the chain MMR updated by the update's MMR delta.
+    pub mmr_path: MerklePath,
+    /// Matching notes included in `block_header`.
+    pub notes: Vec<NoteSyncRecord>,
+}
+
+impl TryFrom<SyncedBlockPb> for SyncedBlock {
+    type Error = RpcClientError;
+
+    fn try_from(value: SyncedBlockPb) -> Result<Self, Self::Error> {
+        Ok(Self {
+            block_header: value
+                .block_header
+                .ok_or(SyncedBlockPb::missing_field(stringify!(block_header)))?
+                .try_into()?,
+            mmr_path: value
+                .mmr_path
+                .ok_or(SyncedBlockPb::missing_field(stringify!(mmr_path)))?
+                .try_into()?,
+            notes: try_convert(expand_merkle_paths(value.notes, value.merkle_path_nodes)?)?,
+        })
+    }
+}
+
+/// Restores the Merkle paths of the `notes` of a block sent as indices into the distinct
+/// `merkle_path_nodes` of the block, see [crate::RpcClient::with_compact_merkle_paths].
+fn expand_merkle_paths(
+    mut notes: Vec<NoteSyncRecordPb>,
+    merkle_path_nodes: Vec<DigestPb>,
+) -> Result<Vec<NoteSyncRecordPb>, ConversionError> {
+    let nodes: Vec<Digest> = try_convert(merkle_path_nodes)?;
+    for note in notes
+        .iter_mut()
+        .filter(|note| note.merkle_path.is_none() && !note.merkle_path_nodes.is_empty())
+    {
+        note.merkle_path = Some(expand_merkle_path(&note.merkle_path_nodes, &nodes)?.into());
+    }
+
+    Ok(notes)
+}
+
+/// The notes of the first block after the request's block with a note matching the request.
+#[derive(Debug)]
+pub struct NoteSyncUpdate {
+    /// Number of the latest block in the chain.
+    pub chain_tip: u32,
+    /// Header of the first block with a note matching the request, or of the chain tip.
+    pub block_header: BlockHeader,
+    /// Path authenticating `block_header` in the chain MMR with `chain_tip + 1` leaves.
+    pub mmr_path: MerklePath,
+    /// Matching notes included in `block_header`.
+    pub notes: Vec<NoteSyncRecord>,
+}
+
+impl NoteSyncUpdate {
+    /// Returns true if this update reaches the chain tip.
+    pub fn is_at_chain_tip(&self) -> bool {
+        self.block_header.block_num() >= self.chain_tip
+    }
+}
+
+impl TryFrom<SyncNotesResponse> for NoteSyncUpdate {
+    type Error = RpcClientError;
+
+    fn try_from(value: SyncNotesResponse) -> Result<Self, Self::Error> {
+        Ok(Self {
+            chain_tip: value.chain_tip,
+            block_header: value
+                .block_header
+                .ok_or(SyncNotesResponse::missing_field(stringify!(block_header)))?
+                .try_into()?,
+            mmr_path: value
+                .mmr_path
+                .ok_or(SyncNotesResponse::missing_field(stringify!(mmr_path)))?
+                .try_into()?,
+            notes: try_convert(value.notes)?,
+        })
+    }
+}
@@ -0,0 +1,25 @@
+use miden_node_proto::{domain::transactions::TransactionRejection, errors::ConversionError};
+use miden_objects::NoteError;
+use thiserror::Error;
+use tonic::{transport::Error as TransportError, Status};
+
+#[derive(Debug, Error)]
+pub enum RpcClientError {
+    #[error("Connection to the node has failed: {0}")]
+    ConnectionFailed(#[from] TransportError),
+
+    #[error("Request to the node has failed: {0}")]
+    RequestFailed(#[from] Status),
+
+    #[error("Transaction was rejected: {0}")]
+    TransactionRejected(TransactionRejection),
+
+    #[error("Failed to convert the node's response: {0}")]
+    ConversionFailed(#[from] ConversionError),
+
+    #[error("Invalid note metadata: {0}")]
+    InvalidNoteMetadata(NoteError),
+
+    #[error("State sync made no progress, requested block {requested}, received block {received}")]
+    SyncStalled { requested: u32, received: u32 },
+}
@@ -0,0 +1,19 @@
+pub mod client;
+pub mod domain;
+pub mod errors;
+pub mod retry;
+
+#[cfg(test)]
+mod tests;
+
+// RE-EXPORTS
+// ================================================================================================
+
+pub use client::{RpcClient, SyncPageSize};
+pub use errors::RpcClientError;
+pub use retry::RetryPolicy;
+
+// CONSTANTS
+// =================================================================================================
+
+pub const COMPONENT: &str = "miden-node-client";
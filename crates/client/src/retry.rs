@@ -0,0 +1,89 @@
+use std::time::Duration;
+
+use tonic::{Code, Status};
+
+// RETRY POLICY
+// ================================================================================================
+
+/// Determines how failed requests are retried.
+///
+/// Only transient failures are retried, see [RetryPolicy::is_retryable]. The delay between
+/// attempts starts at `initial_backoff` and doubles after every attempt, up to `max_backoff`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Maximum number of retries after the first attempt, zero disables retries.
+    pub max_retries: u32,
+    /// Delay before the first retry.
+    pub initial_backoff: Duration,
+    /// Upper bound of the delay between retries.
+    pub max_backoff: Duration,
+}
+
+impl RetryPolicy {
+    /// Returns a policy which never retries.
+    pub fn no_retries() -> Self {
+        Self { max_retries: 0, ..Default::default() }
+    }
+
+    /// Returns the delay before the retry number `attempt`, counting from zero.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        self.initial_backoff
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(self.max_backoff)
+    }
+
+    /// Returns true if the request failed due to a transient condition, and may succeed if sent
+    /// again.
+    pub fn is_retryable(status: &Status) -> bool {
+        matches!(
+            status.code(),
+            Code::Unavailable | Code::DeadlineExceeded | Code::ResourceExhausted | Code::Aborted
+        )
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(2),
+        }
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use tonic::Status;
+
+    use super::RetryPolicy;
+
+    #[test]
+    fn backoff_doubles_up_to_the_maximum() {
+        let policy = RetryPolicy {
+            max_retries: 10,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_millis(500),
+        };
+
+        assert_eq!(policy.backoff(0), Duration::from_millis(100));
+        assert_eq!(policy.backoff(1), Duration::from_millis(200));
+        assert_eq!(policy.backoff(2), Duration::from_millis(400));
+        assert_eq!(policy.backoff(3), Duration::from_millis(500));
+        assert_eq!(policy.backoff(u32::MAX), Duration::from_millis(500));
+    }
+
+    #[test]
+    fn only_transient_errors_are_retried() {
+        assert!(RetryPolicy::is_retryable(&Status::unavailable("")));
+        assert!(RetryPolicy::is_retryable(&Status::deadline_exceeded("")));
+        assert!(!RetryPolicy::is_retryable(&Status::invalid_argument("")));
+        assert!(!RetryPolicy::is_retryable(&Status::not_found("")));
+        assert!(!RetryPolicy::is_retryable(&Status::internal("")));
+    }
+}
@@ -0,0 +1,605 @@
+use std::{
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc, OnceLock,
+    },
+    time::Duration,
+};
+
+use miden_air::HashFunction;
+use miden_node_proto::{
+    domain::merkle::MerklePathNodes,
+    generated::{
+        mmr::MmrDelta,
+        note::NoteSyncRecord,
+        page::{Page, PageToken},
+        requests::{
+            CheckNullifiersByPrefixRequest, CheckNullifiersRequest, GetAccountDeltasRequest,
+            GetAccountDetailsRequest, GetAccountsByPubKeyRequest, GetBlockHeaderByNumberRequest,
+            GetBlockInclusionProofRequest, GetBlockProofRequest, GetChainTipRequest,
+            GetMempoolStatsRequest, GetNodeStatusRequest, GetNoteAuthenticationInfoRequest,
+            GetNoteConsumptionRequest, GetNoteScriptByRootRequest, GetNotesByIdRequest,
+            GetNullifiersByPrefixRequest, GetSerializationVectorsRequest,
+            GetTransactionReceiptRequest, GetVersionRequest, RequestTestnetFundsRequest,
+            SimulateTransactionRequest, SubmitProvenTransactionRequest, SubscribeAccountsRequest,
+            SubscribeBlocksRequest, SyncNotesRequest, SyncStateRequest,
+        },
+        responses::{
+            CheckNullifiersByPrefixResponse, CheckNullifiersResponse, GetAccountDeltasResponse,
+            GetAccountDetailsResponse, GetAccountsByPubKeyResponse, GetBlockHeaderByNumberResponse,
+            GetBlockInclusionProofResponse, GetBlockProofResponse, GetChainTipResponse,
+            GetMempoolStatsResponse, GetNodeStatusResponse, GetNoteAuthenticationInfoResponse,
+            GetNoteConsumptionResponse, GetNoteScriptByRootResponse, GetNotesByIdResponse,
+            GetNullifiersByPrefixResponse, GetSerializationVectorsResponse,
+            GetTransactionReceiptResponse, GetVersionResponse, NullifierUpdate,
+            RequestTestnetFundsResponse, SimulateTransactionResponse,
+            SubmitProvenTransactionResponse, SubscribeAccountsResponse, SubscribeBlocksResponse,
+            SyncNotesResponse, SyncStateResponse, SyncedBlock,
+        },
+        rpc::{
+            api_client::ApiClient,
+            api_server::{Api, ApiServer},
+        },
+    },
+};
+use miden_objects::{
+    accounts::ACCOUNT_ID_OFF_CHAIN_SENDER,
+    crypto::{dsa::rpo_falcon512::SecretKey, merkle::MerklePath},
+    notes::NoteType,
+    transaction::ProvenTransactionBuilder,
+    utils::Serializable,
+    vm::ExecutionProof,
+    BlockHeader, Digest, Felt, ONE, ZERO,
+};
+use tokio::net::TcpListener;
+use tokio_stream::{wrappers::TcpListenerStream, Empty};
+use tonic::{codec::CompressionEncoding, transport::Server, Request, Response, Status};
+use winterfell::StarkProof;
+
+use crate::{domain::ChainTip, RetryPolicy, RpcClient, RpcClientError, SyncPageSize};
+
+// MOCK RPC SERVER
+// ================================================================================================
+
+/// Serves `SyncState` one block at a time up to `chain_tip`, failing the first `failures` requests
+/// of `SyncState` and `SubmitProvenTransaction` with a transient error. The last block of each
+/// response contains [MOCK_NOTES] matching notes.
+struct MockRpc {
+    chain_tip: u32,
+    failures: AtomicU32,
+    requests: Arc<AtomicU32>,
+}
+
+const MOCK_NOTES: u32 = 4;
+
+const MOCK_NULLIFIERS_PAGE: u32 = 2;
+
+/// Returns the Merkle path of the note `note_index` of a mock block, the notes share their last
+/// nodes like in a note tree.
+fn note_merkle_path(note_index: u32) -> MerklePath {
+    let node = |value: u32| Digest::from([Felt::from(value); 4]);
+    MerklePath::new(vec![node(note_index ^ 1), node(10 + ((note_index >> 1) ^ 1)), node(20)])
+}
+
+fn block_header(block_num: u32) -> BlockHeader {
+    let digest = Digest::default();
+    BlockHeader::new(digest, block_num, digest, digest, digest, digest, digest, digest, ZERO, ZERO)
+}
+
+/// Key of the mock sequencer, shared by the tests as it is slow to generate.
+fn signing_key() -> &'static SecretKey {
+    static SIGNING_KEY: OnceLock<SecretKey> = OnceLock::new();
+    SIGNING_KEY.get_or_init(SecretKey::new)
+}
+
+#[tonic::async_trait]
+impl Api for MockRpc {
+    async fn check_nullifiers(
+        &self,
+        _request: Request<CheckNullifiersRequest>,
+    ) -> Result<Response<CheckNullifiersResponse>, Status> {
+        Err(Status::unimplemented(""))
+    }
+
+    async fn check_nullifiers_by_prefix(
+        &self,
+        _request: Request<CheckNullifiersByPrefixRequest>,
+    ) -> Result<Response<CheckNullifiersByPrefixResponse>, Status> {
+        Err(Status::unimplemented(""))
+    }
+
+    /// Returns a nullifier per block after the requested block, [MOCK_NULLIFIERS_PAGE] blocks
+    /// at a time.
+    async fn get_nullifiers_by_prefix(
+        &self,
+        request: Request<GetNullifiersByPrefixRequest>,
+    ) -> Result<Response<GetNullifiersByPrefixResponse>, Status> {
+        self.requests.fetch_add(1, Ordering::SeqCst);
+        let request = request.into_inner();
+
+        let block_start = match request.page.and_then(|page| page.token) {
+            Some(token) => u32::from_le_bytes(token.token.try_into().unwrap()),
+            None => request.from_block,
+        };
+        let block_num = self.chain_tip.min(block_start + MOCK_NULLIFIERS_PAGE);
+        let nullifiers = (block_start + 1..=block_num)
+            .map(|block_num| NullifierUpdate {
+                nullifier: Some(Digest::default().into()),
+                block_num,
+                block_timestamp: 0,
+            })
+            .collect();
+        let next = (block_num < self.chain_tip)
+            .then(|| PageToken { token: block_num.to_le_bytes().to_vec() });
+        let page = Some(Page { next, total_estimate: None });
+
+        Ok(Response::new(GetNullifiersByPrefixResponse { nullifiers, block_num, page }))
+    }
+
+    async fn get_chain_tip(
+        &self,
+        _request: Request<GetChainTipRequest>,
+    ) -> Result<Response<GetChainTipResponse>, Status> {
+        Ok(Response::new(GetChainTipResponse {
+            block_num: self.chain_tip,
+            block_hash: Some(block_header(self.chain_tip).hash().into()),
+        }))
+    }
+
+    async fn get_block_header_by_number(
+        &self,
+        request: Request<GetBlockHeaderByNumberRequest>,
+    ) -> Result<Response<GetBlockHeaderByNumberResponse>, Status> {
+        let block_num = request.into_inner().block_num.unwrap_or(self.chain_tip);
+        let header = block_header(block_num);
+        // The genesis block is not signed
+        let signature = if block_num == 0 {
+            Vec::new()
+        } else {
+            signing_key().sign(header.hash().into()).to_bytes()
+        };
+
+        Ok(Response::new(GetBlockHeaderByNumberResponse {
+            block_header: Some(header.into()),
+            signature,
+        }))
+    }
+
+    async fn get_block_inclusion_proof(
+        &self,
+        _request: Request<GetBlockInclusionProofRequest>,
+    ) -> Result<Response<GetBlockInclusionProofResponse>, Status> {
+        Err(Status::unimplemented(""))
+    }
+
+    async fn get_block_proof(
+        &self,
+        _request: Request<GetBlockProofRequest>,
+    ) -> Result<Response<GetBlockProofResponse>, Status> {
+        Err(Status::unimplemented(""))
+    }
+
+    async fn get_note_authentication_info(
+        &self,
+        _request: Request<GetNoteAuthenticationInfoRequest>,
+    ) -> Result<Response<GetNoteAuthenticationInfoResponse>, Status> {
+        Err(Status::unimplemented(""))
+    }
+
+    async fn get_notes_by_id(
+        &self,
+        _request: Request<GetNotesByIdRequest>,
+    ) -> Result<Response<GetNotesByIdResponse>, Status> {
+        Err(Status::unimplemented(""))
+    }
+
+    async fn get_transaction_receipt(
+        &self,
+        _request: Request<GetTransactionReceiptRequest>,
+    ) -> Result<Response<GetTransactionReceiptResponse>, Status> {
+        Err(Status::not_found(""))
+    }
+
+    async fn get_accounts_by_pub_key(
+        &self,
+        _request: Request<GetAccountsByPubKeyRequest>,
+    ) -> Result<Response<GetAccountsByPubKeyResponse>, Status> {
+        Err(Status::unimplemented(""))
+    }
+
+    async fn get_note_script_by_root(
+        &self,
+        _request: Request<GetNoteScriptByRootRequest>,
+    ) -> Result<Response<GetNoteScriptByRootResponse>, Status> {
+        Err(Status::not_found(""))
+    }
+
+    async fn get_mempool_stats(
+        &self,
+        _request: Request<GetMempoolStatsRequest>,
+    ) -> Result<Response<GetMempoolStatsResponse>, Status> {
+        Err(Status::unimplemented(""))
+    }
+
+    type GetBlocksStream = Empty<Result<SubscribeBlocksResponse, Status>>;
+
+    async fn get_blocks(
+        &self,
+        _request: Request<SubscribeBlocksRequest>,
+    ) -> Result<Response<Self::GetBlocksStream>, Status> {
+        Err(Status::unimplemented(""))
+    }
+
+    type SubscribeAccountsStream = Empty<Result<SubscribeAccountsResponse, Status>>;
+
+    async fn subscribe_accounts(
+        &self,
+        _request: Request<SubscribeAccountsRequest>,
+    ) -> Result<Response<Self::SubscribeAccountsStream>, Status> {
+        Err(Status::unimplemented(""))
+    }
+
+    async fn request_testnet_funds(
+        &self,
+        _request: Request<RequestTestnetFundsRequest>,
+    ) -> Result<Response<RequestTestnetFundsResponse>, Status> {
+        Err(Status::unimplemented(""))
+    }
+
+    async fn get_node_status(
+        &self,
+        _request: Request<GetNodeStatusRequest>,
+    ) -> Result<Response<GetNodeStatusResponse>, Status> {
+        Err(Status::unimplemented(""))
+    }
+
+    async fn get_version(
+        &self,
+        _request: Request<GetVersionRequest>,
+    ) -> Result<Response<GetVersionResponse>, Status> {
+        Err(Status::unimplemented(""))
+    }
+
+    async fn sync_state(
+        &self,
+        request: Request<SyncStateRequest>,
+    ) -> Result<Response<SyncStateResponse>, Status> {
+        self.requests.fetch_add(1, Ordering::SeqCst);
+        if self
+            .failures
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+            .is_ok()
+        {
+            return Err(Status::unavailable("try again"));
+        }
+
+        // every block contains a matching note
+        let request = request.into_inner();
+        let block_num = (request.block_num + request.max_blocks.max(1)).min(self.chain_tip);
+        let preceding_blocks = (request.block_num + 1..block_num)
+            .map(|block_num| SyncedBlock {
+                block_header: Some(block_header(block_num).into()),
+                mmr_path: Some(MerklePath::default().into()),
+                notes: vec![],
+                merkle_path_nodes: vec![],
+            })
+            .collect();
+
+        // a single note tag is synced per response, reported as a nullifier consumed in block
+        // `tag`, the next tags are synced by continuing the request
+        let synced_tags = request.continuation.first().copied().unwrap_or_default() as usize;
+        let nullifiers = request
+            .note_tags
+            .get(synced_tags)
+            .map(|tag| NullifierUpdate {
+                nullifier: Some(Digest::default().into()),
+                block_num: *tag,
+                block_timestamp: 0,
+            })
+            .into_iter()
+            .collect();
+        let truncated = synced_tags + 1 < request.note_tags.len();
+        let continuation = if truncated { vec![synced_tags as u8 + 1] } else { vec![] };
+
+        let mut path_nodes = MerklePathNodes::default();
+        let notes = (0..MOCK_NOTES)
+            .map(|note_index| {
+                let merkle_path = note_merkle_path(note_index);
+                let (merkle_path, merkle_path_nodes) = if request.compact_merkle_paths {
+                    (None, path_nodes.insert(&merkle_path))
+                } else {
+                    (Some(merkle_path.into()), vec![])
+                };
+                NoteSyncRecord {
+                    note_index,
+                    note_id: Some(Digest::from([Felt::from(note_index); 4]).into()),
+                    sender: Some(ACCOUNT_ID_OFF_CHAIN_SENDER.into()),
+                    tag: 0,
+                    aux: u64::from(note_index),
+                    note_type: NoteType::OffChain as u32,
+                    merkle_path,
+                    merkle_path_nodes,
+                }
+            })
+            .collect();
+
+        Ok(Response::new(SyncStateResponse {
+            chain_tip: self.chain_tip,
+            block_header: Some(block_header(block_num).into()),
+            mmr_delta: Some(MmrDelta { forest: block_num as u64, data: vec![] }),
+            accounts: vec![],
+            notes,
+            nullifiers,
+            preceding_blocks,
+            account_details: vec![],
+            truncated,
+            continuation,
+            merkle_path_nodes: path_nodes.into_nodes(),
+        }))
+    }
+
+    async fn sync_notes(
+        &self,
+        _request: Request<SyncNotesRequest>,
+    ) -> Result<Response<SyncNotesResponse>, Status> {
+        Err(Status::unimplemented(""))
+    }
+
+    /// Accepts the transaction, but reports a timeout for the first `failures` submissions.
+    async fn submit_proven_transaction(
+        &self,
+        _request: Request<SubmitProvenTransactionRequest>,
+    ) -> Result<Response<SubmitProvenTransactionResponse>, Status> {
+        self.requests.fetch_add(1, Ordering::SeqCst);
+        if self
+            .failures
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+            .is_ok()
+        {
+            return Err(Status::deadline_exceeded("timed out after accepting the transaction"));
+        }
+
+        Ok(Response::new(SubmitProvenTransactionResponse {}))
+    }
+
+    async fn simulate_transaction(
+        &self,
+        _request: Request<SimulateTransactionRequest>,
+    ) -> Result<Response<SimulateTransactionResponse>, Status> {
+        Err(Status::unimplemented(""))
+    }
+
+    async fn get_account_details(
+        &self,
+        _request: Request<GetAccountDetailsRequest>,
+    ) -> Result<Response<GetAccountDetailsResponse>, Status> {
+        Err(Status::unimplemented(""))
+    }
+
+    async fn get_account_deltas(
+        &self,
+        _request: Request<GetAccountDeltasRequest>,
+    ) -> Result<Response<GetAccountDeltasResponse>, Status> {
+        Err(Status::unimplemented(""))
+    }
+
+    async fn get_note_consumption(
+        &self,
+        _request: Request<GetNoteConsumptionRequest>,
+    ) -> Result<Response<GetNoteConsumptionResponse>, Status> {
+        Err(Status::unimplemented(""))
+    }
+
+    async fn get_serialization_vectors(
+        &self,
+        _request: Request<GetSerializationVectorsRequest>,
+    ) -> Result<Response<GetSerializationVectorsResponse>, Status> {
+        Err(Status::unimplemented(""))
+    }
+}
+
+/// Starts the mock server, returning a client connected to it and the number of `SyncState`
+/// requests received by the server.
+async fn start_mock(chain_tip: u32, failures: u32) -> (RpcClient, Arc<AtomicU32>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let requests = Arc::new(AtomicU32::default());
+    let mock = MockRpc {
+        chain_tip,
+        failures: AtomicU32::new(failures),
+        requests: requests.clone(),
+    };
+
+    tokio::spawn(
+        Server::builder()
+            .add_service(ApiServer::new(mock).send_compressed(CompressionEncoding::Gzip))
+            .serve_with_incoming(TcpListenerStream::new(listener)),
+    );
+
+    let inner = ApiClient::connect(format!("http://{addr}")).await.unwrap();
+    let retry_policy = RetryPolicy {
+        initial_backoff: Duration::from_millis(1),
+        ..Default::default()
+    };
+
+    (RpcClient::new(inner).with_retry_policy(retry_policy), requests)
+}
+
+// TESTS
+// ================================================================================================
+
+#[tokio::test]
+async fn sync_state_to_tip_follows_pagination() {
+    let (client, requests) = start_mock(3, 0).await;
+
+    let updates = client.sync_state_to_tip(0, &[], &[], &[]).await.unwrap();
+
+    let block_nums: Vec<_> = updates.iter().map(|update| update.block_header.block_num()).collect();
+    assert_eq!(block_nums, vec![1, 2, 3]);
+    assert!(updates.last().unwrap().is_at_chain_tip());
+    assert_eq!(requests.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn sync_state_to_tip_with_larger_pages() {
+    let (client, requests) = start_mock(5, 0).await;
+    let client = client.with_sync_page_size(SyncPageSize { max_blocks: 3, max_notes: 0 });
+
+    let updates = client.sync_state_to_tip(0, &[], &[], &[]).await.unwrap();
+
+    let block_nums: Vec<_> = updates.iter().map(|update| update.block_header.block_num()).collect();
+    assert_eq!(block_nums, vec![3, 5]);
+    let preceding: Vec<_> = updates[0]
+        .preceding_blocks
+        .iter()
+        .map(|block| block.block_header.block_num())
+        .collect();
+    assert_eq!(preceding, vec![1, 2]);
+    assert_eq!(requests.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn nullifiers_by_prefix_follow_pagination() {
+    let (client, requests) = start_mock(5, 0).await;
+
+    let (nullifiers, block_num) = client.get_nullifiers_by_prefix(16, &[1], 0).await.unwrap();
+
+    let blocks: Vec<_> = nullifiers.iter().map(|nullifier| nullifier.block_num).collect();
+    assert_eq!(blocks, vec![1, 2, 3, 4, 5]);
+    assert_eq!(block_num, 5);
+    assert_eq!(requests.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn sync_state_at_chain_tip_returns_single_update() {
+    let (client, _) = start_mock(3, 0).await;
+
+    let updates = client.sync_state_to_tip(3, &[], &[], &[]).await.unwrap();
+
+    assert_eq!(updates.len(), 1);
+    assert_eq!(updates[0].block_header.block_num(), 3);
+}
+
+#[tokio::test]
+async fn truncated_state_sync_is_continued() {
+    let (client, requests) = start_mock(5, 0).await;
+    let client = client.with_sync_page_size(SyncPageSize { max_blocks: 3, max_notes: 0 });
+
+    let update = client.sync_state(0, &[], &[7, 8, 9], &[]).await.unwrap();
+
+    assert_eq!(update.block_header.block_num(), 3);
+    assert_eq!(update.preceding_blocks.len(), 2);
+    let nullifier_blocks: Vec<_> = update.nullifiers.iter().map(|n| n.block_num).collect();
+    assert_eq!(nullifier_blocks, vec![7, 8, 9]);
+    assert_eq!(requests.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn compact_merkle_paths_are_expanded() {
+    let (client, _) = start_mock(1, 0).await;
+    let compact_client = client.clone().with_compact_merkle_paths(true).with_compression(true);
+
+    let update = client.sync_state(0, &[], &[], &[]).await.unwrap();
+    let compact_update = compact_client.sync_state(0, &[], &[], &[]).await.unwrap();
+
+    assert_eq!(update.notes.len(), MOCK_NOTES as usize);
+    assert_eq!(compact_update.notes, update.notes);
+    for note in &compact_update.notes {
+        assert_eq!(note.merkle_path, note_merkle_path(note.note_index));
+        assert_eq!(note.metadata.aux(), Felt::from(note.note_index));
+    }
+}
+
+#[tokio::test]
+async fn signed_block_header_is_verified() {
+    let (client, _) = start_mock(3, 0).await;
+
+    let signed = client.get_signed_block_header(Some(2)).await.unwrap();
+    assert_eq!(signed.header.block_num(), 2);
+    assert!(signed.verify(signing_key().public_key()));
+    assert!(!signed.verify(SecretKey::new().public_key()));
+
+    let genesis = client.get_signed_block_header(Some(0)).await.unwrap();
+    assert_eq!(genesis.signature, None);
+    assert!(!genesis.verify(signing_key().public_key()));
+}
+
+#[tokio::test]
+async fn transient_errors_are_retried() {
+    let (client, requests) = start_mock(1, 2).await;
+
+    let update = client.sync_state(0, &[], &[], &[]).await.unwrap();
+
+    assert_eq!(update.block_header.block_num(), 1);
+    assert_eq!(requests.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn retries_are_bounded() {
+    let (client, requests) = start_mock(1, 10).await;
+    let client = client.with_retry_policy(RetryPolicy {
+        max_retries: 1,
+        initial_backoff: Duration::from_millis(1),
+        ..Default::default()
+    });
+
+    let err = client.sync_state(0, &[], &[], &[]).await.unwrap_err();
+
+    assert!(
+        matches!(err, RpcClientError::RequestFailed(status) if status.code() == tonic::Code::Unavailable)
+    );
+    assert_eq!(requests.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn transaction_submissions_are_not_retried() {
+    let (client, requests) = start_mock(1, 1).await;
+    let transaction = ProvenTransactionBuilder::new(
+        ACCOUNT_ID_OFF_CHAIN_SENDER.try_into().unwrap(),
+        Digest::default(),
+        Digest::from([ONE; 4]),
+        Digest::default(),
+        ExecutionProof::new(StarkProof::new_dummy(), HashFunction::Blake3_192),
+    )
+    .build()
+    .unwrap();
+
+    let err = client.submit_proven_transaction(&transaction).await.unwrap_err();
+
+    assert!(
+        matches!(err, RpcClientError::RequestFailed(status) if status.code() == tonic::Code::DeadlineExceeded)
+    );
+    assert_eq!(requests.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn block_header_is_converted() {
+    let (client, _) = start_mock(5, 0).await;
+
+    assert_eq!(client.get_block_header_by_number(None).await.unwrap(), block_header(5));
+    assert_eq!(client.get_block_header_by_number(Some(2)).await.unwrap(), block_header(2));
+}
+
+#[tokio::test]
+async fn chain_tip_is_converted() {
+    let (client, _) = start_mock(5, 0).await;
+
+    let chain_tip = client.get_chain_tip().await.unwrap();
+
+    assert_eq!(
+        chain_tip,
+        ChainTip {
+            block_num: 5,
+            block_hash: block_header(5).hash(),
+        }
+    );
+}
+
+#[tokio::test]
+async fn unknown_transaction_has_no_receipt() {
+    let (client, _) = start_mock(5, 0).await;
+
+    let receipt = client.get_transaction_receipt(Digest::default().into()).await.unwrap();
+
+    assert_eq!(receipt, None);
+}
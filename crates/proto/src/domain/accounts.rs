@@ -5,7 +5,7 @@ use miden_objects::{
     accounts::{Account, AccountId},
     crypto::{hash::rpo::RpoDigest, merkle::MerklePath},
     transaction::AccountDetails,
-    utils::Serializable,
+    utils::{Deserializable, Serializable},
     Digest,
 };
 
@@ -82,6 +82,8 @@ pub struct AccountSummary {
     pub account_id: AccountId,
     pub account_hash: RpoDigest,
     pub block_num: u32,
+    /// Nonce of the account, only known for the public accounts
+    pub nonce: Option<u64>,
 }
 
 impl From<&AccountSummary> for AccountSummaryPb {
@@ -90,10 +92,30 @@ impl From<&AccountSummary> for AccountSummaryPb {
             account_id: Some(update.account_id.into()),
             account_hash: Some(update.account_hash.into()),
             block_num: update.block_num,
+            nonce: update.nonce,
         }
     }
 }
 
+impl TryFrom<AccountSummaryPb> for AccountSummary {
+    type Error = ConversionError;
+
+    fn try_from(value: AccountSummaryPb) -> Result<Self, Self::Error> {
+        Ok(Self {
+            account_id: value
+                .account_id
+                .ok_or(AccountSummaryPb::missing_field(stringify!(account_id)))?
+                .try_into()?,
+            account_hash: value
+                .account_hash
+                .ok_or(AccountSummaryPb::missing_field(stringify!(account_hash)))?
+                .try_into()?,
+            block_num: value.block_num,
+            nonce: value.nonce,
+        })
+    }
+}
+
 #[derive(Debug, PartialEq)]
 pub struct AccountInfo {
     pub summary: AccountSummary,
@@ -109,6 +131,58 @@ impl From<&AccountInfo> for AccountInfoPb {
     }
 }
 
+impl TryFrom<AccountInfoPb> for AccountInfo {
+    type Error = ConversionError;
+
+    fn try_from(value: AccountInfoPb) -> Result<Self, Self::Error> {
+        Ok(Self {
+            summary: value
+                .summary
+                .ok_or(AccountInfoPb::missing_field(stringify!(summary)))?
+                .try_into()?,
+            details: value.details.map(|details| Account::read_from_bytes(&details)).transpose()?,
+        })
+    }
+}
+
+/// Outcome of looking up the latest state of an account.
+#[derive(Debug, PartialEq)]
+pub enum AccountLookup {
+    /// The account was never registered, i.e. no block has updated it.
+    NotFound,
+    /// The account is private, only the hash of its state is stored.
+    PrivateOnChain { summary: AccountSummary },
+    /// The account is public, its full state is stored.
+    Public {
+        summary: AccountSummary,
+        account: Account,
+    },
+}
+
+impl AccountLookup {
+    /// Returns the account info, or `None` if the account was not found.
+    pub fn into_info(self) -> Option<AccountInfo> {
+        match self {
+            AccountLookup::NotFound => None,
+            AccountLookup::PrivateOnChain { summary } => {
+                Some(AccountInfo { summary, details: None })
+            },
+            AccountLookup::Public { summary, account } => {
+                Some(AccountInfo { summary, details: Some(account) })
+            },
+        }
+    }
+}
+
+impl From<AccountInfo> for AccountLookup {
+    fn from(AccountInfo { summary, details }: AccountInfo) -> Self {
+        match details {
+            Some(account) => AccountLookup::Public { summary, account },
+            None => AccountLookup::PrivateOnChain { summary },
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct AccountUpdateDetails {
     pub account_id: AccountId,
@@ -116,6 +190,18 @@ pub struct AccountUpdateDetails {
     pub details: Option<AccountDetails>,
 }
 
+impl AccountUpdateDetails {
+    /// Returns the nonce of the account after the update, if the update carries it: the nonce of
+    /// the account's full details, or the new nonce of its delta.
+    pub fn final_nonce(&self) -> Option<u64> {
+        match &self.details {
+            Some(AccountDetails::Full(account)) => Some(account.nonce().as_int()),
+            Some(AccountDetails::Delta(delta)) => delta.nonce().map(|nonce| nonce.as_int()),
+            None => None,
+        }
+    }
+}
+
 impl From<&AccountUpdateDetails> for AccountUpdate {
     fn from(update: &AccountUpdateDetails) -> Self {
         Self {
@@ -0,0 +1,89 @@
+use miden_node_utils::chain_params::{ChainFees, ChainGenesis, ChainParameters};
+
+use crate::{
+    errors::{ConversionError, MissingFieldHelper},
+    generated::responses,
+};
+
+// CONVERSIONS
+// ================================================================================================
+
+impl From<ChainFees> for responses::ChainFees {
+    fn from(fees: ChainFees) -> Self {
+        Self {
+            operator: Some(fees.operator.into()),
+            faucet_id: Some(fees.faucet_id.into()),
+            min_amount: fees.min_amount,
+        }
+    }
+}
+
+impl TryFrom<responses::ChainFees> for ChainFees {
+    type Error = ConversionError;
+
+    fn try_from(fees: responses::ChainFees) -> Result<Self, Self::Error> {
+        Ok(Self {
+            operator: fees
+                .operator
+                .ok_or(responses::ChainFees::missing_field(stringify!(operator)))?
+                .try_into()?,
+            faucet_id: fees
+                .faucet_id
+                .ok_or(responses::ChainFees::missing_field(stringify!(faucet_id)))?
+                .try_into()?,
+            min_amount: fees.min_amount,
+        })
+    }
+}
+
+impl From<ChainParameters> for responses::ChainParameters {
+    fn from(parameters: ChainParameters) -> Self {
+        Self {
+            chain_id: parameters.chain_id,
+            max_batch_size: parameters.max_batch_size,
+            note_tree_depth: parameters.note_tree_depth.into(),
+            fees: parameters.fees.map(Into::into),
+        }
+    }
+}
+
+impl TryFrom<responses::ChainParameters> for ChainParameters {
+    type Error = ConversionError;
+
+    fn try_from(parameters: responses::ChainParameters) -> Result<Self, Self::Error> {
+        Ok(Self {
+            chain_id: parameters.chain_id,
+            max_batch_size: parameters.max_batch_size,
+            note_tree_depth: parameters.note_tree_depth.try_into().map_err(|_| {
+                ConversionError::ValueOutOfRange {
+                    field_name: stringify!(note_tree_depth),
+                    value: parameters.note_tree_depth.into(),
+                }
+            })?,
+            fees: parameters.fees.map(TryInto::try_into).transpose()?,
+        })
+    }
+}
+
+impl From<ChainGenesis> for responses::GetGenesisResponse {
+    fn from(genesis: ChainGenesis) -> Self {
+        Self {
+            header: Some(genesis.header.into()),
+            parameters: genesis.parameters.map(Into::into),
+        }
+    }
+}
+
+impl TryFrom<responses::GetGenesisResponse> for ChainGenesis {
+    type Error = ConversionError;
+
+    fn try_from(genesis: responses::GetGenesisResponse) -> Result<Self, Self::Error> {
+        Ok(Self {
+            header: genesis
+                .header
+                .ok_or(responses::GetGenesisResponse::missing_field(stringify!(header)))?
+                .try_into()?,
+            parameters: genesis.parameters.map(TryInto::try_into).transpose()?,
+        })
+    }
+}
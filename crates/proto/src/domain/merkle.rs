@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use miden_objects::{
     crypto::merkle::{LeafIndex, MerklePath, MmrDelta, SmtLeaf, SmtProof},
     Digest, Word,
@@ -27,6 +29,57 @@ impl TryFrom<generated::merkle::MerklePath> for MerklePath {
     }
 }
 
+// MERKLE PATH NODES
+// ------------------------------------------------------------------------------------------------
+
+/// Distinct nodes of a set of Merkle paths, e.g. the paths of the notes of a block, which share the
+/// nodes close to the root and the roots of the empty subtrees.
+///
+/// Each node is stored once, and the paths refer to the nodes by index.
+#[derive(Debug, Default)]
+pub struct MerklePathNodes {
+    nodes: Vec<generated::digest::Digest>,
+    indices: BTreeMap<generated::digest::Digest, u32>,
+}
+
+impl MerklePathNodes {
+    /// Adds the nodes of `path` not added yet, and returns the indices of all its nodes.
+    pub fn insert(&mut self, path: &MerklePath) -> Vec<u32> {
+        path.nodes()
+            .iter()
+            .map(|node| {
+                let node = generated::digest::Digest::from(node);
+                *self.indices.entry(node.clone()).or_insert_with(|| {
+                    self.nodes.push(node);
+                    (self.nodes.len() - 1) as u32
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the distinct nodes, in the order of their indices.
+    pub fn into_nodes(self) -> Vec<generated::digest::Digest> {
+        self.nodes
+    }
+}
+
+/// Returns the Merkle path of the nodes at `indices` in `nodes`, the distinct nodes built by
+/// [MerklePathNodes].
+pub fn expand_merkle_path(
+    indices: &[u32],
+    nodes: &[Digest],
+) -> Result<MerklePath, ConversionError> {
+    indices
+        .iter()
+        .map(|&index| {
+            nodes
+                .get(index as usize)
+                .copied()
+                .ok_or(ConversionError::MerklePathNodeOutOfBounds { index, nodes: nodes.len() })
+        })
+        .collect()
+}
+
 // MMR DELTA
 // ================================================================================================
 
@@ -155,3 +208,41 @@ impl From<SmtProof> for generated::smt::SmtOpening {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use miden_objects::{crypto::merkle::MerklePath, Digest, Felt};
+
+    use super::{expand_merkle_path, MerklePathNodes};
+    use crate::errors::ConversionError;
+
+    #[test]
+    fn merkle_path_nodes_are_deduplicated() {
+        let node = |value: u64| Digest::from([Felt::new(value); 4]);
+        let paths = [
+            MerklePath::new(vec![node(1), node(2), node(3)]),
+            MerklePath::new(vec![node(0), node(2), node(3)]),
+            MerklePath::new(vec![node(4), node(4), node(3)]),
+        ];
+
+        let mut path_nodes = MerklePathNodes::default();
+        let indices: Vec<_> = paths.iter().map(|path| path_nodes.insert(path)).collect();
+        assert_eq!(indices, [vec![0, 1, 2], vec![3, 1, 2], vec![4, 4, 2]]);
+
+        let nodes = path_nodes
+            .into_nodes()
+            .into_iter()
+            .map(Digest::try_from)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(nodes.len(), 5);
+        for (path, indices) in paths.iter().zip(&indices) {
+            assert_eq!(&expand_merkle_path(indices, &nodes).unwrap(), path);
+        }
+
+        assert_eq!(
+            expand_merkle_path(&[0, 5], &nodes),
+            Err(ConversionError::MerklePathNodeOutOfBounds { index: 5, nodes: 5 })
+        );
+    }
+}
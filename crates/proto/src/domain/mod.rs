@@ -1,8 +1,15 @@
 pub mod accounts;
 pub mod blocks;
+pub mod chain_params;
 pub mod digest;
 pub mod merkle;
+pub mod notes;
 pub mod nullifiers;
+pub mod pagination;
+pub mod runtime;
+pub mod transactions;
+pub mod vectors;
+pub mod version;
 
 // UTILITIES
 // ================================================================================================
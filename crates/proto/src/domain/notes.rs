@@ -0,0 +1,49 @@
+use miden_objects::notes::{NoteExecutionMode, NoteTag};
+
+// NOTE TAG COMPONENTS
+// ================================================================================================
+
+/// Mask of the use case of a note tag, once shifted by [NOTE_TAG_USE_CASE_SHIFT].
+pub const NOTE_TAG_USE_CASE_MASK: u32 = 0x3fff;
+
+/// Position of the use case in a note tag, the 14 bits following the 2 high bits.
+pub const NOTE_TAG_USE_CASE_SHIFT: u32 = 16;
+
+/// Returns the use case of a note tag, by which the notes are requested in `SyncNotes`.
+///
+/// The use case is the 14 bits following the 2 high bits of the tag. The tags targeting a single
+/// account hold a prefix of the account id instead, and have no use case.
+pub fn note_tag_use_case(tag: u32) -> Option<u32> {
+    (!NoteTag::from(tag).is_single_target())
+        .then_some((tag >> NOTE_TAG_USE_CASE_SHIFT) & NOTE_TAG_USE_CASE_MASK)
+}
+
+/// Returns `true` if the tag hints that the note is executed by the network rather than by a
+/// client.
+pub fn is_network_note_tag(tag: u32) -> bool {
+    NoteTag::from(tag).execution_mode() == NoteExecutionMode::Network
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_network_note_tag, note_tag_use_case};
+
+    #[test]
+    fn note_tags_are_decomposed() {
+        // Local execution by a single account, the account id prefix isn't a use case
+        assert_eq!(note_tag_use_case(0x0123_4567), None);
+        assert!(!is_network_note_tag(0x0123_4567));
+
+        // Network execution by a single account
+        assert_eq!(note_tag_use_case(0x8123_4567), None);
+        assert!(is_network_note_tag(0x8123_4567));
+
+        // Use cases, executed locally unless the 2 high bits are 0b11
+        assert_eq!(note_tag_use_case(0x4123_4567), Some(0x0123));
+        assert!(!is_network_note_tag(0x4123_4567));
+        assert_eq!(note_tag_use_case(0xa123_4567), Some(0x2123));
+        assert!(!is_network_note_tag(0xa123_4567));
+        assert_eq!(note_tag_use_case(0xffff_0000), Some(0x3fff));
+        assert!(is_network_note_tag(0xffff_0000));
+    }
+}
@@ -1,3 +1,4 @@
+use miden_node_utils::chain_params::NULLIFIER_PREFIX_LEN;
 use miden_objects::{
     crypto::{hash::rpo::RpoDigest, merkle::SmtProof},
     notes::Nullifier,
@@ -8,6 +9,27 @@ use crate::{
     generated::{digest::Digest, responses::NullifierBlockInputRecord},
 };
 
+// NULLIFIER PREFIX
+// ================================================================================================
+
+/// Returns the [NULLIFIER_PREFIX_LEN] high bits of the nullifier, by which the nullifiers are
+/// requested in `SyncState`.
+pub fn nullifier_prefix(nullifier: &Nullifier) -> u32 {
+    nullifier_prefix_of_len(nullifier, NULLIFIER_PREFIX_LEN)
+}
+
+/// Returns the `prefix_len` high bits of the nullifier, by which the nullifiers are requested in
+/// `CheckNullifiersByPrefix`.
+///
+/// Shorter prefixes match more nullifiers, hiding better which notes the client is interested in.
+///
+/// # Panics
+/// Panics if `prefix_len` is not in the range `1..=32`.
+pub fn nullifier_prefix_of_len(nullifier: &Nullifier, prefix_len: u32) -> u32 {
+    assert!((1..=32).contains(&prefix_len), "invalid nullifier prefix length {prefix_len}");
+    (nullifier.most_significant_felt().as_int() >> (u64::BITS - prefix_len)) as u32
+}
+
 // FROM NULLIFIER
 // ================================================================================================
 
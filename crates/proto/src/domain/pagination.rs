@@ -0,0 +1,205 @@
+//! Page tokens of the paginated list endpoints, see `page.proto`.
+//!
+//! A token carries the cursor of the endpoint, e.g. the last item of the previous page, followed
+//! by a keyed BLAKE3 MAC of the cursor and of the scope of the request, the endpoint and its
+//! filters. The clients can't forge cursors, nor reuse a token with other filters.
+use miden_objects::utils::{Deserializable, Serializable};
+
+use crate::{
+    errors::PageTokenError,
+    generated::page::{Page, PageRequest, PageToken},
+};
+
+/// Size of the key of the MAC of the page tokens.
+pub const PAGE_TOKEN_KEY_LEN: usize = blake3::KEY_LEN;
+
+/// Size of the MAC appended to the cursors.
+const MAC_LEN: usize = blake3::OUT_LEN;
+
+// PAGE SIZE
+// ================================================================================================
+
+/// Page sizes of a list endpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PageSize {
+    /// Size of the pages requested without a size
+    pub default: usize,
+    /// Maximum size of the pages, larger requested sizes are capped
+    pub max: usize,
+}
+
+impl PageSize {
+    /// Returns the size of the page for the `requested` size, zero requesting the default size.
+    pub fn of(&self, requested: u32) -> usize {
+        match requested {
+            0 => self.default,
+            requested => (requested as usize).min(self.max),
+        }
+    }
+}
+
+/// Truncates `items`, selected with one more item than the page `size`, to the page, returning
+/// the cursor of its last item if the list has more items.
+pub fn next_page_cursor<T, C>(
+    items: &mut Vec<T>,
+    size: usize,
+    cursor: impl Fn(&T) -> C,
+) -> Option<C> {
+    if items.len() <= size {
+        return None;
+    }
+    items.truncate(size);
+    items.last().map(cursor)
+}
+
+// PAGE TOKENS
+// ================================================================================================
+
+/// Page requested by a list request, from the decoded [PageRequest].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PageParams<C> {
+    /// Position of the page, [None] for the first page
+    pub cursor: Option<C>,
+    /// Maximum number of items of the page
+    pub size: usize,
+}
+
+impl<C> PageParams<C> {
+    pub fn is_first(&self) -> bool {
+        self.cursor.is_none()
+    }
+}
+
+/// Issues and verifies the page tokens of the list endpoints of a component.
+///
+/// The tokens are only valid for the key they were issued with, a component using a random key
+/// invalidates its tokens on restart and the clients start over from the first page.
+#[derive(Clone)]
+pub struct PageTokens {
+    key: [u8; PAGE_TOKEN_KEY_LEN],
+}
+
+impl PageTokens {
+    pub fn new(key: [u8; PAGE_TOKEN_KEY_LEN]) -> Self {
+        Self { key }
+    }
+
+    /// Returns page tokens signed with a random key.
+    pub fn random() -> Self {
+        Self::new(rand::random())
+    }
+
+    /// Decodes `request` of a list request of `scope`, [None] requesting the first page of the
+    /// default size.
+    pub fn decode<C: Deserializable>(
+        &self,
+        scope: &str,
+        request: Option<PageRequest>,
+        page_size: PageSize,
+    ) -> Result<PageParams<C>, PageTokenError> {
+        let PageRequest { token, size } = request.unwrap_or_default();
+        let cursor = token.map(|token| self.verify(scope, &token.token)).transpose()?;
+
+        Ok(PageParams { cursor, size: page_size.of(size) })
+    }
+
+    /// Returns the page of a list request of `scope`, continuing after `next` if the list has more
+    /// items.
+    pub fn page<C: Serializable>(
+        &self,
+        scope: &str,
+        next: Option<&C>,
+        total_estimate: Option<u64>,
+    ) -> Page {
+        Page {
+            next: next.map(|cursor| PageToken { token: self.sign(scope, cursor) }),
+            total_estimate,
+        }
+    }
+
+    fn sign<C: Serializable>(&self, scope: &str, cursor: &C) -> Vec<u8> {
+        let mut token = cursor.to_bytes();
+        let mac = self.mac(scope, &token);
+        token.extend_from_slice(mac.as_bytes());
+        token
+    }
+
+    fn verify<C: Deserializable>(&self, scope: &str, token: &[u8]) -> Result<C, PageTokenError> {
+        let Some(cursor_len) = token.len().checked_sub(MAC_LEN) else {
+            return Err(PageTokenError::TooShort(token.len()));
+        };
+        let (cursor, mac) = token.split_at(cursor_len);
+        let mac: [u8; MAC_LEN] = mac.try_into().expect("the MAC has a fixed size");
+
+        // Comparing `Hash`es takes a constant time
+        if self.mac(scope, cursor) != blake3::Hash::from(mac) {
+            return Err(PageTokenError::InvalidMac);
+        }
+
+        C::read_from_bytes(cursor).map_err(PageTokenError::InvalidCursor)
+    }
+
+    fn mac(&self, scope: &str, cursor: &[u8]) -> blake3::Hash {
+        blake3::Hasher::new_keyed(&self.key)
+            .update(&(scope.len() as u64).to_le_bytes())
+            .update(scope.as_bytes())
+            .update(cursor)
+            .finalize()
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE_SIZE: PageSize = PageSize { default: 10, max: 100 };
+
+    fn request(token: Vec<u8>, size: u32) -> Option<PageRequest> {
+        Some(PageRequest { token: Some(PageToken { token }), size })
+    }
+
+    #[test]
+    fn tokens_are_only_valid_for_their_scope_and_key() {
+        let tokens = PageTokens::random();
+        let page = tokens.page("ListNotes", Some(&42u64), Some(1000));
+        assert_eq!(page.total_estimate, Some(1000));
+        let token = page.next.unwrap().token;
+
+        let params = tokens.decode::<u64>("ListNotes", request(token.clone(), 500), PAGE_SIZE);
+        assert_eq!(params, Ok(PageParams { cursor: Some(42), size: 100 }));
+
+        // other filters, or the tokens of another instance
+        let err = tokens.decode::<u64>("ListNullifiers", request(token.clone(), 0), PAGE_SIZE);
+        assert_eq!(err, Err(PageTokenError::InvalidMac));
+        let err =
+            PageTokens::random().decode::<u64>("ListNotes", request(token.clone(), 0), PAGE_SIZE);
+        assert_eq!(err, Err(PageTokenError::InvalidMac));
+
+        // tampered cursor
+        let mut tampered = token.clone();
+        tampered[0] ^= 1;
+        let err = tokens.decode::<u64>("ListNotes", request(tampered, 0), PAGE_SIZE);
+        assert_eq!(err, Err(PageTokenError::InvalidMac));
+
+        let err = tokens.decode::<u64>("ListNotes", request(token[..8].to_vec(), 0), PAGE_SIZE);
+        assert_eq!(err, Err(PageTokenError::TooShort(8)));
+    }
+
+    #[test]
+    fn first_page_without_token() {
+        let tokens = PageTokens::random();
+        assert_eq!(
+            tokens.decode::<u64>("ListNotes", None, PAGE_SIZE),
+            Ok(PageParams { cursor: None, size: 10 })
+        );
+        let request = Some(PageRequest { token: None, size: 5 });
+        assert_eq!(
+            tokens.decode::<u64>("ListNotes", request, PAGE_SIZE),
+            Ok(PageParams { cursor: None, size: 5 })
+        );
+        assert_eq!(tokens.page::<u64>("ListNotes", None, None), Page::default());
+    }
+}
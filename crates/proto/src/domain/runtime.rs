@@ -0,0 +1,21 @@
+use miden_node_utils::runtime::RuntimeMetrics;
+
+use crate::generated::responses;
+
+// RUNTIME METRICS
+// ================================================================================================
+
+impl From<RuntimeMetrics> for responses::RuntimeMetrics {
+    fn from(metrics: RuntimeMetrics) -> Self {
+        Self {
+            workers: metrics.workers as u32,
+            worker_utilization_percent: (metrics.worker_utilization * 100.0).round() as u32,
+            global_queue_depth: metrics.global_queue_depth as u64,
+            local_queue_depth: metrics.local_queue_depth as u64,
+            busy_blocking_threads: metrics.busy_blocking_threads as u32,
+            idle_blocking_threads: metrics.idle_blocking_threads as u32,
+            blocking_queue_depth: metrics.blocking_queue_depth as u64,
+            active_tasks: metrics.active_tasks as u64,
+        }
+    }
+}
@@ -0,0 +1,324 @@
+use std::fmt::{Display, Formatter};
+
+use miden_node_utils::formatting::format_array;
+use miden_objects::{
+    accounts::AccountId,
+    notes::{NoteId, Nullifier},
+    transaction::{ProvenTransaction, TransactionId},
+    Digest,
+};
+use prost::Message;
+use tonic::{Code, Status};
+
+use crate::{
+    errors::{ConversionError, MissingFieldHelper},
+    generated::{
+        digest,
+        transaction::{
+            transaction_rejection::Reason, AccountHashMismatch, ConsumedNullifiers,
+            TransactionFee as TransactionFeePb, TransactionReceipt as TransactionReceiptPb,
+            TransactionRejection as TransactionRejectionPb,
+        },
+    },
+    try_convert,
+};
+
+// TRANSACTION SUMMARY
+// ================================================================================================
+
+/// The effects of a transaction on the state of the chain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransactionSummary {
+    pub id: TransactionId,
+    pub account_id: AccountId,
+    pub initial_account_hash: Digest,
+    pub final_account_hash: Digest,
+    pub output_notes: Vec<NoteId>,
+    pub input_nullifiers: Vec<Nullifier>,
+}
+
+impl From<&ProvenTransaction> for TransactionSummary {
+    fn from(tx: &ProvenTransaction) -> Self {
+        Self {
+            id: tx.id(),
+            account_id: tx.account_id(),
+            initial_account_hash: tx.initial_account_hash(),
+            final_account_hash: tx.final_account_hash(),
+            output_notes: tx.output_notes().iter().map(|note| note.id()).collect(),
+            input_nullifiers: tx.input_notes().iter().copied().collect(),
+        }
+    }
+}
+
+// TRANSACTION RECEIPT
+// ================================================================================================
+
+/// A transaction included in a block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransactionReceipt {
+    pub transaction: TransactionSummary,
+    pub block_num: u32,
+    /// Index of the transaction's batch in the block.
+    pub batch_index: u32,
+    /// Fee paid to the operator, [None] if the node doesn't charge fees.
+    pub fee: Option<TransactionFee>,
+    /// Estimated execution cost of the transaction in VM cycles, 0 if it wasn't recorded.
+    pub cycles: u64,
+}
+
+impl From<&TransactionReceipt> for TransactionReceiptPb {
+    fn from(receipt: &TransactionReceipt) -> Self {
+        let transaction = &receipt.transaction;
+        Self {
+            transaction_id: Some(transaction.id.into()),
+            account_id: Some(transaction.account_id.into()),
+            initial_account_hash: Some(transaction.initial_account_hash.into()),
+            final_account_hash: Some(transaction.final_account_hash.into()),
+            output_notes: transaction.output_notes.iter().map(Into::into).collect(),
+            input_nullifiers: transaction.input_nullifiers.iter().map(Into::into).collect(),
+            block_num: receipt.block_num,
+            batch_index: receipt.batch_index,
+            fee: receipt.fee.map(Into::into),
+            cycles: receipt.cycles,
+        }
+    }
+}
+
+impl From<TransactionReceipt> for TransactionReceiptPb {
+    fn from(receipt: TransactionReceipt) -> Self {
+        (&receipt).into()
+    }
+}
+
+impl TryFrom<TransactionReceiptPb> for TransactionReceipt {
+    type Error = ConversionError;
+
+    fn try_from(value: TransactionReceiptPb) -> Result<Self, Self::Error> {
+        let id: Digest = value
+            .transaction_id
+            .ok_or(TransactionReceiptPb::missing_field(stringify!(transaction_id)))?
+            .try_into()?;
+        let output_notes: Vec<Digest> = try_convert(value.output_notes)?;
+
+        Ok(Self {
+            transaction: TransactionSummary {
+                id: id.into(),
+                account_id: value
+                    .account_id
+                    .ok_or(TransactionReceiptPb::missing_field(stringify!(account_id)))?
+                    .try_into()?,
+                initial_account_hash: value
+                    .initial_account_hash
+                    .ok_or(TransactionReceiptPb::missing_field(stringify!(initial_account_hash)))?
+                    .try_into()?,
+                final_account_hash: value
+                    .final_account_hash
+                    .ok_or(TransactionReceiptPb::missing_field(stringify!(final_account_hash)))?
+                    .try_into()?,
+                output_notes: output_notes.into_iter().map(Into::into).collect(),
+                input_nullifiers: try_convert(value.input_nullifiers)?,
+            },
+            block_num: value.block_num,
+            batch_index: value.batch_index,
+            fee: value.fee.map(TryInto::try_into).transpose()?,
+            cycles: value.cycles,
+        })
+    }
+}
+
+// TRANSACTION FEE
+// ================================================================================================
+
+/// Amount of a fungible asset paid by a transaction to the operator of the node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransactionFee {
+    pub faucet_id: AccountId,
+    pub amount: u64,
+}
+
+impl Display for TransactionFee {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} of faucet {}", self.amount, self.faucet_id)
+    }
+}
+
+impl From<TransactionFee> for TransactionFeePb {
+    fn from(fee: TransactionFee) -> Self {
+        Self {
+            faucet_id: Some(fee.faucet_id.into()),
+            amount: fee.amount,
+        }
+    }
+}
+
+impl TryFrom<TransactionFeePb> for TransactionFee {
+    type Error = ConversionError;
+
+    fn try_from(value: TransactionFeePb) -> Result<Self, Self::Error> {
+        Ok(Self {
+            faucet_id: value
+                .faucet_id
+                .ok_or(TransactionFeePb::missing_field(stringify!(faucet_id)))?
+                .try_into()?,
+            amount: value.amount,
+        })
+    }
+}
+
+// TRANSACTION REJECTION
+// ================================================================================================
+
+/// Reason for the rejection of a transaction submitted to the block producer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TransactionRejection {
+    /// The account was already modified by another transaction which isn't in a block yet.
+    AccountAlreadyModified(AccountId),
+    /// The input notes with these nullifiers were already consumed.
+    InputNotesAlreadyConsumed(Vec<Nullifier>),
+    /// The transaction's initial account hash doesn't match the account's current hash, which is
+    /// [None] for unknown accounts.
+    IncorrectAccountInitialHash {
+        provided: Digest,
+        expected: Option<Digest>,
+    },
+    /// The proof of the transaction is invalid.
+    InvalidTransactionProof(TransactionId),
+    /// The transaction violates a policy of the operator of the node.
+    PolicyViolation(String),
+}
+
+impl TransactionRejection {
+    /// Returns an `INVALID_ARGUMENT` status carrying the encoded rejection in its details.
+    pub fn into_status(self, message: impl Into<String>) -> Status {
+        let details = TransactionRejectionPb::from(self).encode_to_vec();
+        Status::with_details(Code::InvalidArgument, message, details.into())
+    }
+
+    /// Decodes the rejection from the details of `status`, returns [None] if the status has no
+    /// details.
+    pub fn from_status(status: &Status) -> Result<Option<Self>, ConversionError> {
+        if status.details().is_empty() {
+            return Ok(None);
+        }
+
+        TransactionRejectionPb::decode(status.details())?.try_into().map(Some)
+    }
+}
+
+impl Display for TransactionRejection {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::AccountAlreadyModified(account_id) => {
+                write!(f, "Account {account_id} was already modified by a pending transaction")
+            },
+            Self::InputNotesAlreadyConsumed(nullifiers) => write!(
+                f,
+                "Input notes with nullifiers {} were already consumed",
+                format_array(nullifiers.iter().map(Nullifier::to_hex))
+            ),
+            Self::IncorrectAccountInitialHash { provided, expected: None } => {
+                write!(f, "Initial account hash {provided} doesn't match any known account")
+            },
+            Self::IncorrectAccountInitialHash { provided, expected: Some(expected) } => write!(
+                f,
+                "Initial account hash {provided} doesn't match the account's current hash {expected}"
+            ),
+            Self::InvalidTransactionProof(tx_id) => {
+                write!(f, "Invalid proof for transaction {tx_id}")
+            },
+            Self::PolicyViolation(reason) => {
+                write!(f, "Transaction rejected by the node's policy: {reason}")
+            },
+        }
+    }
+}
+
+impl From<TransactionRejection> for TransactionRejectionPb {
+    fn from(rejection: TransactionRejection) -> Self {
+        let reason = match rejection {
+            TransactionRejection::AccountAlreadyModified(account_id) => {
+                Reason::AccountAlreadyModified(account_id.into())
+            },
+            TransactionRejection::InputNotesAlreadyConsumed(nullifiers) => {
+                Reason::InputNotesAlreadyConsumed(ConsumedNullifiers {
+                    nullifiers: nullifiers.iter().map(Into::into).collect(),
+                })
+            },
+            TransactionRejection::IncorrectAccountInitialHash { provided, expected } => {
+                Reason::IncorrectAccountInitialHash(AccountHashMismatch {
+                    provided: Some(provided.into()),
+                    expected: expected.map(Into::into),
+                })
+            },
+            TransactionRejection::InvalidTransactionProof(tx_id) => {
+                Reason::InvalidTransactionProof(tx_id.into())
+            },
+            TransactionRejection::PolicyViolation(reason) => Reason::PolicyViolation(reason),
+        };
+
+        Self { reason: Some(reason) }
+    }
+}
+
+impl TryFrom<TransactionRejectionPb> for TransactionRejection {
+    type Error = ConversionError;
+
+    fn try_from(value: TransactionRejectionPb) -> Result<Self, Self::Error> {
+        let reason =
+            value.reason.ok_or(TransactionRejectionPb::missing_field(stringify!(reason)))?;
+
+        Ok(match reason {
+            Reason::AccountAlreadyModified(account_id) => {
+                Self::AccountAlreadyModified(account_id.try_into()?)
+            },
+            Reason::InputNotesAlreadyConsumed(consumed) => {
+                Self::InputNotesAlreadyConsumed(try_convert(consumed.nullifiers)?)
+            },
+            Reason::IncorrectAccountInitialHash(mismatch) => Self::IncorrectAccountInitialHash {
+                provided: mismatch
+                    .provided
+                    .ok_or(AccountHashMismatch::missing_field(stringify!(provided)))?
+                    .try_into()?,
+                expected: mismatch.expected.map(TryInto::try_into).transpose()?,
+            },
+            Reason::InvalidTransactionProof(tx_id) => {
+                let tx_id: Digest = tx_id.try_into()?;
+                Self::InvalidTransactionProof(tx_id.into())
+            },
+            Reason::PolicyViolation(reason) => Self::PolicyViolation(reason),
+        })
+    }
+}
+
+// TRANSACTION ID
+// ================================================================================================
+
+impl From<TransactionId> for digest::Digest {
+    fn from(value: TransactionId) -> Self {
+        value.inner().into()
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use miden_objects::{Digest, Felt, ZERO};
+    use tonic::{Code, Status};
+
+    use super::TransactionRejection;
+
+    #[test]
+    fn test_transaction_rejection_status() {
+        let nullifier = Digest::new([Felt::new(1), ZERO, ZERO, ZERO]).into();
+        let rejection = TransactionRejection::InputNotesAlreadyConsumed(vec![nullifier]);
+
+        let status = rejection.clone().into_status("Transaction rejected");
+        assert_eq!(status.code(), Code::InvalidArgument);
+        assert_eq!(TransactionRejection::from_status(&status), Ok(Some(rejection)));
+
+        let status = Status::invalid_argument("Invalid transaction");
+        assert_eq!(TransactionRejection::from_status(&status), Ok(None));
+    }
+}
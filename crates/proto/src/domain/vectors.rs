@@ -0,0 +1,327 @@
+//! Canonical encodings of the values exchanged with the node, served by the RPC's
+//! `GetSerializationVectors` endpoint so that client implementations in other languages can test
+//! their decoders against a live node.
+//!
+//! The vectors are built from fixed inputs: the same protocol version always yields the same bytes.
+
+use std::fmt::{Display, Formatter};
+
+use miden_objects::{
+    accounts::AccountId,
+    assembly::ProgramAst,
+    assets::FungibleAsset,
+    block::BlockNoteTree,
+    crypto::merkle::Mmr,
+    notes::{Note, NoteAssets, NoteInputs, NoteMetadata, NoteRecipient, NoteScript, NoteType},
+    utils::Serializable,
+    BlockHeader, Digest, Felt,
+};
+use prost::Message;
+
+use super::{accounts::AccountSummary, version::PROTOCOL_VERSION};
+use crate::generated::{block_header, note, responses};
+
+/// Account sending the note of the vectors: a regular account with immutable code, stored on-chain.
+const SENDER_ID: u64 = 0x0a65_fc5a_39e4_cd3f;
+
+/// Fungible faucet of the asset of the note of the vectors, stored on-chain.
+const FAUCET_ID: u64 = 0x2a65_fc5a_39e4_cd3f;
+
+/// Timestamp of the block of the vectors.
+const BLOCK_TIMESTAMP: u32 = 1_700_000_000;
+
+// SERIALIZATION VECTOR
+// ================================================================================================
+
+/// Encoding of the data of a [SerializationVector].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VectorEncoding {
+    /// Protobuf encoding of a message of the gRPC API.
+    Protobuf,
+    /// Serialization of the Miden protocol, e.g. of the note details of the gRPC API.
+    Miden,
+}
+
+impl Display for VectorEncoding {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VectorEncoding::Protobuf => f.write_str("protobuf"),
+            VectorEncoding::Miden => f.write_str("miden"),
+        }
+    }
+}
+
+/// Canonical encoding of a value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SerializationVector {
+    pub name: &'static str,
+    /// Protobuf message or Miden type of the value.
+    pub type_name: &'static str,
+    pub encoding: VectorEncoding,
+    pub data: Vec<u8>,
+    /// Commitment to the value computed by the protocol, e.g. the block hash, to check the decoded
+    /// value against.
+    pub commitment: Option<Digest>,
+}
+
+impl From<SerializationVector> for responses::SerializationVector {
+    fn from(vector: SerializationVector) -> Self {
+        Self {
+            name: vector.name.to_string(),
+            type_name: vector.type_name.to_string(),
+            encoding: vector.encoding.to_string(),
+            data: vector.data,
+            commitment: vector.commitment.map(Into::into),
+        }
+    }
+}
+
+/// Returns the `GetSerializationVectors` response of the protocol version of the node.
+pub fn serialization_vectors_response() -> responses::GetSerializationVectorsResponse {
+    responses::GetSerializationVectorsResponse {
+        protocol_version: Some(PROTOCOL_VERSION.into()),
+        vectors: serialization_vectors().into_iter().map(Into::into).collect(),
+    }
+}
+
+// VECTORS
+// ================================================================================================
+
+/// Returns the canonical encodings of a block header, of a note created in that block and of the
+/// sync responses reporting them, both in protobuf and, where the gRPC API embeds them, in the
+/// protocol's own serialization.
+///
+/// The block is the block `1` of a chain, creating the note in its first batch.
+pub fn serialization_vectors() -> Vec<SerializationVector> {
+    let note = vector_note();
+    let note_tree = BlockNoteTree::with_entries([(0, 0, (note.id().into(), *note.metadata()))])
+        .expect("a single note fits the note tree");
+    let note_path = note_tree.get_note_path(0, 0).expect("the note is in the tree");
+
+    let genesis_hash = digest(0x10);
+    let mut chain_mmr = Mmr::new();
+    chain_mmr.add(genesis_hash);
+    let chain_root = chain_mmr
+        .peaks(chain_mmr.forest())
+        .expect("the forest is the MMR's")
+        .hash_peaks();
+
+    let header = BlockHeader::new(
+        genesis_hash,
+        1,
+        chain_root,
+        digest(0x20),
+        digest(0x30),
+        note_tree.root(),
+        digest(0x40),
+        Digest::default(),
+        Felt::new(u64::from(PROTOCOL_VERSION.major)),
+        Felt::from(BLOCK_TIMESTAMP),
+    );
+    let mmr_delta = chain_mmr.get_delta(1, chain_mmr.forest()).expect("the forests are the MMR's");
+
+    let sender: AccountId = note.metadata().sender();
+    let note_type = note.metadata().note_type() as u32;
+    let tag = note.metadata().tag().into();
+    let aux = note.metadata().aux().as_int();
+    let account = AccountSummary {
+        account_id: sender,
+        account_hash: digest(0x50),
+        block_num: header.block_num(),
+        nonce: Some(1),
+    };
+    let nullifier = note.nullifier();
+
+    let note_pb = note::Note {
+        block_num: header.block_num(),
+        note_index: 0,
+        note_id: Some(note.id().into()),
+        sender: Some(sender.into()),
+        tag,
+        note_type,
+        merkle_path: Some(note_path.clone().into()),
+        details: Some(note.to_bytes()),
+        aux,
+    };
+    let note_record = note::NoteSyncRecord {
+        note_index: 0,
+        note_id: Some(note.id().into()),
+        sender: Some(sender.into()),
+        tag,
+        note_type,
+        merkle_path: Some(note_path.clone().into()),
+        merkle_path_nodes: vec![],
+        aux,
+    };
+    let sync_state = responses::SyncStateResponse {
+        chain_tip: header.block_num(),
+        block_header: Some(header.into()),
+        mmr_delta: Some(mmr_delta.into()),
+        accounts: vec![(&account).into()],
+        notes: vec![note_record.clone()],
+        nullifiers: vec![responses::NullifierUpdate {
+            nullifier: Some((&nullifier).into()),
+            block_num: header.block_num(),
+            block_timestamp: header.timestamp().into(),
+        }],
+        ..Default::default()
+    };
+    let sync_notes = responses::SyncNotesResponse {
+        chain_tip: header.block_num(),
+        block_header: Some(header.into()),
+        mmr_path: Some(
+            chain_mmr
+                .open(0, chain_mmr.forest())
+                .expect("the genesis is in the MMR")
+                .merkle_path
+                .into(),
+        ),
+        notes: vec![note_record],
+    };
+
+    vec![
+        SerializationVector {
+            name: "block_header",
+            type_name: "BlockHeader",
+            encoding: VectorEncoding::Miden,
+            data: header.to_bytes(),
+            commitment: Some(header.hash()),
+        },
+        SerializationVector {
+            name: "block_header",
+            type_name: "block_header.BlockHeader",
+            encoding: VectorEncoding::Protobuf,
+            data: block_header::BlockHeader::from(header).encode_to_vec(),
+            commitment: Some(header.hash()),
+        },
+        SerializationVector {
+            name: "note",
+            type_name: "Note",
+            encoding: VectorEncoding::Miden,
+            data: note.to_bytes(),
+            commitment: Some(note.id().into()),
+        },
+        SerializationVector {
+            name: "note",
+            type_name: "note.Note",
+            encoding: VectorEncoding::Protobuf,
+            data: note_pb.encode_to_vec(),
+            commitment: Some(note.id().into()),
+        },
+        SerializationVector {
+            name: "nullifier",
+            type_name: "Nullifier",
+            encoding: VectorEncoding::Miden,
+            data: nullifier.to_bytes(),
+            commitment: Some(nullifier.inner()),
+        },
+        SerializationVector {
+            name: "sync_state_response",
+            type_name: "responses.SyncStateResponse",
+            encoding: VectorEncoding::Protobuf,
+            data: sync_state.encode_to_vec(),
+            commitment: None,
+        },
+        SerializationVector {
+            name: "sync_notes_response",
+            type_name: "responses.SyncNotesResponse",
+            encoding: VectorEncoding::Protobuf,
+            data: sync_notes.encode_to_vec(),
+            commitment: None,
+        },
+    ]
+}
+
+// HELPERS
+// ================================================================================================
+
+/// Returns a public pay-to-id note with a fungible asset, whose script is set without assembling it
+/// so that the note doesn't depend on the assembler's output.
+fn vector_note() -> Note {
+    let sender = AccountId::try_from(SENDER_ID).expect("the sender id is valid");
+    let faucet_id = AccountId::try_from(FAUCET_ID).expect("the faucet id is valid");
+    let asset = FungibleAsset::new(faucet_id, 100).expect("the asset is valid");
+
+    let script =
+        NoteScript::from_parts(ProgramAst::parse("begin push.1 drop end").unwrap(), digest(0x70));
+    let inputs = NoteInputs::new(vec![sender.into()]).expect("the inputs are valid");
+    let serial_num = [Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)];
+
+    Note::new(
+        NoteAssets::new(vec![asset.into()]).expect("the assets are valid"),
+        NoteMetadata::new(sender, NoteType::Public, 0x0000_4000.into(), Felt::new(7))
+            .expect("the metadata is valid"),
+        NoteRecipient::new(serial_num, script, inputs),
+    )
+}
+
+/// Returns a digest of consecutive elements starting at `seed`.
+fn digest(seed: u64) -> Digest {
+    Digest::new([Felt::new(seed), Felt::new(seed + 1), Felt::new(seed + 2), Felt::new(seed + 3)])
+}
+
+#[cfg(test)]
+mod tests {
+    use miden_objects::{
+        notes::Note,
+        utils::{Deserializable, Serializable},
+        BlockHeader, Digest, Hasher,
+    };
+    use prost::Message;
+
+    use super::{serialization_vectors, VectorEncoding};
+    use crate::generated::{block_header, note, responses};
+
+    #[test]
+    fn vectors_decode_to_their_commitments() {
+        let vectors = serialization_vectors();
+        assert_eq!(vectors, serialization_vectors());
+
+        for vector in vectors {
+            let commitment = match (vector.type_name, vector.encoding) {
+                ("BlockHeader", VectorEncoding::Miden) => {
+                    Some(BlockHeader::read_from_bytes(&vector.data).unwrap().hash())
+                },
+                ("block_header.BlockHeader", VectorEncoding::Protobuf) => {
+                    let header = block_header::BlockHeader::decode(vector.data.as_slice()).unwrap();
+                    Some(BlockHeader::try_from(header).unwrap().hash())
+                },
+                ("Note", VectorEncoding::Miden) => {
+                    let note = Note::read_from_bytes(&vector.data).unwrap();
+                    assert_eq!(note.to_bytes(), vector.data);
+                    Some(note.id().into())
+                },
+                ("note.Note", VectorEncoding::Protobuf) => {
+                    let note = note::Note::decode(vector.data.as_slice()).unwrap();
+                    Some(Note::read_from_bytes(&note.details.unwrap()).unwrap().id().into())
+                },
+                ("Nullifier", VectorEncoding::Miden) => {
+                    Some(Digest::read_from_bytes(&vector.data).unwrap())
+                },
+                ("responses.SyncStateResponse", VectorEncoding::Protobuf) => {
+                    responses::SyncStateResponse::decode(vector.data.as_slice()).unwrap();
+                    None
+                },
+                ("responses.SyncNotesResponse", VectorEncoding::Protobuf) => {
+                    responses::SyncNotesResponse::decode(vector.data.as_slice()).unwrap();
+                    None
+                },
+                (type_name, encoding) => panic!("unexpected {encoding} vector of {type_name}"),
+            };
+            assert_eq!(commitment, vector.commitment, "{} ({})", vector.name, vector.encoding);
+        }
+    }
+
+    /// The vectors are published for client implementations to test against: changing them
+    /// requires a new protocol version.
+    #[test]
+    fn vectors_are_stable() {
+        let data: Vec<u8> =
+            serialization_vectors().into_iter().flat_map(|vector| vector.data).collect();
+
+        assert_eq!(
+            Hasher::hash(&data).to_hex(),
+            "0xb7a45f973cdbd768ca065c2fab790ed284a8009af59a9c64cc9be0143426133a"
+        );
+    }
+}
@@ -0,0 +1,85 @@
+use std::fmt::{Display, Formatter};
+
+use crate::{
+    errors::{ConversionError, MissingFieldHelper},
+    generated::responses,
+};
+
+// VERSIONS
+// ================================================================================================
+
+/// Version of the gRPC API of the node's components.
+pub const PROTO_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Version of the Miden protocol implemented by the node, i.e. of its blocks, accounts, notes and
+/// transactions. The components only work together if they implement the same major version.
+pub const PROTOCOL_VERSION: ProtocolVersion = ProtocolVersion { major: 0, minor: 2 };
+
+/// Version of the Miden protocol.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProtocolVersion {
+    pub major: u32,
+    pub minor: u32,
+}
+
+impl ProtocolVersion {
+    /// Returns whether a component implementing this version works with one implementing `other`.
+    pub fn is_compatible_with(&self, other: &Self) -> bool {
+        self.major == other.major
+    }
+}
+
+impl Display for ProtocolVersion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
+// CONVERSIONS
+// ================================================================================================
+
+impl From<ProtocolVersion> for responses::ProtocolVersion {
+    fn from(version: ProtocolVersion) -> Self {
+        Self {
+            major: version.major,
+            minor: version.minor,
+        }
+    }
+}
+
+impl From<responses::ProtocolVersion> for ProtocolVersion {
+    fn from(version: responses::ProtocolVersion) -> Self {
+        Self {
+            major: version.major,
+            minor: version.minor,
+        }
+    }
+}
+
+impl TryFrom<&responses::GetVersionResponse> for ProtocolVersion {
+    type Error = ConversionError;
+
+    fn try_from(response: &responses::GetVersionResponse) -> Result<Self, Self::Error> {
+        response
+            .protocol_version
+            .clone()
+            .map(Into::into)
+            .ok_or(responses::GetVersionResponse::missing_field(stringify!(protocol_version)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ProtocolVersion;
+
+    #[test]
+    fn protocol_versions_are_compatible_within_a_major_version() {
+        let version = ProtocolVersion { major: 1, minor: 2 };
+
+        assert!(version.is_compatible_with(&ProtocolVersion { major: 1, minor: 0 }));
+        assert!(version.is_compatible_with(&ProtocolVersion { major: 1, minor: 5 }));
+        assert!(!version.is_compatible_with(&ProtocolVersion { major: 0, minor: 2 }));
+        assert!(!version.is_compatible_with(&ProtocolVersion { major: 2, minor: 2 }));
+        assert_eq!(version.to_string(), "1.2");
+    }
+}
@@ -1,6 +1,9 @@
 use std::any::type_name;
 
-use miden_objects::crypto::merkle::{SmtLeafError, SmtProofError};
+use miden_objects::{
+    crypto::merkle::{SmtLeafError, SmtProofError},
+    utils::DeserializationError,
+};
 use thiserror::Error;
 
 #[derive(Debug, Clone, PartialEq, Error)]
@@ -15,8 +18,16 @@ pub enum ConversionError {
     TooMuchData { expected: usize, got: usize },
     #[error("Not enough data, expected {expected}, got {got}")]
     InsufficientData { expected: usize, got: usize },
+    #[error("Deserialization error: {0}")]
+    DeserializationError(DeserializationError),
+    #[error("Protobuf decoding error: {0}")]
+    DecodeError(#[from] prost::DecodeError),
+    #[error("Merkle path node {index} is out of bounds, there are {nodes} nodes")]
+    MerklePathNodeOutOfBounds { index: u32, nodes: usize },
     #[error("Value is not in the range 0..MODULUS")]
     NotAValidFelt,
+    #[error("Field `{field_name}` is out of range: {value}")]
+    ValueOutOfRange { field_name: &'static str, value: u64 },
     #[error("Field `{field_name}` required to be filled in protobuf representation of {entity}")]
     MissingFieldInProtobufRepresentation {
         entity: &'static str,
@@ -26,6 +37,22 @@ pub enum ConversionError {
 
 impl Eq for ConversionError {}
 
+impl From<DeserializationError> for ConversionError {
+    fn from(value: DeserializationError) -> Self {
+        Self::DeserializationError(value)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum PageTokenError {
+    #[error("Page token of {0} bytes is too short")]
+    TooShort(usize),
+    #[error("Page token wasn't issued for this request by this node")]
+    InvalidMac,
+    #[error("Invalid cursor in the page token: {0}")]
+    InvalidCursor(DeserializationError),
+}
+
 pub trait MissingFieldHelper {
     fn missing_field(field_name: &'static str) -> ConversionError;
 }
@@ -20,6 +20,9 @@ pub struct AccountSummary {
     pub account_hash: ::core::option::Option<super::digest::Digest>,
     #[prost(uint32, tag = "3")]
     pub block_num: u32,
+    /// Nonce of the account, the number of its state updates, if the account is public
+    #[prost(uint64, optional, tag = "4")]
+    pub nonce: ::core::option::Option<u64>,
 }
 #[derive(Eq, PartialOrd, Ord, Hash)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -1,3 +1,4 @@
+pub mod admin;
 pub mod account;
 pub mod block_header;
 pub mod block_producer;
@@ -5,8 +6,11 @@ pub mod digest;
 pub mod merkle;
 pub mod mmr;
 pub mod note;
+pub mod page;
 pub mod requests;
 pub mod responses;
 pub mod rpc;
+pub mod schema;
 pub mod smt;
 pub mod store;
+pub mod transaction;
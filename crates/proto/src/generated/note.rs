@@ -21,6 +21,9 @@ pub struct Note {
     /// details contain the `Note` in a serialized format.
     #[prost(bytes = "vec", optional, tag = "8")]
     pub details: ::core::option::Option<::prost::alloc::vec::Vec<u8>>,
+    /// User defined value of the note's metadata, a field element
+    #[prost(fixed64, tag = "9")]
+    pub aux: u64,
 }
 #[derive(Eq, PartialOrd, Ord, Hash)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -38,6 +41,13 @@ pub struct NoteSyncRecord {
     pub note_type: u32,
     #[prost(message, optional, tag = "6")]
     pub merkle_path: ::core::option::Option<super::merkle::MerklePath>,
+    /// Indices of the nodes of the Merkle path in the `merkle_path_nodes` of the note's block, set
+    /// instead of `merkle_path` when the sync requested compact Merkle paths.
+    #[prost(uint32, repeated, tag = "7")]
+    pub merkle_path_nodes: ::prost::alloc::vec::Vec<u32>,
+    /// User defined value of the note's metadata, a field element
+    #[prost(fixed64, tag = "8")]
+    pub aux: u64,
 }
 #[derive(Eq, PartialOrd, Ord, Hash)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -59,4 +69,17 @@ pub struct NoteCreated {
     /// details contain the `Note` in a serialized format.
     #[prost(bytes = "vec", optional, tag = "7")]
     pub details: ::core::option::Option<::prost::alloc::vec::Vec<u8>>,
+    /// User defined value of the note's metadata, a field element
+    #[prost(fixed64, tag = "8")]
+    pub aux: u64,
+}
+/// Inclusive range of the `aux` values of the notes' metadata
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AuxRange {
+    #[prost(fixed64, tag = "1")]
+    pub min: u64,
+    #[prost(fixed64, tag = "2")]
+    pub max: u64,
 }
@@ -0,0 +1,35 @@
+// This file is @generated by prost-build.
+/// Opaque position in a paginated list, signed by the node which issued it.
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PageToken {
+    #[prost(bytes = "vec", tag = "1")]
+    pub token: ::prost::alloc::vec::Vec<u8>,
+}
+/// Page requested by a paginated list request.
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PageRequest {
+    /// Position of the page, the `next` token of the previous page. Unset for the first page.
+    #[prost(message, optional, tag = "1")]
+    pub token: ::core::option::Option<PageToken>,
+    /// Maximum number of items in the page. Zero defaults to the page size of the endpoint, larger
+    /// sizes are capped to its maximum page size.
+    #[prost(uint32, tag = "2")]
+    pub size: u32,
+}
+/// Page returned by a paginated list request.
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Page {
+    /// Position of the next page, unset if this is the last page.
+    #[prost(message, optional, tag = "1")]
+    pub next: ::core::option::Option<PageToken>,
+    /// Estimate of the total number of items of the list, only set on the first page. Items can be
+    /// added while the following pages are requested.
+    #[prost(uint64, optional, tag = "2")]
+    pub total_estimate: ::core::option::Option<u64>,
+}
@@ -23,6 +23,14 @@ pub struct ApplyBlockRequest {
     pub nullifiers: ::prost::alloc::vec::Vec<super::digest::Digest>,
     #[prost(message, repeated, tag = "4")]
     pub notes: ::prost::alloc::vec::Vec<super::note::NoteCreated>,
+    #[prost(message, repeated, tag = "5")]
+    pub transactions: ::prost::alloc::vec::Vec<super::transaction::TransactionReceipt>,
+    /// Signature of the block header's hash by the sequencer, empty if the block is not signed.
+    #[prost(bytes = "vec", tag = "6")]
+    pub signature: ::prost::alloc::vec::Vec<u8>,
+    /// Validity proof of the block, empty if the block is not proven.
+    #[prost(bytes = "vec", tag = "7")]
+    pub proof: ::prost::alloc::vec::Vec<u8>,
 }
 #[derive(Eq, PartialOrd, Ord, Hash)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -30,10 +38,55 @@ pub struct ApplyBlockRequest {
 pub struct CheckNullifiersRequest {
     #[prost(message, repeated, tag = "1")]
     pub nullifiers: ::prost::alloc::vec::Vec<super::digest::Digest>,
+    /// Block as of which the nullifiers are opened, the chain tip if unset. The proofs are then
+    /// against the nullifier root of its header, which must be at most 256 blocks before the tip.
+    #[prost(fixed32, optional, tag = "2")]
+    pub block_num: ::core::option::Option<u32>,
+}
+/// Returns the nullifiers matching the given prefixes, without revealing the exact nullifiers the
+/// client is interested in.
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CheckNullifiersByPrefixRequest {
+    /// Number of high bits of the nullifiers in each prefix. Shorter prefixes match more nullifiers,
+    /// which increases the client's privacy at the cost of a larger response. Zero defaults to 16.
+    #[prost(uint32, tag = "1")]
+    pub prefix_len: u32,
+    /// The `prefix_len` high bits of the nullifiers the client is interested in.
+    #[prost(uint32, repeated, tag = "2")]
+    pub nullifiers: ::prost::alloc::vec::Vec<u32>,
+    /// Minimum number of confirmations of the returned nullifiers, the block at the chain tip has one
+    /// confirmation. The nullifiers consumed in more recent blocks are omitted. Zero returns them all.
+    #[prost(uint32, tag = "3")]
+    pub min_confirmations: u32,
+}
+/// Returns the nullifiers matching the given prefixes consumed after a block, one page at a time.
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetNullifiersByPrefixRequest {
+    /// Number of high bits of the nullifiers in each prefix. Zero defaults to 16.
+    #[prost(uint32, tag = "1")]
+    pub prefix_len: u32,
+    /// The `prefix_len` high bits of the nullifiers the client is interested in.
+    #[prost(uint32, repeated, tag = "2")]
+    pub nullifiers: ::prost::alloc::vec::Vec<u32>,
+    /// Last block known by the client, the nullifiers consumed in the following blocks are returned.
+    #[prost(fixed32, tag = "3")]
+    pub from_block: u32,
+    /// Page of the nullifiers, ordered by block. The size of the page is the maximum number of
+    /// nullifiers, a block with more matching nullifiers is still returned on its own.
+    #[prost(message, optional, tag = "4")]
+    pub page: ::core::option::Option<super::page::PageRequest>,
 }
 #[derive(Eq, PartialOrd, Ord, Hash)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetChainTipRequest {}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct GetBlockHeaderByNumberRequest {
     /// The block number of the target block.
     ///
@@ -72,9 +125,63 @@ pub struct SyncStateRequest {
     pub note_tags: ::prost::alloc::vec::Vec<u32>,
     /// Determines the nullifiers the client is interested in.
     ///
-    /// Similarly to the note_tags, this determins only the 16high bits of the target nullifier.
+    /// Similarly to the note_tags, this determins only the `nullifier_prefix_len` high bits of the
+    /// target nullifier.
     #[prost(uint32, repeated, tag = "4")]
     pub nullifiers: ::prost::alloc::vec::Vec<u32>,
+    /// Number of high bits of the nullifiers in `nullifiers`. Zero defaults to 16.
+    #[prost(uint32, tag = "5")]
+    pub nullifier_prefix_len: u32,
+    /// Maximum number of blocks with matching notes to include in the response. Zero defaults to a
+    /// single block, and the value is capped by the node.
+    #[prost(uint32, tag = "6")]
+    pub max_blocks: u32,
+    /// Maximum number of notes to include in the response, a block with more matching notes is still
+    /// returned on its own. Zero defaults to the node's cap.
+    #[prost(uint32, tag = "7")]
+    pub max_notes: u32,
+    /// Whether to include the details of the updated public accounts in the response, saving the
+    /// `GetAccountDetails` requests. The node only stores the hash of the private accounts.
+    #[prost(bool, tag = "8")]
+    pub include_account_details: bool,
+    /// Continuation token of a truncated response, to sync the filters left out of it. Empty for a
+    /// new sync. The request must otherwise be identical to the one of the truncated response.
+    #[prost(bytes = "vec", tag = "9")]
+    pub continuation: ::prost::alloc::vec::Vec<u8>,
+    /// Whether to deduplicate the nodes of the notes' Merkle paths. The distinct nodes of each block
+    /// are returned once in the block's `merkle_path_nodes`, and the notes refer to them by index in
+    /// `NoteSyncRecord.merkle_path_nodes` instead of setting `merkle_path`.
+    #[prost(bool, tag = "10")]
+    pub compact_merkle_paths: bool,
+}
+/// Note synchronization request.
+///
+/// Like `SyncStateRequest`, but without the account and nullifier updates, for clients interested
+/// only in notes.
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SyncNotesRequest {
+    /// Last block known by the client. The response will contain data starting from the next block,
+    /// until the first block which contains a note of matching the requested tag, or the chain tip
+    /// if there are no notes.
+    #[prost(fixed32, tag = "1")]
+    pub block_num: u32,
+    /// Determines the tags which the client is interested in. These are only the 16 high bits of the
+    /// note's complete tag.
+    #[prost(uint32, repeated, tag = "2")]
+    pub note_tags: ::prost::alloc::vec::Vec<u32>,
+    /// Use cases of the tags which the client is interested in: the 14 bits following the 2 high bits
+    /// of the tags which don't target a single account.
+    #[prost(uint32, repeated, tag = "3")]
+    pub note_use_cases: ::prost::alloc::vec::Vec<u32>,
+    /// Whether to include the notes whose tag hints at execution by the network.
+    #[prost(bool, tag = "4")]
+    pub network_notes: bool,
+    /// If not empty, only the notes matching the above filters whose `aux` is in one of the ranges
+    /// are included.
+    #[prost(message, repeated, tag = "5")]
+    pub aux_ranges: ::prost::alloc::vec::Vec<super::note::AuxRange>,
 }
 #[derive(Eq, PartialOrd, Ord, Hash)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -103,6 +210,26 @@ pub struct SubmitProvenTransactionRequest {
     /// Transaction encoded using miden's native format
     #[prost(bytes = "vec", tag = "1")]
     pub transaction: ::prost::alloc::vec::Vec<u8>,
+    /// Note created by the transaction paying its fee to the operator of the node, encoded using
+    /// miden's native format. Required if the node charges fees, ignored otherwise.
+    #[prost(bytes = "vec", tag = "2")]
+    pub fee_note: ::prost::alloc::vec::Vec<u8>,
+}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SubmitPriorityTransactionRequest {
+    /// Transaction encoded using miden's native format
+    #[prost(bytes = "vec", tag = "1")]
+    pub transaction: ::prost::alloc::vec::Vec<u8>,
+}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SimulateTransactionRequest {
+    /// Transaction encoded using miden's native format
+    #[prost(bytes = "vec", tag = "1")]
+    pub transaction: ::prost::alloc::vec::Vec<u8>,
 }
 #[derive(Eq, PartialOrd, Ord, Hash)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -111,19 +238,70 @@ pub struct GetNotesByIdRequest {
     /// List of NoteId's to be queried from the database
     #[prost(message, repeated, tag = "1")]
     pub note_ids: ::prost::alloc::vec::Vec<super::digest::Digest>,
+    /// Minimum number of confirmations of the returned notes, the block at the chain tip has one
+    /// confirmation. The notes created in more recent blocks are omitted. Zero returns them all.
+    #[prost(uint32, tag = "2")]
+    pub min_confirmations: u32,
+}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetNoteConsumptionRequest {
+    /// Note whose consumption is returned
+    #[prost(message, optional, tag = "1")]
+    pub note_id: ::core::option::Option<super::digest::Digest>,
 }
 #[derive(Eq, PartialOrd, Ord, Hash)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct ListNullifiersRequest {}
+pub struct ListNullifiersRequest {
+    /// Page of the nullifiers, ordered by nullifier.
+    #[prost(message, optional, tag = "1")]
+    pub page: ::core::option::Option<super::page::PageRequest>,
+}
 #[derive(Eq, PartialOrd, Ord, Hash)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct ListAccountsRequest {}
+pub struct ListAccountsRequest {
+    /// If set, only public (on-chain) accounts are listed when `true`, and only private (off-chain)
+    /// accounts when `false`.
+    #[prost(bool, optional, tag = "1")]
+    pub is_public: ::core::option::Option<bool>,
+    /// If set, only faucet accounts are listed when `true`, and only regular accounts when `false`.
+    #[prost(bool, optional, tag = "2")]
+    pub is_faucet: ::core::option::Option<bool>,
+    /// If set, only accounts updated after the given block are listed.
+    #[prost(fixed32, optional, tag = "3")]
+    pub updated_after_block: ::core::option::Option<u32>,
+    /// Order of the listed accounts:
+    /// - 0: by the block of the latest update, oldest first (default).
+    /// - 1: by the block of the latest update, newest first.
+    /// - 2: by account ID, ascending.
+    /// - 3: by account ID, descending.
+    #[prost(uint32, tag = "4")]
+    pub sort_order: u32,
+    /// Page of the accounts, in the requested order.
+    #[prost(message, optional, tag = "5")]
+    pub page: ::core::option::Option<super::page::PageRequest>,
+}
 #[derive(Eq, PartialOrd, Ord, Hash)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct ListNotesRequest {}
+pub struct ListNotesRequest {
+    /// Page of the notes, ordered by block and position in the block.
+    #[prost(message, optional, tag = "1")]
+    pub page: ::core::option::Option<super::page::PageRequest>,
+    /// If set, only the notes created by the given account are listed.
+    #[prost(message, optional, tag = "2")]
+    pub sender: ::core::option::Option<super::account::AccountId>,
+    /// If set, only the notes created in the given epoch are listed, the blocks
+    /// `[epoch * 2^16, (epoch + 1) * 2^16)`.
+    #[prost(uint32, optional, tag = "3")]
+    pub epoch: ::core::option::Option<u32>,
+    /// If not empty, only the notes whose `aux` is in one of the ranges are listed.
+    #[prost(message, repeated, tag = "4")]
+    pub aux_ranges: ::prost::alloc::vec::Vec<super::note::AuxRange>,
+}
 /// Returns the latest state of an account with the specified ID.
 #[derive(Eq, PartialOrd, Ord, Hash)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -132,4 +310,237 @@ pub struct GetAccountDetailsRequest {
     /// Account ID to get details.
     #[prost(message, optional, tag = "1")]
     pub account_id: ::core::option::Option<super::account::AccountId>,
+    /// Minimum number of confirmations of the account state, the block at the chain tip has one
+    /// confirmation. Fails with `FAILED_PRECONDITION` if the account was updated in a more recent
+    /// block, as its previous states aren't stored. Zero returns the latest state.
+    #[prost(uint32, tag = "2")]
+    pub min_confirmations: u32,
+}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetAccountDeltasRequest {
+    /// Public account whose deltas are returned.
+    #[prost(message, optional, tag = "1")]
+    pub account_id: ::core::option::Option<super::account::AccountId>,
+    /// Block of the account state held by the client, the deltas applied by the later blocks are
+    /// returned.
+    #[prost(fixed32, tag = "2")]
+    pub from_block: u32,
+    /// Last block whose delta is returned, the chain tip if not set or after the chain tip.
+    #[prost(fixed32, optional, tag = "3")]
+    pub to_block: ::core::option::Option<u32>,
+    /// Page of the deltas, ordered by block.
+    #[prost(message, optional, tag = "4")]
+    pub page: ::core::option::Option<super::page::PageRequest>,
+}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetAuditLogRequest {
+    /// If set, only entries recorded at or after the given UNIX timestamp in milliseconds are returned.
+    #[prost(fixed64, optional, tag = "1")]
+    pub since_ms: ::core::option::Option<u64>,
+    /// If set, only entries of the given operation are returned, e.g. `SubmitProvenTransaction`.
+    #[prost(string, optional, tag = "2")]
+    pub operation: ::core::option::Option<::prost::alloc::string::String>,
+    /// Page of the entries, the most recent first.
+    #[prost(message, optional, tag = "3")]
+    pub page: ::core::option::Option<super::page::PageRequest>,
+}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetBlockProductionPausedRequest {
+    /// Whether the production of blocks is paused. While paused, transactions are still accepted and
+    /// batched, they are included in blocks once the production is resumed.
+    #[prost(bool, tag = "1")]
+    pub paused: bool,
+}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetLogFilterRequest {
+    /// Directives of the log filter, in the syntax of the `RUST_LOG` environment variable, e.g.
+    /// `info,miden-store=trace`. Empty restores the filter set on startup.
+    #[prost(string, tag = "1")]
+    pub filter: ::prost::alloc::string::String,
+}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetSubmissionPolicyRequest {
+    /// Policy replacing the current one. Not set restores the policy configured on startup.
+    #[prost(message, optional, tag = "1")]
+    pub policy: ::core::option::Option<super::transaction::SubmissionPolicy>,
+}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetMaintenanceModeRequest {
+    /// Whether the node is in maintenance mode. While in maintenance mode, the block production is
+    /// paused and the client requests are rejected.
+    #[prost(bool, tag = "1")]
+    pub enabled: bool,
+    /// Number of seconds after which the clients should retry their requests, sent in the
+    /// `retry-after` header of the rejected requests. Zero defaults to 60 seconds.
+    #[prost(uint32, tag = "2")]
+    pub retry_after_s: u32,
+}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetUsageRequest {}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FlushDatabaseRequest {}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetTransactionReceiptRequest {
+    #[prost(message, optional, tag = "1")]
+    pub transaction_id: ::core::option::Option<super::digest::Digest>,
+    /// Minimum number of confirmations of the transaction, the block at the chain tip has one
+    /// confirmation. A transaction included in a more recent block is reported as not found. Zero
+    /// returns the receipt as soon as the transaction is included.
+    #[prost(uint32, tag = "2")]
+    pub min_confirmations: u32,
+}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SubscribeBlocksRequest {
+    /// Number of the first block to stream, the blocks already applied are streamed before the new
+    /// blocks
+    #[prost(fixed32, tag = "1")]
+    pub block_num: u32,
+}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetAccountsByPubKeyRequest {
+    /// Authentication public key, as stored in the storage slot 0 of the accounts
+    #[prost(message, optional, tag = "1")]
+    pub public_key: ::core::option::Option<super::digest::Digest>,
+}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetMempoolStatsRequest {}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetMempoolContentsRequest {
+    /// Page of the pending transactions, the oldest first.
+    #[prost(message, optional, tag = "1")]
+    pub page: ::core::option::Option<super::page::PageRequest>,
+}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetNoteScriptByRootRequest {
+    /// Root of the requested note script
+    #[prost(message, optional, tag = "1")]
+    pub script_root: ::core::option::Option<super::digest::Digest>,
+}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SubscribeAccountsRequest {
+    /// Accounts whose updates are streamed
+    #[prost(message, repeated, tag = "1")]
+    pub account_ids: ::prost::alloc::vec::Vec<super::account::AccountId>,
+}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetBlockInclusionProofRequest {
+    /// Number of the block to prove
+    #[prost(uint32, tag = "1")]
+    pub block_num: u32,
+    /// Number of the block whose chain root the proof is against, the latest block if not set. The
+    /// chain root of a block commits to all the blocks before it, so it must be greater than
+    /// `block_num`.
+    #[prost(uint32, optional, tag = "2")]
+    pub at_chain_tip: ::core::option::Option<u32>,
+}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetBlockProofRequest {
+    /// Number of the block whose validity proof is requested
+    #[prost(uint32, tag = "1")]
+    pub block_num: u32,
+}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetNoteAuthenticationInfoRequest {
+    /// Number of the block which created the notes
+    #[prost(uint32, tag = "1")]
+    pub block_num: u32,
+    /// Indexes of the notes in the note tree of the block, as in `note.Note.note_index`
+    #[prost(uint32, repeated, tag = "2")]
+    pub note_indexes: ::prost::alloc::vec::Vec<u32>,
+}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetBlockTimelineRequest {
+    /// Number of the block, the latest block whose production started if unset
+    #[prost(fixed32, optional, tag = "1")]
+    pub block_num: ::core::option::Option<u32>,
+}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetBatchRequest {
+    /// Id of the batch, the 32 bytes logged by the block producer as `batch_id`
+    #[prost(bytes = "vec", tag = "1")]
+    pub batch_id: ::prost::alloc::vec::Vec<u8>,
+}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RequestTestnetFundsRequest {
+    /// Account receiving the funds
+    #[prost(message, optional, tag = "1")]
+    pub account_id: ::core::option::Option<super::account::AccountId>,
+    /// Proof that the request was made by a person, e.g. a captcha response, checked by the
+    /// operator's verification webhook
+    #[prost(string, tag = "2")]
+    pub verification_token: ::prost::alloc::string::String,
 }
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetStoreStatusRequest {}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetBlockProducerStatusRequest {}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetNodeStatusRequest {}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetVersionRequest {}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetChainParametersRequest {}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetGenesisRequest {}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetDatabaseSchemaRequest {}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetSerializationVectorsRequest {}
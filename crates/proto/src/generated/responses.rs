@@ -2,7 +2,12 @@
 #[derive(Eq, PartialOrd, Ord, Hash)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct ApplyBlockResponse {}
+pub struct ApplyBlockResponse {
+    /// Set if the block was applied before, e.g. by a request whose response was lost, and wasn't
+    /// written again.
+    #[prost(bool, tag = "1")]
+    pub already_applied: bool,
+}
 #[derive(Eq, PartialOrd, Ord, Hash)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -10,6 +15,21 @@ pub struct CheckNullifiersResponse {
     /// Each requested nullifier has its corresponding nullifier proof at the same position.
     #[prost(message, repeated, tag = "1")]
     pub proofs: ::prost::alloc::vec::Vec<super::smt::SmtOpening>,
+    /// Timestamp of the block which consumed each requested nullifier, at the same position, zero
+    /// if the nullifier isn't consumed.
+    #[prost(fixed64, repeated, tag = "2")]
+    pub block_timestamps: ::prost::alloc::vec::Vec<u64>,
+}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetChainTipResponse {
+    /// Number of the latest block in the chain.
+    #[prost(fixed32, tag = "1")]
+    pub block_num: u32,
+    /// Hash of the latest block in the chain.
+    #[prost(message, optional, tag = "2")]
+    pub block_hash: ::core::option::Option<super::digest::Digest>,
 }
 #[derive(Eq, PartialOrd, Ord, Hash)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -17,6 +37,10 @@ pub struct CheckNullifiersResponse {
 pub struct GetBlockHeaderByNumberResponse {
     #[prost(message, optional, tag = "1")]
     pub block_header: ::core::option::Option<super::block_header::BlockHeader>,
+    /// Signature of the block header's hash by the sequencer, empty if the block is not signed, e.g.
+    /// the genesis block.
+    #[prost(bytes = "vec", tag = "2")]
+    pub signature: ::prost::alloc::vec::Vec<u8>,
 }
 #[derive(Eq, PartialOrd, Ord, Hash)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -24,8 +48,34 @@ pub struct GetBlockHeaderByNumberResponse {
 pub struct NullifierUpdate {
     #[prost(message, optional, tag = "1")]
     pub nullifier: ::core::option::Option<super::digest::Digest>,
+    /// Block in which the nullifier was consumed
+    #[prost(fixed32, tag = "2")]
+    pub block_num: u32,
+    /// Timestamp of the block in which the nullifier was consumed
+    #[prost(fixed64, tag = "3")]
+    pub block_timestamp: u64,
+}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CheckNullifiersByPrefixResponse {
+    /// The nullifiers matching the requested prefixes, with the block in which they were consumed.
+    #[prost(message, repeated, tag = "1")]
+    pub nullifiers: ::prost::alloc::vec::Vec<NullifierUpdate>,
+}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetNullifiersByPrefixResponse {
+    /// The nullifiers matching the requested prefixes in the blocks of the page, ordered by block.
+    #[prost(message, repeated, tag = "1")]
+    pub nullifiers: ::prost::alloc::vec::Vec<NullifierUpdate>,
+    /// Last block of the page, the next page starts after it.
     #[prost(fixed32, tag = "2")]
     pub block_num: u32,
+    /// Position of the next page. The last page ends at the chain tip of the first page's request.
+    #[prost(message, optional, tag = "3")]
+    pub page: ::core::option::Option<super::page::Page>,
 }
 #[derive(Eq, PartialOrd, Ord, Hash)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -49,6 +99,59 @@ pub struct SyncStateResponse {
     /// a list of nullifiers created between `block_num + 1` and `block_header.block_num`
     #[prost(message, repeated, tag = "7")]
     pub nullifiers: ::prost::alloc::vec::Vec<NullifierUpdate>,
+    /// blocks with matching notes before `block_header`, when the request allows several blocks
+    #[prost(message, repeated, tag = "8")]
+    pub preceding_blocks: ::prost::alloc::vec::Vec<SyncedBlock>,
+    /// the latest details of the public accounts of `accounts`, if requested
+    #[prost(message, repeated, tag = "9")]
+    pub account_details: ::prost::alloc::vec::Vec<super::account::AccountInfo>,
+    /// set if some of the request's account ids, note tags or nullifier prefixes were left out, as
+    /// they exceed the node's caps. The same request with `continuation` syncs the next ones over
+    /// the same block range.
+    #[prost(bool, tag = "10")]
+    pub truncated: bool,
+    /// continuation token of a truncated response, empty otherwise
+    #[prost(bytes = "vec", tag = "11")]
+    pub continuation: ::prost::alloc::vec::Vec<u8>,
+    /// distinct nodes of the Merkle paths of `notes`, if the request asked for compact Merkle paths
+    #[prost(message, repeated, tag = "12")]
+    pub merkle_path_nodes: ::prost::alloc::vec::Vec<super::digest::Digest>,
+}
+/// A block with notes matching a state sync request, other than the last block of the response.
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SyncedBlock {
+    #[prost(message, optional, tag = "1")]
+    pub block_header: ::core::option::Option<super::block_header::BlockHeader>,
+    /// path authenticating the block in the chain MMR updated by the response's `mmr_delta`, i.e. the
+    /// MMR with `block_header.block_num` leaves of the response
+    #[prost(message, optional, tag = "2")]
+    pub mmr_path: ::core::option::Option<super::merkle::MerklePath>,
+    /// a list of all notes together with the Merkle paths from `block_header.note_root`
+    #[prost(message, repeated, tag = "3")]
+    pub notes: ::prost::alloc::vec::Vec<super::note::NoteSyncRecord>,
+    /// distinct nodes of the Merkle paths of `notes`, if the request asked for compact Merkle paths
+    #[prost(message, repeated, tag = "4")]
+    pub merkle_path_nodes: ::prost::alloc::vec::Vec<super::digest::Digest>,
+}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SyncNotesResponse {
+    /// number of the latest block in the chain
+    #[prost(fixed32, tag = "1")]
+    pub chain_tip: u32,
+    /// block header of the block with the first note matching the specified criteria
+    #[prost(message, optional, tag = "2")]
+    pub block_header: ::core::option::Option<super::block_header::BlockHeader>,
+    /// Merkle path to verify the block's inclusion in the chain MMR at `chain_tip + 1` leaves, i.e.
+    /// the MMR committing to all the blocks up to and including the chain tip.
+    #[prost(message, optional, tag = "3")]
+    pub mmr_path: ::core::option::Option<super::merkle::MerklePath>,
+    /// a list of all notes together with the Merkle paths from `block_header.note_root`
+    #[prost(message, repeated, tag = "4")]
+    pub notes: ::prost::alloc::vec::Vec<super::note::NoteSyncRecord>,
 }
 /// An account returned as a response to the GetBlockInputs
 #[derive(Eq, PartialOrd, Ord, Hash)]
@@ -127,34 +230,86 @@ pub struct SubmitProvenTransactionResponse {}
 #[derive(Eq, PartialOrd, Ord, Hash)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SubmitPriorityTransactionResponse {}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SimulateTransactionResponse {
+    /// The state of the transaction's account in the store.
+    #[prost(message, optional, tag = "1")]
+    pub account_state: ::core::option::Option<AccountTransactionInputRecord>,
+    /// The nullifiers of the transaction's input notes, with the block at which they were consumed.
+    #[prost(message, repeated, tag = "2")]
+    pub nullifiers: ::prost::alloc::vec::Vec<NullifierTransactionInputRecord>,
+    /// The reason the transaction would be rejected, not set if it would be accepted.
+    #[prost(message, optional, tag = "3")]
+    pub rejection: ::core::option::Option<super::transaction::TransactionRejection>,
+}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct GetNotesByIdResponse {
     /// Lists Note's returned by the database
     #[prost(message, repeated, tag = "1")]
     pub notes: ::prost::alloc::vec::Vec<super::note::Note>,
 }
+/// Consumption of a note, linking its id to its nullifier.
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct NoteConsumption {
+    #[prost(message, optional, tag = "1")]
+    pub note_id: ::core::option::Option<super::digest::Digest>,
+    #[prost(message, optional, tag = "2")]
+    pub nullifier: ::core::option::Option<super::digest::Digest>,
+    /// Block which consumed the note
+    #[prost(fixed32, tag = "3")]
+    pub block_num: u32,
+    /// Transaction which consumed the note, not set if the receipts of the block weren't stored
+    #[prost(message, optional, tag = "4")]
+    pub transaction_id: ::core::option::Option<super::digest::Digest>,
+}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetNoteConsumptionResponse {
+    /// Not set if the note isn't consumed, or if its nullifier isn't known to the node, e.g. for the
+    /// private notes
+    #[prost(message, optional, tag = "1")]
+    pub consumption: ::core::option::Option<NoteConsumption>,
+}
 #[derive(Eq, PartialOrd, Ord, Hash)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ListNullifiersResponse {
-    /// Lists all nullifiers of the current chain
+    /// The nullifiers of the page, ordered by nullifier
     #[prost(message, repeated, tag = "1")]
     pub nullifiers: ::prost::alloc::vec::Vec<super::smt::SmtLeafEntry>,
+    /// Position of the next page
+    #[prost(message, optional, tag = "2")]
+    pub page: ::core::option::Option<super::page::Page>,
 }
 #[derive(Eq, PartialOrd, Ord, Hash)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ListAccountsResponse {
-    /// Lists all accounts of the current chain
+    /// The accounts of the page, in the requested order
     #[prost(message, repeated, tag = "1")]
     pub accounts: ::prost::alloc::vec::Vec<super::account::AccountInfo>,
+    /// Position of the next page
+    #[prost(message, optional, tag = "2")]
+    pub page: ::core::option::Option<super::page::Page>,
 }
 #[derive(Eq, PartialOrd, Ord, Hash)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ListNotesResponse {
-    /// Lists all notes of the current chain
+    /// The notes of the page, ordered by block and position in the block
     #[prost(message, repeated, tag = "1")]
     pub notes: ::prost::alloc::vec::Vec<super::note::Note>,
+    /// Position of the next page
+    #[prost(message, optional, tag = "2")]
+    pub page: ::core::option::Option<super::page::Page>,
 }
 #[derive(Eq, PartialOrd, Ord, Hash)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -164,3 +319,625 @@ pub struct GetAccountDetailsResponse {
     #[prost(message, optional, tag = "1")]
     pub account: ::core::option::Option<super::account::AccountInfo>,
 }
+/// Delta applied to a public account by a block.
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AccountDeltaUpdate {
+    /// Block which applied the delta
+    #[prost(fixed32, tag = "1")]
+    pub block_num: u32,
+    /// Serialized `AccountDelta`
+    #[prost(bytes = "vec", tag = "2")]
+    pub delta: ::prost::alloc::vec::Vec<u8>,
+    /// Hash of the account state after the delta
+    #[prost(message, optional, tag = "3")]
+    pub account_hash: ::core::option::Option<super::digest::Digest>,
+}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetAccountDeltasResponse {
+    /// Deltas applied to the account after `from_block`, ordered by block
+    #[prost(message, repeated, tag = "1")]
+    pub deltas: ::prost::alloc::vec::Vec<AccountDeltaUpdate>,
+    /// Last block covered by the page, the next page starts after it
+    #[prost(fixed32, tag = "2")]
+    pub block_num: u32,
+    /// Position of the next page. The last page ends at `to_block`, capped to the chain tip of the
+    /// first page's request
+    #[prost(message, optional, tag = "3")]
+    pub page: ::core::option::Option<super::page::Page>,
+}
+/// An operation recorded in the audit log.
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AuditLogEntry {
+    /// UNIX timestamp in milliseconds at which the operation was served.
+    #[prost(fixed64, tag = "1")]
+    pub timestamp_ms: u64,
+    /// Name of the gRPC method, e.g. `SubmitProvenTransaction`.
+    #[prost(string, tag = "2")]
+    pub operation: ::prost::alloc::string::String,
+    /// Address of the peer which requested the operation, if known.
+    #[prost(string, optional, tag = "3")]
+    pub peer: ::core::option::Option<::prost::alloc::string::String>,
+    /// Id of the transaction the operation refers to, if any.
+    #[prost(message, optional, tag = "4")]
+    pub tx_id: ::core::option::Option<super::digest::Digest>,
+    /// Error message if the operation failed, not set if it succeeded.
+    #[prost(string, optional, tag = "5")]
+    pub error: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetAuditLogResponse {
+    /// Matching entries, the most recent first.
+    #[prost(message, repeated, tag = "1")]
+    pub entries: ::prost::alloc::vec::Vec<AuditLogEntry>,
+    /// Position of the next page.
+    #[prost(message, optional, tag = "2")]
+    pub page: ::core::option::Option<super::page::Page>,
+}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetBlockProductionPausedResponse {}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetMaintenanceModeResponse {}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetSubmissionPolicyResponse {
+    /// Replaced policy, to restore it later.
+    #[prost(message, optional, tag = "1")]
+    pub previous_policy: ::core::option::Option<super::transaction::SubmissionPolicy>,
+}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetLogFilterResponse {
+    /// Directives of the replaced log filter, to restore it later.
+    #[prost(string, tag = "1")]
+    pub previous_filter: ::prost::alloc::string::String,
+}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ClientUsage {
+    /// Client the usage is accounted to, the first characters of its API key, or `anonymous` for the
+    /// clients without a known API key.
+    #[prost(string, tag = "1")]
+    pub client: ::prost::alloc::string::String,
+    /// Number of requests served.
+    #[prost(fixed64, tag = "2")]
+    pub requests: u64,
+    /// Number of rows returned, e.g. notes, nullifiers and accounts, by the endpoints querying them.
+    #[prost(fixed64, tag = "3")]
+    pub rows: u64,
+    /// Number of bytes sent in the responses, after compression.
+    #[prost(fixed64, tag = "4")]
+    pub bytes_sent: u64,
+    /// Total cost of the requests served, as configured per endpoint.
+    #[prost(fixed64, tag = "5")]
+    pub cost: u64,
+}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetUsageResponse {
+    /// UNIX timestamp in milliseconds since which the usage is accounted, the start of the RPC.
+    #[prost(fixed64, tag = "1")]
+    pub since_ms: u64,
+    /// Usage of each client, ordered by client.
+    #[prost(message, repeated, tag = "2")]
+    pub clients: ::prost::alloc::vec::Vec<ClientUsage>,
+}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FlushDatabaseResponse {}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetTransactionReceiptResponse {
+    /// The transaction, with the block which includes it
+    #[prost(message, optional, tag = "1")]
+    pub receipt: ::core::option::Option<super::transaction::TransactionReceipt>,
+}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SubscribeBlocksResponse {
+    /// Number of the streamed block
+    #[prost(fixed32, tag = "1")]
+    pub block_num: u32,
+    /// The block, as an encoded `ApplyBlockRequest`
+    #[prost(bytes = "vec", tag = "2")]
+    pub block: ::prost::alloc::vec::Vec<u8>,
+}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetAccountsByPubKeyResponse {
+    /// Public accounts authenticated by the requested key
+    #[prost(message, repeated, tag = "1")]
+    pub account_ids: ::prost::alloc::vec::Vec<super::account::AccountId>,
+}
+/// Number of transactions of an account waiting to be included in a block.
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AccountPendingTransactions {
+    #[prost(message, optional, tag = "1")]
+    pub account_id: ::core::option::Option<super::account::AccountId>,
+    #[prost(uint32, tag = "2")]
+    pub num_transactions: u32,
+}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetMempoolStatsResponse {
+    /// Number of transactions waiting to be batched
+    #[prost(uint32, tag = "1")]
+    pub queued_transactions: u32,
+    /// Number of transactions in batches not yet included in a block
+    #[prost(uint32, tag = "2")]
+    pub batched_transactions: u32,
+    /// Number of batches being proven or waiting to be included in a block
+    #[prost(uint32, tag = "3")]
+    pub in_flight_batches: u32,
+    /// Time elapsed since the oldest pending transaction was accepted, zero if there is none
+    #[prost(uint64, tag = "4")]
+    pub oldest_transaction_age_ms: u64,
+    /// Pending transactions per account, in ascending account id order
+    #[prost(message, repeated, tag = "5")]
+    pub accounts: ::prost::alloc::vec::Vec<AccountPendingTransactions>,
+}
+/// A transaction accepted by the block producer and not yet included in a block.
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PendingTransaction {
+    #[prost(message, optional, tag = "1")]
+    pub transaction_id: ::core::option::Option<super::digest::Digest>,
+    #[prost(message, optional, tag = "2")]
+    pub account_id: ::core::option::Option<super::account::AccountId>,
+    /// Nullifiers of the notes consumed by the transaction
+    #[prost(message, repeated, tag = "3")]
+    pub nullifiers: ::prost::alloc::vec::Vec<super::digest::Digest>,
+    /// Time elapsed since the transaction was accepted
+    #[prost(uint64, tag = "4")]
+    pub age_ms: u64,
+    /// Whether the transaction is part of a batch, otherwise it is waiting to be batched
+    #[prost(bool, tag = "5")]
+    pub batched: bool,
+    /// Whether the transaction was submitted by the operator in the priority lane
+    #[prost(bool, tag = "6")]
+    pub priority: bool,
+}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetMempoolContentsResponse {
+    /// Pending transactions, the oldest first
+    #[prost(message, repeated, tag = "1")]
+    pub transactions: ::prost::alloc::vec::Vec<PendingTransaction>,
+    /// Position of the next page
+    #[prost(message, optional, tag = "2")]
+    pub page: ::core::option::Option<super::page::Page>,
+}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetNoteScriptByRootResponse {
+    /// The `NoteScript`, encoded using Miden's native format
+    #[prost(bytes = "vec", tag = "1")]
+    pub script: ::prost::alloc::vec::Vec<u8>,
+}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SubscribeAccountsResponse {
+    /// New state of a subscribed account, updated by the block `summary.block_num`
+    #[prost(message, optional, tag = "1")]
+    pub summary: ::core::option::Option<super::account::AccountSummary>,
+}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetBlockInclusionProofResponse {
+    /// Header of the proven block
+    #[prost(message, optional, tag = "1")]
+    pub block_header: ::core::option::Option<super::block_header::BlockHeader>,
+    /// Header of the block whose chain root commits to `block_header`
+    #[prost(message, optional, tag = "2")]
+    pub chain_tip: ::core::option::Option<super::block_header::BlockHeader>,
+    /// Path from the hash of `block_header` to one of `mmr_peaks`
+    #[prost(message, optional, tag = "3")]
+    pub mmr_path: ::core::option::Option<super::merkle::MerklePath>,
+    /// Peaks of the chain MMR with `chain_tip.block_num` leaves, hashing to `chain_tip.chain_root`
+    #[prost(message, repeated, tag = "4")]
+    pub mmr_peaks: ::prost::alloc::vec::Vec<super::digest::Digest>,
+}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetBlockProofResponse {
+    /// Header of the block, the public inputs of the proof
+    #[prost(message, optional, tag = "1")]
+    pub block_header: ::core::option::Option<super::block_header::BlockHeader>,
+    /// Validity proof of the block, not set if the block is not proven or its proof was pruned
+    #[prost(bytes = "vec", optional, tag = "2")]
+    pub proof: ::core::option::Option<::prost::alloc::vec::Vec<u8>>,
+}
+/// Authentication path of a note in the note tree of its block.
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct NoteAuthenticationInfo {
+    /// Index of the note in the note tree of the block
+    #[prost(uint32, tag = "1")]
+    pub note_index: u32,
+    #[prost(message, optional, tag = "2")]
+    pub note_id: ::core::option::Option<super::digest::Digest>,
+    /// Path from the note's leaf to the note root of the block
+    #[prost(message, optional, tag = "3")]
+    pub merkle_path: ::core::option::Option<super::merkle::MerklePath>,
+}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetNoteAuthenticationInfoResponse {
+    /// Header of the block which created the notes, its `note_root` authenticates the notes
+    #[prost(message, optional, tag = "1")]
+    pub block_header: ::core::option::Option<super::block_header::BlockHeader>,
+    /// Authentication paths of the requested notes, in the order of the request
+    #[prost(message, repeated, tag = "2")]
+    pub notes: ::prost::alloc::vec::Vec<NoteAuthenticationInfo>,
+}
+/// A transaction of a batch, with the update of its account.
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BatchTransaction {
+    #[prost(message, optional, tag = "1")]
+    pub transaction_id: ::core::option::Option<super::digest::Digest>,
+    #[prost(message, optional, tag = "2")]
+    pub account_id: ::core::option::Option<super::account::AccountId>,
+    /// Hash of the account state before the transaction
+    #[prost(message, optional, tag = "3")]
+    pub initial_account_hash: ::core::option::Option<super::digest::Digest>,
+    /// Hash of the account state after the transaction
+    #[prost(message, optional, tag = "4")]
+    pub final_account_hash: ::core::option::Option<super::digest::Digest>,
+    /// Number of notes created by the transaction
+    #[prost(uint32, tag = "5")]
+    pub num_output_notes: u32,
+    /// Number of notes consumed by the transaction
+    #[prost(uint32, tag = "6")]
+    pub num_input_notes: u32,
+}
+/// Stage reached by a block while it was produced.
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BlockTimelineEvent {
+    /// The stage: `batches_selected`, `inputs_fetched`, `block_built`, `proof_done`, `committed`,
+    /// or `failed` if the production of the block failed and is attempted again
+    #[prost(string, tag = "1")]
+    pub stage: ::prost::alloc::string::String,
+    /// Time the stage was reached, in milliseconds since the UNIX epoch
+    #[prost(fixed64, tag = "2")]
+    pub timestamp_ms: u64,
+    /// Reason of the failure, only set for the `failed` stage
+    #[prost(string, optional, tag = "3")]
+    pub error: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetBlockTimelineResponse {
+    #[prost(fixed32, tag = "1")]
+    pub block_num: u32,
+    /// Number of batches and transactions of the block, as of its last attempt
+    #[prost(uint32, tag = "2")]
+    pub num_batches: u32,
+    #[prost(uint32, tag = "3")]
+    pub num_transactions: u32,
+    /// Stages reached by the block, in order, including the failed attempts to produce it
+    #[prost(message, repeated, tag = "4")]
+    pub events: ::prost::alloc::vec::Vec<BlockTimelineEvent>,
+}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetBatchResponse {
+    /// Whether the batch is proven and waiting to be included in a block, otherwise it is being
+    /// proven or waiting for a proving slot
+    #[prost(bool, tag = "1")]
+    pub proven: bool,
+    /// Transactions of the batch, in the order they were batched
+    #[prost(message, repeated, tag = "2")]
+    pub transactions: ::prost::alloc::vec::Vec<BatchTransaction>,
+    /// Root of the tree of the notes created by the batch, set once the batch is proven
+    #[prost(message, optional, tag = "3")]
+    pub note_root: ::core::option::Option<super::digest::Digest>,
+    /// Number of batches dispatched before this one and not yet included in a block, the batch is
+    /// included in a block once all of them are
+    #[prost(uint32, tag = "4")]
+    pub batches_ahead: u32,
+}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RequestTestnetFundsResponse {
+    /// Transaction minting the funds, submitted to the block producer
+    #[prost(message, optional, tag = "1")]
+    pub transaction_id: ::core::option::Option<super::digest::Digest>,
+    /// Public P2ID note paying the funds to the account, which can consume it once the transaction
+    /// is included in a block
+    #[prost(message, optional, tag = "2")]
+    pub note_id: ::core::option::Option<super::digest::Digest>,
+}
+/// Progress of a replica store applying the blocks of the node it follows.
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FollowerStatus {
+    /// Chain tip of the followed node, as last seen by the replica
+    #[prost(fixed32, tag = "1")]
+    pub upstream_chain_tip: u32,
+    /// Number of blocks of the followed node not yet applied by the replica
+    #[prost(fixed32, tag = "2")]
+    pub sync_lag: u32,
+    /// Whether the replica is currently subscribed to the followed node's blocks
+    #[prost(bool, tag = "3")]
+    pub connected: bool,
+}
+/// Sample of the metrics of the tokio runtime of a component, see `miden_node_utils::runtime`.
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RuntimeMetrics {
+    /// Number of worker threads
+    #[prost(uint32, tag = "1")]
+    pub workers: u32,
+    /// Percentage of the time the workers were busy since the previous sample
+    #[prost(uint32, tag = "2")]
+    pub worker_utilization_percent: u32,
+    /// Number of tasks waiting in the global queue
+    #[prost(uint64, tag = "3")]
+    pub global_queue_depth: u64,
+    /// Number of tasks waiting in the local queues of the workers
+    #[prost(uint64, tag = "4")]
+    pub local_queue_depth: u64,
+    /// Number of blocking threads running a blocking task
+    #[prost(uint32, tag = "5")]
+    pub busy_blocking_threads: u32,
+    /// Number of blocking threads waiting for a blocking task
+    #[prost(uint32, tag = "6")]
+    pub idle_blocking_threads: u32,
+    /// Number of blocking tasks waiting for a blocking thread
+    #[prost(uint64, tag = "7")]
+    pub blocking_queue_depth: u64,
+    /// Number of tasks alive
+    #[prost(uint64, tag = "8")]
+    pub active_tasks: u64,
+}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetStoreStatusResponse {
+    /// Version of the store
+    #[prost(string, tag = "1")]
+    pub version: ::prost::alloc::string::String,
+    /// Hash of the genesis block
+    #[prost(message, optional, tag = "2")]
+    pub genesis_hash: ::core::option::Option<super::digest::Digest>,
+    /// Number of the latest block
+    #[prost(fixed32, tag = "3")]
+    pub chain_tip: u32,
+    /// Timestamp of the latest block
+    #[prost(fixed64, tag = "4")]
+    pub last_block_timestamp: u64,
+    /// Size of the database file, in bytes
+    #[prost(uint64, tag = "5")]
+    pub database_size_bytes: u64,
+    /// Set if the store is a replica
+    #[prost(message, optional, tag = "6")]
+    pub follower: ::core::option::Option<FollowerStatus>,
+    /// Total fees paid by the transactions of the chain, per faucet
+    #[prost(message, repeated, tag = "7")]
+    pub collected_fees: ::prost::alloc::vec::Vec<super::transaction::TransactionFee>,
+    /// Metrics of the store's runtime, not set if the store isn't built with `tokio_unstable`
+    #[prost(message, optional, tag = "8")]
+    pub runtime: ::core::option::Option<RuntimeMetrics>,
+}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetBlockProducerStatusResponse {
+    /// Version of the block producer
+    #[prost(string, tag = "1")]
+    pub version: ::prost::alloc::string::String,
+    /// Number of transactions accepted and not yet included in a block
+    #[prost(uint32, tag = "2")]
+    pub mempool_depth: u32,
+    /// Number of batches being proven or waiting to be included in a block
+    #[prost(uint32, tag = "3")]
+    pub in_flight_batches: u32,
+    /// Whether the production of blocks is paused
+    #[prost(bool, tag = "4")]
+    pub block_production_paused: bool,
+    /// Metrics of the block producer's runtime, not set if it isn't built with `tokio_unstable`
+    #[prost(message, optional, tag = "5")]
+    pub runtime: ::core::option::Option<RuntimeMetrics>,
+    /// Whether the block producer runs in simulation mode, without verifying the transaction proofs
+    #[prost(bool, tag = "6")]
+    pub simulation: bool,
+    /// Estimated VM cycles of the transactions of the last block produced
+    #[prost(fixed64, tag = "7")]
+    pub last_block_cycles: u64,
+    /// Maximum estimated VM cycles of the transactions of a block, 0 if the blocks aren't metered
+    #[prost(fixed64, tag = "8")]
+    pub block_cycle_budget: u64,
+    /// Number of transactions the batches are currently sealed at, adjusted to the load if the batch
+    /// sealing policy is adaptive
+    #[prost(uint32, tag = "9")]
+    pub target_batch_size: u32,
+    /// Moving average of the time a batch takes to be proven, in milliseconds
+    #[prost(fixed64, tag = "10")]
+    pub batch_proving_latency_ms: u64,
+    /// Estimated time from the acceptance of a transaction to the commit of its block, in
+    /// milliseconds
+    #[prost(fixed64, tag = "11")]
+    pub confirmation_latency_ms: u64,
+}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetNodeStatusResponse {
+    /// Version of the RPC
+    #[prost(string, tag = "1")]
+    pub version: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "2")]
+    pub store: ::core::option::Option<GetStoreStatusResponse>,
+    /// Not set if the block producer can't be reached, e.g. on replicas
+    #[prost(message, optional, tag = "3")]
+    pub block_producer: ::core::option::Option<GetBlockProducerStatusResponse>,
+    /// Metrics of the RPC's runtime, not set if the RPC isn't built with `tokio_unstable`
+    #[prost(message, optional, tag = "4")]
+    pub runtime: ::core::option::Option<RuntimeMetrics>,
+}
+/// Version of the Miden protocol, components only work together if their major versions match
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ProtocolVersion {
+    #[prost(uint32, tag = "1")]
+    pub major: u32,
+    #[prost(uint32, tag = "2")]
+    pub minor: u32,
+}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetVersionResponse {
+    /// Name of the component, e.g. `miden-store`
+    #[prost(string, tag = "1")]
+    pub component: ::prost::alloc::string::String,
+    /// Version of the component
+    #[prost(string, tag = "2")]
+    pub version: ::prost::alloc::string::String,
+    /// Version of the Miden protocol implemented by the component
+    #[prost(message, optional, tag = "3")]
+    pub protocol_version: ::core::option::Option<ProtocolVersion>,
+    /// Version of the gRPC API of the component
+    #[prost(string, tag = "4")]
+    pub proto_version: ::prost::alloc::string::String,
+    /// Hash of the genesis block, identifying the chain. Not set by the block producer
+    #[prost(message, optional, tag = "5")]
+    pub genesis_hash: ::core::option::Option<super::digest::Digest>,
+    /// Build options of the component, e.g. `tracing-forest`
+    #[prost(string, repeated, tag = "6")]
+    pub features: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// Set by the RPC: versions of the store and of the block producer, the latter missing if it
+    /// can't be reached
+    #[prost(message, repeated, tag = "7")]
+    pub components: ::prost::alloc::vec::Vec<GetVersionResponse>,
+}
+/// Fee charged for each transaction, paid by a pay-to-id note sending at least `min_amount` of the
+/// fungible asset of `faucet_id` to `operator`
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ChainFees {
+    #[prost(message, optional, tag = "1")]
+    pub operator: ::core::option::Option<super::account::AccountId>,
+    #[prost(message, optional, tag = "2")]
+    pub faucet_id: ::core::option::Option<super::account::AccountId>,
+    #[prost(fixed64, tag = "3")]
+    pub min_amount: u64,
+}
+/// Parameters of a chain chosen at genesis, committed to by the genesis block header
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ChainParameters {
+    #[prost(fixed32, tag = "1")]
+    pub chain_id: u32,
+    /// Maximum number of transactions in a batch
+    #[prost(uint32, tag = "2")]
+    pub max_batch_size: u32,
+    /// Depth of the note tree of the blocks
+    #[prost(uint32, tag = "3")]
+    pub note_tree_depth: u32,
+    /// Not set if the chain doesn't charge fees
+    #[prost(message, optional, tag = "4")]
+    pub fees: ::core::option::Option<ChainFees>,
+}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetChainParametersResponse {
+    /// Not set if the genesis file was created before the chain parameters existed
+    #[prost(message, optional, tag = "1")]
+    pub parameters: ::core::option::Option<ChainParameters>,
+}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetGenesisResponse {
+    /// Header of the genesis block, whose `proof_hash` commits to the parameters
+    #[prost(message, optional, tag = "1")]
+    pub header: ::core::option::Option<super::block_header::BlockHeader>,
+    /// Not set if the genesis file was created before the chain parameters existed
+    #[prost(message, optional, tag = "2")]
+    pub parameters: ::core::option::Option<ChainParameters>,
+}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetDatabaseSchemaResponse {
+    /// Schema of the database of the store
+    #[prost(message, optional, tag = "1")]
+    pub schema: ::core::option::Option<super::schema::DatabaseSchema>,
+}
+/// Canonical encoding of a value of the protocol, for client implementations to test their
+/// decoders against
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SerializationVector {
+    /// Name of the vector, e.g. `block_header`
+    #[prost(string, tag = "1")]
+    pub name: ::prost::alloc::string::String,
+    /// Encoded type: a protobuf message, e.g. `block_header.BlockHeader`, or a type of the Miden
+    /// protocol, e.g. `BlockHeader`
+    #[prost(string, tag = "2")]
+    pub type_name: ::prost::alloc::string::String,
+    /// Encoding of `data`: `protobuf`, or `miden` for the protocol's own serialization
+    #[prost(string, tag = "3")]
+    pub encoding: ::prost::alloc::string::String,
+    #[prost(bytes = "vec", tag = "4")]
+    pub data: ::prost::alloc::vec::Vec<u8>,
+    /// Commitment to the encoded value, e.g. the block hash or the note id, to check the decoded
+    /// value against. Not set for the responses
+    #[prost(message, optional, tag = "5")]
+    pub commitment: ::core::option::Option<super::digest::Digest>,
+}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetSerializationVectorsResponse {
+    /// Version of the Miden protocol the vectors are encoded for
+    #[prost(message, optional, tag = "1")]
+    pub protocol_version: ::core::option::Option<ProtocolVersion>,
+    #[prost(message, repeated, tag = "2")]
+    pub vectors: ::prost::alloc::vec::Vec<SerializationVector>,
+}
@@ -0,0 +1,76 @@
+// This file is @generated by prost-build.
+/// Schema of the store database, for the external tools reading the database file directly.
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DatabaseSchema {
+    /// Number of migrations applied to the database, its `user_version`
+    #[prost(uint32, tag = "1")]
+    pub version: u32,
+    #[prost(message, repeated, tag = "2")]
+    pub tables: ::prost::alloc::vec::Vec<Table>,
+    #[prost(message, repeated, tag = "3")]
+    pub views: ::prost::alloc::vec::Vec<View>,
+}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Table {
+    #[prost(string, tag = "1")]
+    pub name: ::prost::alloc::string::String,
+    /// The `CREATE TABLE` statement of the table
+    #[prost(string, tag = "2")]
+    pub sql: ::prost::alloc::string::String,
+    #[prost(message, repeated, tag = "3")]
+    pub columns: ::prost::alloc::vec::Vec<Column>,
+    #[prost(message, repeated, tag = "4")]
+    pub indexes: ::prost::alloc::vec::Vec<Index>,
+    /// Names of the partitions of a partitioned table, all created by `sql` with their own name.
+    /// Empty for the tables which aren't partitioned.
+    #[prost(string, repeated, tag = "5")]
+    pub partitions: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Column {
+    #[prost(string, tag = "1")]
+    pub name: ::prost::alloc::string::String,
+    /// Declared type of the column, e.g. `INTEGER`
+    #[prost(string, tag = "2")]
+    pub column_type: ::prost::alloc::string::String,
+    #[prost(bool, tag = "3")]
+    pub not_null: bool,
+    /// Set if the column is part of the primary key
+    #[prost(bool, tag = "4")]
+    pub primary_key: bool,
+    /// SQL expression of the default value of the column
+    #[prost(string, optional, tag = "5")]
+    pub default_value: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Index {
+    #[prost(string, tag = "1")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(bool, tag = "2")]
+    pub unique: bool,
+    /// Indexed columns, in the order of the index
+    #[prost(string, repeated, tag = "3")]
+    pub columns: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// The `CREATE INDEX` statement of the index, unset for the indexes created by SQLite for the
+    /// primary keys and unique constraints
+    #[prost(string, optional, tag = "4")]
+    pub sql: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct View {
+    #[prost(string, tag = "1")]
+    pub name: ::prost::alloc::string::String,
+    /// The `CREATE VIEW` statement of the view
+    #[prost(string, tag = "2")]
+    pub sql: ::prost::alloc::string::String,
+}
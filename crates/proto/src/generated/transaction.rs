@@ -0,0 +1,120 @@
+// This file is @generated by prost-build.
+/// A transaction included in a block, with its effects on the state of the chain.
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TransactionReceipt {
+    #[prost(message, optional, tag = "1")]
+    pub transaction_id: ::core::option::Option<super::digest::Digest>,
+    #[prost(message, optional, tag = "2")]
+    pub account_id: ::core::option::Option<super::account::AccountId>,
+    /// Hash of the account state before the transaction.
+    #[prost(message, optional, tag = "3")]
+    pub initial_account_hash: ::core::option::Option<super::digest::Digest>,
+    /// Hash of the account state after the transaction.
+    #[prost(message, optional, tag = "4")]
+    pub final_account_hash: ::core::option::Option<super::digest::Digest>,
+    /// Ids of the notes created by the transaction.
+    #[prost(message, repeated, tag = "5")]
+    pub output_notes: ::prost::alloc::vec::Vec<super::digest::Digest>,
+    /// Nullifiers of the notes consumed by the transaction.
+    #[prost(message, repeated, tag = "6")]
+    pub input_nullifiers: ::prost::alloc::vec::Vec<super::digest::Digest>,
+    /// Number of the block which includes the transaction.
+    #[prost(fixed32, tag = "7")]
+    pub block_num: u32,
+    /// Index of the transaction's batch in the block, starting from 0.
+    #[prost(uint32, tag = "8")]
+    pub batch_index: u32,
+    /// Fee paid by the transaction to the operator of the node, if the node charges fees.
+    #[prost(message, optional, tag = "9")]
+    pub fee: ::core::option::Option<TransactionFee>,
+    /// Estimated execution cost of the transaction, in VM cycles: the length of the execution trace
+    /// of its proof. 0 for the transactions included before the cost was recorded.
+    #[prost(fixed64, tag = "10")]
+    pub cycles: u64,
+}
+/// Amount of a fungible asset paid as fee to the operator of the node.
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TransactionFee {
+    /// Faucet of the fungible asset.
+    #[prost(message, optional, tag = "1")]
+    pub faucet_id: ::core::option::Option<super::account::AccountId>,
+    #[prost(fixed64, tag = "2")]
+    pub amount: u64,
+}
+/// Reason for the rejection of a submitted transaction, encoded in the details of the
+/// `SubmitProvenTransaction` error status.
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TransactionRejection {
+    #[prost(oneof = "transaction_rejection::Reason", tags = "1, 2, 3, 4, 5")]
+    pub reason: ::core::option::Option<transaction_rejection::Reason>,
+}
+/// Nested message and enum types in `TransactionRejection`.
+pub mod transaction_rejection {
+    #[derive(Eq, PartialOrd, Ord, Hash)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Reason {
+        /// The account was already modified by another transaction which isn't in a block yet. Only
+        /// reported for the priority transactions, which can't be included ahead of the pending user
+        /// transactions of their account.
+        #[prost(message, tag = "1")]
+        AccountAlreadyModified(super::super::account::AccountId),
+        /// Some of the transaction's input notes were already consumed.
+        #[prost(message, tag = "2")]
+        InputNotesAlreadyConsumed(super::ConsumedNullifiers),
+        /// The transaction's initial account hash doesn't match the account's current hash.
+        #[prost(message, tag = "3")]
+        IncorrectAccountInitialHash(super::AccountHashMismatch),
+        /// The proof of the transaction with this id is invalid.
+        #[prost(message, tag = "4")]
+        InvalidTransactionProof(super::super::digest::Digest),
+        /// The transaction violates a policy of the operator of the node, e.g. a denied account.
+        #[prost(string, tag = "5")]
+        PolicyViolation(::prost::alloc::string::String),
+    }
+}
+/// Restrictions of the operator of the node on the submitted transactions, e.g. on permissioned
+/// deployments. The denylists take precedence over the allowlists.
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SubmissionPolicy {
+    /// Accounts whose transactions are accepted, all of them if empty.
+    #[prost(message, repeated, tag = "1")]
+    pub allowed_accounts: ::prost::alloc::vec::Vec<super::account::AccountId>,
+    /// Accounts whose transactions are rejected.
+    #[prost(message, repeated, tag = "2")]
+    pub denied_accounts: ::prost::alloc::vec::Vec<super::account::AccountId>,
+    /// Tags of the notes the transactions may create, all of them if empty.
+    #[prost(fixed32, repeated, tag = "3")]
+    pub allowed_note_tags: ::prost::alloc::vec::Vec<u32>,
+    /// Tags of the notes the transactions must not create.
+    #[prost(fixed32, repeated, tag = "4")]
+    pub denied_note_tags: ::prost::alloc::vec::Vec<u32>,
+}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ConsumedNullifiers {
+    /// Nullifiers of the input notes which were already consumed.
+    #[prost(message, repeated, tag = "1")]
+    pub nullifiers: ::prost::alloc::vec::Vec<super::digest::Digest>,
+}
+#[derive(Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AccountHashMismatch {
+    /// Initial account hash of the transaction.
+    #[prost(message, optional, tag = "1")]
+    pub provided: ::core::option::Option<super::digest::Digest>,
+    /// Current hash of the account, after its pending transactions if any, absent if the account
+    /// is unknown.
+    #[prost(message, optional, tag = "2")]
+    pub expected: ::core::option::Option<super::digest::Digest>,
+}
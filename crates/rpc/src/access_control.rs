@@ -0,0 +1,236 @@
+//! Access control, granting roles to the clients by API key or IP address and restricting the
+//! endpoints to the roles allowed to call them.
+use std::{
+    future::{ready, Future},
+    net::IpAddr,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use tonic::{
+    body::BoxBody,
+    codegen::{http, Service},
+    transport::server::TcpConnectInfo,
+    Status,
+};
+use tower::Layer;
+use tracing::debug;
+
+use crate::{
+    config::{AccessControlConfig, Role},
+    grpc::grpc_method,
+    COMPONENT,
+};
+
+/// Header of the requests carrying the API key of the client.
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+// ACCESS CONTROL
+// ================================================================================================
+
+/// Resolves the role of the clients and checks it against the role required by the endpoints.
+#[derive(Debug)]
+pub struct AccessControl {
+    config: AccessControlConfig,
+    /// Role required by the endpoints not listed in the `endpoint_roles` of the configuration
+    default_role: Role,
+}
+
+impl AccessControl {
+    pub fn new(config: AccessControlConfig, default_role: Role) -> Self {
+        Self { config, default_role }
+    }
+
+    /// Authorizes a request to the endpoint `method`, sent with `api_key` from the address `peer`,
+    /// and returns the role of the client.
+    ///
+    /// The client has the highest of the roles of its API key and of its address, or the anonymous
+    /// role if neither is known.
+    ///
+    /// Returns an `UNAUTHENTICATED` status if the API key is unknown, or if the client has no role,
+    /// and a `PERMISSION_DENIED` status if its role isn't allowed to call the endpoint.
+    pub fn authorize(
+        &self,
+        method: &str,
+        api_key: Option<&str>,
+        peer: Option<IpAddr>,
+    ) -> Result<Role, Status> {
+        let key_role = match api_key {
+            Some(api_key) => Some(
+                self.config
+                    .api_keys
+                    .get(api_key)
+                    .copied()
+                    .ok_or_else(|| Status::unauthenticated("Unknown API key"))?,
+            ),
+            None => None,
+        };
+        let peer_role = peer.and_then(|peer| self.config.peers.get(&peer).copied());
+
+        let role = key_role
+            .max(peer_role)
+            .or(self.config.anonymous_role)
+            .ok_or_else(|| Status::unauthenticated("An API key is required"))?;
+
+        let required_role = self.config.required_role(method, self.default_role);
+        if role < required_role {
+            debug!(target: COMPONENT, method, %role, %required_role, ?peer, "Request denied");
+            return Err(Status::permission_denied(format!(
+                "{method} requires the {required_role} role"
+            )));
+        }
+
+        Ok(role)
+    }
+}
+
+// ACCESS CONTROL LAYER
+// ================================================================================================
+
+/// Layer of the RPC server authorizing the requests through the [AccessControl], a pass-through if
+/// access control is disabled.
+#[derive(Debug, Clone)]
+pub struct AccessControlLayer {
+    access_control: Option<Arc<AccessControl>>,
+}
+
+impl AccessControlLayer {
+    /// Returns the layer of a server whose endpoints require `default_role`, unless listed in the
+    /// `endpoint_roles` of `config`.
+    pub fn new(config: Option<&AccessControlConfig>, default_role: Role) -> Self {
+        Self {
+            access_control: config
+                .map(|config| Arc::new(AccessControl::new(config.clone(), default_role))),
+        }
+    }
+}
+
+impl<S> Layer<S> for AccessControlLayer {
+    type Service = AccessControlService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        AccessControlService {
+            inner,
+            access_control: self.access_control.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AccessControlService<S> {
+    inner: S,
+    access_control: Option<Arc<AccessControl>>,
+}
+
+impl<S, B> Service<http::Request<B>> for AccessControlService<S>
+where
+    S: Service<http::Request<B>, Response = http::Response<BoxBody>>,
+    S::Future: Send + 'static,
+    S::Error: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: http::Request<B>) -> Self::Future {
+        if let Some(access_control) = &self.access_control {
+            let method = grpc_method(request.uri().path());
+            let api_key =
+                request.headers().get(API_KEY_HEADER).and_then(|value| value.to_str().ok());
+            let peer = request
+                .extensions()
+                .get::<TcpConnectInfo>()
+                .and_then(TcpConnectInfo::remote_addr)
+                .map(|addr| addr.ip());
+
+            if let Err(status) = access_control.authorize(method, api_key, peer) {
+                return Box::pin(ready(Ok(status.to_http())));
+            }
+        }
+
+        Box::pin(self.inner.call(request))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tonic::Code;
+
+    use super::*;
+
+    #[test]
+    fn requests_are_authorized_by_role() {
+        let partner: IpAddr = [10, 0, 0, 1].into();
+        let config = AccessControlConfig {
+            api_keys: [("submitter-key".to_string(), Role::Submitter)].into(),
+            peers: [(partner, Role::Admin)].into(),
+            ..Default::default()
+        };
+        let access_control = AccessControl::new(config, Role::ReadOnly);
+        let anonymous: IpAddr = [10, 0, 0, 2].into();
+
+        // Anyone reads the state of the chain
+        let role = access_control.authorize("SyncState", None, Some(anonymous)).unwrap();
+        assert_eq!(role, Role::ReadOnly);
+
+        // Only the registered clients submit transactions
+        let status = access_control
+            .authorize("SubmitProvenTransaction", None, Some(anonymous))
+            .unwrap_err();
+        assert_eq!(status.code(), Code::PermissionDenied);
+        let role = access_control
+            .authorize("SubmitProvenTransaction", Some("submitter-key"), Some(anonymous))
+            .unwrap();
+        assert_eq!(role, Role::Submitter);
+
+        // The highest role of the key and of the address applies
+        let role = access_control.authorize("SyncState", Some("submitter-key"), Some(partner));
+        assert_eq!(role.unwrap(), Role::Admin);
+
+        // Unknown keys are rejected rather than treated as anonymous
+        let status = access_control.authorize("SyncState", Some("unknown"), None).unwrap_err();
+        assert_eq!(status.code(), Code::Unauthenticated);
+    }
+
+    #[test]
+    fn anonymous_requests_can_be_rejected() {
+        let config = AccessControlConfig {
+            anonymous_role: None,
+            api_keys: [("reader-key".to_string(), Role::ReadOnly)].into(),
+            ..Default::default()
+        };
+        let access_control = AccessControl::new(config, Role::ReadOnly);
+
+        let status = access_control.authorize("SyncState", None, None).unwrap_err();
+        assert_eq!(status.code(), Code::Unauthenticated);
+        assert!(access_control.authorize("SyncState", Some("reader-key"), None).is_ok());
+    }
+
+    #[test]
+    fn admin_endpoints_require_the_admin_role() {
+        let config = AccessControlConfig {
+            api_keys: [
+                ("submitter-key".to_string(), Role::Submitter),
+                ("admin-key".to_string(), Role::Admin),
+            ]
+            .into(),
+            ..Default::default()
+        };
+        let access_control = AccessControl::new(config, Role::Admin);
+
+        let status = access_control
+            .authorize("SetMaintenanceMode", Some("submitter-key"), None)
+            .unwrap_err();
+        assert_eq!(status.code(), Code::PermissionDenied);
+        let status = access_control.authorize("GetAuditLog", None, None).unwrap_err();
+        assert_eq!(status.code(), Code::PermissionDenied);
+
+        let role = access_control.authorize("SetMaintenanceMode", Some("admin-key"), None);
+        assert_eq!(role.unwrap(), Role::Admin);
+    }
+}
@@ -0,0 +1,286 @@
+//! Append-only audit log of the transactions submitted to the node and of the admin operations.
+//!
+//! The log is stored in its own SQLite database, separate from the store. Updates and deletions
+//! of the recorded entries are rejected by the database.
+use std::{
+    net::SocketAddr,
+    path::Path,
+    sync::{Arc, Mutex},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use miden_node_proto::{domain::pagination::PageSize, generated::responses::AuditLogEntry};
+use miden_objects::{
+    utils::{Deserializable, Serializable},
+    Digest,
+};
+use rusqlite::{params, Connection};
+use tonic::Status;
+use tracing::error;
+
+use crate::{errors::AuditLogError, COMPONENT};
+
+/// Sizes of the pages of `GetAuditLog`, in entries.
+pub const AUDIT_LOG_PAGE_SIZE: PageSize = PageSize { default: 100, max: 1000 };
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS audit_log
+    (
+        id           INTEGER PRIMARY KEY AUTOINCREMENT,
+        timestamp_ms INTEGER NOT NULL,
+        operation    TEXT    NOT NULL,
+        peer         TEXT,
+        tx_id        BLOB,
+        error        TEXT,
+
+        CONSTRAINT audit_log_timestamp_is_u64 CHECK (timestamp_ms >= 0)
+    ) STRICT;
+
+    CREATE INDEX IF NOT EXISTS audit_log_timestamp_ms ON audit_log (timestamp_ms);
+
+    CREATE TRIGGER IF NOT EXISTS audit_log_no_update BEFORE UPDATE ON audit_log
+    BEGIN
+        SELECT RAISE(ABORT, 'audit log is append-only');
+    END;
+
+    CREATE TRIGGER IF NOT EXISTS audit_log_no_delete BEFORE DELETE ON audit_log
+    BEGIN
+        SELECT RAISE(ABORT, 'audit log is append-only');
+    END;
+";
+
+// AUDIT ENTRY
+// ================================================================================================
+
+/// An operation recorded in the audit log.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditEntry {
+    pub timestamp_ms: u64,
+    pub operation: String,
+    pub peer: Option<String>,
+    pub tx_id: Option<Digest>,
+    /// Error message of a failed operation, `None` if the operation succeeded.
+    pub error: Option<String>,
+}
+
+impl AuditEntry {
+    /// Returns the entry of `operation` served now, with the outcome `result`.
+    pub fn new<T>(
+        operation: &str,
+        peer: Option<SocketAddr>,
+        tx_id: Option<Digest>,
+        result: &Result<T, Status>,
+    ) -> Self {
+        let timestamp_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_millis() as u64);
+
+        Self {
+            timestamp_ms,
+            operation: operation.to_string(),
+            peer: peer.map(|peer| peer.to_string()),
+            tx_id,
+            error: result.as_ref().err().map(|status| status.message().to_string()),
+        }
+    }
+}
+
+impl From<AuditEntry> for AuditLogEntry {
+    fn from(entry: AuditEntry) -> Self {
+        Self {
+            timestamp_ms: entry.timestamp_ms,
+            operation: entry.operation,
+            peer: entry.peer,
+            tx_id: entry.tx_id.map(Into::into),
+            error: entry.error,
+        }
+    }
+}
+
+// AUDIT LOG
+// ================================================================================================
+
+#[derive(Clone)]
+pub struct AuditLog {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl AuditLog {
+    /// Opens the audit log stored at `path`, creating it if it doesn't exist.
+    pub fn open(path: &Path) -> Result<Self, AuditLogError> {
+        Self::from_connection(Connection::open(path)?)
+    }
+
+    fn from_connection(conn: Connection) -> Result<Self, AuditLogError> {
+        conn.execute_batch(SCHEMA)?;
+
+        Ok(Self { conn: Arc::new(Mutex::new(conn)) })
+    }
+
+    /// Appends `entry` to the log.
+    ///
+    /// Failures are logged, since the operation has already been served at this point.
+    pub async fn record(&self, entry: AuditEntry) {
+        let result = self
+            .interact(move |conn| {
+                conn.execute(
+                    "INSERT INTO audit_log (timestamp_ms, operation, peer, tx_id, error) \
+                     VALUES (?1, ?2, ?3, ?4, ?5)",
+                    params![
+                        entry.timestamp_ms as i64,
+                        entry.operation,
+                        entry.peer,
+                        entry.tx_id.map(|tx_id| tx_id.to_bytes()),
+                        entry.error,
+                    ],
+                )?;
+                Ok(())
+            })
+            .await;
+
+        if let Err(err) = result {
+            error!(target: COMPONENT, %err, "Failed to record audit log entry");
+        }
+    }
+
+    /// Returns at most `limit` entries recorded at or after `since_ms` of `operation`, with their
+    /// ids, the most recent first. Only the entries before the entry `before` are returned, if
+    /// set.
+    pub async fn query(
+        &self,
+        since_ms: Option<u64>,
+        operation: Option<String>,
+        before: Option<u64>,
+        limit: usize,
+    ) -> Result<Vec<(u64, AuditEntry)>, AuditLogError> {
+        self.interact(move |conn| {
+            let mut stmt = conn.prepare(
+                "SELECT id, timestamp_ms, operation, peer, tx_id, error FROM audit_log \
+                 WHERE timestamp_ms >= ?1 AND (?2 IS NULL OR operation = ?2) \
+                 AND (?3 IS NULL OR id < ?3) \
+                 ORDER BY id DESC LIMIT ?4",
+            )?;
+            let mut rows = stmt.query(params![
+                since_ms.unwrap_or_default() as i64,
+                operation,
+                before.map(|id| id as i64),
+                limit as i64
+            ])?;
+
+            let mut entries = Vec::new();
+            while let Some(row) = rows.next()? {
+                let tx_id = row
+                    .get::<_, Option<Vec<u8>>>(4)?
+                    .map(|tx_id| Digest::read_from_bytes(&tx_id))
+                    .transpose()
+                    .map_err(|err| AuditLogError::MalformedData(err.to_string()))?;
+
+                let entry = AuditEntry {
+                    timestamp_ms: row.get::<_, i64>(1)? as u64,
+                    operation: row.get(2)?,
+                    peer: row.get(3)?,
+                    tx_id,
+                    error: row.get(5)?,
+                };
+                entries.push((row.get::<_, i64>(0)? as u64, entry));
+            }
+
+            Ok(entries)
+        })
+        .await
+    }
+
+    /// Runs `f` with the database connection, on a thread where blocking is allowed.
+    async fn interact<R, F>(&self, f: F) -> Result<R, AuditLogError>
+    where
+        R: Send + 'static,
+        F: FnOnce(&mut Connection) -> Result<R, AuditLogError> + Send + 'static,
+    {
+        let conn = self.conn.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut conn = conn.lock().expect("audit log connection lock poisoned");
+            f(&mut conn)
+        })
+        .await?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use miden_objects::Digest;
+    use rusqlite::Connection;
+    use tonic::Status;
+
+    use super::{AuditEntry, AuditLog};
+
+    fn entry(timestamp_ms: u64, operation: &str, error: Option<&str>) -> AuditEntry {
+        AuditEntry {
+            timestamp_ms,
+            operation: operation.to_string(),
+            peer: Some("127.0.0.1:1234".to_string()),
+            tx_id: Some(Digest::default()),
+            error: error.map(ToString::to_string),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_audit_log_query() {
+        let audit_log = AuditLog::from_connection(Connection::open_in_memory().unwrap()).unwrap();
+        let entries = [
+            entry(1, "SubmitProvenTransaction", None),
+            entry(2, "GetAuditLog", None),
+            entry(3, "SubmitProvenTransaction", Some("Invalid transaction")),
+        ];
+        for entry in entries.clone() {
+            audit_log.record(entry).await;
+        }
+
+        let all = audit_log.query(None, None, None, 100).await.unwrap();
+        assert_eq!(
+            all,
+            entries
+                .iter()
+                .cloned()
+                .enumerate()
+                .rev()
+                .map(|(i, e)| (i as u64 + 1, e))
+                .collect::<Vec<_>>()
+        );
+
+        let submitted = audit_log
+            .query(Some(2), Some("SubmitProvenTransaction".to_string()), None, 100)
+            .await
+            .unwrap();
+        assert_eq!(submitted, vec![(3, entries[2].clone())]);
+
+        let latest = audit_log.query(None, None, None, 1).await.unwrap();
+        assert_eq!(latest, vec![(3, entries[2].clone())]);
+
+        // the next page starts before the last entry of the previous page
+        let previous = audit_log.query(None, None, Some(3), 1).await.unwrap();
+        assert_eq!(previous, vec![(2, entries[1].clone())]);
+    }
+
+    #[tokio::test]
+    async fn test_audit_log_is_append_only() {
+        let audit_log = AuditLog::from_connection(Connection::open_in_memory().unwrap()).unwrap();
+        audit_log
+            .record(AuditEntry::new::<()>(
+                "SubmitProvenTransaction",
+                None,
+                None,
+                &Err(Status::invalid_argument("Invalid transaction")),
+            ))
+            .await;
+
+        {
+            let conn = audit_log.conn.lock().unwrap();
+            assert!(conn.execute("UPDATE audit_log SET error = NULL", []).is_err());
+            assert!(conn.execute("DELETE FROM audit_log", []).is_err());
+        }
+
+        let entries = audit_log.query(None, None, None, 100).await.unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].1.error.as_deref(), Some("Invalid transaction"));
+    }
+}
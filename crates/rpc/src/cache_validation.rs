@@ -0,0 +1,83 @@
+//! Cache validation of the responses of the methods serving immutable data: block headers, notes
+//! and block proofs.
+//!
+//! Their responses carry a validator in the `etag` header, the hash of their content. A client
+//! sending it back in the `if-none-match` header receives an empty response with the
+//! `x-not-modified` header if the response didn't change, instead of the same data again.
+use miden_node_utils::formatting::format_blake3_digest;
+use miden_objects::crypto::hash::blake::Blake3_256;
+use prost::Message;
+use tonic::{
+    metadata::{AsciiMetadataValue, MetadataValue},
+    Request, Response,
+};
+
+/// Name of the header carrying the validator of a response.
+pub const ETAG_HEADER: &str = "etag";
+
+/// Name of the header carrying the validator of the response cached by the client.
+pub const IF_NONE_MATCH_HEADER: &str = "if-none-match";
+
+/// Name of the header set on the empty responses sent instead of the unchanged ones.
+pub const NOT_MODIFIED_HEADER: &str = "x-not-modified";
+
+/// Returns the validator of the response cached by the client of `request`, if any.
+pub fn if_none_match<T>(request: &Request<T>) -> Option<AsciiMetadataValue> {
+    request.metadata().get(IF_NONE_MATCH_HEADER).cloned()
+}
+
+/// Sets the validator of `response`, which is replaced by an empty response with the
+/// `x-not-modified` header if it matches `if_none_match`.
+pub fn validate<T>(response: Response<T>, if_none_match: Option<AsciiMetadataValue>) -> Response<T>
+where
+    T: Message + Default,
+{
+    let (mut metadata, message, extensions) = response.into_parts();
+    let etag = format_blake3_digest(Blake3_256::hash(&message.encode_to_vec()));
+    let etag = AsciiMetadataValue::try_from(etag).expect("hex strings are valid header values");
+
+    let message = if if_none_match.as_ref() == Some(&etag) {
+        metadata.insert(NOT_MODIFIED_HEADER, MetadataValue::from_static("true"));
+        T::default()
+    } else {
+        message
+    };
+    metadata.insert(ETAG_HEADER, etag);
+
+    Response::from_parts(metadata, message, extensions)
+}
+
+#[cfg(test)]
+mod tests {
+    use miden_node_proto::generated::responses::GetBlockProofResponse;
+
+    use super::*;
+
+    #[test]
+    fn unchanged_responses_are_not_sent_again() {
+        let response = || {
+            Response::new(GetBlockProofResponse {
+                block_header: None,
+                proof: Some(vec![7; 1024]),
+            })
+        };
+
+        let first = validate(response(), None);
+        let etag = first.metadata().get(ETAG_HEADER).cloned().unwrap();
+        assert!(first.metadata().get(NOT_MODIFIED_HEADER).is_none());
+        assert_eq!(first.get_ref().proof, Some(vec![7; 1024]));
+
+        let mut request = Request::new(());
+        request.metadata_mut().insert(IF_NONE_MATCH_HEADER, etag.clone());
+        let second = validate(response(), if_none_match(&request));
+        assert_eq!(second.metadata().get(ETAG_HEADER), Some(&etag));
+        assert_eq!(second.metadata().get(NOT_MODIFIED_HEADER).unwrap(), "true");
+        assert_eq!(second.into_inner(), GetBlockProofResponse::default());
+
+        // a stale validator gets the full response
+        let stale = AsciiMetadataValue::from_static("0x00");
+        let third = validate(response(), Some(stale));
+        assert!(third.metadata().get(NOT_MODIFIED_HEADER).is_none());
+        assert_eq!(third.get_ref().proof, Some(vec![7; 1024]));
+    }
+}
@@ -1,6 +1,16 @@
-use std::fmt::{Display, Formatter};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::{Display, Formatter},
+    net::IpAddr,
+    path::PathBuf,
+    time::Duration,
+};
 
-use miden_node_utils::config::Endpoint;
+use miden_node_utils::{
+    config::{AccessLogConfig, ClientConfig, Endpoint},
+    formatting::format_opt,
+};
+use miden_objects::{utils::HexParseError, Digest};
 use serde::{Deserialize, Serialize};
 
 // Main config
@@ -13,19 +23,480 @@ pub struct RpcConfig {
     pub store_url: String,
     /// Block producer gRPC endpoint in the format `http://<host>[:<port>]`.
     pub block_producer_url: String,
+    /// Keep-alive and health checks of the connections to the store and the block producer.
+    #[serde(default)]
+    pub client: ClientConfig,
+    /// Hash of the genesis block of the chain, as logged by the store at startup. If set, the RPC
+    /// refuses to start with a store of another chain.
+    #[serde(default)]
+    pub genesis_hash: Option<String>,
+    /// Maximum time allowed to serve a request, a shorter deadline set by the client takes
+    /// precedence.
+    #[serde(default)]
+    pub timeouts: RequestTimeouts,
+    /// Origins allowed to call the RPC from a browser, over gRPC-web.
+    #[serde(default)]
+    pub cors: CorsConfig,
+    /// Operator facing API, disabled if not set.
+    #[serde(default)]
+    pub admin: Option<AdminConfig>,
+    /// Audit log of the write operations, disabled if not set.
+    #[serde(default)]
+    pub audit: Option<AuditConfig>,
+    /// Rejection of the low priority requests when the RPC is overloaded, disabled if not set.
+    #[serde(default)]
+    pub load_shedding: Option<LoadSheddingConfig>,
+    /// Roles of the clients and endpoints they are allowed to call, all the clients can call all
+    /// the endpoints if not set.
+    #[serde(default)]
+    pub access_control: Option<AccessControlConfig>,
+    /// Faucet minting funds to the accounts requesting them, disabled if not set. Only meant for
+    /// testnets.
+    #[serde(default)]
+    pub testnet_faucet: Option<TestnetFaucetConfig>,
+    /// Access log of the requests served, by the RPC and its admin API, disabled if not set.
+    #[serde(default)]
+    pub access_log: Option<AccessLogConfig>,
+    /// Accounting of the requests served to each API key, disabled if not set.
+    #[serde(default)]
+    pub usage: Option<UsageConfig>,
+    /// Retries and hedging of the reads forwarded to the store, a single attempt is made if not
+    /// set.
+    #[serde(default)]
+    pub store_retry: Option<StoreRetryConfig>,
 }
 
 impl RpcConfig {
     pub fn endpoint_url(&self) -> String {
         self.endpoint.to_string()
     }
+
+    /// Parses the hex encoded `genesis_hash`.
+    pub fn genesis_hash(&self) -> Result<Option<Digest>, HexParseError> {
+        self.genesis_hash.as_deref().map(Digest::try_from).transpose()
+    }
 }
 
 impl Display for RpcConfig {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.write_fmt(format_args!(
-            "{{ endpoint: \"{}\", store_url: \"{}\", block_producer_url: \"{}\" }}",
-            self.endpoint, self.store_url, self.block_producer_url
+            "{{ endpoint: \"{}\", store_url: \"{}\", block_producer_url: \"{}\", client: {}, \
+             genesis_hash: {}, timeouts: {}, cors: {}, admin: {}, audit: {}, load_shedding: {}, \
+             access_control: {}, testnet_faucet: {}, access_log: {}, usage: {}, store_retry: {} }}",
+            self.endpoint,
+            self.store_url,
+            self.block_producer_url,
+            self.client,
+            format_opt(self.genesis_hash.as_ref()),
+            self.timeouts,
+            self.cors,
+            format_opt(self.admin.as_ref()),
+            format_opt(self.audit.as_ref()),
+            format_opt(self.load_shedding.as_ref()),
+            format_opt(self.access_control.as_ref()),
+            format_opt(self.testnet_faucet.as_ref()),
+            format_opt(self.access_log.as_ref()),
+            format_opt(self.usage.as_ref()),
+            format_opt(self.store_retry.as_ref())
+        ))
+    }
+}
+
+// Request timeouts
+// ================================================================================================
+
+/// Timeouts of the RPC endpoints, in milliseconds.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RequestTimeouts {
+    /// Timeout of the endpoints not listed in `endpoints`.
+    pub default_ms: u64,
+    /// Timeouts of specific endpoints, keyed by the gRPC method name, e.g. `SyncState`.
+    pub endpoints: BTreeMap<String, u64>,
+}
+
+impl RequestTimeouts {
+    /// Returns the timeout of the endpoint `method`.
+    pub fn get(&self, method: &str) -> Duration {
+        Duration::from_millis(self.endpoints.get(method).copied().unwrap_or(self.default_ms))
+    }
+}
+
+impl Default for RequestTimeouts {
+    fn default() -> Self {
+        Self {
+            default_ms: 10_000,
+            endpoints: BTreeMap::new(),
+        }
+    }
+}
+
+impl Display for RequestTimeouts {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!(
+            "{{ default_ms: {}, endpoints: {:?} }}",
+            self.default_ms, self.endpoints
+        ))
+    }
+}
+
+// CORS
+// ================================================================================================
+
+/// Value of [CorsConfig::allowed_origins] allowing any origin.
+pub const ANY_ORIGIN: &str = "*";
+
+/// Cross-origin resource sharing policy of the RPC, allowing browser-based wallets to call the
+/// node over gRPC-web without a proxy.
+#[derive(Clone, Default, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CorsConfig {
+    /// Origins allowed to make cross-origin requests, e.g. `https://wallet.example.com`. `*` allows
+    /// any origin, and an empty list rejects all cross-origin requests.
+    pub allowed_origins: Vec<String>,
+}
+
+impl CorsConfig {
+    /// Returns `true` if any origin is allowed to make cross-origin requests.
+    pub fn allows_any_origin(&self) -> bool {
+        self.allowed_origins.iter().any(|origin| origin == ANY_ORIGIN)
+    }
+}
+
+impl Display for CorsConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("{{ allowed_origins: {:?} }}", self.allowed_origins))
+    }
+}
+
+// Admin API
+// ================================================================================================
+
+/// Configuration of the admin API, used by the operator to query the audit log, pause the block
+/// production and enter maintenance mode.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize)]
+pub struct AdminConfig {
+    /// Endpoint of the admin API. It should not be reachable by the clients.
+    pub endpoint: Endpoint,
+}
+
+impl Display for AdminConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("{{ endpoint: \"{}\" }}", self.endpoint))
+    }
+}
+
+// Audit log
+// ================================================================================================
+
+/// Configuration of the audit log, recording the submitted transactions and the admin operations.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize)]
+pub struct AuditConfig {
+    /// Path of the SQLite database storing the audit log, created if it doesn't exist.
+    pub database_filepath: PathBuf,
+}
+
+impl Display for AuditConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("{{ database_filepath: {:?} }}", self.database_filepath))
+    }
+}
+
+// Load shedding
+// ================================================================================================
+
+/// Configuration of the load shedding, rejecting the low priority requests while the cost of the
+/// requests being served exceeds a limit, so explorers scraping the node can't starve the
+/// submission of transactions.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LoadSheddingConfig {
+    /// Maximum total cost of the requests being served, the low priority requests exceeding it are
+    /// rejected.
+    pub max_in_flight_cost: u64,
+    /// Cost of the endpoints not listed in `endpoint_costs`.
+    pub default_cost: u64,
+    /// Costs of specific endpoints, keyed by the gRPC method name, e.g. `SyncState`.
+    pub endpoint_costs: BTreeMap<String, u64>,
+    /// Endpoints which are never rejected, their requests still count towards the in-flight cost.
+    pub priority_endpoints: BTreeSet<String>,
+    /// Number of seconds after which the clients of rejected requests are told to retry.
+    pub retry_after_s: u32,
+}
+
+impl LoadSheddingConfig {
+    /// Returns the cost of a request to the endpoint `method`.
+    pub fn cost(&self, method: &str) -> u64 {
+        self.endpoint_costs.get(method).copied().unwrap_or(self.default_cost)
+    }
+
+    /// Returns `true` if the requests to the endpoint `method` are never rejected.
+    pub fn is_priority(&self, method: &str) -> bool {
+        self.priority_endpoints.contains(method)
+    }
+}
+
+impl Default for LoadSheddingConfig {
+    fn default() -> Self {
+        Self {
+            max_in_flight_cost: 1000,
+            default_cost: 1,
+            endpoint_costs: [("SyncState".to_string(), 10), ("SyncNotes".to_string(), 5)].into(),
+            priority_endpoints: ["SubmitProvenTransaction".to_string()].into(),
+            retry_after_s: 5,
+        }
+    }
+}
+
+impl Display for LoadSheddingConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!(
+            "{{ max_in_flight_cost: {}, default_cost: {}, endpoint_costs: {:?}, \
+             priority_endpoints: {:?}, retry_after_s: {} }}",
+            self.max_in_flight_cost,
+            self.default_cost,
+            self.endpoint_costs,
+            self.priority_endpoints,
+            self.retry_after_s
+        ))
+    }
+}
+
+// Access control
+// ================================================================================================
+
+/// Role of a client of the RPC, allowing it to call the endpoints requiring this role or a lower
+/// one.
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    /// Reads the state of the chain.
+    ReadOnly,
+    /// Also submits transactions.
+    Submitter,
+    /// Calls all the endpoints.
+    Admin,
+}
+
+impl Display for Role {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Role::ReadOnly => "read_only",
+            Role::Submitter => "submitter",
+            Role::Admin => "admin",
+        })
+    }
+}
+
+/// Configuration of the access control, granting roles to the clients by API key or IP address and
+/// restricting endpoints to roles, so a node can serve public reads while only registered partners
+/// submit transactions.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AccessControlConfig {
+    /// Role of the clients without a known API key or address, these clients are rejected if not
+    /// set.
+    pub anonymous_role: Option<Role>,
+    /// Roles of the API keys, sent by the clients in the `x-api-key` header.
+    pub api_keys: BTreeMap<String, Role>,
+    /// Roles of the IP addresses of the clients.
+    pub peers: BTreeMap<IpAddr, Role>,
+    /// Roles required by specific endpoints, keyed by the gRPC method name, e.g.
+    /// `SubmitProvenTransaction`. The other endpoints of the RPC require the `read_only` role, and
+    /// the other endpoints of the admin API the `admin` role.
+    pub endpoint_roles: BTreeMap<String, Role>,
+}
+
+impl AccessControlConfig {
+    /// Returns the role required to call the endpoint `method`, `default_role` if it isn't listed
+    /// in `endpoint_roles`.
+    pub fn required_role(&self, method: &str, default_role: Role) -> Role {
+        self.endpoint_roles.get(method).copied().unwrap_or(default_role)
+    }
+}
+
+impl Default for AccessControlConfig {
+    fn default() -> Self {
+        Self {
+            anonymous_role: Some(Role::ReadOnly),
+            api_keys: BTreeMap::new(),
+            peers: BTreeMap::new(),
+            endpoint_roles: [("SubmitProvenTransaction".to_string(), Role::Submitter)].into(),
+        }
+    }
+}
+
+impl Display for AccessControlConfig {
+    // The API keys are secrets, only their number is displayed
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!(
+            "{{ anonymous_role: {}, api_keys: {}, peers: {:?}, endpoint_roles: {:?} }}",
+            format_opt(self.anonymous_role.as_ref()),
+            self.api_keys.len(),
+            self.peers,
+            self.endpoint_roles
+        ))
+    }
+}
+
+// Usage
+// ================================================================================================
+
+/// Configuration of the usage accounting, counting the requests, rows and bytes served to each API
+/// key so that infra providers can bill or throttle the heavy consumers of the node.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UsageConfig {
+    /// Interval in seconds between two exports of the usage to the logs, zero disables the export.
+    pub export_interval_s: u64,
+    /// Cost of the endpoints not listed in `endpoint_costs`.
+    pub default_cost: u64,
+    /// Costs of specific endpoints, keyed by the gRPC method name, e.g. `SyncState`.
+    pub endpoint_costs: BTreeMap<String, u64>,
+}
+
+impl UsageConfig {
+    /// Returns the cost of a request to the endpoint `method`.
+    pub fn cost(&self, method: &str) -> u64 {
+        self.endpoint_costs.get(method).copied().unwrap_or(self.default_cost)
+    }
+}
+
+impl Default for UsageConfig {
+    fn default() -> Self {
+        Self {
+            export_interval_s: 60,
+            default_cost: 1,
+            endpoint_costs: [("SyncState".to_string(), 10), ("SyncNotes".to_string(), 5)].into(),
+        }
+    }
+}
+
+impl Display for UsageConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!(
+            "{{ export_interval_s: {}, default_cost: {}, endpoint_costs: {:?} }}",
+            self.export_interval_s, self.default_cost, self.endpoint_costs
+        ))
+    }
+}
+
+// Store retries
+// ================================================================================================
+
+/// Configuration of the retries and hedged attempts of the reads forwarded to the store, so a
+/// momentary stall of the store, e.g. on a checkpoint of its database, doesn't fail the requests or
+/// inflate their tail latency.
+///
+/// The retries and hedged attempts are limited by a budget, a fraction of the requests, so they
+/// can't amplify the load of an overloaded store.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StoreRetryConfig {
+    /// Maximum number of attempts of a request failing with `UNAVAILABLE`, including the first.
+    pub max_attempts: u32,
+    /// Delay before the first retry in milliseconds, doubled after each retry.
+    pub backoff_ms: u64,
+    /// Retries and hedged attempts allowed, as a percentage of the requests forwarded to the store.
+    pub budget_percent: u32,
+    /// Retries and hedged attempts allowed on top of the percentage, e.g. after a quiet period.
+    pub budget_reserve: u32,
+    /// Delay in milliseconds after which a second attempt of a read is sent if the first one didn't
+    /// complete, the first response being returned. Zero disables the hedging.
+    pub hedge_delay_ms: u64,
+}
+
+impl StoreRetryConfig {
+    pub fn backoff(&self) -> Duration {
+        Duration::from_millis(self.backoff_ms)
+    }
+
+    /// Returns the delay of the hedged attempts, [None] if the hedging is disabled.
+    pub fn hedge_delay(&self) -> Option<Duration> {
+        (self.hedge_delay_ms != 0).then(|| Duration::from_millis(self.hedge_delay_ms))
+    }
+}
+
+impl Default for StoreRetryConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            backoff_ms: 20,
+            budget_percent: 10,
+            budget_reserve: 10,
+            hedge_delay_ms: 0,
+        }
+    }
+}
+
+impl Display for StoreRetryConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!(
+            "{{ max_attempts: {}, backoff_ms: {}, budget_percent: {}, budget_reserve: {}, \
+             hedge_delay_ms: {} }}",
+            self.max_attempts,
+            self.backoff_ms,
+            self.budget_percent,
+            self.budget_reserve,
+            self.hedge_delay_ms
+        ))
+    }
+}
+
+// Testnet faucet
+// ================================================================================================
+
+/// Configuration of the testnet faucet, minting a fixed amount of a faucet's asset to the accounts
+/// requesting it, within quotas per account and per IP address.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize)]
+pub struct TestnetFaucetConfig {
+    /// Path of the faucet account file, e.g. generated by `make-genesis`. It is updated with the
+    /// new state of the faucet after each mint.
+    pub faucet_account_filepath: PathBuf,
+    /// Amount of the faucet's asset minted per request.
+    pub asset_amount: u64,
+    /// Quota of the requests funding the same account.
+    pub per_account: QuotaConfig,
+    /// Quota of the requests made from the same IP address.
+    pub per_ip: QuotaConfig,
+    /// Webhook approving the requests, e.g. by checking a captcha response, in the format
+    /// `http://<host>[:<port>]/<path>`. The requests are not verified if not set.
+    #[serde(default)]
+    pub verification_url: Option<String>,
+}
+
+impl Display for TestnetFaucetConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!(
+            "{{ faucet_account_filepath: {:?}, asset_amount: {}, per_account: {}, per_ip: {}, \
+             verification_url: {} }}",
+            self.faucet_account_filepath,
+            self.asset_amount,
+            self.per_account,
+            self.per_ip,
+            format_opt(self.verification_url.as_ref())
+        ))
+    }
+}
+
+/// Maximum number of requests allowed over a sliding window.
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize)]
+pub struct QuotaConfig {
+    pub max_requests: usize,
+    pub period_secs: u64,
+}
+
+impl QuotaConfig {
+    pub fn period(&self) -> Duration {
+        Duration::from_secs(self.period_secs)
+    }
+}
+
+impl Display for QuotaConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!(
+            "{{ max_requests: {}, period_secs: {} }}",
+            self.max_requests, self.period_secs
         ))
     }
 }
@@ -0,0 +1,75 @@
+use thiserror::Error;
+use tokio::task::JoinError;
+use tonic::{metadata::MetadataValue, Status};
+
+use crate::maintenance::RETRY_AFTER_HEADER;
+
+// Audit log errors
+// =================================================================================================
+
+#[derive(Debug, Error)]
+pub enum AuditLogError {
+    #[error("SQLite error: {0}")]
+    SqliteError(#[from] rusqlite::Error),
+
+    #[error("Audit log task failed: {0}")]
+    TaskFailed(#[from] JoinError),
+
+    #[error("Audit log contains malformed data: {0}")]
+    MalformedData(String),
+}
+
+// Testnet faucet errors
+// =================================================================================================
+
+#[derive(Debug, Error)]
+pub enum TestnetFaucetError {
+    #[error("Too many requests for this {scope}, retry in {retry_after_s} seconds")]
+    QuotaExceeded { scope: &'static str, retry_after_s: u64 },
+
+    #[error("Request rejected by the verification webhook")]
+    VerificationRejected,
+
+    #[error("Verification webhook failed: {0}")]
+    VerificationFailed(String),
+
+    #[error("Failed to load the faucet account: {0}")]
+    InvalidFaucetAccount(String),
+
+    #[error("Failed to save the faucet account: {0}")]
+    SaveFaucetAccountFailed(std::io::Error),
+
+    #[error("Failed to get the reference block from the store: {0}")]
+    ReferenceBlockFailed(String),
+
+    #[error("Failed to execute the mint transaction: {0}")]
+    ExecutionFailed(String),
+
+    #[error("Failed to prove the mint transaction: {0}")]
+    ProvingFailed(String),
+
+    #[error("Block producer rejected the mint transaction: {0}")]
+    SubmissionFailed(Status),
+
+    #[error("Mint task failed: {0}")]
+    TaskFailed(#[from] JoinError),
+}
+
+impl From<TestnetFaucetError> for Status {
+    fn from(err: TestnetFaucetError) -> Self {
+        match err {
+            TestnetFaucetError::QuotaExceeded { retry_after_s, .. } => {
+                let mut status = Status::resource_exhausted(err.to_string());
+                status
+                    .metadata_mut()
+                    .insert(RETRY_AFTER_HEADER, MetadataValue::from(retry_after_s));
+                status
+            },
+            TestnetFaucetError::VerificationRejected => Status::permission_denied(err.to_string()),
+            TestnetFaucetError::VerificationFailed(_)
+            | TestnetFaucetError::ReferenceBlockFailed(_) => Status::unavailable(err.to_string()),
+            TestnetFaucetError::SubmissionFailed(status) => status,
+            _ => Status::internal(err.to_string()),
+        }
+    }
+}
@@ -0,0 +1,268 @@
+use std::path::PathBuf;
+
+use miden_lib::notes::create_p2id_note;
+use miden_node_block_producer::block::BlockInputs;
+use miden_node_proto::generated::{
+    block_producer::api_client as block_producer_client,
+    requests::{GetBlockInputsRequest, SubmitProvenTransactionRequest},
+    store::api_client as store_client,
+};
+use miden_node_utils::transport::ComponentChannel;
+use miden_objects::{
+    accounts::{Account, AccountData, AccountId, AuthData},
+    assembly::{ModuleAst, ProgramAst},
+    assets::FungibleAsset,
+    crypto::{dsa::rpo_falcon512::SecretKey, merkle::PartialMmr, rand::RpoRandomCoin},
+    notes::{Note, NoteId, NoteType},
+    transaction::{ChainMmr, InputNotes, ProvenTransaction, TransactionArgs, TransactionInputs},
+    utils::serde::Serializable,
+    vm::AdviceMap,
+    BlockHeader, Digest, Felt, Word,
+};
+use miden_tx::{DataStore, DataStoreError, ProvingOptions, TransactionExecutor, TransactionProver};
+use rand_chacha::{rand_core::SeedableRng, ChaCha20Rng};
+use tokio::sync::Mutex;
+use tracing::info;
+
+use crate::{errors::TestnetFaucetError, COMPONENT};
+
+/// Script of the mint transaction, distributing the asset to a note and authenticating the faucet.
+const DISTRIBUTE_SCRIPT: &str = "
+use.miden::contracts::faucets::basic_fungible->faucet
+use.miden::contracts::auth::basic->auth_tx
+
+begin
+    push.{recipient}
+    push.{note_type}
+    push.{tag}
+    push.{amount}
+    call.faucet::distribute
+
+    call.auth_tx::auth_tx_rpo_falcon512
+    dropw dropw
+end
+";
+
+/// Funds minted for an account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MintedFunds {
+    pub transaction_id: Digest,
+    pub note_id: NoteId,
+}
+
+// MINTER
+// ================================================================================================
+
+/// Mints the faucet's asset to the requesting accounts, proving the mint transactions in-process
+/// and submitting them to the block producer.
+pub struct Minter {
+    faucet_account_filepath: PathBuf,
+    asset_amount: u64,
+    /// The faucet's state, locked for the whole mint so the transactions are built on top of each
+    /// other.
+    faucet: Mutex<AccountData>,
+    secret_key: SecretKey,
+    store: store_client::ApiClient<ComponentChannel>,
+    block_producer: block_producer_client::ApiClient<ComponentChannel>,
+}
+
+impl Minter {
+    /// Loads the faucet account from `faucet_account_filepath`.
+    pub fn load(
+        faucet_account_filepath: PathBuf,
+        asset_amount: u64,
+        store: ComponentChannel,
+        block_producer: ComponentChannel,
+    ) -> Result<Self, TestnetFaucetError> {
+        let faucet = AccountData::read(&faucet_account_filepath)
+            .map_err(|err| TestnetFaucetError::InvalidFaucetAccount(err.to_string()))?;
+        if !faucet.account.is_faucet() {
+            return Err(TestnetFaucetError::InvalidFaucetAccount(format!(
+                "account {} is not a faucet",
+                faucet.account.id()
+            )));
+        }
+
+        let secret_key = match faucet.auth {
+            AuthData::RpoFalcon512Seed(seed) => {
+                let mut rng = ChaCha20Rng::from_seed(seed);
+                SecretKey::with_rng(&mut rng)
+            },
+        };
+
+        Ok(Self {
+            faucet_account_filepath,
+            asset_amount,
+            faucet: Mutex::new(faucet),
+            secret_key,
+            store: store_client::ApiClient::new(store),
+            block_producer: block_producer_client::ApiClient::new(block_producer),
+        })
+    }
+
+    /// Mints the configured amount to a public P2ID note for `target`.
+    ///
+    /// The faucet account file is updated once the block producer accepted the transaction.
+    pub async fn mint(&self, target: AccountId) -> Result<MintedFunds, TestnetFaucetError> {
+        let mut faucet = self.faucet.lock().await;
+
+        let (block_header, chain_mmr) = self.reference_block().await?;
+
+        let faucet_id = faucet.account.id();
+        let asset = FungibleAsset::new(faucet_id, self.asset_amount)
+            .map_err(|err| TestnetFaucetError::ExecutionFailed(err.to_string()))?;
+        let rng = RpoRandomCoin::new(rand::random::<[u64; 4]>().map(Felt::new));
+        let note = create_p2id_note(faucet_id, target, vec![asset.into()], NoteType::Public, rng)
+            .map_err(|err| TestnetFaucetError::ExecutionFailed(err.to_string()))?;
+        let note_id = note.id();
+
+        let account = faucet.account.clone();
+        let account_seed = faucet.account_seed.filter(|_| account.is_new());
+        let secret_key = self.secret_key.clone();
+        let (proven_tx, account) = tokio::task::spawn_blocking(move || {
+            let tx_inputs = TransactionInputs::new(
+                account,
+                account_seed,
+                block_header,
+                chain_mmr,
+                InputNotes::new(Vec::new()).expect("empty input notes are valid"),
+            )
+            .map_err(|err| TestnetFaucetError::ExecutionFailed(err.to_string()))?;
+
+            prove_mint(tx_inputs, &secret_key, note, asset.amount())
+        })
+        .await??;
+
+        let transaction_id = proven_tx.id().inner();
+        let request = SubmitProvenTransactionRequest {
+            transaction: proven_tx.to_bytes(),
+            fee_note: Vec::new(),
+        };
+        self.block_producer
+            .clone()
+            .submit_proven_transaction(request)
+            .await
+            .map_err(TestnetFaucetError::SubmissionFailed)?;
+
+        faucet.account = account;
+        faucet
+            .write(&self.faucet_account_filepath)
+            .map_err(TestnetFaucetError::SaveFaucetAccountFailed)?;
+
+        info!(
+            target: COMPONENT,
+            %target,
+            %transaction_id,
+            note_id = %note_id.inner(),
+            amount = self.asset_amount,
+            "Minted testnet funds"
+        );
+
+        Ok(MintedFunds { transaction_id, note_id })
+    }
+
+    /// Returns the header of the chain tip and the chain MMR it commits to, the reference block of
+    /// the mint transaction.
+    async fn reference_block(&self) -> Result<(BlockHeader, ChainMmr), TestnetFaucetError> {
+        let request = GetBlockInputsRequest { account_ids: vec![], nullifiers: vec![] };
+        let response = self
+            .store
+            .clone()
+            .get_block_inputs(request)
+            .await
+            .map_err(|status| TestnetFaucetError::ReferenceBlockFailed(status.to_string()))?;
+
+        let inputs = BlockInputs::try_from(response.into_inner())
+            .map_err(|err| TestnetFaucetError::ReferenceBlockFailed(err.to_string()))?;
+        let chain_mmr = ChainMmr::new(PartialMmr::from_peaks(inputs.chain_peaks), vec![])
+            .map_err(|err| TestnetFaucetError::ReferenceBlockFailed(err.to_string()))?;
+
+        Ok((inputs.block_header, chain_mmr))
+    }
+}
+
+/// Executes and proves the transaction distributing `amount` of the asset to `note` from the faucet
+/// of `tx_inputs`, returning it with the new state of the faucet.
+fn prove_mint(
+    tx_inputs: TransactionInputs,
+    secret_key: &SecretKey,
+    note: Note,
+    amount: u64,
+) -> Result<(ProvenTransaction, Account), TestnetFaucetError> {
+    let faucet_id = tx_inputs.account().id();
+    let block_ref = tx_inputs.block_header().block_num();
+    let mut account = tx_inputs.account().clone();
+
+    let mut executor = TransactionExecutor::new(MintDataStore { tx_inputs });
+    executor
+        .load_account(faucet_id)
+        .map_err(|err| TestnetFaucetError::ExecutionFailed(err.to_string()))?;
+
+    let recipient = note
+        .recipient_digest()
+        .iter()
+        .map(|felt| felt.as_int().to_string())
+        .collect::<Vec<_>>()
+        .join(".");
+    let script = DISTRIBUTE_SCRIPT
+        .replace("{recipient}", &recipient)
+        .replace("{note_type}", &Felt::new(note.metadata().note_type() as u64).to_string())
+        .replace("{tag}", &Felt::new(note.metadata().tag().inner().into()).to_string())
+        .replace("{amount}", &Felt::new(amount).to_string());
+    let script = ProgramAst::parse(&script).expect("distribute script is well-formed");
+
+    let pub_key: Word = secret_key.public_key().into();
+    let mut auth_inputs = secret_key.to_bytes();
+    auth_inputs.append(&mut pub_key.to_bytes());
+    let auth_inputs = auth_inputs.into_iter().map(|byte| Felt::new(byte.into())).collect();
+
+    let script = executor
+        .compile_tx_script(script, vec![(pub_key, auth_inputs)], vec![])
+        .map_err(|err| TestnetFaucetError::ExecutionFailed(err.to_string()))?;
+    let mut tx_args = TransactionArgs::new(Some(script), None, AdviceMap::new());
+    tx_args.extend_expected_output_notes(vec![note]);
+
+    let executed_tx = executor
+        .execute_transaction(faucet_id, block_ref, &[], tx_args)
+        .map_err(|err| TestnetFaucetError::ExecutionFailed(err.to_string()))?;
+    account
+        .apply_delta(executed_tx.account_delta())
+        .map_err(|err| TestnetFaucetError::ExecutionFailed(err.to_string()))?;
+
+    let proven_tx = TransactionProver::new(ProvingOptions::default())
+        .prove_transaction(executed_tx)
+        .map_err(|err| TestnetFaucetError::ProvingFailed(err.to_string()))?;
+
+    Ok((proven_tx, account))
+}
+
+/// Data store of a mint transaction, holding the faucet and the reference block.
+struct MintDataStore {
+    tx_inputs: TransactionInputs,
+}
+
+impl DataStore for MintDataStore {
+    fn get_transaction_inputs(
+        &self,
+        account_id: AccountId,
+        block_ref: u32,
+        _notes: &[NoteId],
+    ) -> Result<TransactionInputs, DataStoreError> {
+        if account_id != self.tx_inputs.account().id() {
+            return Err(DataStoreError::AccountNotFound(account_id));
+        }
+        if block_ref != self.tx_inputs.block_header().block_num() {
+            return Err(DataStoreError::BlockNotFound(block_ref));
+        }
+
+        Ok(self.tx_inputs.clone())
+    }
+
+    fn get_account_code(&self, account_id: AccountId) -> Result<ModuleAst, DataStoreError> {
+        if account_id != self.tx_inputs.account().id() {
+            return Err(DataStoreError::AccountNotFound(account_id));
+        }
+
+        Ok(self.tx_inputs.account().code().module().clone())
+    }
+}
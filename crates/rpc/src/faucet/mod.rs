@@ -0,0 +1,160 @@
+//! Testnet faucet, minting funds to the accounts requesting them through the RPC.
+//!
+//! A request is first approved by the verifier, e.g. the operator's captcha webhook, then counted
+//! towards the quotas of the account and of the IP address it was made from. The faucet then mints
+//! the funds to a public P2ID note, proving the transaction in-process and submitting it to the
+//! block producer.
+use std::{net::IpAddr, time::Instant};
+
+use miden_node_utils::transport::ComponentChannel;
+use miden_objects::accounts::AccountId;
+
+pub use self::minter::MintedFunds;
+use self::{
+    minter::Minter,
+    quota::Quota,
+    verifier::{VerificationRequest, Verifier, WebhookVerifier},
+};
+use crate::{config::TestnetFaucetConfig, errors::TestnetFaucetError};
+
+mod minter;
+mod quota;
+mod verifier;
+
+// TESTNET FAUCET
+// ================================================================================================
+
+pub struct TestnetFaucet {
+    admission: Admission,
+    minter: Minter,
+}
+
+impl TestnetFaucet {
+    /// Loads the faucet account of `config`, the mint transactions are based on the chain tip of
+    /// `store` and submitted to `block_producer`.
+    pub fn load(
+        config: &TestnetFaucetConfig,
+        store: ComponentChannel,
+        block_producer: ComponentChannel,
+    ) -> Result<Self, TestnetFaucetError> {
+        let minter = Minter::load(
+            config.faucet_account_filepath.clone(),
+            config.asset_amount,
+            store,
+            block_producer,
+        )?;
+        let verifier = config
+            .verification_url
+            .clone()
+            .map(|url| Box::new(WebhookVerifier::new(url)) as Box<dyn Verifier>);
+
+        let admission = Admission {
+            verifier,
+            account_quota: Quota::new("account", config.per_account),
+            ip_quota: Quota::new("IP address", config.per_ip),
+        };
+
+        Ok(Self { admission, minter })
+    }
+
+    /// Mints funds to `account_id`, if the request made from `ip` with the verification `token` is
+    /// approved and within the quotas.
+    pub async fn request_funds(
+        &self,
+        account_id: AccountId,
+        ip: Option<IpAddr>,
+        token: String,
+    ) -> Result<MintedFunds, TestnetFaucetError> {
+        self.admission.admit(account_id, ip, token, Instant::now()).await?;
+        self.minter.mint(account_id).await
+    }
+}
+
+/// Admission of the requests, before any funds are minted.
+struct Admission {
+    verifier: Option<Box<dyn Verifier>>,
+    account_quota: Quota<AccountId>,
+    ip_quota: Quota<IpAddr>,
+}
+
+impl Admission {
+    /// Checks the request with the verifier, then counts it towards the quotas.
+    async fn admit(
+        &self,
+        account_id: AccountId,
+        ip: Option<IpAddr>,
+        token: String,
+        now: Instant,
+    ) -> Result<(), TestnetFaucetError> {
+        if let Some(verifier) = &self.verifier {
+            verifier.verify(&VerificationRequest::new(account_id, ip, token)).await?;
+        }
+
+        if let Some(ip) = ip {
+            self.ip_quota.check(ip, now)?;
+        }
+        self.account_quota.check(account_id, now)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        net::{IpAddr, Ipv4Addr},
+        time::Instant,
+    };
+
+    use miden_objects::accounts::{AccountId, ACCOUNT_ID_REGULAR_ACCOUNT_IMMUTABLE_CODE_ON_CHAIN};
+
+    use super::{
+        quota::Quota,
+        verifier::{VerificationRequest, Verifier},
+        Admission,
+    };
+    use crate::{config::QuotaConfig, errors::TestnetFaucetError};
+
+    /// Approves the requests with the token `valid`.
+    struct TokenVerifier;
+
+    #[tonic::async_trait]
+    impl Verifier for TokenVerifier {
+        async fn verify(&self, request: &VerificationRequest) -> Result<(), TestnetFaucetError> {
+            if request.token == "valid" {
+                Ok(())
+            } else {
+                Err(TestnetFaucetError::VerificationRejected)
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn rejected_requests_do_not_count_towards_quotas() {
+        let quota = QuotaConfig { max_requests: 1, period_secs: 60 };
+        let admission = Admission {
+            verifier: Some(Box::new(TokenVerifier)),
+            account_quota: Quota::new("account", quota),
+            ip_quota: Quota::new("IP address", quota),
+        };
+        let account_id =
+            AccountId::try_from(ACCOUNT_ID_REGULAR_ACCOUNT_IMMUTABLE_CODE_ON_CHAIN).unwrap();
+        let ip = Some(IpAddr::V4(Ipv4Addr::LOCALHOST));
+        let now = Instant::now();
+
+        let result = admission.admit(account_id, ip, "invalid".to_string(), now).await;
+        assert!(matches!(result, Err(TestnetFaucetError::VerificationRejected)));
+
+        admission.admit(account_id, ip, "valid".to_string(), now).await.unwrap();
+
+        let result = admission.admit(account_id, None, "valid".to_string(), now).await;
+        assert!(matches!(
+            result,
+            Err(TestnetFaucetError::QuotaExceeded { scope: "account", .. })
+        ));
+
+        let result = admission.admit(account_id, ip, "valid".to_string(), now).await;
+        assert!(matches!(
+            result,
+            Err(TestnetFaucetError::QuotaExceeded { scope: "IP address", .. })
+        ));
+    }
+}
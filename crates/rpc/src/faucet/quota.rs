@@ -0,0 +1,81 @@
+use std::{
+    collections::{BTreeMap, VecDeque},
+    sync::Mutex,
+    time::Instant,
+};
+
+use crate::{config::QuotaConfig, errors::TestnetFaucetError};
+
+/// Tracks the requests made for each key, e.g. an account or an IP address, over a sliding window.
+pub struct Quota<K> {
+    /// Name of the keys, used in the error returned when the quota is exceeded
+    scope: &'static str,
+    config: QuotaConfig,
+    requests: Mutex<BTreeMap<K, VecDeque<Instant>>>,
+}
+
+impl<K: Ord> Quota<K> {
+    pub fn new(scope: &'static str, config: QuotaConfig) -> Self {
+        Self {
+            scope,
+            config,
+            requests: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// Records a request for `key` made at `now`, or returns an error if `key` already reached the
+    /// maximum number of requests in the current period.
+    pub fn check(&self, key: K, now: Instant) -> Result<(), TestnetFaucetError> {
+        let period = self.config.period();
+        let mut requests = self.requests.lock().expect("lock is not poisoned");
+
+        // forget the requests which are out of the window
+        requests.retain(|_, times| {
+            while times.front().is_some_and(|time| now.duration_since(*time) >= period) {
+                times.pop_front();
+            }
+            !times.is_empty()
+        });
+
+        let times = requests.entry(key).or_default();
+        if times.len() >= self.config.max_requests {
+            let retry_in = times
+                .front()
+                .map(|oldest| period.saturating_sub(now.duration_since(*oldest)))
+                .unwrap_or(period);
+            return Err(TestnetFaucetError::QuotaExceeded {
+                scope: self.scope,
+                retry_after_s: retry_in.as_secs().max(1),
+            });
+        }
+
+        times.push_back(now);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::Quota;
+    use crate::{config::QuotaConfig, errors::TestnetFaucetError};
+
+    #[test]
+    fn requests_are_limited_per_key() {
+        let quota = Quota::new("account", QuotaConfig { max_requests: 2, period_secs: 60 });
+        let start = Instant::now();
+
+        assert!(quota.check(1, start).is_ok());
+        assert!(quota.check(1, start + Duration::from_secs(10)).is_ok());
+        assert!(matches!(
+            quota.check(1, start + Duration::from_secs(20)),
+            Err(TestnetFaucetError::QuotaExceeded { scope: "account", retry_after_s: 40 })
+        ));
+        assert!(quota.check(2, start + Duration::from_secs(20)).is_ok());
+
+        // the first request leaves the window
+        assert!(quota.check(1, start + Duration::from_secs(60)).is_ok());
+    }
+}
@@ -0,0 +1,83 @@
+use std::{net::IpAddr, time::Duration};
+
+use hyper::{client::HttpConnector, header::CONTENT_TYPE, Body, Client, Method};
+use miden_objects::accounts::AccountId;
+use serde::Serialize;
+
+use crate::errors::TestnetFaucetError;
+
+/// Time allowed to the verification webhook to answer.
+const VERIFICATION_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Request for testnet funds, as submitted to the verifier.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct VerificationRequest {
+    /// Account receiving the funds, in hex
+    pub account_id: String,
+    /// IP address the request was made from, if known
+    pub ip: Option<IpAddr>,
+    /// Token provided by the client, e.g. a captcha response
+    pub token: String,
+}
+
+impl VerificationRequest {
+    pub fn new(account_id: AccountId, ip: Option<IpAddr>, token: String) -> Self {
+        Self {
+            account_id: account_id.to_hex(),
+            ip,
+            token,
+        }
+    }
+}
+
+// VERIFIER
+// ================================================================================================
+
+/// Approves the requests for testnet funds before they are counted towards the quotas.
+#[tonic::async_trait]
+pub trait Verifier: Send + Sync {
+    async fn verify(&self, request: &VerificationRequest) -> Result<(), TestnetFaucetError>;
+}
+
+/// Verifier posting the requests as JSON to the operator's webhook.
+///
+/// The request is approved if the webhook answers with a success status, and rejected if it
+/// answers with a client error status.
+pub struct WebhookVerifier {
+    url: String,
+    client: Client<HttpConnector>,
+}
+
+impl WebhookVerifier {
+    pub fn new(url: String) -> Self {
+        Self { url, client: Client::new() }
+    }
+}
+
+#[tonic::async_trait]
+impl Verifier for WebhookVerifier {
+    async fn verify(&self, request: &VerificationRequest) -> Result<(), TestnetFaucetError> {
+        let body = serde_json::to_vec(request)
+            .map_err(|err| TestnetFaucetError::VerificationFailed(err.to_string()))?;
+        let request = hyper::Request::builder()
+            .method(Method::POST)
+            .uri(&self.url)
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from(body))
+            .map_err(|err| TestnetFaucetError::VerificationFailed(err.to_string()))?;
+
+        let response = tokio::time::timeout(VERIFICATION_TIMEOUT, self.client.request(request))
+            .await
+            .map_err(|_| TestnetFaucetError::VerificationFailed("timed out".to_string()))?
+            .map_err(|err| TestnetFaucetError::VerificationFailed(err.to_string()))?;
+
+        let status = response.status();
+        if status.is_success() {
+            Ok(())
+        } else if status.is_client_error() {
+            Err(TestnetFaucetError::VerificationRejected)
+        } else {
+            Err(TestnetFaucetError::VerificationFailed(format!("unexpected status {status}")))
+        }
+    }
+}
@@ -0,0 +1,22 @@
+//! Helpers shared by the layers of the RPC server inspecting the gRPC requests.
+
+/// Returns the gRPC method of a request from its path, the last segment of the path, e.g.
+/// `SyncState` for `/rpc.Api/SyncState`.
+pub(crate) fn grpc_method(path: &str) -> &str {
+    path.rsplit('/').next().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grpc_method_is_the_last_segment_of_the_path() {
+        assert_eq!(grpc_method("/rpc.Api/SyncState"), "SyncState");
+        assert_eq!(
+            grpc_method("/admin.Api/SubmitPriorityTransaction"),
+            "SubmitPriorityTransaction"
+        );
+        assert_eq!(grpc_method(""), "");
+    }
+}
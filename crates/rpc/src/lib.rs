@@ -1,5 +1,15 @@
+mod access_control;
+mod audit;
+mod cache_validation;
 pub mod config;
+pub mod errors;
+mod faucet;
+mod grpc;
+mod load_shedding;
+mod maintenance;
+mod retry;
 pub mod server;
+mod usage;
 
 // CONSTANTS
 // =================================================================================================
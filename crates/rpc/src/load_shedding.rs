@@ -0,0 +1,183 @@
+//! Load shedding, rejecting the low priority requests while the RPC is overloaded so that bursts of
+//! read requests don't starve the submission of transactions.
+use std::{
+    future::{ready, Future},
+    pin::Pin,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+};
+
+use tonic::{
+    body::BoxBody,
+    codegen::{http, Service},
+    metadata::MetadataValue,
+    Status,
+};
+use tower::Layer;
+use tracing::debug;
+
+use crate::{
+    config::LoadSheddingConfig, grpc::grpc_method, maintenance::RETRY_AFTER_HEADER, COMPONENT,
+};
+
+// LOAD SHEDDER
+// ================================================================================================
+
+/// Tracks the cost of the requests being served, and rejects the low priority requests exceeding
+/// the configured maximum.
+#[derive(Debug)]
+pub struct LoadShedder {
+    config: LoadSheddingConfig,
+    in_flight_cost: AtomicU64,
+}
+
+impl LoadShedder {
+    pub fn new(config: LoadSheddingConfig) -> Self {
+        Self {
+            config,
+            in_flight_cost: AtomicU64::new(0),
+        }
+    }
+
+    /// Admits a request to the endpoint `method`, its cost is released when the returned permit is
+    /// dropped.
+    ///
+    /// Returns an `UNAVAILABLE` status with the `retry-after` header if the endpoint isn't a
+    /// priority endpoint and the request would exceed the maximum in-flight cost.
+    pub fn admit(self: &Arc<Self>, method: &str) -> Result<LoadPermit, Status> {
+        let cost = self.config.cost(method);
+        let in_flight_cost = self.in_flight_cost.fetch_add(cost, Ordering::AcqRel) + cost;
+        let permit = LoadPermit { shedder: self.clone(), cost };
+
+        if in_flight_cost > self.config.max_in_flight_cost && !self.config.is_priority(method) {
+            debug!(target: COMPONENT, method, in_flight_cost, "Request shed");
+
+            let mut status = Status::unavailable("Node is overloaded");
+            status
+                .metadata_mut()
+                .insert(RETRY_AFTER_HEADER, MetadataValue::from(self.config.retry_after_s));
+
+            return Err(status);
+        }
+
+        Ok(permit)
+    }
+}
+
+/// Cost of an admitted request, released when the request has been served.
+#[derive(Debug)]
+pub struct LoadPermit {
+    shedder: Arc<LoadShedder>,
+    cost: u64,
+}
+
+impl Drop for LoadPermit {
+    fn drop(&mut self) {
+        self.shedder.in_flight_cost.fetch_sub(self.cost, Ordering::AcqRel);
+    }
+}
+
+// LOAD SHEDDING LAYER
+// ================================================================================================
+
+/// Layer of the RPC server admitting the requests through the [LoadShedder], a pass-through if
+/// load shedding is disabled.
+#[derive(Debug, Clone)]
+pub struct LoadSheddingLayer {
+    shedder: Option<Arc<LoadShedder>>,
+}
+
+impl LoadSheddingLayer {
+    pub fn new(config: Option<&LoadSheddingConfig>) -> Self {
+        Self {
+            shedder: config.map(|config| Arc::new(LoadShedder::new(config.clone()))),
+        }
+    }
+}
+
+impl<S> Layer<S> for LoadSheddingLayer {
+    type Service = LoadSheddingService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        LoadSheddingService { inner, shedder: self.shedder.clone() }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LoadSheddingService<S> {
+    inner: S,
+    shedder: Option<Arc<LoadShedder>>,
+}
+
+impl<S, B> Service<http::Request<B>> for LoadSheddingService<S>
+where
+    S: Service<http::Request<B>, Response = http::Response<BoxBody>>,
+    S::Future: Send + 'static,
+    S::Error: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: http::Request<B>) -> Self::Future {
+        let permit = match &self.shedder {
+            Some(shedder) => {
+                let method = grpc_method(request.uri().path());
+                match shedder.admit(method) {
+                    Ok(permit) => Some(permit),
+                    Err(status) => return Box::pin(ready(Ok(status.to_http()))),
+                }
+            },
+            None => None,
+        };
+
+        let response = self.inner.call(request);
+        Box::pin(async move {
+            let response = response.await;
+            drop(permit);
+            response
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tonic::Code;
+
+    use super::*;
+
+    #[test]
+    fn low_priority_requests_are_shed() {
+        let config = LoadSheddingConfig {
+            max_in_flight_cost: 10,
+            retry_after_s: 3,
+            ..Default::default()
+        };
+        let shedder = Arc::new(LoadShedder::new(config));
+
+        // `SyncState` costs 10, filling the capacity
+        let sync_state = shedder.admit("SyncState").unwrap();
+        assert_eq!(shedder.in_flight_cost.load(Ordering::Acquire), 10);
+
+        let status = shedder.admit("GetBlockHeaderByNumber").unwrap_err();
+        assert_eq!(status.code(), Code::Unavailable);
+        assert_eq!(status.metadata().get(RETRY_AFTER_HEADER).unwrap(), "3");
+        assert_eq!(shedder.in_flight_cost.load(Ordering::Acquire), 10);
+
+        // Transactions are always admitted
+        let submit = shedder.admit("SubmitProvenTransaction").unwrap();
+        assert_eq!(shedder.in_flight_cost.load(Ordering::Acquire), 11);
+
+        drop(sync_state);
+        drop(submit);
+        assert_eq!(shedder.in_flight_cost.load(Ordering::Acquire), 0);
+        assert!(shedder.admit("GetBlockHeaderByNumber").is_ok());
+    }
+}
@@ -0,0 +1,83 @@
+//! Maintenance mode, during which the client requests are rejected so the operator can snapshot or
+//! upgrade the store.
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use tonic::{metadata::MetadataValue, Status};
+
+/// Number of seconds after which the clients are told to retry, if the operator doesn't set it.
+pub const DEFAULT_RETRY_AFTER_S: u32 = 60;
+
+/// Name of the header telling the clients when to retry a request rejected during maintenance.
+pub const RETRY_AFTER_HEADER: &str = "retry-after";
+
+// MAINTENANCE MODE
+// ================================================================================================
+
+/// Whether the node is in maintenance mode, shared by the RPC and the admin APIs.
+#[derive(Debug, Default)]
+pub struct MaintenanceMode {
+    /// Number of seconds after which the clients should retry, zero when not in maintenance.
+    retry_after_s: AtomicU32,
+}
+
+impl MaintenanceMode {
+    /// Enters maintenance mode, the clients are told to retry after `retry_after_s` seconds, or
+    /// [DEFAULT_RETRY_AFTER_S] if zero.
+    pub fn enable(&self, retry_after_s: u32) {
+        let retry_after_s = if retry_after_s == 0 {
+            DEFAULT_RETRY_AFTER_S
+        } else {
+            retry_after_s
+        };
+        self.retry_after_s.store(retry_after_s, Ordering::Release);
+    }
+
+    /// Leaves maintenance mode.
+    pub fn disable(&self) {
+        self.retry_after_s.store(0, Ordering::Release);
+    }
+
+    /// Returns an `UNAVAILABLE` status with the `retry-after` header if the node is in maintenance
+    /// mode.
+    pub fn check(&self) -> Result<(), Status> {
+        let retry_after_s = self.retry_after_s.load(Ordering::Acquire);
+        if retry_after_s == 0 {
+            return Ok(());
+        }
+
+        let mut status = Status::unavailable("Node is in maintenance mode");
+        status
+            .metadata_mut()
+            .insert(RETRY_AFTER_HEADER, MetadataValue::from(retry_after_s));
+
+        Err(status)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tonic::Code;
+
+    use super::*;
+
+    #[test]
+    fn requests_are_rejected_during_maintenance() {
+        let maintenance = MaintenanceMode::default();
+        assert!(maintenance.check().is_ok());
+
+        maintenance.enable(0);
+        let status = maintenance.check().unwrap_err();
+        assert_eq!(status.code(), Code::Unavailable);
+        assert_eq!(
+            status.metadata().get(RETRY_AFTER_HEADER).unwrap(),
+            DEFAULT_RETRY_AFTER_S.to_string().as_str()
+        );
+
+        maintenance.enable(5);
+        let status = maintenance.check().unwrap_err();
+        assert_eq!(status.metadata().get(RETRY_AFTER_HEADER).unwrap(), "5");
+
+        maintenance.disable();
+        assert!(maintenance.check().is_ok());
+    }
+}
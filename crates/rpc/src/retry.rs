@@ -0,0 +1,285 @@
+//! Retries and hedging of the reads forwarded to the store.
+//!
+//! A read failing with `UNAVAILABLE`, e.g. while the store restarts, is retried after a backoff,
+//! and a read the store is slow to answer, e.g. while it checkpoints its database, can be hedged
+//! by a second attempt. Both are limited by a [RetryBudget], so that they can't turn into a retry
+//! storm when the store is overloaded.
+use std::{
+    future::Future,
+    sync::atomic::{AtomicU64, Ordering},
+    time::Instant,
+};
+
+use miden_node_utils::deadline::propagate_deadline;
+use tonic::{metadata::MetadataMap, Code, Extensions, Request, Response, Status};
+use tracing::debug;
+
+use crate::{config::StoreRetryConfig, COMPONENT};
+
+// RETRY BUDGET
+// ================================================================================================
+
+/// Units of the budget withdrawn by a retry, so the requests can deposit a fraction of a retry.
+const RETRY_COST: u64 = 100;
+
+/// Budget of the retries and hedged attempts, a token bucket filled by the requests.
+///
+/// Each request deposits `budget_percent` hundredths of a retry, up to a balance of
+/// `budget_reserve` retries, which is also the initial balance.
+#[derive(Debug)]
+pub struct RetryBudget {
+    deposit: u64,
+    max_balance: u64,
+    balance: AtomicU64,
+}
+
+impl RetryBudget {
+    pub fn new(budget_percent: u32, budget_reserve: u32) -> Self {
+        let max_balance = u64::from(budget_reserve.max(1)) * RETRY_COST;
+        Self {
+            deposit: u64::from(budget_percent),
+            max_balance,
+            balance: AtomicU64::new(max_balance),
+        }
+    }
+
+    /// Deposits the share of a request to the budget.
+    pub fn deposit(&self) {
+        let _ = self.balance.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |balance| {
+            Some((balance + self.deposit).min(self.max_balance))
+        });
+    }
+
+    /// Withdraws a retry from the budget, returns `false` if the budget is exhausted.
+    pub fn withdraw(&self) -> bool {
+        self.balance
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |balance| {
+                balance.checked_sub(RETRY_COST)
+            })
+            .is_ok()
+    }
+}
+
+// STORE RETRY
+// ================================================================================================
+
+/// Sends the reads to the store, retrying and hedging them as configured.
+#[derive(Debug)]
+pub struct StoreRetry {
+    config: StoreRetryConfig,
+    budget: RetryBudget,
+}
+
+impl StoreRetry {
+    pub fn new(config: StoreRetryConfig) -> Self {
+        let budget = RetryBudget::new(config.budget_percent, config.budget_reserve);
+        Self { config, budget }
+    }
+
+    /// Sends `request` to the store with `call`, until `deadline`, returning the first successful
+    /// response or the error of the last attempt.
+    ///
+    /// `call` must be idempotent, as it can be called multiple times and concurrently.
+    pub async fn call<T, R, F, Fut>(
+        &self,
+        method: &str,
+        request: Request<T>,
+        deadline: Instant,
+        call: F,
+    ) -> Result<Response<R>, Status>
+    where
+        T: Clone,
+        F: Fn(Request<T>) -> Fut,
+        Fut: Future<Output = Result<Response<R>, Status>>,
+    {
+        let (metadata, _, message) = request.into_parts();
+        let attempt = || attempt_request(&metadata, &message, deadline);
+        self.budget.deposit();
+
+        let mut backoff = self.config.backoff();
+        let mut attempts = 1;
+        loop {
+            let result = match self.config.hedge_delay() {
+                Some(hedge_delay) => {
+                    let first = call(attempt()?);
+                    tokio::pin!(first);
+                    tokio::select! {
+                        result = &mut first => result,
+                        _ = tokio::time::sleep(hedge_delay) => {
+                            self.hedge(method, first, &call, attempt).await
+                        },
+                    }
+                },
+                None => call(attempt()?).await,
+            };
+
+            match result {
+                Err(status)
+                    if status.code() == Code::Unavailable
+                        && attempts < self.config.max_attempts
+                        && Instant::now() + backoff < deadline =>
+                {
+                    if !self.budget.withdraw() {
+                        debug!(target: COMPONENT, method, "Retry budget exhausted");
+                        return Err(status);
+                    }
+                    debug!(target: COMPONENT, method, attempts, %status, "Retrying store request");
+                    tokio::time::sleep(backoff).await;
+                    backoff *= 2;
+                    attempts += 1;
+                },
+                result => return result,
+            }
+        }
+    }
+
+    /// Sends a second attempt while the `first` one is pending, if the budget allows it, and
+    /// returns the first successful response of both.
+    async fn hedge<T, R, F, Fut>(
+        &self,
+        method: &str,
+        first: impl Future<Output = Result<Response<R>, Status>>,
+        call: &F,
+        attempt: impl Fn() -> Result<Request<T>, Status>,
+    ) -> Result<Response<R>, Status>
+    where
+        F: Fn(Request<T>) -> Fut,
+        Fut: Future<Output = Result<Response<R>, Status>>,
+    {
+        tokio::pin!(first);
+        if !self.budget.withdraw() {
+            debug!(target: COMPONENT, method, "Retry budget exhausted, not hedging");
+            return first.await;
+        }
+
+        let second = match attempt() {
+            Ok(request) => call(request),
+            Err(_) => return first.await,
+        };
+        tokio::pin!(second);
+        debug!(target: COMPONENT, method, "Hedging store request");
+
+        tokio::select! {
+            result = &mut first => match result {
+                Ok(response) => Ok(response),
+                Err(_) => second.await,
+            },
+            result = &mut second => match result {
+                Ok(response) => Ok(response),
+                Err(_) => first.await,
+            },
+        }
+    }
+}
+
+/// Returns a new attempt of the request, its timeout being the time remaining until `deadline`.
+fn attempt_request<T: Clone>(
+    metadata: &MetadataMap,
+    message: &T,
+    deadline: Instant,
+) -> Result<Request<T>, Status> {
+    let mut request = Request::from_parts(metadata.clone(), Extensions::default(), message.clone());
+    propagate_deadline(&mut request, deadline)?;
+    Ok(request)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::atomic::{AtomicU32, Ordering},
+        time::Duration,
+    };
+
+    use super::*;
+
+    fn config() -> StoreRetryConfig {
+        StoreRetryConfig {
+            backoff_ms: 1,
+            budget_reserve: 2,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn budget_is_refilled_by_requests() {
+        let budget = RetryBudget::new(50, 1);
+        assert!(budget.withdraw());
+        assert!(!budget.withdraw());
+
+        budget.deposit();
+        assert!(!budget.withdraw());
+        budget.deposit();
+        assert!(budget.withdraw());
+
+        // the balance is capped by the reserve
+        for _ in 0..10 {
+            budget.deposit();
+        }
+        assert!(budget.withdraw());
+        assert!(!budget.withdraw());
+    }
+
+    #[tokio::test]
+    async fn unavailable_requests_are_retried_within_budget() {
+        let retry = StoreRetry::new(config());
+        let deadline = Instant::now() + Duration::from_secs(10);
+        let attempts = AtomicU32::new(0);
+        let call = |request: Request<u32>| {
+            let attempt = attempts.fetch_add(1, Ordering::Relaxed);
+            async move {
+                match attempt {
+                    0 => Err(Status::unavailable("store restarting")),
+                    _ => Ok(Response::new(request.into_inner())),
+                }
+            }
+        };
+
+        let response = retry.call("GetChainTip", Request::new(7), deadline, call).await.unwrap();
+        assert_eq!(response.into_inner(), 7);
+        assert_eq!(attempts.load(Ordering::Relaxed), 2);
+
+        // the other errors aren't retried
+        let attempts = AtomicU32::new(0);
+        let call = |_: Request<u32>| {
+            attempts.fetch_add(1, Ordering::Relaxed);
+            async { Err::<Response<u32>, _>(Status::not_found("no such block")) }
+        };
+        let status = retry.call("GetChainTip", Request::new(7), deadline, call).await.unwrap_err();
+        assert_eq!(status.code(), Code::NotFound);
+        assert_eq!(attempts.load(Ordering::Relaxed), 1);
+
+        // a failing store exhausts the budget, the requests are then attempted once
+        let attempts = AtomicU32::new(0);
+        let call = |_: Request<u32>| {
+            attempts.fetch_add(1, Ordering::Relaxed);
+            async { Err::<Response<u32>, _>(Status::unavailable("store down")) }
+        };
+        for _ in 0..3 {
+            let status =
+                retry.call("GetChainTip", Request::new(7), deadline, call).await.unwrap_err();
+            assert_eq!(status.code(), Code::Unavailable);
+        }
+        assert_eq!(attempts.load(Ordering::Relaxed), 4);
+    }
+
+    #[tokio::test]
+    async fn slow_requests_are_hedged() {
+        let retry = StoreRetry::new(StoreRetryConfig { hedge_delay_ms: 10, ..config() });
+        let deadline = Instant::now() + Duration::from_secs(10);
+        let attempts = AtomicU32::new(0);
+        let call = |request: Request<u32>| {
+            let attempt = attempts.fetch_add(1, Ordering::Relaxed);
+            async move {
+                if attempt == 0 {
+                    tokio::time::sleep(Duration::from_secs(5)).await;
+                }
+                Ok(Response::new(request.into_inner() + attempt))
+            }
+        };
+
+        let started = Instant::now();
+        let response = retry.call("SyncState", Request::new(7), deadline, call).await.unwrap();
+        assert_eq!(response.into_inner(), 8);
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+}
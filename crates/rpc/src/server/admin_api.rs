@@ -0,0 +1,396 @@
+use std::{net::SocketAddr, sync::Arc};
+
+use miden_node_proto::{
+    domain::{
+        pagination::{next_page_cursor, PageTokens},
+        transactions::TransactionRejection,
+    },
+    generated::{
+        admin::api_server,
+        block_producer::api_client as block_producer_client,
+        requests::{
+            FlushDatabaseRequest, GetAuditLogRequest, GetBatchRequest, GetBlockTimelineRequest,
+            GetMempoolContentsRequest, GetUsageRequest, SetBlockProductionPausedRequest,
+            SetLogFilterRequest, SetMaintenanceModeRequest, SetSubmissionPolicyRequest,
+            SubmitPriorityTransactionRequest,
+        },
+        responses::{
+            GetAuditLogResponse, GetBatchResponse, GetBlockTimelineResponse,
+            GetMempoolContentsResponse, GetUsageResponse, SetBlockProductionPausedResponse,
+            SetLogFilterResponse, SetMaintenanceModeResponse, SetSubmissionPolicyResponse,
+            SubmitPriorityTransactionResponse,
+        },
+        store::api_client as store_client,
+    },
+};
+use miden_node_utils::{
+    formatting::format_opt, logging::set_log_filter, transport::ComponentChannel,
+};
+use miden_objects::{
+    transaction::ProvenTransaction, utils::serde::Deserializable, MIN_PROOF_SECURITY_LEVEL,
+};
+use miden_tx::TransactionVerifier;
+use tonic::{Request, Response, Status};
+use tracing::{debug, info, instrument};
+
+use crate::{
+    audit::{AuditEntry, AuditLog, AUDIT_LOG_PAGE_SIZE},
+    maintenance::MaintenanceMode,
+    usage::UsageTracker,
+    COMPONENT,
+};
+
+// ADMIN API
+// ================================================================================================
+
+/// Operator facing API, served on its own endpoint.
+pub struct AdminApi {
+    store: store_client::ApiClient<ComponentChannel>,
+    block_producer: block_producer_client::ApiClient<ComponentChannel>,
+    maintenance: Arc<MaintenanceMode>,
+    audit_log: Option<AuditLog>,
+    usage: Option<Arc<UsageTracker>>,
+    page_tokens: PageTokens,
+}
+
+impl AdminApi {
+    pub(super) fn new(
+        store: ComponentChannel,
+        block_producer: ComponentChannel,
+        maintenance: Arc<MaintenanceMode>,
+        audit_log: Option<AuditLog>,
+        usage: Option<Arc<UsageTracker>>,
+    ) -> Self {
+        Self {
+            store: store_client::ApiClient::new(store),
+            block_producer: block_producer_client::ApiClient::new(block_producer),
+            maintenance,
+            audit_log,
+            usage,
+            page_tokens: PageTokens::random(),
+        }
+    }
+
+    /// Records the outcome of `operation` in the audit log, if enabled.
+    async fn record<T>(
+        &self,
+        operation: &str,
+        peer: Option<SocketAddr>,
+        result: &Result<T, Status>,
+    ) {
+        if let Some(audit_log) = &self.audit_log {
+            audit_log.record(AuditEntry::new(operation, peer, None, result)).await;
+        }
+    }
+
+    async fn update_block_production(&self, paused: bool) -> Result<(), Status> {
+        self.block_producer
+            .clone()
+            .set_block_production_paused(SetBlockProductionPausedRequest { paused })
+            .await?;
+
+        Ok(())
+    }
+
+    /// Pauses the block production and flushes the store, once the client requests are rejected.
+    async fn enter_maintenance(&self, retry_after_s: u32) -> Result<(), Status> {
+        self.maintenance.enable(retry_after_s);
+        self.update_block_production(true).await?;
+        self.store.clone().flush_database(FlushDatabaseRequest {}).await?;
+
+        Ok(())
+    }
+
+    /// Replaces the log filter of the RPC, then of the store and the block producer. Returns the
+    /// filter replaced on the RPC.
+    async fn update_log_filter(&self, filter: String) -> Result<String, Status> {
+        // An invalid filter is rejected before reaching the other components
+        let previous_filter = set_log_filter(&filter)?;
+        self.store
+            .clone()
+            .set_log_filter(SetLogFilterRequest { filter: filter.clone() })
+            .await?;
+        self.block_producer
+            .clone()
+            .set_log_filter(SetLogFilterRequest { filter })
+            .await?;
+
+        Ok(previous_filter)
+    }
+
+    /// Resumes the block production, then serves the client requests again.
+    async fn leave_maintenance(&self) -> Result<(), Status> {
+        self.update_block_production(false).await?;
+        self.maintenance.disable();
+
+        Ok(())
+    }
+}
+
+// FIXME: remove the allow when the upstream clippy issue is fixed:
+// https://github.com/rust-lang/rust-clippy/issues/12281
+#[allow(clippy::blocks_in_conditions)]
+#[tonic::async_trait]
+impl api_server::Api for AdminApi {
+    /// Returns a page of the entries of the audit log, the most recent first. The query itself is
+    /// recorded as well.
+    #[instrument(target = "miden-rpc", name = "admin:get_audit_log", skip_all, err)]
+    async fn get_audit_log(
+        &self,
+        request: Request<GetAuditLogRequest>,
+    ) -> Result<Response<GetAuditLogResponse>, Status> {
+        debug!(target: COMPONENT, request = ?request.get_ref());
+
+        let Some(audit_log) = &self.audit_log else {
+            return Err(Status::failed_precondition("Audit log is disabled"));
+        };
+
+        let peer = request.remote_addr();
+        let GetAuditLogRequest { since_ms, operation, page } = request.into_inner();
+
+        // The tokens are only valid for the filters of the first page, the cursor is the id of
+        // the last entry of the previous page
+        let scope = audit_log_scope(since_ms, operation.as_deref());
+        let result = match self.page_tokens.decode::<u64>(&scope, page, AUDIT_LOG_PAGE_SIZE) {
+            Ok(page) => audit_log
+                .query(since_ms, operation, page.cursor, page.size + 1)
+                .await
+                .map(|mut entries| {
+                    let next = next_page_cursor(&mut entries, page.size, |(id, _)| *id);
+                    Response::new(GetAuditLogResponse {
+                        entries: entries.into_iter().map(|(_, entry)| entry.into()).collect(),
+                        page: Some(self.page_tokens.page(&scope, next.as_ref(), None)),
+                    })
+                })
+                .map_err(|err| Status::internal(err.to_string())),
+            Err(err) => Err(Status::invalid_argument(err.to_string())),
+        };
+
+        self.record("GetAuditLog", peer, &result).await;
+
+        result
+    }
+
+    /// Pauses or resumes the block production. Pausing returns once the block being built, if
+    /// any, has been applied to the store.
+    #[instrument(target = "miden-rpc", name = "admin:set_block_production_paused", skip_all, err)]
+    async fn set_block_production_paused(
+        &self,
+        request: Request<SetBlockProductionPausedRequest>,
+    ) -> Result<Response<SetBlockProductionPausedResponse>, Status> {
+        debug!(target: COMPONENT, request = ?request.get_ref());
+
+        let peer = request.remote_addr();
+        let paused = request.into_inner().paused;
+
+        let result = self
+            .update_block_production(paused)
+            .await
+            .map(|_| Response::new(SetBlockProductionPausedResponse {}));
+
+        self.record("SetBlockProductionPaused", peer, &result).await;
+        info!(target: COMPONENT, paused, success = result.is_ok(), "Block production updated");
+
+        result
+    }
+
+    /// Returns the transactions accepted by the block producer and not yet included in a block.
+    #[instrument(target = "miden-rpc", name = "admin:get_mempool_contents", skip_all, err)]
+    async fn get_mempool_contents(
+        &self,
+        request: Request<GetMempoolContentsRequest>,
+    ) -> Result<Response<GetMempoolContentsResponse>, Status> {
+        debug!(target: COMPONENT, request = ?request.get_ref());
+
+        let peer = request.remote_addr();
+        let result = self.block_producer.clone().get_mempool_contents(request.into_inner()).await;
+
+        self.record("GetMempoolContents", peer, &result).await;
+
+        result
+    }
+
+    /// Enters or leaves maintenance mode.
+    ///
+    /// Entering maintenance mode rejects the client requests, pauses the block production and
+    /// flushes the store, so its database file can be copied or replaced once this returns.
+    /// Leaving maintenance mode resumes the block production.
+    #[instrument(target = "miden-rpc", name = "admin:set_maintenance_mode", skip_all, err)]
+    async fn set_maintenance_mode(
+        &self,
+        request: Request<SetMaintenanceModeRequest>,
+    ) -> Result<Response<SetMaintenanceModeResponse>, Status> {
+        debug!(target: COMPONENT, request = ?request.get_ref());
+
+        let peer = request.remote_addr();
+        let SetMaintenanceModeRequest { enabled, retry_after_s } = request.into_inner();
+
+        let result = if enabled {
+            self.enter_maintenance(retry_after_s).await
+        } else {
+            self.leave_maintenance().await
+        }
+        .map(|_| Response::new(SetMaintenanceModeResponse {}));
+
+        self.record("SetMaintenanceMode", peer, &result).await;
+        info!(target: COMPONENT, enabled, success = result.is_ok(), "Maintenance mode updated");
+
+        result
+    }
+
+    /// Returns the transactions and the proving status of a batch of the block producer.
+    #[instrument(target = "miden-rpc", name = "admin:get_batch", skip_all, err)]
+    async fn get_batch(
+        &self,
+        request: Request<GetBatchRequest>,
+    ) -> Result<Response<GetBatchResponse>, Status> {
+        debug!(target: COMPONENT, request = ?request.get_ref());
+
+        let peer = request.remote_addr();
+        let result = self.block_producer.clone().get_batch(request.into_inner()).await;
+
+        self.record("GetBatch", peer, &result).await;
+
+        result
+    }
+
+    /// Returns the stages reached by a block of the block producer while it was produced, with
+    /// their timestamps.
+    #[instrument(target = "miden-rpc", name = "admin:get_block_timeline", skip_all, err)]
+    async fn get_block_timeline(
+        &self,
+        request: Request<GetBlockTimelineRequest>,
+    ) -> Result<Response<GetBlockTimelineResponse>, Status> {
+        debug!(target: COMPONENT, request = ?request.get_ref());
+
+        let peer = request.remote_addr();
+        let result = self.block_producer.clone().get_block_timeline(request.into_inner()).await;
+
+        self.record("GetBlockTimeline", peer, &result).await;
+
+        result
+    }
+
+    /// Replaces the log filter of the RPC, the store and the block producer, without restarting
+    /// them. Returns the filter replaced on the RPC.
+    #[instrument(target = "miden-rpc", name = "admin:set_log_filter", skip_all, err)]
+    async fn set_log_filter(
+        &self,
+        request: Request<SetLogFilterRequest>,
+    ) -> Result<Response<SetLogFilterResponse>, Status> {
+        debug!(target: COMPONENT, request = ?request.get_ref());
+
+        let peer = request.remote_addr();
+        let filter = request.into_inner().filter;
+
+        let result = self
+            .update_log_filter(filter.clone())
+            .await
+            .map(|previous_filter| Response::new(SetLogFilterResponse { previous_filter }));
+
+        self.record("SetLogFilter", peer, &result).await;
+        info!(target: COMPONENT, filter, success = result.is_ok(), "Log filter updated");
+
+        result
+    }
+
+    /// Replaces the submission policy of the block producer, or restores its configured policy if
+    /// the request has none. Returns the replaced policy.
+    #[instrument(target = "miden-rpc", name = "admin:set_submission_policy", skip_all, err)]
+    async fn set_submission_policy(
+        &self,
+        request: Request<SetSubmissionPolicyRequest>,
+    ) -> Result<Response<SetSubmissionPolicyResponse>, Status> {
+        debug!(target: COMPONENT, request = ?request.get_ref());
+
+        let peer = request.remote_addr();
+        let request = request.into_inner();
+        let reset = request.policy.is_none();
+        let result = self.block_producer.clone().set_submission_policy(request).await;
+
+        self.record("SetSubmissionPolicy", peer, &result).await;
+        info!(target: COMPONENT, reset, success = result.is_ok(), "Submission policy updated");
+
+        result
+    }
+
+    /// Returns the requests, rows, bytes and cost served to each client since the start of the
+    /// RPC.
+    #[instrument(target = "miden-rpc", name = "admin:get_usage", skip_all, err)]
+    async fn get_usage(
+        &self,
+        request: Request<GetUsageRequest>,
+    ) -> Result<Response<GetUsageResponse>, Status> {
+        debug!(target: COMPONENT, request = ?request.get_ref());
+
+        let peer = request.remote_addr();
+        let result = self
+            .usage
+            .as_ref()
+            .map(|usage| Response::new(usage.usage_response()))
+            .ok_or_else(|| Status::failed_precondition("Usage accounting is disabled"));
+
+        self.record("GetUsage", peer, &result).await;
+
+        result
+    }
+
+    /// Submits an operator transaction, e.g. a faucet drip or a fee sweep, in the priority lane of
+    /// the block producer: it is included in the next block ahead of the user transactions and
+    /// doesn't pay a fee. The transaction is recorded in the audit log.
+    #[instrument(target = "miden-rpc", name = "admin:submit_priority_transaction", skip_all, err)]
+    async fn submit_priority_transaction(
+        &self,
+        request: Request<SubmitPriorityTransactionRequest>,
+    ) -> Result<Response<SubmitPriorityTransactionResponse>, Status> {
+        debug!(target: COMPONENT, request = ?request.get_ref());
+
+        let peer = request.remote_addr();
+        let request = request.into_inner();
+
+        let tx = ProvenTransaction::read_from_bytes(&request.transaction)
+            .map_err(|_| Status::invalid_argument("Invalid transaction"));
+        let tx_id = tx.as_ref().ok().map(|tx| tx.id().inner());
+
+        let result = async {
+            let tx = tx?;
+            TransactionVerifier::new(MIN_PROOF_SECURITY_LEVEL).verify(tx.clone()).map_err(
+                |_| {
+                    TransactionRejection::InvalidTransactionProof(tx.id()).into_status(format!(
+                        "Invalid transaction proof for transaction: {}",
+                        tx.id()
+                    ))
+                },
+            )?;
+
+            self.block_producer.clone().submit_priority_transaction(request).await
+        }
+        .await;
+
+        if let Some(audit_log) = &self.audit_log {
+            let entry = AuditEntry::new("SubmitPriorityTransaction", peer, tx_id, &result);
+            audit_log.record(entry).await;
+        }
+        info!(
+            target: COMPONENT,
+            tx_id = %format_opt(tx_id.as_ref()),
+            success = result.is_ok(),
+            "Priority transaction submitted"
+        );
+
+        result
+    }
+}
+
+// HELPERS
+// ================================================================================================
+
+/// Returns the scope of the page tokens of `GetAuditLog` with the filters of the request, each
+/// filter encoded explicitly, `-` if it isn't set.
+fn audit_log_scope(since_ms: Option<u64>, operation: Option<&str>) -> String {
+    format!(
+        "GetAuditLog since_ms={} operation={}",
+        since_ms.map_or_else(|| "-".to_string(), |since_ms| since_ms.to_string()),
+        operation.unwrap_or("-")
+    )
+}
@@ -1,54 +1,138 @@
+use std::{future::Future, sync::Arc};
+
 use miden_node_proto::{
+    domain::{
+        transactions::TransactionRejection,
+        vectors::serialization_vectors_response,
+        version::{PROTOCOL_VERSION, PROTO_VERSION},
+    },
     generated::{
         block_producer::api_client as block_producer_client,
         requests::{
-            CheckNullifiersRequest, GetAccountDetailsRequest, GetBlockHeaderByNumberRequest,
-            GetNotesByIdRequest, SubmitProvenTransactionRequest, SyncStateRequest,
+            CheckNullifiersByPrefixRequest, CheckNullifiersRequest, GetAccountDeltasRequest,
+            GetAccountDetailsRequest, GetAccountsByPubKeyRequest, GetBlockHeaderByNumberRequest,
+            GetBlockInclusionProofRequest, GetBlockProducerStatusRequest, GetBlockProofRequest,
+            GetChainTipRequest, GetMempoolStatsRequest, GetNodeStatusRequest,
+            GetNoteAuthenticationInfoRequest, GetNoteConsumptionRequest,
+            GetNoteScriptByRootRequest, GetNotesByIdRequest, GetNullifiersByPrefixRequest,
+            GetSerializationVectorsRequest, GetStoreStatusRequest, GetTransactionReceiptRequest,
+            GetVersionRequest, RequestTestnetFundsRequest, SimulateTransactionRequest,
+            SubmitProvenTransactionRequest, SubscribeAccountsRequest, SubscribeBlocksRequest,
+            SyncNotesRequest, SyncStateRequest,
         },
         responses::{
-            CheckNullifiersResponse, GetAccountDetailsResponse, GetBlockHeaderByNumberResponse,
-            GetNotesByIdResponse, SubmitProvenTransactionResponse, SyncStateResponse,
+            CheckNullifiersByPrefixResponse, CheckNullifiersResponse, GetAccountDeltasResponse,
+            GetAccountDetailsResponse, GetAccountsByPubKeyResponse, GetBlockHeaderByNumberResponse,
+            GetBlockInclusionProofResponse, GetBlockProofResponse, GetChainTipResponse,
+            GetMempoolStatsResponse, GetNodeStatusResponse, GetNoteAuthenticationInfoResponse,
+            GetNoteConsumptionResponse, GetNoteScriptByRootResponse, GetNotesByIdResponse,
+            GetNullifiersByPrefixResponse, GetSerializationVectorsResponse,
+            GetTransactionReceiptResponse, GetVersionResponse, RequestTestnetFundsResponse,
+            SimulateTransactionResponse, SubmitProvenTransactionResponse,
+            SubscribeAccountsResponse, SubscribeBlocksResponse, SyncNotesResponse,
+            SyncStateResponse,
         },
         rpc::api_server,
         store::api_client as store_client,
     },
     try_convert,
 };
+use miden_node_utils::{
+    deadline::{propagate_deadline, request_deadline},
+    features::build_features,
+    runtime::runtime_metrics,
+    transport::ComponentChannel,
+};
 use miden_objects::{
     accounts::AccountId, crypto::hash::rpo::RpoDigest, transaction::ProvenTransaction,
     utils::serde::Deserializable, Digest, MIN_PROOF_SECURITY_LEVEL,
 };
 use miden_tx::TransactionVerifier;
-use tonic::{
-    transport::{Channel, Error},
-    Request, Response, Status,
-};
-use tracing::{debug, info, instrument};
+use tonic::{Request, Response, Status, Streaming};
+use tracing::{debug, info, instrument, warn};
 
-use crate::{config::RpcConfig, COMPONENT};
+use crate::{
+    audit::{AuditEntry, AuditLog},
+    cache_validation::{if_none_match, validate},
+    config::{RequestTimeouts, RpcConfig},
+    faucet::TestnetFaucet,
+    maintenance::MaintenanceMode,
+    retry::StoreRetry,
+    usage::with_rows,
+    COMPONENT,
+};
 
 // RPC API
 // ================================================================================================
 
+type StoreClient = store_client::ApiClient<ComponentChannel>;
+
 pub struct RpcApi {
-    store: store_client::ApiClient<Channel>,
-    block_producer: block_producer_client::ApiClient<Channel>,
+    store: StoreClient,
+    store_retry: Option<StoreRetry>,
+    block_producer: block_producer_client::ApiClient<ComponentChannel>,
+    timeouts: RequestTimeouts,
+    maintenance: Arc<MaintenanceMode>,
+    audit_log: Option<AuditLog>,
+    faucet: Option<TestnetFaucet>,
 }
 
 impl RpcApi {
-    pub(super) async fn from_config(config: &RpcConfig) -> Result<Self, Error> {
-        let store = store_client::ApiClient::connect(config.store_url.clone()).await?;
-        info!(target: COMPONENT, store_endpoint = config.store_url, "Store client initialized");
-
-        let block_producer =
-            block_producer_client::ApiClient::connect(config.block_producer_url.clone()).await?;
-        info!(
-            target: COMPONENT,
-            block_producer_endpoint = config.block_producer_url,
-            "Block producer client initialized",
-        );
+    pub(super) fn new(
+        config: &RpcConfig,
+        store: ComponentChannel,
+        block_producer: ComponentChannel,
+        maintenance: Arc<MaintenanceMode>,
+        audit_log: Option<AuditLog>,
+        faucet: Option<TestnetFaucet>,
+    ) -> Self {
+        Self {
+            store: store_client::ApiClient::new(store),
+            store_retry: config.store_retry.clone().map(StoreRetry::new),
+            block_producer: block_producer_client::ApiClient::new(block_producer),
+            timeouts: config.timeouts.clone(),
+            maintenance,
+            audit_log,
+            faucet,
+        }
+    }
+
+    /// Prepares `request` to be forwarded to the store or the block producer.
+    ///
+    /// The deadline of the forwarded request is the earliest of the client's deadline and the
+    /// configured timeout of the endpoint `method`. Requests are rejected in maintenance mode.
+    fn forward<T>(&self, method: &str, mut request: Request<T>) -> Result<Request<T>, Status> {
+        self.maintenance.check()?;
+
+        let deadline = request_deadline(&request, self.timeouts.get(method));
+        propagate_deadline(&mut request, deadline)?;
+
+        Ok(request)
+    }
+
+    /// Forwards the read `request` to the store with `call`, like [Self::forward], retrying and
+    /// hedging it if configured, see [StoreRetry].
+    async fn read_store<T, R, F, Fut>(
+        &self,
+        method: &str,
+        request: Request<T>,
+        call: F,
+    ) -> Result<Response<R>, Status>
+    where
+        T: Clone,
+        F: Fn(StoreClient, Request<T>) -> Fut,
+        Fut: Future<Output = Result<Response<R>, Status>>,
+    {
+        let Some(store_retry) = &self.store_retry else {
+            let request = self.forward(method, request)?;
+            return call(self.store.clone(), request).await;
+        };
 
-        Ok(Self { store, block_producer })
+        self.maintenance.check()?;
+        let deadline = request_deadline(&request, self.timeouts.get(method));
+        store_retry
+            .call(method, request, deadline, |request| call(self.store.clone(), request))
+            .await
     }
 }
 
@@ -77,7 +161,79 @@ impl api_server::Api for RpcApi {
                 .or(Err(Status::invalid_argument("Digest field is not in the modulus range")))?;
         }
 
-        self.store.clone().check_nullifiers(request).await
+        let response = self
+            .read_store("CheckNullifiers", request, |mut store, request| async move {
+                store.check_nullifiers(request).await
+            })
+            .await?;
+        let rows = response.get_ref().proofs.len();
+
+        Ok(with_rows(response, rows))
+    }
+
+    #[instrument(
+        target = "miden-rpc",
+        name = "rpc:check_nullifiers_by_prefix",
+        skip_all,
+        ret(level = "debug"),
+        err
+    )]
+    async fn check_nullifiers_by_prefix(
+        &self,
+        request: Request<CheckNullifiersByPrefixRequest>,
+    ) -> Result<Response<CheckNullifiersByPrefixResponse>, Status> {
+        debug!(target: COMPONENT, request = ?request.get_ref());
+
+        let response = self
+            .read_store("CheckNullifiersByPrefix", request, |mut store, request| async move {
+                store.check_nullifiers_by_prefix(request).await
+            })
+            .await?;
+        let rows = response.get_ref().nullifiers.len();
+
+        Ok(with_rows(response, rows))
+    }
+
+    #[instrument(
+        target = "miden-rpc",
+        name = "rpc:get_nullifiers_by_prefix",
+        skip_all,
+        ret(level = "debug"),
+        err
+    )]
+    async fn get_nullifiers_by_prefix(
+        &self,
+        request: Request<GetNullifiersByPrefixRequest>,
+    ) -> Result<Response<GetNullifiersByPrefixResponse>, Status> {
+        debug!(target: COMPONENT, request = ?request.get_ref());
+
+        let response = self
+            .read_store("GetNullifiersByPrefix", request, |mut store, request| async move {
+                store.get_nullifiers_by_prefix(request).await
+            })
+            .await?;
+        let rows = response.get_ref().nullifiers.len();
+
+        Ok(with_rows(response, rows))
+    }
+
+    #[instrument(
+        target = "miden-rpc",
+        name = "rpc:get_chain_tip",
+        skip_all,
+        ret(level = "debug"),
+        err
+    )]
+    async fn get_chain_tip(
+        &self,
+        request: Request<GetChainTipRequest>,
+    ) -> Result<Response<GetChainTipResponse>, Status> {
+        debug!(target: COMPONENT, request = ?request.get_ref());
+
+        self.read_store("GetChainTip", request, |mut store, request| async move {
+            store.get_chain_tip(request).await
+        })
+        .await
     }
 
     #[instrument(
@@ -93,7 +249,69 @@ impl api_server::Api for RpcApi {
     ) -> Result<Response<GetBlockHeaderByNumberResponse>, Status> {
         info!(target: COMPONENT, request = ?request.get_ref());
 
-        self.store.clone().get_block_header_by_number(request).await
+        let if_none_match = if_none_match(&request);
+        let response = self
+            .read_store("GetBlockHeaderByNumber", request, |mut store, request| async move {
+                store.get_block_header_by_number(request).await
+            })
+            .await?;
+
+        Ok(validate(response, if_none_match))
+    }
+
+    #[instrument(
+        target = "miden-rpc",
+        name = "rpc:get_block_inclusion_proof",
+        skip_all,
+        ret(level = "debug"),
+        err
+    )]
+    async fn get_block_inclusion_proof(
+        &self,
+        request: Request<GetBlockInclusionProofRequest>,
+    ) -> Result<Response<GetBlockInclusionProofResponse>, Status> {
+        info!(target: COMPONENT, request = ?request.get_ref());
+
+        self.read_store("GetBlockInclusionProof", request, |mut store, request| async move {
+            store.get_block_inclusion_proof(request).await
+        })
+        .await
+    }
+
+    #[instrument(target = "miden-rpc", name = "rpc:get_block_proof", skip_all, err)]
+    async fn get_block_proof(
+        &self,
+        request: Request<GetBlockProofRequest>,
+    ) -> Result<Response<GetBlockProofResponse>, Status> {
+        info!(target: COMPONENT, request = ?request.get_ref());
+
+        let if_none_match = if_none_match(&request);
+        let response = self
+            .read_store("GetBlockProof", request, |mut store, request| async move {
+                store.get_block_proof(request).await
+            })
+            .await?;
+
+        Ok(validate(response, if_none_match))
+    }
+
+    #[instrument(
+        target = "miden-rpc",
+        name = "rpc:get_note_authentication_info",
+        skip_all,
+        ret(level = "debug"),
+        err
+    )]
+    async fn get_note_authentication_info(
+        &self,
+        request: Request<GetNoteAuthenticationInfoRequest>,
+    ) -> Result<Response<GetNoteAuthenticationInfoResponse>, Status> {
+        info!(target: COMPONENT, request = ?request.get_ref());
+
+        self.read_store("GetNoteAuthenticationInfo", request, |mut store, request| async move {
+            store.get_note_authentication_info(request).await
+        })
+        .await
     }
 
     #[instrument(
@@ -109,7 +327,38 @@ impl api_server::Api for RpcApi {
     ) -> Result<Response<SyncStateResponse>, Status> {
         debug!(target: COMPONENT, request = ?request.get_ref());
 
-        self.store.clone().sync_state(request).await
+        let response = self
+            .read_store("SyncState", request, |mut store, request| async move {
+                store.sync_state(request).await
+            })
+            .await?;
+        let SyncStateResponse { accounts, notes, nullifiers, .. } = response.get_ref();
+        let rows = accounts.len() + notes.len() + nullifiers.len();
+
+        Ok(with_rows(response, rows))
+    }
+
+    #[instrument(
+        target = "miden-rpc",
+        name = "rpc:sync_notes",
+        skip_all,
+        ret(level = "debug"),
+        err
+    )]
+    async fn sync_notes(
+        &self,
+        request: Request<SyncNotesRequest>,
+    ) -> Result<Response<SyncNotesResponse>, Status> {
+        debug!(target: COMPONENT, request = ?request.get_ref());
+
+        let response = self
+            .read_store("SyncNotes", request, |mut store, request| async move {
+                store.sync_notes(request).await
+            })
+            .await?;
+        let rows = response.get_ref().notes.len();
+
+        Ok(with_rows(response, rows))
     }
 
     #[instrument(
@@ -131,7 +380,235 @@ impl api_server::Api for RpcApi {
         let _: Vec<RpoDigest> = try_convert(note_ids)
             .map_err(|err| Status::invalid_argument(format!("Invalid NoteId: {}", err)))?;
 
-        self.store.clone().get_notes_by_id(request).await
+        let if_none_match = if_none_match(&request);
+        let response = self
+            .read_store("GetNotesById", request, |mut store, request| async move {
+                store.get_notes_by_id(request).await
+            })
+            .await?;
+        let response = validate(response, if_none_match);
+        let rows = response.get_ref().notes.len();
+
+        Ok(with_rows(response, rows))
+    }
+
+    /// Returns the block and the transaction which consumed a public note.
+    #[instrument(
+        target = "miden-rpc",
+        name = "rpc:get_note_consumption",
+        skip_all,
+        ret(level = "debug"),
+        err
+    )]
+    async fn get_note_consumption(
+        &self,
+        request: Request<GetNoteConsumptionRequest>,
+    ) -> Result<Response<GetNoteConsumptionResponse>, Status> {
+        debug!(target: COMPONENT, request = ?request.get_ref());
+
+        self.read_store("GetNoteConsumption", request, |mut store, request| async move {
+            store.get_note_consumption(request).await
+        })
+        .await
+    }
+
+    #[instrument(
+        target = "miden-rpc",
+        name = "rpc:get_transaction_receipt",
+        skip_all,
+        ret(level = "debug"),
+        err
+    )]
+    async fn get_transaction_receipt(
+        &self,
+        request: Request<GetTransactionReceiptRequest>,
+    ) -> Result<Response<GetTransactionReceiptResponse>, Status> {
+        debug!(target: COMPONENT, request = ?request.get_ref());
+
+        self.read_store("GetTransactionReceipt", request, |mut store, request| async move {
+            store.get_transaction_receipt(request).await
+        })
+        .await
+    }
+
+    #[instrument(
+        target = "miden-rpc",
+        name = "rpc:get_accounts_by_pub_key",
+        skip_all,
+        ret(level = "debug"),
+        err
+    )]
+    async fn get_accounts_by_pub_key(
+        &self,
+        request: Request<GetAccountsByPubKeyRequest>,
+    ) -> Result<Response<GetAccountsByPubKeyResponse>, Status> {
+        debug!(target: COMPONENT, request = ?request.get_ref());
+
+        self.read_store("GetAccountsByPubKey", request, |mut store, request| async move {
+            store.get_accounts_by_pub_key(request).await
+        })
+        .await
+    }
+
+    #[instrument(
+        target = "miden-rpc",
+        name = "rpc:get_note_script_by_root",
+        skip_all,
+        ret(level = "debug"),
+        err
+    )]
+    async fn get_note_script_by_root(
+        &self,
+        request: Request<GetNoteScriptByRootRequest>,
+    ) -> Result<Response<GetNoteScriptByRootResponse>, Status> {
+        debug!(target: COMPONENT, request = ?request.get_ref());
+
+        self.read_store("GetNoteScriptByRoot", request, |mut store, request| async move {
+            store.get_note_script_by_root(request).await
+        })
+        .await
+    }
+
+    #[instrument(
+        target = "miden-rpc",
+        name = "rpc:get_mempool_stats",
+        skip_all,
+        ret(level = "debug"),
+        err
+    )]
+    async fn get_mempool_stats(
+        &self,
+        request: Request<GetMempoolStatsRequest>,
+    ) -> Result<Response<GetMempoolStatsResponse>, Status> {
+        debug!(target: COMPONENT, request = ?request.get_ref());
+
+        let request = self.forward("GetMempoolStats", request)?;
+        self.block_producer.clone().get_mempool_stats(request).await
+    }
+
+    /// Returns the versions and the state of the node's components.
+    ///
+    /// The block producer's status is left unset if it can't be reached, e.g. on replicas, which
+    /// don't run a block producer.
+    #[instrument(
+        target = "miden-rpc",
+        name = "rpc:get_node_status",
+        skip_all,
+        ret(level = "debug"),
+        err
+    )]
+    async fn get_node_status(
+        &self,
+        request: Request<GetNodeStatusRequest>,
+    ) -> Result<Response<GetNodeStatusResponse>, Status> {
+        debug!(target: COMPONENT, request = ?request.get_ref());
+
+        let request = self.forward("GetNodeStatus", request)?;
+        let mut block_producer_request = Request::new(GetBlockProducerStatusRequest {});
+        *block_producer_request.metadata_mut() = request.metadata().clone();
+        let store_request = request.map(|_| GetStoreStatusRequest {});
+
+        let mut store = self.store.clone();
+        let mut block_producer = self.block_producer.clone();
+        let (store_status, block_producer_status) = tokio::join!(
+            store.get_status(store_request),
+            block_producer.get_status(block_producer_request),
+        );
+
+        let block_producer_status = match block_producer_status {
+            Ok(response) => Some(response.into_inner()),
+            Err(status) => {
+                warn!(target: COMPONENT, %status, "Failed to get the block producer's status");
+                None
+            },
+        };
+
+        Ok(Response::new(GetNodeStatusResponse {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            store: Some(store_status?.into_inner()),
+            block_producer: block_producer_status,
+            runtime: runtime_metrics().map(Into::into),
+        }))
+    }
+
+    /// Returns the versions of the RPC, the store and the block producer, along with the hash of
+    /// the genesis block identifying the chain.
+    ///
+    /// The block producer's version is left out if it can't be reached, e.g. on replicas.
+    #[instrument(
+        target = "miden-rpc",
+        name = "rpc:get_version",
+        skip_all,
+        ret(level = "debug"),
+        err
+    )]
+    async fn get_version(
+        &self,
+        request: Request<GetVersionRequest>,
+    ) -> Result<Response<GetVersionResponse>, Status> {
+        debug!(target: COMPONENT, request = ?request.get_ref());
+
+        let request = self.forward("GetVersion", request)?;
+        let mut block_producer_request = Request::new(GetVersionRequest {});
+        *block_producer_request.metadata_mut() = request.metadata().clone();
+
+        let mut store = self.store.clone();
+        let mut block_producer = self.block_producer.clone();
+        let (store_version, block_producer_version) = tokio::join!(
+            store.get_version(request),
+            block_producer.get_version(block_producer_request),
+        );
+
+        let store_version = store_version?.into_inner();
+        let mut components = vec![store_version.clone()];
+        match block_producer_version {
+            Ok(response) => components.push(response.into_inner()),
+            Err(status) => {
+                warn!(target: COMPONENT, %status, "Failed to get the block producer's version");
+            },
+        }
+
+        Ok(Response::new(GetVersionResponse {
+            component: COMPONENT.to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            protocol_version: Some(PROTOCOL_VERSION.into()),
+            proto_version: PROTO_VERSION.to_string(),
+            genesis_hash: store_version.genesis_hash,
+            features: build_features(),
+            components,
+        }))
+    }
+
+    type GetBlocksStream = Streaming<SubscribeBlocksResponse>;
+
+    /// Streams the blocks applied by the store, used by the replicas of this node.
+    ///
+    /// The stream doesn't end, so the configured timeouts don't apply.
+    #[instrument(target = "miden-rpc", name = "rpc:get_blocks", skip_all, err)]
+    async fn get_blocks(
+        &self,
+        request: Request<SubscribeBlocksRequest>,
+    ) -> Result<Response<Self::GetBlocksStream>, Status> {
+        debug!(target: COMPONENT, request = ?request.get_ref());
+
+        self.maintenance.check()?;
+        self.store.clone().subscribe_blocks(request).await
+    }
+
+    type SubscribeAccountsStream = Streaming<SubscribeAccountsResponse>;
+
+    /// Streams the new state of the requested accounts each time a block updates them.
+    ///
+    /// The stream doesn't end, so the configured timeouts don't apply.
+    #[instrument(target = "miden-rpc", name = "rpc:subscribe_accounts", skip_all, err)]
+    async fn subscribe_accounts(
+        &self,
+        request: Request<SubscribeAccountsRequest>,
+    ) -> Result<Response<Self::SubscribeAccountsStream>, Status> {
+        debug!(target: COMPONENT, request = ?request.get_ref());
+
+        self.maintenance.check()?;
+        self.store.clone().subscribe_accounts(request).await
     }
 
     #[instrument(target = "miden-rpc", name = "rpc:submit_proven_transaction", skip_all, err)]
@@ -141,21 +618,121 @@ impl api_server::Api for RpcApi {
     ) -> Result<Response<SubmitProvenTransactionResponse>, Status> {
         debug!(target: COMPONENT, request = ?request.get_ref());
 
+        // The deadline includes the time spent verifying the transaction proof
+        let deadline = request_deadline(&request, self.timeouts.get("SubmitProvenTransaction"));
+        let peer = request.remote_addr();
         let request = request.into_inner();
 
         let tx = ProvenTransaction::read_from_bytes(&request.transaction)
+            .map_err(|_| Status::invalid_argument("Invalid transaction"));
+        let tx_id = tx.as_ref().ok().map(|tx| tx.id().inner());
+
+        let result = async {
+            self.maintenance.check()?;
+            let tx = tx?;
+            let tx_verifier = TransactionVerifier::new(MIN_PROOF_SECURITY_LEVEL);
+
+            tx_verifier.verify(tx.clone()).map_err(|_| {
+                TransactionRejection::InvalidTransactionProof(tx.id())
+                    .into_status(format!("Invalid transaction proof for transaction: {}", tx.id()))
+            })?;
+
+            let mut request = Request::new(request);
+            propagate_deadline(&mut request, deadline)?;
+
+            self.block_producer.clone().submit_proven_transaction(request).await
+        }
+        .await;
+
+        if let Some(audit_log) = &self.audit_log {
+            let entry = AuditEntry::new("SubmitProvenTransaction", peer, tx_id, &result);
+            audit_log.record(entry).await;
+        }
+
+        result
+    }
+
+    /// Verifies a transaction as `SubmitProvenTransaction` does, without submitting it.
+    ///
+    /// The proof is verified by the RPC when the block producer doesn't reject the transaction for
+    /// another reason, so the simulation covers the checks done on submission.
+    #[instrument(
+        target = "miden-rpc",
+        name = "rpc:simulate_transaction",
+        skip_all,
+        ret(level = "debug"),
+        err
+    )]
+    async fn simulate_transaction(
+        &self,
+        request: Request<SimulateTransactionRequest>,
+    ) -> Result<Response<SimulateTransactionResponse>, Status> {
+        debug!(target: COMPONENT, request = ?request.get_ref());
+
+        let tx = ProvenTransaction::read_from_bytes(&request.get_ref().transaction)
             .map_err(|_| Status::invalid_argument("Invalid transaction"))?;
 
-        let tx_verifier = TransactionVerifier::new(MIN_PROOF_SECURITY_LEVEL);
+        let request = self.forward("SimulateTransaction", request)?;
+        let mut response = self.block_producer.clone().simulate_transaction(request).await?;
+
+        if response.get_ref().rejection.is_none() {
+            let tx_verifier = TransactionVerifier::new(MIN_PROOF_SECURITY_LEVEL);
+            if tx_verifier.verify(tx.clone()).is_err() {
+                let rejection = TransactionRejection::InvalidTransactionProof(tx.id());
+                response.get_mut().rejection = Some(rejection.into());
+            }
+        }
+
+        Ok(response)
+    }
+
+    /// Mints testnet funds to the requested account, if the testnet faucet is enabled.
+    ///
+    /// The mint transaction is proven by the RPC, the configured timeouts don't apply.
+    #[instrument(target = "miden-rpc", name = "rpc:request_testnet_funds", skip_all, err)]
+    async fn request_testnet_funds(
+        &self,
+        request: Request<RequestTestnetFundsRequest>,
+    ) -> Result<Response<RequestTestnetFundsResponse>, Status> {
+        debug!(target: COMPONENT, request = ?request.get_ref());
+
+        let faucet = self
+            .faucet
+            .as_ref()
+            .ok_or(Status::unimplemented("Testnet faucet is not enabled"))?;
+        let peer = request.remote_addr();
+        let request = request.into_inner();
 
-        tx_verifier.verify(tx.clone()).map_err(|_| {
-            Status::invalid_argument(format!(
-                "Invalid transaction proof for transaction: {}",
-                tx.id()
-            ))
-        })?;
+        let result = async {
+            self.maintenance.check()?;
+            let account_id: AccountId = request
+                .account_id
+                .ok_or(Status::invalid_argument("account_id is missing"))?
+                .try_into()
+                .map_err(|err| Status::invalid_argument(format!("Invalid account id: {err}")))?;
 
-        self.block_producer.clone().submit_proven_transaction(request).await
+            let funds = faucet
+                .request_funds(account_id, peer.map(|peer| peer.ip()), request.verification_token)
+                .await?;
+
+            Ok(Response::new(RequestTestnetFundsResponse {
+                transaction_id: Some(funds.transaction_id.into()),
+                note_id: Some(funds.note_id.inner().into()),
+            }))
+        }
+        .await;
+
+        if let Some(audit_log) = &self.audit_log {
+            let tx_id = result
+                .as_ref()
+                .ok()
+                .and_then(|response| response.get_ref().transaction_id.as_ref())
+                .and_then(|tx_id| tx_id.try_into().ok());
+            let entry = AuditEntry::new("RequestTestnetFunds", peer, tx_id, &result);
+            audit_log.record(entry).await;
+        }
+
+        result
     }
 
     /// Returns details for public (on-chain) account by id.
@@ -181,6 +758,46 @@ impl api_server::Api for RpcApi {
             .try_into()
             .map_err(|err| Status::invalid_argument(format!("Invalid account id: {err}")))?;
 
-        self.store.clone().get_account_details(request).await
+        self.read_store("GetAccountDetails", request, |mut store, request| async move {
+            store.get_account_details(request).await
+        })
+        .await
+    }
+
+    /// Returns the deltas applied to a public account after the requested block.
+    #[instrument(
+        target = "miden-rpc",
+        name = "rpc:get_account_deltas",
+        skip_all,
+        ret(level = "debug"),
+        err
+    )]
+    async fn get_account_deltas(
+        &self,
+        request: Request<GetAccountDeltasRequest>,
+    ) -> Result<Response<GetAccountDeltasResponse>, Status> {
+        debug!(target: COMPONENT, request = ?request.get_ref());
+
+        self.read_store("GetAccountDeltas", request, |mut store, request| async move {
+            store.get_account_deltas(request).await
+        })
+        .await
+    }
+
+    /// Returns the canonical encodings of a block header, a note and the sync responses, for client
+    /// implementations to test their decoders against.
+    ///
+    /// Only served by nodes built with the `serialization-vectors` feature.
+    async fn get_serialization_vectors(
+        &self,
+        request: Request<GetSerializationVectorsRequest>,
+    ) -> Result<Response<GetSerializationVectorsResponse>, Status> {
+        debug!(target: COMPONENT, request = ?request.get_ref());
+
+        if cfg!(feature = "serialization-vectors") {
+            Ok(Response::new(serialization_vectors_response()))
+        } else {
+            Err(Status::unimplemented("Serialization vectors are not enabled"))
+        }
     }
 }
@@ -1,39 +1,274 @@
-use std::net::ToSocketAddrs;
+use std::{
+    net::{SocketAddr, ToSocketAddrs},
+    sync::Arc,
+    time::Duration,
+};
 
-use miden_node_proto::generated::rpc::api_server;
-use miden_node_utils::errors::ApiError;
-use tonic::transport::Server;
+use miden_node_proto::generated::{
+    admin, requests::GetGenesisRequest, rpc::api_server, store::api_client as store_client,
+};
+use miden_node_utils::{
+    access_log::AccessLogLayer,
+    chain_params::ChainGenesis,
+    config::Endpoint,
+    errors::ApiError,
+    runtime::{run_named, spawn_named, start_runtime_metrics_sampling},
+    transport::ComponentChannel,
+};
+use tonic::{
+    codec::CompressionEncoding,
+    codegen::http::{header::HeaderName, HeaderValue, Method},
+    transport::Server,
+};
+use tonic_web::GrpcWebLayer;
+use tower_http::cors::{AllowOrigin, CorsLayer};
 use tracing::info;
 
-use crate::{config::RpcConfig, COMPONENT};
+use crate::{
+    access_control::{AccessControlLayer, API_KEY_HEADER},
+    audit::AuditLog,
+    cache_validation::{ETAG_HEADER, IF_NONE_MATCH_HEADER, NOT_MODIFIED_HEADER},
+    config::{CorsConfig, Role, RpcConfig},
+    faucet::TestnetFaucet,
+    load_shedding::LoadSheddingLayer,
+    maintenance::{MaintenanceMode, RETRY_AFTER_HEADER},
+    usage::{UsageLayer, UsageTracker},
+    COMPONENT,
+};
 
+mod admin_api;
 mod api;
 
+/// Duration for which the browsers may cache the response to a CORS preflight request.
+const CORS_MAX_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+
 // RPC INITIALIZER
 // ================================================================================================
 
 pub async fn serve(config: RpcConfig) -> Result<(), ApiError> {
-    info!(target: COMPONENT, %config, "Initializing server");
-
-    let api = api::RpcApi::from_config(&config)
+    let store = ComponentChannel::connect_with(config.store_url.clone(), &config.client)
         .await
         .map_err(ApiError::ApiInitialisationFailed)?;
-    let rpc = api_server::ApiServer::new(api);
+    info!(target: COMPONENT, store_endpoint = config.store_url, "Store client initialized");
 
-    info!(target: COMPONENT, "Server initialized");
+    let block_producer =
+        ComponentChannel::connect_with(config.block_producer_url.clone(), &config.client)
+            .await
+            .map_err(ApiError::ApiInitialisationFailed)?;
+    info!(
+        target: COMPONENT,
+        block_producer_endpoint = config.block_producer_url,
+        "Block producer client initialized",
+    );
 
-    let addr = config
-        .endpoint
-        .to_socket_addrs()
-        .map_err(ApiError::EndpointToSocketFailed)?
-        .next()
-        .ok_or_else(|| ApiError::AddressResolutionFailed(config.endpoint.to_string()))?;
+    serve_with(config, store, block_producer).await
+}
+
+/// Serves the RPC API, forwarding requests to the store and the block producer over the given
+/// channels.
+pub async fn serve_with(
+    config: RpcConfig,
+    store: ComponentChannel,
+    block_producer: ComponentChannel,
+) -> Result<(), ApiError> {
+    info!(target: COMPONENT, %config, "Initializing server");
+
+    load_genesis(&config, store.clone()).await?;
+    start_runtime_metrics_sampling();
+    let audit_log = open_audit_log(&config)?;
+    let faucet = load_testnet_faucet(&config, &store, &block_producer)?;
+    let maintenance = Arc::new(MaintenanceMode::default());
+    let usage = config
+        .usage
+        .as_ref()
+        .map(|usage| Arc::new(UsageTracker::new(usage.clone(), config.access_control.as_ref())));
+    if let Some(usage) = &usage {
+        spawn_named("usage-export", usage.clone().export());
+    }
+
+    let rpc = api_server::ApiServer::new(api::RpcApi::new(
+        &config,
+        store.clone(),
+        block_producer.clone(),
+        maintenance.clone(),
+        audit_log.clone(),
+        faucet,
+    ))
+    // Only the responses to the clients accepting gzip are compressed
+    .send_compressed(CompressionEncoding::Gzip)
+    .accept_compressed(CompressionEncoding::Gzip);
+
+    info!(target: COMPONENT, "Server initialized");
 
-    Server::builder()
+    let addr = socket_addr(&config.endpoint)?;
+    let rpc_server = Server::builder()
+        // gRPC-web requests are sent over HTTP/1.1 by the browsers
+        .accept_http1(true)
+        .layer(cors_layer(&config.cors)?)
+        .layer(GrpcWebLayer::new())
+        // Inside the gRPC-web layer, so that the requests are logged as plain gRPC, and outside
+        // the access control and load shedding, so that the rejected requests are logged too
+        .layer(AccessLogLayer::new(COMPONENT, config.access_log.as_ref()))
+        // Unauthorized requests are rejected before they are counted by the load shedding
+        .layer(AccessControlLayer::new(config.access_control.as_ref(), Role::ReadOnly))
+        .layer(LoadSheddingLayer::new(config.load_shedding.as_ref()))
+        // The shed requests aren't accounted to the clients
+        .layer(UsageLayer::new(usage.clone()))
         .add_service(rpc)
-        .serve(addr)
+        .serve(addr);
+
+    match &config.admin {
+        Some(admin_config) => {
+            let admin = admin::api_server::ApiServer::new(admin_api::AdminApi::new(
+                store,
+                block_producer,
+                maintenance,
+                audit_log,
+                usage,
+            ));
+            let admin_addr = socket_addr(&admin_config.endpoint)?;
+            info!(target: COMPONENT, admin_endpoint = %admin_config.endpoint, "Serving admin API");
+
+            let admin_server = Server::builder()
+                .layer(AccessLogLayer::new(COMPONENT, config.access_log.as_ref()))
+                // The admin methods require the admin role, unless listed in `endpoint_roles`
+                .layer(AccessControlLayer::new(config.access_control.as_ref(), Role::Admin))
+                .add_service(admin)
+                .serve(admin_addr);
+            tokio::try_join!(
+                run_named("rpc-grpc-server", rpc_server),
+                run_named("admin-grpc-server", admin_server)
+            )
+            .map_err(ApiError::ApiServeFailed)?;
+        },
+        None => run_named("rpc-grpc-server", rpc_server)
+            .await
+            .map_err(ApiError::ApiServeFailed)?,
+    }
+
+    Ok(())
+}
+
+/// Builds the RPC's gRPC service, forwarding requests to the store and the block producer over the
+/// given channels.
+///
+/// Used to embed the RPC in another process, e.g. in tests. The service is not wrapped in the CORS,
+/// gRPC-web, access control, load shedding and usage layers, and the admin API is not served.
+pub fn init(
+    config: &RpcConfig,
+    store: ComponentChannel,
+    block_producer: ComponentChannel,
+) -> Result<api_server::ApiServer<impl api_server::Api>, ApiError> {
+    let audit_log = open_audit_log(config)?;
+    let faucet = load_testnet_faucet(config, &store, &block_producer)?;
+
+    Ok(api_server::ApiServer::new(api::RpcApi::new(
+        config,
+        store,
+        block_producer,
+        Arc::new(MaintenanceMode::default()),
+        audit_log,
+        faucet,
+    ))
+    .send_compressed(CompressionEncoding::Gzip)
+    .accept_compressed(CompressionEncoding::Gzip))
+}
+
+/// Fetches the genesis block and the parameters of the chain from the store, and verifies the
+/// genesis block commits to the parameters and is the configured genesis block if any.
+async fn load_genesis(config: &RpcConfig, store: ComponentChannel) -> Result<(), ApiError> {
+    let expected_hash = config
+        .genesis_hash()
+        .map_err(|err| ApiError::GenesisLoadFailed(format!("invalid genesis hash: {err}")))?;
+    let response = store_client::ApiClient::new(store)
+        .get_genesis(GetGenesisRequest {})
         .await
-        .map_err(ApiError::ApiServeFailed)?;
+        .map_err(|status| ApiError::GenesisLoadFailed(status.to_string()))?
+        .into_inner();
+    let genesis = ChainGenesis::try_from(response)
+        .map_err(|err| ApiError::GenesisLoadFailed(err.to_string()))?;
+    genesis
+        .verify(expected_hash)
+        .map_err(|err| ApiError::GenesisLoadFailed(err.to_string()))?;
+
+    info!(target: COMPONENT, genesis_hash = %genesis.hash(), "Genesis block loaded");
 
     Ok(())
 }
+
+/// Loads the testnet faucet, if configured.
+fn load_testnet_faucet(
+    config: &RpcConfig,
+    store: &ComponentChannel,
+    block_producer: &ComponentChannel,
+) -> Result<Option<TestnetFaucet>, ApiError> {
+    config
+        .testnet_faucet
+        .as_ref()
+        .map(|faucet| TestnetFaucet::load(faucet, store.clone(), block_producer.clone()))
+        .transpose()
+        .map_err(|err| ApiError::InvalidTestnetFaucet(err.to_string()))
+}
+
+/// Opens the audit log, if configured.
+fn open_audit_log(config: &RpcConfig) -> Result<Option<AuditLog>, ApiError> {
+    config
+        .audit
+        .as_ref()
+        .map(|audit| AuditLog::open(&audit.database_filepath))
+        .transpose()
+        .map_err(|err| ApiError::DatabaseConnectionFailed(err.to_string()))
+}
+
+/// Builds the CORS layer of the RPC server, allowing the gRPC-web requests from the configured
+/// origins.
+fn cors_layer(config: &CorsConfig) -> Result<CorsLayer, ApiError> {
+    let allow_origin = if config.allows_any_origin() {
+        AllowOrigin::any()
+    } else {
+        let origins = config
+            .allowed_origins
+            .iter()
+            .map(|origin| {
+                HeaderValue::from_str(origin)
+                    .map_err(|_| ApiError::InvalidCorsOrigin(origin.clone()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        AllowOrigin::list(origins)
+    };
+
+    Ok(CorsLayer::new()
+        .allow_origin(allow_origin)
+        .allow_methods([Method::POST])
+        .allow_headers(
+            [
+                "x-grpc-web",
+                "x-user-agent",
+                "content-type",
+                "grpc-timeout",
+                API_KEY_HEADER,
+                IF_NONE_MATCH_HEADER,
+            ]
+            .map(HeaderName::from_static),
+        )
+        .expose_headers(
+            [
+                "grpc-status",
+                "grpc-message",
+                "grpc-status-details-bin",
+                RETRY_AFTER_HEADER,
+                ETAG_HEADER,
+                NOT_MODIFIED_HEADER,
+            ]
+            .map(HeaderName::from_static),
+        )
+        .max_age(CORS_MAX_AGE))
+}
+
+fn socket_addr(endpoint: &Endpoint) -> Result<SocketAddr, ApiError> {
+    endpoint
+        .to_socket_addrs()
+        .map_err(ApiError::EndpointToSocketFailed)?
+        .next()
+        .ok_or_else(|| ApiError::AddressResolutionFailed(endpoint.to_string()))
+}
@@ -0,0 +1,337 @@
+//! Usage accounting, counting the requests, rows and bytes served to each API key so that infra
+//! providers can bill or throttle the heavy consumers of the node.
+//!
+//! The clients are identified by the first characters of their API key, so that the exported
+//! usage doesn't leak the keys. Only the keys listed in the access control are accounted
+//! separately, the other clients are accounted together as [ANONYMOUS_CLIENT].
+use std::{
+    collections::BTreeMap,
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use http_body::{Body, SizeHint};
+use miden_node_proto::generated::responses;
+use tonic::{
+    body::BoxBody,
+    codegen::{http, Bytes, Service},
+    Response, Status,
+};
+use tower::Layer;
+use tracing::info;
+
+use crate::{
+    access_control::API_KEY_HEADER,
+    config::{AccessControlConfig, UsageConfig},
+    grpc::grpc_method,
+    COMPONENT,
+};
+
+/// Client of the requests without a known API key.
+pub const ANONYMOUS_CLIENT: &str = "anonymous";
+
+/// Number of characters of the API keys identifying the clients.
+const CLIENT_KEY_PREFIX_LEN: usize = 8;
+
+// RESPONSE ROWS
+// ================================================================================================
+
+/// Number of rows returned by a request, set by the endpoints in the extensions of their response.
+#[derive(Clone, Copy, Debug)]
+pub struct ResponseRows(pub u64);
+
+/// Sets the number of rows returned in `response`, accounted to the client of the request.
+pub fn with_rows<T>(mut response: Response<T>, rows: usize) -> Response<T> {
+    response.extensions_mut().insert(ResponseRows(rows as u64));
+    response
+}
+
+// USAGE TRACKER
+// ================================================================================================
+
+/// Usage of the node by a client, since the start of the RPC.
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct ClientUsage {
+    pub requests: u64,
+    pub rows: u64,
+    pub bytes_sent: u64,
+    pub cost: u64,
+}
+
+/// Accounts the requests served to each client.
+#[derive(Debug)]
+pub struct UsageTracker {
+    config: UsageConfig,
+    /// Clients of the known API keys.
+    clients: BTreeMap<String, String>,
+    /// UNIX timestamp in milliseconds since which the usage is accounted.
+    since_ms: u64,
+    usage: Mutex<BTreeMap<String, ClientUsage>>,
+}
+
+impl UsageTracker {
+    /// Creates a tracker accounting the API keys of `access_control` separately.
+    pub fn new(config: UsageConfig, access_control: Option<&AccessControlConfig>) -> Self {
+        let clients = access_control
+            .into_iter()
+            .flat_map(|access_control| access_control.api_keys.keys())
+            .map(|api_key| (api_key.clone(), api_key.chars().take(CLIENT_KEY_PREFIX_LEN).collect()))
+            .collect();
+        let since_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_millis() as u64);
+
+        Self {
+            config,
+            clients,
+            since_ms,
+            usage: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// Returns the client sending `api_key`.
+    ///
+    /// The keys sharing their first characters are accounted to the same client.
+    pub fn client(&self, api_key: Option<&str>) -> &str {
+        api_key
+            .and_then(|api_key| self.clients.get(api_key))
+            .map_or(ANONYMOUS_CLIENT, String::as_str)
+    }
+
+    /// Accounts a request of `client` to the endpoint `method`.
+    pub fn record_request(&self, client: &str, method: &str) {
+        self.update(client, |usage| {
+            usage.requests += 1;
+            usage.cost += self.config.cost(method);
+        });
+    }
+
+    /// Accounts `rows` returned to `client`.
+    pub fn record_rows(&self, client: &str, rows: u64) {
+        self.update(client, |usage| usage.rows += rows);
+    }
+
+    /// Accounts `bytes` sent to `client`.
+    pub fn record_bytes_sent(&self, client: &str, bytes: u64) {
+        self.update(client, |usage| usage.bytes_sent += bytes);
+    }
+
+    fn update(&self, client: &str, update: impl FnOnce(&mut ClientUsage)) {
+        let mut usage = self.usage.lock().expect("Poisoned lock");
+        match usage.get_mut(client) {
+            Some(client_usage) => update(client_usage),
+            None => update(usage.entry(client.to_string()).or_default()),
+        }
+    }
+
+    /// Returns the usage of the clients which sent requests, ordered by client.
+    pub fn usage(&self) -> Vec<(String, ClientUsage)> {
+        let usage = self.usage.lock().expect("Poisoned lock");
+        usage.iter().map(|(client, usage)| (client.clone(), *usage)).collect()
+    }
+
+    /// Returns the usage of the clients, as returned by the `GetUsage` admin endpoint.
+    pub fn usage_response(&self) -> responses::GetUsageResponse {
+        responses::GetUsageResponse {
+            since_ms: self.since_ms,
+            clients: self
+                .usage()
+                .into_iter()
+                .map(|(client, usage)| responses::ClientUsage {
+                    client,
+                    requests: usage.requests,
+                    rows: usage.rows,
+                    bytes_sent: usage.bytes_sent,
+                    cost: usage.cost,
+                })
+                .collect(),
+        }
+    }
+
+    /// Logs the usage of the clients every `export_interval_s`, if set.
+    pub async fn export(self: Arc<Self>) {
+        if self.config.export_interval_s == 0 {
+            return;
+        }
+
+        let mut interval =
+            tokio::time::interval(Duration::from_secs(self.config.export_interval_s));
+        // The first tick completes immediately, before any request is served
+        interval.tick().await;
+
+        loop {
+            interval.tick().await;
+            for (client, usage) in self.usage() {
+                info!(
+                    target: COMPONENT,
+                    client,
+                    requests = usage.requests,
+                    rows = usage.rows,
+                    bytes_sent = usage.bytes_sent,
+                    cost = usage.cost,
+                    "Client usage"
+                );
+            }
+        }
+    }
+}
+
+// USAGE LAYER
+// ================================================================================================
+
+/// Layer of the RPC server accounting the requests to the [UsageTracker], a pass-through if usage
+/// accounting is disabled.
+#[derive(Debug, Clone)]
+pub struct UsageLayer {
+    tracker: Option<Arc<UsageTracker>>,
+}
+
+impl UsageLayer {
+    pub fn new(tracker: Option<Arc<UsageTracker>>) -> Self {
+        Self { tracker }
+    }
+}
+
+impl<S> Layer<S> for UsageLayer {
+    type Service = UsageService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        UsageService { inner, tracker: self.tracker.clone() }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct UsageService<S> {
+    inner: S,
+    tracker: Option<Arc<UsageTracker>>,
+}
+
+impl<S, B> Service<http::Request<B>> for UsageService<S>
+where
+    S: Service<http::Request<B>, Response = http::Response<BoxBody>>,
+    S::Future: Send + 'static,
+    S::Error: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: http::Request<B>) -> Self::Future {
+        let Some(tracker) = self.tracker.clone() else {
+            return Box::pin(self.inner.call(request));
+        };
+
+        let method = grpc_method(request.uri().path());
+        let api_key = request.headers().get(API_KEY_HEADER).and_then(|value| value.to_str().ok());
+        let client = tracker.client(api_key).to_string();
+        tracker.record_request(&client, method);
+
+        let response = self.inner.call(request);
+        Box::pin(async move {
+            let response = response.await?;
+            if let Some(ResponseRows(rows)) = response.extensions().get() {
+                tracker.record_rows(&client, *rows);
+            }
+
+            Ok(response
+                .map(|body| BoxBody::new(CountedResponseBody { inner: body, tracker, client })))
+        })
+    }
+}
+
+/// Response body accounting its bytes to the client as they are sent.
+struct CountedResponseBody {
+    inner: BoxBody,
+    tracker: Arc<UsageTracker>,
+    client: String,
+}
+
+impl Body for CountedResponseBody {
+    type Data = Bytes;
+    type Error = Status;
+
+    fn poll_data(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Self::Data, Self::Error>>> {
+        let this = self.get_mut();
+        let chunk = Pin::new(&mut this.inner).poll_data(cx);
+        if let Poll::Ready(Some(Ok(chunk))) = &chunk {
+            this.tracker.record_bytes_sent(&this.client, chunk.len() as u64);
+        }
+
+        chunk
+    }
+
+    fn poll_trailers(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<Option<http::HeaderMap>, Self::Error>> {
+        Pin::new(&mut self.get_mut().inner).poll_trailers(cx)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Role;
+
+    #[test]
+    fn usage_is_accounted_per_api_key() {
+        let access_control = AccessControlConfig {
+            api_keys: [("partner-key-1".to_string(), Role::Submitter)].into(),
+            ..Default::default()
+        };
+        let tracker = UsageTracker::new(UsageConfig::default(), Some(&access_control));
+
+        let partner = tracker.client(Some("partner-key-1")).to_string();
+        assert_eq!(partner, "partner-");
+        assert_eq!(tracker.client(Some("unknown-key")), ANONYMOUS_CLIENT);
+        assert_eq!(tracker.client(None), ANONYMOUS_CLIENT);
+
+        tracker.record_request(&partner, "SyncState");
+        tracker.record_rows(&partner, 12);
+        tracker.record_bytes_sent(&partner, 300);
+        tracker.record_request(&partner, "GetChainTip");
+        tracker.record_request(ANONYMOUS_CLIENT, "SyncNotes");
+
+        assert_eq!(
+            tracker.usage(),
+            [
+                (
+                    ANONYMOUS_CLIENT.to_string(),
+                    ClientUsage {
+                        requests: 1,
+                        rows: 0,
+                        bytes_sent: 0,
+                        cost: 5
+                    }
+                ),
+                (
+                    partner,
+                    ClientUsage {
+                        requests: 2,
+                        rows: 12,
+                        bytes_sent: 300,
+                        cost: 11
+                    }
+                ),
+            ]
+        );
+    }
+}
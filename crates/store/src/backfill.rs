@@ -0,0 +1,139 @@
+//! Rebuilds the database from the blocks it stores, re-deriving the notes, nullifiers, accounts and
+//! transaction receipts of each block.
+//!
+//! The blocks are replayed on a new database, created from the genesis file, and their roots are
+//! verified as when they were first applied. The source database is only read, so a partially
+//! corrupted database can be recovered from, and the tables added by a new release are filled for
+//! the blocks applied before it.
+use std::path::{Path, PathBuf};
+
+use miden_node_proto::generated::requests::ApplyBlockRequest;
+use miden_objects::BlockHeader;
+use prost::Message;
+use tracing::info;
+
+use crate::{
+    config::StoreConfig,
+    db::{Db, DbInspector},
+    errors::BackfillError,
+    server::api::block_update,
+    state::State,
+    types::BlockNumber,
+    COMPONENT,
+};
+
+/// Number of blocks read from the source database at once.
+const BACKFILL_CHUNK_SIZE: usize = 100;
+
+// BACKFILL
+// ================================================================================================
+
+/// Outcome of a successful backfill.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BackfillReport {
+    /// Path of the rebuilt database
+    pub output: PathBuf,
+    /// Latest block of the rebuilt database, the chain tip of the source database when the
+    /// backfill started
+    pub chain_tip: BlockNumber,
+    /// Number of blocks replayed, after the genesis block
+    pub blocks: usize,
+}
+
+/// Rebuilds the database of `config` into a new database at `output`, by replaying the stored
+/// blocks up to the chain tip.
+///
+/// The genesis block is created from the genesis file of `config`, which must match the genesis
+/// block of the source database. Each block must apply on its parent with the roots of its header,
+/// and the rebuilt chain tip must be the source's. Fails if `output` already exists.
+pub async fn backfill(config: StoreConfig, output: &Path) -> Result<BackfillReport, BackfillError> {
+    if output.exists() {
+        return Err(BackfillError::OutputExists(output.to_path_buf()));
+    }
+
+    let mut source = DbInspector::open(&config.database_filepath)?;
+    let (source_genesis, _) = source
+        .block_header(Some(BlockNumber::GENESIS))?
+        .ok_or(BackfillError::MissingBlock(BlockNumber::GENESIS))?;
+    let (source_tip, _) = source
+        .block_header(None)?
+        .ok_or(BackfillError::MissingBlock(BlockNumber::GENESIS))?;
+    let chain_tip = BlockNumber::from(source_tip.block_num());
+
+    let config = StoreConfig {
+        database_filepath: output.to_path_buf(),
+        follow: None,
+        read_only: false,
+        backup: None,
+        ..config
+    };
+    let db = Db::setup(config.clone()).await?;
+    let state = State::load(db, &config.cache).await?;
+
+    let genesis = state
+        .get_block_header(Some(BlockNumber::GENESIS))
+        .await?
+        .ok_or(BackfillError::MissingBlock(BlockNumber::GENESIS))?;
+    check_block_hash(&genesis, &source_genesis)?;
+
+    info!(target: COMPONENT, output = %output.display(), %chain_tip, "Replaying the stored blocks");
+
+    let mut next = BlockNumber::GENESIS.child().expect("the genesis block has a child");
+    let mut replayed = 0;
+    'replay: while next <= chain_tip {
+        let blocks = source.blocks(next, BACKFILL_CHUNK_SIZE)?;
+        if blocks.is_empty() {
+            return Err(BackfillError::MissingBlock(next));
+        }
+
+        for (block_num, block) in
+            blocks.into_iter().take_while(|(block_num, _)| *block_num <= chain_tip)
+        {
+            if block_num != next {
+                return Err(BackfillError::MissingBlock(next));
+            }
+
+            let invalid_block = |reason: String| BackfillError::InvalidBlock { block_num, reason };
+            let request = ApplyBlockRequest::decode(block.as_slice())
+                .map_err(|err| invalid_block(err.to_string()))?;
+            let block = block_update(request)
+                .map_err(|status| invalid_block(status.message().to_string()))?;
+            block.apply(&state).await.map_err(|err| invalid_block(err.to_string()))?;
+
+            replayed += 1;
+            // The last block number is necessarily the chain tip
+            let Some(child) = next.child() else {
+                break 'replay;
+            };
+            next = child;
+        }
+
+        info!(target: COMPONENT, replayed, %chain_tip, "Blocks replayed");
+    }
+
+    // The replayed chain must end in the chain tip of the source
+    let rebuilt_tip = state
+        .get_block_header(None)
+        .await?
+        .ok_or(BackfillError::MissingBlock(BlockNumber::GENESIS))?;
+    check_block_hash(&rebuilt_tip, &source_tip)?;
+
+    Ok(BackfillReport {
+        output: output.to_path_buf(),
+        chain_tip,
+        blocks: replayed,
+    })
+}
+
+/// Checks the rebuilt `block` is the `expected` block of the source database.
+fn check_block_hash(block: &BlockHeader, expected: &BlockHeader) -> Result<(), BackfillError> {
+    if block.block_num() != expected.block_num() || block.hash() != expected.hash() {
+        return Err(BackfillError::BlockHashMismatch {
+            block_num: expected.block_num().into(),
+            expected: expected.hash(),
+            actual: block.hash(),
+        });
+    }
+
+    Ok(())
+}
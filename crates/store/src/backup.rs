@@ -0,0 +1,273 @@
+//! Scheduled online backups of the database, so the chain state survives the loss of the store's
+//! disk without relying on manual snapshots.
+//!
+//! Each backup is a consistent copy of the database taken with the SQLite backup API while the
+//! blocks keep being applied, named after the latest block it contains.
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    sync::{Arc, RwLock},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use tokio::time::{self, Interval, MissedTickBehavior};
+use tracing::{info, warn};
+
+use crate::{
+    config::BackupConfig, db::Db, errors::BackupError, state::State, types::BlockNumber, COMPONENT,
+};
+
+/// Prefix and extension of the backup files, `miden-store-<block_num>.sqlite3`.
+const BACKUP_FILE_PREFIX: &str = "miden-store-";
+const BACKUP_FILE_EXTENSION: &str = ".sqlite3";
+
+/// Placeholder of the backup path in the upload command.
+const UPLOAD_PATH_PLACEHOLDER: &str = "{path}";
+
+/// Latest successful backup, shared by the whole process.
+static LAST_BACKUP: RwLock<Option<BackupInfo>> = RwLock::new(None);
+
+// BACKUP INFO
+// ================================================================================================
+
+/// A successful backup of the database.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BackupInfo {
+    pub path: PathBuf,
+    /// Latest block of the backup
+    pub block_num: BlockNumber,
+    pub size_bytes: u64,
+    /// Time at which the backup completed, in seconds since the Unix epoch
+    pub timestamp: u64,
+}
+
+/// Returns the latest successful backup taken by this process, [None] until the first one.
+pub fn last_backup() -> Option<BackupInfo> {
+    LAST_BACKUP.read().expect("Poisoned lock").clone()
+}
+
+// BACKUP SCHEDULER
+// ================================================================================================
+
+/// Backs the database up to `config.directory` every `config.interval_blocks` blocks or every
+/// `config.interval_s` seconds, whichever comes first, if blocks were applied since the latest
+/// backup. The backups exceeding `config.keep_last` are removed, oldest first.
+///
+/// A failed backup is logged and retried on the next trigger. Returns immediately if both
+/// intervals are disabled.
+pub async fn schedule_backups(state: Arc<State>, config: BackupConfig) {
+    if config.interval_blocks == 0 && config.interval().is_none() {
+        warn!(target: COMPONENT, "Backups are configured without an interval, none is taken");
+        return;
+    }
+
+    let db = state.db();
+    let mut chain_tip = state.subscribe_chain_tip();
+    let mut interval = config.interval().map(|period| {
+        let mut interval = time::interval(period);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        interval
+    });
+
+    // Backups taken before a restart count towards the interval
+    let mut last_block_num = match list_backups(&config.directory) {
+        Ok(backups) => backups.last().map(|(block_num, _)| *block_num),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => None,
+        Err(err) => {
+            warn!(target: COMPONENT, %err, "Failed to list the existing backups");
+            None
+        },
+    };
+
+    loop {
+        let is_due = tokio::select! {
+            changed = chain_tip.changed() => {
+                if changed.is_err() {
+                    return;
+                }
+                let block_num = chain_tip.borrow_and_update().block_num;
+                config.interval_blocks != 0
+                    && last_block_num.map_or(true, |last_block_num| {
+                        block_num.as_u32()
+                            >= last_block_num.as_u32().saturating_add(config.interval_blocks)
+                    })
+            },
+            _ = tick(&mut interval) => {
+                let block_num = chain_tip.borrow().block_num;
+                last_block_num.map_or(true, |last_block_num| block_num > last_block_num)
+            },
+        };
+        if !is_due {
+            continue;
+        }
+
+        match backup(&db, &config).await {
+            Ok(backup) => {
+                info!(
+                    target: COMPONENT,
+                    path = %backup.path.display(),
+                    block_num = %backup.block_num,
+                    size_bytes = backup.size_bytes,
+                    "Database backed up"
+                );
+                last_block_num = Some(backup.block_num);
+                *LAST_BACKUP.write().expect("Poisoned lock") = Some(backup);
+            },
+            Err(err) => warn!(target: COMPONENT, %err, "Failed to back the database up"),
+        }
+    }
+}
+
+/// Waits for the next tick of `interval`, forever if the backups by time are disabled.
+async fn tick(interval: &mut Option<Interval>) {
+    match interval {
+        Some(interval) => {
+            interval.tick().await;
+        },
+        None => std::future::pending().await,
+    }
+}
+
+/// Backs the database up to `config.directory`, uploads the backup and enforces the retention.
+///
+/// The backup is written to a temporary file first, renamed once complete, so an interrupted
+/// backup is never mistaken for a complete one.
+async fn backup(db: &Db, config: &BackupConfig) -> Result<BackupInfo, BackupError> {
+    let io_error = |path: &Path| {
+        let path = path.to_path_buf();
+        move |error| BackupError::IoError { path, error }
+    };
+
+    fs::create_dir_all(&config.directory).map_err(io_error(&config.directory))?;
+    let partial_path = config
+        .directory
+        .join(format!(".{BACKUP_FILE_PREFIX}partial{BACKUP_FILE_EXTENSION}"));
+    match fs::remove_file(&partial_path) {
+        Err(err) if err.kind() != io::ErrorKind::NotFound => {
+            return Err(io_error(&partial_path)(err));
+        },
+        _ => {},
+    }
+
+    let block_header = db.backup(partial_path.clone()).await?;
+    let block_num = BlockNumber::from(block_header.block_num());
+    let path = backup_path(&config.directory, block_num);
+    fs::rename(&partial_path, &path).map_err(io_error(&path))?;
+    let size_bytes = fs::metadata(&path).map_err(io_error(&path))?.len();
+
+    if !config.upload_command.is_empty() {
+        upload(&config.upload_command, &path).await?;
+    }
+
+    for (_, expired) in
+        expired_backups(&config.directory, config.keep_last).map_err(io_error(&config.directory))?
+    {
+        fs::remove_file(&expired).map_err(io_error(&expired))?;
+    }
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default();
+
+    Ok(BackupInfo { path, block_num, size_bytes, timestamp })
+}
+
+/// Runs the upload `command`, its `{path}` arguments replaced by the path of the backup.
+async fn upload(command: &[String], path: &Path) -> Result<(), BackupError> {
+    let path = path.display().to_string();
+    let args: Vec<String> =
+        command.iter().map(|arg| arg.replace(UPLOAD_PATH_PLACEHOLDER, &path)).collect();
+    let upload_failed =
+        |reason: String| BackupError::UploadFailed { command: command.join(" "), reason };
+
+    let output = tokio::task::spawn_blocking(move || {
+        std::process::Command::new(&args[0]).args(&args[1..]).output()
+    })
+    .await
+    .map_err(|err| upload_failed(err.to_string()))?
+    .map_err(|err| upload_failed(err.to_string()))?;
+
+    if !output.status.success() {
+        return Err(upload_failed(format!(
+            "{}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    Ok(())
+}
+
+// BACKUP FILES
+// ================================================================================================
+
+/// Returns the path of the backup of the database at `block_num`, zero-padded so the backups sort
+/// by block number.
+fn backup_path(directory: &Path, block_num: BlockNumber) -> PathBuf {
+    directory.join(format!("{BACKUP_FILE_PREFIX}{:010}{BACKUP_FILE_EXTENSION}", block_num.as_u32()))
+}
+
+/// Lists the backups in `directory`, oldest first.
+fn list_backups(directory: &Path) -> io::Result<Vec<(BlockNumber, PathBuf)>> {
+    let mut backups = Vec::new();
+    for entry in fs::read_dir(directory)? {
+        let path = entry?.path();
+        let block_num = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.strip_prefix(BACKUP_FILE_PREFIX))
+            .and_then(|name| name.strip_suffix(BACKUP_FILE_EXTENSION))
+            .and_then(|block_num| block_num.parse::<u32>().ok());
+        if let Some(block_num) = block_num {
+            backups.push((block_num.into(), path));
+        }
+    }
+    backups.sort();
+
+    Ok(backups)
+}
+
+/// Returns the backups in `directory` exceeding the `keep_last` most recent ones, none if
+/// `keep_last` is zero.
+fn expired_backups(directory: &Path, keep_last: usize) -> io::Result<Vec<(BlockNumber, PathBuf)>> {
+    let mut backups = list_backups(directory)?;
+    if keep_last == 0 {
+        return Ok(Vec::new());
+    }
+    backups.truncate(backups.len().saturating_sub(keep_last));
+
+    Ok(backups)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::{backup_path, expired_backups, list_backups};
+    use crate::types::BlockNumber;
+
+    #[test]
+    fn test_backup_retention() {
+        let directory =
+            std::env::temp_dir().join(format!("miden-store-backups-{}", std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+        for block_num in [9, 10, 100, 2] {
+            fs::write(backup_path(&directory, block_num.into()), []).unwrap();
+        }
+        // Files not named like backups are ignored
+        fs::write(directory.join(".miden-store-partial.sqlite3"), []).unwrap();
+        fs::write(directory.join("miden-store.sqlite3"), []).unwrap();
+
+        let backups = list_backups(&directory).unwrap();
+        let block_nums: Vec<_> = backups.iter().map(|(block_num, _)| block_num.as_u32()).collect();
+        assert_eq!(block_nums, [2, 9, 10, 100]);
+
+        let expired = expired_backups(&directory, 3).unwrap();
+        assert_eq!(expired, [(BlockNumber::new(2), backup_path(&directory, 2.into()))]);
+        assert!(expired_backups(&directory, 0).unwrap().is_empty());
+        assert!(expired_backups(&directory, 5).unwrap().is_empty());
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
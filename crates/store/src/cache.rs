@@ -0,0 +1,125 @@
+//! In-process cache of immutable data which is frequently requested.
+//!
+//! Block headers and notes never change once committed, so cached entries don't need to be
+//! invalidated, and entries for new blocks are added as the blocks are applied. Entries are evicted
+//! once the configured capacity is reached.
+use miden_objects::{notes::NoteId, BlockHeader};
+use moka::sync::Cache;
+
+use crate::{config::CacheConfig, db::Note, types::BlockNumber};
+
+/// Notes are keyed by the byte representation of their ids, since [NoteId] doesn't implement
+/// [std::hash::Hash].
+type NoteKey = [u8; 32];
+
+pub struct StateCache {
+    block_headers: Cache<BlockNumber, BlockHeader>,
+    notes: Cache<NoteKey, Note>,
+}
+
+impl StateCache {
+    pub fn new(config: &CacheConfig) -> Self {
+        Self {
+            block_headers: Cache::new(config.block_headers),
+            notes: Cache::new(config.notes),
+        }
+    }
+
+    /// Returns the cached header of the block `block_num`.
+    pub fn get_block_header(&self, block_num: BlockNumber) -> Option<BlockHeader> {
+        self.block_headers.get(&block_num)
+    }
+
+    pub fn insert_block_header(&self, block_header: BlockHeader) {
+        self.block_headers.insert(block_header.block_num().into(), block_header);
+    }
+
+    /// Returns the cached notes with the given ids, and the ids missing from the cache.
+    pub fn get_notes(&self, note_ids: Vec<NoteId>) -> (Vec<Note>, Vec<NoteId>) {
+        let mut notes = Vec::new();
+        let mut missing = Vec::new();
+        for note_id in note_ids {
+            match self.notes.get(&note_key(&note_id)) {
+                Some(note) => notes.push(note),
+                None => missing.push(note_id),
+            }
+        }
+
+        (notes, missing)
+    }
+
+    pub fn insert_notes<'a>(&self, notes: impl IntoIterator<Item = &'a Note>) {
+        for note in notes {
+            self.notes.insert(note_key(&note.note_created.note_id.into()), note.clone());
+        }
+    }
+}
+
+fn note_key(note_id: &NoteId) -> NoteKey {
+    note_id.inner().as_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use miden_objects::{
+        accounts::ACCOUNT_ID_OFF_CHAIN_SENDER, crypto::merkle::MerklePath, notes::NoteType,
+        BlockHeader, Digest, Felt, ONE, ZERO,
+    };
+
+    use super::StateCache;
+    use crate::{
+        config::CacheConfig,
+        db::{Note, NoteCreated},
+    };
+
+    fn note(id: u64) -> Note {
+        Note {
+            block_num: 1.into(),
+            note_created: NoteCreated {
+                batch_index: 0,
+                note_index: id as u32,
+                note_id: Digest::new([Felt::new(id), ZERO, ZERO, ZERO]),
+                note_type: NoteType::OffChain,
+                sender: ACCOUNT_ID_OFF_CHAIN_SENDER.into(),
+                tag: 0,
+                aux: 0,
+                details: None,
+            },
+            merkle_path: MerklePath::new(vec![]),
+        }
+    }
+
+    #[test]
+    fn test_cached_notes() {
+        let cache = StateCache::new(&CacheConfig::default());
+        let notes = [note(1), note(2)];
+        cache.insert_notes(&notes);
+
+        let ids = [1, 2, 3].map(|id| note(id).note_created.note_id.into()).to_vec();
+        let (cached, missing) = cache.get_notes(ids);
+
+        assert_eq!(cached, notes);
+        assert_eq!(missing, vec![note(3).note_created.note_id.into()]);
+    }
+
+    #[test]
+    fn test_cached_block_headers() {
+        let cache = StateCache::new(&CacheConfig::default());
+        let block_header = BlockHeader::new(
+            Digest::default(),
+            7,
+            Digest::default(),
+            Digest::default(),
+            Digest::default(),
+            Digest::default(),
+            Digest::default(),
+            Digest::default(),
+            ZERO,
+            ONE,
+        );
+        cache.insert_block_header(block_header);
+
+        assert_eq!(cache.get_block_header(7.into()), Some(block_header));
+        assert_eq!(cache.get_block_header(8.into()), None);
+    }
+}
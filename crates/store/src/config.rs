@@ -1,9 +1,17 @@
 use std::{
     fmt::{Display, Formatter},
     path::PathBuf,
+    time::Duration,
 };
 
-use miden_node_utils::config::Endpoint;
+use miden_node_utils::{
+    config::{AccessLogConfig, Endpoint},
+    formatting::format_opt,
+};
+use miden_objects::{
+    crypto::{dsa::rpo_falcon512::PublicKey, utils::HexParseError},
+    Digest, Word,
+};
 use serde::{Deserialize, Serialize};
 
 // Main config
@@ -17,9 +25,63 @@ pub struct StoreConfig {
     pub database_filepath: PathBuf,
     /// Genesis file
     pub genesis_filepath: PathBuf,
+    /// Capacity of the in-process cache of frequently requested data
+    #[serde(default)]
+    pub cache: CacheConfig,
+    /// Lock contention handling and slow write reporting of the SQLite database
+    #[serde(default)]
+    pub database: DatabaseConfig,
+    /// Sampling of the database size and warning before the disk fills up
+    #[serde(default)]
+    pub database_size: DatabaseSizeConfig,
+    /// Node followed by this store, which is then a replica applying the blocks of the followed
+    /// node instead of the blocks of a local block producer. Not set for the sequencer.
+    #[serde(default)]
+    pub follow: Option<FollowConfig>,
+    /// Serve the reads of an existing database without ever writing to it, e.g. of a snapshot or a
+    /// replicated database file. Blocks can't be applied, the database isn't migrated and isn't
+    /// locked, so any number of read-only stores can serve the same file.
+    #[serde(default)]
+    pub read_only: bool,
+    /// Maintain an index of the public accounts by authentication public key, used to serve
+    /// `GetAccountsByPubKey`
+    #[serde(default)]
+    pub index_account_keys: bool,
+    /// Maximum sizes of the filters of the state syncs
+    #[serde(default)]
+    pub sync_filters: SyncFiltersConfig,
+    /// Scheduled backups of the database, disabled if not set
+    #[serde(default)]
+    pub backup: Option<BackupConfig>,
+    /// Retention of the validity proofs of the blocks, independent of the retention of the blocks
+    #[serde(default)]
+    pub block_proofs: BlockProofsConfig,
+    /// Access log of the requests served, disabled if not set.
+    #[serde(default)]
+    pub access_log: Option<AccessLogConfig>,
 }
 
 impl StoreConfig {
+    /// Returns the configuration of a store listening on `endpoint`, with its database and genesis
+    /// files at the given paths, and the defaults of all the other settings.
+    pub fn new(endpoint: Endpoint, database_filepath: PathBuf, genesis_filepath: PathBuf) -> Self {
+        Self {
+            endpoint,
+            database_filepath,
+            genesis_filepath,
+            cache: Default::default(),
+            database: Default::default(),
+            database_size: Default::default(),
+            follow: None,
+            read_only: false,
+            index_account_keys: false,
+            sync_filters: Default::default(),
+            backup: None,
+            block_proofs: Default::default(),
+            access_log: None,
+        }
+    }
+
     pub fn endpoint_url(&self) -> String {
         self.endpoint.to_string()
     }
@@ -28,8 +90,293 @@ impl StoreConfig {
 impl Display for StoreConfig {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.write_fmt(format_args!(
-            "{{ endpoint: \"{}\",  database_filepath: {:?}, genesis_filepath: {:?} }}",
-            self.endpoint, self.database_filepath, self.genesis_filepath
+            "{{ endpoint: \"{}\",  database_filepath: {:?}, genesis_filepath: {:?}, cache: {}, \
+             database: {}, database_size: {}, follow: {}, read_only: {}, index_account_keys: {}, \
+             sync_filters: {}, backup: {}, block_proofs: {}, access_log: {} }}",
+            self.endpoint,
+            self.database_filepath,
+            self.genesis_filepath,
+            self.cache,
+            self.database,
+            self.database_size,
+            format_opt(self.follow.as_ref()),
+            self.read_only,
+            self.index_account_keys,
+            self.sync_filters,
+            format_opt(self.backup.as_ref()),
+            self.block_proofs,
+            format_opt(self.access_log.as_ref())
+        ))
+    }
+}
+
+// Cache config
+// ================================================================================================
+
+/// Maximum number of entries kept in the in-process cache, per type of data. Zero disables the
+/// cache for that type.
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CacheConfig {
+    /// Block headers, keyed by block number
+    pub block_headers: u64,
+    /// Committed notes, keyed by note id
+    pub notes: u64,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self { block_headers: 10_000, notes: 100_000 }
+    }
+}
+
+impl Display for CacheConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!(
+            "{{ block_headers: {}, notes: {} }}",
+            self.block_headers, self.notes
+        ))
+    }
+}
+
+// Database config
+// ================================================================================================
+
+/// Handling of the SQLite lock contention and logging of the slow statements, in milliseconds.
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DatabaseConfig {
+    /// Maximum time a statement waits for a lock held by another connection, before failing with
+    /// "database is locked".
+    pub busy_timeout_ms: u64,
+    /// `apply_block` transactions taking longer than this are logged with the time spent in each
+    /// statement.
+    pub slow_apply_block_ms: u64,
+    /// Read queries taking longer than this are logged with their parameters and the number of
+    /// rows they returned.
+    pub slow_query_ms: u64,
+}
+
+impl DatabaseConfig {
+    pub fn busy_timeout(&self) -> Duration {
+        Duration::from_millis(self.busy_timeout_ms)
+    }
+
+    pub fn slow_apply_block(&self) -> Duration {
+        Duration::from_millis(self.slow_apply_block_ms)
+    }
+
+    pub fn slow_query(&self) -> Duration {
+        Duration::from_millis(self.slow_query_ms)
+    }
+}
+
+impl Default for DatabaseConfig {
+    fn default() -> Self {
+        Self {
+            busy_timeout_ms: 5_000,
+            slow_apply_block_ms: 1_000,
+            slow_query_ms: 500,
+        }
+    }
+}
+
+impl Display for DatabaseConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!(
+            "{{ busy_timeout_ms: {}, slow_apply_block_ms: {}, slow_query_ms: {} }}",
+            self.busy_timeout_ms, self.slow_apply_block_ms, self.slow_query_ms
+        ))
+    }
+}
+
+// Database size config
+// ================================================================================================
+
+/// Sampling of the database size.
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DatabaseSizeConfig {
+    /// Interval between two samples of the database size, in seconds. Zero disables the sampling.
+    pub sample_interval_s: u64,
+    /// Size the database must not outgrow, e.g. the size of its disk, in megabytes. Zero disables
+    /// the warning.
+    pub max_size_mb: u64,
+    /// A warning is logged once the database reaches this percentage of `max_size_mb`.
+    pub warn_percent: u64,
+}
+
+impl DatabaseSizeConfig {
+    /// Returns the interval between two samples, [None] if the sampling is disabled.
+    pub fn sample_interval(&self) -> Option<Duration> {
+        (self.sample_interval_s != 0).then(|| Duration::from_secs(self.sample_interval_s))
+    }
+
+    /// Returns `true` if a database of `size_bytes` reached `warn_percent` of the maximum size.
+    pub fn is_approaching_max_size(&self, size_bytes: u64) -> bool {
+        let max_size_bytes = self.max_size_mb.saturating_mul(1024 * 1024);
+        max_size_bytes != 0
+            && u128::from(size_bytes) * 100
+                >= u128::from(max_size_bytes) * u128::from(self.warn_percent)
+    }
+}
+
+impl Default for DatabaseSizeConfig {
+    fn default() -> Self {
+        Self {
+            sample_interval_s: 60,
+            max_size_mb: 0,
+            warn_percent: 80,
+        }
+    }
+}
+
+impl Display for DatabaseSizeConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!(
+            "{{ sample_interval_s: {}, max_size_mb: {}, warn_percent: {} }}",
+            self.sample_interval_s, self.max_size_mb, self.warn_percent
+        ))
+    }
+}
+
+// Block proofs config
+// ================================================================================================
+
+/// Retention of the validity proofs of the blocks.
+#[derive(
+    Clone, Copy, Default, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize,
+)]
+#[serde(default)]
+pub struct BlockProofsConfig {
+    /// Only the proofs of this many latest blocks are kept, the older ones are removed as new
+    /// blocks are applied. Zero keeps all the proofs.
+    pub keep_last_blocks: u32,
+}
+
+impl Display for BlockProofsConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("{{ keep_last_blocks: {} }}", self.keep_last_blocks))
+    }
+}
+
+// Sync filters config
+// ================================================================================================
+
+/// Maximum number of account ids, note tags and nullifier prefixes of a state sync. The filters
+/// exceeding them are truncated, and the rest is synced by continuing the state sync.
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SyncFiltersConfig {
+    pub max_account_ids: usize,
+    pub max_note_tags: usize,
+    pub max_nullifier_prefixes: usize,
+    /// Filters are split into chunks of at most this many values, each queried separately, to
+    /// bound the size of the temporary tables of the queries. At least one.
+    pub chunk_size: usize,
+}
+
+impl Default for SyncFiltersConfig {
+    fn default() -> Self {
+        Self {
+            max_account_ids: 1_000,
+            max_note_tags: 1_000,
+            max_nullifier_prefixes: 1_000,
+            chunk_size: 250,
+        }
+    }
+}
+
+impl Display for SyncFiltersConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!(
+            "{{ max_account_ids: {}, max_note_tags: {}, max_nullifier_prefixes: {}, chunk_size: \
+             {} }}",
+            self.max_account_ids, self.max_note_tags, self.max_nullifier_prefixes, self.chunk_size
+        ))
+    }
+}
+
+// Follow config
+// ================================================================================================
+
+/// Node followed by a replica store, either the sequencer or another replica.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize)]
+pub struct FollowConfig {
+    /// RPC gRPC endpoint of the followed node, in the format `http://<host>[:<port>]`.
+    pub rpc_url: String,
+    /// Public key of the sequencer, as logged by its block producer at startup. If set, blocks not
+    /// signed with the matching key are rejected, otherwise only the headers and the roots of the
+    /// blocks are validated.
+    #[serde(default)]
+    pub sequencer_public_key: Option<String>,
+}
+
+impl FollowConfig {
+    /// Parses the hex encoded `sequencer_public_key`.
+    pub fn public_key(&self) -> Result<Option<PublicKey>, HexParseError> {
+        self.sequencer_public_key
+            .as_deref()
+            .map(|public_key| {
+                let public_key = Digest::try_from(public_key)?;
+                Ok(PublicKey::new(Word::from(public_key)))
+            })
+            .transpose()
+    }
+}
+
+impl Display for FollowConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!(
+            "{{ rpc_url: \"{}\", sequencer_public_key: {} }}",
+            self.rpc_url,
+            format_opt(self.sequencer_public_key.as_ref())
+        ))
+    }
+}
+
+// Backup config
+// ================================================================================================
+
+/// Online backups of the database, taken every `interval_blocks` blocks or every `interval_s`
+/// seconds, whichever comes first, as long as new blocks were applied since the last backup.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize)]
+pub struct BackupConfig {
+    /// Directory the backups are written to, as `miden-store-<block_num>.sqlite3`.
+    pub directory: PathBuf,
+    /// Number of blocks between two backups. Zero disables the backups by number of blocks.
+    #[serde(default)]
+    pub interval_blocks: u32,
+    /// Number of seconds between two backups. Zero disables the backups by time.
+    #[serde(default)]
+    pub interval_s: u64,
+    /// Number of backups kept in `directory`, the oldest ones being removed. Zero keeps them all.
+    #[serde(default)]
+    pub keep_last: usize,
+    /// Command run after each backup to upload it, e.g. to an S3-compatible endpoint with
+    /// `["aws", "s3", "cp", "{path}", "s3://bucket/backups/"]`. `{path}` is replaced by the path
+    /// of the backup file. The retention only applies to `directory`.
+    #[serde(default)]
+    pub upload_command: Vec<String>,
+}
+
+impl BackupConfig {
+    /// Returns the interval between two backups by time, [None] if disabled.
+    pub fn interval(&self) -> Option<Duration> {
+        (self.interval_s != 0).then(|| Duration::from_secs(self.interval_s))
+    }
+}
+
+impl Display for BackupConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!(
+            "{{ directory: {:?}, interval_blocks: {}, interval_s: {}, keep_last: {}, \
+             upload_command: {:?} }}",
+            self.directory,
+            self.interval_blocks,
+            self.interval_s,
+            self.keep_last,
+            self.upload_command
         ))
     }
 }
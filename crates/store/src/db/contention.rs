@@ -0,0 +1,216 @@
+//! Telemetry of the SQLite lock contention and of the slow `apply_block` transactions.
+//!
+//! SQLite calls the busy handler when a statement can't acquire a lock held by another connection.
+//! The handler installed here waits with the same backoff as SQLite's `busy_timeout`, while
+//! counting the contention events, so that "database is locked" stalls can be diagnosed.
+use std::{
+    fmt::{Display, Formatter},
+    sync::atomic::{AtomicU64, Ordering},
+    thread,
+    time::{Duration, Instant},
+};
+
+use rusqlite::Connection;
+use tracing::{debug, warn};
+
+use crate::COMPONENT;
+
+/// Delays between the attempts to acquire a lock, the last delay is repeated until the timeout.
+const BUSY_DELAYS_MS: [u64; 12] = [1, 2, 5, 10, 15, 20, 25, 25, 25, 50, 50, 100];
+
+/// The busy handler is a plain function, so its timeout and the metrics are process wide.
+static BUSY_TIMEOUT_MS: AtomicU64 = AtomicU64::new(5_000);
+static METRICS: ContentionCounters = ContentionCounters::new();
+
+// CONTENTION METRICS
+// ================================================================================================
+
+struct ContentionCounters {
+    busy_events: AtomicU64,
+    busy_timeouts: AtomicU64,
+    busy_wait_ms: AtomicU64,
+}
+
+impl ContentionCounters {
+    const fn new() -> Self {
+        Self {
+            busy_events: AtomicU64::new(0),
+            busy_timeouts: AtomicU64::new(0),
+            busy_wait_ms: AtomicU64::new(0),
+        }
+    }
+}
+
+/// Lock contention observed since the process started.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ContentionMetrics {
+    /// Number of statements which found the database locked
+    pub busy_events: u64,
+    /// Number of statements which gave up waiting and failed with "database is locked"
+    pub busy_timeouts: u64,
+    /// Total time spent waiting for locks, in milliseconds
+    pub busy_wait_ms: u64,
+}
+
+impl Display for ContentionMetrics {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!(
+            "{{ busy_events: {}, busy_timeouts: {}, busy_wait_ms: {} }}",
+            self.busy_events, self.busy_timeouts, self.busy_wait_ms
+        ))
+    }
+}
+
+/// Returns the lock contention observed since the process started.
+pub fn contention_metrics() -> ContentionMetrics {
+    ContentionMetrics {
+        busy_events: METRICS.busy_events.load(Ordering::Relaxed),
+        busy_timeouts: METRICS.busy_timeouts.load(Ordering::Relaxed),
+        busy_wait_ms: METRICS.busy_wait_ms.load(Ordering::Relaxed),
+    }
+}
+
+// BUSY HANDLER
+// ================================================================================================
+
+/// Sets the timeout of the busy handler, shared by all connections.
+pub fn set_busy_timeout(timeout: Duration) {
+    BUSY_TIMEOUT_MS.store(timeout.as_millis().try_into().unwrap_or(u64::MAX), Ordering::Relaxed);
+}
+
+/// Installs the busy handler recording the contention events on `conn`.
+pub fn install_busy_handler(conn: &Connection) -> rusqlite::Result<()> {
+    conn.busy_handler(Some(busy_handler))
+}
+
+/// Called by SQLite with the number of times it was already called for the same lock. Returns
+/// `false` to give up.
+fn busy_handler(attempts: i32) -> bool {
+    let attempts = usize::try_from(attempts).unwrap_or_default();
+    let waited_ms = busy_waited_ms(attempts);
+    let timeout_ms = BUSY_TIMEOUT_MS.load(Ordering::Relaxed);
+
+    if attempts == 0 {
+        METRICS.busy_events.fetch_add(1, Ordering::Relaxed);
+        debug!(target: COMPONENT, "Database is locked, waiting");
+    }
+
+    if waited_ms >= timeout_ms {
+        METRICS.busy_timeouts.fetch_add(1, Ordering::Relaxed);
+        warn!(
+            target: COMPONENT,
+            waited_ms,
+            contention = %contention_metrics(),
+            "Database is locked, giving up"
+        );
+        return false;
+    }
+
+    let delay_ms = busy_delay_ms(attempts).min(timeout_ms - waited_ms);
+    thread::sleep(Duration::from_millis(delay_ms));
+    METRICS.busy_wait_ms.fetch_add(delay_ms, Ordering::Relaxed);
+
+    true
+}
+
+fn busy_delay_ms(attempt: usize) -> u64 {
+    BUSY_DELAYS_MS[attempt.min(BUSY_DELAYS_MS.len() - 1)]
+}
+
+/// Returns the time waited before the attempt `attempts`.
+fn busy_waited_ms(attempts: usize) -> u64 {
+    (0..attempts).map(busy_delay_ms).sum()
+}
+
+// STATEMENT TIMINGS
+// ================================================================================================
+
+/// Time spent in each statement of a transaction.
+#[derive(Debug)]
+pub struct StatementTimings {
+    started: Instant,
+    last: Instant,
+    statements: Vec<(&'static str, Duration)>,
+}
+
+impl StatementTimings {
+    pub fn start() -> Self {
+        let now = Instant::now();
+        Self {
+            started: now,
+            last: now,
+            statements: Vec::new(),
+        }
+    }
+
+    /// Records the time elapsed since the previous statement as spent in `statement`.
+    pub fn record(&mut self, statement: &'static str) {
+        let now = Instant::now();
+        self.statements.push((statement, now - self.last));
+        self.last = now;
+    }
+
+    /// Returns the time elapsed since the start of the transaction.
+    pub fn total(&self) -> Duration {
+        self.last - self.started
+    }
+}
+
+impl Display for StatementTimings {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("{ ")?;
+        for (index, (statement, elapsed)) in self.statements.iter().enumerate() {
+            if index > 0 {
+                f.write_str(", ")?;
+            }
+            f.write_fmt(format_args!("{statement}: {}ms", elapsed.as_millis()))?;
+        }
+        f.write_str(" }")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use rusqlite::{Connection, ErrorCode, TransactionBehavior};
+
+    use super::{busy_waited_ms, contention_metrics, install_busy_handler, set_busy_timeout};
+
+    #[test]
+    fn test_busy_backoff() {
+        assert_eq!(busy_waited_ms(0), 0);
+        assert_eq!(busy_waited_ms(3), 1 + 2 + 5);
+        assert_eq!(busy_waited_ms(14), 328 + 100 + 100);
+    }
+
+    #[test]
+    fn test_busy_handler_records_contention() {
+        let path =
+            std::env::temp_dir().join(format!("miden-store-busy-{}.sqlite3", std::process::id()));
+        let mut holder = Connection::open(&path).unwrap();
+        let waiter = Connection::open(&path).unwrap();
+        holder.execute("CREATE TABLE IF NOT EXISTS t (x INTEGER)", ()).unwrap();
+
+        set_busy_timeout(Duration::from_millis(50));
+        install_busy_handler(&waiter).unwrap();
+        let before = contention_metrics();
+
+        // The write lock is held until the transaction is dropped
+        let transaction = holder.transaction_with_behavior(TransactionBehavior::Immediate).unwrap();
+        let err = waiter.execute("INSERT INTO t VALUES (1)", ()).unwrap_err();
+        assert_eq!(err.sqlite_error_code(), Some(ErrorCode::DatabaseBusy));
+        drop(transaction);
+
+        let after = contention_metrics();
+        assert!(after.busy_events > before.busy_events);
+        assert!(after.busy_timeouts > before.busy_timeouts);
+        assert!(after.busy_wait_ms >= before.busy_wait_ms + 50);
+
+        // Once the lock is released, the statement succeeds
+        waiter.execute("INSERT INTO t VALUES (1)", ()).unwrap();
+
+        drop((holder, waiter));
+        let _ = std::fs::remove_file(path);
+    }
+}
@@ -0,0 +1,186 @@
+//! Read-only access to the database file, used by the operator to inspect the state of the chain
+//! without going through the store's API, e.g. while the node is stopped.
+use std::{ops::RangeInclusive, path::Path};
+
+use miden_node_proto::domain::accounts::{AccountLookup, AccountSummary};
+use miden_objects::{
+    crypto::dsa::rpo_falcon512::Signature,
+    notes::{NoteId, Nullifier},
+    BlockHeader,
+};
+use rusqlite::{vtab::array, Connection, OpenFlags};
+
+use super::{
+    schema::DatabaseSchema, size::DatabaseSize, sql, sql::NULLIFIER_EPOCH_BLOCKS, Note,
+    NoteCreated, NullifierInfo, Result,
+};
+use crate::types::{AccountId, BlockNumber};
+
+// DATABASE INSPECTOR
+// ================================================================================================
+
+/// Read-only connection to the database file.
+///
+/// The file is never written, the migrations are not applied and the genesis block is not
+/// checked, so a database of a running node can be inspected.
+pub struct DbInspector {
+    conn: Connection,
+}
+
+impl DbInspector {
+    /// Opens the database at `path` in read-only mode.
+    pub fn open(path: &Path) -> Result<Self> {
+        let conn = Connection::open_with_flags(
+            path,
+            OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+        )?;
+        array::load_module(&conn)?;
+
+        Ok(Self { conn })
+    }
+
+    /// Loads the account `account_id`, see [sql::select_account].
+    pub fn account(&mut self, account_id: AccountId) -> Result<AccountLookup> {
+        sql::select_account(&mut self.conn, account_id)
+    }
+
+    /// Loads the note `note_id`, [None] if it doesn't exist.
+    pub fn note(&mut self, note_id: NoteId) -> Result<Option<Note>> {
+        Ok(sql::select_notes_by_id(&mut self.conn, &[note_id])?.pop())
+    }
+
+    /// Returns the number of the block which consumed `nullifier`, [None] if it wasn't consumed.
+    pub fn nullifier(&mut self, nullifier: &Nullifier) -> Result<Option<BlockNumber>> {
+        sql::select_nullifier_block_num(&mut self.conn, nullifier)
+    }
+
+    /// Loads the header of the block `block_num` and its signature, the latest block if
+    /// `block_num` is [None].
+    pub fn block_header(
+        &mut self,
+        block_num: Option<BlockNumber>,
+    ) -> Result<Option<(BlockHeader, Option<Signature>)>> {
+        let Some(block_header) = sql::select_block_header_by_block_num(&mut self.conn, block_num)?
+        else {
+            return Ok(None);
+        };
+        let signature =
+            sql::select_block_signature(&mut self.conn, block_header.block_num().into())?;
+
+        Ok(Some((block_header, signature)))
+    }
+
+    /// Loads at most `limit` encoded blocks, starting from the block `block_num`, see
+    /// [sql::select_blocks].
+    pub fn blocks(
+        &mut self,
+        block_num: BlockNumber,
+        limit: usize,
+    ) -> Result<Vec<(BlockNumber, Vec<u8>)>> {
+        sql::select_blocks(&mut self.conn, block_num, limit)
+    }
+
+    /// Loads the changes of the state made by the blocks of `block_range`.
+    ///
+    /// The nullifiers are read by scanning the partitions overlapping the range, a large range is
+    /// best split by epoch, see [epoch_ranges].
+    pub fn delta(&mut self, block_range: RangeInclusive<BlockNumber>) -> Result<StateDelta> {
+        Ok(StateDelta {
+            accounts: sql::select_accounts_updated_in_range(&mut self.conn, block_range.clone())?,
+            notes: sql::select_notes_in_block_range(&mut self.conn, block_range.clone())?,
+            nullifiers: sql::select_nullifiers_in_block_range(&mut self.conn, block_range)?,
+        })
+    }
+
+    /// Returns the tables, indexes and views of the database, see [sql::select_schema].
+    pub fn schema(&mut self) -> Result<DatabaseSchema> {
+        sql::select_schema(&mut self.conn)
+    }
+
+    /// Returns the chain tip, the number of rows and the size of the tables.
+    pub fn stats(&mut self) -> Result<DbStats> {
+        let chain_tip = sql::select_block_header_by_block_num(&mut self.conn, None)?
+            .map(|block_header| block_header.block_num().into());
+
+        Ok(DbStats {
+            chain_tip,
+            rows: sql::select_row_counts(&mut self.conn)?,
+            size: sql::select_database_size(&mut self.conn)?,
+        })
+    }
+}
+
+// STATE DELTA
+// ================================================================================================
+
+/// Changes of the state over a range of blocks, ordered by block.
+#[derive(Debug, Default, PartialEq)]
+pub struct StateDelta {
+    /// Accounts whose latest update is in the range, an account updated again after the range is
+    /// part of the delta of its latest update
+    pub accounts: Vec<AccountSummary>,
+    /// Notes created in the range, without their details
+    pub notes: Vec<(BlockNumber, NoteCreated)>,
+    /// Nullifiers consumed in the range
+    pub nullifiers: Vec<NullifierInfo>,
+}
+
+/// Splits `block_range` into the ranges of its blocks in the same epoch of the nullifier
+/// partitions, in ascending order.
+pub fn epoch_ranges(block_range: RangeInclusive<BlockNumber>) -> Vec<RangeInclusive<BlockNumber>> {
+    let (mut start, end) = (block_range.start().as_u32(), block_range.end().as_u32());
+    let mut ranges = Vec::new();
+    while start <= end {
+        let epoch_end = start | (NULLIFIER_EPOCH_BLOCKS - 1);
+        ranges.push(BlockNumber::new(start)..=BlockNumber::new(epoch_end.min(end)));
+        match epoch_end.checked_add(1) {
+            Some(next) => start = next,
+            None => break,
+        }
+    }
+    ranges
+}
+
+// DATABASE STATS
+// ================================================================================================
+
+/// Overview of the content of the database.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DbStats {
+    /// Number of the latest block, [None] if the database is empty
+    pub chain_tip: Option<BlockNumber>,
+    pub rows: RowCounts,
+    pub size: DatabaseSize,
+}
+
+/// Number of rows of the tables holding the state of the chain.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct RowCounts {
+    pub block_headers: u64,
+    pub accounts: u64,
+    pub notes: u64,
+    pub nullifiers: u64,
+    pub transactions: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn epoch_ranges_split_at_the_epochs() {
+        let block = BlockNumber::new;
+        let epoch = NULLIFIER_EPOCH_BLOCKS;
+        assert_eq!(epoch_ranges(block(3)..=block(5)), [block(3)..=block(5)]);
+        assert_eq!(
+            epoch_ranges(block(epoch - 1)..=block(2 * epoch + 1)),
+            [
+                block(epoch - 1)..=block(epoch - 1),
+                block(epoch)..=block(2 * epoch - 1),
+                block(2 * epoch)..=block(2 * epoch + 1)
+            ]
+        );
+        assert_eq!(epoch_ranges(BlockNumber::MAX..=BlockNumber::MAX).len(), 1);
+        assert!(epoch_ranges(block(5)..=block(3)).is_empty());
+    }
+}
@@ -0,0 +1,170 @@
+//! Exclusive ownership of the database by a single store.
+//!
+//! The store mirrors the database in its in-memory trees, which a second store writing to the same
+//! database would silently invalidate. Each store holds an advisory lock on a file next to the
+//! database for as long as it runs, and records itself as the owner of the database.
+use std::{
+    fmt::{Display, Formatter},
+    fs::{File, OpenOptions},
+    io::{Read, Seek, Write},
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use uuid::Uuid;
+
+use crate::errors::DatabaseSetupError;
+
+/// Application id of the store databases, `MIDN`, set in the header of the SQLite file to tell
+/// them apart from the other SQLite databases.
+pub const APPLICATION_ID: i32 = i32::from_be_bytes(*b"MIDN");
+
+// STORE INSTANCE
+// ================================================================================================
+
+/// A run of a store process, recorded as the owner of the database it uses.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoreInstance {
+    /// Random id of the run
+    pub instance_id: Uuid,
+    pub process_id: u32,
+    /// Time at which the store started, in seconds since the Unix epoch
+    pub started_at: u64,
+}
+
+impl StoreInstance {
+    /// Returns the instance of the current process.
+    fn current() -> Self {
+        Self {
+            instance_id: Uuid::new_v4(),
+            process_id: std::process::id(),
+            started_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|elapsed| elapsed.as_secs())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl Display for StoreInstance {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "store instance {} (process {}, started at {})",
+            self.instance_id, self.process_id, self.started_at
+        )
+    }
+}
+
+// DATABASE LOCK
+// ================================================================================================
+
+/// Exclusive lock on the database, released when dropped or when the process exits, even if it
+/// crashes.
+#[derive(Debug)]
+pub struct DatabaseLock {
+    /// The locked file, kept open to hold the lock
+    _file: File,
+    instance: StoreInstance,
+}
+
+impl DatabaseLock {
+    /// Locks the database at `database_filepath` for the current process, with an advisory lock on
+    /// `<database_filepath>.lock`.
+    ///
+    /// Fails with [DatabaseSetupError::DatabaseInUse] if another store holds the lock, reporting
+    /// the instance which holds it.
+    pub fn acquire(database_filepath: &Path) -> Result<Self, DatabaseSetupError> {
+        let path = lock_path(database_filepath);
+        let lock_failed = |error| DatabaseSetupError::LockFailed { path: path.clone(), error };
+
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&path)
+            .map_err(lock_failed)?;
+
+        if !try_lock(&file).map_err(lock_failed)? {
+            // The owner wrote its instance to the file once it locked it
+            let mut owner = String::new();
+            file.read_to_string(&mut owner).map_err(lock_failed)?;
+            return Err(DatabaseSetupError::DatabaseInUse {
+                path: database_filepath.to_path_buf(),
+                owner: owner.trim().to_string(),
+            });
+        }
+
+        let instance = StoreInstance::current();
+        file.set_len(0).map_err(lock_failed)?;
+        file.rewind().map_err(lock_failed)?;
+        writeln!(file, "{instance}").map_err(lock_failed)?;
+
+        Ok(Self { _file: file, instance })
+    }
+
+    /// Returns the store instance holding the lock.
+    pub fn instance(&self) -> &StoreInstance {
+        &self.instance
+    }
+}
+
+/// Returns the path of the lock file of the database at `database_filepath`.
+fn lock_path(database_filepath: &Path) -> PathBuf {
+    let mut path = database_filepath.as_os_str().to_owned();
+    path.push(".lock");
+    path.into()
+}
+
+/// Tries to lock `file` exclusively without waiting, returns false if it's locked by another
+/// process.
+#[cfg(unix)]
+fn try_lock(file: &File) -> std::io::Result<bool> {
+    use rustix::{
+        fs::{flock, FlockOperation},
+        io::Errno,
+    };
+
+    match flock(file, FlockOperation::NonBlockingLockExclusive) {
+        Ok(()) => Ok(true),
+        Err(Errno::WOULDBLOCK) => Ok(false),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Advisory locks are only supported on Unix, elsewhere the ownership of the database isn't
+/// enforced.
+#[cfg(not(unix))]
+fn try_lock(_file: &File) -> std::io::Result<bool> {
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DatabaseLock;
+    use crate::errors::DatabaseSetupError;
+
+    #[test]
+    fn test_database_lock_is_exclusive() {
+        let directory =
+            std::env::temp_dir().join(format!("miden-store-lock-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        let database_filepath = directory.join("miden-store.sqlite3");
+
+        let lock = DatabaseLock::acquire(&database_filepath).unwrap();
+        match DatabaseLock::acquire(&database_filepath) {
+            Err(DatabaseSetupError::DatabaseInUse { path, owner }) => {
+                assert_eq!(path, database_filepath);
+                assert!(owner.contains(&lock.instance().instance_id.to_string()), "{owner}");
+            },
+            other => panic!("the database was locked twice: {other:?}"),
+        }
+
+        // Dropping the lock releases it
+        drop(lock);
+        DatabaseLock::acquire(&database_filepath).unwrap();
+
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+}
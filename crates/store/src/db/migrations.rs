@@ -1,9 +1,13 @@
 use once_cell::sync::Lazy;
-use rusqlite_migration::{Migrations, M};
+use rusqlite::{Connection, Transaction};
+use rusqlite_migration::{HookResult, Migrations, M};
+
+use super::sql::{create_nullifier_partition, nullifier_partition, NULLIFIER_EPOCH_BLOCKS};
 
 pub static MIGRATIONS: Lazy<Migrations> = Lazy::new(|| {
-    Migrations::new(vec![M::up(
-        "
+    Migrations::new(vec![
+        M::up(
+            "
         CREATE TABLE
             block_headers
         (
@@ -62,9 +66,361 @@ pub static MIGRATIONS: Lazy<Migrations> = Lazy::new(|| {
             CONSTRAINT nullifiers_block_num_is_u32 CHECK (block_num BETWEEN 0 AND 0xFFFFFFFF)
         ) STRICT, WITHOUT ROWID;
         ",
-    )])
+        ),
+        M::up(
+            "
+        CREATE TABLE
+            transactions
+        (
+            transaction_id BLOB NOT NULL,
+            account_id INTEGER NOT NULL,
+            block_num INTEGER NOT NULL,
+            batch_index INTEGER NOT NULL,   -- Index of batch in block, starting from 0
+            initial_account_hash BLOB NOT NULL,
+            final_account_hash BLOB NOT NULL,
+            output_notes BLOB NOT NULL,     -- Serialized vector of note IDs
+            input_nullifiers BLOB NOT NULL, -- Serialized vector of nullifiers
+
+            PRIMARY KEY (transaction_id),
+            CONSTRAINT fk_block_num FOREIGN KEY (block_num) REFERENCES block_headers (block_num),
+            CONSTRAINT transactions_transaction_id_is_digest CHECK (length(transaction_id) = 32),
+            CONSTRAINT transactions_block_num_is_u32 CHECK (block_num BETWEEN 0 AND 0xFFFFFFFF),
+            CONSTRAINT transactions_batch_index_is_u32 CHECK (batch_index BETWEEN 0 AND 0xFFFFFFFF)
+        ) STRICT, WITHOUT ROWID;
+        ",
+        ),
+        M::up(
+            "
+        ALTER TABLE block_headers ADD COLUMN signature BLOB; -- NULL for unsigned blocks
+        ",
+        ),
+        M::up(
+            "
+        CREATE TABLE
+            blocks
+        (
+            block_num INTEGER NOT NULL,
+            block BLOB NOT NULL, -- Encoded `ApplyBlockRequest`, streamed to the follower nodes
+
+            PRIMARY KEY (block_num),
+            CONSTRAINT fk_block_num FOREIGN KEY (block_num) REFERENCES block_headers (block_num),
+            CONSTRAINT blocks_block_num_is_u32 CHECK (block_num BETWEEN 0 AND 0xFFFFFFFF)
+        ) STRICT, WITHOUT ROWID;
+        ",
+        ),
+        M::up(
+            "
+        CREATE TABLE
+            account_keys
+        (
+            public_key BLOB NOT NULL, -- Authentication public key, from the storage of the account
+            account_id INTEGER NOT NULL,
+
+            PRIMARY KEY (public_key, account_id),
+            CONSTRAINT account_keys_public_key_is_digest CHECK (length(public_key) = 32)
+        ) STRICT, WITHOUT ROWID;
+
+        CREATE INDEX idx_account_keys_account_id ON account_keys(account_id);
+        ",
+        ),
+        M::up(
+            "
+        CREATE TABLE
+            note_scripts
+        (
+            script_root BLOB NOT NULL,
+            script BLOB NOT NULL, -- Serialized `NoteScript`, shared by the notes with this root
+
+            PRIMARY KEY (script_root),
+            CONSTRAINT note_scripts_script_root_is_digest CHECK (length(script_root) = 32)
+        ) STRICT, WITHOUT ROWID;
+
+        -- Set for the public notes whose script is stored in `note_scripts`, their `details` then
+        -- exclude the script. NULL for the notes inserted before this column was added.
+        ALTER TABLE notes ADD COLUMN script_root BLOB;
+        ",
+        ),
+        M::up(
+            "
+        -- Timestamp of the block, denormalized from `block_header` so the nullifiers can be
+        -- returned with the time they were consumed. NULL for the blocks inserted before this
+        -- column was added, until they are backfilled when the store starts.
+        ALTER TABLE block_headers ADD COLUMN timestamp INTEGER;
+        ",
+        ),
+        M::up(
+            "
+        -- Fee paid by the transaction to the operator of the node, NULL if the node didn't charge
+        -- fees. The amount is in the fungible asset of the faucet `fee_faucet_id`.
+        ALTER TABLE transactions ADD COLUMN fee_faucet_id INTEGER;
+        ALTER TABLE transactions ADD COLUMN fee_amount INTEGER;
+        ",
+        ),
+        M::up(
+            "
+        -- Filters of the state sync, the primary key columns are appended to each index, so the
+        -- notes of a tag or sender are ordered by block
+        CREATE INDEX idx_notes_tag ON notes(tag);
+        CREATE INDEX idx_notes_sender ON notes(sender);
+        CREATE INDEX idx_nullifiers_prefix_block_num ON nullifiers(nullifier_prefix, block_num);
+        CREATE INDEX idx_accounts_block_num ON accounts(block_num);
+
+        -- Lookup of the notes by id, and sum of the collected fees
+        CREATE INDEX idx_notes_note_hash ON notes(note_hash);
+        CREATE INDEX idx_transactions_fee ON transactions(fee_faucet_id, fee_amount);
+        ",
+        ),
+        M::up(
+            "
+        -- Roots of the block, denormalized from `block_header` like `timestamp`, so they are
+        -- queried without decoding the headers. NULL until backfilled for the older blocks.
+        ALTER TABLE block_headers ADD COLUMN note_root BLOB;
+        ALTER TABLE block_headers ADD COLUMN nullifier_root BLOB;
+        ALTER TABLE block_headers ADD COLUMN account_root BLOB;
+        ",
+        ),
+        M::up(
+            "
+        -- Components of the note tags, so the notes are selected by use case or by execution hint
+        -- without scanning all the tags, see `note_tag_use_case` and `is_network_note_tag`. The
+        -- use case is NULL for the tags targeting a single account, the network hint is 1 for the
+        -- notes executed by the network and 0 otherwise.
+        ALTER TABLE notes ADD COLUMN tag_use_case INTEGER;
+        ALTER TABLE notes ADD COLUMN tag_network_hint INTEGER NOT NULL DEFAULT 0;
+
+        UPDATE notes SET
+            tag_use_case = CASE
+                WHEN tag >> 30 = 0 OR tag >> 29 = 4 THEN NULL
+                ELSE (tag >> 16) & 0x3FFF
+            END,
+            tag_network_hint = tag >> 31 = 1 AND tag >> 29 != 5;
+
+        CREATE INDEX idx_notes_tag_use_case ON notes(tag_use_case);
+        CREATE INDEX idx_notes_tag_network_hint ON notes(tag_network_hint);
+        ",
+        ),
+        M::up(
+            "
+        -- Validity proofs of the blocks, for the light clients and bridges. Pruned according to
+        -- their own retention, so a proof may be missing for a block which is still stored.
+        CREATE TABLE
+            block_proofs
+        (
+            block_num INTEGER NOT NULL,
+            proof BLOB NOT NULL,
+
+            PRIMARY KEY (block_num),
+            CONSTRAINT fk_block_num FOREIGN KEY (block_num) REFERENCES block_headers (block_num),
+            CONSTRAINT block_proofs_block_num_is_u32 CHECK (block_num BETWEEN 0 AND 0xFFFFFFFF)
+        ) STRICT, WITHOUT ROWID;
+        ",
+        ),
+        M::up(
+            "
+        -- Store instance which owns the database, replaced by each store when it starts. The
+        -- table has a single row.
+        CREATE TABLE
+            store_instance
+        (
+            id INTEGER NOT NULL,
+            instance_id BLOB NOT NULL,
+            process_id INTEGER NOT NULL,
+            started_at INTEGER NOT NULL,
+
+            PRIMARY KEY (id),
+            CONSTRAINT store_instance_single_row CHECK (id = 0)
+        ) STRICT;
+        ",
+        ),
+        M::up(
+            "
+        -- Nonce of the public accounts, from their details, set by the store on startup for the
+        -- accounts stored before the column was added. Unknown for the private accounts.
+        ALTER TABLE accounts ADD COLUMN nonce INTEGER;
+        ",
+        ),
+        M::up(
+            "
+        -- Note tree of each block with notes, set by the store on startup for the blocks inserted
+        -- before the table was added. The Merkle paths of the notes are computed from it.
+        CREATE TABLE
+            block_note_trees
+        (
+            block_num INTEGER NOT NULL,
+            note_tree BLOB NOT NULL,
+
+            PRIMARY KEY (block_num),
+            CONSTRAINT fk_block_num FOREIGN KEY (block_num) REFERENCES block_headers (block_num)
+        ) STRICT, WITHOUT ROWID;
+
+        -- Replaced by the note trees of the blocks
+        ALTER TABLE notes DROP COLUMN merkle_path;
+        ",
+        ),
+        M::up(
+            "
+        -- Deltas applied to the public accounts by each block, with the hash of the account state
+        -- after the delta. The accounts created by a block have no delta in that block.
+        CREATE TABLE
+            account_deltas
+        (
+            account_id INTEGER NOT NULL,
+            block_num INTEGER NOT NULL,
+            delta BLOB NOT NULL,
+            account_hash BLOB NOT NULL,
+
+            PRIMARY KEY (account_id, block_num),
+            CONSTRAINT fk_account_id FOREIGN KEY (account_id) REFERENCES accounts (account_id),
+            CONSTRAINT fk_block_num FOREIGN KEY (block_num) REFERENCES block_headers (block_num)
+        ) STRICT, WITHOUT ROWID;
+
+        -- First block whose account deltas are stored, the blocks applied before the table was
+        -- added have none. The table has a single row.
+        CREATE TABLE
+            account_deltas_start
+        (
+            id INTEGER NOT NULL,
+            block_num INTEGER NOT NULL,
+
+            PRIMARY KEY (id),
+            CONSTRAINT account_deltas_start_single_row CHECK (id = 0)
+        ) STRICT;
+
+        INSERT INTO account_deltas_start (id, block_num)
+            SELECT 0, COALESCE(MAX(block_num) + 1, 0) FROM block_headers;
+        ",
+        ),
+        M::up(
+            "
+        -- Nullifier of the public notes, computed from their details, so the consumption of the
+        -- notes is recorded when their nullifier is. NULL for the private notes and the notes
+        -- inserted before this column was added.
+        ALTER TABLE notes ADD COLUMN nullifier BLOB;
+
+        CREATE INDEX idx_notes_nullifier ON notes(nullifier) WHERE nullifier IS NOT NULL;
+
+        -- Consumption of the notes whose nullifier is known, by the block and, if its receipt was
+        -- stored, the transaction consuming them
+        CREATE TABLE
+            note_consumptions
+        (
+            note_id BLOB NOT NULL,
+            nullifier BLOB NOT NULL,
+            block_num INTEGER NOT NULL,
+            transaction_id BLOB,
+
+            PRIMARY KEY (note_id),
+            CONSTRAINT fk_nullifier FOREIGN KEY (nullifier) REFERENCES nullifiers (nullifier),
+            CONSTRAINT fk_block_num FOREIGN KEY (block_num) REFERENCES block_headers (block_num),
+            CONSTRAINT note_consumptions_note_id_is_digest CHECK (length(note_id) = 32)
+        ) STRICT, WITHOUT ROWID;
+        ",
+        ),
+        M::up_with_hook(
+            "
+        -- Epochs of the partitions of the nullifiers, the nullifiers consumed in an epoch are
+        -- stored in the table `nullifiers_<epoch>`, see `sql::create_nullifier_partition`
+        CREATE TABLE
+            nullifier_partitions
+        (
+            epoch INTEGER NOT NULL,
+
+            PRIMARY KEY (epoch)
+        ) STRICT, WITHOUT ROWID;
+
+        -- The consumptions can't reference the nullifiers once they are partitioned
+        CREATE TABLE
+            note_consumptions_partitioned
+        (
+            note_id BLOB NOT NULL,
+            nullifier BLOB NOT NULL,
+            block_num INTEGER NOT NULL,
+            transaction_id BLOB,
+
+            PRIMARY KEY (note_id),
+            CONSTRAINT fk_block_num FOREIGN KEY (block_num) REFERENCES block_headers (block_num),
+            CONSTRAINT note_consumptions_note_id_is_digest CHECK (length(note_id) = 32)
+        ) STRICT, WITHOUT ROWID;
+
+        INSERT INTO note_consumptions_partitioned SELECT * FROM note_consumptions;
+        DROP TABLE note_consumptions;
+        ALTER TABLE note_consumptions_partitioned RENAME TO note_consumptions;
+        ",
+            partition_nullifiers,
+        ),
+        M::up(
+            "
+        -- Estimated execution cost of the transaction in VM cycles, 0 for the transactions
+        -- inserted before this column was added.
+        ALTER TABLE transactions ADD COLUMN cycles INTEGER NOT NULL DEFAULT 0;
+        ",
+        ),
+        M::up_with_hook(
+            "
+        -- User defined value of the note's metadata, stored as `i64` like the other `u64` values.
+        -- 0 for the notes inserted before this column was added, as the store didn't receive it.
+        ALTER TABLE notes ADD COLUMN aux INTEGER NOT NULL DEFAULT 0;
+
+        CREATE INDEX idx_notes_aux ON notes(aux);
+        ",
+            add_note_epochs,
+        ),
+    ])
 });
 
+/// Moves the nullifiers to the partitions of their epoch, then drops the `nullifiers` table.
+///
+/// The partition of the first epoch is always created, so the nullifiers of an empty database
+/// still have a table.
+fn partition_nullifiers(transaction: &Transaction) -> HookResult {
+    let mut epochs: Vec<u32> = transaction
+        .prepare("SELECT DISTINCT block_num / ?1 FROM nullifiers ORDER BY 1;")?
+        .query_map([NULLIFIER_EPOCH_BLOCKS], |row| row.get(0))?
+        .collect::<rusqlite::Result<_>>()?;
+    if epochs.first() != Some(&0) {
+        epochs.insert(0, 0);
+    }
+
+    for epoch in epochs {
+        create_nullifier_partition(transaction, epoch)?;
+        transaction.execute(
+            &format!(
+                "
+                INSERT INTO {}
+                    SELECT nullifier, nullifier_prefix, block_num FROM nullifiers
+                    WHERE block_num / ?1 = ?2;",
+                nullifier_partition(epoch)
+            ),
+            [NULLIFIER_EPOCH_BLOCKS, epoch],
+        )?;
+    }
+
+    transaction.execute_batch("DROP TABLE nullifiers;")?;
+    Ok(())
+}
+
+/// Adds the epoch of the notes, the epoch of their block like the partitions of the nullifiers, so
+/// the notes of a sender are listed by epoch.
+fn add_note_epochs(transaction: &Transaction) -> HookResult {
+    transaction.execute_batch(&format!(
+        "
+        ALTER TABLE notes ADD COLUMN epoch INTEGER
+            GENERATED ALWAYS AS (block_num / {NULLIFIER_EPOCH_BLOCKS}) VIRTUAL;
+
+        CREATE INDEX idx_notes_sender_epoch ON notes(sender, epoch);
+        "
+    ))?;
+    Ok(())
+}
+
+/// Returns `true` if all the [MIGRATIONS] were applied to the database.
+pub fn is_up_to_date(conn: &Connection) -> rusqlite_migration::Result<bool> {
+    let mut latest = Connection::open_in_memory()?;
+    MIGRATIONS.to_latest(&mut latest)?;
+
+    Ok(MIGRATIONS.current_version(conn)? == MIGRATIONS.current_version(&latest)?)
+}
+
 #[test]
 fn migrations_test() {
     assert_eq!(MIGRATIONS.validate(), Ok(()));
@@ -1,27 +1,62 @@
-use std::fs::{self, create_dir_all};
+use std::{
+    collections::BTreeMap,
+    fs::{self, create_dir_all},
+    path::PathBuf,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use deadpool_sqlite::{Config as SqliteConfig, Hook, HookError, Pool, Runtime};
-use miden_node_proto::domain::accounts::{AccountInfo, AccountSummary, AccountUpdateDetails};
+use miden_node_proto::domain::{
+    accounts::{AccountInfo, AccountLookup, AccountUpdateDetails},
+    transactions::{TransactionFee, TransactionReceipt},
+};
+use miden_node_utils::{chain_params::ChainParameters, formatting::format_opt};
 use miden_objects::{
     block::BlockNoteTree,
-    crypto::{hash::rpo::RpoDigest, merkle::MerklePath, utils::Deserializable},
-    notes::{NoteId, NoteType, Nullifier},
-    BlockHeader, GENESIS_BLOCK,
+    crypto::{
+        dsa::rpo_falcon512::Signature,
+        hash::rpo::RpoDigest,
+        merkle::MerklePath,
+        utils::{Deserializable, Serializable},
+    },
+    notes::{NoteId, NoteMetadata, NoteType, Nullifier},
+    transaction::AccountDetails,
+    BlockHeader, Felt, GENESIS_BLOCK,
 };
-use rusqlite::vtab::array;
+use rusqlite::{vtab::array, Connection, InterruptHandle, TransactionBehavior};
 use tokio::sync::oneshot;
-use tracing::{info, info_span, instrument};
+use tracing::{info, info_span, instrument, warn};
 
 use crate::{
     config::StoreConfig,
-    errors::{DatabaseError, DatabaseSetupError, GenesisError, StateSyncError},
-    genesis::GenesisState,
+    errors::{DatabaseError, DatabaseSetupError, GenesisError, NoteSyncError, StateSyncError},
+    genesis::{genesis_note_position, GenesisState},
     types::{AccountId, BlockNumber},
-    COMPONENT,
+    COMPONENT, STATE_LOAD_CHUNK_SIZE,
 };
 
+mod contention;
+mod inspect;
+mod lock;
 mod migrations;
+mod query_stats;
+mod schema;
+mod size;
 mod sql;
+mod state_sync;
+mod views;
+
+pub use contention::{contention_metrics, ContentionMetrics};
+use contention::{install_busy_handler, set_busy_timeout, StatementTimings};
+pub use inspect::{epoch_ranges, DbInspector, DbStats, RowCounts, StateDelta};
+pub use lock::StoreInstance;
+use lock::{DatabaseLock, APPLICATION_ID};
+pub use query_stats::{query_metrics, QueryMetrics, QUERY_DURATION_BUCKETS_MS};
+use query_stats::{record_query, set_slow_query_threshold, QueryRows};
+pub use schema::{ColumnSchema, DatabaseSchema, IndexSchema, TableSchema, ViewSchema};
+pub use size::{database_size, monitor_size, DatabaseSize};
+use state_sync::StateSyncPlan;
 
 #[cfg(test)]
 mod tests;
@@ -30,12 +65,57 @@ pub type Result<T, E = DatabaseError> = std::result::Result<T, E>;
 
 pub struct Db {
     pool: Pool,
+    /// `apply_block` transactions taking longer than this are logged
+    slow_apply_block: Duration,
+    /// Maintain the index of the accounts by authentication public key
+    index_account_keys: bool,
+    /// Maximum number of values of a state sync filter queried at once
+    sync_filter_chunk_size: usize,
+    /// Number of latest blocks whose proofs are kept, all if zero
+    keep_last_block_proofs: u32,
+    /// Exclusive lock on the database, held as long as the database is used. Not taken if the
+    /// database is only read
+    lock: Option<DatabaseLock>,
+    /// Parameters of the chain, from the genesis file
+    chain_parameters: Option<ChainParameters>,
+}
+
+/// Delta applied to a public account by a block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccountDeltaInfo {
+    pub block_num: BlockNumber,
+    /// Serialized [AccountDelta](miden_objects::accounts::AccountDelta)
+    pub delta: Vec<u8>,
+    /// Hash of the account state after the delta
+    pub account_hash: RpoDigest,
+}
+
+/// Consumption of a note whose nullifier is known to the store.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NoteConsumption {
+    pub note_id: RpoDigest,
+    pub nullifier: Nullifier,
+    pub block_num: BlockNumber,
+    /// Transaction consuming the note, [None] if the receipts of its block weren't stored
+    pub transaction_id: Option<RpoDigest>,
 }
 
 #[derive(Debug, PartialEq)]
 pub struct NullifierInfo {
     pub nullifier: Nullifier,
     pub block_num: BlockNumber,
+    /// Timestamp of the block `block_num`
+    pub block_timestamp: u64,
+}
+
+/// Fields of a block header stored in their own columns, queried without decoding the header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockHeaderFields {
+    pub block_num: BlockNumber,
+    pub timestamp: u64,
+    pub note_root: RpoDigest,
+    pub nullifier_root: RpoDigest,
+    pub account_root: RpoDigest,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -46,6 +126,8 @@ pub struct NoteCreated {
     pub note_type: NoteType,
     pub sender: AccountId,
     pub tag: u32,
+    /// User defined value of the note's metadata, a field element
+    pub aux: u64,
     pub details: Option<Vec<u8>>,
 }
 
@@ -57,6 +139,32 @@ impl NoteCreated {
     }
 }
 
+/// Creates the note tree of a block from all its `notes`.
+///
+/// Used to check the note root of the blocks applied, and to store their note trees.
+#[instrument(target = "miden-store", skip_all)]
+pub fn build_note_tree(notes: &[NoteCreated]) -> Result<BlockNoteTree> {
+    let entries = notes
+        .iter()
+        .map(|note| {
+            let note_metadata = NoteMetadata::new(
+                note.sender.try_into()?,
+                note.note_type,
+                note.tag.into(),
+                Felt::new(note.aux),
+            )?;
+
+            Ok((
+                note.batch_index as usize,
+                note.note_index as usize,
+                (note.note_id, note_metadata),
+            ))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(BlockNoteTree::with_entries(entries)?)
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct Note {
     pub block_num: BlockNumber,
@@ -64,47 +172,177 @@ pub struct Note {
     pub merkle_path: MerklePath,
 }
 
+/// Filters and sort order used to list accounts.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct AccountFilter {
+    /// If set, only public (`true`) or only private (`false`) accounts are listed.
+    pub is_public: Option<bool>,
+    /// If set, only faucet (`true`) or only regular (`false`) accounts are listed.
+    pub is_faucet: Option<bool>,
+    /// If set, only accounts updated after this block are listed.
+    pub updated_after: Option<BlockNumber>,
+    pub sort_order: AccountSortOrder,
+}
+
+/// Filters of the synced notes, a note matching any of them is selected.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct NoteFilter {
+    pub tags: Vec<u32>,
+    /// Use cases of the note tags, see `note_tag_use_case`
+    pub use_cases: Vec<u32>,
+    pub senders: Vec<AccountId>,
+    /// Select the notes whose tag hints at execution by the network, see `is_network_note_tag`
+    pub network_notes: bool,
+    /// If not empty, only the notes matching the above filters whose `aux` is in one of the
+    /// ranges are selected
+    pub aux_ranges: Vec<AuxRange>,
+}
+
+/// Filters of the listed notes, all the notes are listed if none is set.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct NoteListFilter {
+    pub sender: Option<AccountId>,
+    /// Epoch of the block creating the notes, the epochs of the nullifier partitions
+    pub epoch: Option<u32>,
+    /// If not empty, only the notes whose `aux` is in one of the ranges are listed
+    pub aux_ranges: Vec<AuxRange>,
+}
+
+impl NoteListFilter {
+    pub fn is_empty(&self) -> bool {
+        self.sender.is_none() && self.epoch.is_none() && self.aux_ranges.is_empty()
+    }
+}
+
+/// Inclusive range of the `aux` values of the notes' metadata.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AuxRange {
+    pub min: u64,
+    pub max: u64,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum AccountSortOrder {
+    /// By the block of the latest update, oldest first.
+    #[default]
+    BlockNumAsc,
+    /// By the block of the latest update, newest first.
+    BlockNumDesc,
+    AccountIdAsc,
+    AccountIdDesc,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct NoteSyncUpdate {
+    pub notes: Vec<Note>,
+    pub block_header: BlockHeader,
+    pub chain_tip: BlockNumber,
+}
+
 #[derive(Debug, PartialEq)]
 pub struct StateSyncUpdate {
     pub notes: Vec<Note>,
     pub block_header: BlockHeader,
     pub chain_tip: BlockNumber,
-    pub account_updates: Vec<AccountSummary>,
+    /// Latest states of the requested accounts, with the details of the public accounts if
+    /// requested
+    pub account_updates: Vec<AccountInfo>,
     pub nullifiers: Vec<NullifierInfo>,
+    /// Blocks with matching notes before `block_header`, in ascending order
+    pub preceding_blocks: Vec<BlockNotes>,
+}
+
+/// The matching notes of a block.
+#[derive(Debug, PartialEq)]
+pub struct BlockNotes {
+    pub block_header: BlockHeader,
+    pub notes: Vec<Note>,
+}
+
+/// Maximum size of a state sync update.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StateSyncLimits {
+    /// Maximum number of blocks with matching notes, at least one
+    pub max_blocks: u32,
+    /// Maximum number of notes, exceeded only if the first block has more notes
+    pub max_notes: usize,
+    /// Last block of the update, set when continuing a truncated state sync
+    pub until: Option<BlockNumber>,
+}
+
+impl StateSyncLimits {
+    /// Returns the limits of the continuation of a truncated state sync ending at block `until`,
+    /// the continuation covers all the blocks of the truncated update.
+    pub fn until(until: BlockNumber) -> Self {
+        Self {
+            max_blocks: u32::MAX,
+            max_notes: usize::MAX,
+            until: Some(until),
+        }
+    }
 }
 
 impl Db {
     /// Open a connection to the DB, apply any pending migrations, and ensure that the genesis block
     /// is as expected and present in the database.
+    ///
+    /// The database is locked for this store as long as the returned [Db] is alive, the setup of a
+    /// second store using the same database fails.
+    ///
+    /// If [StoreConfig::read_only] is set, the database must exist and be migrated, and it is
+    /// neither locked nor written to: its genesis block is only checked.
     // TODO: This span is logged in a root span, we should connect it to the parent one.
     #[instrument(target = "miden-store", skip_all)]
     pub async fn setup(config: StoreConfig) -> Result<Self, DatabaseSetupError> {
         info!(target: COMPONENT, %config, "Connecting to the database");
 
-        if let Some(p) = config.database_filepath.parent() {
+        let read_only = config.read_only;
+        if read_only {
+            if !config.database_filepath.exists() {
+                return Err(DatabaseSetupError::MissingDatabase { path: config.database_filepath });
+            }
+        } else if let Some(p) = config.database_filepath.parent() {
             create_dir_all(p).map_err(DatabaseError::IoError)?;
         }
 
+        // Locked before the database is opened, so a database used by another store is left
+        // untouched. A read-only store doesn't lock it, any number of them can read the database
+        let lock = (!read_only)
+            .then(|| DatabaseLock::acquire(&config.database_filepath))
+            .transpose()?;
+
+        set_busy_timeout(config.database.busy_timeout());
+        set_slow_query_threshold(config.database.slow_query());
+
         let pool = SqliteConfig::new(config.database_filepath.clone())
             .builder(Runtime::Tokio1)
             .expect("Infallible")
             .post_create(Hook::async_fn(move |conn, _| {
                 Box::pin(async move {
                     let _ = conn
-                        .interact(|conn| {
+                        .interact(move |conn| {
                             // Feature used to support `IN` and `NOT IN` queries. We need to load
                             // this module for every connection we create to the DB to support the
                             // queries we want to run
                             array::load_module(conn)?;
 
-                            // Enable the WAL mode. This allows concurrent reads while the
-                            // transaction is being written, this is required for proper
-                            // synchronization of the servers in-memory and on-disk representations
-                            // (see [State::apply_block])
-                            conn.execute("PRAGMA journal_mode = WAL;", ())?;
+                            if read_only {
+                                // Any statement writing to the database fails. The journal mode
+                                // is left as is, changing it writes to the database
+                                conn.execute("PRAGMA query_only = ON;", ())?;
+                            } else {
+                                // Enable the WAL mode. This allows concurrent reads while the
+                                // transaction is being written, this is required for proper
+                                // synchronization of the servers in-memory and on-disk
+                                // representations (see [State::apply_block])
+                                conn.execute("PRAGMA journal_mode = WAL;", ())?;
+                            }
 
                             // Enable foreign key checks.
-                            conn.execute("PRAGMA foreign_keys = ON;", ())
+                            conn.execute("PRAGMA foreign_keys = ON;", ())?;
+
+                            // Wait for locks held by other connections, recording the contention
+                            install_busy_handler(conn)
                         })
                         .await
                         .map_err(|e| {
@@ -119,147 +357,673 @@ impl Db {
         info!(
             target: COMPONENT,
             sqlite = format!("{}", config.database_filepath.display()),
+            read_only,
             "Connected to the database"
         );
 
         let conn = pool.get().await.map_err(DatabaseError::MissingDbConnection)?;
 
-        conn.interact(|conn| migrations::MIGRATIONS.to_latest(conn))
+        // The databases created before the application id was set have none
+        let application_id = conn
+            .interact(sql::select_application_id)
             .await
-            .map_err(|err| {
-                DatabaseError::InteractError(format!("Migration task failed: {err}"))
-            })??;
+            .map_err(|err| DatabaseError::InteractError(err.to_string()))??;
+        if application_id != 0 && application_id != APPLICATION_ID {
+            return Err(DatabaseSetupError::NotAStoreDatabase {
+                path: config.database_filepath,
+                application_id,
+            });
+        }
+
+        match &lock {
+            Some(lock) => {
+                // The views are dropped while the tables are migrated, and created again once they
+                // are up to date
+                conn.interact(|conn| views::drop_views(conn))
+                    .await
+                    .map_err(|err| DatabaseError::InteractError(err.to_string()))?
+                    .map_err(DatabaseError::from)?;
+                conn.interact(|conn| migrations::MIGRATIONS.to_latest(conn)).await.map_err(
+                    |err| DatabaseError::InteractError(format!("Migration task failed: {err}")),
+                )??;
+                conn.interact(|conn| -> Result<()> {
+                    let transaction = conn.transaction()?;
+                    views::create_views(&transaction)?;
+                    transaction.commit()?;
+                    Ok(())
+                })
+                .await
+                .map_err(|err| DatabaseError::InteractError(err.to_string()))??;
+
+                let instance = lock.instance().clone();
+                let previous_instance = conn
+                    .interact(move |conn| -> Result<_> {
+                        sql::set_application_id(conn, APPLICATION_ID)?;
+                        let previous_instance = sql::select_store_instance(conn)?;
 
-        let db = Db { pool };
-        db.ensure_genesis_block(&config.genesis_filepath.as_path().to_string_lossy())
+                        let transaction = conn.transaction()?;
+                        sql::upsert_store_instance(&transaction, &instance)?;
+                        transaction.commit()?;
+
+                        Ok(previous_instance)
+                    })
+                    .await
+                    .map_err(|err| DatabaseError::InteractError(err.to_string()))??;
+                info!(
+                    target: COMPONENT,
+                    instance = %lock.instance(),
+                    previous_instance = %format_opt(previous_instance.as_ref()),
+                    "Took ownership of the database"
+                );
+            },
+            None => {
+                let up_to_date = conn
+                    .interact(|conn| migrations::is_up_to_date(conn))
+                    .await
+                    .map_err(|err| {
+                        DatabaseError::InteractError(format!("Schema version task failed: {err}"))
+                    })??;
+                if !up_to_date {
+                    return Err(DatabaseSetupError::OutdatedSchema {
+                        path: config.database_filepath,
+                    });
+                }
+            },
+        }
+
+        let mut db = Db {
+            pool,
+            slow_apply_block: config.database.slow_apply_block(),
+            index_account_keys: config.index_account_keys,
+            sync_filter_chunk_size: config.sync_filters.chunk_size,
+            keep_last_block_proofs: config.block_proofs.keep_last_blocks,
+            lock,
+            chain_parameters: None,
+        };
+        db.chain_parameters = db
+            .ensure_genesis_block(&config.genesis_filepath.as_path().to_string_lossy())
             .await?;
+        if !read_only {
+            db.sync_account_keys_index().await?;
+            db.backfill_block_header_columns().await?;
+            db.backfill_account_nonces().await?;
+            db.backfill_note_trees().await?;
+        }
 
         Ok(db)
     }
 
-    /// Loads all the nullifiers from the DB.
-    #[instrument(target = "miden-store", skip_all, ret(level = "debug"), err)]
-    pub async fn select_nullifiers(&self) -> Result<Vec<(Nullifier, BlockNumber)>> {
-        self.pool.get().await?.interact(sql::select_nullifiers).await.map_err(|err| {
-            DatabaseError::InteractError(format!("Select nullifiers task failed: {err}"))
-        })?
+    /// Returns the store instance owning the database, [None] if the database is read-only.
+    pub fn instance(&self) -> Option<&StoreInstance> {
+        self.lock.as_ref().map(DatabaseLock::instance)
     }
 
-    /// Loads all the notes from the DB.
-    #[instrument(target = "miden-store", skip_all, ret(level = "debug"), err)]
-    pub async fn select_notes(&self) -> Result<Vec<Note>> {
-        self.pool.get().await?.interact(sql::select_notes).await.map_err(|err| {
-            DatabaseError::InteractError(format!("Select notes task failed: {err}"))
-        })?
+    /// Returns `true` if the database is only read, see [StoreConfig::read_only].
+    pub fn is_read_only(&self) -> bool {
+        self.lock.is_none()
     }
 
-    /// Loads all the accounts from the DB.
-    #[instrument(target = "miden-store", skip_all, ret(level = "debug"), err)]
-    pub async fn select_accounts(&self) -> Result<Vec<AccountInfo>> {
-        self.pool.get().await?.interact(sql::select_accounts).await.map_err(|err| {
-            DatabaseError::InteractError(format!("Select accounts task failed: {err}"))
-        })?
+    /// Returns the parameters of the chain, or [None] if the genesis file doesn't set them.
+    pub fn chain_parameters(&self) -> Option<ChainParameters> {
+        self.chain_parameters
     }
 
-    /// Search for a [BlockHeader] from the database by its `block_num`.
-    ///
-    /// When `block_number` is [None], the latest block header is returned.
-    #[instrument(target = "miden-store", skip_all, ret(level = "debug"), err)]
-    pub async fn select_block_header_by_block_num(
-        &self,
-        block_number: Option<BlockNumber>,
-    ) -> Result<Option<BlockHeader>> {
+    /// Sets the nonce of the public accounts stored before it was stored in its own column.
+    #[instrument(target = "miden-store", skip_all, err)]
+    async fn backfill_account_nonces(&self) -> Result<()> {
         self.pool
             .get()
             .await?
-            .interact(move |conn| sql::select_block_header_by_block_num(conn, block_number))
+            .interact(move |conn| -> Result<()> {
+                let transaction = conn.transaction()?;
+                let count = sql::backfill_account_nonces(&transaction)?;
+                transaction.commit()?;
+
+                if count > 0 {
+                    info!(target: COMPONENT, count, "Backfilled the account nonces");
+                }
+
+                Ok(())
+            })
             .await
             .map_err(|err| {
-                DatabaseError::InteractError(format!("Select block header task failed: {err}"))
+                DatabaseError::InteractError(format!("Backfill account nonces task failed: {err}"))
             })?
     }
 
-    /// Loads all the block headers from the DB.
-    #[instrument(target = "miden-store", skip_all, ret(level = "debug"), err)]
-    pub async fn select_block_headers(&self) -> Result<Vec<BlockHeader>> {
+    /// Stores the note trees of the blocks inserted before they were stored in their own table.
+    #[instrument(target = "miden-store", skip_all, err)]
+    async fn backfill_note_trees(&self) -> Result<()> {
         self.pool
             .get()
             .await?
-            .interact(sql::select_block_headers)
+            .interact(move |conn| -> Result<()> {
+                let transaction = conn.transaction()?;
+                let count = sql::backfill_note_trees(&transaction)?;
+                transaction.commit()?;
+
+                if count > 0 {
+                    info!(target: COMPONENT, count, "Backfilled the note trees");
+                }
+
+                Ok(())
+            })
             .await
             .map_err(|err| {
-                DatabaseError::InteractError(format!("Select block headers task failed: {err}"))
+                DatabaseError::InteractError(format!("Backfill note trees task failed: {err}"))
             })?
     }
 
-    /// Loads all the account hashes from the DB.
-    #[instrument(target = "miden-store", skip_all, ret(level = "debug"), err)]
-    pub async fn select_account_hashes(&self) -> Result<Vec<(AccountId, RpoDigest)>> {
+    /// Sets the timestamp and the roots of the blocks inserted before they were stored in their own
+    /// columns.
+    #[instrument(target = "miden-store", skip_all, err)]
+    async fn backfill_block_header_columns(&self) -> Result<()> {
         self.pool
             .get()
             .await?
-            .interact(sql::select_account_hashes)
+            .interact(move |conn| -> Result<()> {
+                let transaction = conn.transaction()?;
+                let count = sql::backfill_block_header_columns(&transaction)?;
+                transaction.commit()?;
+
+                if count > 0 {
+                    info!(target: COMPONENT, count, "Backfilled the block header columns");
+                }
+
+                Ok(())
+            })
             .await
             .map_err(|err| {
-                DatabaseError::InteractError(format!("Select account hashes task failed: {err}"))
+                DatabaseError::InteractError(format!(
+                    "Backfill block header columns task failed: {err}"
+                ))
             })?
     }
 
-    /// Loads public account details from the DB.
-    #[instrument(target = "miden-store", skip_all, ret(level = "debug"), err)]
-    pub async fn select_account(&self, id: AccountId) -> Result<AccountInfo> {
+    /// Clears the account public key index if it is disabled, so it is never stale, or builds it if
+    /// it is enabled and empty, e.g. the first time it is enabled.
+    #[instrument(target = "miden-store", skip_all, err)]
+    async fn sync_account_keys_index(&self) -> Result<()> {
+        let index_account_keys = self.index_account_keys;
+
         self.pool
             .get()
             .await?
-            .interact(move |conn| sql::select_account(conn, id))
+            .interact(move |conn| -> Result<()> {
+                let transaction = conn.transaction()?;
+                if !index_account_keys {
+                    sql::clear_account_keys(&transaction)?;
+                } else if sql::is_account_keys_empty(&transaction)? {
+                    let count = sql::rebuild_account_keys(&transaction)?;
+                    info!(target: COMPONENT, count, "Built the account public key index");
+                }
+                transaction.commit()?;
+
+                Ok(())
+            })
             .await
             .map_err(|err| {
-                DatabaseError::InteractError(format!("Get account details task failed: {err}"))
+                DatabaseError::InteractError(format!("Sync account keys index task failed: {err}"))
             })?
     }
 
+    /// Loads the ids of the accounts authenticated by `public_key`.
+    ///
+    /// Returns [None] if the account public key index is disabled.
+    #[instrument(target = "miden-store", skip_all, ret(level = "debug"), err)]
+    pub async fn select_accounts_by_public_key(
+        &self,
+        public_key: RpoDigest,
+    ) -> Result<Option<Vec<AccountId>>> {
+        if !self.index_account_keys {
+            return Ok(None);
+        }
+
+        let params = format!("public_key={public_key}");
+        self.interruptible_query("Select accounts by public key", params, move |conn| {
+            sql::select_accounts_by_public_key(conn, public_key)
+        })
+        .await
+        .map(Some)
+    }
+
+    /// Loads at most `limit` nullifiers from the DB, ordered by nullifier and starting after the
+    /// nullifier `after`.
+    #[instrument(target = "miden-store", skip_all, ret(level = "debug"), err)]
+    pub async fn select_nullifiers_page(
+        &self,
+        after: Option<Nullifier>,
+        limit: usize,
+    ) -> Result<Vec<(Nullifier, BlockNumber)>> {
+        let params = format!("after={after:?}, limit={limit}");
+        self.interruptible_query("Select nullifiers", params, move |conn| {
+            sql::select_nullifiers_chunk(conn, after, limit)
+        })
+        .await
+    }
+
+    /// Loads the number of rows of the tables holding the state of the chain.
+    #[instrument(target = "miden-store", skip_all, ret(level = "debug"), err)]
+    pub async fn select_row_counts(&self) -> Result<RowCounts> {
+        self.interruptible_query("Select row counts", String::new(), sql::select_row_counts)
+            .await
+    }
+
+    /// Checkpoints the write-ahead log into the database file, see [sql::checkpoint_wal].
+    #[instrument(target = "miden-store", skip_all, err)]
+    pub async fn checkpoint_wal(&self) -> Result<()> {
+        self.interruptible_query("Checkpoint WAL", String::new(), sql::checkpoint_wal)
+            .await
+    }
+
+    /// Copies the database to `path` and returns the latest block header of the copy, see
+    /// [sql::backup_database].
+    #[instrument(target = "miden-store", skip_all, fields(path = %path.display()), err)]
+    pub async fn backup(&self, path: PathBuf) -> Result<BlockHeader> {
+        let params = format!("path={}", path.display());
+        self.interruptible_query("Backup database", params, move |conn| {
+            sql::backup_database(conn, &path)
+        })
+        .await
+    }
+
+    /// Returns the size of the database file, see [sql::select_database_bytes].
+    #[instrument(target = "miden-store", skip_all, ret(level = "debug"), err)]
+    pub async fn select_database_bytes(&self) -> Result<u64> {
+        self.interruptible_query("Select database bytes", String::new(), sql::select_database_bytes)
+            .await
+    }
+
+    /// Returns the total fees paid by the transactions, per faucet.
+    #[instrument(target = "miden-store", skip_all, ret(level = "debug"), err)]
+    pub async fn select_collected_fees(&self) -> Result<Vec<TransactionFee>> {
+        self.interruptible_query("Select collected fees", String::new(), sql::select_collected_fees)
+            .await
+    }
+
+    /// Returns the size of the database and of each of its tables, see
+    /// [sql::select_database_size].
+    #[instrument(target = "miden-store", skip_all, ret(level = "debug"), err)]
+    pub async fn select_database_size(&self) -> Result<DatabaseSize> {
+        self.interruptible_query("Select database size", String::new(), sql::select_database_size)
+            .await
+    }
+
+    /// Returns the tables, indexes and views of the database, see [sql::select_schema].
+    #[instrument(target = "miden-store", skip_all, ret(level = "debug"), err)]
+    pub async fn select_schema(&self) -> Result<DatabaseSchema> {
+        self.interruptible_query("Select schema", String::new(), sql::select_schema)
+            .await
+    }
+
+    /// Loads at most `limit` notes matching `filter` from the DB, ordered by block and position in
+    /// the block and starting after the note at `after`, see [sql::select_notes_page].
+    #[instrument(target = "miden-store", skip_all, ret(level = "debug"), err)]
+    pub async fn select_notes_page(
+        &self,
+        filter: NoteListFilter,
+        after: Option<(BlockNumber, u32, u32)>,
+        limit: usize,
+    ) -> Result<Vec<Note>> {
+        let params = format!("filter={filter:?}, after={after:?}, limit={limit}");
+        self.interruptible_query("Select notes", params, move |conn| {
+            sql::select_notes_page(conn, &filter, after, Some(limit))
+        })
+        .await
+    }
+
+    /// Loads at most `limit` accounts matching `filter` from the DB, starting after the account
+    /// `after`, see [sql::select_accounts_page].
+    #[instrument(target = "miden-store", skip_all, ret(level = "debug"), err)]
+    pub async fn select_accounts_page(
+        &self,
+        filter: AccountFilter,
+        after: Option<(BlockNumber, AccountId)>,
+        limit: usize,
+    ) -> Result<Vec<AccountInfo>> {
+        let params = format!("filter={filter:?}, after={after:?}, limit={limit}");
+        self.interruptible_query("Select accounts", params, move |conn| {
+            sql::select_accounts_page(conn, &filter, after, Some(limit))
+        })
+        .await
+    }
+
+    /// Loads the timestamps of the blocks `block_nums`, keyed by block number.
+    #[instrument(target = "miden-store", skip_all, ret(level = "debug"), err)]
+    pub async fn select_block_timestamps(
+        &self,
+        block_nums: Vec<BlockNumber>,
+    ) -> Result<BTreeMap<BlockNumber, u64>> {
+        let params = format!("block_nums={block_nums:?}");
+        self.interruptible_query("Select block timestamps", params, move |conn| {
+            sql::select_block_timestamps(conn, &block_nums)
+        })
+        .await
+    }
+
+    /// Loads the [BlockHeaderFields] of at most `limit` blocks, starting from the block
+    /// `block_from`.
+    #[instrument(target = "miden-store", skip_all, ret(level = "debug"), err)]
+    pub async fn select_block_header_fields(
+        &self,
+        block_from: BlockNumber,
+        limit: usize,
+    ) -> Result<Vec<BlockHeaderFields>> {
+        let params = format!("block_from={block_from}, limit={limit}");
+        self.interruptible_query("Select block header fields", params, move |conn| {
+            sql::select_block_header_fields(conn, block_from, limit)
+        })
+        .await
+    }
+
+    /// Search for a [BlockHeader] from the database by its `block_num`.
+    ///
+    /// When `block_number` is [None], the latest block header is returned.
+    #[instrument(target = "miden-store", skip_all, ret(level = "debug"), err)]
+    pub async fn select_block_header_by_block_num(
+        &self,
+        block_number: Option<BlockNumber>,
+    ) -> Result<Option<BlockHeader>> {
+        let params = format!("block_number={block_number:?}");
+        self.interruptible_query("Select block header", params, move |conn| {
+            sql::select_block_header_by_block_num(conn, block_number)
+        })
+        .await
+    }
+
+    /// Loads the sequencer's signature of the block `block_num`, [None] if the block doesn't exist
+    /// or is not signed.
+    #[instrument(target = "miden-store", skip_all, ret(level = "debug"), err)]
+    pub async fn select_block_signature(
+        &self,
+        block_num: BlockNumber,
+    ) -> Result<Option<Signature>> {
+        let params = format!("block_num={block_num}");
+        self.interruptible_query("Select block signature", params, move |conn| {
+            sql::select_block_signature(conn, block_num)
+        })
+        .await
+    }
+
+    /// Loads at most `limit` encoded blocks, starting from the block `block_num`.
+    #[instrument(target = "miden-store", skip_all, err)]
+    pub async fn select_blocks(
+        &self,
+        block_num: BlockNumber,
+        limit: usize,
+    ) -> Result<Vec<(BlockNumber, Vec<u8>)>> {
+        let params = format!("block_num={block_num}, limit={limit}");
+        self.interruptible_query("Select blocks", params, move |conn| {
+            sql::select_blocks(conn, block_num, limit)
+        })
+        .await
+    }
+
+    /// Loads all the nullifiers from the DB in chunks, partition by partition, see
+    /// [Db::select_in_chunks].
+    #[instrument(target = "miden-store", skip_all, err)]
+    pub async fn load_nullifiers(&self) -> Result<Vec<(Nullifier, BlockNumber)>> {
+        self.select_in_chunks(
+            "Load nullifiers",
+            sql::select_nullifiers_by_partition_chunk,
+            |(nullifier, block_num)| (sql::nullifier_epoch(*block_num), *nullifier),
+        )
+        .await
+    }
+
+    /// Loads all the block headers from the DB in chunks, ordered by block number, see
+    /// [Db::select_in_chunks].
+    #[instrument(target = "miden-store", skip_all, err)]
+    pub async fn load_block_headers(&self) -> Result<Vec<BlockHeader>> {
+        self.select_in_chunks("Load block headers", sql::select_block_headers_chunk, |header| {
+            header.block_num().into()
+        })
+        .await
+    }
+
+    /// Loads all the account hashes from the DB in chunks, see [Db::select_in_chunks].
+    #[instrument(target = "miden-store", skip_all, err)]
+    pub async fn load_account_hashes(&self) -> Result<Vec<(AccountId, RpoDigest)>> {
+        self.select_in_chunks("Load account hashes", sql::select_account_hashes_chunk, |(id, _)| {
+            *id
+        })
+        .await
+    }
+
+    /// Loads the latest state of the account from the DB, with its details if it's public.
+    #[instrument(target = "miden-store", skip_all, ret(level = "debug"), err)]
+    pub async fn select_account(&self, id: AccountId) -> Result<AccountLookup> {
+        let params = format!("id={id}");
+        self.interruptible_query("Get account details", params, move |conn| {
+            sql::select_account(conn, id)
+        })
+        .await
+    }
+
+    /// Loads at most `limit` deltas applied to the account `account_id` by the blocks in
+    /// `(block_start, block_end]`, see [sql::select_account_deltas].
+    #[instrument(target = "miden-store", skip_all, ret(level = "debug"), err)]
+    pub async fn select_account_deltas(
+        &self,
+        account_id: AccountId,
+        block_start: BlockNumber,
+        block_end: BlockNumber,
+        limit: usize,
+    ) -> Result<Vec<AccountDeltaInfo>> {
+        let params = format!(
+            "account_id={account_id}, block_start={block_start}, block_end={block_end}, \
+             limit={limit}"
+        );
+        self.interruptible_query("Select account deltas", params, move |conn| {
+            sql::select_account_deltas(conn, account_id, block_start, block_end, limit)
+        })
+        .await
+    }
+
+    /// Loads the first block whose account deltas are stored.
+    #[instrument(target = "miden-store", skip_all, ret(level = "debug"), err)]
+    pub async fn select_account_deltas_start(&self) -> Result<BlockNumber> {
+        self.interruptible_query(
+            "Select account deltas start",
+            String::new(),
+            sql::select_account_deltas_start,
+        )
+        .await
+    }
+
+    /// Loads the state necessary for a state sync, see [StateSyncPlan].
+    ///
+    /// The account updates are selected with the snapshot block, in the same read transaction, then
+    /// the notes and nullifiers are selected concurrently on pooled connections, bounded by the
+    /// snapshot block, and merged at the end.
+    #[allow(clippy::too_many_arguments)]
     #[instrument(target = "miden-store", skip_all, ret(level = "debug"), err)]
     pub async fn get_state_sync(
         &self,
         block_num: BlockNumber,
         account_ids: &[AccountId],
         note_tag_prefixes: &[u32],
+        nullifier_prefix_len: u32,
         nullifier_prefixes: &[u32],
+        limits: StateSyncLimits,
+        include_account_details: bool,
     ) -> Result<StateSyncUpdate, StateSyncError> {
-        let account_ids = account_ids.to_vec();
-        let note_tag_prefixes = note_tag_prefixes.to_vec();
-        let nullifier_prefixes = nullifier_prefixes.to_vec();
+        let params = format!(
+            "block_num={block_num}, account_ids={}, note_tag_prefixes={}, nullifier_prefixes={}, \
+            nullifier_prefix_len={nullifier_prefix_len}, \
+            include_account_details={include_account_details}",
+            account_ids.len(),
+            note_tag_prefixes.len(),
+            nullifier_prefixes.len()
+        );
+        let plan = Arc::new(StateSyncPlan {
+            block_num,
+            account_ids: account_ids.to_vec(),
+            note_tag_prefixes: note_tag_prefixes.to_vec(),
+            nullifier_prefix_len,
+            nullifier_prefixes: nullifier_prefixes.to_vec(),
+            limits,
+            include_account_details,
+            filter_chunk_size: self.sync_filter_chunk_size,
+        });
 
-        self.pool
-            .get()
-            .await
-            .map_err(DatabaseError::MissingDbConnection)?
-            .interact(move |conn| {
-                sql::get_state_sync(
-                    conn,
-                    block_num,
-                    &account_ids,
-                    &note_tag_prefixes,
-                    &nullifier_prefixes,
-                )
+        let (chain_tip, snapshot_header, account_updates) = self
+            .interruptible_query("Get state sync accounts", params.clone(), {
+                let plan = plan.clone();
+                move |conn| plan.select_snapshot_and_account_updates(conn)
             })
-            .await
-            .map_err(|err| {
-                DatabaseError::InteractError(format!("Get state sync task failed: {err}"))
-            })?
+            .await?;
+        let snapshot = BlockNumber::from(snapshot_header.block_num());
+
+        let (blocks, nullifiers) = tokio::try_join!(
+            self.interruptible_query("Get state sync notes", params.clone(), {
+                let plan = plan.clone();
+                move |conn| plan.select_notes(conn, snapshot)
+            }),
+            self.interruptible_query("Get state sync nullifiers", params.clone(), {
+                let plan = plan.clone();
+                move |conn| plan.select_nullifiers(conn, snapshot)
+            }),
+        )
+        .map_err(StateSyncError::DatabaseError)?;
+
+        self.interruptible_query("Get state sync merge", params, move |conn| {
+            plan.merge(conn, chain_tip, snapshot_header, blocks, account_updates, nullifiers)
+        })
+        .await
+    }
+
+    #[instrument(target = "miden-store", skip_all, ret(level = "debug"), err)]
+    pub async fn get_note_sync(
+        &self,
+        block_num: BlockNumber,
+        filter: NoteFilter,
+    ) -> Result<NoteSyncUpdate, NoteSyncError> {
+        let params = format!(
+            "block_num={block_num}, note_tags={}, use_cases={}, network_notes={}, aux_ranges={:?}",
+            filter.tags.len(),
+            filter.use_cases.len(),
+            filter.network_notes,
+            filter.aux_ranges
+        );
+
+        self.interruptible_query("Get notes sync", params, move |conn| {
+            sql::get_note_sync(conn, block_num, &filter)
+        })
+        .await
+    }
+
+    /// Loads all the nullifiers whose `prefix_len` high bits match one of `nullifier_prefixes`.
+    #[instrument(target = "miden-store", skip_all, ret(level = "debug"), err)]
+    pub async fn select_nullifiers_by_prefix(
+        &self,
+        prefix_len: u32,
+        nullifier_prefixes: Vec<u32>,
+    ) -> Result<Vec<NullifierInfo>> {
+        let params =
+            format!("prefix_len={prefix_len}, nullifier_prefixes={}", nullifier_prefixes.len());
+        self.interruptible_query("Select nullifiers by prefix", params, move |conn| {
+            sql::select_nullifiers_by_prefix(conn, prefix_len, &nullifier_prefixes)
+        })
+        .await
+    }
+
+    /// Loads a page of the nullifiers consumed in `(block_start, block_end]` whose `prefix_len`
+    /// high bits match one of `nullifier_prefixes`, see
+    /// [sql::select_nullifiers_page_by_block_range].
+    #[instrument(target = "miden-store", skip_all, ret(level = "debug"), err)]
+    pub async fn select_nullifiers_page_by_block_range(
+        &self,
+        block_start: BlockNumber,
+        block_end: BlockNumber,
+        prefix_len: u32,
+        nullifier_prefixes: Vec<u32>,
+        max_nullifiers: usize,
+    ) -> Result<(Vec<NullifierInfo>, Option<BlockNumber>)> {
+        let params = format!(
+            "block_start={block_start}, block_end={block_end}, prefix_len={prefix_len}, \
+             nullifier_prefixes={}, max_nullifiers={max_nullifiers}",
+            nullifier_prefixes.len()
+        );
+        self.interruptible_query("Select nullifiers page", params, move |conn| {
+            sql::select_nullifiers_page_by_block_range(
+                conn,
+                block_start,
+                block_end,
+                prefix_len,
+                &nullifier_prefixes,
+                max_nullifiers,
+            )
+        })
+        .await
     }
 
     /// Loads all the Note's matching a certain NoteId from the database.
     #[instrument(target = "miden-store", skip_all, ret(level = "debug"), err)]
     pub async fn select_notes_by_id(&self, note_ids: Vec<NoteId>) -> Result<Vec<Note>> {
-        self.pool
-            .get()
-            .await?
-            .interact(move |conn| sql::select_notes_by_id(conn, &note_ids))
-            .await
-            .map_err(|err| {
-                DatabaseError::InteractError(format!("Select note by id task failed: {err}"))
-            })?
+        let params = format!("note_ids={}", note_ids.len());
+        self.interruptible_query("Select note by id", params, move |conn| {
+            sql::select_notes_by_id(conn, &note_ids)
+        })
+        .await
+    }
+
+    /// Loads the notes created by the block `block_num`, without their details, from the database.
+    #[instrument(target = "miden-store", skip_all, ret(level = "debug"), err)]
+    pub async fn select_block_notes(&self, block_num: BlockNumber) -> Result<Vec<NoteCreated>> {
+        let params = format!("block_num={block_num}");
+        self.interruptible_query("Select block notes", params, move |conn| {
+            sql::select_block_notes(conn, block_num)
+        })
+        .await
+    }
+
+    /// Loads the receipt of the transaction with the given id from the database.
+    #[instrument(target = "miden-store", skip_all, ret(level = "debug"), err)]
+    pub async fn select_transaction_receipt(
+        &self,
+        transaction_id: RpoDigest,
+    ) -> Result<Option<TransactionReceipt>> {
+        let params = format!("transaction_id={transaction_id}");
+        self.interruptible_query("Select transaction receipt", params, move |conn| {
+            sql::select_transaction_receipt(conn, transaction_id)
+        })
+        .await
+    }
+
+    /// Loads the consumption of the note with the given id from the database, [None] if the note
+    /// isn't consumed or its nullifier isn't known.
+    #[instrument(target = "miden-store", skip_all, ret(level = "debug"), err)]
+    pub async fn select_note_consumption(
+        &self,
+        note_id: RpoDigest,
+    ) -> Result<Option<NoteConsumption>> {
+        let params = format!("note_id={note_id}");
+        self.interruptible_query("Select note consumption", params, move |conn| {
+            sql::select_note_consumption(conn, note_id)
+        })
+        .await
+    }
+
+    /// Loads the validity proof of the block `block_num` from the database, [None] if the block
+    /// isn't proven or its proof was pruned.
+    #[instrument(target = "miden-store", skip_all, err)]
+    pub async fn select_block_proof(&self, block_num: BlockNumber) -> Result<Option<Vec<u8>>> {
+        let params = format!("block_num={block_num}");
+        self.interruptible_query("Select block proof", params, move |conn| {
+            sql::select_block_proof(conn, block_num)
+        })
+        .await
+    }
+
+    /// Loads the serialized note script with the given root from the database.
+    #[instrument(target = "miden-store", skip_all, ret(level = "debug"), err)]
+    pub async fn select_note_script(&self, script_root: RpoDigest) -> Result<Option<Vec<u8>>> {
+        let params = format!("script_root={script_root}");
+        self.interruptible_query("Select note script", params, move |conn| {
+            sql::select_note_script(conn, script_root)
+        })
+        .await
     }
 
     /// Inserts the data of a new block into the DB.
@@ -267,16 +1031,25 @@ impl Db {
     /// `allow_acquire` and `acquire_done` are used to synchronize writes to the DB with writes to
     /// the in-memory trees. Further details available on [super::state::State::apply_block].
     // TODO: This span is logged in a root span, we should connect it to the parent one.
+    #[allow(clippy::too_many_arguments)]
     #[instrument(target = "miden-store", skip_all, err)]
     pub async fn apply_block(
         &self,
         allow_acquire: oneshot::Sender<()>,
         acquire_done: oneshot::Receiver<()>,
         block_header: BlockHeader,
+        signature: Option<Signature>,
+        block: Vec<u8>,
+        proof: Option<Vec<u8>>,
         notes: Vec<Note>,
         nullifiers: Vec<Nullifier>,
         accounts: Vec<AccountUpdateDetails>,
+        transactions: Vec<TransactionReceipt>,
     ) -> Result<()> {
+        let slow_apply_block = self.slow_apply_block;
+        let index_account_keys = self.index_account_keys;
+        let keep_last_block_proofs = self.keep_last_block_proofs;
+
         self.pool
             .get()
             .await?
@@ -284,15 +1057,62 @@ impl Db {
                 // TODO: This span is logged in a root span, we should connect it to the parent one.
                 let _span = info_span!(target: COMPONENT, "write_block_to_db").entered();
 
-                let transaction = conn.transaction()?;
-                sql::apply_block(&transaction, &block_header, &notes, &nullifiers, &accounts)?;
+                let block_num = block_header.block_num();
+                let mut timings = StatementTimings::start();
+
+                // The write lock is acquired upfront, a deferred transaction failing to upgrade its
+                // lock would return "database is locked" without calling the busy handler
+                let transaction = conn.transaction_with_behavior(TransactionBehavior::Immediate)?;
+                timings.record("begin");
+
+                sql::apply_block(
+                    &transaction,
+                    &block_header,
+                    signature.as_ref(),
+                    Some(&block),
+                    proof.as_deref(),
+                    &notes,
+                    &nullifiers,
+                    &accounts,
+                    &transactions,
+                    &mut timings,
+                )?;
+
+                if index_account_keys {
+                    let public_accounts: Vec<_> = accounts
+                        .iter()
+                        .filter(|update| update.details.is_some())
+                        .map(|update| update.account_id.into())
+                        .collect();
+                    sql::upsert_account_keys(&transaction, &public_accounts)?;
+                    timings.record("upsert_account_keys");
+                }
+
+                if keep_last_block_proofs != 0 {
+                    let oldest_kept = block_num.saturating_sub(keep_last_block_proofs - 1);
+                    sql::delete_block_proofs_before(&transaction, oldest_kept.into())?;
+                    timings.record("delete_block_proofs");
+                }
 
                 let _ = allow_acquire.send(());
                 acquire_done
                     .blocking_recv()
                     .map_err(DatabaseError::ApplyBlockFailedClosedChannel)?;
+                timings.record("wait_for_in_memory_update");
 
                 transaction.commit()?;
+                timings.record("commit");
+
+                if timings.total() > slow_apply_block {
+                    warn!(
+                        target: COMPONENT,
+                        block_num,
+                        total_ms = timings.total().as_millis(),
+                        statements = %timings,
+                        contention = %contention_metrics(),
+                        "Slow apply_block transaction"
+                    );
+                }
 
                 Ok(())
             })
@@ -310,9 +1130,15 @@ impl Db {
     /// If the database is empty, generates and stores the genesis block. Otherwise, it ensures that the
     /// genesis block in the database is consistent with the genesis block data in the genesis JSON
     /// file.
+    ///
+    /// Returns the parameters of the chain set by the genesis file, committed to by the genesis
+    /// block header.
     #[instrument(target = "miden-store", skip_all, err)]
-    async fn ensure_genesis_block(&self, genesis_filepath: &str) -> Result<(), GenesisError> {
-        let (expected_genesis_header, account_smt) = {
+    async fn ensure_genesis_block(
+        &self,
+        genesis_filepath: &str,
+    ) -> Result<Option<ChainParameters>, GenesisError> {
+        let (expected_genesis_header, accounts, notes, parameters) = {
             let file_contents = fs::read(genesis_filepath).map_err(|error| {
                 GenesisError::FailedToReadGenesisFile {
                     genesis_filepath: genesis_filepath.to_string(),
@@ -322,12 +1148,72 @@ impl Db {
 
             let genesis_state = GenesisState::read_from_bytes(&file_contents)
                 .map_err(GenesisError::GenesisFileDeserializationError)?;
+            let parameters = genesis_state.parameters;
+            if let Some(parameters) = &parameters {
+                parameters.validate()?;
+            }
+
+            // Public accounts are stored with their details, private accounts only with their hash
+            let accounts: Vec<_> = genesis_state
+                .accounts
+                .iter()
+                .map(|account| AccountUpdateDetails {
+                    account_id: account.id(),
+                    final_state_hash: account.hash(),
+                    details: account
+                        .id()
+                        .is_on_chain()
+                        .then(|| AccountDetails::Full(account.clone())),
+                })
+                .collect();
+
+            // Public notes are stored with their details, private notes only with their metadata
+            let notes: Vec<_> = genesis_state
+                .notes
+                .iter()
+                .enumerate()
+                .map(|(index, note)| {
+                    let (batch_index, note_index) = genesis_note_position(index);
+                    NoteCreated {
+                        batch_index: batch_index as u32,
+                        note_index: note_index as u32,
+                        note_id: note.id().into(),
+                        note_type: note.metadata().note_type(),
+                        sender: note.metadata().sender().into(),
+                        tag: note.metadata().tag().into(),
+                        aux: note.metadata().aux().as_int(),
+                        details: (note.metadata().note_type() == NoteType::Public)
+                            .then(|| note.to_bytes()),
+                    }
+                })
+                .collect();
+
+            let (block_header, _, note_tree) =
+                genesis_state.into_block_parts().map_err(GenesisError::MalformedGenesisState)?;
+
+            let notes = notes
+                .into_iter()
+                .map(|note_created| {
+                    let merkle_path = note_tree
+                        .get_note_path(
+                            note_created.batch_index as usize,
+                            note_created.note_index as usize,
+                        )
+                        .map_err(GenesisError::MalformedGenesisState)?;
+
+                    Ok(Note {
+                        block_num: GENESIS_BLOCK.into(),
+                        note_created,
+                        merkle_path,
+                    })
+                })
+                .collect::<Result<Vec<_>, GenesisError>>()?;
 
-            genesis_state.into_block_parts().map_err(GenesisError::MalformedGenesisState)?
+            (block_header, accounts, notes, parameters)
         };
 
         let maybe_block_header_in_store = self
-            .select_block_header_by_block_num(Some(GENESIS_BLOCK))
+            .select_block_header_by_block_num(Some(GENESIS_BLOCK.into()))
             .await
             .map_err(|err| GenesisError::SelectBlockHeaderByBlockNumError(err.into()))?;
 
@@ -341,6 +1227,7 @@ impl Db {
                     })?;
                 }
             },
+            None if self.is_read_only() => Err(GenesisError::MissingGenesisBlock)?,
             None => {
                 // add genesis header to store
                 self.pool
@@ -353,22 +1240,17 @@ impl Db {
                         let guard = span.enter();
 
                         let transaction = conn.transaction()?;
-                        let accounts: Vec<_> = account_smt
-                            .leaves()
-                            .map(|(account_id, state_hash)| {
-                                Ok(AccountUpdateDetails {
-                                    account_id: account_id.try_into()?,
-                                    final_state_hash: state_hash.into(),
-                                    details: None,
-                                })
-                            })
-                            .collect::<Result<_, DatabaseError>>()?;
                         sql::apply_block(
                             &transaction,
                             &expected_genesis_header,
-                            &[],
+                            None,
+                            None,
+                            None,
+                            &notes,
                             &[],
                             &accounts,
+                            &[],
+                            &mut StatementTimings::start(),
                         )?;
 
                         transaction.commit()?;
@@ -381,6 +1263,125 @@ impl Db {
             },
         }
 
-        Ok(())
+        Ok(parameters)
+    }
+
+    /// Selects all the rows of a table in chunks of [STATE_LOAD_CHUNK_SIZE] rows.
+    ///
+    /// `select_chunk` selects the rows following the one with the given key, `key` returns the key
+    /// of a row. Every chunk is a separate query, so that the loads of several tables run
+    /// concurrently on the pooled connections and none holds a read transaction for the whole
+    /// load. The progress is logged after each chunk.
+    async fn select_in_chunks<T, K>(
+        &self,
+        name: &'static str,
+        select_chunk: fn(&mut Connection, Option<K>, usize) -> Result<Vec<T>>,
+        key: fn(&T) -> K,
+    ) -> Result<Vec<T>>
+    where
+        T: Send + 'static,
+        K: Send + 'static,
+    {
+        let started = Instant::now();
+        let mut rows = Vec::new();
+        let mut after = None;
+        loop {
+            let params = format!("loaded={}", rows.len());
+            let chunk = self
+                .interruptible_query(name, params, move |conn| {
+                    select_chunk(conn, after, STATE_LOAD_CHUNK_SIZE)
+                })
+                .await?;
+
+            let complete = chunk.len() < STATE_LOAD_CHUNK_SIZE;
+            after = chunk.last().map(key);
+            rows.extend(chunk);
+            info!(target: COMPONENT, name, rows = rows.len(), "Loading rows");
+
+            if complete {
+                break;
+            }
+        }
+
+        info!(
+            target: COMPONENT,
+            name,
+            rows = rows.len(),
+            elapsed = ?started.elapsed(),
+            "Loaded rows"
+        );
+        Ok(rows)
+    }
+
+    /// Runs the read-only `query` on a pooled connection.
+    ///
+    /// The query is interrupted if the returned future is dropped before the query completes. This
+    /// happens when the gRPC server abandons a request after its deadline passed, and prevents the
+    /// abandoned query from running to completion.
+    ///
+    /// The query is timed and recorded under `name`, see [query_stats]. `params` summarizes the
+    /// parameters of the query for the logs, e.g. the number of the requested ids rather than the
+    /// ids.
+    async fn interruptible_query<F, R, E>(
+        &self,
+        name: &'static str,
+        params: String,
+        query: F,
+    ) -> Result<R, E>
+    where
+        F: FnOnce(&mut Connection) -> Result<R, E> + Send + 'static,
+        R: QueryRows + Send + 'static,
+        E: From<DatabaseError> + Send + 'static,
+    {
+        let conn = self.pool.get().await.map_err(DatabaseError::MissingDbConnection)?;
+
+        let (handle_sender, handle_receiver) = oneshot::channel();
+        let interrupt_guard = InterruptOnDrop::new(handle_receiver);
+
+        let result = conn
+            .interact(move |conn| {
+                // The receiver is dropped if the request was abandoned before the query started
+                if handle_sender.send(conn.get_interrupt_handle()).is_err() {
+                    return Err(DatabaseError::QueryInterrupted.into());
+                }
+
+                let started = Instant::now();
+                let result = query(conn);
+                record_query(name, &params, result.as_ref().ok().map(R::rows), started.elapsed());
+
+                result
+            })
+            .await;
+        interrupt_guard.disarm();
+
+        result.map_err(|err| DatabaseError::InteractError(format!("{name} task failed: {err}")))?
+    }
+}
+
+// INTERRUPT GUARD
+// ================================================================================================
+
+/// Interrupts the query running on a connection when dropped, unless disarmed.
+///
+/// The connection's [InterruptHandle] is received once the query starts.
+pub(crate) struct InterruptOnDrop(Option<oneshot::Receiver<InterruptHandle>>);
+
+impl InterruptOnDrop {
+    pub(crate) fn new(handle: oneshot::Receiver<InterruptHandle>) -> Self {
+        Self(Some(handle))
+    }
+
+    /// Consumes the guard without interrupting the query.
+    pub(crate) fn disarm(mut self) {
+        self.0 = None;
+    }
+}
+
+impl Drop for InterruptOnDrop {
+    fn drop(&mut self) {
+        if let Some(handle) = self.0.as_mut().and_then(|receiver| receiver.try_recv().ok()) {
+            info!(target: COMPONENT, "Interrupting abandoned query");
+            handle.interrupt();
+        }
     }
 }
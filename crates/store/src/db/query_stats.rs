@@ -0,0 +1,295 @@
+//! Telemetry of the read queries, see [super::Db::interruptible_query].
+//!
+//! Every query is logged at the debug level with its parameters, the number of rows it returned
+//! and its duration, and is recorded in a duration histogram per query. Queries slower than the
+//! configured threshold are logged as warnings, with the histogram of the query for comparison.
+use std::{
+    collections::BTreeMap,
+    fmt::{Display, Formatter},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+    time::Duration,
+};
+
+use miden_node_proto::domain::accounts::{AccountInfo, AccountLookup};
+use miden_objects::BlockHeader;
+use tracing::{debug, warn};
+
+use super::{
+    schema::DatabaseSchema, size::DatabaseSize, NoteSyncUpdate, NullifierInfo, RowCounts,
+    StateSyncUpdate,
+};
+use crate::{types::BlockNumber, COMPONENT};
+
+/// Upper bounds of the buckets of the duration histograms, in milliseconds. The last bucket counts
+/// the queries slower than the last bound.
+pub const QUERY_DURATION_BUCKETS_MS: [u64; 10] = [1, 5, 10, 25, 50, 100, 250, 500, 1_000, 5_000];
+
+/// The queries run on the connections of a process wide pool, so the threshold and the histograms
+/// are process wide.
+static SLOW_QUERY_MS: AtomicU64 = AtomicU64::new(500);
+static HISTOGRAMS: Mutex<BTreeMap<&'static str, QueryMetrics>> = Mutex::new(BTreeMap::new());
+
+// QUERY METRICS
+// ================================================================================================
+
+/// Durations of the runs of a query since the process started.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct QueryMetrics {
+    /// Number of runs of the query
+    pub count: u64,
+    /// Number of runs which failed
+    pub errors: u64,
+    /// Total time spent running the query, in microseconds
+    pub total_us: u64,
+    /// Duration of the slowest run, in microseconds
+    pub max_us: u64,
+    /// Number of runs per duration bucket, see [QUERY_DURATION_BUCKETS_MS]
+    pub buckets: [u64; QUERY_DURATION_BUCKETS_MS.len() + 1],
+}
+
+impl QueryMetrics {
+    fn record(&mut self, elapsed: Duration, failed: bool) {
+        let elapsed_us = elapsed.as_micros().try_into().unwrap_or(u64::MAX);
+        let elapsed_ms = elapsed_us / 1_000;
+        let bucket = QUERY_DURATION_BUCKETS_MS
+            .iter()
+            .position(|bound| elapsed_ms < *bound)
+            .unwrap_or(QUERY_DURATION_BUCKETS_MS.len());
+
+        self.count += 1;
+        self.errors += u64::from(failed);
+        self.total_us = self.total_us.saturating_add(elapsed_us);
+        self.max_us = self.max_us.max(elapsed_us);
+        self.buckets[bucket] += 1;
+    }
+
+    /// Returns the upper bound of the bucket holding the `quantile` of the durations, in
+    /// milliseconds, [None] if it is in the last, unbounded, bucket or if the query never ran.
+    pub fn quantile_ms(&self, quantile: f64) -> Option<u64> {
+        if self.count == 0 {
+            return None;
+        }
+
+        let target = ((self.count as f64) * quantile).ceil().max(1.0) as u64;
+        let mut seen = 0;
+        for (bucket, count) in self.buckets.iter().enumerate() {
+            seen += count;
+            if seen >= target {
+                return QUERY_DURATION_BUCKETS_MS.get(bucket).copied();
+            }
+        }
+
+        None
+    }
+}
+
+impl Display for QueryMetrics {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let quantile = |quantile| match self.quantile_ms(quantile) {
+            Some(bound) => format!("<{bound}ms"),
+            None => "-".to_string(),
+        };
+
+        f.write_fmt(format_args!(
+            "{{ count: {}, errors: {}, total_ms: {}, max_ms: {}, p50: {}, p99: {} }}",
+            self.count,
+            self.errors,
+            self.total_us / 1_000,
+            self.max_us / 1_000,
+            quantile(0.5),
+            quantile(0.99),
+        ))
+    }
+}
+
+/// Returns the duration histograms of the queries run since the process started, by query name.
+pub fn query_metrics() -> BTreeMap<&'static str, QueryMetrics> {
+    HISTOGRAMS.lock().expect("query histograms lock poisoned").clone()
+}
+
+// QUERY LOGGING
+// ================================================================================================
+
+/// Sets the duration above which the queries are logged as slow.
+pub fn set_slow_query_threshold(threshold: Duration) {
+    SLOW_QUERY_MS.store(threshold.as_millis().try_into().unwrap_or(u64::MAX), Ordering::Relaxed);
+}
+
+/// Records a run of the query `name` in its histogram and logs it.
+///
+/// `rows` is the number of rows returned, [None] if the query failed.
+pub fn record_query(name: &'static str, params: &str, rows: Option<usize>, elapsed: Duration) {
+    let metrics = {
+        let mut histograms = HISTOGRAMS.lock().expect("query histograms lock poisoned");
+        let metrics = histograms.entry(name).or_default();
+        metrics.record(elapsed, rows.is_none());
+        *metrics
+    };
+
+    let duration_ms = elapsed.as_millis();
+    if duration_ms >= u128::from(SLOW_QUERY_MS.load(Ordering::Relaxed)) {
+        warn!(
+            target: COMPONENT,
+            query = name,
+            params,
+            rows,
+            duration_ms,
+            histogram = %metrics,
+            "Slow query"
+        );
+    } else {
+        debug!(
+            target: COMPONENT,
+            query = name,
+            params,
+            rows,
+            duration_us = elapsed.as_micros(),
+            "Query completed"
+        );
+    }
+}
+
+// QUERY ROWS
+// ================================================================================================
+
+/// Number of rows returned by a query, as logged by [record_query].
+pub trait QueryRows {
+    fn rows(&self) -> usize;
+}
+
+impl QueryRows for () {
+    fn rows(&self) -> usize {
+        0
+    }
+}
+
+impl<T> QueryRows for Option<T> {
+    fn rows(&self) -> usize {
+        usize::from(self.is_some())
+    }
+}
+
+impl<K, V> QueryRows for BTreeMap<K, V> {
+    fn rows(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T> QueryRows for Vec<T> {
+    fn rows(&self) -> usize {
+        self.len()
+    }
+}
+
+impl QueryRows for u64 {
+    fn rows(&self) -> usize {
+        1
+    }
+}
+
+impl QueryRows for BlockNumber {
+    fn rows(&self) -> usize {
+        1
+    }
+}
+
+impl QueryRows for BlockHeader {
+    fn rows(&self) -> usize {
+        1
+    }
+}
+
+/// The chain tip, the snapshot block of a state sync and the account updates up to it.
+impl QueryRows for (BlockNumber, BlockHeader, Vec<AccountInfo>) {
+    fn rows(&self) -> usize {
+        1 + self.2.len()
+    }
+}
+
+/// A page of nullifiers and its last block, if it ends before the requested range.
+impl QueryRows for (Vec<NullifierInfo>, Option<BlockNumber>) {
+    fn rows(&self) -> usize {
+        self.0.len()
+    }
+}
+
+impl QueryRows for AccountLookup {
+    fn rows(&self) -> usize {
+        usize::from(!matches!(self, AccountLookup::NotFound))
+    }
+}
+
+impl QueryRows for DatabaseSize {
+    fn rows(&self) -> usize {
+        self.tables.len()
+    }
+}
+
+impl QueryRows for DatabaseSchema {
+    fn rows(&self) -> usize {
+        self.tables.len() + self.views.len()
+    }
+}
+
+impl QueryRows for RowCounts {
+    fn rows(&self) -> usize {
+        1
+    }
+}
+
+impl QueryRows for NoteSyncUpdate {
+    fn rows(&self) -> usize {
+        self.notes.len()
+    }
+}
+
+impl QueryRows for StateSyncUpdate {
+    fn rows(&self) -> usize {
+        let preceding_notes: usize =
+            self.preceding_blocks.iter().map(|block| block.notes.len()).sum();
+
+        self.notes.len() + self.account_updates.len() + self.nullifiers.len() + preceding_notes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{query_metrics, record_query, QueryMetrics};
+
+    #[test]
+    fn test_query_histogram() {
+        let mut metrics = QueryMetrics::default();
+        assert_eq!(metrics.quantile_ms(0.5), None);
+
+        for elapsed_ms in [0, 3, 3, 7, 7000] {
+            metrics.record(Duration::from_millis(elapsed_ms), false);
+        }
+        metrics.record(Duration::from_millis(30), true);
+
+        assert_eq!(metrics.count, 6);
+        assert_eq!(metrics.errors, 1);
+        assert_eq!(metrics.max_us, 7_000_000);
+        assert_eq!(metrics.buckets[..5], [1, 2, 1, 0, 1]);
+        assert_eq!(metrics.buckets[10], 1);
+        assert_eq!(metrics.quantile_ms(0.5), Some(5));
+        assert_eq!(metrics.quantile_ms(0.8), Some(50));
+        assert_eq!(metrics.quantile_ms(1.0), None);
+    }
+
+    #[test]
+    fn test_record_query_per_name() {
+        record_query("Test query", "id=1", Some(2), Duration::from_millis(2));
+        record_query("Test query", "id=2", None, Duration::from_millis(20));
+
+        let metrics = query_metrics()["Test query"];
+        assert_eq!(metrics.count, 2);
+        assert_eq!(metrics.errors, 1);
+        assert_eq!(metrics.buckets[..3], [0, 1, 0]);
+        assert_eq!(metrics.buckets[3], 1);
+    }
+}
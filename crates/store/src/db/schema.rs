@@ -0,0 +1,196 @@
+//! Description of the tables of the database, exported for the external indexers and BI tools
+//! reading the database file directly, see `miden-node store schema`.
+use miden_node_proto::generated::schema as proto;
+
+use super::sql::NULLIFIER_EPOCH_BLOCKS;
+
+// DATABASE SCHEMA
+// ================================================================================================
+
+/// Tables, indexes and views of the database.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DatabaseSchema {
+    /// Number of migrations applied to the database, its `user_version`
+    pub version: u32,
+    pub tables: Vec<TableSchema>,
+    pub views: Vec<ViewSchema>,
+}
+
+/// Definition of a table.
+///
+/// The nullifiers are partitioned in tables of the same definition, one per epoch of
+/// [NULLIFIER_EPOCH_BLOCKS] blocks, described once by the definition of their first partition.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TableSchema {
+    pub name: String,
+    /// The `CREATE TABLE` statement of the table
+    pub sql: String,
+    pub columns: Vec<ColumnSchema>,
+    pub indexes: Vec<IndexSchema>,
+    /// Names of the partitions of a partitioned table, empty for the other tables
+    pub partitions: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ColumnSchema {
+    pub name: String,
+    /// Declared type of the column, e.g. `INTEGER`
+    pub column_type: String,
+    pub not_null: bool,
+    /// Set if the column is part of the primary key
+    pub primary_key: bool,
+    /// SQL expression of the default value of the column
+    pub default_value: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IndexSchema {
+    pub name: String,
+    pub unique: bool,
+    /// Indexed columns, in the order of the index
+    pub columns: Vec<String>,
+    /// The `CREATE INDEX` statement of the index, [None] for the indexes created by SQLite for
+    /// the primary keys and unique constraints
+    pub sql: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ViewSchema {
+    pub name: String,
+    /// The `CREATE VIEW` statement of the view
+    pub sql: String,
+}
+
+impl DatabaseSchema {
+    /// Returns the SQL statements creating the tables, indexes and views of the schema.
+    pub fn to_sql(&self) -> String {
+        let mut sql = format!("-- Schema version {}\n", self.version);
+
+        for table in &self.tables {
+            sql.push('\n');
+            if !table.partitions.is_empty() {
+                sql.push_str(&format!(
+                    "-- Partitioned by epochs of {NULLIFIER_EPOCH_BLOCKS} blocks: {}\n",
+                    table.partitions.join(", ")
+                ));
+            }
+            sql.push_str(&format!("{};\n", table.sql));
+            for index in &table.indexes {
+                if let Some(index_sql) = &index.sql {
+                    sql.push_str(&format!("{index_sql};\n"));
+                }
+            }
+        }
+
+        for view in &self.views {
+            sql.push_str(&format!("\n{};\n", view.sql));
+        }
+
+        sql
+    }
+}
+
+// CONVERSIONS
+// ================================================================================================
+
+impl From<DatabaseSchema> for proto::DatabaseSchema {
+    fn from(schema: DatabaseSchema) -> Self {
+        Self {
+            version: schema.version,
+            tables: schema.tables.into_iter().map(Into::into).collect(),
+            views: schema.views.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<proto::DatabaseSchema> for DatabaseSchema {
+    fn from(schema: proto::DatabaseSchema) -> Self {
+        Self {
+            version: schema.version,
+            tables: schema.tables.into_iter().map(Into::into).collect(),
+            views: schema.views.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<TableSchema> for proto::Table {
+    fn from(table: TableSchema) -> Self {
+        Self {
+            name: table.name,
+            sql: table.sql,
+            columns: table.columns.into_iter().map(Into::into).collect(),
+            indexes: table.indexes.into_iter().map(Into::into).collect(),
+            partitions: table.partitions,
+        }
+    }
+}
+
+impl From<proto::Table> for TableSchema {
+    fn from(table: proto::Table) -> Self {
+        Self {
+            name: table.name,
+            sql: table.sql,
+            columns: table.columns.into_iter().map(Into::into).collect(),
+            indexes: table.indexes.into_iter().map(Into::into).collect(),
+            partitions: table.partitions,
+        }
+    }
+}
+
+impl From<ColumnSchema> for proto::Column {
+    fn from(column: ColumnSchema) -> Self {
+        Self {
+            name: column.name,
+            column_type: column.column_type,
+            not_null: column.not_null,
+            primary_key: column.primary_key,
+            default_value: column.default_value,
+        }
+    }
+}
+
+impl From<proto::Column> for ColumnSchema {
+    fn from(column: proto::Column) -> Self {
+        Self {
+            name: column.name,
+            column_type: column.column_type,
+            not_null: column.not_null,
+            primary_key: column.primary_key,
+            default_value: column.default_value,
+        }
+    }
+}
+
+impl From<IndexSchema> for proto::Index {
+    fn from(index: IndexSchema) -> Self {
+        Self {
+            name: index.name,
+            unique: index.unique,
+            columns: index.columns,
+            sql: index.sql,
+        }
+    }
+}
+
+impl From<proto::Index> for IndexSchema {
+    fn from(index: proto::Index) -> Self {
+        Self {
+            name: index.name,
+            unique: index.unique,
+            columns: index.columns,
+            sql: index.sql,
+        }
+    }
+}
+
+impl From<ViewSchema> for proto::View {
+    fn from(view: ViewSchema) -> Self {
+        Self { name: view.name, sql: view.sql }
+    }
+}
+
+impl From<proto::View> for ViewSchema {
+    fn from(view: proto::View) -> Self {
+        Self { name: view.name, sql: view.sql }
+    }
+}
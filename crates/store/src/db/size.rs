@@ -0,0 +1,100 @@
+//! Sampling of the database size, so the operator can follow its growth and is warned before the
+//! disk fills up.
+use std::{
+    collections::BTreeMap,
+    fmt::{Display, Formatter},
+    sync::{Arc, RwLock},
+};
+
+use tokio::time::{self, MissedTickBehavior};
+use tracing::{info, warn};
+
+use super::Db;
+use crate::{config::DatabaseSizeConfig, COMPONENT};
+
+/// Latest sample of the database size, shared by the whole process.
+static LATEST: RwLock<Option<DatabaseSize>> = RwLock::new(None);
+
+// DATABASE SIZE
+// ================================================================================================
+
+/// Size of the database, in bytes.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DatabaseSize {
+    /// Size of the database file
+    pub total_bytes: u64,
+    /// Size of each table, including its indexes
+    pub tables: BTreeMap<String, u64>,
+}
+
+impl DatabaseSize {
+    /// Returns the size of `table`, zero if the table doesn't exist.
+    pub fn table_bytes(&self, table: &str) -> u64 {
+        self.tables.get(table).copied().unwrap_or_default()
+    }
+}
+
+impl Display for DatabaseSize {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("{{ total_bytes: {}", self.total_bytes))?;
+        for (table, bytes) in self.tables.iter() {
+            f.write_fmt(format_args!(", {table}: {bytes}"))?;
+        }
+        f.write_str(" }")
+    }
+}
+
+/// Returns the latest sample of the database size, [None] until the first sample is taken.
+pub fn database_size() -> Option<DatabaseSize> {
+    LATEST.read().expect("Poisoned lock").clone()
+}
+
+// SIZE MONITOR
+// ================================================================================================
+
+/// Samples the database size every `config.sample_interval_s` seconds, logging the size of the
+/// tables and a warning once the size reaches `config.warn_percent` of `config.max_size_mb`.
+///
+/// Returns immediately if the sampling is disabled.
+pub async fn monitor_size(db: Arc<Db>, config: DatabaseSizeConfig) {
+    let Some(sample_interval) = config.sample_interval() else {
+        return;
+    };
+
+    let mut interval = time::interval(sample_interval);
+    interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    loop {
+        interval.tick().await;
+
+        let size = match db.select_database_size().await {
+            Ok(size) => size,
+            Err(err) => {
+                warn!(target: COMPONENT, %err, "Failed to sample the database size");
+                continue;
+            },
+        };
+
+        info!(
+            target: COMPONENT,
+            total_bytes = size.total_bytes,
+            accounts_bytes = size.table_bytes("accounts"),
+            notes_bytes = size.table_bytes("notes"),
+            nullifiers_bytes = size.table_bytes("nullifiers"),
+            block_headers_bytes = size.table_bytes("block_headers"),
+            transactions_bytes = size.table_bytes("transactions"),
+            "Database size"
+        );
+
+        if config.is_approaching_max_size(size.total_bytes) {
+            warn!(
+                target: COMPONENT,
+                max_size_mb = config.max_size_mb,
+                size = %size,
+                "Database size is approaching its maximum"
+            );
+        }
+
+        *LATEST.write().expect("Poisoned lock") = Some(size);
+    }
+}
@@ -1,51 +1,149 @@
 //! Wrapper functions for SQL statements.
 
-use std::{borrow::Cow, rc::Rc};
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, BTreeSet},
+    iter,
+    ops::RangeInclusive,
+    path::Path,
+    rc::Rc,
+};
 
-use miden_node_proto::domain::accounts::{AccountInfo, AccountSummary, AccountUpdateDetails};
+use miden_node_proto::domain::{
+    accounts::{AccountInfo, AccountLookup, AccountSummary, AccountUpdateDetails},
+    notes::{is_network_note_tag, note_tag_use_case},
+    nullifiers::{nullifier_prefix, nullifier_prefix_of_len},
+    transactions::{TransactionFee, TransactionReceipt, TransactionSummary},
+};
+use miden_node_utils::chain_params::NULLIFIER_PREFIX_LEN;
 use miden_objects::{
-    accounts::{Account, AccountDelta},
-    crypto::{hash::rpo::RpoDigest, merkle::MerklePath},
-    notes::{NoteId, Nullifier},
+    accounts::{
+        Account, AccountDelta, AccountId as AccountIdObject, ACCOUNT_ISFAUCET_MASK,
+        ACCOUNT_STORAGE_MASK_SHIFT,
+    },
+    block::BlockNoteTree,
+    crypto::{dsa::rpo_falcon512::Signature, hash::rpo::RpoDigest},
+    notes::{
+        Note as NoteObject, NoteAssets, NoteId, NoteInputs, NoteMetadata, NoteRecipient,
+        NoteScript, Nullifier,
+    },
     transaction::AccountDetails,
-    utils::serde::{Deserializable, Serializable},
-    BlockHeader,
+    utils::{
+        serde::{Deserializable, Serializable},
+        SliceReader,
+    },
+    BlockHeader, Word,
 };
 use rusqlite::{
-    params,
-    types::{Value, ValueRef},
-    Connection, Transaction,
+    backup::{Backup, StepResult},
+    params, params_from_iter,
+    types::{ToSql, Value, ValueRef},
+    Connection, OptionalExtension, Row, Transaction,
+};
+use uuid::Uuid;
+
+use super::{
+    build_note_tree,
+    contention::StatementTimings,
+    inspect::RowCounts,
+    lock::StoreInstance,
+    schema::{ColumnSchema, DatabaseSchema, IndexSchema, TableSchema, ViewSchema},
+    size::DatabaseSize,
+    views, AccountDeltaInfo, AccountFilter, AccountSortOrder, AuxRange, BlockHeaderFields, Note,
+    NoteConsumption, NoteCreated, NoteFilter, NoteListFilter, NoteSyncUpdate, NullifierInfo,
+    Result,
 };
-
-use super::{Note, NoteCreated, NullifierInfo, Result, StateSyncUpdate};
 use crate::{
-    errors::{DatabaseError, StateSyncError},
+    errors::{DatabaseError, NoteSyncError},
     types::{AccountId, BlockNumber},
+    ACCOUNT_AUTH_KEY_SLOT,
 };
 
+/// Number of high bits of the nullifiers stored in the `nullifier_prefix` column.
+const NULLIFIER_PREFIX_COLUMN_LEN: u32 = NULLIFIER_PREFIX_LEN;
+
 // ACCOUNT QUERIES
 // ================================================================================================
 
-/// Select all accounts from the DB using the given [Connection].
+/// Select the accounts matching `filter` from the DB using the given [Connection].
 ///
 /// # Returns
 ///
-/// A vector with accounts, or an error.
-pub fn select_accounts(conn: &mut Connection) -> Result<Vec<AccountInfo>> {
-    let mut stmt = conn.prepare(
+/// A vector with accounts, sorted according to the filter's sort order, or an error.
+#[cfg(test)]
+pub fn select_accounts(conn: &mut Connection, filter: &AccountFilter) -> Result<Vec<AccountInfo>> {
+    select_accounts_page(conn, filter, None, None)
+}
+
+/// Select at most `limit` accounts matching `filter` from the DB using the given [Connection],
+/// starting after the account `after` in the filter's sort order, given with the block of its
+/// latest update, or from the first account if [None].
+///
+/// The accounts updated in the same block are sorted by account ID, so that the pages don't skip
+/// or repeat accounts.
+///
+/// # Returns
+///
+/// A vector with accounts, sorted according to the filter's sort order, or an error.
+pub fn select_accounts_page(
+    conn: &mut Connection,
+    filter: &AccountFilter,
+    after: Option<(BlockNumber, AccountId)>,
+    limit: Option<usize>,
+) -> Result<Vec<AccountInfo>> {
+    // Account IDs are stored as `i64`, the IDs with the high bit set are negative
+    let (order_by, after_clause) = match filter.sort_order {
+        AccountSortOrder::BlockNumAsc => {
+            ("block_num ASC, account_id ASC", "(block_num, account_id) > (?6, ?7)")
+        },
+        AccountSortOrder::BlockNumDesc => {
+            ("block_num DESC, account_id DESC", "(block_num, account_id) < (?6, ?7)")
+        },
+        AccountSortOrder::AccountIdAsc => (
+            "account_id < 0 ASC, account_id ASC",
+            "(account_id < 0, account_id) > (?7 < 0, ?7)",
+        ),
+        AccountSortOrder::AccountIdDesc => (
+            "account_id < 0 DESC, account_id DESC",
+            "(account_id < 0, account_id) < (?7 < 0, ?7)",
+        ),
+    };
+
+    // The storage mode and account type are encoded in the high bits of the account ID, public
+    // accounts have both storage bits unset
+    let mut stmt = conn.prepare(&format!(
         "
         SELECT
             account_id,
             account_hash,
             block_num,
-            details
+            details,
+            nonce
         FROM
             accounts
+        WHERE
+            (?1 IS NULL OR ((account_id & ?2) = 0) = ?1) AND
+            (?3 IS NULL OR ((account_id & ?4) != 0) = ?3) AND
+            (?5 IS NULL OR block_num > ?5) AND
+            (?6 IS NULL OR {after_clause})
         ORDER BY
-            block_num ASC;
-    ",
-    )?;
-    let mut rows = stmt.query([])?;
+            {order_by}
+        LIMIT
+            ?8;
+    "
+    ))?;
+    let (after_block_num, after_account_id) = after.unzip();
+    let mut rows = stmt.query(params![
+        filter.is_public,
+        u64_to_value(0b11 << ACCOUNT_STORAGE_MASK_SHIFT),
+        filter.is_faucet,
+        u64_to_value(ACCOUNT_ISFAUCET_MASK),
+        filter.updated_after,
+        after_block_num,
+        after_account_id,
+        // A negative limit selects all the accounts
+        limit.map_or(-1, |limit| limit as i64),
+    ])?;
 
     let mut accounts = vec![];
     while let Some(row) = rows.next()? {
@@ -54,19 +152,49 @@ pub fn select_accounts(conn: &mut Connection) -> Result<Vec<AccountInfo>> {
     Ok(accounts)
 }
 
-/// Select all account hashes from the DB using the given [Connection].
+/// Select at most `limit` account hashes from the DB using the given [Connection], ordered by
+/// account id and starting after the account `after`, or from the first account if [None].
 ///
 /// # Returns
 ///
 /// The vector with the account id and corresponding hash, or an error.
-pub fn select_account_hashes(conn: &mut Connection) -> Result<Vec<(AccountId, RpoDigest)>> {
-    let mut stmt =
-        conn.prepare("SELECT account_id, account_hash FROM accounts ORDER BY block_num ASC;")?;
-    let mut rows = stmt.query([])?;
+pub fn select_account_hashes_chunk(
+    conn: &mut Connection,
+    after: Option<AccountId>,
+    limit: usize,
+) -> Result<Vec<(AccountId, RpoDigest)>> {
+    // The first chunk has its own statement, `?1 IS NULL OR account_id > ?1` can't use the
+    // primary key to seek to the start of the chunk
+    let mut stmt;
+    let mut rows = match after {
+        Some(after) => {
+            stmt = conn.prepare(
+                "
+                SELECT
+                    account_id, account_hash
+                FROM
+                    accounts
+                WHERE
+                    account_id > ?1
+                ORDER BY
+                    account_id ASC
+                LIMIT
+                    ?2;
+            ",
+            )?;
+            stmt.query(params![after, limit])?
+        },
+        None => {
+            stmt = conn.prepare(
+                "SELECT account_id, account_hash FROM accounts ORDER BY account_id ASC LIMIT ?1;",
+            )?;
+            stmt.query(params![limit])?
+        },
+    };
 
     let mut result = Vec::new();
     while let Some(row) = rows.next()? {
-        let account_id = column_value_as_u64(row, 0)?;
+        let account_id = row.get(0)?;
         let account_hash_data = row.get_ref(1)?.as_blob()?;
         let account_hash = RpoDigest::read_from_bytes(account_hash_data)?;
 
@@ -76,26 +204,31 @@ pub fn select_account_hashes(conn: &mut Connection) -> Result<Vec<(AccountId, Rp
     Ok(result)
 }
 
-/// Select [AccountSummary] from the DB using the given [Connection], given that the account
+/// Select [AccountInfo] from the DB using the given [Connection], given that the account
 /// update was done between `(block_start, block_end]`.
 ///
+/// The details of the public accounts are only loaded if `include_details` is set.
+///
 /// # Returns
 ///
-/// The vector of [AccountSummary] with the matching accounts.
+/// The vector of [AccountInfo] with the matching accounts.
 pub fn select_accounts_by_block_range(
     conn: &mut Connection,
     block_start: BlockNumber,
     block_end: BlockNumber,
     account_ids: &[AccountId],
-) -> Result<Vec<AccountSummary>> {
-    let account_ids: Vec<Value> = account_ids.iter().copied().map(u64_to_value).collect();
+    include_details: bool,
+) -> Result<Vec<AccountInfo>> {
+    let account_ids: Vec<Value> = account_ids.iter().copied().map(Value::from).collect();
 
     let mut stmt = conn.prepare(
         "
         SELECT
             account_id,
             account_hash,
-            block_num
+            block_num,
+            CASE WHEN ?4 THEN details END,
+            nonce
         FROM
             accounts
         WHERE
@@ -107,7 +240,46 @@ pub fn select_accounts_by_block_range(
     ",
     )?;
 
-    let mut rows = stmt.query(params![block_start, block_end, Rc::new(account_ids)])?;
+    let mut rows =
+        stmt.query(params![block_start, block_end, Rc::new(account_ids), include_details])?;
+
+    let mut result = Vec::new();
+    while let Some(row) = rows.next()? {
+        result.push(account_info_from_row(row)?)
+    }
+
+    Ok(result)
+}
+
+/// Select the summaries of the accounts whose latest update was done in `block_range` from the DB
+/// using the given [Connection], ordered by block.
+///
+/// # Returns
+///
+/// The summaries of the updated accounts, or an error.
+pub fn select_accounts_updated_in_range(
+    conn: &mut Connection,
+    block_range: RangeInclusive<BlockNumber>,
+) -> Result<Vec<AccountSummary>> {
+    let mut stmt = conn.prepare(
+        "
+        SELECT
+            account_id,
+            account_hash,
+            block_num,
+            NULL,
+            nonce
+        FROM
+            accounts
+        WHERE
+            block_num BETWEEN ?1 AND ?2
+        ORDER BY
+            block_num ASC,
+            account_id ASC
+    ",
+    )?;
+
+    let mut rows = stmt.query(params![block_range.start(), block_range.end()])?;
 
     let mut result = Vec::new();
     while let Some(row) = rows.next()? {
@@ -122,14 +294,15 @@ pub fn select_accounts_by_block_range(
 /// # Returns
 ///
 /// The latest account details, or an error.
-pub fn select_account(conn: &mut Connection, account_id: AccountId) -> Result<AccountInfo> {
+pub fn select_account(conn: &mut Connection, account_id: AccountId) -> Result<AccountLookup> {
     let mut stmt = conn.prepare(
         "
         SELECT
             account_id,
             account_hash,
             block_num,
-            details
+            details,
+            nonce
         FROM
             accounts
         WHERE
@@ -137,10 +310,12 @@ pub fn select_account(conn: &mut Connection, account_id: AccountId) -> Result<Ac
     ",
     )?;
 
-    let mut rows = stmt.query(params![u64_to_value(account_id)])?;
-    let row = rows.next()?.ok_or(DatabaseError::AccountNotFoundInDb(account_id))?;
+    let mut rows = stmt.query(params![account_id])?;
+    let Some(row) = rows.next()? else {
+        return Ok(AccountLookup::NotFound);
+    };
 
-    account_info_from_row(row)
+    account_lookup_from_row(row)
 }
 
 /// Inserts or updates accounts to the DB using the given [Transaction].
@@ -159,18 +334,23 @@ pub fn upsert_accounts(
     block_num: BlockNumber,
 ) -> Result<usize> {
     let mut upsert_stmt = transaction.prepare(
-        "INSERT OR REPLACE INTO accounts (account_id, account_hash, block_num, details) VALUES (?1, ?2, ?3, ?4);",
+        "INSERT OR REPLACE INTO accounts (account_id, account_hash, block_num, details, nonce) VALUES (?1, ?2, ?3, ?4, ?5);",
     )?;
     let mut select_details_stmt =
         transaction.prepare("SELECT details FROM accounts WHERE account_id = ?1;")?;
+    let mut select_nonce_stmt =
+        transaction.prepare("SELECT nonce FROM accounts WHERE account_id = ?1;")?;
+    let mut insert_delta_stmt = transaction.prepare(
+        "INSERT INTO account_deltas (account_id, block_num, delta, account_hash) VALUES (?1, ?2, ?3, ?4);",
+    )?;
 
     let mut count = 0;
     for update in accounts.iter() {
-        let account_id = update.account_id.into();
+        let account_id = AccountId::from(update.account_id);
         let full_account = match &update.details {
             None => None,
             Some(AccountDetails::Full(account)) => {
-                debug_assert_eq!(account_id, u64::from(account.id()));
+                debug_assert_eq!(account_id, account.id().into());
 
                 if account.hash() != update.final_state_hash {
                     return Err(DatabaseError::ApplyBlockFailedAccountHashesMismatch {
@@ -182,7 +362,7 @@ pub fn upsert_accounts(
                 Some(Cow::Borrowed(account))
             },
             Some(AccountDetails::Delta(delta)) => {
-                let mut rows = select_details_stmt.query(params![u64_to_value(account_id)])?;
+                let mut rows = select_details_stmt.query(params![account_id])?;
                 let Some(row) = rows.next()? else {
                     return Err(DatabaseError::AccountNotFoundInDb(account_id));
                 };
@@ -194,250 +374,1196 @@ pub fn upsert_accounts(
             },
         };
 
+        // The nonce of the public accounts must increase with each update
+        let nonce = full_account.as_ref().map(|account| account.nonce().as_int());
+        if let Some(nonce) = nonce {
+            let previous_nonce: Option<i64> = select_nonce_stmt
+                .query_row(params![account_id], |row| row.get(0))
+                .optional()?
+                .flatten();
+            if let Some(previous_nonce) = previous_nonce.map(|nonce| nonce as u64) {
+                if nonce <= previous_nonce {
+                    return Err(DatabaseError::AccountNonceNotIncreasing {
+                        account_id,
+                        previous_nonce,
+                        nonce,
+                    });
+                }
+            }
+        }
+
         let inserted = upsert_stmt.execute(params![
-            u64_to_value(account_id),
+            account_id,
             update.final_state_hash.to_bytes(),
             block_num,
             full_account.as_ref().map(|account| account.to_bytes()),
+            nonce.map(u64_to_value),
         ])?;
 
         debug_assert_eq!(inserted, 1);
 
+        if let Some(AccountDetails::Delta(delta)) = &update.details {
+            insert_delta_stmt.execute(params![
+                account_id,
+                block_num,
+                delta.to_bytes(),
+                update.final_state_hash.to_bytes(),
+            ])?;
+        }
+
         count += inserted;
     }
 
     Ok(count)
 }
 
-// NULLIFIER QUERIES
-// ================================================================================================
+/// Selects the deltas applied to the account `account_id` by the blocks in
+/// `(block_start, block_end]` using the given [Connection], at most `limit` of them.
+///
+/// # Returns
+///
+/// The deltas ordered by block number.
+pub fn select_account_deltas(
+    conn: &mut Connection,
+    account_id: AccountId,
+    block_start: BlockNumber,
+    block_end: BlockNumber,
+    limit: usize,
+) -> Result<Vec<AccountDeltaInfo>> {
+    let mut stmt = conn.prepare(
+        "
+        SELECT
+            block_num,
+            delta,
+            account_hash
+        FROM
+            account_deltas
+        WHERE
+            account_id = ?1 AND
+            block_num > ?2 AND
+            block_num <= ?3
+        ORDER BY
+            block_num ASC
+        LIMIT ?4;
+    ",
+    )?;
 
-/// Insert nullifiers to the DB using the given [Transaction].
+    let mut rows = stmt.query(params![account_id, block_start, block_end, limit])?;
+    let mut deltas = Vec::new();
+    while let Some(row) = rows.next()? {
+        let account_hash = row.get_ref(2)?.as_blob()?;
+        deltas.push(AccountDeltaInfo {
+            block_num: row.get(0)?,
+            delta: row.get(1)?,
+            account_hash: RpoDigest::read_from_bytes(account_hash)?,
+        });
+    }
+
+    Ok(deltas)
+}
+
+/// Selects the first block whose account deltas are stored using the given [Connection], the
+/// blocks applied before the account deltas were stored have none.
+pub fn select_account_deltas_start(conn: &mut Connection) -> Result<BlockNumber> {
+    Ok(conn.query_row("SELECT block_num FROM account_deltas_start;", [], |row| row.get(0))?)
+}
+
+/// Sets the `nonce` column of the public accounts stored before it was added, from their details.
 ///
 /// # Returns
 ///
-/// The number of affected rows.
+/// The number of updated accounts.
+pub fn backfill_account_nonces(transaction: &Transaction) -> Result<usize> {
+    let mut select_stmt = transaction
+        .prepare("SELECT details FROM accounts WHERE nonce IS NULL AND details IS NOT NULL;")?;
+    let mut update_stmt =
+        transaction.prepare("UPDATE accounts SET nonce = ?2 WHERE account_id = ?1;")?;
+
+    let mut rows = select_stmt.query([])?;
+    let mut count = 0;
+    while let Some(row) = rows.next()? {
+        let account = Account::read_from_bytes(row.get_ref(0)?.as_blob()?)?;
+        count += update_stmt.execute(params![
+            AccountId::from(account.id()),
+            u64_to_value(account.nonce().as_int()),
+        ])?;
+    }
+
+    Ok(count)
+}
+
+/// Updates the authentication public keys of the accounts `account_ids` in the public key index,
+/// from the latest details of the accounts.
 ///
-/// # Note
+/// The key is read from the storage slot [ACCOUNT_AUTH_KEY_SLOT], accounts without details or with
+/// an empty slot are removed from the index.
 ///
-/// The [Transaction] object is not consumed. It's up to the caller to commit or rollback the
-/// transaction.
-pub fn insert_nullifiers_for_block(
-    transaction: &Transaction,
-    nullifiers: &[Nullifier],
-    block_num: BlockNumber,
-) -> Result<usize> {
-    let mut stmt = transaction.prepare(
-        "INSERT INTO nullifiers (nullifier, nullifier_prefix, block_num) VALUES (?1, ?2, ?3);",
-    )?;
+/// # Returns
+///
+/// The number of indexed accounts.
+pub fn upsert_account_keys(transaction: &Transaction, account_ids: &[AccountId]) -> Result<usize> {
+    let mut delete_stmt = transaction.prepare("DELETE FROM account_keys WHERE account_id = ?1;")?;
+    let mut insert_stmt = transaction
+        .prepare("INSERT INTO account_keys (public_key, account_id) VALUES (?1, ?2);")?;
+    let mut select_details_stmt =
+        transaction.prepare("SELECT details FROM accounts WHERE account_id = ?1;")?;
 
     let mut count = 0;
-    for nullifier in nullifiers.iter() {
-        count +=
-            stmt.execute(params![nullifier.to_bytes(), get_nullifier_prefix(nullifier), block_num])?
+    for &account_id in account_ids {
+        delete_stmt.execute(params![account_id])?;
+
+        let mut rows = select_details_stmt.query(params![account_id])?;
+        let details: Option<Vec<u8>> = match rows.next()? {
+            Some(row) => row.get(0)?,
+            None => None,
+        };
+        let Some(details) = details else {
+            continue;
+        };
+
+        let account = Account::read_from_bytes(&details)?;
+        let public_key = account.storage().get_item(ACCOUNT_AUTH_KEY_SLOT);
+        if public_key == RpoDigest::default() {
+            continue;
+        }
+
+        count += insert_stmt.execute(params![public_key.to_bytes(), account_id])?;
     }
+
     Ok(count)
 }
 
-/// Select all nullifiers from the DB using the given [Connection].
+/// Rebuilds the public key index from the details of all the public accounts.
 ///
 /// # Returns
 ///
-/// A vector with nullifiers and the block height at which they were created, or an error.
-pub fn select_nullifiers(conn: &mut Connection) -> Result<Vec<(Nullifier, BlockNumber)>> {
+/// The number of indexed accounts.
+pub fn rebuild_account_keys(transaction: &Transaction) -> Result<usize> {
+    clear_account_keys(transaction)?;
+
     let mut stmt =
-        conn.prepare("SELECT nullifier, block_num FROM nullifiers ORDER BY block_num ASC;")?;
+        transaction.prepare("SELECT account_id FROM accounts WHERE details IS NOT NULL;")?;
     let mut rows = stmt.query([])?;
-
-    let mut result = vec![];
+    let mut account_ids = Vec::new();
     while let Some(row) = rows.next()? {
-        let nullifier_data = row.get_ref(0)?.as_blob()?;
-        let nullifier = Nullifier::read_from_bytes(nullifier_data)?;
-        let block_number = row.get(1)?;
-        result.push((nullifier, block_number));
+        account_ids.push(row.get(0)?);
     }
-    Ok(result)
+
+    upsert_account_keys(transaction, &account_ids)
 }
 
-/// Select nullifiers created between `(block_start, block_end]` that also match the
-/// `nullifier_prefixes` filter using the given [Connection].
-///
-/// Each value of the `nullifier_prefixes` is only the 16 most significant bits of the nullifier of
-/// interest to the client. This hides the details of the specific nullifier being requested.
+/// Removes all the accounts from the public key index.
+pub fn clear_account_keys(transaction: &Transaction) -> Result<usize> {
+    Ok(transaction.execute("DELETE FROM account_keys;", [])?)
+}
+
+/// Returns `true` if the public key index contains no account.
+pub fn is_account_keys_empty(conn: &Connection) -> Result<bool> {
+    Ok(conn.query_row("SELECT NOT EXISTS (SELECT 1 FROM account_keys);", [], |row| row.get(0))?)
+}
+
+/// Select the ids of the accounts authenticated by `public_key` from the public key index.
 ///
 /// # Returns
 ///
-/// A vector of [NullifierInfo] with the nullifiers and the block height at which they were
-/// created, or an error.
-pub fn select_nullifiers_by_block_range(
+/// The account ids, in ascending order.
+pub fn select_accounts_by_public_key(
     conn: &mut Connection,
-    block_start: BlockNumber,
-    block_end: BlockNumber,
-    nullifier_prefixes: &[u32],
-) -> Result<Vec<NullifierInfo>> {
-    let nullifier_prefixes: Vec<Value> =
-        nullifier_prefixes.iter().copied().map(u32_to_value).collect();
-
+    public_key: RpoDigest,
+) -> Result<Vec<AccountId>> {
     let mut stmt = conn.prepare(
-        "
-        SELECT
-            nullifier,
-            block_num
-        FROM
-            nullifiers
-        WHERE
-            block_num > ?1 AND
-            block_num <= ?2 AND
-            nullifier_prefix IN rarray(?3)
-        ORDER BY
-            block_num ASC
-    ",
+        "SELECT account_id FROM account_keys WHERE public_key = ?1 ORDER BY account_id ASC;",
     )?;
-
-    let mut rows = stmt.query(params![block_start, block_end, Rc::new(nullifier_prefixes)])?;
+    let mut rows = stmt.query(params![public_key.to_bytes()])?;
 
     let mut result = Vec::new();
     while let Some(row) = rows.next()? {
-        let nullifier_data = row.get_ref(0)?.as_blob()?;
-        let nullifier = Nullifier::read_from_bytes(nullifier_data)?;
-        let block_num = row.get(1)?;
-        result.push(NullifierInfo { nullifier, block_num });
+        result.push(row.get(0)?);
     }
+
     Ok(result)
 }
 
-// NOTE QUERIES
+// NULLIFIER PARTITIONS
 // ================================================================================================
 
-/// Select all notes from the DB using the given [Connection].
-///
+/// Number of blocks of an epoch of the nullifiers. The nullifiers consumed in an epoch are stored
+/// in their own partition, the table `nullifiers_<epoch>`, so the queries of a block range only
+/// read the partitions of its epochs.
 ///
-/// # Returns
-///
-/// A vector with notes, or an error.
-pub fn select_notes(conn: &mut Connection) -> Result<Vec<Note>> {
-    let mut stmt = conn.prepare(
-        "
-        SELECT
-            block_num,
-            batch_index,
-            note_index,
-            note_hash,
-            note_type,
-            sender,
-            tag,
-            merkle_path,
-            details
-        FROM
-            notes
-        ORDER BY
-            block_num ASC;
-        ",
-    )?;
-    let mut rows = stmt.query([])?;
+/// The partitions are not a pruning mechanism: the nullifier tree is loaded from all of them, and
+/// dropping a table doesn't shrink the database file.
+pub const NULLIFIER_EPOCH_BLOCKS: u32 = 1 << 16;
 
-    let mut notes = vec![];
-    while let Some(row) = rows.next()? {
-        let note_id_data = row.get_ref(3)?.as_blob()?;
-        let note_id = RpoDigest::read_from_bytes(note_id_data)?;
-
-        let merkle_path_data = row.get_ref(7)?.as_blob()?;
-        let merkle_path = MerklePath::read_from_bytes(merkle_path_data)?;
+/// Returns the epoch of the nullifiers consumed in `block_num`.
+pub fn nullifier_epoch(block_num: BlockNumber) -> u32 {
+    block_num.as_u32() / NULLIFIER_EPOCH_BLOCKS
+}
 
-        let details_data = row.get_ref(8)?.as_blob_or_null()?;
-        let details = details_data.map(<Vec<u8>>::read_from_bytes).transpose()?;
+/// Returns the name of the table of the nullifiers consumed in `epoch`.
+pub fn nullifier_partition(epoch: u32) -> String {
+    format!("nullifiers_{epoch}")
+}
 
-        notes.push(Note {
-            block_num: row.get(0)?,
-            note_created: NoteCreated {
-                batch_index: row.get(1)?,
-                note_index: row.get(2)?,
-                note_id,
-                note_type: row.get::<_, u8>(4)?.try_into()?,
-                sender: column_value_as_u64(row, 5)?,
-                tag: row.get(6)?,
-                details,
-            },
-            merkle_path,
-        })
-    }
-    Ok(notes)
+/// Returns `true` if `table` is a partition of the nullifiers.
+fn is_nullifier_partition(table: &str) -> bool {
+    table
+        .strip_prefix("nullifiers_")
+        .is_some_and(|epoch| epoch.parse::<u32>().is_ok())
 }
 
-/// Insert notes to the DB using the given [Transaction].
-///
-/// # Returns
-///
-/// The number of affected rows.
+/// Creates the partition of the nullifiers consumed in `epoch` and registers it in
+/// `nullifier_partitions`, if it doesn't exist yet. The view of the nullifiers is created again to
+/// include the new partition.
 ///
 /// # Note
 ///
 /// The [Transaction] object is not consumed. It's up to the caller to commit or rollback the
 /// transaction.
-pub fn insert_notes(transaction: &Transaction, notes: &[Note]) -> Result<usize> {
-    let mut stmt = transaction.prepare(
+pub fn create_nullifier_partition(transaction: &Transaction, epoch: u32) -> rusqlite::Result<()> {
+    let created = transaction
+        .execute("INSERT OR IGNORE INTO nullifier_partitions (epoch) VALUES (?1);", [epoch])?;
+    if created == 0 {
+        return Ok(());
+    }
+
+    let partition = nullifier_partition(epoch);
+    transaction.execute_batch(&format!(
         "
-        INSERT INTO
-        notes
-        (
-            block_num,
-            batch_index,
-            note_index,
-            note_hash,
-            note_type,
-            sender,
-            tag,
-            merkle_path,
-            details
-        )
-        VALUES
+        CREATE TABLE
+            {partition}
         (
-            ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9
-        );",
-    )?;
+            nullifier BLOB NOT NULL,
+            nullifier_prefix INTEGER NOT NULL,
+            block_num INTEGER NOT NULL,
+
+            PRIMARY KEY (nullifier),
+            CONSTRAINT fk_block_num FOREIGN KEY (block_num) REFERENCES block_headers (block_num),
+            CONSTRAINT {partition}_nullifier_is_digest CHECK (length(nullifier) = 32),
+            CONSTRAINT {partition}_nullifier_prefix_is_u16 CHECK (nullifier_prefix BETWEEN 0 AND 0xFFFF),
+            CONSTRAINT {partition}_block_num_in_epoch CHECK (block_num / {NULLIFIER_EPOCH_BLOCKS} = {epoch})
+        ) STRICT, WITHOUT ROWID;
+
+        CREATE INDEX idx_{partition}_prefix_block_num ON {partition}(nullifier_prefix, block_num);
+        "
+    ))?;
 
-    let mut count = 0;
-    for note in notes.iter() {
-        let details = note.note_created.details.as_ref().map(|details| details.to_bytes());
+    views::update_nullifiers_view(transaction)
+}
 
-        count += stmt.execute(params![
-            note.block_num,
-            note.note_created.batch_index,
-            note.note_created.note_index,
-            note.note_created.note_id.to_bytes(),
-            note.note_created.note_type as u8,
-            u64_to_value(note.note_created.sender),
-            note.note_created.tag,
-            note.merkle_path.to_bytes(),
-            details
-        ])?;
-    }
+/// Returns the epochs of the partitions of the nullifiers overlapping `block_range`, in ascending
+/// order, or all of them if [None].
+fn select_nullifier_epochs(
+    conn: &Connection,
+    block_range: Option<&RangeInclusive<BlockNumber>>,
+) -> Result<Vec<u32>> {
+    let epochs = match block_range {
+        Some(block_range) => conn
+            .prepare(
+                "SELECT epoch FROM nullifier_partitions WHERE epoch BETWEEN ?1 AND ?2 ORDER BY epoch ASC;",
+            )?
+            .query_map(
+                [nullifier_epoch(*block_range.start()), nullifier_epoch(*block_range.end())],
+                |row| row.get(0),
+            )?
+            .collect::<rusqlite::Result<_>>()?,
+        None => conn
+            .prepare("SELECT epoch FROM nullifier_partitions ORDER BY epoch ASC;")?
+            .query_map([], |row| row.get(0))?
+            .collect::<rusqlite::Result<_>>()?,
+    };
 
-    Ok(count)
+    Ok(epochs)
 }
 
-/// Select notes matching the tag and account_ids search criteria using the given [Connection].
+// NULLIFIER QUERIES
+// ================================================================================================
+
+/// Insert nullifiers to the DB using the given [Transaction].
+///
+/// The nullifiers are inserted in the partition of the epoch of `block_num`, which is created if
+/// it's the first block of the epoch consuming nullifiers.
 ///
 /// # Returns
 ///
-/// - Empty vector if no tag created after `block_num` match `tags` or `account_ids`.
-/// - Otherwise, notes which the 16 high bits match `tags`, or the `sender` is one of the
-///   `account_ids`.
+/// The number of affected rows.
 ///
 /// # Note
 ///
-/// This method returns notes from a single block. To fetch all notes up to the chain tip,
-/// multiple requests are necessary.
-pub fn select_notes_since_block_by_tag_and_sender(
-    conn: &mut Connection,
-    tags: &[u32],
-    account_ids: &[AccountId],
+/// The [Transaction] object is not consumed. It's up to the caller to commit or rollback the
+/// transaction.
+pub fn insert_nullifiers_for_block(
+    transaction: &Transaction,
+    nullifiers: &[Nullifier],
+    block_num: BlockNumber,
+) -> Result<usize> {
+    if nullifiers.is_empty() {
+        return Ok(0);
+    }
+
+    let epoch = nullifier_epoch(block_num);
+    create_nullifier_partition(transaction, epoch)?;
+
+    let mut stmt = transaction.prepare(&format!(
+        "INSERT INTO {} (nullifier, nullifier_prefix, block_num) VALUES (?1, ?2, ?3);",
+        nullifier_partition(epoch)
+    ))?;
+
+    let mut count = 0;
+    for nullifier in nullifiers.iter() {
+        count +=
+            stmt.execute(params![nullifier.to_bytes(), nullifier_prefix(nullifier), block_num])?
+    }
+    Ok(count)
+}
+
+/// Select all nullifiers from the DB using the given [Connection].
+///
+/// # Returns
+///
+/// A vector with nullifiers and the block height at which they were created, or an error.
+#[cfg(test)]
+pub fn select_nullifiers(conn: &mut Connection) -> Result<Vec<(Nullifier, BlockNumber)>> {
+    let mut result = vec![];
+    for epoch in select_nullifier_epochs(conn, None)? {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT nullifier, block_num FROM {} ORDER BY block_num ASC;",
+            nullifier_partition(epoch)
+        ))?;
+        let mut rows = stmt.query([])?;
+
+        while let Some(row) = rows.next()? {
+            let nullifier_data = row.get_ref(0)?.as_blob()?;
+            let nullifier = Nullifier::read_from_bytes(nullifier_data)?;
+            let block_number = row.get(1)?;
+            result.push((nullifier, block_number));
+        }
+    }
+    Ok(result)
+}
+
+/// Select at most `limit` nullifiers from the DB using the given [Connection], partition by
+/// partition in the order of their epochs, starting after the nullifier `after` of the partition
+/// of its epoch, or from the first nullifier of the first partition if [None].
+///
+/// Loading all the nullifiers by chunks reads each partition once, unlike
+/// [select_nullifiers_chunk].
+///
+/// # Returns
+///
+/// A vector with nullifiers and the block height at which they were created, or an error.
+pub fn select_nullifiers_by_partition_chunk(
+    conn: &mut Connection,
+    after: Option<(u32, Nullifier)>,
+    limit: usize,
+) -> Result<Vec<(Nullifier, BlockNumber)>> {
+    let first_epoch = after.map_or(0, |(epoch, _)| epoch);
+    let mut result = vec![];
+    for epoch in select_nullifier_epochs(conn, None)? {
+        if epoch < first_epoch {
+            continue;
+        }
+        if result.len() == limit {
+            break;
+        }
+
+        let partition = nullifier_partition(epoch);
+        let remaining = limit - result.len();
+        // The first chunk of a partition has its own statement, see [select_account_hashes_chunk]
+        let mut stmt;
+        let mut rows = match after.filter(|(after_epoch, _)| *after_epoch == epoch) {
+            Some((_, after)) => {
+                stmt = conn.prepare(&format!(
+                    "
+                    SELECT
+                        nullifier, block_num
+                    FROM
+                        {partition}
+                    WHERE
+                        nullifier > ?1
+                    ORDER BY
+                        nullifier ASC
+                    LIMIT
+                        ?2;
+                "
+                ))?;
+                stmt.query(params![after.to_bytes(), remaining])?
+            },
+            None => {
+                stmt = conn.prepare(&format!(
+                    "SELECT nullifier, block_num FROM {partition} ORDER BY nullifier ASC LIMIT ?1;"
+                ))?;
+                stmt.query(params![remaining])?
+            },
+        };
+
+        while let Some(row) = rows.next()? {
+            let nullifier_data = row.get_ref(0)?.as_blob()?;
+            let nullifier = Nullifier::read_from_bytes(nullifier_data)?;
+            let block_number = row.get(1)?;
+            result.push((nullifier, block_number));
+        }
+    }
+    Ok(result)
+}
+
+/// Select at most `limit` nullifiers from the DB using the given [Connection], ordered by
+/// nullifier and starting after the nullifier `after`, or from the first nullifier if [None].
+///
+/// The first `limit` nullifiers of each partition are selected, then merged, so the chunks are
+/// only meant to page through the nullifiers in order, see
+/// [select_nullifiers_by_partition_chunk] to load all of them.
+///
+/// # Returns
+///
+/// A vector with nullifiers and the block height at which they were created, or an error.
+pub fn select_nullifiers_chunk(
+    conn: &mut Connection,
+    after: Option<Nullifier>,
+    limit: usize,
+) -> Result<Vec<(Nullifier, BlockNumber)>> {
+    let mut result = vec![];
+    for epoch in select_nullifier_epochs(conn, None)? {
+        let partition = nullifier_partition(epoch);
+        // The first chunk has its own statement, see [select_account_hashes_chunk]
+        let mut stmt;
+        let mut rows = match after {
+            Some(after) => {
+                stmt = conn.prepare(&format!(
+                    "
+                    SELECT
+                        nullifier, block_num
+                    FROM
+                        {partition}
+                    WHERE
+                        nullifier > ?1
+                    ORDER BY
+                        nullifier ASC
+                    LIMIT
+                        ?2;
+                "
+                ))?;
+                stmt.query(params![after.to_bytes(), limit])?
+            },
+            None => {
+                stmt = conn.prepare(&format!(
+                    "SELECT nullifier, block_num FROM {partition} ORDER BY nullifier ASC LIMIT ?1;"
+                ))?;
+                stmt.query(params![limit])?
+            },
+        };
+
+        while let Some(row) = rows.next()? {
+            let nullifier_data = row.get_ref(0)?.as_blob()?;
+            let nullifier = Nullifier::read_from_bytes(nullifier_data)?;
+            let block_number = row.get(1)?;
+            result.push((nullifier, block_number));
+        }
+    }
+
+    // the nullifiers are ordered by their bytes, as in the partitions
+    result.sort_by_cached_key(|(nullifier, _)| nullifier.to_bytes());
+    result.truncate(limit);
+    Ok(result)
+}
+
+/// Select the number of the block which consumed `nullifier`, [None] if it wasn't consumed.
+///
+/// The nullifier is looked up in each partition, from the most recent one.
+pub fn select_nullifier_block_num(
+    conn: &mut Connection,
+    nullifier: &Nullifier,
+) -> Result<Option<BlockNumber>> {
+    for epoch in select_nullifier_epochs(conn, None)?.into_iter().rev() {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT block_num FROM {} WHERE nullifier = ?1;",
+            nullifier_partition(epoch)
+        ))?;
+        let mut rows = stmt.query(params![nullifier.to_bytes()])?;
+
+        if let Some(row) = rows.next()? {
+            return Ok(Some(row.get(0)?));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Select nullifiers created between `(block_start, block_end]` that also match the
+/// `nullifier_prefixes` filter using the given [Connection].
+///
+/// Each value of the `nullifier_prefixes` is only the `prefix_len` most significant bits of the
+/// nullifier of interest to the client. This hides the details of the specific nullifier being
+/// requested. `prefix_len` must be between 1 and 32.
+///
+/// # Returns
+///
+/// A vector of [NullifierInfo] with the nullifiers and the number and timestamp of the block in
+/// which they were consumed, or an error.
+pub fn select_nullifiers_by_block_range(
+    conn: &mut Connection,
+    block_start: BlockNumber,
+    block_end: BlockNumber,
+    prefix_len: u32,
+    nullifier_prefixes: &[u32],
+) -> Result<Vec<NullifierInfo>> {
+    if block_start >= block_end {
+        return Ok(Vec::new());
+    }
+
+    let (nullifiers, _) = select_nullifiers_by_prefix_in_range(
+        conn,
+        block_start.child().expect("block_start is below block_end")..=block_end,
+        prefix_len,
+        nullifier_prefixes,
+        usize::MAX,
+    )?;
+    Ok(nullifiers)
+}
+
+/// Select a page of the nullifiers created between `(block_start, block_end]` that also match the
+/// `nullifier_prefixes` filter using the given [Connection], see
+/// [select_nullifiers_by_block_range].
+///
+/// The page ends with the first block which brings the number of nullifiers to
+/// `max_nullifiers`, so the blocks of the page are complete.
+///
+/// # Returns
+///
+/// The nullifiers of the page, and its last block if it ends before `block_end`.
+pub fn select_nullifiers_page_by_block_range(
+    conn: &mut Connection,
+    block_start: BlockNumber,
+    block_end: BlockNumber,
+    prefix_len: u32,
+    nullifier_prefixes: &[u32],
+    max_nullifiers: usize,
+) -> Result<(Vec<NullifierInfo>, Option<BlockNumber>)> {
+    if block_start >= block_end {
+        return Ok((Vec::new(), None));
+    }
+
+    select_nullifiers_by_prefix_in_range(
+        conn,
+        block_start.child().expect("block_start is below block_end")..=block_end,
+        prefix_len,
+        nullifier_prefixes,
+        max_nullifiers,
+    )
+}
+
+/// Select all the nullifiers created in `block_range` using the given [Connection], ordered by
+/// block.
+///
+/// # Returns
+///
+/// A vector of [NullifierInfo] with the nullifiers and the number and timestamp of the block in
+/// which they were consumed, or an error.
+pub fn select_nullifiers_in_block_range(
+    conn: &mut Connection,
+    block_range: RangeInclusive<BlockNumber>,
+) -> Result<Vec<NullifierInfo>> {
+    let mut result = Vec::new();
+    for epoch in select_nullifier_epochs(conn, Some(&block_range))? {
+        let partition = nullifier_partition(epoch);
+        let mut stmt = conn.prepare(&format!(
+            "
+            SELECT
+                {partition}.nullifier,
+                {partition}.block_num,
+                block_headers.timestamp
+            FROM
+                {partition}
+            JOIN
+                block_headers ON block_headers.block_num = {partition}.block_num
+            WHERE
+                {partition}.block_num >= ?1 AND
+                {partition}.block_num <= ?2
+            ORDER BY
+                {partition}.block_num ASC
+        "
+        ))?;
+        let mut rows = stmt.query(params![block_range.start(), block_range.end()])?;
+
+        while let Some(row) = rows.next()? {
+            let nullifier_data = row.get_ref(0)?.as_blob()?;
+            let block_timestamp: i64 = row.get(2)?;
+            result.push(NullifierInfo {
+                nullifier: Nullifier::read_from_bytes(nullifier_data)?,
+                block_num: row.get(1)?,
+                block_timestamp: block_timestamp as u64,
+            });
+        }
+    }
+    Ok(result)
+}
+
+/// Select all the nullifiers matching the `nullifier_prefixes` filter using the given
+/// [Connection], see [select_nullifiers_by_block_range].
+pub fn select_nullifiers_by_prefix(
+    conn: &mut Connection,
+    prefix_len: u32,
+    nullifier_prefixes: &[u32],
+) -> Result<Vec<NullifierInfo>> {
+    let (nullifiers, _) = select_nullifiers_by_prefix_in_range(
+        conn,
+        BlockNumber::GENESIS..=BlockNumber::MAX,
+        prefix_len,
+        nullifier_prefixes,
+        usize::MAX,
+    )?;
+    Ok(nullifiers)
+}
+
+/// Returns the nullifiers of the blocks in `block_range` matching the `nullifier_prefixes`, up to
+/// the first block reaching `max_nullifiers`, and that block if the range isn't fully selected.
+///
+/// Only the partitions of the epochs overlapping `block_range` are read, in the order of the
+/// epochs, so the page carries over from a partition to the next.
+fn select_nullifiers_by_prefix_in_range(
+    conn: &mut Connection,
+    block_range: RangeInclusive<BlockNumber>,
+    prefix_len: u32,
+    nullifier_prefixes: &[u32],
+    max_nullifiers: usize,
+) -> Result<(Vec<NullifierInfo>, Option<BlockNumber>)> {
+    // the `nullifier_prefix` column only contains the 16 high bits of the nullifiers, the matching
+    // rows are filtered by the full prefix below
+    let column_prefixes = Rc::new(
+        nullifier_prefix_column_values(prefix_len, nullifier_prefixes)
+            .into_iter()
+            .map(u32_to_value)
+            .collect::<Vec<Value>>(),
+    );
+    let nullifier_prefixes: BTreeSet<u32> = nullifier_prefixes.iter().copied().collect();
+
+    let mut result: Vec<NullifierInfo> = Vec::new();
+    for epoch in select_nullifier_epochs(conn, Some(&block_range))? {
+        let partition = nullifier_partition(epoch);
+        let mut stmt = conn.prepare(&format!(
+            "
+            SELECT
+                {partition}.nullifier,
+                {partition}.block_num,
+                block_headers.timestamp
+            FROM
+                {partition}
+            JOIN
+                block_headers ON block_headers.block_num = {partition}.block_num
+            WHERE
+                {partition}.block_num >= ?1 AND
+                {partition}.block_num <= ?2 AND
+                {partition}.nullifier_prefix IN rarray(?3)
+            ORDER BY
+                {partition}.block_num ASC
+        "
+        ))?;
+
+        let mut rows = stmt.query(params![
+            block_range.start(),
+            block_range.end(),
+            Rc::clone(&column_prefixes)
+        ])?;
+
+        while let Some(row) = rows.next()? {
+            let nullifier_data = row.get_ref(0)?.as_blob()?;
+            let nullifier = Nullifier::read_from_bytes(nullifier_data)?;
+            if !nullifier_prefixes.contains(&nullifier_prefix_of_len(&nullifier, prefix_len)) {
+                continue;
+            }
+
+            let block_num = row.get(1)?;
+            // the page ends with the block of the last nullifier, once it has enough nullifiers
+            if let Some(last) = result.last().filter(|_| result.len() >= max_nullifiers) {
+                if last.block_num != block_num {
+                    let last_block = last.block_num;
+                    return Ok((result, Some(last_block)));
+                }
+            }
+
+            let block_timestamp: i64 = row.get(2)?;
+            result.push(NullifierInfo {
+                nullifier,
+                block_num,
+                block_timestamp: block_timestamp as u64,
+            });
+        }
+    }
+    Ok((result, None))
+}
+
+// NOTE QUERIES
+// ================================================================================================
+
+/// Splits the details of a public note, a serialized [NoteObject], into its
+/// script and the remaining details, so the scripts shared by many notes are stored once. The
+/// nullifier of the note is returned with them.
+///
+/// Returns `None` if the details can't be split without loss, such details are stored as is.
+fn split_note_details(details: &[u8]) -> Option<(NoteScript, Vec<u8>, Nullifier)> {
+    let note = NoteObject::read_from_bytes(details).ok()?;
+    if note.to_bytes() != details {
+        return None;
+    }
+
+    let mut remaining = Vec::new();
+    note.assets().write_into(&mut remaining);
+    note.metadata().write_into(&mut remaining);
+    note.inputs().write_into(&mut remaining);
+    note.serial_num().write_into(&mut remaining);
+
+    Some((note.script().clone(), remaining, note.nullifier()))
+}
+
+/// Rebuilds the details of a public note from the parts returned by [split_note_details].
+fn join_note_details(details: &[u8], script: &[u8]) -> Result<Vec<u8>> {
+    let mut source = SliceReader::new(details);
+    let assets = NoteAssets::read_from(&mut source)?;
+    let metadata = NoteMetadata::read_from(&mut source)?;
+    let inputs = NoteInputs::read_from(&mut source)?;
+    let serial_num = Word::read_from(&mut source)?;
+    let script = NoteScript::read_from_bytes(script)?;
+
+    let recipient = NoteRecipient::new(serial_num, script, inputs);
+
+    Ok(NoteObject::new(assets, metadata, recipient).to_bytes())
+}
+
+/// Reads the note details from the `details` column at `index`, followed by the `script` column
+/// of the joined `note_scripts` table.
+fn note_details_from_row(row: &Row<'_>, index: usize) -> Result<Option<Vec<u8>>> {
+    let Some(details) = row.get_ref(index)?.as_blob_or_null()? else {
+        return Ok(None);
+    };
+    let details = <Vec<u8>>::read_from_bytes(details)?;
+
+    match row.get_ref(index + 1)?.as_blob_or_null()? {
+        Some(script) => Ok(Some(join_note_details(&details, script)?)),
+        None => Ok(Some(details)),
+    }
+}
+
+/// Constructs a [NoteCreated] from a row selecting `batch_index`, `note_index`, `note_hash`,
+/// `note_type`, `sender`, `tag`, `aux`, `details` and the script of the note, after its block.
+fn note_created_from_row(row: &Row<'_>) -> Result<NoteCreated> {
+    let note_id_data = row.get_ref(3)?.as_blob()?;
+    let note_id = RpoDigest::read_from_bytes(note_id_data)?;
+
+    Ok(NoteCreated {
+        batch_index: row.get(1)?,
+        note_index: row.get(2)?,
+        note_id,
+        note_type: row.get::<_, u8>(4)?.try_into()?,
+        sender: row.get(5)?,
+        tag: row.get(6)?,
+        aux: row.get::<_, i64>(7)? as u64,
+        details: note_details_from_row(row, 8)?,
+    })
+}
+
+/// Select all notes from the DB using the given [Connection].
+///
+///
+/// # Returns
+///
+/// A vector with notes, or an error.
+#[cfg(test)]
+pub fn select_notes(conn: &mut Connection) -> Result<Vec<Note>> {
+    select_notes_page(conn, &NoteListFilter::default(), None, None)
+}
+
+/// Select at most `limit` notes matching `filter` from the DB using the given [Connection], ordered
+/// by block and position in the block, and starting after the note at `after`, given by its block,
+/// batch index and note index, or from the first note if [None].
+///
+/// # Returns
+///
+/// A vector with notes, or an error.
+pub fn select_notes_page(
+    conn: &mut Connection,
+    filter: &NoteListFilter,
+    after: Option<(BlockNumber, u32, u32)>,
+    limit: Option<usize>,
+) -> Result<Vec<Note>> {
+    // Only the set filters are part of the statement, so that the notes of a sender are read
+    // through the index of the senders
+    let mut conditions = Vec::new();
+    let mut params: Vec<Value> = Vec::new();
+    if let Some(sender) = filter.sender {
+        params.push(sender.into());
+        conditions.push(format!("sender = ?{}", params.len()));
+    }
+    if let Some(epoch) = filter.epoch {
+        params.push(u32_to_value(epoch));
+        conditions.push(format!("epoch = ?{}", params.len()));
+    }
+    if !filter.aux_ranges.is_empty() {
+        let (aux_condition, aux_params) =
+            aux_ranges_condition(&filter.aux_ranges, params.len() + 1);
+        conditions.push(aux_condition);
+        params.extend(aux_params);
+    }
+    if let Some((block_num, batch_index, note_index)) = after {
+        conditions.push(format!(
+            "(notes.block_num, notes.batch_index, notes.note_index) > (?{}, ?{}, ?{})",
+            params.len() + 1,
+            params.len() + 2,
+            params.len() + 3
+        ));
+        params.extend([block_num.into(), u32_to_value(batch_index), u32_to_value(note_index)]);
+    }
+    // A negative limit selects all the notes
+    params.push(Value::Integer(limit.map_or(-1, |limit| limit as i64)));
+    let where_clause = if conditions.is_empty() {
+        String::new()
+    } else {
+        format!("WHERE {}", conditions.join(" AND "))
+    };
+
+    let mut stmt = conn.prepare(&format!(
+        "
+        SELECT
+            notes.block_num,
+            notes.batch_index,
+            notes.note_index,
+            note_hash,
+            note_type,
+            sender,
+            tag,
+            aux,
+            details,
+            note_scripts.script
+        FROM
+            notes
+        LEFT JOIN
+            note_scripts ON note_scripts.script_root = notes.script_root
+        {where_clause}
+        ORDER BY
+            notes.block_num ASC,
+            notes.batch_index ASC,
+            notes.note_index ASC
+        LIMIT
+            ?{};",
+        params.len()
+    ))?;
+    let mut rows = stmt.query(params_from_iter(params))?;
+
+    let mut notes = vec![];
+    while let Some(row) = rows.next()? {
+        notes.push((row.get(0)?, note_created_from_row(row)?));
+    }
+    drop(rows);
+    with_merkle_paths(conn, notes)
+}
+
+/// Select the notes created by the block `block_num`, without their details, ordered by their
+/// position in the note tree of the block.
+///
+/// # Returns
+///
+/// The leaves of the note tree of the block, which are enough to rebuild it.
+pub fn select_block_notes(conn: &Connection, block_num: BlockNumber) -> Result<Vec<NoteCreated>> {
+    let mut stmt = conn.prepare(
+        "
+        SELECT
+            batch_index,
+            note_index,
+            note_hash,
+            note_type,
+            sender,
+            tag,
+            aux
+        FROM
+            notes
+        WHERE
+            block_num = ?1
+        ORDER BY
+            batch_index ASC,
+            note_index ASC
+        ",
+    )?;
+    let mut rows = stmt.query([block_num])?;
+
+    let mut notes = Vec::new();
+    while let Some(row) = rows.next()? {
+        let note_id_data = row.get_ref(2)?.as_blob()?;
+        let note_id = RpoDigest::read_from_bytes(note_id_data)?;
+
+        notes.push(NoteCreated {
+            batch_index: row.get(0)?,
+            note_index: row.get(1)?,
+            note_id,
+            note_type: row.get::<_, u8>(3)?.try_into()?,
+            sender: row.get(4)?,
+            tag: row.get(5)?,
+            aux: row.get::<_, i64>(6)? as u64,
+            details: None,
+        });
+    }
+    Ok(notes)
+}
+
+/// Select the notes created in `block_range`, without their details, using the given
+/// [Connection], ordered by block and position in the block.
+///
+/// # Returns
+///
+/// The notes with the number of the block which created them, or an error.
+pub fn select_notes_in_block_range(
+    conn: &mut Connection,
+    block_range: RangeInclusive<BlockNumber>,
+) -> Result<Vec<(BlockNumber, NoteCreated)>> {
+    let mut stmt = conn.prepare(
+        "
+        SELECT
+            block_num,
+            batch_index,
+            note_index,
+            note_hash,
+            note_type,
+            sender,
+            tag,
+            aux,
+            NULL,
+            NULL
+        FROM
+            notes
+        WHERE
+            block_num BETWEEN ?1 AND ?2
+        ORDER BY
+            block_num ASC,
+            batch_index ASC,
+            note_index ASC
+        ",
+    )?;
+    let mut rows = stmt.query([block_range.start(), block_range.end()])?;
+
+    let mut notes = Vec::new();
+    while let Some(row) = rows.next()? {
+        notes.push((row.get(0)?, note_created_from_row(row)?));
+    }
+    Ok(notes)
+}
+
+/// Insert notes to the DB using the given [Transaction], and the note trees of their blocks.
+///
+/// # Returns
+///
+/// The number of inserted notes.
+///
+/// # Note
+///
+/// The [Transaction] object is not consumed. It's up to the caller to commit or rollback the
+/// transaction.
+pub fn insert_notes(transaction: &Transaction, notes: &[Note]) -> Result<usize> {
+    let mut stmt = transaction.prepare(
+        "
+        INSERT INTO
+        notes
+        (
+            block_num,
+            batch_index,
+            note_index,
+            note_hash,
+            note_type,
+            sender,
+            tag,
+            details,
+            script_root,
+            tag_use_case,
+            tag_network_hint,
+            nullifier,
+            aux
+        )
+        VALUES
+        (
+            ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13
+        );",
+    )?;
+    let mut script_stmt = transaction
+        .prepare("INSERT OR IGNORE INTO note_scripts (script_root, script) VALUES (?1, ?2);")?;
+
+    let mut count = 0;
+    for note in notes.iter() {
+        let (details, script_root, nullifier) =
+            match note.note_created.details.as_deref().map(split_note_details) {
+                Some(Some((script, details, nullifier))) => {
+                    let script_root = script.hash().to_bytes();
+                    script_stmt.execute(params![script_root, script.to_bytes()])?;
+                    (Some(details.to_bytes()), Some(script_root), Some(nullifier.to_bytes()))
+                },
+                Some(None) => (
+                    note.note_created.details.as_ref().map(|details| details.to_bytes()),
+                    None,
+                    None,
+                ),
+                None => (None, None, None),
+            };
+
+        count += stmt.execute(params![
+            note.block_num,
+            note.note_created.batch_index,
+            note.note_created.note_index,
+            note.note_created.note_id.to_bytes(),
+            note.note_created.note_type as u8,
+            note.note_created.sender,
+            note.note_created.tag,
+            details,
+            script_root,
+            note_tag_use_case(note.note_created.tag),
+            is_network_note_tag(note.note_created.tag),
+            nullifier,
+            u64_to_value(note.note_created.aux),
+        ])?;
+    }
+
+    let block_nums: BTreeSet<BlockNumber> = notes.iter().map(|note| note.block_num).collect();
+    upsert_note_trees(transaction, &block_nums)?;
+
+    Ok(count)
+}
+
+/// Stores the note trees of the blocks `block_nums`, rebuilt from all the notes of each block, so
+/// it must be called once the notes of the blocks are inserted.
+///
+/// # Returns
+///
+/// The number of stored note trees.
+pub fn upsert_note_trees(
+    transaction: &Transaction,
+    block_nums: &BTreeSet<BlockNumber>,
+) -> Result<usize> {
+    let mut stmt = transaction.prepare(
+        "INSERT OR REPLACE INTO block_note_trees (block_num, note_tree) VALUES (?1, ?2);",
+    )?;
+
+    let mut count = 0;
+    for &block_num in block_nums {
+        let notes = select_block_notes(transaction, block_num)?;
+        count += stmt.execute(params![block_num, build_note_tree(&notes)?.to_bytes()])?;
+    }
+
+    Ok(count)
+}
+
+/// Stores the note trees of the blocks with notes inserted before the trees were stored.
+///
+/// # Returns
+///
+/// The number of stored note trees.
+pub fn backfill_note_trees(transaction: &Transaction) -> Result<usize> {
+    let mut stmt = transaction.prepare(
+        "
+        SELECT DISTINCT
+            block_num
+        FROM
+            notes
+        WHERE
+            block_num NOT IN (SELECT block_num FROM block_note_trees);
+        ",
+    )?;
+    let block_nums = stmt
+        .query_map([], |row| row.get(0))?
+        .collect::<rusqlite::Result<BTreeSet<BlockNumber>>>()?;
+
+    upsert_note_trees(transaction, &block_nums)
+}
+
+/// Select the note trees of the blocks `block_nums` using the given [Connection].
+///
+/// # Returns
+///
+/// The note trees by block, the blocks without notes are missing.
+pub fn select_note_trees(
+    conn: &Connection,
+    block_nums: &BTreeSet<BlockNumber>,
+) -> Result<BTreeMap<BlockNumber, BlockNoteTree>> {
+    let block_nums: Vec<Value> = block_nums.iter().copied().map(Value::from).collect();
+
+    let mut stmt = conn.prepare(
+        "SELECT block_num, note_tree FROM block_note_trees WHERE block_num IN rarray(?1);",
+    )?;
+    let mut rows = stmt.query(params![Rc::new(block_nums)])?;
+
+    let mut note_trees = BTreeMap::new();
+    while let Some(row) = rows.next()? {
+        let note_tree = BlockNoteTree::read_from_bytes(row.get_ref(1)?.as_blob()?)?;
+        note_trees.insert(row.get(0)?, note_tree);
+    }
+    Ok(note_trees)
+}
+
+/// Returns the `notes` with their Merkle paths, opened in the stored note trees of their blocks.
+fn with_merkle_paths(
+    conn: &Connection,
+    notes: Vec<(BlockNumber, NoteCreated)>,
+) -> Result<Vec<Note>> {
+    let block_nums = notes.iter().map(|(block_num, _)| *block_num).collect();
+    let note_trees = select_note_trees(conn, &block_nums)?;
+
+    notes
+        .into_iter()
+        .map(|(block_num, note_created)| {
+            let merkle_path = note_trees
+                .get(&block_num)
+                .ok_or(DatabaseError::NoteTreeMissing(block_num))?
+                .get_note_path(
+                    note_created.batch_index as usize,
+                    note_created.note_index as usize,
+                )?;
+
+            Ok(Note { block_num, note_created, merkle_path })
+        })
+        .collect()
+}
+
+/// Select notes matching the tag and account_ids search criteria using the given [Connection].
+///
+/// # Returns
+///
+/// - Empty vector if no tag created after `block_num` match `tags` or `account_ids`.
+/// - Otherwise, notes which the 16 high bits match `tags`, or the `sender` is one of the
+///   `account_ids`.
+///
+/// # Note
+///
+/// This method returns notes from a single block. To fetch all notes up to the chain tip,
+/// multiple requests are necessary.
+#[cfg(test)]
+pub fn select_notes_since_block_by_tag_and_sender(
+    conn: &mut Connection,
+    tags: &[u32],
+    account_ids: &[AccountId],
     block_num: BlockNumber,
 ) -> Result<Vec<Note>> {
-    let tags: Vec<Value> = tags.iter().copied().map(u32_to_value).collect();
-    let account_ids: Vec<Value> = account_ids.iter().copied().map(u64_to_value).collect();
+    let filter = NoteFilter {
+        tags: tags.to_vec(),
+        senders: account_ids.to_vec(),
+        ..Default::default()
+    };
+    select_notes_of_next_blocks(conn, &filter, block_num, 1)
+}
 
-    let mut stmt = conn.prepare(
+/// Select the notes matching the `filter` of the next `max_blocks` blocks after `block_num`
+/// containing a matching note, using the given [Connection].
+///
+/// # Returns
+///
+/// The matching notes, ordered by block and by position in the block.
+pub fn select_notes_of_next_blocks(
+    conn: &mut Connection,
+    filter: &NoteFilter,
+    block_num: BlockNumber,
+    max_blocks: u32,
+) -> Result<Vec<Note>> {
+    let tags: Vec<Value> = filter.tags.iter().copied().map(u32_to_value).collect();
+    let use_cases: Vec<Value> = filter.use_cases.iter().copied().map(u32_to_value).collect();
+    let senders: Vec<Value> = filter.senders.iter().copied().map(Value::from).collect();
+    // NULL never matches, leaving the network notes out
+    let network_hint = filter.network_notes.then_some(true);
+    let (aux_condition, aux_params) = aux_ranges_condition(&filter.aux_ranges, 7);
+
+    let mut stmt = conn.prepare(&format!(
         "
         SELECT
             block_num,
@@ -447,138 +1573,506 @@ pub fn select_notes_since_block_by_tag_and_sender(
             note_type,
             sender,
             tag,
-            merkle_path,
-            details
+            aux,
+            details,
+            note_scripts.script
         FROM
             notes
+        LEFT JOIN
+            note_scripts ON note_scripts.script_root = notes.script_root
         WHERE
-            -- find the next block which contains at least one note with a matching tag
-            block_num = (
-                SELECT
+            -- find the next blocks which contain at least one matching note
+            block_num IN (
+                SELECT DISTINCT
                     block_num
                 FROM
                     notes
                 WHERE
-                    (tag IN rarray(?1) OR sender IN rarray(?2)) AND
-                    block_num > ?3
+                    (
+                        tag IN rarray(?1) OR
+                        tag_use_case IN rarray(?2) OR
+                        sender IN rarray(?3) OR
+                        tag_network_hint = ?4
+                    ) AND
+                    {aux_condition} AND
+                    block_num > ?5
                 ORDER BY
                     block_num ASC
                 LIMIT
-                    1
+                    ?6
             ) AND
-            -- filter the block's notes and return only the ones matching the requested tags
-            (tag IN rarray(?1) OR sender IN rarray(?2));
-    ",
+            -- filter the blocks' notes and return only the matching ones
+            (
+                tag IN rarray(?1) OR
+                tag_use_case IN rarray(?2) OR
+                sender IN rarray(?3) OR
+                tag_network_hint = ?4
+            ) AND
+            {aux_condition}
+        ORDER BY
+            block_num ASC,
+            batch_index ASC,
+            note_index ASC;
+    "
+    ))?;
+    let (tags, use_cases, senders) = (Rc::new(tags), Rc::new(use_cases), Rc::new(senders));
+    let mut params: Vec<&dyn ToSql> =
+        vec![&tags, &use_cases, &senders, &network_hint, &block_num, &max_blocks];
+    params.extend(aux_params.iter().map(|param| param as &dyn ToSql));
+    let mut rows = stmt.query(params.as_slice())?;
+
+    let mut res = Vec::new();
+    while let Some(row) = rows.next()? {
+        res.push((row.get(0)?, note_created_from_row(row)?));
+    }
+    with_merkle_paths(conn, res)
+}
+
+/// Select Note's matching the NoteId using the given [Connection].
+///
+/// # Returns
+///
+/// - Empty vector if no matching `note`.
+/// - Otherwise, notes which `note_hash` matches the `NoteId` as bytes.
+pub fn select_notes_by_id(conn: &mut Connection, note_ids: &[NoteId]) -> Result<Vec<Note>> {
+    let note_ids: Vec<Value> = note_ids.iter().map(|id| id.to_bytes().into()).collect();
+
+    let mut stmt = conn.prepare(
+        "
+        SELECT
+            block_num,
+            batch_index,
+            note_index,
+            note_hash,
+            note_type,
+            sender,
+            tag,
+            aux,
+            details,
+            note_scripts.script
+        FROM
+            notes
+        LEFT JOIN
+            note_scripts ON note_scripts.script_root = notes.script_root
+        WHERE
+            note_hash IN rarray(?1)
+        ",
+    )?;
+    let mut rows = stmt.query(params![Rc::new(note_ids)])?;
+
+    let mut notes = Vec::new();
+    while let Some(row) = rows.next()? {
+        notes.push((row.get(0)?, note_created_from_row(row)?));
+    }
+    with_merkle_paths(conn, notes)
+}
+
+/// Select the script with the given root from the DB using the given [Connection].
+///
+/// # Returns
+///
+/// The serialized [NoteScript], or `None` if no stored note uses this script.
+pub fn select_note_script(
+    conn: &mut Connection,
+    script_root: RpoDigest,
+) -> Result<Option<Vec<u8>>> {
+    let mut stmt = conn.prepare("SELECT script FROM note_scripts WHERE script_root = ?1;")?;
+    let mut rows = stmt.query(params![script_root.to_bytes()])?;
+
+    match rows.next()? {
+        Some(row) => Ok(Some(row.get(0)?)),
+        None => Ok(None),
+    }
+}
+
+/// Records the consumption of the notes whose nullifier is among the `nullifiers` consumed by the
+/// block `block_num` using the given [Transaction]. The consuming transactions are looked up in
+/// the `receipts` of the block.
+///
+/// Must be called once the notes and the nullifiers of the block are inserted, a note can be
+/// consumed by the block creating it.
+///
+/// # Returns
+///
+/// The number of recorded consumptions.
+pub fn insert_note_consumptions(
+    transaction: &Transaction,
+    nullifiers: &[Nullifier],
+    block_num: BlockNumber,
+    receipts: &[TransactionReceipt],
+) -> Result<usize> {
+    let consuming_transactions: BTreeMap<RpoDigest, RpoDigest> = receipts
+        .iter()
+        .flat_map(|receipt| {
+            let tx = &receipt.transaction;
+            tx.input_nullifiers.iter().map(|nullifier| (nullifier.inner(), tx.id.inner()))
+        })
+        .collect();
+
+    let mut stmt = transaction.prepare(
+        "
+        INSERT INTO
+        note_consumptions
+        (
+            note_id,
+            nullifier,
+            block_num,
+            transaction_id
+        )
+        SELECT
+            note_hash, nullifier, ?2, ?3
+        FROM
+            notes
+        WHERE
+            nullifier = ?1;",
+    )?;
+
+    let mut count = 0;
+    for nullifier in nullifiers {
+        let transaction_id = consuming_transactions.get(&nullifier.inner());
+        count += stmt.execute(params![
+            nullifier.to_bytes(),
+            block_num,
+            transaction_id.map(|id| id.to_bytes()),
+        ])?;
+    }
+    Ok(count)
+}
+
+/// Select the consumption of the note `note_id` using the given [Connection].
+///
+/// # Returns
+///
+/// The consumption of the note, or [None] if it isn't consumed or its nullifier isn't known.
+pub fn select_note_consumption(
+    conn: &mut Connection,
+    note_id: RpoDigest,
+) -> Result<Option<NoteConsumption>> {
+    let mut stmt = conn.prepare(
+        "
+        SELECT
+            nullifier,
+            block_num,
+            transaction_id
+        FROM
+            note_consumptions
+        WHERE
+            note_id = ?1;",
+    )?;
+    let mut rows = stmt.query(params![note_id.to_bytes()])?;
+
+    let Some(row) = rows.next()? else {
+        return Ok(None);
+    };
+    let transaction_id =
+        row.get_ref(2)?.as_blob_or_null()?.map(RpoDigest::read_from_bytes).transpose()?;
+
+    Ok(Some(NoteConsumption {
+        note_id,
+        nullifier: Nullifier::read_from_bytes(row.get_ref(0)?.as_blob()?)?,
+        block_num: row.get(1)?,
+        transaction_id,
+    }))
+}
+
+// TRANSACTION QUERIES
+// ================================================================================================
+
+/// Insert the receipts of the transactions of a block to the DB using the given [Transaction].
+///
+/// # Returns
+///
+/// The number of affected rows.
+///
+/// # Note
+///
+/// The [Transaction] object is not consumed. It's up to the caller to commit or rollback the
+/// transaction.
+pub fn insert_transactions(
+    transaction: &Transaction,
+    receipts: &[TransactionReceipt],
+) -> Result<usize> {
+    let mut stmt = transaction.prepare(
+        "
+        INSERT INTO
+        transactions
+        (
+            transaction_id,
+            account_id,
+            block_num,
+            batch_index,
+            initial_account_hash,
+            final_account_hash,
+            output_notes,
+            input_nullifiers,
+            fee_faucet_id,
+            fee_amount,
+            cycles
+        )
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11);",
+    )?;
+
+    let mut count = 0;
+    for receipt in receipts.iter() {
+        let tx = &receipt.transaction;
+        count += stmt.execute(params![
+            tx.id.to_bytes(),
+            AccountId::from(tx.account_id),
+            receipt.block_num,
+            receipt.batch_index,
+            tx.initial_account_hash.to_bytes(),
+            tx.final_account_hash.to_bytes(),
+            tx.output_notes.to_bytes(),
+            tx.input_nullifiers.to_bytes(),
+            receipt.fee.map(|fee| AccountId::from(fee.faucet_id)),
+            receipt.fee.map(|fee| fee.amount as i64),
+            receipt.cycles as i64,
+        ])?;
+    }
+    Ok(count)
+}
+
+/// Select the receipt of the transaction with the given id from the DB using the given
+/// [Connection].
+///
+/// # Returns
+///
+/// The receipt of the transaction, or [None] if it wasn't included in any block.
+pub fn select_transaction_receipt(
+    conn: &mut Connection,
+    transaction_id: RpoDigest,
+) -> Result<Option<TransactionReceipt>> {
+    let mut stmt = conn.prepare(
+        "
+        SELECT
+            account_id,
+            block_num,
+            batch_index,
+            initial_account_hash,
+            final_account_hash,
+            output_notes,
+            input_nullifiers,
+            fee_faucet_id,
+            fee_amount,
+            cycles
+        FROM
+            transactions
+        WHERE
+            transaction_id = ?1;",
+    )?;
+    let mut rows = stmt.query(params![transaction_id.to_bytes()])?;
+
+    let Some(row) = rows.next()? else {
+        return Ok(None);
+    };
+
+    let account_id = AccountIdObject::try_from(row.get::<_, AccountId>(0)?)?;
+    let initial_account_hash = RpoDigest::read_from_bytes(row.get_ref(3)?.as_blob()?)?;
+    let final_account_hash = RpoDigest::read_from_bytes(row.get_ref(4)?.as_blob()?)?;
+    let output_notes = <Vec<NoteId>>::read_from_bytes(row.get_ref(5)?.as_blob()?)?;
+    let input_nullifiers = <Vec<Nullifier>>::read_from_bytes(row.get_ref(6)?.as_blob()?)?;
+    let fee = match (row.get::<_, Option<AccountId>>(7)?, row.get::<_, Option<i64>>(8)?) {
+        (Some(faucet_id), Some(amount)) => Some(TransactionFee {
+            faucet_id: faucet_id.try_into()?,
+            amount: amount as u64,
+        }),
+        _ => None,
+    };
+
+    Ok(Some(TransactionReceipt {
+        transaction: TransactionSummary {
+            id: transaction_id.into(),
+            account_id,
+            initial_account_hash,
+            final_account_hash,
+            output_notes,
+            input_nullifiers,
+        },
+        block_num: row.get(1)?,
+        batch_index: row.get(2)?,
+        fee,
+        cycles: row.get::<_, i64>(9)? as u64,
+    }))
+}
+
+/// Select the total fees paid by the transactions, per faucet, using the given [Connection].
+///
+/// # Returns
+///
+/// The total amount of each faucet's asset paid as fees, ordered by faucet id.
+pub fn select_collected_fees(conn: &mut Connection) -> Result<Vec<TransactionFee>> {
+    let mut stmt = conn.prepare(
+        "
+        SELECT
+            fee_faucet_id,
+            SUM(fee_amount)
+        FROM
+            transactions
+        WHERE
+            fee_faucet_id IS NOT NULL
+        GROUP BY
+            fee_faucet_id
+        ORDER BY
+            fee_faucet_id ASC;",
+    )?;
+    let mut rows = stmt.query([])?;
+
+    let mut fees = Vec::new();
+    while let Some(row) = rows.next()? {
+        fees.push(TransactionFee {
+            faucet_id: row.get::<_, AccountId>(0)?.try_into()?,
+            amount: row.get::<_, i64>(1)? as u64,
+        });
+    }
+    Ok(fees)
+}
+
+// BLOCK CHAIN QUERIES
+// ================================================================================================
+
+/// Insert a [BlockHeader] to the DB using the given [Transaction].
+///
+/// # Returns
+///
+/// The number of affected rows.
+///
+/// # Note
+///
+/// The [Transaction] object is not consumed. It's up to the caller to commit or rollback the
+/// transaction.
+pub fn insert_block_header(
+    transaction: &Transaction,
+    block_header: &BlockHeader,
+    signature: Option<&Signature>,
+) -> Result<usize> {
+    let mut stmt = transaction.prepare(
+        "
+        INSERT INTO
+            block_headers (block_num, block_header, signature, timestamp, note_root, nullifier_root,
+                account_root)
+        VALUES
+            (?1, ?2, ?3, ?4, ?5, ?6, ?7);",
     )?;
-    let mut rows = stmt.query(params![Rc::new(tags), Rc::new(account_ids), block_num])?;
+    Ok(stmt.execute(params![
+        block_header.block_num(),
+        block_header.to_bytes(),
+        signature.map(Serializable::to_bytes),
+        u64_to_value(block_header.timestamp().as_int()),
+        block_header.note_root().to_bytes(),
+        block_header.nullifier_root().to_bytes(),
+        block_header.account_root().to_bytes(),
+    ])?)
+}
+
+/// Sets the columns denormalized from the encoded header, `timestamp` and the roots, of the block
+/// headers inserted before they were added.
+///
+/// # Returns
+///
+/// The number of updated block headers.
+pub fn backfill_block_header_columns(transaction: &Transaction) -> Result<usize> {
+    let mut select_stmt = transaction.prepare(
+        "SELECT block_header FROM block_headers WHERE timestamp IS NULL OR note_root IS NULL;",
+    )?;
+    let mut update_stmt = transaction.prepare(
+        "
+        UPDATE
+            block_headers
+        SET
+            timestamp = ?2,
+            note_root = ?3,
+            nullifier_root = ?4,
+            account_root = ?5
+        WHERE
+            block_num = ?1;
+        ",
+    )?;
+
+    let mut rows = select_stmt.query([])?;
+    let mut count = 0;
+    while let Some(row) = rows.next()? {
+        let block_header = BlockHeader::read_from_bytes(row.get_ref(0)?.as_blob()?)?;
+        count += update_stmt.execute(params![
+            block_header.block_num(),
+            u64_to_value(block_header.timestamp().as_int()),
+            block_header.note_root().to_bytes(),
+            block_header.nullifier_root().to_bytes(),
+            block_header.account_root().to_bytes(),
+        ])?;
+    }
 
-    let mut res = Vec::new();
+    Ok(count)
+}
+
+/// Select the timestamps of the blocks `block_nums`.
+///
+/// # Returns
+///
+/// The timestamp of each existing block, keyed by block number.
+pub fn select_block_timestamps(
+    conn: &mut Connection,
+    block_nums: &[BlockNumber],
+) -> Result<BTreeMap<BlockNumber, u64>> {
+    let block_nums: Vec<Value> =
+        block_nums.iter().map(|block_num| u32_to_value(block_num.as_u32())).collect();
+
+    let mut stmt = conn
+        .prepare("SELECT block_num, timestamp FROM block_headers WHERE block_num IN rarray(?1);")?;
+    let mut rows = stmt.query(params![Rc::new(block_nums)])?;
+
+    let mut result = BTreeMap::new();
     while let Some(row) = rows.next()? {
-        let block_num = row.get(0)?;
-        let batch_index = row.get(1)?;
-        let note_index = row.get(2)?;
-        let note_id_data = row.get_ref(3)?.as_blob()?;
-        let note_id = RpoDigest::read_from_bytes(note_id_data)?;
-        let note_type = row.get::<_, u8>(4)?.try_into()?;
-        let sender = column_value_as_u64(row, 5)?;
-        let tag = row.get(6)?;
-        let merkle_path_data = row.get_ref(7)?.as_blob()?;
-        let merkle_path = MerklePath::read_from_bytes(merkle_path_data)?;
-        let details_data = row.get_ref(8)?.as_blob_or_null()?;
-        let details = details_data.map(<Vec<u8>>::read_from_bytes).transpose()?;
-
-        let note = Note {
-            block_num,
-            note_created: NoteCreated {
-                batch_index,
-                note_index,
-                note_id,
-                note_type,
-                sender,
-                tag,
-                details,
-            },
-            merkle_path,
-        };
-        res.push(note);
+        let timestamp: i64 = row.get(1)?;
+        result.insert(row.get(0)?, timestamp as u64);
     }
-    Ok(res)
+
+    Ok(result)
 }
 
-/// Select Note's matching the NoteId using the given [Connection].
+/// Select the [BlockHeaderFields] of at most `limit` blocks, starting from the block `block_from`,
+/// without decoding the headers.
 ///
 /// # Returns
 ///
-/// - Empty vector if no matching `note`.
-/// - Otherwise, notes which `note_hash` matches the `NoteId` as bytes.
-pub fn select_notes_by_id(conn: &mut Connection, note_ids: &[NoteId]) -> Result<Vec<Note>> {
-    let note_ids: Vec<Value> = note_ids.iter().map(|id| id.to_bytes().into()).collect();
-
+/// The fields of the blocks, ordered by block number.
+pub fn select_block_header_fields(
+    conn: &mut Connection,
+    block_from: BlockNumber,
+    limit: usize,
+) -> Result<Vec<BlockHeaderFields>> {
     let mut stmt = conn.prepare(
         "
         SELECT
             block_num,
-            batch_index,
-            note_index,
-            note_hash,
-            note_type,
-            sender,
-            tag,
-            merkle_path,
-            details
+            timestamp,
+            note_root,
+            nullifier_root,
+            account_root
         FROM
-            notes
+            block_headers
         WHERE
-            note_hash IN rarray(?1)
+            block_num >= ?1
+        ORDER BY
+            block_num ASC
+        LIMIT
+            ?2;
         ",
     )?;
-    let mut rows = stmt.query(params![Rc::new(note_ids)])?;
+    let mut rows = stmt.query(params![block_from, limit])?;
 
-    let mut notes = Vec::new();
+    let mut result = vec![];
     while let Some(row) = rows.next()? {
-        let note_id_data = row.get_ref(3)?.as_blob()?;
-        let note_id = NoteId::read_from_bytes(note_id_data)?;
-
-        let merkle_path_data = row.get_ref(7)?.as_blob()?;
-        let merkle_path = MerklePath::read_from_bytes(merkle_path_data)?;
-
-        let details_data = row.get_ref(8)?.as_blob_or_null()?;
-        let details = details_data.map(<Vec<u8>>::read_from_bytes).transpose()?;
-
-        notes.push(Note {
+        let timestamp: i64 = row.get(1)?;
+        result.push(BlockHeaderFields {
             block_num: row.get(0)?,
-            note_created: NoteCreated {
-                batch_index: row.get(1)?,
-                note_index: row.get(2)?,
-                details,
-                note_id: note_id.into(),
-                note_type: row.get::<_, u8>(4)?.try_into()?,
-                sender: column_value_as_u64(row, 5)?,
-                tag: row.get(6)?,
-            },
-            merkle_path,
-        })
+            timestamp: timestamp as u64,
+            note_root: RpoDigest::read_from_bytes(row.get_ref(2)?.as_blob()?)?,
+            nullifier_root: RpoDigest::read_from_bytes(row.get_ref(3)?.as_blob()?)?,
+            account_root: RpoDigest::read_from_bytes(row.get_ref(4)?.as_blob()?)?,
+        });
     }
-    Ok(notes)
-}
 
-// BLOCK CHAIN QUERIES
-// ================================================================================================
-
-/// Insert a [BlockHeader] to the DB using the given [Transaction].
-///
-/// # Returns
-///
-/// The number of affected rows.
-///
-/// # Note
-///
-/// The [Transaction] object is not consumed. It's up to the caller to commit or rollback the
-/// transaction.
-pub fn insert_block_header(transaction: &Transaction, block_header: &BlockHeader) -> Result<usize> {
-    let mut stmt = transaction
-        .prepare("INSERT INTO block_headers (block_num, block_header) VALUES (?1, ?2);")?;
-    Ok(stmt.execute(params![block_header.block_num(), block_header.to_bytes()])?)
+    Ok(result)
 }
 
 /// Select a [BlockHeader] from the DB by its `block_num` using the given [Connection].
@@ -614,15 +2108,145 @@ pub fn select_block_header_by_block_num(
     }
 }
 
-/// Select all block headers from the DB using the given [Connection].
+/// Select the sequencer's [Signature] of the block header with the given `block_num`.
 ///
 /// # Returns
 ///
-/// A vector of [BlockHeader] or an error.
-pub fn select_block_headers(conn: &mut Connection) -> Result<Vec<BlockHeader>> {
+/// [None] if the block doesn't exist or is not signed.
+pub fn select_block_signature(
+    conn: &mut Connection,
+    block_num: BlockNumber,
+) -> Result<Option<Signature>> {
+    let mut stmt = conn.prepare("SELECT signature FROM block_headers WHERE block_num = ?1")?;
+    let mut rows = stmt.query([block_num])?;
+
+    match rows.next()? {
+        Some(row) => match row.get_ref(0)?.as_blob_or_null()? {
+            Some(data) => Ok(Some(Signature::read_from_bytes(data)?)),
+            None => Ok(None),
+        },
+        None => Ok(None),
+    }
+}
+
+/// Insert the encoded `block` applied as the block `block_num`, so it can be streamed to the
+/// replica nodes.
+///
+/// # Returns
+///
+/// The number of affected rows.
+pub fn insert_block(
+    transaction: &Transaction,
+    block_num: BlockNumber,
+    block: &[u8],
+) -> Result<usize> {
+    let mut stmt = transaction.prepare("INSERT INTO blocks (block_num, block) VALUES (?1, ?2);")?;
+    Ok(stmt.execute(params![block_num, block])?)
+}
+
+/// Insert the validity `proof` of the block `block_num`.
+///
+/// # Returns
+///
+/// The number of affected rows.
+pub fn insert_block_proof(
+    transaction: &Transaction,
+    block_num: BlockNumber,
+    proof: &[u8],
+) -> Result<usize> {
     let mut stmt =
-        conn.prepare("SELECT block_header FROM block_headers ORDER BY block_num ASC;")?;
-    let mut rows = stmt.query([])?;
+        transaction.prepare("INSERT INTO block_proofs (block_num, proof) VALUES (?1, ?2);")?;
+    Ok(stmt.execute(params![block_num, proof])?)
+}
+
+/// Select the validity proof of the block `block_num`, [None] if the block isn't proven or its
+/// proof was pruned.
+pub fn select_block_proof(
+    conn: &mut Connection,
+    block_num: BlockNumber,
+) -> Result<Option<Vec<u8>>> {
+    let mut stmt = conn.prepare("SELECT proof FROM block_proofs WHERE block_num = ?1")?;
+    let mut rows = stmt.query([block_num])?;
+
+    match rows.next()? {
+        Some(row) => Ok(Some(row.get(0)?)),
+        None => Ok(None),
+    }
+}
+
+/// Delete the validity proofs of the blocks before `block_num`.
+///
+/// # Returns
+///
+/// The number of deleted proofs.
+pub fn delete_block_proofs_before(
+    transaction: &Transaction,
+    block_num: BlockNumber,
+) -> Result<usize> {
+    let mut stmt = transaction.prepare("DELETE FROM block_proofs WHERE block_num < ?1")?;
+    Ok(stmt.execute([block_num])?)
+}
+
+/// Select at most `limit` encoded blocks, starting from the block `block_num`.
+///
+/// # Returns
+///
+/// The block numbers and encoded blocks, ordered by block number.
+pub fn select_blocks(
+    conn: &mut Connection,
+    block_num: BlockNumber,
+    limit: usize,
+) -> Result<Vec<(BlockNumber, Vec<u8>)>> {
+    let mut stmt = conn.prepare(
+        "SELECT block_num, block FROM blocks WHERE block_num >= ?1 ORDER BY block_num ASC LIMIT ?2",
+    )?;
+    let mut rows = stmt.query(params![block_num, limit])?;
+
+    let mut result = vec![];
+    while let Some(row) = rows.next()? {
+        result.push((row.get(0)?, row.get(1)?));
+    }
+    Ok(result)
+}
+
+/// Select at most `limit` block headers from the DB using the given [Connection], ordered by
+/// block number and starting after the block `after`, or from the genesis block if [None].
+///
+/// # Returns
+///
+/// A vector of [BlockHeader] or an error.
+pub fn select_block_headers_chunk(
+    conn: &mut Connection,
+    after: Option<BlockNumber>,
+    limit: usize,
+) -> Result<Vec<BlockHeader>> {
+    // The first chunk has its own statement, see [select_account_hashes_chunk]
+    let mut stmt;
+    let mut rows = match after {
+        Some(after) => {
+            stmt = conn.prepare(
+                "
+                SELECT
+                    block_header
+                FROM
+                    block_headers
+                WHERE
+                    block_num > ?1
+                ORDER BY
+                    block_num ASC
+                LIMIT
+                    ?2;
+            ",
+            )?;
+            stmt.query(params![after, limit])?
+        },
+        None => {
+            stmt = conn.prepare(
+                "SELECT block_header FROM block_headers ORDER BY block_num ASC LIMIT ?1;",
+            )?;
+            stmt.query(params![limit])?
+        },
+    };
     let mut result = vec![];
     while let Some(row) = rows.next()? {
         let block_header_data = row.get_ref(0)?.as_blob()?;
@@ -633,55 +2257,31 @@ pub fn select_block_headers(conn: &mut Connection) -> Result<Vec<BlockHeader>> {
     Ok(result)
 }
 
-// STATE SYNC
+// NOTE SYNC
 // ================================================================================================
 
-/// Loads the state necessary for a state sync.
-pub fn get_state_sync(
+/// Loads the notes of the first block after `block_num` with a note matching `filter`, without the
+/// account and nullifier updates of a state sync.
+pub fn get_note_sync(
     conn: &mut Connection,
     block_num: BlockNumber,
-    account_ids: &[AccountId],
-    note_tag_prefixes: &[u32],
-    nullifier_prefixes: &[u32],
-) -> Result<StateSyncUpdate, StateSyncError> {
-    let notes = select_notes_since_block_by_tag_and_sender(
-        conn,
-        note_tag_prefixes,
-        account_ids,
-        block_num,
-    )?;
+    filter: &NoteFilter,
+) -> Result<NoteSyncUpdate, NoteSyncError> {
+    let notes = select_notes_of_next_blocks(conn, filter, block_num, 1)?;
 
-    let (block_header, chain_tip) = if !notes.is_empty() {
-        let block_header = select_block_header_by_block_num(conn, Some(notes[0].block_num))?
-            .ok_or(StateSyncError::EmptyBlockHeadersTable)?;
-        let tip = select_block_header_by_block_num(conn, None)?
-            .ok_or(StateSyncError::EmptyBlockHeadersTable)?;
-
-        (block_header, tip.block_num())
-    } else {
-        let block_header = select_block_header_by_block_num(conn, None)?
-            .ok_or(StateSyncError::EmptyBlockHeadersTable)?;
+    let chain_tip = select_block_header_by_block_num(conn, None)?
+        .ok_or(NoteSyncError::EmptyBlockHeadersTable)?;
 
-        let block_num = block_header.block_num();
-        (block_header, block_num)
+    let block_header = match notes.first() {
+        Some(note) => select_block_header_by_block_num(conn, Some(note.block_num))?
+            .ok_or(NoteSyncError::EmptyBlockHeadersTable)?,
+        None => chain_tip,
     };
 
-    let account_updates =
-        select_accounts_by_block_range(conn, block_num, block_header.block_num(), account_ids)?;
-
-    let nullifiers = select_nullifiers_by_block_range(
-        conn,
-        block_num,
-        block_header.block_num(),
-        nullifier_prefixes,
-    )?;
-
-    Ok(StateSyncUpdate {
+    Ok(NoteSyncUpdate {
         notes,
         block_header,
-        chain_tip,
-        account_updates,
-        nullifiers,
+        chain_tip: chain_tip.block_num().into(),
     })
 }
 
@@ -690,30 +2290,369 @@ pub fn get_state_sync(
 
 /// Updates the DB with the state of a new block.
 ///
+/// The time spent in each statement is recorded in `timings`.
+///
 /// # Returns
 ///
 /// The number of affected rows in the DB.
+#[allow(clippy::too_many_arguments)]
 pub fn apply_block(
     transaction: &Transaction,
     block_header: &BlockHeader,
+    signature: Option<&Signature>,
+    block: Option<&[u8]>,
+    proof: Option<&[u8]>,
     notes: &[Note],
     nullifiers: &[Nullifier],
     accounts: &[AccountUpdateDetails],
+    transactions: &[TransactionReceipt],
+    timings: &mut StatementTimings,
 ) -> Result<usize> {
     let mut count = 0;
-    count += insert_block_header(transaction, block_header)?;
+    count += insert_block_header(transaction, block_header, signature)?;
+    timings.record("insert_block_header");
+    if let Some(block) = block {
+        count += insert_block(transaction, block_header.block_num().into(), block)?;
+        timings.record("insert_block");
+    }
+    if let Some(proof) = proof {
+        count += insert_block_proof(transaction, block_header.block_num().into(), proof)?;
+        timings.record("insert_block_proof");
+    }
     count += insert_notes(transaction, notes)?;
-    count += upsert_accounts(transaction, accounts, block_header.block_num())?;
-    count += insert_nullifiers_for_block(transaction, nullifiers, block_header.block_num())?;
+    timings.record("insert_notes");
+    count += upsert_accounts(transaction, accounts, block_header.block_num().into())?;
+    timings.record("upsert_accounts");
+    count += insert_nullifiers_for_block(transaction, nullifiers, block_header.block_num().into())?;
+    timings.record("insert_nullifiers");
+    count += insert_transactions(transaction, transactions)?;
+    timings.record("insert_transactions");
+    count += insert_note_consumptions(
+        transaction,
+        nullifiers,
+        block_header.block_num().into(),
+        transactions,
+    )?;
+    timings.record("insert_note_consumptions");
     Ok(count)
 }
 
+// MAINTENANCE
+// ================================================================================================
+
+/// Moves the content of the write-ahead log into the database file and truncates the log, so the
+/// database file alone holds the latest state.
+pub fn checkpoint_wal(conn: &mut Connection) -> Result<()> {
+    // The first column is 1 if the checkpoint couldn't complete because of a concurrent reader or
+    // writer
+    let busy: u32 = conn.query_row("PRAGMA wal_checkpoint(TRUNCATE);", [], |row| row.get(0))?;
+    if busy != 0 {
+        return Err(DatabaseError::WalCheckpointBusy);
+    }
+
+    Ok(())
+}
+
+/// Copies the database to a new database at `path` with the online backup API, and returns the
+/// latest block header of the copy.
+///
+/// The copy is made in a single step, holding a read transaction for its whole duration, so it's a
+/// consistent snapshot of the database. In WAL mode, the blocks keep being applied meanwhile.
+pub fn backup_database(conn: &mut Connection, path: &Path) -> Result<BlockHeader> {
+    let mut backup_conn = Connection::open(path)?;
+    // A negative number of pages copies the whole database at once
+    if Backup::new(conn, &mut backup_conn)?.step(-1)? != StepResult::Done {
+        return Err(DatabaseError::BackupBusy);
+    }
+
+    select_block_header_by_block_num(&mut backup_conn, None)?
+        .ok_or_else(|| DatabaseError::CorruptedData("Backup has no block header".to_string()))
+}
+
+/// Returns the application id of the database, zero if it was never set.
+pub fn select_application_id(conn: &mut Connection) -> Result<i32> {
+    Ok(conn.query_row("PRAGMA application_id;", [], |row| row.get(0))?)
+}
+
+/// Sets the application id of the database, stored in the header of the database file.
+pub fn set_application_id(conn: &mut Connection, application_id: i32) -> Result<()> {
+    Ok(conn.pragma_update(None, "application_id", application_id)?)
+}
+
+/// Select the store instance which last owned the database, [None] if no store owned it yet.
+pub fn select_store_instance(conn: &mut Connection) -> Result<Option<StoreInstance>> {
+    let mut stmt = conn
+        .prepare("SELECT instance_id, process_id, started_at FROM store_instance WHERE id = 0")?;
+    let mut rows = stmt.query([])?;
+
+    let Some(row) = rows.next()? else {
+        return Ok(None);
+    };
+    let instance_id: Vec<u8> = row.get(0)?;
+    let instance_id = Uuid::from_slice(&instance_id)
+        .map_err(|err| DatabaseError::CorruptedData(format!("Invalid store instance id: {err}")))?;
+
+    Ok(Some(StoreInstance {
+        instance_id,
+        process_id: row.get(1)?,
+        started_at: row.get::<_, i64>(2)? as u64,
+    }))
+}
+
+/// Records `instance` as the owner of the database, replacing the previous owner.
+///
+/// # Returns
+///
+/// The number of affected rows.
+pub fn upsert_store_instance(transaction: &Transaction, instance: &StoreInstance) -> Result<usize> {
+    let mut stmt = transaction.prepare(
+        "
+        INSERT OR REPLACE INTO store_instance (id, instance_id, process_id, started_at)
+        VALUES (0, ?1, ?2, ?3);
+        ",
+    )?;
+    Ok(stmt.execute(params![
+        instance.instance_id.as_bytes(),
+        instance.process_id,
+        u64_to_value(instance.started_at)
+    ])?)
+}
+
+/// Returns the size of the database file, without the write-ahead log.
+pub fn select_database_bytes(conn: &mut Connection) -> Result<u64> {
+    let page_count: u64 = conn.query_row("PRAGMA page_count;", [], |row| row.get(0))?;
+    let page_size: u64 = conn.query_row("PRAGMA page_size;", [], |row| row.get(0))?;
+
+    Ok(page_count * page_size)
+}
+
+/// Returns the size of the database file and of each of its tables, including their indexes.
+pub fn select_database_size(conn: &mut Connection) -> Result<DatabaseSize> {
+    let total_bytes = select_database_bytes(conn)?;
+
+    // `dbstat` lists the b-trees by name, the indexes are attributed to their table
+    let mut stmt = conn.prepare(
+        "
+        SELECT
+            COALESCE(sqlite_master.tbl_name, dbstat.name),
+            SUM(dbstat.pgsize)
+        FROM
+            dbstat
+        LEFT JOIN
+            sqlite_master ON sqlite_master.name = dbstat.name
+        GROUP BY
+            1;",
+    )?;
+    let mut rows = stmt.query([])?;
+
+    // the partitions of the nullifiers are reported as the `nullifiers` table
+    let mut tables = BTreeMap::new();
+    while let Some(row) = rows.next()? {
+        let table: String = row.get(0)?;
+        let bytes: u64 = row.get(1)?;
+        let table = if is_nullifier_partition(&table) {
+            "nullifiers".to_string()
+        } else {
+            table
+        };
+        *tables.entry(table).or_insert(0) += bytes;
+    }
+
+    Ok(DatabaseSize { total_bytes, tables })
+}
+
+/// Returns the number of rows of the tables holding the state of the chain.
+pub fn select_row_counts(conn: &mut Connection) -> Result<RowCounts> {
+    let mut counts = conn.query_row(
+        "
+        SELECT
+            (SELECT COUNT(*) FROM block_headers),
+            (SELECT COUNT(*) FROM accounts),
+            (SELECT COUNT(*) FROM notes),
+            (SELECT COUNT(*) FROM transactions);",
+        [],
+        |row| {
+            Ok(RowCounts {
+                block_headers: row.get(0)?,
+                accounts: row.get(1)?,
+                notes: row.get(2)?,
+                nullifiers: 0,
+                transactions: row.get(3)?,
+            })
+        },
+    )?;
+
+    for epoch in select_nullifier_epochs(conn, None)? {
+        let nullifiers: u64 = conn.query_row(
+            &format!("SELECT COUNT(*) FROM {};", nullifier_partition(epoch)),
+            [],
+            |row| row.get(0),
+        )?;
+        counts.nullifiers += nullifiers;
+    }
+
+    Ok(counts)
+}
+
+/// Returns the tables, indexes and views of the database, the partitions of the nullifiers being
+/// described by their first partition.
+pub fn select_schema(conn: &mut Connection) -> Result<DatabaseSchema> {
+    let version: u32 = conn.query_row("PRAGMA user_version;", [], |row| row.get(0))?;
+
+    let mut stmt = conn.prepare(
+        "
+        SELECT
+            type, name, sql
+        FROM
+            sqlite_master
+        WHERE
+            type IN ('table', 'view') AND name NOT LIKE 'sqlite_%'
+        ORDER BY
+            name;",
+    )?;
+    let objects = stmt
+        .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get(1)?, row.get(2)?)))?
+        .collect::<rusqlite::Result<Vec<(String, String, String)>>>()?;
+
+    let mut schema = DatabaseSchema { version, ..Default::default() };
+    let mut partitions = Vec::new();
+    for (object_type, name, sql) in objects {
+        if object_type == "view" {
+            schema.views.push(ViewSchema { name, sql });
+        } else if is_nullifier_partition(&name) {
+            partitions.push((name, sql));
+        } else {
+            schema.tables.push(select_table_schema(conn, name, sql)?);
+        }
+    }
+
+    partitions.sort_by_key(|(name, _)| {
+        name.trim_start_matches("nullifiers_")
+            .parse::<u32>()
+            .expect("partitions have an epoch")
+    });
+    let mut partitions = partitions.into_iter();
+    if let Some((name, sql)) = partitions.next() {
+        let mut table = select_table_schema(conn, name, sql)?;
+        table.partitions =
+            iter::once(table.name.clone()).chain(partitions.map(|(name, _)| name)).collect();
+        schema.tables.push(table);
+    }
+
+    Ok(schema)
+}
+
+/// Returns the columns and indexes of the table `name`, created by `sql`.
+fn select_table_schema(conn: &Connection, name: String, sql: String) -> Result<TableSchema> {
+    let mut stmt = conn.prepare_cached(
+        "SELECT name, type, \"notnull\", dflt_value, pk FROM pragma_table_info(?1);",
+    )?;
+    let columns = stmt
+        .query_map([&name], |row| {
+            Ok(ColumnSchema {
+                name: row.get(0)?,
+                column_type: row.get(1)?,
+                not_null: row.get(2)?,
+                default_value: row.get(3)?,
+                primary_key: row.get::<_, u32>(4)? > 0,
+            })
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+
+    let mut stmt = conn.prepare_cached(
+        "
+        SELECT
+            pragma_index_list.name, pragma_index_list.\"unique\", sqlite_master.sql
+        FROM
+            pragma_index_list(?1)
+        LEFT JOIN
+            sqlite_master ON sqlite_master.type = 'index' AND sqlite_master.name = pragma_index_list.name
+        ORDER BY
+            pragma_index_list.name;",
+    )?;
+    let indexes = stmt
+        .query_map([&name], |row| {
+            Ok(IndexSchema {
+                name: row.get(0)?,
+                unique: row.get(1)?,
+                columns: Vec::new(),
+                sql: row.get(2)?,
+            })
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+
+    let mut stmt = conn.prepare_cached("SELECT name FROM pragma_index_info(?1) ORDER BY seqno;")?;
+    let indexes = indexes
+        .into_iter()
+        .map(|index| {
+            let columns = stmt
+                // the expressions of the indexes on expressions have no name
+                .query_map([&index.name], |row| {
+                    Ok(row.get::<_, Option<String>>(0)?.unwrap_or_else(|| "<expression>".to_string()))
+                })?
+                .collect::<rusqlite::Result<Vec<_>>>()?;
+            Ok(IndexSchema { columns, ..index })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(TableSchema {
+        name,
+        sql,
+        columns,
+        indexes,
+        partitions: Vec::new(),
+    })
+}
+
 // UTILITIES
 // ================================================================================================
 
-/// Returns the high 16 bits of the provided nullifier.
-pub(crate) fn get_nullifier_prefix(nullifier: &Nullifier) -> u32 {
-    (nullifier.most_significant_felt().as_int() >> 48) as u32
+/// Returns the values of the `nullifier_prefix` column matching the `prefix_len` bits prefixes.
+///
+/// Prefixes shorter than the column match a range of values, longer prefixes match the value of
+/// their high bits.
+fn nullifier_prefix_column_values(prefix_len: u32, nullifier_prefixes: &[u32]) -> BTreeSet<u32> {
+    nullifier_prefixes
+        .iter()
+        .flat_map(|&prefix| {
+            if prefix_len >= NULLIFIER_PREFIX_COLUMN_LEN {
+                let value = prefix >> (prefix_len - NULLIFIER_PREFIX_COLUMN_LEN);
+                value..=value
+            } else {
+                let shift = NULLIFIER_PREFIX_COLUMN_LEN - prefix_len;
+                (prefix << shift)..=((prefix << shift) | ((1 << shift) - 1))
+            }
+        })
+        .collect()
+}
+
+/// Returns the condition selecting the notes whose `aux` is in one of the `ranges`, always true if
+/// there are none, with its parameters numbered from `first_param`.
+///
+/// The `aux` values are stored as `i64`, the values above `i64::MAX` being negative, so each range
+/// is split into the range of its values stored as non-negative and the range of the others.
+fn aux_ranges_condition(ranges: &[AuxRange], first_param: usize) -> (String, Vec<Value>) {
+    if ranges.is_empty() {
+        return ("TRUE".to_string(), Vec::new());
+    }
+
+    let mut conditions = Vec::new();
+    let mut params = Vec::new();
+    for range in ranges {
+        let bounds =
+            [(range.min, range.max.min(i64::MAX as u64)), (range.min.max(1 << 63), range.max)];
+        for (min, max) in bounds.into_iter().filter(|(min, max)| min <= max) {
+            let param = first_param + params.len();
+            conditions.push(format!("notes.aux BETWEEN ?{} AND ?{}", param, param + 1));
+            params.extend([u64_to_value(min), u64_to_value(max)]);
+        }
+    }
+
+    if conditions.is_empty() {
+        ("FALSE".to_string(), params)
+    } else {
+        (format!("({})", conditions.join(" OR ")), params)
+    }
 }
 
 /// Converts a `u64` into a [Value].
@@ -732,31 +2671,21 @@ fn u32_to_value(v: u32) -> Value {
     Value::Integer(v)
 }
 
-/// Gets a `u64` value from the database.
-///
-/// Sqlite uses `i64` as its internal representation format, and so when retrieving
-/// we need to make sure we cast as `u64` to get the original value
-fn column_value_as_u64<I: rusqlite::RowIndex>(
-    row: &rusqlite::Row<'_>,
-    index: I,
-) -> rusqlite::Result<u64> {
-    let value: i64 = row.get(index)?;
-    Ok(value as u64)
-}
-
 /// Constructs `AccountSummary` from the row of `accounts` table.
 ///
 /// Note: field ordering must be the same, as in `accounts` table!
 fn account_hash_update_from_row(row: &rusqlite::Row<'_>) -> Result<AccountSummary> {
-    let account_id = column_value_as_u64(row, 0)?;
+    let account_id: AccountId = row.get(0)?;
     let account_hash_data = row.get_ref(1)?.as_blob()?;
     let account_hash = RpoDigest::read_from_bytes(account_hash_data)?;
     let block_num = row.get(2)?;
+    let nonce: Option<i64> = row.get(4)?;
 
     Ok(AccountSummary {
         account_id: account_id.try_into()?,
         account_hash,
         block_num,
+        nonce: nonce.map(|nonce| nonce as u64),
     })
 }
 
@@ -772,9 +2701,25 @@ fn account_info_from_row(row: &rusqlite::Row<'_>) -> Result<AccountInfo> {
     Ok(AccountInfo { summary: update, details })
 }
 
+/// Returns the lookup outcome of the account stored in `row`.
+///
+/// Accounts are registered by the first block updating them, a registered account without stored
+/// details is private. Public accounts must always have their details stored.
+fn account_lookup_from_row(row: &rusqlite::Row<'_>) -> Result<AccountLookup> {
+    let AccountInfo { summary, details } = account_info_from_row(row)?;
+
+    match details {
+        Some(account) => Ok(AccountLookup::Public { summary, account }),
+        None if summary.account_id.is_on_chain() => {
+            Err(DatabaseError::AccountDetailsMissing(summary.account_id.into()))
+        },
+        None => Ok(AccountLookup::PrivateOnChain { summary }),
+    }
+}
+
 /// Deserializes account and applies account delta.
 fn apply_delta(
-    account_id: u64,
+    account_id: AccountId,
     value: &ValueRef<'_>,
     delta: &AccountDelta,
     final_state_hash: &RpoDigest,
@@ -0,0 +1,308 @@
+//! Planner of the state sync queries.
+//!
+//! A state sync is made of three independent queries: the notes, the account updates and the
+//! nullifiers matching the filters of the request. The plan reads the chain tip first, the
+//! snapshot block, and bounds the three queries by it. The notes and the nullifiers are never
+//! modified once inserted, so they can be selected concurrently on different connections of the
+//! pool and still be merged consistently. The accounts only hold their latest state, their updates
+//! are selected in the same read transaction as the snapshot, see [read_snapshot]. The merge then
+//! picks the blocks of the update from the notes and keeps the account updates and the nullifiers
+//! up to its last block.
+//!
+//! The filters are split into chunks, each queried on its own, so that a request with thousands of
+//! account ids, tags or nullifier prefixes doesn't build a huge temporary table per query.
+use std::collections::{BTreeMap, BTreeSet};
+
+use miden_node_proto::domain::accounts::AccountInfo;
+use miden_objects::BlockHeader;
+use rusqlite::Connection;
+
+use super::{
+    sql, BlockNotes, Note, NoteFilter, NullifierInfo, Result, StateSyncLimits, StateSyncUpdate,
+};
+use crate::{
+    errors::{DatabaseError, StateSyncError},
+    types::{AccountId, BlockNumber},
+};
+
+// STATE SYNC PLAN
+// ================================================================================================
+
+/// The filters of a state sync.
+///
+/// The update covers the next `limits.max_blocks` blocks with a matching note, as long as they
+/// contain at most `limits.max_notes` notes, the first block being always included. If there are no
+/// further blocks with matching notes, the update extends to the chain tip.
+///
+/// The account updates include the details of the public accounts if `include_account_details` is
+/// set.
+///
+/// When continuing a truncated state sync, `limits.until` is set to the last block of the truncated
+/// update, and the update covers the same blocks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StateSyncPlan {
+    pub block_num: BlockNumber,
+    pub account_ids: Vec<AccountId>,
+    pub note_tag_prefixes: Vec<u32>,
+    pub nullifier_prefix_len: u32,
+    pub nullifier_prefixes: Vec<u32>,
+    pub limits: StateSyncLimits,
+    pub include_account_details: bool,
+    /// Maximum number of values of a filter queried at once
+    pub filter_chunk_size: usize,
+}
+
+impl StateSyncPlan {
+    /// Loads the state necessary for a state sync, running the queries one after the other on
+    /// `conn`.
+    #[cfg(test)]
+    pub fn run(&self, conn: &mut Connection) -> Result<StateSyncUpdate, StateSyncError> {
+        let (chain_tip, snapshot_header, account_updates) =
+            self.select_snapshot_and_account_updates(conn)?;
+        let snapshot = snapshot_header.block_num().into();
+
+        let blocks = self.select_notes(conn, snapshot)?;
+        let nullifiers = self.select_nullifiers(conn, snapshot)?;
+
+        self.merge(conn, chain_tip, snapshot_header, blocks, account_updates, nullifiers)
+    }
+
+    /// Loads the number of the chain tip, the header of the snapshot block and the updates of the
+    /// requested accounts up to it, in a single read transaction.
+    ///
+    /// An account updated again by a block applied after the snapshot would otherwise be missing
+    /// from the update, its row holding the later state.
+    pub fn select_snapshot_and_account_updates(
+        &self,
+        conn: &mut Connection,
+    ) -> Result<(BlockNumber, BlockHeader, Vec<AccountInfo>), StateSyncError> {
+        read_snapshot(conn, |conn| {
+            let (chain_tip, snapshot_header) = self.select_snapshot(conn)?;
+            let snapshot = snapshot_header.block_num().into();
+            let account_updates = self.select_account_updates(conn, snapshot)?;
+
+            Ok((chain_tip, snapshot_header, account_updates))
+        })
+    }
+
+    /// Loads the number of the chain tip, and the header of the snapshot block bounding the other
+    /// queries: the chain tip, or `limits.until` if set.
+    fn select_snapshot(
+        &self,
+        conn: &mut Connection,
+    ) -> Result<(BlockNumber, BlockHeader), StateSyncError> {
+        let chain_tip = sql::select_block_header_by_block_num(conn, None)?
+            .ok_or(StateSyncError::EmptyBlockHeadersTable)?;
+        let chain_tip_num = BlockNumber::from(chain_tip.block_num());
+
+        let Some(until) = self.limits.until else {
+            return Ok((chain_tip_num, chain_tip));
+        };
+        if until < self.block_num || until > chain_tip_num {
+            return Err(StateSyncError::InvalidSyncEnd {
+                block_num: self.block_num,
+                until,
+                chain_tip: chain_tip_num,
+            });
+        }
+
+        Ok((chain_tip_num, select_block_header(conn, until)?))
+    }
+
+    /// Loads the matching notes of the next blocks after `block_num` up to `snapshot`, grouped by
+    /// block.
+    pub fn select_notes(
+        &self,
+        conn: &mut Connection,
+        snapshot: BlockNumber,
+    ) -> Result<Vec<(BlockNumber, Vec<Note>)>> {
+        let tag_chunks = filter_chunks(&self.note_tag_prefixes, self.filter_chunk_size);
+        let sender_chunks = filter_chunks(&self.account_ids, self.filter_chunk_size);
+
+        // A note can match both a tag and a sender, the notes are deduplicated by their position
+        let mut notes = BTreeMap::new();
+        for chunk in 0..tag_chunks.len().max(sender_chunks.len()) {
+            let filter = NoteFilter {
+                tags: tag_chunks.get(chunk).cloned().unwrap_or_default(),
+                senders: sender_chunks.get(chunk).cloned().unwrap_or_default(),
+                ..Default::default()
+            };
+            let chunk_notes = sql::select_notes_of_next_blocks(
+                conn,
+                &filter,
+                self.block_num,
+                self.limits.max_blocks,
+            )?;
+            for note in chunk_notes {
+                let position =
+                    (note.block_num, note.note_created.batch_index, note.note_created.note_index);
+                notes.insert(position, note);
+            }
+        }
+
+        // Each chunk selects its own next blocks, the first blocks of all the chunks are the next
+        // blocks of the whole filter. The blocks applied after the snapshot are left out, as if the
+        // queries ran at the snapshot.
+        let mut blocks = group_notes_by_block(notes.into_values().collect());
+        blocks.truncate(self.limits.max_blocks as usize);
+        blocks.retain(|(block_num, _)| *block_num <= snapshot);
+
+        Ok(blocks)
+    }
+
+    /// Loads the updates of the requested accounts after `block_num` up to `snapshot`.
+    pub fn select_account_updates(
+        &self,
+        conn: &mut Connection,
+        snapshot: BlockNumber,
+    ) -> Result<Vec<AccountInfo>> {
+        let mut account_updates = Vec::new();
+        for account_ids in filter_chunks(&self.account_ids, self.filter_chunk_size) {
+            account_updates.extend(sql::select_accounts_by_block_range(
+                conn,
+                self.block_num,
+                snapshot,
+                &account_ids,
+                self.include_account_details,
+            )?);
+        }
+        account_updates.sort_by_key(|update| update.summary.block_num);
+
+        Ok(account_updates)
+    }
+
+    /// Loads the matching nullifiers consumed after `block_num` up to `snapshot`.
+    pub fn select_nullifiers(
+        &self,
+        conn: &mut Connection,
+        snapshot: BlockNumber,
+    ) -> Result<Vec<NullifierInfo>> {
+        let mut nullifiers = Vec::new();
+        for nullifier_prefixes in filter_chunks(&self.nullifier_prefixes, self.filter_chunk_size) {
+            nullifiers.extend(sql::select_nullifiers_by_block_range(
+                conn,
+                self.block_num,
+                snapshot,
+                self.nullifier_prefix_len,
+                &nullifier_prefixes,
+            )?);
+        }
+        nullifiers.sort_by_key(|nullifier| nullifier.block_num);
+
+        Ok(nullifiers)
+    }
+
+    /// Merges the results of the queries bounded by the snapshot block `snapshot` into the update,
+    /// loading the headers of its blocks from `conn`.
+    pub fn merge(
+        &self,
+        conn: &mut Connection,
+        chain_tip: BlockNumber,
+        snapshot: BlockHeader,
+        mut blocks: Vec<(BlockNumber, Vec<Note>)>,
+        mut account_updates: Vec<AccountInfo>,
+        mut nullifiers: Vec<NullifierInfo>,
+    ) -> Result<StateSyncUpdate, StateSyncError> {
+        let found_blocks = blocks.len();
+
+        let mut included_notes = 0;
+        let included_blocks = blocks
+            .iter()
+            .take_while(|(_, notes)| {
+                included_notes += notes.len();
+                included_notes <= self.limits.max_notes
+            })
+            .count()
+            .max(1);
+        blocks.truncate(included_blocks);
+
+        let snapshot_num = BlockNumber::from(snapshot.block_num());
+
+        // The update extends to the snapshot if it includes the last blocks with matching notes,
+        // otherwise it ends at the last included block
+        let reaches_snapshot =
+            found_blocks < self.limits.max_blocks as usize && blocks.len() == found_blocks;
+        let last_block = match blocks.last() {
+            Some((last_block_num, _)) if reaches_snapshot && *last_block_num != snapshot_num => {
+                None
+            },
+            _ => blocks.pop(),
+        };
+
+        let (block_header, notes) = match last_block {
+            Some((last_block_num, notes)) => {
+                let block_header = select_block_header(conn, last_block_num)?;
+                (block_header, notes)
+            },
+            None => (snapshot, Vec::new()),
+        };
+
+        let preceding_blocks = blocks
+            .into_iter()
+            .map(|(block_num, notes)| {
+                let block_header = select_block_header(conn, block_num)?;
+                Ok(BlockNotes { block_header, notes })
+            })
+            .collect::<Result<Vec<_>, StateSyncError>>()?;
+
+        let last_block_num = BlockNumber::from(block_header.block_num());
+        account_updates.retain(|update| update.summary.block_num <= last_block_num.as_u32());
+        nullifiers.retain(|nullifier| nullifier.block_num <= last_block_num);
+
+        Ok(StateSyncUpdate {
+            notes,
+            block_header,
+            chain_tip,
+            account_updates,
+            nullifiers,
+            preceding_blocks,
+        })
+    }
+}
+
+/// Runs `query` in a read transaction on `conn`, so that all its statements read the same state of
+/// the database while blocks are applied on other connections.
+pub fn read_snapshot<R, E>(
+    conn: &mut Connection,
+    query: impl FnOnce(&mut Connection) -> Result<R, E>,
+) -> Result<R, E>
+where
+    E: From<DatabaseError>,
+{
+    conn.execute_batch("BEGIN DEFERRED;").map_err(DatabaseError::from)?;
+    let result = query(conn);
+
+    // The transaction only reads, rolling it back releases its snapshot. An interrupted statement
+    // may have ended it already.
+    if !conn.is_autocommit() {
+        conn.execute_batch("ROLLBACK;").map_err(DatabaseError::from)?;
+    }
+
+    result
+}
+
+fn select_block_header(
+    conn: &mut Connection,
+    block_num: BlockNumber,
+) -> Result<BlockHeader, StateSyncError> {
+    sql::select_block_header_by_block_num(conn, Some(block_num))?
+        .ok_or(StateSyncError::EmptyBlockHeadersTable)
+}
+
+/// Splits the values of a filter into chunks of at most `chunk_size` distinct values.
+fn filter_chunks<T: Ord + Copy>(values: &[T], chunk_size: usize) -> Vec<Vec<T>> {
+    let values: Vec<T> = values.iter().copied().collect::<BTreeSet<_>>().into_iter().collect();
+    values.chunks(chunk_size.max(1)).map(<[T]>::to_vec).collect()
+}
+
+/// Groups notes ordered by block number into the notes of each block.
+fn group_notes_by_block(notes: Vec<Note>) -> Vec<(BlockNumber, Vec<Note>)> {
+    let mut blocks: Vec<(BlockNumber, Vec<Note>)> = Vec::new();
+    for note in notes {
+        match blocks.last_mut() {
+            Some((block_num, notes)) if *block_num == note.block_num => notes.push(note),
+            _ => blocks.push((note.block_num, vec![note])),
+        }
+    }
+    blocks
+}
@@ -1,5 +1,18 @@
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    path::{Path, PathBuf},
+};
+
 use miden_lib::transaction::TransactionKernel;
-use miden_node_proto::domain::accounts::{AccountSummary, AccountUpdateDetails};
+use miden_node_proto::domain::{
+    accounts::{AccountLookup, AccountSummary, AccountUpdateDetails},
+    notes::{is_network_note_tag, note_tag_use_case},
+    nullifiers::nullifier_prefix,
+    transactions::{TransactionFee, TransactionReceipt, TransactionSummary},
+};
+use miden_node_utils::config::Endpoint;
 use miden_objects::{
     accounts::{
         Account, AccountCode, AccountDelta, AccountId, AccountStorage, AccountStorageDelta,
@@ -8,18 +21,40 @@ use miden_objects::{
         ACCOUNT_ID_REGULAR_ACCOUNT_IMMUTABLE_CODE_ON_CHAIN,
         ACCOUNT_ID_REGULAR_ACCOUNT_UPDATABLE_CODE_OFF_CHAIN,
     },
-    assembly::{Assembler, ModuleAst},
+    assembly::{Assembler, ModuleAst, ProgramAst},
     assets::{Asset, AssetVault, FungibleAsset, NonFungibleAsset, NonFungibleAssetDetails},
     block::BlockNoteTree,
-    crypto::{hash::rpo::RpoDigest, merkle::MerklePath},
-    notes::{NoteId, NoteMetadata, NoteType, Nullifier},
+    crypto::{
+        dsa::rpo_falcon512::SecretKey,
+        hash::rpo::RpoDigest,
+        merkle::{MerklePath, Mmr},
+    },
+    notes::{
+        Note as NoteObject, NoteAssets, NoteId, NoteInputs, NoteMetadata, NoteRecipient,
+        NoteScript, NoteType, Nullifier,
+    },
     transaction::AccountDetails,
-    BlockHeader, Felt, FieldElement, Word, ONE, ZERO,
+    utils::Serializable,
+    BlockHeader, Felt, FieldElement, StarkField, Word, ONE, ZERO,
+};
+use rusqlite::{params, vtab::array, Connection, ErrorCode};
+use tokio::sync::oneshot;
+
+use super::{
+    sql, state_sync::read_snapshot, views, AccountFilter, AccountInfo, AccountSortOrder, AuxRange,
+    BlockHeaderFields, DatabaseSchema, Db, InterruptOnDrop, Note, NoteConsumption, NoteCreated,
+    NoteFilter, NoteListFilter, NullifierInfo, StateSyncLimits, StateSyncPlan, StateSyncUpdate,
+    StoreInstance, APPLICATION_ID,
+};
+use crate::{
+    config::StoreConfig,
+    db::{build_note_tree, migrations},
+    errors::{DatabaseError, StateInitializationError, StateSyncError},
+    genesis::GenesisState,
+    nullifier_tree::NullifierTree,
+    state::{ApplyOutcome, State},
+    types::{self, BlockNumber},
 };
-use rusqlite::{vtab::array, Connection};
-
-use super::{sql, AccountInfo, Note, NoteCreated, NullifierInfo};
-use crate::db::migrations;
 
 fn create_db() -> Connection {
     let mut conn = Connection::open_in_memory().unwrap();
@@ -28,10 +63,17 @@ fn create_db() -> Connection {
     conn
 }
 
-fn create_block(conn: &mut Connection, block_num: u32) {
+fn tag_filter(tags: &[u32]) -> NoteFilter {
+    NoteFilter {
+        tags: tags.to_vec(),
+        ..Default::default()
+    }
+}
+
+fn create_block(conn: &mut Connection, block_num: BlockNumber) {
     let block_header = BlockHeader::new(
         num_to_rpo_digest(1),
-        block_num,
+        block_num.into(),
         num_to_rpo_digest(3),
         num_to_rpo_digest(4),
         num_to_rpo_digest(5),
@@ -43,17 +85,43 @@ fn create_block(conn: &mut Connection, block_num: u32) {
     );
 
     let transaction = conn.transaction().unwrap();
-    sql::insert_block_header(&transaction, &block_header).unwrap();
+    sql::insert_block_header(&transaction, &block_header, None).unwrap();
     transaction.commit().unwrap();
 }
 
+/// Returns the `notes` with their Merkle paths in the note trees of their blocks, which contain
+/// exactly the `notes`.
+fn with_note_paths(notes: &[Note]) -> Vec<Note> {
+    let mut block_notes: BTreeMap<BlockNumber, Vec<NoteCreated>> = BTreeMap::new();
+    for note in notes {
+        block_notes.entry(note.block_num).or_default().push(note.note_created.clone());
+    }
+    let note_trees: BTreeMap<_, _> = block_notes
+        .into_iter()
+        .map(|(block_num, notes)| (block_num, build_note_tree(&notes).unwrap()))
+        .collect();
+
+    notes
+        .iter()
+        .map(|note| Note {
+            merkle_path: note_trees[&note.block_num]
+                .get_note_path(
+                    note.note_created.batch_index as usize,
+                    note.note_created.note_index as usize,
+                )
+                .unwrap(),
+            ..note.clone()
+        })
+        .collect()
+}
+
 #[test]
 fn test_sql_insert_nullifiers_for_block() {
     let mut conn = create_db();
 
     let nullifiers = [num_to_nullifier(1 << 48)];
 
-    let block_num = 1;
+    let block_num = BlockNumber::new(1);
     create_block(&mut conn, block_num);
 
     // Insert a new nullifier succeeds
@@ -74,7 +142,8 @@ fn test_sql_insert_nullifiers_for_block() {
     // even if the block number is different
     {
         let transaction = conn.transaction().unwrap();
-        let res = sql::insert_nullifiers_for_block(&transaction, &nullifiers, block_num + 1);
+        let res =
+            sql::insert_nullifiers_for_block(&transaction, &nullifiers, block_num.child().unwrap());
         transaction.commit().unwrap();
         assert!(
             res.is_err(),
@@ -85,7 +154,7 @@ fn test_sql_insert_nullifiers_for_block() {
     // test inserting multiple nullifiers
     {
         let nullifiers: Vec<_> = (0..10).map(num_to_nullifier).collect();
-        let block_num = 1;
+        let block_num = BlockNumber::new(1);
         let transaction = conn.transaction().unwrap();
         let res = sql::insert_nullifiers_for_block(&transaction, &nullifiers, block_num);
         transaction.commit().unwrap();
@@ -97,7 +166,7 @@ fn test_sql_insert_nullifiers_for_block() {
 fn test_sql_select_nullifiers() {
     let mut conn = create_db();
 
-    let block_num = 1;
+    let block_num = BlockNumber::new(1);
     create_block(&mut conn, block_num);
 
     // test querying empty table
@@ -119,18 +188,73 @@ fn test_sql_select_nullifiers() {
     }
 }
 
+#[test]
+fn test_sql_select_nullifiers_chunk() {
+    let mut conn = create_db();
+
+    let block_num = BlockNumber::new(1);
+    create_block(&mut conn, block_num);
+
+    let nullifiers: Vec<_> = (0..10).map(num_to_nullifier).collect();
+    let transaction = conn.transaction().unwrap();
+    sql::insert_nullifiers_for_block(&transaction, &nullifiers, block_num).unwrap();
+    transaction.commit().unwrap();
+
+    // loading the nullifiers by chunks yields each of them exactly once
+    let mut loaded = vec![];
+    let mut after = None;
+    loop {
+        let chunk = sql::select_nullifiers_chunk(&mut conn, after, 3).unwrap();
+        assert!(chunk.len() <= 3);
+        if chunk.is_empty() {
+            break;
+        }
+        after = chunk.last().map(|(nullifier, _)| *nullifier);
+        loaded.extend(chunk);
+    }
+
+    let mut expected = sql::select_nullifiers(&mut conn).unwrap();
+    expected.sort_by_key(|(nullifier, _)| nullifier.to_bytes());
+    assert_eq!(loaded, expected);
+    assert_eq!(loaded.len(), nullifiers.len());
+}
+
+#[test]
+fn test_sql_select_nullifier_block_num() {
+    let mut conn = create_db();
+
+    let block_num = BlockNumber::new(1);
+    create_block(&mut conn, block_num);
+
+    let nullifier = num_to_nullifier(1);
+    assert_eq!(sql::select_nullifier_block_num(&mut conn, &nullifier).unwrap(), None);
+
+    let transaction = conn.transaction().unwrap();
+    sql::insert_nullifiers_for_block(&transaction, &[nullifier], block_num).unwrap();
+    transaction.commit().unwrap();
+
+    assert_eq!(sql::select_nullifier_block_num(&mut conn, &nullifier).unwrap(), Some(block_num));
+    assert_eq!(sql::select_nullifier_block_num(&mut conn, &num_to_nullifier(2)).unwrap(), None);
+
+    let row_counts = sql::select_row_counts(&mut conn).unwrap();
+    assert_eq!(row_counts.block_headers, 1);
+    assert_eq!(row_counts.nullifiers, 1);
+    assert_eq!(row_counts.accounts, 0);
+}
+
 #[test]
 fn test_sql_select_notes() {
     let mut conn = create_db();
 
-    let block_num = 1;
+    let block_num = BlockNumber::new(1);
     create_block(&mut conn, block_num);
 
     // test querying empty table
     let notes = sql::select_notes(&mut conn).unwrap();
     assert!(notes.is_empty());
 
-    // test multiple entries
+    // test multiple entries, the paths of the notes change as notes are added to the block
+    let sender = AccountId::new_unchecked(Felt::new(ACCOUNT_ID_OFF_CHAIN_SENDER));
     let mut state = vec![];
     for i in 0..10 {
         let note = Note {
@@ -140,8 +264,9 @@ fn test_sql_select_notes() {
                 note_index: i,
                 note_id: num_to_rpo_digest(i as u64),
                 note_type: NoteType::Public,
-                sender: i as u64,
+                sender: sender.into(),
                 tag: i,
+                aux: 0,
                 details: Some(vec![1, 2, 3]),
             },
             merkle_path: MerklePath::new(vec![]),
@@ -153,19 +278,305 @@ fn test_sql_select_notes() {
         assert_eq!(res.unwrap(), 1, "One element must have been inserted");
         transaction.commit().unwrap();
         let notes = sql::select_notes(&mut conn).unwrap();
-        assert_eq!(notes, state);
+        assert_eq!(notes, with_note_paths(&state));
+    }
+
+    // the pages start after the last note of the previous page
+    let notes = with_note_paths(&state);
+    let page =
+        sql::select_notes_page(&mut conn, &NoteListFilter::default(), None, Some(4)).unwrap();
+    assert_eq!(page, notes[..4]);
+    let page = sql::select_notes_page(
+        &mut conn,
+        &NoteListFilter::default(),
+        Some((block_num, 0, 3)),
+        Some(4),
+    )
+    .unwrap();
+    assert_eq!(page, notes[4..8]);
+    let page = sql::select_notes_page(
+        &mut conn,
+        &NoteListFilter::default(),
+        Some((block_num, 0, 7)),
+        Some(4),
+    )
+    .unwrap();
+    assert_eq!(page, notes[8..]);
+}
+
+#[test]
+fn test_sql_select_block_notes() {
+    let mut conn = create_db();
+
+    let block_num = BlockNumber::new(1);
+    create_block(&mut conn, block_num);
+    create_block(&mut conn, 2.into());
+
+    // Notes of two blocks, inserted out of order
+    let sender = AccountId::new_unchecked(Felt::new(ACCOUNT_ID_OFF_CHAIN_SENDER));
+    let notes: Vec<Note> = [(2, 0, 0), (1, 1, 0), (1, 0, 1), (1, 0, 0)]
+        .into_iter()
+        .map(|(block_num, batch_index, note_index)| Note {
+            block_num: BlockNumber::new(block_num),
+            note_created: NoteCreated {
+                batch_index,
+                note_index,
+                note_id: num_to_rpo_digest(
+                    (block_num * 100 + batch_index * 10 + note_index) as u64,
+                ),
+                note_type: NoteType::Public,
+                sender: sender.into(),
+                tag: note_index,
+                aux: 0,
+                details: Some(vec![1, 2, 3]),
+            },
+            merkle_path: MerklePath::new(vec![]),
+        })
+        .collect();
+
+    let transaction = conn.transaction().unwrap();
+    sql::insert_notes(&transaction, &notes).unwrap();
+    transaction.commit().unwrap();
+
+    // The notes of the block are ordered by position in the note tree, without their details
+    let block_notes = sql::select_block_notes(&conn, block_num).unwrap();
+    let expected: Vec<NoteCreated> = [&notes[3], &notes[2], &notes[1]]
+        .into_iter()
+        .map(|note| NoteCreated {
+            aux: 0,
+            details: None,
+            ..note.note_created.clone()
+        })
+        .collect();
+    assert_eq!(block_notes, expected);
+
+    assert!(sql::select_block_notes(&conn, 3.into()).unwrap().is_empty());
+}
+
+#[test]
+fn test_sql_note_trees() {
+    let mut conn = create_db();
+    for block_num in (1..=3).map(BlockNumber::new) {
+        create_block(&mut conn, block_num);
     }
+
+    // Notes of two blocks, the block 3 has none
+    let sender = AccountId::new_unchecked(Felt::new(ACCOUNT_ID_OFF_CHAIN_SENDER));
+    let notes: Vec<Note> = [(1, 0, 0), (1, 0, 1), (1, 2, 0), (2, 1, 3)]
+        .into_iter()
+        .map(|(block_num, batch_index, note_index)| Note {
+            block_num: BlockNumber::new(block_num),
+            note_created: NoteCreated {
+                batch_index,
+                note_index,
+                note_id: num_to_rpo_digest(
+                    (block_num * 100 + batch_index * 10 + note_index) as u64,
+                ),
+                note_type: NoteType::OffChain,
+                sender: sender.into(),
+                tag: 5,
+                aux: 0,
+                details: None,
+            },
+            merkle_path: MerklePath::new(vec![]),
+        })
+        .collect();
+    let notes = with_note_paths(&notes);
+
+    let transaction = conn.transaction().unwrap();
+    sql::insert_notes(&transaction, &notes).unwrap();
+    transaction.commit().unwrap();
+
+    let note_trees =
+        sql::select_note_trees(&conn, &(1..=3).map(BlockNumber::new).collect()).unwrap();
+    assert_eq!(note_trees.keys().copied().collect::<Vec<_>>(), [1.into(), 2.into()]);
+    assert_eq!(
+        note_trees[&BlockNumber::new(1)],
+        build_note_tree(&sql::select_block_notes(&conn, 1.into()).unwrap()).unwrap()
+    );
+    assert_eq!(sql::select_notes(&mut conn).unwrap(), notes);
+
+    // The trees removed, e.g. stored before the table was added, are backfilled
+    conn.execute("DELETE FROM block_note_trees WHERE block_num = 2;", []).unwrap();
+    assert!(matches!(
+        sql::select_notes(&mut conn),
+        Err(DatabaseError::NoteTreeMissing(block_num)) if block_num == 2.into()
+    ));
+
+    let transaction = conn.transaction().unwrap();
+    assert_eq!(sql::backfill_note_trees(&transaction).unwrap(), 1);
+    assert_eq!(sql::backfill_note_trees(&transaction).unwrap(), 0);
+    transaction.commit().unwrap();
+    assert_eq!(sql::select_notes(&mut conn).unwrap(), notes);
+}
+
+#[test]
+fn test_sql_note_scripts() {
+    let mut conn = create_db();
+
+    let block_num = BlockNumber::new(1);
+    create_block(&mut conn, block_num);
+
+    let sender = AccountId::try_from(ACCOUNT_ID_REGULAR_ACCOUNT_IMMUTABLE_CODE_ON_CHAIN).unwrap();
+    let faucet_id = AccountId::try_from(ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN).unwrap();
+    let (script, _) =
+        NoteScript::new(ProgramAst::parse("begin push.1 drop end").unwrap(), &Assembler::default())
+            .unwrap();
+
+    // Two public notes sharing the same script, and a note with opaque details
+    let details: Vec<Vec<u8>> = (0..2)
+        .map(|i| {
+            let recipient = NoteRecipient::new(
+                num_to_word(i),
+                script.clone(),
+                NoteInputs::new(vec![Felt::new(i)]).unwrap(),
+            );
+            let note = NoteObject::new(
+                NoteAssets::new(vec![FungibleAsset::new(faucet_id, 10 + i).unwrap().into()])
+                    .unwrap(),
+                NoteMetadata::new(sender, NoteType::Public, 0.into(), ZERO).unwrap(),
+                recipient,
+            );
+            note.to_bytes()
+        })
+        .chain([vec![1, 2, 3]])
+        .collect();
+
+    let notes: Vec<Note> = details
+        .into_iter()
+        .enumerate()
+        .map(|(i, details)| Note {
+            block_num,
+            note_created: NoteCreated {
+                batch_index: 0,
+                note_index: i as u32,
+                note_id: num_to_rpo_digest(i as u64),
+                note_type: NoteType::Public,
+                sender: sender.into(),
+                tag: 0,
+                aux: 0,
+                details: Some(details),
+            },
+            merkle_path: MerklePath::new(vec![]),
+        })
+        .collect();
+
+    let transaction = conn.transaction().unwrap();
+    sql::insert_notes(&transaction, &notes).unwrap();
+    transaction.commit().unwrap();
+
+    // The script is stored once, and the full details are returned
+    let num_scripts: u32 = conn
+        .query_row("SELECT COUNT(*) FROM note_scripts;", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(num_scripts, 1);
+    let notes = with_note_paths(&notes);
+    assert_eq!(sql::select_notes(&mut conn).unwrap(), notes);
+    assert_eq!(
+        sql::select_notes_by_id(&mut conn, &[NoteId::from(num_to_rpo_digest(1))]).unwrap(),
+        [notes[1].clone()]
+    );
+
+    assert_eq!(
+        sql::select_note_script(&mut conn, script.hash()).unwrap(),
+        Some(script.to_bytes())
+    );
+    assert_eq!(sql::select_note_script(&mut conn, num_to_rpo_digest(1)).unwrap(), None);
+}
+
+#[test]
+fn test_sql_note_consumptions() {
+    let mut conn = create_db();
+
+    let block_num = BlockNumber::new(1);
+    create_block(&mut conn, block_num);
+    create_block(&mut conn, block_num.child().unwrap());
+
+    let sender = AccountId::try_from(ACCOUNT_ID_REGULAR_ACCOUNT_IMMUTABLE_CODE_ON_CHAIN).unwrap();
+    let faucet_id = AccountId::try_from(ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN).unwrap();
+    let (script, _) =
+        NoteScript::new(ProgramAst::parse("begin push.1 drop end").unwrap(), &Assembler::default())
+            .unwrap();
+    let public_note = NoteObject::new(
+        NoteAssets::new(vec![FungibleAsset::new(faucet_id, 10).unwrap().into()]).unwrap(),
+        NoteMetadata::new(sender, NoteType::Public, 0.into(), ZERO).unwrap(),
+        NoteRecipient::new(num_to_word(1), script, NoteInputs::new(vec![]).unwrap()),
+    );
+
+    // A public note, whose nullifier is known, and a private note
+    let notes: Vec<Note> = [
+        (public_note.id().inner(), NoteType::Public, Some(public_note.to_bytes())),
+        (num_to_rpo_digest(2), NoteType::OffChain, None),
+    ]
+    .into_iter()
+    .enumerate()
+    .map(|(i, (note_id, note_type, details))| Note {
+        block_num,
+        note_created: NoteCreated {
+            batch_index: 0,
+            note_index: i as u32,
+            note_id,
+            note_type,
+            sender: sender.into(),
+            tag: 0,
+            aux: 0,
+            details,
+        },
+        merkle_path: MerklePath::new(vec![]),
+    })
+    .collect();
+    let nullifiers = [public_note.nullifier(), num_to_nullifier(3)];
+    let receipt = TransactionReceipt {
+        transaction: TransactionSummary {
+            id: num_to_rpo_digest(4).into(),
+            account_id: sender,
+            initial_account_hash: num_to_rpo_digest(5),
+            final_account_hash: num_to_rpo_digest(6),
+            output_notes: vec![],
+            input_nullifiers: nullifiers.to_vec(),
+        },
+        block_num: block_num.child().unwrap().into(),
+        batch_index: 0,
+        fee: None,
+        cycles: 0,
+    };
+
+    let transaction = conn.transaction().unwrap();
+    sql::insert_notes(&transaction, &notes).unwrap();
+    sql::insert_nullifiers_for_block(&transaction, &nullifiers, block_num.child().unwrap())
+        .unwrap();
+    // only the public note's consumption is recorded
+    let count = sql::insert_note_consumptions(
+        &transaction,
+        &nullifiers,
+        block_num.child().unwrap(),
+        &[receipt],
+    )
+    .unwrap();
+    assert_eq!(count, 1);
+    transaction.commit().unwrap();
+
+    assert_eq!(
+        sql::select_note_consumption(&mut conn, public_note.id().inner()).unwrap(),
+        Some(NoteConsumption {
+            note_id: public_note.id().inner(),
+            nullifier: public_note.nullifier(),
+            block_num: block_num.child().unwrap(),
+            transaction_id: Some(num_to_rpo_digest(4)),
+        })
+    );
+    assert_eq!(sql::select_note_consumption(&mut conn, num_to_rpo_digest(2)).unwrap(), None);
 }
 
 #[test]
 fn test_sql_select_accounts() {
     let mut conn = create_db();
 
-    let block_num = 1;
+    let block_num = BlockNumber::new(1);
     create_block(&mut conn, block_num);
 
     // test querying empty table
-    let accounts = sql::select_accounts(&mut conn).unwrap();
+    let accounts = sql::select_accounts(&mut conn, &AccountFilter::default()).unwrap();
     assert!(accounts.is_empty());
     // test multiple entries
     let mut state = vec![];
@@ -177,7 +588,8 @@ fn test_sql_select_accounts() {
             summary: AccountSummary {
                 account_id: account_id.try_into().unwrap(),
                 account_hash,
-                block_num,
+                block_num: block_num.into(),
+                nonce: None,
             },
             details: None,
         });
@@ -194,16 +606,173 @@ fn test_sql_select_accounts() {
         );
         assert_eq!(res.unwrap(), 1, "One element must have been inserted");
         transaction.commit().unwrap();
-        let accounts = sql::select_accounts(&mut conn).unwrap();
+        let accounts = sql::select_accounts(&mut conn, &AccountFilter::default()).unwrap();
         assert_eq!(accounts, state);
     }
 }
 
+#[test]
+fn test_sql_select_accounts_filtered() {
+    let mut conn = create_db();
+
+    let account_ids: Vec<AccountId> = [
+        ACCOUNT_ID_REGULAR_ACCOUNT_IMMUTABLE_CODE_ON_CHAIN,
+        ACCOUNT_ID_REGULAR_ACCOUNT_UPDATABLE_CODE_OFF_CHAIN,
+        ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN,
+    ]
+    .into_iter()
+    .map(|id| id.try_into().unwrap())
+    .collect();
+
+    // Each account is updated in its own block
+    for (block_num, account_id) in (1..).map(BlockNumber::new).zip(account_ids.iter()) {
+        create_block(&mut conn, block_num);
+
+        let transaction = conn.transaction().unwrap();
+        sql::upsert_accounts(
+            &transaction,
+            &[AccountUpdateDetails {
+                account_id: *account_id,
+                final_state_hash: num_to_rpo_digest(block_num.as_u32().into()),
+                details: None,
+            }],
+            block_num,
+        )
+        .unwrap();
+        transaction.commit().unwrap();
+    }
+
+    let mut select = |filter: AccountFilter| -> Vec<AccountId> {
+        sql::select_accounts(&mut conn, &filter)
+            .unwrap()
+            .into_iter()
+            .map(|account| account.summary.account_id)
+            .collect()
+    };
+
+    let [regular_on_chain, regular_off_chain, faucet_on_chain] = account_ids[..] else {
+        unreachable!()
+    };
+
+    assert_eq!(select(AccountFilter::default()), account_ids);
+    assert_eq!(
+        select(AccountFilter {
+            is_public: Some(true),
+            ..Default::default()
+        }),
+        vec![regular_on_chain, faucet_on_chain]
+    );
+    assert_eq!(
+        select(AccountFilter {
+            is_public: Some(false),
+            ..Default::default()
+        }),
+        vec![regular_off_chain]
+    );
+    assert_eq!(
+        select(AccountFilter {
+            is_faucet: Some(true),
+            ..Default::default()
+        }),
+        vec![faucet_on_chain]
+    );
+    assert_eq!(
+        select(AccountFilter {
+            is_public: Some(true),
+            is_faucet: Some(false),
+            ..Default::default()
+        }),
+        vec![regular_on_chain]
+    );
+    assert_eq!(
+        select(AccountFilter {
+            updated_after: Some(BlockNumber::new(1)),
+            ..Default::default()
+        }),
+        vec![regular_off_chain, faucet_on_chain]
+    );
+    assert_eq!(
+        select(AccountFilter {
+            sort_order: AccountSortOrder::BlockNumDesc,
+            ..Default::default()
+        }),
+        vec![faucet_on_chain, regular_off_chain, regular_on_chain]
+    );
+
+    let mut sorted_ids = account_ids.clone();
+    sorted_ids.sort_by_key(|id| u64::from(*id));
+    assert_eq!(
+        select(AccountFilter {
+            sort_order: AccountSortOrder::AccountIdAsc,
+            ..Default::default()
+        }),
+        sorted_ids
+    );
+    sorted_ids.reverse();
+    assert_eq!(
+        select(AccountFilter {
+            sort_order: AccountSortOrder::AccountIdDesc,
+            ..Default::default()
+        }),
+        sorted_ids
+    );
+}
+
+#[test]
+fn test_sql_select_accounts_page() {
+    let mut conn = create_db();
+
+    // Two accounts per block, with IDs stored as positive and negative integers
+    let account_ids: Vec<AccountId> = (0..5)
+        .map(|i| ACCOUNT_ID_REGULAR_ACCOUNT_UPDATABLE_CODE_OFF_CHAIN + (i << 32) + 0b1111100000)
+        .chain([ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN])
+        .map(|id| id.try_into().unwrap())
+        .collect();
+    for (block_num, account_ids) in (1..).map(BlockNumber::new).zip(account_ids.chunks(2)) {
+        create_block(&mut conn, block_num);
+
+        let transaction = conn.transaction().unwrap();
+        let updates: Vec<_> = account_ids
+            .iter()
+            .map(|account_id| AccountUpdateDetails {
+                account_id: *account_id,
+                final_state_hash: num_to_rpo_digest(u64::from(*account_id)),
+                details: None,
+            })
+            .collect();
+        sql::upsert_accounts(&transaction, &updates, block_num).unwrap();
+        transaction.commit().unwrap();
+    }
+
+    for sort_order in [
+        AccountSortOrder::BlockNumAsc,
+        AccountSortOrder::BlockNumDesc,
+        AccountSortOrder::AccountIdAsc,
+        AccountSortOrder::AccountIdDesc,
+    ] {
+        let filter = AccountFilter { sort_order, ..Default::default() };
+        let all_accounts = sql::select_accounts(&mut conn, &filter).unwrap();
+        assert_eq!(all_accounts.len(), account_ids.len());
+
+        let mut pages = vec![];
+        let mut after = None;
+        loop {
+            let page = sql::select_accounts_page(&mut conn, &filter, after, Some(4)).unwrap();
+            let Some(last) = page.last() else {
+                break;
+            };
+            after = Some((last.summary.block_num.into(), last.summary.account_id.into()));
+            pages.extend(page);
+        }
+        assert_eq!(pages, all_accounts, "{sort_order:?}");
+    }
+}
+
 #[test]
 fn test_sql_public_account_details() {
     let mut conn = create_db();
 
-    let block_num = 1;
+    let block_num = BlockNumber::new(1);
     create_block(&mut conn, block_num);
 
     let account_id =
@@ -237,7 +806,7 @@ fn test_sql_public_account_details() {
     );
 
     // test querying empty table
-    let accounts_in_db = sql::select_accounts(&mut conn).unwrap();
+    let accounts_in_db = sql::select_accounts(&mut conn, &AccountFilter::default()).unwrap();
     assert!(accounts_in_db.is_empty());
 
     let transaction = conn.transaction().unwrap();
@@ -256,13 +825,30 @@ fn test_sql_public_account_details() {
 
     transaction.commit().unwrap();
 
-    let mut accounts_in_db = sql::select_accounts(&mut conn).unwrap();
+    let mut accounts_in_db = sql::select_accounts(&mut conn, &AccountFilter::default()).unwrap();
 
     assert_eq!(accounts_in_db.len(), 1, "One element must have been inserted");
 
     let account_read = accounts_in_db.pop().unwrap().details.unwrap();
     assert_eq!(account_read, account);
 
+    // the state sync only loads the details when requested
+    let sync_details = |conn: &mut Connection, include_details| {
+        sql::select_accounts_by_block_range(
+            conn,
+            BlockNumber::GENESIS,
+            block_num,
+            &[account_id.into()],
+            include_details,
+        )
+        .unwrap()
+        .pop()
+        .unwrap()
+        .details
+    };
+    assert_eq!(sync_details(&mut conn, false), None);
+    assert_eq!(sync_details(&mut conn, true), Some(account.clone()));
+
     let storage_delta = AccountStorageDelta {
         cleared_items: vec![3],
         updated_items: vec![(4, num_to_word(5)), (5, num_to_word(6))],
@@ -300,12 +886,30 @@ fn test_sql_public_account_details() {
 
     transaction.commit().unwrap();
 
-    let mut accounts_in_db = sql::select_accounts(&mut conn).unwrap();
+    let mut accounts_in_db = sql::select_accounts(&mut conn, &AccountFilter::default()).unwrap();
 
     assert_eq!(accounts_in_db.len(), 1, "One element must have been inserted");
 
     let mut account_read = accounts_in_db.pop().unwrap().details.unwrap();
 
+    // only the delta updates are stored as deltas
+    assert_eq!(sql::select_account_deltas_start(&mut conn).unwrap(), BlockNumber::GENESIS);
+    let deltas = sql::select_account_deltas(
+        &mut conn,
+        account_id.into(),
+        BlockNumber::GENESIS,
+        block_num,
+        10,
+    )
+    .unwrap();
+    assert_eq!(deltas.len(), 1);
+    assert_eq!(deltas[0].block_num, block_num);
+    assert_eq!(deltas[0].delta, delta.to_bytes());
+    assert_eq!(deltas[0].account_hash, account.hash());
+    let deltas =
+        sql::select_account_deltas(&mut conn, account_id.into(), block_num, block_num, 10).unwrap();
+    assert!(deltas.is_empty());
+
     assert_eq!(account_read.id(), account.id());
     assert_eq!(account_read.vault(), account.vault());
     assert_eq!(account_read.nonce(), account.nonce());
@@ -328,108 +932,419 @@ fn test_sql_public_account_details() {
 }
 
 #[test]
-fn test_sql_select_nullifiers_by_block_range() {
+fn test_sql_account_nonces() {
     let mut conn = create_db();
 
-    // test empty table
-    let nullifiers = sql::select_nullifiers_by_block_range(&mut conn, 0, u32::MAX, &[]).unwrap();
-    assert!(nullifiers.is_empty());
+    let block_num = BlockNumber::new(1);
+    create_block(&mut conn, block_num);
 
-    // test single item
-    let nullifier1 = num_to_nullifier(1 << 48);
-    let block_number1 = 1;
-    create_block(&mut conn, block_number1);
+    let account_id =
+        AccountId::try_from(ACCOUNT_ID_REGULAR_ACCOUNT_IMMUTABLE_CODE_ON_CHAIN).unwrap();
+    let account = Account::new(
+        account_id,
+        AssetVault::default(),
+        AccountStorage::new(vec![]).unwrap(),
+        mock_account_code(&TransactionKernel::assembler()),
+        ONE,
+    );
+    let update = AccountUpdateDetails {
+        account_id,
+        final_state_hash: account.hash(),
+        details: Some(AccountDetails::Full(account.clone())),
+    };
 
     let transaction = conn.transaction().unwrap();
-    sql::insert_nullifiers_for_block(&transaction, &[nullifier1], block_number1).unwrap();
+    sql::upsert_accounts(&transaction, &[update.clone()], block_num).unwrap();
     transaction.commit().unwrap();
 
-    let nullifiers = sql::select_nullifiers_by_block_range(
-        &mut conn,
-        0,
-        u32::MAX,
-        &[sql::get_nullifier_prefix(&nullifier1)],
-    )
-    .unwrap();
-    assert_eq!(
-        nullifiers,
-        vec![NullifierInfo {
-            nullifier: nullifier1,
-            block_num: block_number1
-        }]
-    );
-
-    // test two elements
-    let nullifier2 = num_to_nullifier(2 << 48);
-    let block_number2 = 2;
-    create_block(&mut conn, block_number2);
+    let summary = sql::select_account(&mut conn, account_id.into())
+        .unwrap()
+        .into_info()
+        .unwrap()
+        .summary;
+    assert_eq!(summary.nonce, Some(1));
 
+    // an update which doesn't increase the nonce is rejected
     let transaction = conn.transaction().unwrap();
-    sql::insert_nullifiers_for_block(&transaction, &[nullifier2], block_number2).unwrap();
+    let result = sql::upsert_accounts(&transaction, &[update], block_num);
+    assert!(matches!(
+        result,
+        Err(DatabaseError::AccountNonceNotIncreasing { previous_nonce: 1, nonce: 1, .. })
+    ));
+    drop(transaction);
+
+    // the nonces of the accounts stored before the column was added are backfilled
+    conn.execute("UPDATE accounts SET nonce = NULL;", []).unwrap();
+    let transaction = conn.transaction().unwrap();
+    assert_eq!(sql::backfill_account_nonces(&transaction).unwrap(), 1);
+    assert_eq!(sql::backfill_account_nonces(&transaction).unwrap(), 0);
     transaction.commit().unwrap();
 
-    let nullifiers = sql::select_nullifiers(&mut conn).unwrap();
-    assert_eq!(nullifiers, vec![(nullifier1, block_number1), (nullifier2, block_number2)]);
+    let summary = sql::select_account(&mut conn, account_id.into())
+        .unwrap()
+        .into_info()
+        .unwrap()
+        .summary;
+    assert_eq!(summary.nonce, Some(1));
+}
 
-    // only the nullifiers matching the prefix are included
-    let nullifiers = sql::select_nullifiers_by_block_range(
-        &mut conn,
-        0,
-        u32::MAX,
-        &[sql::get_nullifier_prefix(&nullifier1)],
+#[test]
+fn test_sql_account_keys() {
+    let mut conn = create_db();
+
+    let block_num = BlockNumber::new(1);
+    create_block(&mut conn, block_num);
+
+    let public_key = num_to_rpo_digest(7);
+    let rotated_key = num_to_rpo_digest(8);
+
+    let accounts: Vec<Account> = [
+        ACCOUNT_ID_REGULAR_ACCOUNT_IMMUTABLE_CODE_ON_CHAIN,
+        ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN,
+    ]
+    .into_iter()
+    .map(|account_id| {
+        let mut storage = AccountStorage::new(vec![]).unwrap();
+        storage.set_item(0, public_key.into()).unwrap();
+
+        Account::new(
+            AccountId::try_from(account_id).unwrap(),
+            AssetVault::default(),
+            storage,
+            mock_account_code(&TransactionKernel::assembler()),
+            ZERO,
+        )
+    })
+    .collect();
+    let account_ids: Vec<types::AccountId> =
+        accounts.iter().map(|account| account.id().into()).collect();
+
+    let transaction = conn.transaction().unwrap();
+    sql::upsert_accounts(
+        &transaction,
+        &accounts
+            .iter()
+            .map(|account| AccountUpdateDetails {
+                account_id: account.id(),
+                final_state_hash: account.hash(),
+                details: Some(AccountDetails::Full(account.clone())),
+            })
+            .collect::<Vec<_>>(),
+        block_num,
     )
     .unwrap();
-    assert_eq!(
-        nullifiers,
+    assert!(sql::is_account_keys_empty(&transaction).unwrap());
+    assert_eq!(sql::upsert_account_keys(&transaction, &account_ids).unwrap(), 2);
+    transaction.commit().unwrap();
+
+    assert_eq!(sql::select_accounts_by_public_key(&mut conn, public_key).unwrap(), account_ids);
+    assert!(sql::select_accounts_by_public_key(&mut conn, rotated_key).unwrap().is_empty());
+
+    // rotating the key of one account moves it in the index
+    let delta = AccountDelta::new(
+        AccountStorageDelta {
+            cleared_items: vec![],
+            updated_items: vec![(0, rotated_key.into())],
+        },
+        AccountVaultDelta::default(),
+        Some(ONE),
+    )
+    .unwrap();
+    let mut account = accounts[0].clone();
+    account.apply_delta(&delta).unwrap();
+
+    let transaction = conn.transaction().unwrap();
+    sql::upsert_accounts(
+        &transaction,
+        &[AccountUpdateDetails {
+            account_id: account.id(),
+            final_state_hash: account.hash(),
+            details: Some(AccountDetails::Delta(delta)),
+        }],
+        block_num,
+    )
+    .unwrap();
+    sql::upsert_account_keys(&transaction, &[account_ids[0]]).unwrap();
+    transaction.commit().unwrap();
+
+    assert_eq!(
+        sql::select_accounts_by_public_key(&mut conn, public_key).unwrap(),
+        [account_ids[1]]
+    );
+    assert_eq!(
+        sql::select_accounts_by_public_key(&mut conn, rotated_key).unwrap(),
+        [account_ids[0]]
+    );
+
+    // the index can be rebuilt from the account details
+    let transaction = conn.transaction().unwrap();
+    sql::clear_account_keys(&transaction).unwrap();
+    assert!(sql::is_account_keys_empty(&transaction).unwrap());
+    assert_eq!(sql::rebuild_account_keys(&transaction).unwrap(), 2);
+    transaction.commit().unwrap();
+
+    assert_eq!(
+        sql::select_accounts_by_public_key(&mut conn, public_key).unwrap(),
+        [account_ids[1]]
+    );
+    assert_eq!(
+        sql::select_accounts_by_public_key(&mut conn, rotated_key).unwrap(),
+        [account_ids[0]]
+    );
+}
+
+#[test]
+fn test_sql_select_account_lookup() {
+    let mut conn = create_db();
+
+    let block_num = BlockNumber::new(1);
+    create_block(&mut conn, block_num);
+
+    let private_id =
+        AccountId::try_from(ACCOUNT_ID_REGULAR_ACCOUNT_UPDATABLE_CODE_OFF_CHAIN).unwrap();
+    let public_id =
+        AccountId::try_from(ACCOUNT_ID_REGULAR_ACCOUNT_IMMUTABLE_CODE_ON_CHAIN).unwrap();
+    let account = Account::new(
+        public_id,
+        AssetVault::new(&[]).unwrap(),
+        AccountStorage::new(vec![]).unwrap(),
+        mock_account_code(&TransactionKernel::assembler()),
+        ZERO,
+    );
+
+    // accounts which were never registered are not found
+    let lookup = sql::select_account(&mut conn, private_id.into()).unwrap();
+    assert_eq!(lookup, AccountLookup::NotFound);
+
+    let transaction = conn.transaction().unwrap();
+    sql::upsert_accounts(
+        &transaction,
+        &[
+            AccountUpdateDetails {
+                account_id: private_id,
+                final_state_hash: num_to_rpo_digest(1),
+                details: None,
+            },
+            AccountUpdateDetails {
+                account_id: public_id,
+                final_state_hash: account.hash(),
+                details: Some(AccountDetails::Full(account.clone())),
+            },
+        ],
+        block_num,
+    )
+    .unwrap();
+    transaction.commit().unwrap();
+
+    // registered private accounts only have their hash stored
+    let lookup = sql::select_account(&mut conn, private_id.into()).unwrap();
+    let summary = AccountSummary {
+        account_id: private_id,
+        account_hash: num_to_rpo_digest(1),
+        block_num: block_num.into(),
+        nonce: None,
+    };
+    assert_eq!(lookup, AccountLookup::PrivateOnChain { summary });
+
+    // public accounts have their details stored
+    let lookup = sql::select_account(&mut conn, public_id.into()).unwrap();
+    let summary = AccountSummary {
+        account_id: public_id,
+        account_hash: account.hash(),
+        block_num: block_num.into(),
+        nonce: Some(account.nonce().as_int()),
+    };
+    assert_eq!(lookup, AccountLookup::Public { summary, account });
+}
+
+#[test]
+fn test_sql_backfill_block_header_columns() {
+    let mut conn = create_db();
+    create_block(&mut conn, BlockNumber::new(1));
+    create_block(&mut conn, BlockNumber::new(2));
+    create_block(&mut conn, BlockNumber::new(3));
+
+    // The blocks inserted before the timestamp and root columns were added have none of them
+    conn.execute("UPDATE block_headers SET timestamp = NULL WHERE block_num = 1;", [])
+        .unwrap();
+    conn.execute(
+        "UPDATE block_headers SET note_root = NULL, nullifier_root = NULL, account_root = NULL \
+         WHERE block_num <= 2;",
+        [],
+    )
+    .unwrap();
+
+    let transaction = conn.transaction().unwrap();
+    assert_eq!(sql::backfill_block_header_columns(&transaction).unwrap(), 2);
+    transaction.commit().unwrap();
+
+    let timestamps = sql::select_block_timestamps(
+        &mut conn,
+        &[BlockNumber::new(1), BlockNumber::new(2), BlockNumber::new(4)],
+    )
+    .unwrap();
+    assert_eq!(
+        timestamps,
+        BTreeMap::from([(BlockNumber::new(1), 10), (BlockNumber::new(2), 10)])
+    );
+
+    let fields = sql::select_block_header_fields(&mut conn, BlockNumber::new(1), 10).unwrap();
+    assert_eq!(fields.len(), 3);
+    assert!(fields.iter().all(|fields| fields.note_root == num_to_rpo_digest(6)));
+}
+
+#[test]
+fn test_sql_select_block_header_fields() {
+    let mut conn = create_db();
+    for block_num in 1..=4 {
+        create_block(&mut conn, BlockNumber::new(block_num));
+    }
+
+    let fields = sql::select_block_header_fields(&mut conn, BlockNumber::new(2), 2).unwrap();
+    let block_header = sql::select_block_header_by_block_num(&mut conn, Some(2.into()))
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        fields.iter().map(|fields| fields.block_num).collect::<Vec<_>>(),
+        [BlockNumber::new(2), BlockNumber::new(3)]
+    );
+    assert_eq!(
+        fields[0],
+        BlockHeaderFields {
+            block_num: BlockNumber::new(2),
+            timestamp: block_header.timestamp().as_int(),
+            note_root: block_header.note_root(),
+            nullifier_root: block_header.nullifier_root(),
+            account_root: block_header.account_root(),
+        }
+    );
+
+    // past the chain tip
+    assert!(sql::select_block_header_fields(&mut conn, BlockNumber::new(5), 2)
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn test_sql_select_nullifiers_by_block_range() {
+    let mut conn = create_db();
+
+    // test empty table
+    let nullifiers = sql::select_nullifiers_by_block_range(
+        &mut conn,
+        BlockNumber::GENESIS,
+        BlockNumber::MAX,
+        16,
+        &[],
+    )
+    .unwrap();
+    assert!(nullifiers.is_empty());
+
+    // test single item
+    let nullifier1 = num_to_nullifier(1 << 48);
+    let block_number1 = BlockNumber::new(1);
+    create_block(&mut conn, block_number1);
+
+    let transaction = conn.transaction().unwrap();
+    sql::insert_nullifiers_for_block(&transaction, &[nullifier1], block_number1).unwrap();
+    transaction.commit().unwrap();
+
+    let nullifiers = sql::select_nullifiers_by_block_range(
+        &mut conn,
+        BlockNumber::GENESIS,
+        BlockNumber::MAX,
+        16,
+        &[nullifier_prefix(&nullifier1)],
+    )
+    .unwrap();
+    assert_eq!(
+        nullifiers,
+        vec![NullifierInfo {
+            nullifier: nullifier1,
+            block_num: block_number1,
+            block_timestamp: 10,
+        }]
+    );
+
+    // test two elements
+    let nullifier2 = num_to_nullifier(2 << 48);
+    let block_number2 = BlockNumber::new(2);
+    create_block(&mut conn, block_number2);
+
+    let transaction = conn.transaction().unwrap();
+    sql::insert_nullifiers_for_block(&transaction, &[nullifier2], block_number2).unwrap();
+    transaction.commit().unwrap();
+
+    let nullifiers = sql::select_nullifiers(&mut conn).unwrap();
+    assert_eq!(nullifiers, vec![(nullifier1, block_number1), (nullifier2, block_number2)]);
+
+    // only the nullifiers matching the prefix are included
+    let nullifiers = sql::select_nullifiers_by_block_range(
+        &mut conn,
+        BlockNumber::GENESIS,
+        BlockNumber::MAX,
+        16,
+        &[nullifier_prefix(&nullifier1)],
+    )
+    .unwrap();
+    assert_eq!(
+        nullifiers,
         vec![NullifierInfo {
             nullifier: nullifier1,
-            block_num: block_number1
+            block_num: block_number1,
+            block_timestamp: 10,
         }]
     );
     let nullifiers = sql::select_nullifiers_by_block_range(
         &mut conn,
-        0,
-        u32::MAX,
-        &[sql::get_nullifier_prefix(&nullifier2)],
+        BlockNumber::GENESIS,
+        BlockNumber::MAX,
+        16,
+        &[nullifier_prefix(&nullifier2)],
     )
     .unwrap();
     assert_eq!(
         nullifiers,
         vec![NullifierInfo {
             nullifier: nullifier2,
-            block_num: block_number2
+            block_num: block_number2,
+            block_timestamp: 10,
         }]
     );
 
     // Nullifiers created at block_end are included
     let nullifiers = sql::select_nullifiers_by_block_range(
         &mut conn,
-        0,
-        1,
-        &[sql::get_nullifier_prefix(&nullifier1), sql::get_nullifier_prefix(&nullifier2)],
+        BlockNumber::GENESIS,
+        BlockNumber::new(1),
+        16,
+        &[nullifier_prefix(&nullifier1), nullifier_prefix(&nullifier2)],
     )
     .unwrap();
     assert_eq!(
         nullifiers,
         vec![NullifierInfo {
             nullifier: nullifier1,
-            block_num: block_number1
+            block_num: block_number1,
+            block_timestamp: 10,
         }]
     );
 
     // Nullifiers created at block_start are not included
     let nullifiers = sql::select_nullifiers_by_block_range(
         &mut conn,
-        1,
-        u32::MAX,
-        &[sql::get_nullifier_prefix(&nullifier1), sql::get_nullifier_prefix(&nullifier2)],
+        BlockNumber::new(1),
+        BlockNumber::MAX,
+        16,
+        &[nullifier_prefix(&nullifier1), nullifier_prefix(&nullifier2)],
     )
     .unwrap();
     assert_eq!(
         nullifiers,
         vec![NullifierInfo {
             nullifier: nullifier2,
-            block_num: block_number2
+            block_num: block_number2,
+            block_timestamp: 10,
         }]
     );
 
@@ -437,27 +1352,294 @@ fn test_sql_select_nullifiers_by_block_range() {
     // when the client requests a sync update, and it is already tracking the chain tip.
     let nullifiers = sql::select_nullifiers_by_block_range(
         &mut conn,
-        2,
-        2,
-        &[sql::get_nullifier_prefix(&nullifier1), sql::get_nullifier_prefix(&nullifier2)],
+        BlockNumber::new(2),
+        BlockNumber::new(2),
+        16,
+        &[nullifier_prefix(&nullifier1), nullifier_prefix(&nullifier2)],
+    )
+    .unwrap();
+    assert!(nullifiers.is_empty());
+}
+
+#[test]
+fn test_sql_nullifier_partitions() {
+    let mut conn = create_db();
+
+    // the nullifiers of the first block of the second epoch sort before the ones of the first
+    let epoch_start = BlockNumber::new(sql::NULLIFIER_EPOCH_BLOCKS);
+    let blocks = [BlockNumber::new(1), epoch_start, epoch_start.child().unwrap()];
+    let nullifiers =
+        [num_to_nullifier(3 << 48), num_to_nullifier(1 << 48), num_to_nullifier(2 << 48)];
+    for (block_num, nullifier) in blocks.iter().zip(&nullifiers) {
+        create_block(&mut conn, *block_num);
+        let transaction = conn.transaction().unwrap();
+        sql::insert_nullifiers_for_block(&transaction, &[*nullifier], *block_num).unwrap();
+        transaction.commit().unwrap();
+    }
+
+    let partitions: Vec<String> = conn
+        .prepare("SELECT name FROM sqlite_master WHERE type = 'table' AND name LIKE 'nullifiers_%' ORDER BY name;")
+        .unwrap()
+        .query_map([], |row| row.get(0))
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(partitions, ["nullifiers_0", "nullifiers_1"]);
+
+    let prefixes: Vec<u32> = nullifiers.iter().map(nullifier_prefix).collect();
+    let infos: Vec<(Nullifier, BlockNumber)> = sql::select_nullifiers_by_block_range(
+        &mut conn,
+        BlockNumber::GENESIS,
+        BlockNumber::MAX,
+        16,
+        &prefixes,
+    )
+    .unwrap()
+    .into_iter()
+    .map(|info| (info.nullifier, info.block_num))
+    .collect();
+    assert_eq!(infos, blocks.iter().zip(&nullifiers).map(|(b, n)| (*n, *b)).collect::<Vec<_>>());
+
+    // a range within the second epoch
+    let infos = sql::select_nullifiers_by_block_range(
+        &mut conn,
+        epoch_start,
+        BlockNumber::MAX,
+        16,
+        &prefixes,
+    )
+    .unwrap();
+    assert_eq!(infos.len(), 1);
+    assert_eq!(infos[0].nullifier, nullifiers[2]);
+
+    // the pages carry over from a partition to the next
+    let (page, last_block) = sql::select_nullifiers_page_by_block_range(
+        &mut conn,
+        BlockNumber::GENESIS,
+        BlockNumber::MAX,
+        16,
+        &prefixes,
+        1,
+    )
+    .unwrap();
+    assert_eq!(page.len(), 1);
+    assert_eq!(last_block, Some(blocks[0]));
+    let (page, last_block) = sql::select_nullifiers_page_by_block_range(
+        &mut conn,
+        blocks[0],
+        BlockNumber::MAX,
+        16,
+        &prefixes,
+        1,
+    )
+    .unwrap();
+    assert_eq!(page[0].nullifier, nullifiers[1]);
+    assert_eq!(last_block, Some(blocks[1]));
+
+    // the chunks are merged across the partitions
+    let chunk = sql::select_nullifiers_chunk(&mut conn, None, 2).unwrap();
+    assert_eq!(chunk, vec![(nullifiers[1], blocks[1]), (nullifiers[2], blocks[2])]);
+    let chunk = sql::select_nullifiers_chunk(&mut conn, Some(nullifiers[2]), 2).unwrap();
+    assert_eq!(chunk, vec![(nullifiers[0], blocks[0])]);
+
+    // the chunks of the partitions follow the epochs, and carry over from a partition to the next
+    let chunk = sql::select_nullifiers_by_partition_chunk(&mut conn, None, 2).unwrap();
+    assert_eq!(chunk, vec![(nullifiers[0], blocks[0]), (nullifiers[1], blocks[1])]);
+    let chunk =
+        sql::select_nullifiers_by_partition_chunk(&mut conn, Some((1, nullifiers[1])), 2).unwrap();
+    assert_eq!(chunk, vec![(nullifiers[2], blocks[2])]);
+    let chunk =
+        sql::select_nullifiers_by_partition_chunk(&mut conn, Some((1, nullifiers[2])), 2).unwrap();
+    assert!(chunk.is_empty());
+
+    assert_eq!(
+        sql::select_nullifier_block_num(&mut conn, &nullifiers[0]).unwrap(),
+        Some(blocks[0])
+    );
+    assert_eq!(
+        sql::select_nullifier_block_num(&mut conn, &nullifiers[2]).unwrap(),
+        Some(blocks[2])
+    );
+    assert_eq!(sql::select_nullifier_block_num(&mut conn, &num_to_nullifier(4)).unwrap(), None);
+
+    // the partitions are accounted as the `nullifiers` table
+    assert_eq!(sql::select_row_counts(&mut conn).unwrap().nullifiers, 3);
+    let size = sql::select_database_size(&mut conn).unwrap();
+    assert!(size.tables.contains_key("nullifiers"));
+    assert!(!size.tables.keys().any(|table| table.starts_with("nullifiers_")));
+}
+
+#[tokio::test]
+async fn test_pruned_nullifiers_prevent_the_startup() {
+    let directory = TestDirectory::new("pruning");
+    let config = StoreConfig::new(
+        Endpoint { host: "127.0.0.1".to_string(), port: 0 },
+        directory.path().join("miden-store.sqlite3"),
+        directory.path().join("genesis.dat"),
+    );
+    let genesis = GenesisState::new(Vec::new(), Vec::new(), 1, 1);
+    fs::write(&config.genesis_filepath, genesis.to_bytes()).unwrap();
+
+    let db = Db::setup(config.clone()).await.unwrap();
+    let state = State::load(db, &config.cache).await.unwrap();
+
+    // an empty block consuming a single nullifier
+    let genesis_header = state.get_block_header(None).await.unwrap().unwrap();
+    let block_num = BlockNumber::new(1);
+    let nullifier = num_to_nullifier(1);
+    let nullifier_tree = NullifierTree::with_entries([(nullifier, block_num)]).unwrap();
+    let block_header = BlockHeader::new(
+        genesis_header.hash(),
+        block_num.as_u32(),
+        Mmr::from([genesis_header.hash()]).peaks(1).unwrap().hash_peaks(),
+        genesis_header.account_root(),
+        nullifier_tree.root(),
+        build_note_tree(&[]).unwrap().root(),
+        RpoDigest::default(),
+        RpoDigest::default(),
+        ZERO,
+        ONE,
+    );
+    let outcome = state
+        .apply_block(
+            block_header,
+            None,
+            Vec::new(),
+            None,
+            vec![nullifier],
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(outcome, ApplyOutcome::Applied);
+    drop(state);
+
+    // the state is loaded again as long as the nullifiers are kept
+    let db = Db::setup(config.clone()).await.unwrap();
+    drop(State::load(db, &config.cache).await.unwrap());
+
+    // pruning the partition of the first epoch
+    Connection::open(&config.database_filepath)
+        .unwrap()
+        .execute_batch("DELETE FROM nullifiers_0;")
+        .unwrap();
+
+    let db = Db::setup(config.clone()).await.unwrap();
+    match State::load(db, &config.cache).await {
+        Err(StateInitializationError::TreeRootMismatch { tree, block_num, .. }) => {
+            assert_eq!(tree, "nullifier");
+            assert_eq!(block_num, BlockNumber::new(1));
+        },
+        other => panic!("the state was loaded without the pruned nullifiers: {:?}", other.err()),
+    }
+}
+
+#[test]
+fn test_sql_select_nullifiers_by_prefix_len() {
+    let mut conn = create_db();
+
+    let nullifier1 = num_to_nullifier(0x0102_0003 << 32);
+    let nullifier2 = num_to_nullifier(0x0102_0004 << 32);
+    let nullifier3 = num_to_nullifier(0x0105_0000 << 32);
+    create_block(&mut conn, BlockNumber::new(1));
+
+    let transaction = conn.transaction().unwrap();
+    sql::insert_nullifiers_for_block(&transaction, &[nullifier1, nullifier2, nullifier3], 1.into())
+        .unwrap();
+    transaction.commit().unwrap();
+
+    let select = |conn: &mut Connection, prefix_len, prefix| {
+        sql::select_nullifiers_by_prefix(conn, prefix_len, &[prefix])
+            .unwrap()
+            .into_iter()
+            .map(|info| info.nullifier)
+            .collect::<BTreeSet<_>>()
+    };
+
+    // prefixes shorter than the indexed column
+    assert_eq!(select(&mut conn, 8, 0x01), BTreeSet::from([nullifier1, nullifier2, nullifier3]));
+    assert_eq!(
+        select(&mut conn, 12, 0x010),
+        BTreeSet::from([nullifier1, nullifier2, nullifier3])
+    );
+    assert!(select(&mut conn, 8, 0x02).is_empty());
+
+    // prefixes as long as the indexed column
+    assert_eq!(select(&mut conn, 16, 0x0102), BTreeSet::from([nullifier1, nullifier2]));
+
+    // prefixes longer than the indexed column
+    assert_eq!(select(&mut conn, 24, 0x01_0200), BTreeSet::from([nullifier1, nullifier2]));
+    assert_eq!(select(&mut conn, 32, 0x0102_0003), BTreeSet::from([nullifier1]));
+    assert!(select(&mut conn, 32, 0x0102_0005).is_empty());
+
+    // the block range is applied on top of the prefix
+    let nullifiers = sql::select_nullifiers_by_block_range(
+        &mut conn,
+        BlockNumber::new(1),
+        BlockNumber::new(2),
+        8,
+        &[0x01],
     )
     .unwrap();
     assert!(nullifiers.is_empty());
 }
 
+#[test]
+fn test_sql_select_nullifiers_page_by_block_range() {
+    let mut conn = create_db();
+    for block_num in (1..=4).map(BlockNumber::new) {
+        create_block(&mut conn, block_num);
+    }
+
+    // blocks 1 and 3 consume two matching nullifiers, block 2 one and block 4 none
+    let transaction = conn.transaction().unwrap();
+    for (block_num, values) in [(1, vec![1, 2]), (2, vec![3]), (3, vec![4, 5]), (4, vec![])] {
+        let nullifiers: Vec<_> = values.into_iter().map(|n| num_to_nullifier(n << 48)).collect();
+        sql::insert_nullifiers_for_block(&transaction, &nullifiers, BlockNumber::new(block_num))
+            .unwrap();
+    }
+    transaction.commit().unwrap();
+
+    let page = |conn: &mut Connection, block_start, max_nullifiers| {
+        let (nullifiers, last_block) = sql::select_nullifiers_page_by_block_range(
+            conn,
+            BlockNumber::new(block_start),
+            BlockNumber::new(4),
+            16,
+            &[1, 2, 3, 4, 5],
+            max_nullifiers,
+        )
+        .unwrap();
+        let blocks: Vec<u32> =
+            nullifiers.iter().map(|nullifier| nullifier.block_num.as_u32()).collect();
+        (blocks, last_block.map(BlockNumber::as_u32))
+    };
+
+    // the pages end with complete blocks, even if it exceeds the maximum
+    assert_eq!(page(&mut conn, 0, 2), (vec![1, 1], Some(1)));
+    assert_eq!(page(&mut conn, 0, 1), (vec![1, 1], Some(1)));
+    assert_eq!(page(&mut conn, 0, 3), (vec![1, 1, 2], Some(2)));
+    assert_eq!(page(&mut conn, 2, 2), (vec![3, 3], None));
+    // the last page covers the range
+    assert_eq!(page(&mut conn, 0, 10), (vec![1, 1, 2, 3, 3], None));
+    assert_eq!(page(&mut conn, 4, 10), (vec![], None));
+}
+
 #[test]
 fn test_db_block_header() {
     let mut conn = create_db();
 
     // test querying empty table
-    let block_number = 1;
+    let block_number = BlockNumber::new(1);
     let res = sql::select_block_header_by_block_num(&mut conn, Some(block_number)).unwrap();
     assert!(res.is_none());
 
     let res = sql::select_block_header_by_block_num(&mut conn, None).unwrap();
     assert!(res.is_none());
 
-    let res = sql::select_block_headers(&mut conn).unwrap();
+    let res = sql::select_block_headers_chunk(&mut conn, None, 10).unwrap();
     assert!(res.is_empty());
 
     let block_header = BlockHeader::new(
@@ -475,17 +1657,18 @@ fn test_db_block_header() {
 
     // test insertion
     let transaction = conn.transaction().unwrap();
-    sql::insert_block_header(&transaction, &block_header).unwrap();
+    sql::insert_block_header(&transaction, &block_header, None).unwrap();
     transaction.commit().unwrap();
 
     // test fetch unknown block header
-    let block_number = 1;
+    let block_number = BlockNumber::new(1);
     let res = sql::select_block_header_by_block_num(&mut conn, Some(block_number)).unwrap();
     assert!(res.is_none());
 
     // test fetch block header by block number
     let res =
-        sql::select_block_header_by_block_num(&mut conn, Some(block_header.block_num())).unwrap();
+        sql::select_block_header_by_block_num(&mut conn, Some(block_header.block_num().into()))
+            .unwrap();
     assert_eq!(res.unwrap(), block_header);
 
     // test fetch latest block header
@@ -505,27 +1688,50 @@ fn test_db_block_header() {
         20_u8.into(),
     );
 
+    let signature = SecretKey::new().sign(block_header2.hash().into());
+
     let transaction = conn.transaction().unwrap();
-    sql::insert_block_header(&transaction, &block_header2).unwrap();
+    sql::insert_block_header(&transaction, &block_header2, Some(&signature)).unwrap();
     transaction.commit().unwrap();
 
     let res = sql::select_block_header_by_block_num(&mut conn, None).unwrap();
     assert_eq!(res.unwrap(), block_header2);
 
-    let res = sql::select_block_headers(&mut conn).unwrap();
+    // test fetch block signatures, the first block is not signed
+    let res = sql::select_block_signature(&mut conn, block_header.block_num().into()).unwrap();
+    assert!(res.is_none());
+    let res = sql::select_block_signature(&mut conn, block_header2.block_num().into()).unwrap();
+    assert_eq!(res, Some(signature));
+
+    let res = sql::select_block_headers_chunk(&mut conn, None, 10).unwrap();
     assert_eq!(res, [block_header, block_header2]);
+
+    // the chunks start after the given block
+    let res = sql::select_block_headers_chunk(&mut conn, None, 1).unwrap();
+    assert_eq!(res, [block_header]);
+    let res = sql::select_block_headers_chunk(&mut conn, Some(block_header.block_num().into()), 10)
+        .unwrap();
+    assert_eq!(res, [block_header2]);
 }
 
 #[test]
 fn test_db_account() {
     let mut conn = create_db();
 
-    let block_num = 1;
+    let block_num = BlockNumber::new(1);
     create_block(&mut conn, block_num);
 
     // test empty table
-    let account_ids = vec![ACCOUNT_ID_REGULAR_ACCOUNT_UPDATABLE_CODE_OFF_CHAIN, 1, 2, 3, 4, 5];
-    let res = sql::select_accounts_by_block_range(&mut conn, 0, u32::MAX, &account_ids).unwrap();
+    let account_ids = [ACCOUNT_ID_REGULAR_ACCOUNT_UPDATABLE_CODE_OFF_CHAIN, 1, 2, 3, 4, 5]
+        .map(types::AccountId::new);
+    let res = sql::select_accounts_by_block_range(
+        &mut conn,
+        BlockNumber::GENESIS,
+        BlockNumber::MAX,
+        &account_ids,
+        true,
+    )
+    .unwrap();
     assert!(res.is_empty());
 
     // test insertion
@@ -548,24 +1754,47 @@ fn test_db_account() {
     assert_eq!(row_count, 1);
 
     // test successful query
-    let res = sql::select_accounts_by_block_range(&mut conn, 0, u32::MAX, &account_ids).unwrap();
+    let res = sql::select_accounts_by_block_range(
+        &mut conn,
+        BlockNumber::GENESIS,
+        BlockNumber::MAX,
+        &account_ids,
+        true,
+    )
+    .unwrap();
     assert_eq!(
         res,
-        vec![AccountSummary {
-            account_id: account_id.try_into().unwrap(),
-            account_hash,
-            block_num,
+        vec![AccountInfo {
+            summary: AccountSummary {
+                account_id: account_id.try_into().unwrap(),
+                account_hash,
+                block_num: block_num.into(),
+                nonce: None,
+            },
+            details: None,
         }]
     );
 
     // test query for update outside the block range
-    let res = sql::select_accounts_by_block_range(&mut conn, block_num + 1, u32::MAX, &account_ids)
-        .unwrap();
+    let res = sql::select_accounts_by_block_range(
+        &mut conn,
+        block_num.child().unwrap(),
+        BlockNumber::MAX,
+        &account_ids,
+        true,
+    )
+    .unwrap();
     assert!(res.is_empty());
 
     // test query with unknown accounts
-    let res = sql::select_accounts_by_block_range(&mut conn, block_num + 1, u32::MAX, &[6, 7, 8])
-        .unwrap();
+    let res = sql::select_accounts_by_block_range(
+        &mut conn,
+        block_num.child().unwrap(),
+        BlockNumber::MAX,
+        &[6, 7, 8].map(types::AccountId::new),
+        true,
+    )
+    .unwrap();
     assert!(res.is_empty());
 }
 
@@ -573,15 +1802,22 @@ fn test_db_account() {
 fn test_notes() {
     let mut conn = create_db();
 
-    let block_num_1 = 1;
+    let block_num_1 = BlockNumber::new(1);
     create_block(&mut conn, block_num_1);
 
     // test empty table
-    let res = sql::select_notes_since_block_by_tag_and_sender(&mut conn, &[], &[], 0).unwrap();
+    let res =
+        sql::select_notes_since_block_by_tag_and_sender(&mut conn, &[], &[], BlockNumber::GENESIS)
+            .unwrap();
     assert!(res.is_empty());
 
-    let res =
-        sql::select_notes_since_block_by_tag_and_sender(&mut conn, &[1, 2, 3], &[], 0).unwrap();
+    let res = sql::select_notes_since_block_by_tag_and_sender(
+        &mut conn,
+        &[1, 2, 3],
+        &[],
+        BlockNumber::GENESIS,
+    )
+    .unwrap();
     assert!(res.is_empty());
 
     // test insertion
@@ -606,6 +1842,7 @@ fn test_notes() {
             note_type: NoteType::Public,
             sender: sender.into(),
             tag,
+            aux: 0,
             details,
         },
         merkle_path: merkle_path.clone(),
@@ -616,7 +1853,9 @@ fn test_notes() {
     transaction.commit().unwrap();
 
     // test empty tags
-    let res = sql::select_notes_since_block_by_tag_and_sender(&mut conn, &[], &[], 0).unwrap();
+    let res =
+        sql::select_notes_since_block_by_tag_and_sender(&mut conn, &[], &[], BlockNumber::GENESIS)
+            .unwrap();
     assert!(res.is_empty());
 
     // test no updates
@@ -625,12 +1864,16 @@ fn test_notes() {
     assert!(res.is_empty());
 
     // test match
-    let res =
-        sql::select_notes_since_block_by_tag_and_sender(&mut conn, &[tag], &[], block_num_1 - 1)
-            .unwrap();
+    let res = sql::select_notes_since_block_by_tag_and_sender(
+        &mut conn,
+        &[tag],
+        &[],
+        BlockNumber::GENESIS,
+    )
+    .unwrap();
     assert_eq!(res, vec![note.clone()]);
 
-    let block_num_2 = note.block_num + 1;
+    let block_num_2 = note.block_num.child().unwrap();
     create_block(&mut conn, block_num_2);
 
     // insertion second note with same tag, but on higher block
@@ -643,6 +1886,7 @@ fn test_notes() {
             note_type: NoteType::OffChain,
             sender: note.note_created.sender,
             tag: note.note_created.tag,
+            aux: note.note_created.aux,
             details: None,
         },
         merkle_path,
@@ -653,9 +1897,13 @@ fn test_notes() {
     transaction.commit().unwrap();
 
     // only first note is returned
-    let res =
-        sql::select_notes_since_block_by_tag_and_sender(&mut conn, &[tag], &[], block_num_1 - 1)
-            .unwrap();
+    let res = sql::select_notes_since_block_by_tag_and_sender(
+        &mut conn,
+        &[tag],
+        &[],
+        BlockNumber::GENESIS,
+    )
+    .unwrap();
     assert_eq!(res, vec![note.clone()]);
 
     // only the second note is returned
@@ -681,6 +1929,1241 @@ fn test_notes() {
 
 // UTILITIES
 // -------------------------------------------------------------------------------------------
+#[test]
+fn test_sql_get_note_sync() {
+    let mut conn = create_db();
+
+    for block_num in (1..=3).map(BlockNumber::new) {
+        create_block(&mut conn, block_num);
+    }
+
+    let tag = 5u32;
+    let sender = AccountId::new_unchecked(Felt::new(ACCOUNT_ID_OFF_CHAIN_SENDER));
+    let note = Note {
+        block_num: 2.into(),
+        note_created: NoteCreated {
+            batch_index: 0,
+            note_index: 0,
+            note_id: num_to_rpo_digest(3),
+            note_type: NoteType::OffChain,
+            sender: sender.into(),
+            tag,
+            aux: 0,
+            details: None,
+        },
+        merkle_path: MerklePath::new(vec![]),
+    };
+
+    let transaction = conn.transaction().unwrap();
+    sql::insert_notes(&transaction, &[note.clone()]).unwrap();
+    transaction.commit().unwrap();
+
+    // the first block with a matching note is returned
+    let res = sql::get_note_sync(&mut conn, BlockNumber::GENESIS, &tag_filter(&[tag])).unwrap();
+    assert_eq!(res.notes, with_note_paths(&[note]));
+    assert_eq!(res.block_header.block_num(), 2);
+    assert_eq!(res.chain_tip, 3.into());
+
+    // without matching notes, the chain tip is returned
+    let res = sql::get_note_sync(&mut conn, 2.into(), &tag_filter(&[tag])).unwrap();
+    assert!(res.notes.is_empty());
+    assert_eq!(res.block_header.block_num(), 3);
+    assert_eq!(res.chain_tip, 3.into());
+
+    // notes are not matched by their sender
+    let res = sql::get_note_sync(&mut conn, BlockNumber::GENESIS, &tag_filter(&[tag + 1])).unwrap();
+    assert!(res.notes.is_empty());
+    assert_eq!(res.block_header.block_num(), 3);
+}
+
+#[test]
+fn test_sql_select_notes_by_tag_components() {
+    let mut conn = create_db();
+    for block_num in (1..=4).map(BlockNumber::new) {
+        create_block(&mut conn, block_num);
+    }
+
+    // block 2 has a local use case note, block 3 a network note targeting an account and block 4
+    // another local use case note. The tags of the network use cases are inconsistent with all
+    // the note types, so these notes can't be created.
+    let sender = AccountId::new_unchecked(Felt::new(ACCOUNT_ID_OFF_CHAIN_SENDER));
+    let notes: Vec<Note> = [
+        (2, 0x4001_0000, NoteType::OffChain),
+        (2, 0x0001_0000, NoteType::OffChain),
+        (3, 0x8001_0000, NoteType::Public),
+        (4, 0x4002_0000, NoteType::OffChain),
+    ]
+    .into_iter()
+    .enumerate()
+    .map(|(index, (block_num, tag, note_type))| Note {
+        block_num: BlockNumber::new(block_num),
+        note_created: NoteCreated {
+            batch_index: 0,
+            note_index: index as u32,
+            note_id: num_to_rpo_digest(index as u64),
+            note_type,
+            sender: sender.into(),
+            tag,
+            aux: 0,
+            details: None,
+        },
+        merkle_path: MerklePath::new(vec![]),
+    })
+    .collect();
+    let transaction = conn.transaction().unwrap();
+    sql::insert_notes(&transaction, &notes).unwrap();
+    transaction.commit().unwrap();
+    let notes = with_note_paths(&notes);
+
+    // the tags targeting an account have no use case, although their bits match
+    let filter = NoteFilter { use_cases: vec![1], ..Default::default() };
+    let res =
+        sql::select_notes_of_next_blocks(&mut conn, &filter, BlockNumber::GENESIS, 5).unwrap();
+    assert_eq!(res, [notes[0].clone()]);
+
+    let filter = NoteFilter {
+        network_notes: true,
+        ..Default::default()
+    };
+    let res =
+        sql::select_notes_of_next_blocks(&mut conn, &filter, BlockNumber::GENESIS, 5).unwrap();
+    assert_eq!(res, [notes[2].clone()]);
+
+    // a note matches if any of the filters matches
+    let filter = NoteFilter {
+        tags: vec![0x0001_0000],
+        use_cases: vec![0x0002],
+        ..Default::default()
+    };
+    let res = sql::get_note_sync(&mut conn, 2.into(), &filter).unwrap();
+    assert_eq!(res.notes, [notes[3].clone()]);
+    assert_eq!(res.block_header.block_num(), 4);
+}
+
+#[test]
+fn test_sql_select_notes_by_aux_and_epoch() {
+    let mut conn = create_db();
+    let epoch_start = BlockNumber::new(sql::NULLIFIER_EPOCH_BLOCKS);
+    for block_num in [BlockNumber::new(1), BlockNumber::new(2), epoch_start] {
+        create_block(&mut conn, block_num);
+    }
+
+    // the largest aux is stored as a negative integer
+    let sender = types::AccountId::from(ACCOUNT_ID_OFF_CHAIN_SENDER);
+    let other_sender = types::AccountId::from(ACCOUNT_ID_REGULAR_ACCOUNT_UPDATABLE_CODE_OFF_CHAIN);
+    let notes: Vec<Note> = [
+        (BlockNumber::new(1), sender, 7),
+        (BlockNumber::new(2), other_sender, 1 << 40),
+        (BlockNumber::new(2), sender, <Felt as StarkField>::MODULUS - 1),
+        (epoch_start, sender, 8),
+    ]
+    .into_iter()
+    .enumerate()
+    .map(|(index, (block_num, sender, aux))| Note {
+        block_num,
+        note_created: NoteCreated {
+            batch_index: 0,
+            note_index: index as u32,
+            note_id: num_to_rpo_digest(index as u64),
+            note_type: NoteType::OffChain,
+            sender,
+            tag: 5,
+            aux,
+            details: None,
+        },
+        merkle_path: MerklePath::new(vec![]),
+    })
+    .collect();
+    let transaction = conn.transaction().unwrap();
+    sql::insert_notes(&transaction, &notes).unwrap();
+    transaction.commit().unwrap();
+    let notes = with_note_paths(&notes);
+
+    let list = |conn: &mut Connection, filter: NoteListFilter| {
+        sql::select_notes_page(conn, &filter, None, None).unwrap()
+    };
+    let aux_ranges = |ranges: &[(u64, u64)]| {
+        ranges.iter().map(|&(min, max)| AuxRange { min, max }).collect::<Vec<_>>()
+    };
+
+    let filter = NoteListFilter {
+        sender: Some(sender),
+        ..Default::default()
+    };
+    assert_eq!(list(&mut conn, filter), [notes[0].clone(), notes[2].clone(), notes[3].clone()]);
+    let filter = NoteListFilter { epoch: Some(1), ..Default::default() };
+    assert_eq!(list(&mut conn, filter), [notes[3].clone()]);
+    let filter = NoteListFilter {
+        sender: Some(sender),
+        epoch: Some(0),
+        aux_ranges: aux_ranges(&[(0, 1 << 41)]),
+    };
+    assert_eq!(list(&mut conn, filter), [notes[0].clone()]);
+
+    // the ranges spanning the values stored as negative integers
+    let filter = NoteListFilter {
+        aux_ranges: aux_ranges(&[(8, u64::MAX)]),
+        ..Default::default()
+    };
+    assert_eq!(list(&mut conn, filter), notes[1..]);
+    let filter = NoteListFilter {
+        aux_ranges: aux_ranges(&[(1 << 63, u64::MAX), (0, 7)]),
+        ..Default::default()
+    };
+    assert_eq!(list(&mut conn, filter), [notes[0].clone(), notes[2].clone()]);
+
+    // the aux ranges narrow the synced notes
+    let filter = NoteFilter {
+        tags: vec![5],
+        aux_ranges: aux_ranges(&[(1 << 40, 1 << 40)]),
+        ..Default::default()
+    };
+    let res = sql::get_note_sync(&mut conn, BlockNumber::GENESIS, &filter).unwrap();
+    assert_eq!(res.notes, [notes[1].clone()]);
+    let res = sql::select_notes_of_next_blocks(&mut conn, &filter, 2.into(), 5).unwrap();
+    assert!(res.is_empty());
+}
+
+#[test]
+fn test_sql_select_state_delta() {
+    let mut conn = create_db();
+    let epoch_start = BlockNumber::new(sql::NULLIFIER_EPOCH_BLOCKS);
+    for block_num in [1, 2, 3].map(BlockNumber::new).into_iter().chain([epoch_start]) {
+        create_block(&mut conn, block_num);
+    }
+
+    // the account is updated again in block 3, its update of block 1 is no longer stored
+    let account_id = AccountId::new_unchecked(Felt::new(ACCOUNT_ID_OFF_CHAIN_SENDER));
+    for (block_num, hash) in [(1, 1), (3, 2)] {
+        let transaction = conn.transaction().unwrap();
+        let update = AccountUpdateDetails {
+            account_id,
+            final_state_hash: num_to_rpo_digest(hash),
+            details: None,
+        };
+        sql::upsert_accounts(&transaction, &[update], BlockNumber::new(block_num)).unwrap();
+        transaction.commit().unwrap();
+    }
+
+    let notes: Vec<Note> = [1, 3]
+        .into_iter()
+        .map(|block_num| Note {
+            block_num: BlockNumber::new(block_num),
+            note_created: NoteCreated {
+                batch_index: 0,
+                note_index: 0,
+                note_id: num_to_rpo_digest(u64::from(block_num)),
+                note_type: NoteType::OffChain,
+                sender: account_id.into(),
+                tag: 5,
+                aux: 7,
+                details: None,
+            },
+            merkle_path: MerklePath::new(vec![]),
+        })
+        .collect();
+    let transaction = conn.transaction().unwrap();
+    sql::insert_notes(&transaction, &notes).unwrap();
+    sql::insert_nullifiers_for_block(&transaction, &[num_to_nullifier(2)], 2.into()).unwrap();
+    sql::insert_nullifiers_for_block(&transaction, &[num_to_nullifier(3)], epoch_start).unwrap();
+    transaction.commit().unwrap();
+
+    let accounts = sql::select_accounts_updated_in_range(&mut conn, 2.into()..=3.into()).unwrap();
+    assert_eq!(accounts.len(), 1);
+    assert_eq!((accounts[0].block_num, accounts[0].account_hash), (3, num_to_rpo_digest(2)));
+    assert!(sql::select_accounts_updated_in_range(&mut conn, 1.into()..=2.into())
+        .unwrap()
+        .is_empty());
+
+    let created = sql::select_notes_in_block_range(&mut conn, 2.into()..=3.into()).unwrap();
+    assert_eq!(created, [(notes[1].block_num, notes[1].note_created.clone())]);
+
+    // the nullifiers of each partition overlapping the range
+    let nullifiers: Vec<_> =
+        sql::select_nullifiers_in_block_range(&mut conn, 2.into()..=epoch_start)
+            .unwrap()
+            .into_iter()
+            .map(|info| (info.nullifier, info.block_num))
+            .collect();
+    assert_eq!(
+        nullifiers,
+        [(num_to_nullifier(2), 2.into()), (num_to_nullifier(3), epoch_start)]
+    );
+    assert!(sql::select_nullifiers_in_block_range(&mut conn, 3.into()..=3.into())
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn test_tag_components_migration() {
+    let mut conn = Connection::open_in_memory().unwrap();
+
+    // the notes inserted before the tag components were stored are backfilled
+    let tags: Vec<u32> = (0..8)
+        .map(|prefix| (prefix << 29) | (0x1234_5678 & 0x1fff_ffff))
+        .chain([u32::MAX])
+        .collect();
+    // the schema before the migration adding the tag components
+    migrations::MIGRATIONS.to_version(&mut conn, 10).unwrap();
+    conn.execute("INSERT INTO block_headers (block_num, block_header) VALUES (1, x'00')", [])
+        .unwrap();
+    for (index, tag) in tags.iter().enumerate() {
+        conn.execute(
+            "INSERT INTO notes (block_num, batch_index, note_index, note_hash, note_type, sender, \
+             tag, merkle_path) VALUES (1, 0, ?1, x'00', 1, 0, ?2, x'00')",
+            params![index, tag],
+        )
+        .unwrap();
+    }
+    migrations::MIGRATIONS.to_latest(&mut conn).unwrap();
+
+    let mut stmt = conn
+        .prepare("SELECT tag, tag_use_case, tag_network_hint FROM notes ORDER BY note_index")
+        .unwrap();
+    let rows: Vec<(u32, Option<u32>, bool)> = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    let expected: Vec<_> = tags
+        .iter()
+        .map(|&tag| (tag, note_tag_use_case(tag), is_network_note_tag(tag)))
+        .collect();
+    assert_eq!(rows, expected);
+}
+
+#[test]
+fn test_sql_get_state_sync_multiple_blocks() {
+    let mut conn = create_db();
+
+    for block_num in (1..=5).map(BlockNumber::new) {
+        create_block(&mut conn, block_num);
+    }
+
+    // blocks 2, 3 and 4 contain matching notes, block 2 contains two of them
+    let tag = 5u32;
+    let sender = AccountId::new_unchecked(Felt::new(ACCOUNT_ID_OFF_CHAIN_SENDER));
+    let notes: Vec<Note> = [(2, 0), (2, 1), (3, 0), (4, 0)]
+        .into_iter()
+        .map(|(block_num, note_index)| Note {
+            block_num: block_num.into(),
+            note_created: NoteCreated {
+                batch_index: 0,
+                note_index,
+                note_id: num_to_rpo_digest(u64::from(block_num * 10 + note_index)),
+                note_type: NoteType::OffChain,
+                sender: sender.into(),
+                tag,
+                aux: 0,
+                details: None,
+            },
+            merkle_path: MerklePath::new(vec![]),
+        })
+        .collect();
+
+    let transaction = conn.transaction().unwrap();
+    sql::insert_notes(&transaction, &notes).unwrap();
+    transaction.commit().unwrap();
+
+    let mut sync = |max_blocks, max_notes| {
+        let limits = StateSyncLimits { max_blocks, max_notes, until: None };
+        let plan = StateSyncPlan {
+            block_num: BlockNumber::GENESIS,
+            account_ids: vec![],
+            note_tag_prefixes: vec![tag],
+            nullifier_prefix_len: 16,
+            nullifier_prefixes: vec![],
+            limits,
+            include_account_details: false,
+            filter_chunk_size: 250,
+        };
+        let res = plan.run(&mut conn).unwrap();
+        let preceding_blocks: Vec<_> = res
+            .preceding_blocks
+            .iter()
+            .map(|block| (block.block_header.block_num(), block.notes.len()))
+            .collect();
+        (res.block_header.block_num(), res.notes.len(), preceding_blocks)
+    };
+
+    // a single block, as before the limits were introduced
+    assert_eq!(sync(1, 1000), (2, 2, vec![]));
+    // the last block of the update contains the last notes
+    assert_eq!(sync(2, 1000), (3, 1, vec![(2, 2)]));
+    // the update reaches the chain tip once all the blocks with matching notes are included
+    assert_eq!(sync(10, 1000), (5, 0, vec![(2, 2), (3, 1), (4, 1)]));
+    // the blocks which would exceed the maximum number of notes are left out
+    assert_eq!(sync(10, 3), (3, 1, vec![(2, 2)]));
+    // the first block is always included
+    assert_eq!(sync(10, 1), (2, 2, vec![]));
+}
+
+#[test]
+fn test_state_sync_plan_snapshot() {
+    let mut conn = create_db();
+
+    for block_num in (1..=5).map(BlockNumber::new) {
+        create_block(&mut conn, block_num);
+    }
+
+    // blocks 2 and 4 contain matching notes, blocks 2, 3 and 5 consume matching nullifiers
+    let tag = 5u32;
+    let sender = AccountId::new_unchecked(Felt::new(ACCOUNT_ID_OFF_CHAIN_SENDER));
+    let notes: Vec<Note> = [2, 4]
+        .into_iter()
+        .map(|block_num| Note {
+            block_num: block_num.into(),
+            note_created: NoteCreated {
+                batch_index: 0,
+                note_index: 0,
+                note_id: num_to_rpo_digest(u64::from(block_num)),
+                note_type: NoteType::OffChain,
+                sender: sender.into(),
+                tag,
+                aux: 0,
+                details: None,
+            },
+            merkle_path: MerklePath::new(vec![]),
+        })
+        .collect();
+    let nullifiers: Vec<_> = [2, 3, 5]
+        .into_iter()
+        .map(|n| (num_to_nullifier(u64::from(n) << 48), n))
+        .collect();
+
+    let transaction = conn.transaction().unwrap();
+    sql::insert_notes(&transaction, &notes).unwrap();
+    for (nullifier, block_num) in &nullifiers {
+        sql::insert_nullifiers_for_block(&transaction, &[*nullifier], BlockNumber::new(*block_num))
+            .unwrap();
+    }
+    transaction.commit().unwrap();
+
+    let plan = |max_blocks| StateSyncPlan {
+        block_num: BlockNumber::GENESIS,
+        account_ids: vec![],
+        note_tag_prefixes: vec![tag],
+        nullifier_prefix_len: 16,
+        nullifier_prefixes: nullifiers
+            .iter()
+            .map(|(nullifier, _)| nullifier_prefix(nullifier))
+            .collect(),
+        limits: StateSyncLimits { max_blocks, max_notes: 1000, until: None },
+        include_account_details: false,
+        filter_chunk_size: 250,
+    };
+    let nullifier_blocks = |update: &StateSyncUpdate| -> Vec<u32> {
+        update.nullifiers.iter().map(|nullifier| nullifier.block_num.as_u32()).collect()
+    };
+
+    // the queries bounded by block 3 ignore the later blocks, as if block 3 was the chain tip
+    let snapshot = BlockNumber::new(3);
+    let chain_tip = sql::select_block_header_by_block_num(&mut conn, Some(snapshot))
+        .unwrap()
+        .unwrap();
+    let sync = |conn: &mut Connection, plan: StateSyncPlan| {
+        let blocks = plan.select_notes(conn, snapshot).unwrap();
+        let account_updates = plan.select_account_updates(conn, snapshot).unwrap();
+        let nullifiers = plan.select_nullifiers(conn, snapshot).unwrap();
+        plan.merge(conn, snapshot, chain_tip, blocks, account_updates, nullifiers)
+            .unwrap()
+    };
+
+    let update = sync(&mut conn, plan(10));
+    assert_eq!(update.block_header.block_num(), 3);
+    assert_eq!(update.chain_tip, snapshot);
+    assert_eq!(update.preceding_blocks.len(), 1);
+    assert_eq!(nullifier_blocks(&update), [2, 3]);
+
+    // the nullifiers after the last block of the update are left out
+    let update = sync(&mut conn, plan(1));
+    assert_eq!(update.block_header.block_num(), 2);
+    assert_eq!(update.notes.len(), 1);
+    assert_eq!(nullifier_blocks(&update), [2]);
+
+    // running the plan on the chain tip sees all the blocks
+    let update = plan(10).run(&mut conn).unwrap();
+    assert_eq!(update.block_header.block_num(), 5);
+    assert_eq!(update.preceding_blocks.len(), 2);
+    assert_eq!(nullifier_blocks(&update), [2, 3, 5]);
+
+    // the filters queried one value at a time match the same notes and nullifiers
+    let chunked = StateSyncPlan { filter_chunk_size: 1, ..plan(10) };
+    assert_eq!(chunked.run(&mut conn).unwrap(), update);
+    let chunked = StateSyncPlan { filter_chunk_size: 1, ..plan(1) };
+    assert_eq!(chunked.run(&mut conn).unwrap().block_header.block_num(), 2);
+
+    // a continuation covers all the blocks up to its last block, whatever the block limit
+    let continuation = StateSyncPlan {
+        limits: StateSyncLimits::until(BlockNumber::new(3)),
+        ..plan(1)
+    };
+    let update = continuation.run(&mut conn).unwrap();
+    assert_eq!(update.block_header.block_num(), 3);
+    assert!(update.chain_tip >= BlockNumber::new(5));
+    assert_eq!(update.preceding_blocks.len(), 1);
+    assert_eq!(nullifier_blocks(&update), [2, 3]);
+
+    // a continuation can't end after the chain tip
+    let continuation = StateSyncPlan {
+        limits: StateSyncLimits::until(BlockNumber::new(1000)),
+        ..plan(1)
+    };
+    assert!(matches!(
+        continuation.run(&mut conn),
+        Err(StateSyncError::InvalidSyncEnd { .. })
+    ));
+}
+
+#[test]
+fn test_state_sync_read_snapshot() {
+    let path =
+        std::env::temp_dir().join(format!("miden-store-snapshot-{}.sqlite3", std::process::id()));
+    let open = || {
+        let conn = Connection::open(&path).unwrap();
+        conn.pragma_update(None, "journal_mode", "WAL").unwrap();
+        array::load_module(&conn).unwrap();
+        conn
+    };
+    let mut conn = open();
+    migrations::MIGRATIONS.to_latest(&mut conn).unwrap();
+    let mut writer_conn = open();
+
+    let account_id = AccountId::new_unchecked(Felt::new(ACCOUNT_ID_OFF_CHAIN_SENDER));
+    let update_account = |conn: &mut Connection, block_num: BlockNumber| {
+        create_block(conn, block_num);
+        let transaction = conn.transaction().unwrap();
+        let update = AccountUpdateDetails {
+            account_id,
+            final_state_hash: num_to_rpo_digest(block_num.as_u32().into()),
+            details: None,
+        };
+        sql::upsert_accounts(&transaction, &[update], block_num).unwrap();
+        transaction.commit().unwrap();
+    };
+    let select_updates = |conn: &mut Connection| {
+        sql::select_accounts_by_block_range(
+            conn,
+            BlockNumber::GENESIS,
+            BlockNumber::new(1),
+            &[account_id.into()],
+            false,
+        )
+    };
+    update_account(&mut conn, BlockNumber::new(1));
+
+    // the account updated again by a block applied meanwhile is still seen in its state of block 1
+    read_snapshot(&mut conn, |conn| {
+        let updates = select_updates(conn)?;
+        assert_eq!(updates.len(), 1);
+
+        update_account(&mut writer_conn, BlockNumber::new(2));
+        assert_eq!(select_updates(conn)?, updates);
+
+        Ok::<_, DatabaseError>(())
+    })
+    .unwrap();
+
+    // outside of the snapshot, the account has moved to block 2
+    assert!(select_updates(&mut conn).unwrap().is_empty());
+
+    drop((conn, writer_conn));
+    for suffix in ["", "-wal", "-shm"] {
+        let _ = std::fs::remove_file(format!("{}{suffix}", path.display()));
+    }
+}
+
+#[test]
+fn test_sql_database_size() {
+    let mut conn = create_db();
+
+    let empty = sql::select_database_size(&mut conn).unwrap();
+    assert!(empty.total_bytes > 0);
+    for table in ["accounts", "block_headers", "notes", "nullifiers", "transactions"] {
+        assert!(empty.tables.contains_key(table), "Missing table {table}");
+    }
+
+    for block_num in (1..=100).map(BlockNumber::new) {
+        create_block(&mut conn, block_num);
+    }
+
+    let size = sql::select_database_size(&mut conn).unwrap();
+    assert!(size.total_bytes > empty.total_bytes);
+    assert!(size.table_bytes("block_headers") > empty.table_bytes("block_headers"));
+    assert_eq!(size.table_bytes("nullifiers"), empty.table_bytes("nullifiers"));
+}
+
+#[test]
+fn test_sql_backup_database() {
+    let mut conn = create_db();
+    for block_num in (1..=3).map(BlockNumber::new) {
+        create_block(&mut conn, block_num);
+    }
+    let path =
+        std::env::temp_dir().join(format!("miden-store-backup-{}.sqlite3", std::process::id()));
+
+    let block_header = sql::backup_database(&mut conn, &path).unwrap();
+    assert_eq!(block_header.block_num(), 3);
+
+    // The backup is a complete database, left untouched by the later blocks
+    create_block(&mut conn, BlockNumber::new(4));
+    let mut backup_conn = Connection::open(&path).unwrap();
+    let latest = sql::select_block_header_by_block_num(&mut backup_conn, None).unwrap();
+    assert_eq!(latest, Some(block_header));
+    let first = sql::select_block_header_by_block_num(&mut backup_conn, Some(1.into())).unwrap();
+    assert!(first.is_some());
+
+    drop(backup_conn);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_sql_transaction_receipts() {
+    let mut conn = create_db();
+
+    let block_num = BlockNumber::new(1);
+    create_block(&mut conn, block_num);
+
+    let receipt = TransactionReceipt {
+        transaction: TransactionSummary {
+            id: num_to_rpo_digest(1).into(),
+            account_id: AccountId::new_unchecked(Felt::new(ACCOUNT_ID_OFF_CHAIN_SENDER)),
+            initial_account_hash: num_to_rpo_digest(2),
+            final_account_hash: num_to_rpo_digest(3),
+            output_notes: vec![num_to_rpo_digest(4).into(), num_to_rpo_digest(5).into()],
+            input_nullifiers: vec![num_to_nullifier(6)],
+        },
+        block_num: block_num.into(),
+        batch_index: 2,
+        fee: None,
+        cycles: 1 << 16,
+    };
+
+    // unknown transactions have no receipt
+    let res = sql::select_transaction_receipt(&mut conn, num_to_rpo_digest(1)).unwrap();
+    assert_eq!(res, None);
+
+    let transaction = conn.transaction().unwrap();
+    let res = sql::insert_transactions(&transaction, &[receipt.clone()]);
+    assert_eq!(res.unwrap(), 1, "One element must have been inserted");
+    transaction.commit().unwrap();
+
+    let res = sql::select_transaction_receipt(&mut conn, num_to_rpo_digest(1)).unwrap();
+    assert_eq!(res, Some(receipt.clone()));
+
+    // a transaction can only be included once
+    let transaction = conn.transaction().unwrap();
+    assert!(sql::insert_transactions(&transaction, &[receipt]).is_err());
+}
+
+#[test]
+fn test_sql_collected_fees() {
+    let mut conn = create_db();
+
+    let block_num = BlockNumber::new(1);
+    create_block(&mut conn, block_num);
+
+    let faucet_id = AccountId::new_unchecked(Felt::new(ACCOUNT_ID_FUNGIBLE_FAUCET_ON_CHAIN));
+    let receipt = |id: u64, fee: Option<u64>| TransactionReceipt {
+        transaction: TransactionSummary {
+            id: num_to_rpo_digest(id).into(),
+            account_id: AccountId::new_unchecked(Felt::new(ACCOUNT_ID_OFF_CHAIN_SENDER)),
+            initial_account_hash: num_to_rpo_digest(2),
+            final_account_hash: num_to_rpo_digest(3),
+            output_notes: vec![],
+            input_nullifiers: vec![],
+        },
+        block_num: block_num.into(),
+        batch_index: 0,
+        fee: fee.map(|amount| TransactionFee { faucet_id, amount }),
+        cycles: 0,
+    };
+
+    assert!(sql::select_collected_fees(&mut conn).unwrap().is_empty());
+
+    let receipts = [receipt(1, Some(10)), receipt(2, None), receipt(3, Some(5))];
+    let transaction = conn.transaction().unwrap();
+    sql::insert_transactions(&transaction, &receipts).unwrap();
+    transaction.commit().unwrap();
+
+    // the fees are returned with the receipts, and summed per faucet
+    let res = sql::select_transaction_receipt(&mut conn, num_to_rpo_digest(1)).unwrap();
+    assert_eq!(res, Some(receipts[0].clone()));
+    let res = sql::select_transaction_receipt(&mut conn, num_to_rpo_digest(2)).unwrap();
+    assert_eq!(res.unwrap().fee, None);
+
+    let fees = sql::select_collected_fees(&mut conn).unwrap();
+    assert_eq!(fees, [TransactionFee { faucet_id, amount: 15 }]);
+}
+
+#[test]
+fn test_sql_blocks() {
+    let mut conn = create_db();
+
+    for block_num in (1..=3).map(BlockNumber::new) {
+        create_block(&mut conn, block_num);
+
+        let transaction = conn.transaction().unwrap();
+        let res = sql::insert_block(&transaction, block_num, &[block_num.as_u32() as u8; 4]);
+        assert_eq!(res.unwrap(), 1, "One element must have been inserted");
+        transaction.commit().unwrap();
+    }
+
+    let res = sql::select_blocks(&mut conn, 2.into(), 10).unwrap();
+    assert_eq!(res, vec![(2.into(), vec![2; 4]), (3.into(), vec![3; 4])]);
+
+    let res = sql::select_blocks(&mut conn, 1.into(), 1).unwrap();
+    assert_eq!(res, vec![(1.into(), vec![1; 4])]);
+
+    let res = sql::select_blocks(&mut conn, 4.into(), 10).unwrap();
+    assert!(res.is_empty());
+}
+
+#[test]
+fn test_sql_block_proofs() {
+    let mut conn = create_db();
+
+    for block_num in (1..=3).map(BlockNumber::new) {
+        create_block(&mut conn, block_num);
+
+        let transaction = conn.transaction().unwrap();
+        let res = sql::insert_block_proof(&transaction, block_num, &[block_num.as_u32() as u8; 4]);
+        assert_eq!(res.unwrap(), 1, "One element must have been inserted");
+        transaction.commit().unwrap();
+    }
+    create_block(&mut conn, 4.into());
+
+    let res = sql::select_block_proof(&mut conn, 2.into()).unwrap();
+    assert_eq!(res, Some(vec![2; 4]));
+
+    // blocks which aren't proven have no proof
+    let res = sql::select_block_proof(&mut conn, 4.into()).unwrap();
+    assert_eq!(res, None);
+
+    // pruning the proofs keeps the blocks
+    let transaction = conn.transaction().unwrap();
+    let res = sql::delete_block_proofs_before(&transaction, 3.into()).unwrap();
+    assert_eq!(res, 2);
+    transaction.commit().unwrap();
+
+    assert_eq!(sql::select_block_proof(&mut conn, 2.into()).unwrap(), None);
+    assert_eq!(sql::select_block_proof(&mut conn, 3.into()).unwrap(), Some(vec![3; 4]));
+    assert!(sql::select_block_header_by_block_num(&mut conn, Some(2.into()))
+        .unwrap()
+        .is_some());
+}
+
+#[test]
+fn test_sql_store_instance() {
+    let mut conn = create_db();
+
+    // a new database has no application id and no owner
+    assert_eq!(sql::select_application_id(&mut conn).unwrap(), 0);
+    assert_eq!(sql::select_store_instance(&mut conn).unwrap(), None);
+
+    sql::set_application_id(&mut conn, APPLICATION_ID).unwrap();
+    assert_eq!(sql::select_application_id(&mut conn).unwrap(), APPLICATION_ID);
+
+    // each store replaces the previous owner
+    for process_id in [1, 2] {
+        let instance = StoreInstance {
+            instance_id: uuid::Uuid::new_v4(),
+            process_id,
+            started_at: 1_700_000_000,
+        };
+        let transaction = conn.transaction().unwrap();
+        assert_eq!(sql::upsert_store_instance(&transaction, &instance).unwrap(), 1);
+        transaction.commit().unwrap();
+
+        assert_eq!(sql::select_store_instance(&mut conn).unwrap(), Some(instance));
+    }
+}
+
+#[test]
+fn test_sql_block_number_range() {
+    let conn = create_db();
+    let select =
+        |value: i64| conn.query_row("SELECT ?1", [value], |row| row.get::<_, BlockNumber>(0));
+
+    assert_eq!(select(u32::MAX.into()).unwrap(), BlockNumber::MAX);
+    assert!(select(i64::from(u32::MAX) + 1).is_err());
+    assert!(select(-1).is_err());
+}
+
+#[test]
+fn test_interrupt_on_drop() {
+    let conn = create_db();
+    let (handle_sender, handle_receiver) = oneshot::channel();
+    assert!(handle_sender.send(conn.get_interrupt_handle()).is_ok());
+
+    // A query which never completes unless interrupted
+    let query = std::thread::spawn(move || {
+        conn.query_row(
+            "WITH RECURSIVE c(x) AS (SELECT 1 UNION ALL SELECT x + 1 FROM c) SELECT max(x) FROM c",
+            [],
+            |row| row.get::<_, i64>(0),
+        )
+    });
+
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    drop(InterruptOnDrop::new(handle_receiver));
+
+    let err = query.join().unwrap().unwrap_err();
+    assert_eq!(err.sqlite_error_code(), Some(ErrorCode::OperationInterrupted));
+}
+
+#[test]
+fn test_disarmed_interrupt_guard() {
+    let conn = create_db();
+    let (handle_sender, handle_receiver) = oneshot::channel();
+    assert!(handle_sender.send(conn.get_interrupt_handle()).is_ok());
+
+    InterruptOnDrop::new(handle_receiver).disarm();
+
+    let count: i64 = conn
+        .query_row("SELECT count(*) FROM block_headers", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(count, 0);
+}
+
+// QUERY PLANS
+// ================================================================================================
+
+thread_local! {
+    /// Statements run by the connections traced by [full_scans] on this thread.
+    static TRACED_STATEMENTS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+fn trace_statement(statement: &str) {
+    TRACED_STATEMENTS.with(|statements| statements.borrow_mut().push(statement.to_string()));
+}
+
+/// Runs `query` on `conn` and returns the tables fully scanned by the plans of its statements,
+/// with the plans for the assertion messages.
+///
+/// The statements are traced with their parameters inlined, a table is fully scanned if the plan
+/// walks its rows, or one of its indexes, without a key constraint.
+fn full_scans(
+    conn: &mut Connection,
+    query: impl FnOnce(&mut Connection),
+) -> (BTreeSet<String>, Vec<String>) {
+    conn.trace(Some(trace_statement));
+    query(conn);
+    conn.trace(None);
+
+    let tables: BTreeSet<String> = conn
+        .prepare("SELECT name FROM sqlite_master WHERE type = 'table';")
+        .unwrap()
+        .query_map([], |row| row.get(0))
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+
+    let mut scans = BTreeSet::new();
+    let mut plans = Vec::new();
+    for statement in TRACED_STATEMENTS.with(|statements| statements.take()) {
+        // the statements run internally, e.g. by the virtual tables, are traced as comments
+        if statement.starts_with("--") {
+            continue;
+        }
+        let mut stmt = conn.prepare(&format!("EXPLAIN QUERY PLAN {statement}")).unwrap();
+        let details: Vec<String> =
+            stmt.query_map([], |row| row.get(3)).unwrap().collect::<Result<_, _>>().unwrap();
+        for detail in &details {
+            let scanned = detail.strip_prefix("SCAN ").and_then(|rest| rest.split(' ').next());
+            if let Some(table) = scanned.filter(|table| tables.contains(*table)) {
+                scans.insert(table.to_string());
+            }
+        }
+        plans.push(format!("{statement}\n  {}", details.join("\n  ")));
+    }
+
+    (scans, plans)
+}
+
+/// Asserts that `query` only fully scans the `expected` tables, the other tables being read by key.
+#[track_caller]
+fn assert_full_scans(
+    conn: &mut Connection,
+    expected: &[&str],
+    query: impl FnOnce(&mut Connection),
+) {
+    let (scans, plans) = full_scans(conn, query);
+    let expected: BTreeSet<String> = expected.iter().map(ToString::to_string).collect();
+    assert_eq!(
+        scans,
+        expected,
+        "Unexpected full table scans, query plans:\n{}",
+        plans.join("\n")
+    );
+}
+
+/// Every query of [sql] must read the tables through their primary key or an index, except the
+/// queries loading a whole table on purpose. A query added to [sql] must be added here.
+#[test]
+fn test_sql_query_plans() {
+    let mut conn = create_db();
+    create_block(&mut conn, 1.into());
+
+    let account_id = AccountId::new_unchecked(Felt::new(ACCOUNT_ID_OFF_CHAIN_SENDER)).into();
+    let nullifier = num_to_nullifier(1);
+    let digest = num_to_rpo_digest(1);
+
+    // accounts
+    assert_full_scans(&mut conn, &[], |conn| {
+        sql::select_account(conn, account_id).unwrap();
+        sql::select_account_hashes_chunk(conn, Some(account_id), 10).unwrap();
+        sql::select_accounts_by_block_range(conn, 0.into(), 1.into(), &[account_id], true).unwrap();
+        sql::select_accounts_updated_in_range(conn, 0.into()..=1.into()).unwrap();
+        sql::select_accounts_by_public_key(conn, digest).unwrap();
+        sql::select_account_deltas(conn, account_id, 0.into(), 1.into(), 10).unwrap();
+
+        let transaction = conn.transaction().unwrap();
+        sql::upsert_account_keys(&transaction, &[account_id]).unwrap();
+        transaction.commit().unwrap();
+    });
+    // listed and loaded from the first account
+    for sort_order in [AccountSortOrder::BlockNumAsc, AccountSortOrder::AccountIdDesc] {
+        let filter = AccountFilter { sort_order, ..Default::default() };
+        assert_full_scans(&mut conn, &["accounts"], |conn| {
+            sql::select_accounts(conn, &filter).unwrap();
+        });
+    }
+    assert_full_scans(&mut conn, &["accounts"], |conn| {
+        sql::select_account_hashes_chunk(conn, None, 10).unwrap();
+
+        let transaction = conn.transaction().unwrap();
+        sql::backfill_account_nonces(&transaction).unwrap();
+        transaction.commit().unwrap();
+    });
+    assert_full_scans(&mut conn, &["accounts", "account_keys"], |conn| {
+        let transaction = conn.transaction().unwrap();
+        sql::rebuild_account_keys(&transaction).unwrap();
+        transaction.commit().unwrap();
+        sql::is_account_keys_empty(conn).unwrap();
+    });
+
+    // a single row
+    assert_full_scans(&mut conn, &["account_deltas_start"], |conn| {
+        sql::select_account_deltas_start(conn).unwrap();
+    });
+
+    // nullifiers, the partitions of a block range are searched by epoch
+    assert_full_scans(&mut conn, &[], |conn| {
+        sql::select_nullifiers_by_block_range(conn, 0.into(), 1.into(), 16, &[1, 2]).unwrap();
+        sql::select_nullifiers_by_block_range(conn, 0.into(), 1.into(), 8, &[1]).unwrap();
+        sql::select_nullifiers_page_by_block_range(conn, 0.into(), 1.into(), 16, &[1], 10).unwrap();
+        sql::select_nullifiers_by_prefix(conn, 16, &[1, 2]).unwrap();
+    });
+    // the delta of a range reads the whole partitions overlapping it
+    assert_full_scans(&mut conn, &["nullifiers_0"], |conn| {
+        sql::select_nullifiers_in_block_range(conn, 0.into()..=1.into()).unwrap();
+    });
+    // listing the partitions of the nullifiers, which are few, to search each of them
+    assert_full_scans(&mut conn, &["nullifier_partitions"], |conn| {
+        sql::select_nullifier_block_num(conn, &nullifier).unwrap();
+        sql::select_nullifiers_chunk(conn, Some(nullifier), 10).unwrap();
+    });
+    assert_full_scans(&mut conn, &["nullifier_partitions", "nullifiers_0"], |conn| {
+        sql::select_nullifiers(conn).unwrap();
+        sql::select_nullifiers_chunk(conn, None, 10).unwrap();
+    });
+
+    // notes
+    assert_full_scans(&mut conn, &[], |conn| {
+        let filter = NoteFilter {
+            tags: vec![1],
+            use_cases: vec![2],
+            senders: vec![account_id],
+            network_notes: true,
+            aux_ranges: vec![AuxRange { min: 1, max: u64::MAX }],
+        };
+        sql::select_notes_of_next_blocks(conn, &filter, 0.into(), 5).unwrap();
+        sql::select_notes_in_block_range(conn, 0.into()..=1.into()).unwrap();
+        let filter = NoteListFilter {
+            sender: Some(account_id),
+            epoch: Some(0),
+            ..Default::default()
+        };
+        sql::select_notes_page(conn, &filter, None, Some(10)).unwrap();
+        sql::select_notes_since_block_by_tag_and_sender(conn, &[1], &[], 0.into()).unwrap();
+        sql::select_notes_by_id(conn, &[digest.into()]).unwrap();
+        sql::select_block_notes(conn, 1.into()).unwrap();
+        sql::select_note_script(conn, digest).unwrap();
+        sql::select_note_consumption(conn, digest).unwrap();
+        sql::select_note_trees(conn, &BTreeSet::from([1.into()])).unwrap();
+
+        let transaction = conn.transaction().unwrap();
+        sql::upsert_note_trees(&transaction, &BTreeSet::from([1.into()])).unwrap();
+        sql::insert_note_consumptions(&transaction, &[nullifier], 1.into(), &[]).unwrap();
+        transaction.commit().unwrap();
+    });
+    assert_full_scans(&mut conn, &["notes"], |conn| {
+        sql::select_notes(conn).unwrap();
+
+        let transaction = conn.transaction().unwrap();
+        sql::backfill_note_trees(&transaction).unwrap();
+        transaction.commit().unwrap();
+    });
+
+    // transactions
+    assert_full_scans(&mut conn, &[], |conn| {
+        sql::select_transaction_receipt(conn, digest).unwrap();
+        sql::select_collected_fees(conn).unwrap();
+    });
+
+    // blocks
+    assert_full_scans(&mut conn, &[], |conn| {
+        sql::select_block_header_by_block_num(conn, Some(1.into())).unwrap();
+        sql::select_block_signature(conn, 1.into()).unwrap();
+        sql::select_block_timestamps(conn, &[1.into()]).unwrap();
+        sql::select_block_header_fields(conn, 1.into(), 10).unwrap();
+        sql::select_block_headers_chunk(conn, Some(1.into()), 10).unwrap();
+        sql::select_blocks(conn, 1.into(), 10).unwrap();
+        sql::select_block_proof(conn, 1.into()).unwrap();
+
+        let transaction = conn.transaction().unwrap();
+        sql::delete_block_proofs_before(&transaction, 1.into()).unwrap();
+        transaction.commit().unwrap();
+    });
+
+    // store instance
+    assert_full_scans(&mut conn, &[], |conn| {
+        sql::select_store_instance(conn).unwrap();
+
+        let transaction = conn.transaction().unwrap();
+        sql::upsert_store_instance(
+            &transaction,
+            &StoreInstance {
+                instance_id: uuid::Uuid::new_v4(),
+                process_id: 1,
+                started_at: 0,
+            },
+        )
+        .unwrap();
+        transaction.commit().unwrap();
+    });
+    // the latest block is the last row of the primary key
+    assert_full_scans(&mut conn, &["block_headers"], |conn| {
+        sql::select_block_header_by_block_num(conn, None).unwrap();
+        sql::select_block_headers_chunk(conn, None, 10).unwrap();
+
+        let transaction = conn.transaction().unwrap();
+        sql::backfill_block_header_columns(&transaction).unwrap();
+        transaction.commit().unwrap();
+    });
+
+    // maintenance
+    assert_full_scans(
+        &mut conn,
+        &[
+            "accounts",
+            "block_headers",
+            "notes",
+            "nullifier_partitions",
+            "nullifiers_0",
+            "transactions",
+        ],
+        |conn| {
+            sql::select_row_counts(conn).unwrap();
+            sql::select_database_size(conn).unwrap();
+        },
+    );
+}
+
+#[test]
+fn test_sql_select_schema() {
+    let mut conn = create_db();
+    let transaction = conn.transaction().unwrap();
+    for epoch in [10, 0, 2] {
+        sql::create_nullifier_partition(&transaction, epoch).unwrap();
+    }
+    transaction.commit().unwrap();
+
+    let schema = sql::select_schema(&mut conn).unwrap();
+    let user_version: u32 = conn.query_row("PRAGMA user_version;", [], |row| row.get(0)).unwrap();
+    assert_eq!(schema.version, user_version);
+
+    let notes = schema.tables.iter().find(|table| table.name == "notes").unwrap();
+    assert!(notes.sql.starts_with("CREATE TABLE"));
+    let block_num = notes.columns.iter().find(|column| column.name == "block_num").unwrap();
+    assert_eq!(block_num.column_type, "INTEGER");
+    assert!(block_num.not_null && block_num.primary_key);
+    let tag_index = notes.indexes.iter().find(|index| index.name == "idx_notes_tag").unwrap();
+    assert_eq!(tag_index.columns, vec!["tag".to_string()]);
+    assert!(!tag_index.unique);
+
+    // The partitions are described once, in the order of their epochs
+    let partitioned: Vec<_> = schema
+        .tables
+        .iter()
+        .filter(|table| table.name.starts_with("nullifiers_"))
+        .collect();
+    assert_eq!(partitioned.len(), 1);
+    assert_eq!(partitioned[0].name, "nullifiers_0");
+    assert_eq!(partitioned[0].partitions, ["nullifiers_0", "nullifiers_2", "nullifiers_10"]);
+
+    let sql = schema.to_sql();
+    assert!(sql.contains("CREATE INDEX idx_notes_tag ON notes(tag);"));
+    assert!(sql.contains("nullifiers_0, nullifiers_2, nullifiers_10"));
+    assert!(!sql.contains("CREATE TABLE\n            nullifiers_2"));
+
+    // The schema reported by the running store is the one of the database file
+    let proto = miden_node_proto::generated::schema::DatabaseSchema::from(schema.clone());
+    assert_eq!(DatabaseSchema::from(proto), schema);
+}
+
+#[test]
+fn test_sql_views() {
+    let mut conn = create_db();
+    let transaction = conn.transaction().unwrap();
+    views::create_views(&transaction).unwrap();
+    transaction.commit().unwrap();
+
+    let block_num = BlockNumber::new(1);
+    let epoch_start = BlockNumber::new(sql::NULLIFIER_EPOCH_BLOCKS);
+    create_block(&mut conn, block_num);
+    create_block(&mut conn, epoch_start);
+
+    // The identifiers above `i64::MAX`, stored as negative integers, are presented as their `u64`
+    // value
+    let faucet_id = AccountId::new_unchecked(Felt::new(0xa000_0000_0000_00ff));
+    let note = Note {
+        block_num,
+        note_created: NoteCreated {
+            batch_index: 0,
+            note_index: 0,
+            note_id: num_to_rpo_digest(1),
+            note_type: NoteType::Public,
+            sender: faucet_id.into(),
+            tag: 0x8001_0000,
+            aux: u64::MAX,
+            details: None,
+        },
+        merkle_path: MerklePath::new(vec![]),
+    };
+    let receipt = TransactionReceipt {
+        transaction: TransactionSummary {
+            id: num_to_rpo_digest(2).into(),
+            account_id: faucet_id,
+            initial_account_hash: num_to_rpo_digest(3),
+            final_account_hash: num_to_rpo_digest(4),
+            output_notes: vec![],
+            input_nullifiers: vec![],
+        },
+        block_num: block_num.into(),
+        batch_index: 0,
+        fee: Some(TransactionFee { faucet_id, amount: 7 }),
+        cycles: 0,
+    };
+
+    let transaction = conn.transaction().unwrap();
+    sql::upsert_accounts(
+        &transaction,
+        &[AccountUpdateDetails {
+            account_id: faucet_id,
+            final_state_hash: num_to_rpo_digest(4),
+            details: None,
+        }],
+        block_num,
+    )
+    .unwrap();
+    sql::insert_notes(&transaction, &[note]).unwrap();
+    sql::insert_transactions(&transaction, &[receipt]).unwrap();
+    // The nullifiers of a new partition are included in the view
+    sql::insert_nullifiers_for_block(&transaction, &[num_to_nullifier(5)], block_num).unwrap();
+    sql::insert_nullifiers_for_block(&transaction, &[num_to_nullifier(6)], epoch_start).unwrap();
+    transaction.commit().unwrap();
+
+    let block: (String, String) = conn
+        .query_row("SELECT time, note_root FROM analytics_blocks WHERE block_num = 1;", [], |row| {
+            Ok((row.get(0)?, row.get(1)?))
+        })
+        .unwrap();
+    assert_eq!(block, ("1970-01-01 00:00:10".to_string(), num_to_rpo_digest(6).to_hex()));
+
+    let account: (String, String, bool) = conn
+        .query_row("SELECT account_id, account_hash, public FROM analytics_accounts;", [], |row| {
+            Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+        })
+        .unwrap();
+    assert_eq!(account, (faucet_id.to_hex(), num_to_rpo_digest(4).to_hex(), false));
+
+    let note: (String, String, String, String, String, String) = conn
+        .query_row(
+            "SELECT note_id, note_type, sender, tag, tag_execution, aux FROM analytics_notes;",
+            [],
+            |row| {
+                Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?, row.get(5)?))
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        note,
+        (
+            num_to_rpo_digest(1).to_hex(),
+            "public".to_string(),
+            faucet_id.to_hex(),
+            "0x80010000".to_string(),
+            "network".to_string(),
+            "0xffffffffffffffff".to_string(),
+        )
+    );
+
+    let fee: (String, Option<String>, u64) = conn
+        .query_row(
+            "SELECT transaction_id, fee_faucet_id, fee_amount FROM analytics_transactions;",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+        .unwrap();
+    assert_eq!(fee, (num_to_rpo_digest(2).to_hex(), Some(faucet_id.to_hex()), 7));
+
+    let nullifiers: Vec<(String, u32)> = conn
+        .prepare("SELECT nullifier, block_num FROM analytics_nullifiers ORDER BY block_num;")
+        .unwrap()
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(
+        nullifiers,
+        [
+            (num_to_nullifier(5).to_hex(), u32::from(block_num)),
+            (num_to_nullifier(6).to_hex(), u32::from(epoch_start)),
+        ]
+    );
+
+    // The views are replaced when the store starts, and dropped while the tables are migrated
+    let transaction = conn.transaction().unwrap();
+    views::create_views(&transaction).unwrap();
+    transaction.commit().unwrap();
+    views::drop_views(&conn).unwrap();
+    let schema = sql::select_schema(&mut conn).unwrap();
+    assert!(schema.views.is_empty());
+}
+
+/// Temporary directory of a test, removed when dropped, even if the test fails.
+struct TestDirectory(PathBuf);
+
+impl TestDirectory {
+    fn new(name: &str) -> Self {
+        let path = std::env::temp_dir().join(format!("miden-store-{name}-{}", std::process::id()));
+        fs::create_dir_all(&path).unwrap();
+        Self(path)
+    }
+
+    fn path(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TestDirectory {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
 fn num_to_rpo_digest(n: u64) -> RpoDigest {
     RpoDigest::new(num_to_word(n))
 }
@@ -0,0 +1,176 @@
+//! Views of the tables with decoded columns, for the analytics tools attaching the database file
+//! read-only, see `miden-node store schema`.
+//!
+//! The digests are presented as `0x` prefixed hex strings and the identifiers as the hex strings
+//! of their `u64` value, like the RPC presents them, and the timestamps of the blocks as UTC
+//! dates. The views are created by the store on startup, after the migrations, so they always
+//! match the current tables.
+use rusqlite::{Connection, Transaction};
+
+use super::sql::nullifier_partition;
+
+/// Prefix of the names of the views, the views with this prefix are replaced on startup.
+const VIEW_PREFIX: &str = "analytics_";
+
+/// Name of the view of the nullifiers, created over all their partitions.
+const NULLIFIERS_VIEW: &str = "analytics_nullifiers";
+
+/// Names and `SELECT` statements of the views, except the view of the nullifiers which depends on
+/// the partitions.
+const VIEWS: [(&str, &str); 4] = [
+    (
+        "analytics_blocks",
+        "
+    SELECT
+        block_num,
+        timestamp,
+        datetime(timestamp, 'unixepoch') AS time,
+        signature IS NOT NULL AS signed,
+        nullif('0x' || lower(hex(note_root)), '0x') AS note_root,
+        nullif('0x' || lower(hex(nullifier_root)), '0x') AS nullifier_root,
+        nullif('0x' || lower(hex(account_root)), '0x') AS account_root
+    FROM
+        block_headers",
+    ),
+    (
+        "analytics_accounts",
+        "
+    SELECT
+        printf('0x%016x', accounts.account_id) AS account_id,
+        '0x' || lower(hex(accounts.account_hash)) AS account_hash,
+        accounts.nonce,
+        accounts.details IS NOT NULL AS public,
+        accounts.block_num,
+        datetime(block_headers.timestamp, 'unixepoch') AS updated_at
+    FROM
+        accounts
+    JOIN
+        block_headers ON block_headers.block_num = accounts.block_num",
+    ),
+    (
+        "analytics_notes",
+        "
+    SELECT
+        notes.block_num,
+        notes.batch_index,
+        notes.note_index,
+        '0x' || lower(hex(notes.note_hash)) AS note_id,
+        CASE notes.note_type
+            WHEN 1 THEN 'public'
+            WHEN 2 THEN 'offchain'
+            WHEN 3 THEN 'encrypted'
+        END AS note_type,
+        printf('0x%016x', notes.sender) AS sender,
+        printf('0x%08x', notes.tag) AS tag,
+        notes.tag_use_case,
+        CASE notes.tag_network_hint WHEN 1 THEN 'network' ELSE 'local' END AS tag_execution,
+        printf('0x%016x', notes.aux) AS aux,
+        nullif('0x' || lower(hex(notes.script_root)), '0x') AS script_root,
+        nullif('0x' || lower(hex(notes.nullifier)), '0x') AS nullifier,
+        datetime(block_headers.timestamp, 'unixepoch') AS created_at,
+        note_consumptions.block_num AS consumed_block_num,
+        nullif('0x' || lower(hex(note_consumptions.transaction_id)), '0x') AS consumed_by
+    FROM
+        notes
+    JOIN
+        block_headers ON block_headers.block_num = notes.block_num
+    LEFT JOIN
+        note_consumptions ON note_consumptions.note_id = notes.note_hash",
+    ),
+    (
+        "analytics_transactions",
+        "
+    SELECT
+        '0x' || lower(hex(transactions.transaction_id)) AS transaction_id,
+        printf('0x%016x', transactions.account_id) AS account_id,
+        transactions.block_num,
+        transactions.batch_index,
+        '0x' || lower(hex(transactions.initial_account_hash)) AS initial_account_hash,
+        '0x' || lower(hex(transactions.final_account_hash)) AS final_account_hash,
+        CASE
+            WHEN transactions.fee_faucet_id IS NULL THEN NULL
+            ELSE printf('0x%016x', transactions.fee_faucet_id)
+        END AS fee_faucet_id,
+        transactions.fee_amount,
+        transactions.cycles,
+        datetime(block_headers.timestamp, 'unixepoch') AS committed_at
+    FROM
+        transactions
+    JOIN
+        block_headers ON block_headers.block_num = transactions.block_num",
+    ),
+];
+
+/// Drops the views, so that the migrations can alter the columns they select.
+pub fn drop_views(conn: &Connection) -> rusqlite::Result<()> {
+    let views = conn
+        .prepare("SELECT name FROM sqlite_master WHERE type = 'view' AND name LIKE ?1 || '%';")?
+        .query_map([VIEW_PREFIX], |row| row.get::<_, String>(0))?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+
+    for view in views {
+        conn.execute_batch(&format!("DROP VIEW {view};"))?;
+    }
+
+    Ok(())
+}
+
+/// Creates the views, replacing the existing ones.
+///
+/// # Note
+///
+/// The [Transaction] object is not consumed. It's up to the caller to commit or rollback the
+/// transaction.
+pub fn create_views(transaction: &Transaction) -> rusqlite::Result<()> {
+    drop_views(transaction)?;
+
+    for (name, select) in VIEWS {
+        transaction.execute_batch(&format!("CREATE VIEW {name} AS{select};"))?;
+    }
+
+    create_nullifiers_view(transaction)
+}
+
+/// Creates the view of the nullifiers again over all their partitions, if the views were created.
+///
+/// Called once a partition is created, so that the view includes its nullifiers.
+pub fn update_nullifiers_view(transaction: &Transaction) -> rusqlite::Result<()> {
+    let exists: bool = transaction.query_row(
+        "SELECT EXISTS (SELECT 1 FROM sqlite_master WHERE type = 'view' AND name = ?1);",
+        [NULLIFIERS_VIEW],
+        |row| row.get(0),
+    )?;
+    if !exists {
+        return Ok(());
+    }
+
+    transaction.execute_batch(&format!("DROP VIEW {NULLIFIERS_VIEW};"))?;
+    create_nullifiers_view(transaction)
+}
+
+/// Creates the view of the nullifiers over their partitions, in the order of their epochs.
+fn create_nullifiers_view(transaction: &Transaction) -> rusqlite::Result<()> {
+    let partitions = transaction
+        .prepare("SELECT epoch FROM nullifier_partitions ORDER BY epoch;")?
+        .query_map([], |row| row.get(0))?
+        .map(|epoch| {
+            epoch.map(|epoch: u32| format!("SELECT * FROM {}", nullifier_partition(epoch)))
+        })
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+
+    // The partition of the first epoch always exists, see `migrations::partition_nullifiers`
+    transaction.execute_batch(&format!(
+        "
+    CREATE VIEW {NULLIFIERS_VIEW} AS
+    SELECT
+        '0x' || lower(hex(nullifiers.nullifier)) AS nullifier,
+        nullifiers.nullifier_prefix,
+        nullifiers.block_num,
+        datetime(block_headers.timestamp, 'unixepoch') AS consumed_at
+    FROM
+        ({}) AS nullifiers
+    JOIN
+        block_headers ON block_headers.block_num = nullifiers.block_num;",
+        partitions.join(" UNION ALL ")
+    ))
+}
@@ -1,6 +1,7 @@
-use std::io;
+use std::{io, path::PathBuf};
 
 use deadpool_sqlite::PoolError;
+use miden_node_utils::errors::ChainParametersError;
 use miden_objects::{
     crypto::{
         hash::rpo::RpoDigest,
@@ -28,6 +29,11 @@ pub enum NullifierTreeError {
         nullifier: Nullifier,
         block_num: BlockNumber,
     },
+    #[error("Nullifier tree can't be opened as of block {block_num}, its history starts at block {oldest}")]
+    HistoryPruned {
+        block_num: BlockNumber,
+        oldest: BlockNumber,
+    },
 }
 
 // DATABASE ERRORS
@@ -47,8 +53,12 @@ pub enum DatabaseError {
     AccountError(#[from] AccountError),
     #[error("Note error: {0}")]
     NoteError(#[from] NoteError),
+    #[error("Merkle error: {0}")]
+    MerkleError(#[from] MerkleError),
     #[error("SQLite pool interaction task failed: {0}")]
     InteractError(String),
+    #[error("Query was interrupted because the request was abandoned")]
+    QueryInterrupted,
     #[error("Deserialization of BLOB data from database failed: {0}")]
     DeserializationError(DeserializationError),
     #[error("Corrupted data: {0}")]
@@ -59,6 +69,20 @@ pub enum DatabaseError {
     AccountNotFoundInDb(AccountId),
     #[error("Account {0} is not on the chain")]
     AccountNotOnChain(AccountId),
+    #[error("Nonce {nonce} of account {account_id} doesn't increase its previous nonce {previous_nonce}")]
+    AccountNonceNotIncreasing {
+        account_id: AccountId,
+        previous_nonce: u64,
+        nonce: u64,
+    },
+    #[error("Note tree of block {0} is missing from the database")]
+    NoteTreeMissing(BlockNumber),
+    #[error("Details of the public account {0} are missing from the database")]
+    AccountDetailsMissing(AccountId),
+    #[error("Checkpoint of the write-ahead log was blocked by a concurrent connection")]
+    WalCheckpointBusy,
+    #[error("Backup of the database was blocked by a concurrent connection")]
+    BackupBusy,
     #[error("Failed to apply block because of on-chain account final hashes mismatch (expected {expected}, \
         but calculated is {calculated}")]
     ApplyBlockFailedAccountHashesMismatch {
@@ -84,6 +108,18 @@ pub enum StateInitializationError {
     FailedToCreateNullifierTree(NullifierTreeError),
     #[error("Failed to create accounts tree: {0}")]
     FailedToCreateAccountsTree(MerkleError),
+    #[error("Database contains no block headers, the genesis block must be applied first")]
+    MissingBlockHeaders,
+    #[error(
+        "Root of the {tree} tree {actual} doesn't match the root {expected} of the latest block \
+         #{block_num}"
+    )]
+    TreeRootMismatch {
+        tree: &'static str,
+        block_num: BlockNumber,
+        expected: RpoDigest,
+        actual: RpoDigest,
+    },
 }
 
 #[derive(Debug, Error)]
@@ -96,6 +132,30 @@ pub enum DatabaseSetupError {
     PoolBuildError(#[from] deadpool_sqlite::BuildError),
     #[error("SQLite migration error: {0}")]
     SqliteMigrationError(#[from] rusqlite_migration::Error),
+    #[error(
+        "Database {} is already used by another store, {owner}. A database can only be used by a \
+         single store at a time",
+        path.display()
+    )]
+    DatabaseInUse { path: PathBuf, owner: String },
+    #[error("Failed to lock the database with {}: {error}", path.display())]
+    LockFailed { path: PathBuf, error: io::Error },
+    #[error(
+        "Database {} is not a store database, its application id is {application_id:#010x}",
+        path.display()
+    )]
+    NotAStoreDatabase { path: PathBuf, application_id: i32 },
+    #[error(
+        "Database {} doesn't exist, a read-only store only serves an existing database",
+        path.display()
+    )]
+    MissingDatabase { path: PathBuf },
+    #[error(
+        "Database {} isn't migrated to the latest schema, a read-only store doesn't migrate it. \
+         Start a store which isn't read-only on it first",
+        path.display()
+    )]
+    OutdatedSchema { path: PathBuf },
 }
 
 #[derive(Debug, Error)]
@@ -118,8 +178,12 @@ pub enum GenesisError {
     },
     #[error("Malformed genesis state: {0}")]
     MalformedGenesisState(MerkleError),
+    #[error("Unsupported chain parameters in genesis file: {0}")]
+    InvalidChainParameters(#[from] ChainParametersError),
     #[error("Retrieving genesis block header failed: {0}")]
     SelectBlockHeaderByBlockNumError(Box<DatabaseError>),
+    #[error("The database has no genesis block, a read-only store can't store it")]
+    MissingGenesisBlock,
 }
 
 // ENDPOINT ERRORS
@@ -149,12 +213,16 @@ pub enum ApplyBlockError {
     NewBlockInvalidNullifierRoot,
     #[error("Duplicated nullifiers {0:?}")]
     DuplicatedNullifiers(Vec<Nullifier>),
+    #[error("Transaction receipt must have the number of the new block")]
+    TransactionInvalidBlockNum,
     #[error("Unable to create proof for note: {0}")]
     UnableToCreateProofForNote(MerkleError),
+    #[error("Block wasn't committed to the database, it can be applied again: {0}")]
+    CommitFailed(String),
     #[error("Block applying was broken because of closed channel on database side: {0}")]
     BlockApplyingBrokenBecauseOfClosedChannel(RecvError),
     #[error("Failed to create notes tree: {0}")]
-    FailedToCreateNoteTree(MerkleError),
+    FailedToCreateNoteTree(#[source] DatabaseError),
     #[error("Database doesn't have any block header data")]
     DbBlockHeaderEmpty,
     #[error("Failed to get MMR peaks for forest ({forest}): {error}")]
@@ -183,6 +251,140 @@ pub enum StateSyncError {
     DatabaseError(#[from] DatabaseError),
     #[error("Block headers table is empty")]
     EmptyBlockHeadersTable,
+    #[error("State sync from block {block_num} can't end at block {until}, the chain tip is {chain_tip}")]
+    InvalidSyncEnd {
+        block_num: BlockNumber,
+        until: BlockNumber,
+        chain_tip: BlockNumber,
+    },
     #[error("Failed to build MMR delta: {0}")]
     FailedToBuildMmrDelta(MmrError),
+    #[error("Failed to open MMR: {0}")]
+    FailedToOpenMmr(MmrError),
+}
+
+#[derive(Error, Debug)]
+pub enum AccountDeltasError {
+    #[error("Database error: {0}")]
+    DatabaseError(#[from] DatabaseError),
+    #[error("Account deltas are only stored from block {0}")]
+    DeltasNotStored(BlockNumber),
+}
+
+#[derive(Error, Debug)]
+pub enum NoteSyncError {
+    #[error("Database error: {0}")]
+    DatabaseError(#[from] DatabaseError),
+    #[error("Block headers table is empty")]
+    EmptyBlockHeadersTable,
+    #[error("Failed to open the block in the chain MMR: {0}")]
+    FailedToOpenMmr(MmrError),
+}
+
+#[derive(Error, Debug)]
+pub enum CheckNullifiersError {
+    #[error("Database error: {0}")]
+    DatabaseError(#[from] DatabaseError),
+    #[error("Block {0} doesn't exist")]
+    UnknownBlock(BlockNumber),
+    #[error("Failed to open the nullifier tree: {0}")]
+    FailedToOpenNullifierTree(#[from] NullifierTreeError),
+}
+
+#[derive(Error, Debug)]
+pub enum BlockInclusionProofError {
+    #[error("Database error: {0}")]
+    DatabaseError(#[from] DatabaseError),
+    #[error("Block {0} doesn't exist")]
+    UnknownBlock(BlockNumber),
+    #[error("Block {block_num} is not committed to by the chain root of block {chain_tip}, it must be an earlier block")]
+    NotCommitted {
+        block_num: BlockNumber,
+        chain_tip: BlockNumber,
+    },
+    #[error("Failed to open the block in the chain MMR: {0}")]
+    FailedToOpenMmr(MmrError),
+}
+
+#[derive(Error, Debug)]
+pub enum NoteAuthenticationInfoError {
+    #[error("Database error: {0}")]
+    DatabaseError(#[from] DatabaseError),
+    #[error("Block {0} doesn't exist")]
+    UnknownBlock(BlockNumber),
+    #[error("Block {block_num} has no note at index {note_index}")]
+    UnknownNote { block_num: BlockNumber, note_index: u32 },
+    #[error("Failed to rebuild the note tree of block {0}: {1}")]
+    FailedToBuildNoteTree(BlockNumber, #[source] DatabaseError),
+    #[error("The rebuilt note tree of block {0} doesn't match the note root of its header")]
+    NoteRootMismatch(BlockNumber),
+    #[error("Failed to open the note in the note tree: {0}")]
+    FailedToOpenNoteTree(MerkleError),
+}
+
+// NETWORK ERRORS
+// =================================================================================================
+
+#[derive(Error, Debug)]
+pub enum FollowError {
+    #[error("Failed to connect to the followed node: {0}")]
+    ConnectionFailed(#[from] tonic::transport::Error),
+    #[error("Block subscription failed: {0}")]
+    SubscriptionFailed(#[from] tonic::Status),
+    #[error("Failed to decode block {block_num}: {error}")]
+    DecodeFailed {
+        block_num: BlockNumber,
+        error: prost::DecodeError,
+    },
+    #[error("Block {block_num} is invalid: {status}")]
+    InvalidBlock {
+        block_num: BlockNumber,
+        status: tonic::Status,
+    },
+    #[error("Block {0} is not signed by the sequencer")]
+    InvalidSignature(BlockNumber),
+    #[error("Block {0} is the last block number, no block can follow it")]
+    ChainEnded(BlockNumber),
+    #[error("Block {received} was received instead of block {expected}")]
+    UnexpectedBlock {
+        expected: BlockNumber,
+        received: BlockNumber,
+    },
+    #[error("Failed to apply block: {0}")]
+    ApplyBlockFailed(#[from] ApplyBlockError),
+}
+
+// BACKUP ERRORS
+// =================================================================================================
+
+#[derive(Error, Debug)]
+pub enum BackupError {
+    #[error("Database error: {0}")]
+    DatabaseError(#[from] DatabaseError),
+    #[error("Failed to access backup path {path}: {error}")]
+    IoError { path: PathBuf, error: io::Error },
+    #[error("Upload command `{command}` failed: {reason}")]
+    UploadFailed { command: String, reason: String },
+}
+
+#[derive(Error, Debug)]
+pub enum BackfillError {
+    #[error("Database error: {0}")]
+    DatabaseError(#[from] DatabaseError),
+    #[error("Failed to set up the output database: {0}")]
+    DatabaseSetupError(#[from] DatabaseSetupError),
+    #[error("Failed to load the state of the output database: {0}")]
+    StateInitializationError(#[from] StateInitializationError),
+    #[error("Output database {} already exists", .0.display())]
+    OutputExists(PathBuf),
+    #[error("Block {0} is not stored in the source database, the blocks can't be replayed")]
+    MissingBlock(BlockNumber),
+    #[error("Block {block_num} can't be replayed: {reason}")]
+    InvalidBlock { block_num: BlockNumber, reason: String },
+    #[error("Replayed block {block_num} has hash {actual}, the source database has {expected}")]
+    BlockHashMismatch {
+        block_num: BlockNumber,
+        expected: RpoDigest,
+        actual: RpoDigest,
+    },
 }
@@ -1,9 +1,11 @@
+use miden_node_utils::chain_params::{ChainParameters, ACCOUNT_TREE_DEPTH, MAX_NOTES_PER_BATCH};
 use miden_objects::{
     accounts::Account,
-    crypto::merkle::{EmptySubtreeRoots, MerkleError, MmrPeaks, SimpleSmt, Smt},
-    notes::NOTE_LEAF_DEPTH,
+    block::BlockNoteTree,
+    crypto::merkle::{MerkleError, MmrPeaks, SimpleSmt, Smt},
+    notes::Note,
     utils::serde::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable},
-    BlockHeader, Digest, ACCOUNT_TREE_DEPTH, GENESIS_BLOCK,
+    BlockHeader, Digest, GENESIS_BLOCK,
 };
 
 // GENESIS STATE
@@ -13,34 +15,59 @@ use miden_objects::{
 #[derive(Debug, PartialEq, Eq)]
 pub struct GenesisState {
     pub accounts: Vec<Account>,
+    /// Notes created by the genesis block, e.g. P2ID notes funding the genesis accounts.
+    ///
+    /// The notes fill the batches of the genesis block in order, see [genesis_note_position].
+    pub notes: Vec<Note>,
     pub version: u64,
     pub timestamp: u64,
+    /// Parameters of the chain, committed to by the `proof_hash` of the genesis block header as
+    /// the genesis block has no proof. Not set by the genesis files created before they existed.
+    pub parameters: Option<ChainParameters>,
 }
 
 impl GenesisState {
-    pub fn new(accounts: Vec<Account>, version: u64, timestamp: u64) -> Self {
-        Self { accounts, version, timestamp }
+    pub fn new(accounts: Vec<Account>, notes: Vec<Note>, version: u64, timestamp: u64) -> Self {
+        Self {
+            accounts,
+            notes,
+            version,
+            timestamp,
+            parameters: None,
+        }
     }
 
-    /// Returns the block header and the account SMT
+    /// Sets the parameters of the chain.
+    pub fn with_parameters(mut self, parameters: ChainParameters) -> Self {
+        self.parameters = Some(parameters);
+        self
+    }
+
+    /// Returns the block header, the account SMT and the note tree
     pub fn into_block_parts(
         self,
-    ) -> Result<(BlockHeader, SimpleSmt<ACCOUNT_TREE_DEPTH>), MerkleError> {
+    ) -> Result<(BlockHeader, SimpleSmt<ACCOUNT_TREE_DEPTH>, BlockNoteTree), MerkleError> {
         let account_smt: SimpleSmt<ACCOUNT_TREE_DEPTH> = SimpleSmt::with_leaves(
             self.accounts
                 .into_iter()
                 .map(|account| (account.id().into(), account.hash().into())),
         )?;
 
+        let note_tree =
+            BlockNoteTree::with_entries(self.notes.iter().enumerate().map(|(index, note)| {
+                let (batch_index, note_index) = genesis_note_position(index);
+                (batch_index, note_index, (note.id().into(), *note.metadata()))
+            }))?;
+
         let block_header = BlockHeader::new(
             Digest::default(),
             GENESIS_BLOCK,
             MmrPeaks::new(0, Vec::new()).unwrap().hash_peaks(),
             account_smt.root(),
             Smt::default().root(),
-            *EmptySubtreeRoots::entry(NOTE_LEAF_DEPTH, 0),
-            Digest::default(),
+            note_tree.root(),
             Digest::default(),
+            self.parameters.as_ref().map(ChainParameters::commitment).unwrap_or_default(),
             self.version
                 .try_into()
                 .expect("version value is greater than or equal to the field modulus"),
@@ -49,10 +76,15 @@ impl GenesisState {
                 .expect("timestamp value is greater than or equal to the field modulus"),
         );
 
-        Ok((block_header, account_smt))
+        Ok((block_header, account_smt, note_tree))
     }
 }
 
+/// Returns the batch index and the index in the batch of the `index`-th note of the genesis block.
+pub fn genesis_note_position(index: usize) -> (usize, usize) {
+    (index / MAX_NOTES_PER_BATCH, index % MAX_NOTES_PER_BATCH)
+}
+
 // SERIALIZATION
 // ================================================================================================
 
@@ -62,8 +94,17 @@ impl Serializable for GenesisState {
         target.write_usize(self.accounts.len());
         target.write_many(&self.accounts);
 
+        assert!(self.notes.len() <= u64::MAX as usize, "too many notes in GenesisState");
+        target.write_usize(self.notes.len());
+        target.write_many(&self.notes);
+
         target.write_u64(self.version);
         target.write_u64(self.timestamp);
+
+        // Left out when not set, for the genesis files created before the parameters existed
+        if let Some(parameters) = &self.parameters {
+            parameters.write_into(target);
+        }
     }
 }
 
@@ -72,9 +113,22 @@ impl Deserializable for GenesisState {
         let num_accounts = source.read_usize()?;
         let accounts = source.read_many::<Account>(num_accounts)?;
 
+        let num_notes = source.read_usize()?;
+        let notes = source.read_many::<Note>(num_notes)?;
+
         let version = source.read_u64()?;
         let timestamp = source.read_u64()?;
+        let parameters = source
+            .has_more_bytes()
+            .then(|| ChainParameters::read_from(source))
+            .transpose()?;
 
-        Ok(Self::new(accounts, version, timestamp))
+        Ok(Self {
+            accounts,
+            notes,
+            version,
+            timestamp,
+            parameters,
+        })
     }
 }
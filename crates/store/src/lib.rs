@@ -1,12 +1,77 @@
+use std::time::Duration;
+
+use miden_node_proto::domain::pagination::PageSize;
+use miden_node_utils::chain_params::NULLIFIER_PREFIX_LEN;
+
+pub mod backfill;
+pub mod backup;
+mod cache;
 pub mod config;
 pub mod db;
 pub mod errors;
 pub mod genesis;
+pub mod network;
 mod nullifier_tree;
 pub mod server;
 pub mod state;
+pub mod subscriptions;
 pub mod types;
 
 // CONSTANTS
 // =================================================================================================
 pub const COMPONENT: &str = "miden-store";
+
+/// Number of high bits of the nullifier prefixes requested by clients, when not specified.
+pub const DEFAULT_NULLIFIER_PREFIX_LEN: u32 = NULLIFIER_PREFIX_LEN;
+
+/// Shortest nullifier prefix accepted from clients. Shorter prefixes match more nullifiers, this
+/// bounds the size of the responses.
+pub const MIN_NULLIFIER_PREFIX_LEN: u32 = 8;
+
+/// Longest nullifier prefix accepted from clients, prefixes are sent as `u32`.
+pub const MAX_NULLIFIER_PREFIX_LEN: u32 = 32;
+
+/// Maximum number of blocks with matching notes in a state sync response.
+pub const MAX_STATE_SYNC_BLOCKS: u32 = 100;
+
+/// Maximum number of notes in a state sync response, unless the first block has more notes.
+pub const MAX_STATE_SYNC_NOTES: u32 = 1000;
+
+/// Sizes of the pages of `GetNullifiersByPrefix`, in nullifiers, unless the first block of a page
+/// has more nullifiers.
+pub const NULLIFIERS_PAGE_SIZE: PageSize = PageSize { default: 1000, max: 1000 };
+
+/// Sizes of the pages of the `List*` endpoints.
+pub const LIST_PAGE_SIZE: PageSize = PageSize { default: 100, max: 1000 };
+
+/// Number of blocks before the chain tip as of which `CheckNullifiers` can open the nullifiers.
+pub const NULLIFIER_TREE_HISTORY: u32 = 256;
+
+/// Sizes of the pages of `GetAccountDeltas`, in deltas.
+pub const ACCOUNT_DELTAS_PAGE_SIZE: PageSize = PageSize { default: 100, max: 100 };
+
+/// Storage slot holding the authentication public key of the accounts, by the convention of the
+/// wallets and faucets of `miden-lib`. Indexed by the account public key index.
+pub const ACCOUNT_AUTH_KEY_SLOT: u8 = 0;
+
+/// Number of blocks loaded at once from the database by a block subscription, and buffered until
+/// they are sent to the replica.
+pub const SUBSCRIPTION_PAGE_SIZE: usize = 16;
+
+/// Maximum number of aux ranges filtering the synced or listed notes.
+pub const MAX_AUX_RANGES: usize = 16;
+
+/// Maximum number of accounts watched by an account subscription.
+pub const MAX_SUBSCRIBED_ACCOUNTS: usize = 1000;
+
+/// Number of account updates buffered for a subscriber, a subscriber lagging further behind is
+/// disconnected.
+pub const ACCOUNT_SUBSCRIPTION_BUFFER: usize = 64;
+
+/// Number of rows selected at once when loading the nullifiers, account hashes and block headers
+/// into memory on startup.
+pub const STATE_LOAD_CHUNK_SIZE: usize = 100_000;
+
+/// Delay before a replica reconnects to the followed node, after the connection is lost or a
+/// block is rejected.
+pub const FOLLOW_RETRY_INTERVAL: Duration = Duration::from_secs(5);
@@ -0,0 +1,247 @@
+//! Propagation of the blocks from the sequencer to the replica nodes.
+//!
+//! The store of every node streams the blocks it applied to the nodes subscribing to it, through
+//! the `GetBlocks` endpoint of the node's RPC. The replicas run only the store and the RPC, their
+//! store subscribes to the sequencer or to another replica and applies the streamed blocks once
+//! they are validated, so the RPC can be scaled out without running a block producer per node.
+use std::sync::{
+    atomic::{AtomicBool, AtomicU32, Ordering},
+    Arc,
+};
+
+use miden_node_proto::generated::{
+    requests::{ApplyBlockRequest, GetChainTipRequest, SubscribeBlocksRequest},
+    responses::{FollowerStatus, SubscribeBlocksResponse},
+    rpc::api_client as rpc_client,
+};
+use miden_node_utils::transport::ComponentChannel;
+use miden_objects::{
+    crypto::dsa::rpo_falcon512::{PublicKey, Signature},
+    BlockHeader,
+};
+use prost::Message;
+use tokio::{
+    sync::mpsc::{self, Sender},
+    time,
+};
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::Status;
+use tracing::{debug, info, warn};
+
+use crate::{
+    errors::FollowError, server::api::block_update, state::State, types::BlockNumber, COMPONENT,
+    FOLLOW_RETRY_INTERVAL, SUBSCRIPTION_PAGE_SIZE,
+};
+
+// BLOCK SUBSCRIPTION
+// ================================================================================================
+
+/// Stream of the blocks sent to a replica.
+pub type BlockSubscription = ReceiverStream<Result<SubscribeBlocksResponse, Status>>;
+
+/// Returns a stream of the applied blocks starting from the block `block_num`, followed by the new
+/// blocks as they are applied.
+pub fn subscribe_blocks(state: Arc<State>, block_num: BlockNumber) -> BlockSubscription {
+    let (sender, receiver) = mpsc::channel(SUBSCRIPTION_PAGE_SIZE);
+    tokio::spawn(stream_blocks(state, block_num, sender));
+
+    ReceiverStream::new(receiver)
+}
+
+/// Sends the blocks to `sender` until the subscriber disconnects.
+async fn stream_blocks(
+    state: Arc<State>,
+    mut block_num: BlockNumber,
+    sender: Sender<Result<SubscribeBlocksResponse, Status>>,
+) {
+    let mut chain_tip = state.subscribe_chain_tip();
+
+    loop {
+        // Marked as seen before reading the blocks, a block committed after the read notifies the
+        // receiver
+        chain_tip.borrow_and_update();
+
+        let blocks = match state.get_blocks(block_num, SUBSCRIPTION_PAGE_SIZE).await {
+            Ok(blocks) => blocks,
+            Err(err) => {
+                let _ = sender.send(Err(Status::internal(err.to_string()))).await;
+                return;
+            },
+        };
+        let is_last_page = blocks.len() < SUBSCRIPTION_PAGE_SIZE;
+
+        for (num, block) in blocks {
+            if sender
+                .send(Ok(SubscribeBlocksResponse { block_num: num.into(), block }))
+                .await
+                .is_err()
+            {
+                return;
+            }
+            // No block follows the last block number
+            let Some(child) = num.child() else {
+                return;
+            };
+            block_num = child;
+        }
+
+        if is_last_page {
+            tokio::select! {
+                changed = chain_tip.changed() => if changed.is_err() { return },
+                _ = sender.closed() => return,
+            }
+        }
+    }
+}
+
+// FOLLOWER
+// ================================================================================================
+
+/// Progress of the replica, reported by the store's status.
+#[derive(Debug, Default)]
+pub struct FollowProgress {
+    /// Latest chain tip of the followed node known by the replica
+    upstream_chain_tip: AtomicU32,
+    /// Set while the replica is subscribed to the followed node's blocks
+    connected: AtomicBool,
+}
+
+impl FollowProgress {
+    /// Returns the status of the replica whose latest applied block is `chain_tip`.
+    pub fn status(&self, chain_tip: BlockNumber) -> FollowerStatus {
+        let upstream_chain_tip = self.upstream_chain_tip.load(Ordering::Relaxed);
+
+        FollowerStatus {
+            upstream_chain_tip,
+            sync_lag: upstream_chain_tip.saturating_sub(chain_tip.as_u32()),
+            connected: self.connected.load(Ordering::Relaxed),
+        }
+    }
+
+    fn observe_upstream_block(&self, block_num: BlockNumber) {
+        self.upstream_chain_tip.fetch_max(block_num.as_u32(), Ordering::Relaxed);
+    }
+}
+
+/// Applies the blocks of the node whose RPC is at `rpc_url`, reconnecting to it when the
+/// connection is lost or a block is rejected.
+///
+/// The headers and the roots of the blocks are validated against the local state, and if
+/// `public_key` is set the blocks not signed with it are rejected.
+pub async fn follow(
+    state: Arc<State>,
+    rpc_url: String,
+    public_key: Option<PublicKey>,
+    progress: Arc<FollowProgress>,
+) {
+    loop {
+        let result = follow_node(&state, &rpc_url, public_key, &progress).await;
+        progress.connected.store(false, Ordering::Relaxed);
+
+        match result {
+            Ok(()) => warn!(target: COMPONENT, "Block subscription closed by the followed node"),
+            Err(err) => warn!(target: COMPONENT, %err, "Following the node failed"),
+        }
+
+        time::sleep(FOLLOW_RETRY_INTERVAL).await;
+    }
+}
+
+/// Subscribes to the blocks of the followed node after the local chain tip, and applies them until
+/// the subscription ends.
+async fn follow_node(
+    state: &State,
+    rpc_url: &str,
+    public_key: Option<PublicKey>,
+    progress: &FollowProgress,
+) -> Result<(), FollowError> {
+    let channel = ComponentChannel::connect(rpc_url.to_string()).await?;
+    let mut client = rpc_client::ApiClient::new(channel);
+
+    let upstream_chain_tip = client.get_chain_tip(GetChainTipRequest {}).await?.into_inner();
+    progress.observe_upstream_block(upstream_chain_tip.block_num.into());
+
+    let chain_tip = state.chain_tip().block_num;
+    let mut expected = chain_tip.child().ok_or(FollowError::ChainEnded(chain_tip))?;
+    info!(target: COMPONENT, rpc_url, block_num = %expected, "Subscribing to the followed node's blocks");
+
+    let mut blocks = client
+        .get_blocks(SubscribeBlocksRequest { block_num: expected.into() })
+        .await?
+        .into_inner();
+    progress.connected.store(true, Ordering::Relaxed);
+
+    while let Some(response) = blocks.message().await? {
+        let block_num = BlockNumber::from(response.block_num);
+        progress.observe_upstream_block(block_num);
+        let request = ApplyBlockRequest::decode(response.block.as_slice())
+            .map_err(|error| FollowError::DecodeFailed { block_num, error })?;
+        let block = block_update(request)
+            .map_err(|status| FollowError::InvalidBlock { block_num, status })?;
+
+        let received = BlockNumber::from(block.block_header.block_num());
+        if block_num != expected || received != expected {
+            return Err(FollowError::UnexpectedBlock { expected, received });
+        }
+        if let Some(public_key) = public_key {
+            if !is_signed_by(&block.block_header, block.signature.as_ref(), public_key) {
+                return Err(FollowError::InvalidSignature(block_num));
+            }
+        }
+
+        block.apply(state).await?;
+        debug!(target: COMPONENT, %block_num, "Applied the followed node's block");
+
+        expected = expected.child().ok_or(FollowError::ChainEnded(expected))?;
+    }
+
+    Ok(())
+}
+
+/// Returns `true` if `signature` is the signature of `block_header` with the key `public_key`.
+fn is_signed_by(
+    block_header: &BlockHeader,
+    signature: Option<&Signature>,
+    public_key: PublicKey,
+) -> bool {
+    signature.is_some_and(|signature| public_key.verify(block_header.hash().into(), signature))
+}
+
+#[cfg(test)]
+mod tests {
+    use miden_objects::{crypto::dsa::rpo_falcon512::SecretKey, BlockHeader, Digest, ONE, ZERO};
+
+    use super::{is_signed_by, FollowProgress};
+    use crate::types::BlockNumber;
+
+    #[test]
+    fn only_blocks_signed_by_the_sequencer_are_accepted() {
+        let digest = Digest::default();
+        let block_header =
+            BlockHeader::new(digest, 1, digest, digest, digest, digest, digest, digest, ZERO, ONE);
+        let sequencer_key = SecretKey::new();
+        let signature = sequencer_key.sign(block_header.hash().into());
+
+        let public_key = sequencer_key.public_key();
+        assert!(is_signed_by(&block_header, Some(&signature), public_key));
+        assert!(!is_signed_by(&block_header, None, public_key));
+
+        let other_signature = SecretKey::new().sign(block_header.hash().into());
+        assert!(!is_signed_by(&block_header, Some(&other_signature), public_key));
+    }
+
+    #[test]
+    fn sync_lag_is_the_number_of_blocks_behind_the_followed_node() {
+        let progress = FollowProgress::default();
+        progress.observe_upstream_block(BlockNumber::new(10));
+        progress.observe_upstream_block(BlockNumber::new(7));
+
+        let status = progress.status(BlockNumber::new(4));
+        assert_eq!(status.upstream_chain_tip, 10);
+        assert_eq!(status.sync_lag, 6);
+        assert!(!status.connected);
+
+        // The replica may apply a block before observing the followed node's chain tip
+        assert_eq!(progress.status(BlockNumber::new(11)).sync_lag, 0);
+    }
+}
@@ -1,40 +1,108 @@
+use std::{
+    collections::BTreeMap,
+    ops::Bound::{Excluded, Unbounded},
+    sync::Arc,
+};
+
 use miden_objects::{
     crypto::{
-        hash::rpo::RpoDigest,
-        merkle::{Smt, SmtProof},
+        hash::rpo::{Rpo256, RpoDigest},
+        merkle::{MerklePath, NodeIndex, Smt, SmtLeaf, SmtProof},
     },
     notes::Nullifier,
     Felt, FieldElement, Word,
 };
 
-use crate::{errors::NullifierTreeError, types::BlockNumber};
+use crate::{errors::NullifierTreeError, types::BlockNumber, NULLIFIER_TREE_HISTORY};
 
 /// Nullifier SMT.
+///
+/// The tree keeps the values its nodes had before each of the last [NULLIFIER_TREE_HISTORY]
+/// blocks, so that it can also be opened as of these blocks. The nodes updated by a block are
+/// copied on write, shared by the clones of the tree.
 #[derive(Debug, Clone)]
-pub struct NullifierTree(Smt);
+pub struct NullifierTree {
+    smt: Smt,
+    /// Previous value of each node updated by the nullifiers of a block, per block
+    history: BTreeMap<BlockNumber, Arc<BTreeMap<NodeIndex, RpoDigest>>>,
+    /// Oldest block as of which the tree can be opened
+    oldest: BlockNumber,
+}
 
 impl NullifierTree {
     /// Construct new nullifier tree from list of items.
+    ///
+    /// The nullifiers of the last [NULLIFIER_TREE_HISTORY] blocks are inserted one block after the
+    /// other, to rebuild the history of the tree.
     pub fn with_entries(
         entries: impl IntoIterator<Item = (Nullifier, BlockNumber)>,
     ) -> Result<Self, NullifierTreeError> {
-        let leaves = entries.into_iter().map(|(nullifier, block_num)| {
+        let entries: Vec<_> = entries.into_iter().collect();
+        let latest = entries.iter().map(|&(_, block_num)| block_num).max().unwrap_or_default();
+        let oldest = BlockNumber::new(latest.as_u32().saturating_sub(NULLIFIER_TREE_HISTORY));
+
+        let (mut recent, older): (Vec<_>, Vec<_>) =
+            entries.into_iter().partition(|&(_, block_num)| block_num > oldest);
+        let leaves = older.into_iter().map(|(nullifier, block_num)| {
             (nullifier.inner(), Self::block_num_to_leaf_value(block_num))
         });
 
-        let inner = Smt::with_entries(leaves)?;
+        let smt = Smt::with_entries(leaves)?;
+        let mut tree = Self { smt, history: BTreeMap::new(), oldest };
 
-        Ok(Self(inner))
+        recent.sort_by_key(|&(_, block_num)| block_num);
+        for (nullifier, block_num) in recent {
+            tree.insert(&nullifier, block_num)?;
+        }
+
+        Ok(tree)
     }
 
     /// Get SMT root.
     pub fn root(&self) -> RpoDigest {
-        self.0.root()
+        self.smt.root()
     }
 
     /// Returns an opening of the leaf associated with the given nullifier.
     pub fn open(&self, nullifier: &Nullifier) -> SmtProof {
-        self.0.open(&nullifier.inner())
+        self.smt.open(&nullifier.inner())
+    }
+
+    /// Returns an opening of the leaf associated with the given nullifier as of `block_num`, i.e.
+    /// without the nullifiers consumed in later blocks. Its root is the nullifier root of the
+    /// header of `block_num`.
+    ///
+    /// `block_num` must not be later than the latest block of the tree, and not earlier than
+    /// [NULLIFIER_TREE_HISTORY] blocks before it.
+    pub fn open_at(
+        &self,
+        nullifier: &Nullifier,
+        block_num: BlockNumber,
+    ) -> Result<SmtProof, NullifierTreeError> {
+        if block_num < self.oldest {
+            return Err(NullifierTreeError::HistoryPruned { block_num, oldest: self.oldest });
+        }
+
+        let (path, leaf) = self.smt.open(&nullifier.inner()).into_parts();
+        let leaf_index = leaf.index();
+        let entries = leaf
+            .into_entries()
+            .into_iter()
+            .filter(|&(_, value)| Self::leaf_value_to_block_num(value) <= block_num)
+            .collect();
+        let leaf = SmtLeaf::new(entries, leaf_index).expect("entries are from the same leaf");
+
+        let mut index = NodeIndex::from(leaf_index);
+        let path: Vec<_> = path
+            .iter()
+            .map(|&sibling| {
+                let sibling = self.node_at(index.sibling(), block_num).unwrap_or(sibling);
+                index.move_up();
+                sibling
+            })
+            .collect();
+
+        Ok(SmtProof::new(path.into(), leaf).expect("path has the depth of the tree"))
     }
 
     /// Inserts block number in which nullifier was consumed.
@@ -44,7 +112,7 @@ impl NullifierTree {
         block_num: BlockNumber,
     ) -> Result<(), NullifierTreeError> {
         let key = nullifier.inner();
-        let prev_value = self.0.get_value(&key);
+        let prev_value = self.smt.get_value(&key);
         if prev_value != Smt::EMPTY_VALUE {
             return Err(NullifierTreeError::NullifierAlreadyExists {
                 nullifier: *nullifier,
@@ -52,7 +120,9 @@ impl NullifierTree {
             });
         }
 
-        self.0.insert(key, Self::block_num_to_leaf_value(block_num));
+        let (path, leaf) = self.smt.open(&key).into_parts();
+        self.record_path(&leaf, &path, block_num);
+        self.smt.insert(key, Self::block_num_to_leaf_value(block_num));
 
         Ok(())
     }
@@ -60,7 +130,7 @@ impl NullifierTree {
     /// Returns block number stored for the given nullifier or `None` if the nullifier wasn't
     /// consumed.
     pub fn get_block_num(&self, nullifier: &Nullifier) -> Option<BlockNumber> {
-        let value = self.0.get_value(&nullifier.inner());
+        let value = self.smt.get_value(&nullifier.inner());
         if value == Smt::EMPTY_VALUE {
             return None;
         }
@@ -68,9 +138,41 @@ impl NullifierTree {
         Some(Self::leaf_value_to_block_num(value))
     }
 
+    /// Records the values of the nodes from `leaf` to the root, before they are updated by the
+    /// insertion of a nullifier of `block_num`, and prunes the history of the blocks which are
+    /// now more than [NULLIFIER_TREE_HISTORY] blocks old.
+    fn record_path(&mut self, leaf: &SmtLeaf, path: &MerklePath, block_num: BlockNumber) {
+        let oldest = BlockNumber::new(block_num.as_u32().saturating_sub(NULLIFIER_TREE_HISTORY));
+        if oldest > self.oldest {
+            let first_kept =
+                oldest.child().expect("the oldest block is below the last block number");
+            self.history = self.history.split_off(&first_kept);
+            self.oldest = oldest;
+        }
+
+        // only the values before the first update of the block are kept
+        let nodes = Arc::make_mut(self.history.entry(block_num).or_default());
+        let mut index = NodeIndex::from(leaf.index());
+        let mut value = leaf.hash();
+        for sibling in path.iter() {
+            nodes.entry(index).or_insert(value);
+            value = Rpo256::merge(&index.build_node(value, *sibling));
+            index.move_up();
+        }
+        nodes.entry(index).or_insert(value);
+    }
+
+    /// Returns the value of the node at `index` as of `block_num`, [None] if it wasn't updated
+    /// since.
+    fn node_at(&self, index: NodeIndex, block_num: BlockNumber) -> Option<RpoDigest> {
+        self.history
+            .range((Excluded(block_num), Unbounded))
+            .find_map(|(_, nodes)| nodes.get(&index).copied())
+    }
+
     /// Returns the nullifier's leaf value in the SMT by its block number.
     fn block_num_to_leaf_value(block: BlockNumber) -> Word {
-        [Felt::from(block), Felt::ZERO, Felt::ZERO, Felt::ZERO]
+        [Felt::from(block.as_u32()), Felt::ZERO, Felt::ZERO, Felt::ZERO]
     }
 
     /// Given the leaf value of the nullifier SMT, returns the nullifier's block number.
@@ -84,24 +186,74 @@ impl NullifierTree {
 
 #[cfg(test)]
 mod tests {
-    use miden_objects::{Felt, ZERO};
+    use miden_objects::{notes::Nullifier, Felt, ONE, ZERO};
 
     use super::NullifierTree;
+    use crate::{errors::NullifierTreeError, types::BlockNumber, NULLIFIER_TREE_HISTORY};
+
+    /// Returns a nullifier sharing its leaf with the nullifier of the adjacent seed.
+    fn nullifier(seed: u64) -> Nullifier {
+        let leaf = (seed / 2).wrapping_mul(0x9e37_79b9_7f4a_7c15);
+        Nullifier::from([Felt::new(seed), ONE, ZERO, Felt::new(leaf)])
+    }
 
     #[test]
     fn test_leaf_value_encoding() {
-        let block_num = 123;
+        let block_num = BlockNumber::new(123);
         let nullifier_value = NullifierTree::block_num_to_leaf_value(block_num);
 
-        assert_eq!(nullifier_value, [Felt::from(block_num), ZERO, ZERO, ZERO])
+        assert_eq!(nullifier_value, [Felt::from(block_num.as_u32()), ZERO, ZERO, ZERO])
     }
 
     #[test]
     fn test_leaf_value_decoding() {
-        let block_num = 123;
-        let nullifier_value = [Felt::from(block_num), ZERO, ZERO, ZERO];
+        let block_num = BlockNumber::new(123);
+        let nullifier_value = [Felt::from(block_num.as_u32()), ZERO, ZERO, ZERO];
         let decoded_block_num = NullifierTree::leaf_value_to_block_num(nullifier_value);
 
         assert_eq!(decoded_block_num, block_num);
     }
+
+    #[test]
+    fn test_open_at_previous_blocks() {
+        // nullifiers 0..30 consumed in blocks 1..=10, three per block
+        let entries: Vec<_> =
+            (0..30).map(|i| (nullifier(i), BlockNumber::new(i as u32 / 3 + 1))).collect();
+        let loaded = NullifierTree::with_entries(entries.clone()).unwrap();
+
+        let mut applied = NullifierTree::with_entries([]).unwrap();
+        for (nullifier, block_num) in &entries {
+            applied.insert(nullifier, *block_num).unwrap();
+        }
+        assert_eq!(loaded.root(), applied.root());
+
+        for block_num in 0..=11 {
+            let block_num = BlockNumber::new(block_num);
+            let expected = NullifierTree::with_entries(
+                entries.iter().copied().filter(|&(_, consumed_in)| consumed_in <= block_num),
+            )
+            .unwrap();
+
+            for nullifier in (0..35).map(nullifier) {
+                let proof = expected.open(&nullifier);
+                assert_eq!(loaded.open_at(&nullifier, block_num).unwrap(), proof);
+                assert_eq!(applied.open_at(&nullifier, block_num).unwrap(), proof);
+                assert_eq!(proof.compute_root(), expected.root());
+            }
+        }
+    }
+
+    #[test]
+    fn test_open_at_pruned_block() {
+        let latest = NULLIFIER_TREE_HISTORY + 10;
+        let entries = (1..=latest).map(|block_num| (nullifier(block_num.into()), block_num.into()));
+        let tree = NullifierTree::with_entries(entries).unwrap();
+
+        let oldest = BlockNumber::new(10);
+        assert!(tree.open_at(&nullifier(1), oldest).is_ok());
+        assert!(matches!(
+            tree.open_at(&nullifier(1), BlockNumber::new(9)),
+            Err(NullifierTreeError::HistoryPruned { oldest: err_oldest, .. }) if err_oldest == oldest
+        ));
+    }
 }
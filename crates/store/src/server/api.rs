@@ -1,48 +1,119 @@
-use std::sync::Arc;
+use std::{collections::BTreeSet, fmt::Display, sync::Arc};
 
 use miden_node_proto::{
     convert,
-    domain::accounts::AccountUpdateDetails,
+    domain::{
+        accounts::AccountUpdateDetails,
+        merkle::MerklePathNodes,
+        pagination::{next_page_cursor, PageParams, PageTokens},
+        transactions::TransactionReceipt,
+        version::{PROTOCOL_VERSION, PROTO_VERSION},
+    },
     errors::ConversionError,
     generated::{
         self,
-        account::AccountSummary,
         note::NoteSyncRecord,
         requests::{
-            ApplyBlockRequest, CheckNullifiersRequest, GetAccountDetailsRequest,
-            GetBlockHeaderByNumberRequest, GetBlockInputsRequest, GetNotesByIdRequest,
-            GetTransactionInputsRequest, ListAccountsRequest, ListNotesRequest,
-            ListNullifiersRequest, SyncStateRequest,
+            ApplyBlockRequest, CheckNullifiersByPrefixRequest, CheckNullifiersRequest,
+            FlushDatabaseRequest, GetAccountDeltasRequest, GetAccountDetailsRequest,
+            GetAccountsByPubKeyRequest, GetBlockHeaderByNumberRequest,
+            GetBlockInclusionProofRequest, GetBlockInputsRequest, GetBlockProofRequest,
+            GetChainParametersRequest, GetChainTipRequest, GetDatabaseSchemaRequest,
+            GetGenesisRequest, GetNoteAuthenticationInfoRequest, GetNoteConsumptionRequest,
+            GetNoteScriptByRootRequest, GetNotesByIdRequest, GetNullifiersByPrefixRequest,
+            GetStoreStatusRequest, GetTransactionInputsRequest, GetTransactionReceiptRequest,
+            GetVersionRequest, ListAccountsRequest, ListNotesRequest, ListNullifiersRequest,
+            SetLogFilterRequest, SubscribeAccountsRequest, SubscribeBlocksRequest,
+            SyncNotesRequest, SyncStateRequest,
         },
         responses::{
-            AccountTransactionInputRecord, ApplyBlockResponse, CheckNullifiersResponse,
-            GetAccountDetailsResponse, GetBlockHeaderByNumberResponse, GetBlockInputsResponse,
-            GetNotesByIdResponse, GetTransactionInputsResponse, ListAccountsResponse,
-            ListNotesResponse, ListNullifiersResponse, NullifierTransactionInputRecord,
-            NullifierUpdate, SyncStateResponse,
+            AccountDeltaUpdate, AccountTransactionInputRecord, ApplyBlockResponse,
+            CheckNullifiersByPrefixResponse, CheckNullifiersResponse, FlushDatabaseResponse,
+            GetAccountDeltasResponse, GetAccountDetailsResponse, GetAccountsByPubKeyResponse,
+            GetBlockHeaderByNumberResponse, GetBlockInclusionProofResponse, GetBlockInputsResponse,
+            GetBlockProofResponse, GetChainParametersResponse, GetChainTipResponse,
+            GetDatabaseSchemaResponse, GetGenesisResponse, GetNoteAuthenticationInfoResponse,
+            GetNoteConsumptionResponse, GetNoteScriptByRootResponse, GetNotesByIdResponse,
+            GetNullifiersByPrefixResponse, GetStoreStatusResponse, GetTransactionInputsResponse,
+            GetTransactionReceiptResponse, GetVersionResponse, ListAccountsResponse,
+            ListNotesResponse, ListNullifiersResponse, NoteAuthenticationInfo, NoteConsumption,
+            NullifierTransactionInputRecord, NullifierUpdate, SetLogFilterResponse,
+            SyncNotesResponse, SyncStateResponse, SyncedBlock,
         },
         smt::SmtLeafEntry,
         store::api_server,
     },
     try_convert, AccountState,
 };
+use miden_node_utils::{
+    chain_params::ChainGenesis, features::build_features, logging::set_log_filter,
+    runtime::runtime_metrics,
+};
 use miden_objects::{
-    crypto::hash::rpo::RpoDigest,
+    accounts::AccountId as AccountIdObject,
+    crypto::{dsa::rpo_falcon512::Signature, hash::rpo::RpoDigest},
     notes::{NoteId, NoteType, Nullifier},
     transaction::AccountDetails,
-    utils::Deserializable,
+    utils::{Deserializable, Serializable},
     BlockHeader, Felt, NoteError, ZERO,
 };
+use prost::Message;
 use tonic::{Response, Status};
 use tracing::{debug, info, instrument};
 
-use crate::{db::NoteCreated, state::State, types::AccountId, COMPONENT};
+use super::continuation::{
+    AccountsListCursor, BlockRangeCursor, NotesListCursor, StateSyncContinuation, SyncedFilters,
+};
+use crate::{
+    config::SyncFiltersConfig,
+    db::{
+        AccountFilter, AccountSortOrder, AuxRange, Note, NoteCreated, NoteFilter, NoteListFilter,
+        RowCounts, StateSyncLimits,
+    },
+    errors::{
+        AccountDeltasError, ApplyBlockError, BlockInclusionProofError, CheckNullifiersError,
+        NoteAuthenticationInfoError, NullifierTreeError, StateSyncError,
+    },
+    network::{subscribe_blocks, BlockSubscription, FollowProgress},
+    state::{ApplyOutcome, State},
+    subscriptions::AccountSubscription,
+    types::{AccountId, BlockNumber},
+    ACCOUNT_DELTAS_PAGE_SIZE, COMPONENT, DEFAULT_NULLIFIER_PREFIX_LEN, LIST_PAGE_SIZE,
+    MAX_AUX_RANGES, MAX_NULLIFIER_PREFIX_LEN, MAX_STATE_SYNC_BLOCKS, MAX_STATE_SYNC_NOTES,
+    MAX_SUBSCRIBED_ACCOUNTS, MIN_NULLIFIER_PREFIX_LEN, NULLIFIERS_PAGE_SIZE,
+};
 
 // STORE API
 // ================================================================================================
 
 pub struct StoreApi {
     pub(super) state: Arc<State>,
+    /// Set if the store is a replica, the blocks are then only received from the followed node.
+    /// Holds the replica's progress applying them.
+    pub(super) following: Option<Arc<FollowProgress>>,
+    /// Set if the store only serves the reads of its database, see
+    /// [crate::config::StoreConfig::read_only].
+    pub(super) read_only: bool,
+    /// Caps of the state sync filters
+    pub(super) sync_filters: SyncFiltersConfig,
+    /// Signs the page tokens of the list endpoints
+    pub(super) page_tokens: PageTokens,
+}
+
+impl StoreApi {
+    /// Returns the number of rows selected by `rows` for the first `page` of a list, [None] for
+    /// the following pages.
+    async fn total_estimate<C>(
+        &self,
+        page: &PageParams<C>,
+        rows: impl FnOnce(RowCounts) -> u64,
+    ) -> Result<Option<u64>, Status> {
+        if !page.is_first() {
+            return Ok(None);
+        }
+        let counts = self.state.row_counts().await.map_err(internal_error)?;
+        Ok(Some(rows(counts)))
+    }
 }
 
 // FIXME: remove the allow when the upstream clippy issue is fixed:
@@ -53,6 +124,29 @@ impl api_server::Api for StoreApi {
     // CLIENT ENDPOINTS
     // --------------------------------------------------------------------------------------------
 
+    /// Returns the number and hash of the latest block.
+    ///
+    /// The chain tip is kept in memory, so this endpoint is cheaper than requesting the latest
+    /// block header.
+    #[instrument(
+        target = "miden-store",
+        name = "store:get_chain_tip",
+        skip_all,
+        ret(level = "debug"),
+        err
+    )]
+    async fn get_chain_tip(
+        &self,
+        _request: tonic::Request<GetChainTipRequest>,
+    ) -> Result<Response<GetChainTipResponse>, Status> {
+        let chain_tip = self.state.chain_tip();
+
+        Ok(Response::new(GetChainTipResponse {
+            block_num: chain_tip.block_num.into(),
+            block_hash: Some(chain_tip.block_hash.into()),
+        }))
+    }
+
     /// Returns block header for the specified block number.
     ///
     /// If the block number is not provided, block header for the latest block is returned.
@@ -69,21 +163,134 @@ impl api_server::Api for StoreApi {
     ) -> Result<Response<GetBlockHeaderByNumberResponse>, Status> {
         info!(target: COMPONENT, ?request);
 
-        let block_num = request.into_inner().block_num;
+        let block_num = request.into_inner().block_num.map(BlockNumber::from);
+        let block_header = self.state.get_block_header(block_num).await.map_err(internal_error)?;
+
+        let signature = match &block_header {
+            Some(block_header) => self
+                .state
+                .get_block_signature(block_header.block_num().into())
+                .await
+                .map_err(internal_error)?
+                .map(|signature| signature.to_bytes())
+                .unwrap_or_default(),
+            None => Vec::new(),
+        };
+
+        Ok(Response::new(GetBlockHeaderByNumberResponse {
+            block_header: block_header.map(Into::into),
+            signature,
+        }))
+    }
+
+    /// Returns the proof that a block is committed to by the chain root of a later block.
+    #[instrument(
+        target = "miden-store",
+        name = "store:get_block_inclusion_proof",
+        skip_all,
+        ret(level = "debug"),
+        err
+    )]
+    async fn get_block_inclusion_proof(
+        &self,
+        request: tonic::Request<GetBlockInclusionProofRequest>,
+    ) -> Result<Response<GetBlockInclusionProofResponse>, Status> {
+        info!(target: COMPONENT, ?request);
+
+        let request = request.into_inner();
+        let proof = self
+            .state
+            .get_block_inclusion_proof(
+                request.block_num.into(),
+                request.at_chain_tip.map(BlockNumber::from),
+            )
+            .await
+            .map_err(|err| match err {
+                BlockInclusionProofError::UnknownBlock(_) => Status::not_found(err.to_string()),
+                BlockInclusionProofError::NotCommitted { .. } => {
+                    Status::invalid_argument(err.to_string())
+                },
+                err => internal_error(err),
+            })?;
+
+        Ok(Response::new(GetBlockInclusionProofResponse {
+            block_header: Some(proof.block_header.into()),
+            chain_tip: Some(proof.chain_tip.into()),
+            mmr_path: Some(proof.mmr_path.into()),
+            mmr_peaks: proof.mmr_peaks.peaks().iter().map(Into::into).collect(),
+        }))
+    }
+
+    /// Returns the validity proof of a block, for the light clients and bridges.
+    #[instrument(target = "miden-store", name = "store:get_block_proof", skip_all, err)]
+    async fn get_block_proof(
+        &self,
+        request: tonic::Request<GetBlockProofRequest>,
+    ) -> Result<Response<GetBlockProofResponse>, Status> {
+        info!(target: COMPONENT, ?request);
+
+        let block_num = BlockNumber::from(request.into_inner().block_num);
         let block_header = self
             .state
-            .get_block_header(block_num)
+            .get_block_header(Some(block_num))
             .await
             .map_err(internal_error)?
-            .map(Into::into);
+            .ok_or_else(|| Status::not_found(format!("Block {block_num} doesn't exist")))?;
+        let proof = self.state.get_block_proof(block_num).await.map_err(internal_error)?;
+
+        Ok(Response::new(GetBlockProofResponse {
+            block_header: Some(block_header.into()),
+            proof,
+        }))
+    }
+
+    /// Returns the authentication paths of notes in the note tree of their block, so that notes
+    /// learned about off-band can be proven without syncing.
+    #[instrument(
+        target = "miden-store",
+        name = "store:get_note_authentication_info",
+        skip_all,
+        ret(level = "debug"),
+        err
+    )]
+    async fn get_note_authentication_info(
+        &self,
+        request: tonic::Request<GetNoteAuthenticationInfoRequest>,
+    ) -> Result<Response<GetNoteAuthenticationInfoResponse>, Status> {
+        info!(target: COMPONENT, ?request);
+
+        let request = request.into_inner();
+        let info = self
+            .state
+            .get_note_authentication_info(request.block_num.into(), &request.note_indexes)
+            .await
+            .map_err(|err| match err {
+                NoteAuthenticationInfoError::UnknownBlock(_)
+                | NoteAuthenticationInfoError::UnknownNote { .. } => {
+                    Status::not_found(err.to_string())
+                },
+                err => internal_error(err),
+            })?;
 
-        Ok(Response::new(GetBlockHeaderByNumberResponse { block_header }))
+        Ok(Response::new(GetNoteAuthenticationInfoResponse {
+            block_header: Some(info.block_header.into()),
+            notes: info
+                .notes
+                .into_iter()
+                .map(|(note_index, note_id, merkle_path)| NoteAuthenticationInfo {
+                    note_index,
+                    note_id: Some(note_id.into()),
+                    merkle_path: Some(merkle_path.into()),
+                })
+                .collect(),
+        }))
     }
 
     /// Returns info on whether the specified nullifiers have been consumed.
     ///
     /// This endpoint also returns Merkle authentication path for each requested nullifier which can
-    /// be verified against the latest root of the nullifier database.
+    /// be verified against the latest root of the nullifier database, or against the nullifier root
+    /// of the requested block.
     #[instrument(
         target = "miden-store",
         name = "store:check_nullifiers",
@@ -100,9 +307,126 @@ impl api_server::Api for StoreApi {
         let nullifiers = validate_nullifiers(&request.nullifiers)?;
 
         // Query the state for the request's nullifiers
-        let proofs = self.state.check_nullifiers(&nullifiers).await;
+        let (proofs, block_timestamps): (Vec<_>, Vec<_>) = self
+            .state
+            .check_nullifiers(&nullifiers, request.block_num.map(BlockNumber::new))
+            .await
+            .map_err(|err| match err {
+                CheckNullifiersError::UnknownBlock(_) => Status::not_found(err.to_string()),
+                CheckNullifiersError::FailedToOpenNullifierTree(
+                    NullifierTreeError::HistoryPruned { .. },
+                ) => Status::out_of_range(err.to_string()),
+                err => internal_error(err),
+            })?
+            .into_iter()
+            .unzip();
 
-        Ok(Response::new(CheckNullifiersResponse { proofs: convert(proofs) }))
+        Ok(Response::new(CheckNullifiersResponse {
+            proofs: convert(proofs),
+            block_timestamps,
+        }))
+    }
+
+    /// Returns the nullifiers matching the requested prefixes, with the block in which they were
+    /// consumed, omitting the ones consumed in blocks with fewer than `min_confirmations`.
+    #[instrument(
+        target = "miden-store",
+        name = "store:check_nullifiers_by_prefix",
+        skip_all,
+        ret(level = "debug"),
+        err
+    )]
+    async fn check_nullifiers_by_prefix(
+        &self,
+        request: tonic::Request<CheckNullifiersByPrefixRequest>,
+    ) -> Result<Response<CheckNullifiersByPrefixResponse>, Status> {
+        let request = request.into_inner();
+        let prefix_len = validate_nullifier_prefixes(request.prefix_len, &request.nullifiers)?;
+
+        let nullifiers = self
+            .state
+            .check_nullifiers_by_prefix(prefix_len, request.nullifiers)
+            .await
+            .map_err(internal_error)?;
+
+        let chain_tip = self.state.chain_tip().block_num;
+        let nullifiers = nullifiers
+            .into_iter()
+            .filter(|nullifier_info| {
+                is_confirmed(
+                    nullifier_info.block_num.as_u32(),
+                    chain_tip,
+                    request.min_confirmations,
+                )
+            })
+            .map(|nullifier_info| NullifierUpdate {
+                nullifier: Some(nullifier_info.nullifier.into()),
+                block_num: nullifier_info.block_num.into(),
+                block_timestamp: nullifier_info.block_timestamp,
+            })
+            .collect();
+
+        Ok(Response::new(CheckNullifiersByPrefixResponse { nullifiers }))
+    }
+
+    /// Returns a page of the nullifiers matching the requested prefixes consumed after the
+    /// requested block, ordered by block, and the token of the next page.
+    #[instrument(
+        target = "miden-store",
+        name = "store:get_nullifiers_by_prefix",
+        skip_all,
+        ret(level = "debug"),
+        err
+    )]
+    async fn get_nullifiers_by_prefix(
+        &self,
+        request: tonic::Request<GetNullifiersByPrefixRequest>,
+    ) -> Result<Response<GetNullifiersByPrefixResponse>, Status> {
+        let request = request.into_inner();
+        let max_prefixes = self.sync_filters.max_nullifier_prefixes;
+        if request.nullifiers.len() > max_prefixes {
+            return Err(Status::invalid_argument(format!(
+                "At most {max_prefixes} nullifier prefixes can be requested, got {}",
+                request.nullifiers.len()
+            )));
+        }
+        let prefix_len = validate_nullifier_prefixes(request.prefix_len, &request.nullifiers)?;
+
+        // The tokens are only valid for the filters of the first page
+        let from_block = BlockNumber::from(request.from_block);
+        let scope = nullifiers_by_prefix_scope(from_block, prefix_len, &request.nullifiers);
+        let page = self
+            .page_tokens
+            .decode::<BlockRangeCursor>(&scope, request.page, NULLIFIERS_PAGE_SIZE)
+            .map_err(|err| Status::invalid_argument(err.to_string()))?;
+
+        // The pages end at the chain tip of the first page's request
+        let (block_start, until) = match page.cursor {
+            Some(cursor) => (cursor.block_num, cursor.until),
+            None => (from_block, self.state.chain_tip().block_num),
+        };
+
+        let (nullifiers, last_block) = self
+            .state
+            .get_nullifiers_by_prefix(block_start, until, prefix_len, request.nullifiers, page.size)
+            .await
+            .map_err(internal_error)?;
+
+        let next = last_block.map(|block_num| BlockRangeCursor { block_num, until });
+        let nullifiers = nullifiers
+            .into_iter()
+            .map(|nullifier_info| NullifierUpdate {
+                nullifier: Some(nullifier_info.nullifier.into()),
+                block_num: nullifier_info.block_num.into(),
+                block_timestamp: nullifier_info.block_timestamp,
+            })
+            .collect();
+
+        Ok(Response::new(GetNullifiersByPrefixResponse {
+            nullifiers,
+            block_num: last_block.unwrap_or(until).into(),
+            page: Some(self.page_tokens.page(&scope, next.as_ref(), None)),
+        }))
     }
 
     /// Returns info which can be used by the client to sync up to the latest state of the chain
@@ -119,35 +443,103 @@ impl api_server::Api for StoreApi {
         request: tonic::Request<SyncStateRequest>,
     ) -> Result<Response<SyncStateResponse>, Status> {
         let request = request.into_inner();
+        let block_num = BlockNumber::from(request.block_num);
+
+        // A truncated state sync is continued with the next values of the filters
+        let continuation = StateSyncContinuation::decode(&request.continuation)?;
+        let synced = match continuation {
+            Some(continuation) if continuation.block_num != block_num => {
+                return Err(Status::invalid_argument(format!(
+                    "The continuation token belongs to a state sync from block {}, not {block_num}",
+                    continuation.block_num
+                )));
+            },
+            Some(continuation) => continuation.synced,
+            None => SyncedFilters::default(),
+        };
+
+        let filters = &self.sync_filters;
+        let (account_ids, synced_account_ids) =
+            filter_slice(&request.account_ids, synced.account_ids, filters.max_account_ids)?;
+        let (note_tags, synced_note_tags) =
+            filter_slice(&request.note_tags, synced.note_tags, filters.max_note_tags)?;
+        let (nullifiers, synced_nullifiers) =
+            filter_slice(&request.nullifiers, synced.nullifiers, filters.max_nullifier_prefixes)?;
+
+        let nullifier_prefix_len =
+            validate_nullifier_prefixes(request.nullifier_prefix_len, nullifiers)?;
+
+        let limits = match continuation {
+            Some(continuation) => StateSyncLimits::until(continuation.until),
+            None => state_sync_limits(request.max_blocks, request.max_notes),
+        };
 
-        let account_ids: Vec<u64> = request.account_ids.iter().map(|e| e.id).collect();
+        let account_ids: Vec<AccountId> = account_ids.iter().map(|e| e.id.into()).collect();
 
-        let (state, delta) = self
+        let (state, delta, mmr_paths) = self
             .state
-            .sync_state(request.block_num, &account_ids, &request.note_tags, &request.nullifiers)
+            .sync_state(
+                block_num,
+                &account_ids,
+                note_tags,
+                nullifier_prefix_len,
+                nullifiers,
+                limits,
+                request.include_account_details,
+            )
             .await
-            .map_err(internal_error)?;
+            .map_err(|err| match err {
+                StateSyncError::InvalidSyncEnd { .. } => invalid_argument(err),
+                _ => internal_error(err),
+            })?;
+
+        let synced = SyncedFilters {
+            account_ids: synced_account_ids,
+            note_tags: synced_note_tags,
+            nullifiers: synced_nullifiers,
+        };
+        let truncated = synced.account_ids < request.account_ids.len()
+            || synced.note_tags < request.note_tags.len()
+            || synced.nullifiers < request.nullifiers.len();
+        let continuation = if truncated {
+            StateSyncContinuation {
+                block_num,
+                until: state.block_header.block_num().into(),
+                synced,
+            }
+            .to_bytes()
+        } else {
+            Vec::new()
+        };
 
         let accounts = state
             .account_updates
-            .into_iter()
-            .map(|account_info| AccountSummary {
-                account_id: Some(account_info.account_id.into()),
-                account_hash: Some(account_info.account_hash.into()),
-                block_num: account_info.block_num,
-            })
+            .iter()
+            .map(|account_info| (&account_info.summary).into())
+            .collect();
+        let account_details = state
+            .account_updates
+            .iter()
+            .filter(|account_info| account_info.details.is_some())
+            .map(Into::into)
             .collect();
 
-        let notes = state
-            .notes
+        let (notes, merkle_path_nodes) =
+            note_sync_records(state.notes, request.compact_merkle_paths);
+
+        let preceding_blocks = state
+            .preceding_blocks
             .into_iter()
-            .map(|note| NoteSyncRecord {
-                note_index: note.note_created.absolute_note_index(),
-                note_type: note.note_created.note_type as u32,
-                note_id: Some(note.note_created.note_id.into()),
-                sender: Some(note.note_created.sender.into()),
-                tag: note.note_created.tag,
-                merkle_path: Some(note.merkle_path.into()),
+            .zip(mmr_paths)
+            .map(|(block, mmr_path)| {
+                let (notes, merkle_path_nodes) =
+                    note_sync_records(block.notes, request.compact_merkle_paths);
+                SyncedBlock {
+                    block_header: Some(block.block_header.into()),
+                    mmr_path: Some(mmr_path.into()),
+                    notes,
+                    merkle_path_nodes,
+                }
             })
             .collect();
 
@@ -156,23 +548,179 @@ impl api_server::Api for StoreApi {
             .into_iter()
             .map(|nullifier_info| NullifierUpdate {
                 nullifier: Some(nullifier_info.nullifier.into()),
-                block_num: nullifier_info.block_num,
+                block_num: nullifier_info.block_num.into(),
+                block_timestamp: nullifier_info.block_timestamp,
             })
             .collect();
 
         Ok(Response::new(SyncStateResponse {
-            chain_tip: state.chain_tip,
+            chain_tip: state.chain_tip.into(),
             block_header: Some(state.block_header.into()),
             mmr_delta: Some(delta.into()),
             accounts,
             notes,
             nullifiers,
+            preceding_blocks,
+            account_details,
+            truncated,
+            continuation,
+            merkle_path_nodes,
+        }))
+    }
+
+    /// Returns the notes of the first block after the requested block with a note matching the
+    /// requested tags, use cases or network execution hint, or the chain tip if there is no such
+    /// block.
+    #[instrument(
+        target = "miden-store",
+        name = "store:sync_notes",
+        skip_all,
+        ret(level = "debug"),
+        err
+    )]
+    async fn sync_notes(
+        &self,
+        request: tonic::Request<SyncNotesRequest>,
+    ) -> Result<Response<SyncNotesResponse>, Status> {
+        let request = request.into_inner();
+
+        let (state, mmr_path) = self
+            .state
+            .sync_notes(
+                request.block_num.into(),
+                NoteFilter {
+                    tags: request.note_tags,
+                    use_cases: request.note_use_cases,
+                    network_notes: request.network_notes,
+                    aux_ranges: aux_ranges(request.aux_ranges)?,
+                    ..Default::default()
+                },
+            )
+            .await
+            .map_err(internal_error)?;
+
+        let notes = state.notes.into_iter().map(note_sync_record).collect();
+
+        Ok(Response::new(SyncNotesResponse {
+            chain_tip: state.chain_tip.into(),
+            block_header: Some(state.block_header.into()),
+            mmr_path: Some(mmr_path.into()),
+            notes,
+        }))
+    }
+
+    /// Returns the receipt of a transaction included in a block.
+    ///
+    /// Fails with `NOT_FOUND` if the transaction hasn't been included in a block, or if the block
+    /// has fewer than `min_confirmations`.
+    #[instrument(
+        target = "miden-store",
+        name = "store:get_transaction_receipt",
+        skip_all,
+        ret(level = "debug"),
+        err
+    )]
+    async fn get_transaction_receipt(
+        &self,
+        request: tonic::Request<GetTransactionReceiptRequest>,
+    ) -> Result<Response<GetTransactionReceiptResponse>, Status> {
+        debug!(target: COMPONENT, ?request);
+
+        let request = request.into_inner();
+        let transaction_id: RpoDigest = request
+            .transaction_id
+            .ok_or(invalid_argument("Transaction id missing"))?
+            .try_into()
+            .map_err(|err: ConversionError| Status::invalid_argument(err.to_string()))?;
+
+        let receipt = self
+            .state
+            .get_transaction_receipt(transaction_id)
+            .await
+            .map_err(internal_error)?;
+
+        let chain_tip = self.state.chain_tip().block_num;
+        let receipt = receipt
+            .filter(|receipt| is_confirmed(receipt.block_num, chain_tip, request.min_confirmations))
+            .ok_or_else(|| {
+                Status::not_found(format!("Transaction {} not found", transaction_id))
+            })?;
+
+        Ok(Response::new(GetTransactionReceiptResponse { receipt: Some(receipt.into()) }))
+    }
+
+    /// Returns the script with the given root, shared by the public notes using it.
+    ///
+    /// Fails with `NOT_FOUND` if no public note stored by the node uses this script.
+    #[instrument(
+        target = "miden-store",
+        name = "store:get_note_script_by_root",
+        skip_all,
+        ret(level = "debug"),
+        err
+    )]
+    async fn get_note_script_by_root(
+        &self,
+        request: tonic::Request<GetNoteScriptByRootRequest>,
+    ) -> Result<Response<GetNoteScriptByRootResponse>, Status> {
+        debug!(target: COMPONENT, ?request);
+
+        let script_root: RpoDigest = request
+            .into_inner()
+            .script_root
+            .ok_or(invalid_argument("Script root missing"))?
+            .try_into()
+            .map_err(|err: ConversionError| Status::invalid_argument(err.to_string()))?;
+
+        let script = self
+            .state
+            .get_note_script(script_root)
+            .await
+            .map_err(internal_error)?
+            .ok_or_else(|| Status::not_found(format!("Note script {} not found", script_root)))?;
+
+        Ok(Response::new(GetNoteScriptByRootResponse { script }))
+    }
+
+    /// Returns the ids of the public accounts authenticated by the requested public key.
+    ///
+    /// Fails with `UNIMPLEMENTED` if the account public key index is disabled.
+    #[instrument(
+        target = "miden-store",
+        name = "store:get_accounts_by_pub_key",
+        skip_all,
+        ret(level = "debug"),
+        err
+    )]
+    async fn get_accounts_by_pub_key(
+        &self,
+        request: tonic::Request<GetAccountsByPubKeyRequest>,
+    ) -> Result<Response<GetAccountsByPubKeyResponse>, Status> {
+        debug!(target: COMPONENT, ?request);
+
+        let public_key: RpoDigest = request
+            .into_inner()
+            .public_key
+            .ok_or(invalid_argument("Public key missing"))?
+            .try_into()
+            .map_err(|err: ConversionError| Status::invalid_argument(err.to_string()))?;
+
+        let account_ids = self
+            .state
+            .get_accounts_by_public_key(public_key)
+            .await
+            .map_err(internal_error)?
+            .ok_or_else(|| Status::unimplemented("Account public key index is disabled"))?;
+
+        Ok(Response::new(GetAccountsByPubKeyResponse {
+            account_ids: account_ids.into_iter().map(Into::into).collect(),
         }))
     }
 
     /// Returns a list of Note's for the specified NoteId's.
     ///
-    /// If the list is empty or no Note matched the requested NoteId and empty list is returned.
+    /// If the list is empty or no Note matched the requested NoteId and empty list is returned. The
+    /// notes created in blocks with fewer than `min_confirmations` are omitted.
     #[instrument(
         target = "miden-store",
         name = "store:get_notes_by_id",
@@ -186,21 +734,23 @@ impl api_server::Api for StoreApi {
     ) -> Result<Response<GetNotesByIdResponse>, Status> {
         info!(target: COMPONENT, ?request);
 
-        let note_ids = request.into_inner().note_ids;
+        let request = request.into_inner();
 
-        let note_ids: Vec<RpoDigest> = try_convert(note_ids)
+        let note_ids: Vec<RpoDigest> = try_convert(request.note_ids)
             .map_err(|err| Status::invalid_argument(format!("Invalid NoteId: {}", err)))?;
 
         let note_ids: Vec<NoteId> = note_ids.into_iter().map(From::from).collect();
 
-        let notes = self
-            .state
-            .get_notes_by_id(note_ids)
-            .await
-            .map_err(internal_error)?
+        let notes = self.state.get_notes_by_id(note_ids).await.map_err(internal_error)?;
+
+        let chain_tip = self.state.chain_tip().block_num;
+        let notes = notes
             .into_iter()
+            .filter(|note| {
+                is_confirmed(note.block_num.as_u32(), chain_tip, request.min_confirmations)
+            })
             .map(|note| generated::note::Note {
-                block_num: note.block_num,
+                block_num: note.block_num.into(),
                 note_index: note.note_created.absolute_note_index(),
                 note_id: Some(note.note_created.note_id.into()),
                 sender: Some(note.note_created.sender.into()),
@@ -208,13 +758,54 @@ impl api_server::Api for StoreApi {
                 note_type: note.note_created.note_type as u32,
                 merkle_path: Some(note.merkle_path.into()),
                 details: note.note_created.details,
+                aux: note.note_created.aux,
             })
             .collect();
 
         Ok(Response::new(GetNotesByIdResponse { notes }))
     }
 
+    /// Returns the block and the transaction which consumed a note, linking the note to its
+    /// nullifier.
+    ///
+    /// The consumption is only known for the notes whose nullifier the store computed from their
+    /// details, i.e. the public notes, the response is empty otherwise.
+    #[instrument(
+        target = "miden-store",
+        name = "store:get_note_consumption",
+        skip_all,
+        ret(level = "debug"),
+        err
+    )]
+    async fn get_note_consumption(
+        &self,
+        request: tonic::Request<GetNoteConsumptionRequest>,
+    ) -> Result<Response<GetNoteConsumptionResponse>, Status> {
+        debug!(target: COMPONENT, ?request);
+
+        let request = request.into_inner();
+        let note_id: RpoDigest = request
+            .note_id
+            .ok_or(invalid_argument("Note id missing"))?
+            .try_into()
+            .map_err(|err: ConversionError| Status::invalid_argument(err.to_string()))?;
+
+        let consumption = self.state.get_note_consumption(note_id).await.map_err(internal_error)?;
+
+        Ok(Response::new(GetNoteConsumptionResponse {
+            consumption: consumption.map(|consumption| NoteConsumption {
+                note_id: Some(consumption.note_id.into()),
+                nullifier: Some(consumption.nullifier.inner().into()),
+                block_num: consumption.block_num.into(),
+                transaction_id: consumption.transaction_id.map(Into::into),
+            }),
+        }))
+    }
+
     /// Returns details for public (on-chain) account by id.
+    ///
+    /// Fails with `FAILED_PRECONDITION` if the account was updated in a block with fewer than
+    /// `min_confirmations`, its previous states aren't stored.
     #[instrument(
         target = "miden-store",
         name = "store:get_account_details",
@@ -227,19 +818,114 @@ impl api_server::Api for StoreApi {
         request: tonic::Request<GetAccountDetailsRequest>,
     ) -> Result<Response<GetAccountDetailsResponse>, Status> {
         let request = request.into_inner();
+        let account_id: AccountId =
+            request.account_id.ok_or(invalid_argument("Account missing id"))?.into();
+
         let account_info = self
             .state
-            .get_account_details(
-                request.account_id.ok_or(invalid_argument("Account missing id"))?.into(),
-            )
+            .get_account_details(account_id)
             .await
-            .map_err(internal_error)?;
+            .map_err(internal_error)?
+            .into_info()
+            .ok_or_else(|| Status::not_found(format!("Account {account_id} not found")))?;
+
+        let block_num = account_info.summary.block_num;
+        if !is_confirmed(block_num, self.state.chain_tip().block_num, request.min_confirmations) {
+            return Err(Status::failed_precondition(format!(
+                "Account {account_id} was updated in block {block_num}, which has fewer than {} \
+                 confirmations",
+                request.min_confirmations
+            )));
+        }
 
         Ok(Response::new(GetAccountDetailsResponse {
             account: Some((&account_info).into()),
         }))
     }
 
+    /// Returns a page of the deltas applied to a public account after the requested block, ordered
+    /// by block, so clients holding the account's state can update it without downloading it
+    /// again.
+    ///
+    /// Fails with `FAILED_PRECONDITION` if the account is private, or if the deltas of some
+    /// requested blocks weren't stored.
+    #[instrument(
+        target = "miden-store",
+        name = "store:get_account_deltas",
+        skip_all,
+        ret(level = "debug"),
+        err
+    )]
+    async fn get_account_deltas(
+        &self,
+        request: tonic::Request<GetAccountDeltasRequest>,
+    ) -> Result<Response<GetAccountDeltasResponse>, Status> {
+        let request = request.into_inner();
+        let account_id: AccountId =
+            request.account_id.ok_or(invalid_argument("Account missing id"))?.into();
+        let on_chain = AccountIdObject::try_from(account_id)
+            .map_err(|err| Status::invalid_argument(err.to_string()))?
+            .is_on_chain();
+        if !on_chain {
+            return Err(Status::failed_precondition(format!(
+                "Account {account_id} is private, its deltas aren't stored"
+            )));
+        }
+
+        // The tokens are only valid for the filters of the first page
+        let from_block = BlockNumber::from(request.from_block);
+        let scope = account_deltas_scope(account_id, from_block, request.to_block);
+        let page = self
+            .page_tokens
+            .decode::<BlockRangeCursor>(&scope, request.page, ACCOUNT_DELTAS_PAGE_SIZE)
+            .map_err(|err| Status::invalid_argument(err.to_string()))?;
+
+        // The pages end at the last requested block as of the first page's request
+        let (block_start, until) = match page.cursor {
+            Some(cursor) => (cursor.block_num, cursor.until),
+            None => {
+                let chain_tip = self.state.chain_tip().block_num;
+                let to_block =
+                    request.to_block.map_or(chain_tip, |to_block| chain_tip.min(to_block.into()));
+                if from_block > to_block {
+                    return Err(Status::invalid_argument(format!(
+                        "Block {from_block} is after the last requested block {to_block}"
+                    )));
+                }
+                (from_block, to_block)
+            },
+        };
+
+        // One more delta tells whether there is a next page
+        let mut deltas = self
+            .state
+            .get_account_deltas(account_id, block_start, until, page.size + 1)
+            .await
+            .map_err(|err| match err {
+                AccountDeltasError::DeltasNotStored(_) => {
+                    Status::failed_precondition(err.to_string())
+                },
+                err => internal_error(err),
+            })?;
+        let next = next_page_cursor(&mut deltas, page.size, |delta| BlockRangeCursor {
+            block_num: delta.block_num,
+            until,
+        });
+
+        Ok(Response::new(GetAccountDeltasResponse {
+            deltas: deltas
+                .into_iter()
+                .map(|delta| AccountDeltaUpdate {
+                    block_num: delta.block_num.into(),
+                    delta: delta.delta,
+                    account_hash: Some(delta.account_hash.into()),
+                })
+                .collect(),
+            block_num: next.map_or(until, |cursor| cursor.block_num).into(),
+            page: Some(self.page_tokens.page(&scope, next.as_ref(), None)),
+        }))
+    }
+
     // BLOCK PRODUCER ENDPOINTS
     // --------------------------------------------------------------------------------------------
 
@@ -255,81 +941,50 @@ impl api_server::Api for StoreApi {
         &self,
         request: tonic::Request<ApplyBlockRequest>,
     ) -> Result<tonic::Response<ApplyBlockResponse>, tonic::Status> {
+        if self.read_only {
+            return Err(Status::failed_precondition(
+                "The store is read-only, blocks can't be applied",
+            ));
+        }
+        if self.following.is_some() {
+            return Err(Status::failed_precondition(
+                "The store is a replica, blocks can't be applied directly",
+            ));
+        }
+
         let request = request.into_inner();
 
         debug!(target: COMPONENT, ?request);
-        let block_header: BlockHeader = request
-            .block
-            .ok_or(invalid_argument("Apply block missing block header"))?
-            .try_into()
-            .map_err(|err: ConversionError| Status::invalid_argument(err.to_string()))?;
+        let block = block_update(request)?;
 
-        info!(target: COMPONENT, block_num = block_header.block_num(), block_hash = %block_header.hash());
+        info!(target: COMPONENT, block_num = block.block_header.block_num(), block_hash = %block.block_header.hash());
 
-        let nullifiers = validate_nullifiers(&request.nullifiers)?;
-        let accounts = request
-            .accounts
-            .iter()
-            .map(|account_update| {
-                let account_state: AccountState = account_update
-                    .try_into()
-                    .map_err(|err: ConversionError| Status::invalid_argument(err.to_string()))?;
-
-                match (account_state.account_id.is_on_chain(), account_update.details.is_some()) {
-                    (true, false) => {
-                        return Err(Status::invalid_argument("On-chain account must have details"));
-                    },
-                    (false, true) => {
-                        return Err(Status::invalid_argument(
-                            "Off-chain account must not have details",
-                        ));
-                    },
-                    _ => (),
-                }
+        let outcome = block.apply(&self.state).await.map_err(|err| match err {
+            // The block producer sends the block again once the block being applied is committed
+            ApplyBlockError::ConcurrentWrite => Status::unavailable(err.to_string()),
+            // Nothing of the block was applied, it can be sent again as is
+            ApplyBlockError::CommitFailed(_)
+            | ApplyBlockError::DatabaseError(_)
+            | ApplyBlockError::BlockApplyingBrokenBecauseOfClosedChannel(_) => {
+                Status::aborted(err.to_string())
+            },
+            // The block doesn't follow the chain tip or its contents are invalid
+            ApplyBlockError::NewBlockInvalidBlockNum
+            | ApplyBlockError::NewBlockInvalidPrevHash
+            | ApplyBlockError::NewBlockInvalidChainRoot
+            | ApplyBlockError::NewBlockInvalidAccountRoot
+            | ApplyBlockError::NewBlockInvalidNoteRoot
+            | ApplyBlockError::NewBlockInvalidNullifierRoot
+            | ApplyBlockError::DuplicatedNullifiers(_)
+            | ApplyBlockError::TransactionInvalidBlockNum => {
+                Status::invalid_argument(err.to_string())
+            },
+            err => Status::internal(err.to_string()),
+        })?;
 
-                let details = account_update
-                    .details
-                    .as_ref()
-                    .map(|data| AccountDetails::read_from_bytes(data))
-                    .transpose()
-                    .map_err(|err| Status::invalid_argument(err.to_string()))?;
-
-                Ok(AccountUpdateDetails {
-                    account_id: account_state.account_id,
-                    details,
-                    final_state_hash: account_state
-                        .account_hash
-                        .ok_or(invalid_argument("Account update missing account hash"))?,
-                })
-            })
-            .collect::<Result<Vec<_>, Status>>()?;
-
-        let notes = request
-            .notes
-            .into_iter()
-            .map(|note| {
-                Ok(NoteCreated {
-                    batch_index: note.batch_index,
-                    note_index: note.note_index,
-                    note_id: note
-                        .note_id
-                        .ok_or(invalid_argument("Note missing id"))?
-                        .try_into()
-                        .map_err(|err: ConversionError| {
-                            Status::invalid_argument(err.to_string())
-                        })?,
-                    note_type: NoteType::try_from(note.note_type as u64)
-                        .map_err(|err: NoteError| Status::invalid_argument(err.to_string()))?,
-                    sender: note.sender.ok_or(invalid_argument("Note missing sender"))?.into(),
-                    tag: note.tag,
-                    details: note.details,
-                })
-            })
-            .collect::<Result<Vec<_>, Status>>()?;
-
-        let _ = self.state.apply_block(block_header, nullifiers, accounts, notes).await;
-
-        Ok(Response::new(ApplyBlockResponse {}))
+        Ok(Response::new(ApplyBlockResponse {
+            already_applied: outcome == ApplyOutcome::AlreadyApplied,
+        }))
     }
 
     /// Returns data needed by the block producer to construct and prove the next block.
@@ -346,8 +1001,19 @@ impl api_server::Api for StoreApi {
     ) -> Result<Response<GetBlockInputsResponse>, Status> {
         let request = request.into_inner();
 
-        let nullifiers = validate_nullifiers(&request.nullifiers)?;
-        let account_ids: Vec<AccountId> = request.account_ids.iter().map(|e| e.id).collect();
+        // The ids and nullifiers are deduplicated, a block can update an account in several batches
+        let nullifiers: Vec<Nullifier> = validate_nullifiers(&request.nullifiers)?
+            .into_iter()
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        let account_ids: Vec<AccountId> = request
+            .account_ids
+            .iter()
+            .map(|e| e.id.into())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
 
         let (latest, accumulator, account_states, nullifier_records) = self
             .state
@@ -379,7 +1045,7 @@ impl api_server::Api for StoreApi {
         debug!(target: COMPONENT, ?request);
 
         let nullifiers = validate_nullifiers(&request.nullifiers)?;
-        let account_id = request.account_id.ok_or(invalid_argument("Account_id missing"))?.id;
+        let account_id = request.account_id.ok_or(invalid_argument("Account_id missing"))?.into();
 
         let tx_inputs = self.state.get_transaction_inputs(account_id, &nullifiers).await;
 
@@ -391,18 +1057,194 @@ impl api_server::Api for StoreApi {
             nullifiers: tx_inputs
                 .nullifiers
                 .into_iter()
-                .map(|nullifier| NullifierTransactionInputRecord {
-                    nullifier: Some(nullifier.nullifier.into()),
-                    block_num: nullifier.block_num,
+                .map(|(nullifier, block_num)| NullifierTransactionInputRecord {
+                    nullifier: Some(nullifier.into()),
+                    block_num: block_num.into(),
                 })
                 .collect(),
         }))
     }
 
+    // ADMIN ENDPOINTS
+    // --------------------------------------------------------------------------------------------
+
+    /// Flushes the committed blocks to the database file, so it can be copied as a snapshot.
+    #[instrument(target = "miden-store", name = "store:flush_database", skip_all, err)]
+    async fn flush_database(
+        &self,
+        _request: tonic::Request<FlushDatabaseRequest>,
+    ) -> Result<Response<FlushDatabaseResponse>, Status> {
+        if self.read_only {
+            return Err(Status::failed_precondition("The store is read-only, nothing to flush"));
+        }
+
+        self.state.flush().await.map_err(internal_error)?;
+
+        info!(target: COMPONENT, "Database flushed");
+
+        Ok(Response::new(FlushDatabaseResponse {}))
+    }
+
+    /// Returns the state of the store: its version, the genesis block hash, the latest block, the
+    /// database size and, for replicas, how far behind the followed node the store is.
+    #[instrument(
+        target = "miden-store",
+        name = "store:get_status",
+        skip_all,
+        ret(level = "debug"),
+        err
+    )]
+    async fn get_status(
+        &self,
+        _request: tonic::Request<GetStoreStatusRequest>,
+    ) -> Result<Response<GetStoreStatusResponse>, Status> {
+        let chain_tip = self.state.chain_tip();
+
+        let genesis = self
+            .state
+            .get_block_header(Some(BlockNumber::GENESIS))
+            .await
+            .map_err(internal_error)?
+            .ok_or_else(|| Status::internal("Genesis block not found"))?;
+        let latest = self
+            .state
+            .get_block_header(Some(chain_tip.block_num))
+            .await
+            .map_err(internal_error)?
+            .ok_or_else(|| Status::internal("Latest block not found"))?;
+        let database_size_bytes = self.state.database_bytes().await.map_err(internal_error)?;
+        let collected_fees = self.state.collected_fees().await.map_err(internal_error)?;
+
+        Ok(Response::new(GetStoreStatusResponse {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            genesis_hash: Some(genesis.hash().into()),
+            chain_tip: chain_tip.block_num.into(),
+            last_block_timestamp: latest.timestamp().as_int(),
+            database_size_bytes,
+            follower: self.following.as_ref().map(|progress| progress.status(chain_tip.block_num)),
+            collected_fees: collected_fees.into_iter().map(Into::into).collect(),
+            runtime: runtime_metrics().map(Into::into),
+        }))
+    }
+
+    /// Returns the versions of the store and the hash of its genesis block, checked by the block
+    /// producer on startup.
+    #[instrument(
+        target = "miden-store",
+        name = "store:get_version",
+        skip_all,
+        ret(level = "debug"),
+        err
+    )]
+    async fn get_version(
+        &self,
+        _request: tonic::Request<GetVersionRequest>,
+    ) -> Result<Response<GetVersionResponse>, Status> {
+        let genesis = self
+            .state
+            .get_block_header(Some(BlockNumber::GENESIS))
+            .await
+            .map_err(internal_error)?
+            .ok_or_else(|| Status::internal("Genesis block not found"))?;
+
+        Ok(Response::new(GetVersionResponse {
+            component: COMPONENT.to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            protocol_version: Some(PROTOCOL_VERSION.into()),
+            proto_version: PROTO_VERSION.to_string(),
+            genesis_hash: Some(genesis.hash().into()),
+            features: build_features(),
+            components: Vec::new(),
+        }))
+    }
+
+    /// Returns the parameters of the chain set by the genesis file, loaded by the block producer
+    /// on startup.
+    #[instrument(
+        target = "miden-store",
+        name = "store:get_chain_parameters",
+        skip_all,
+        ret(level = "debug"),
+        err
+    )]
+    async fn get_chain_parameters(
+        &self,
+        _request: tonic::Request<GetChainParametersRequest>,
+    ) -> Result<Response<GetChainParametersResponse>, Status> {
+        Ok(Response::new(GetChainParametersResponse {
+            parameters: self.state.db().chain_parameters().map(Into::into),
+        }))
+    }
+
+    /// Returns the genesis block header and the parameters of the chain it commits to, fetched by
+    /// the block producer and the RPC on startup instead of reading the genesis file.
+    #[instrument(
+        target = "miden-store",
+        name = "store:get_genesis",
+        skip_all,
+        ret(level = "debug"),
+        err
+    )]
+    async fn get_genesis(
+        &self,
+        _request: tonic::Request<GetGenesisRequest>,
+    ) -> Result<Response<GetGenesisResponse>, Status> {
+        let header = self
+            .state
+            .get_block_header(Some(BlockNumber::GENESIS))
+            .await
+            .map_err(internal_error)?
+            .ok_or_else(|| Status::internal("Genesis block not found"))?;
+        let genesis = ChainGenesis {
+            header,
+            parameters: self.state.db().chain_parameters(),
+        };
+
+        Ok(Response::new(genesis.into()))
+    }
+
+    /// Returns the tables, indexes and views of the database, for the external tools reading the
+    /// database file directly.
+    #[instrument(
+        target = "miden-store",
+        name = "store:get_database_schema",
+        skip_all,
+        ret(level = "debug"),
+        err
+    )]
+    async fn get_database_schema(
+        &self,
+        _request: tonic::Request<GetDatabaseSchemaRequest>,
+    ) -> Result<Response<GetDatabaseSchemaResponse>, Status> {
+        let schema = self.state.db().select_schema().await.map_err(internal_error)?;
+
+        Ok(Response::new(GetDatabaseSchemaResponse { schema: Some(schema.into()) }))
+    }
+
+    /// Replaces the log filter of the store, returning the replaced one.
+    #[instrument(
+        target = "miden-store",
+        name = "store:set_log_filter",
+        skip_all,
+        ret(level = "debug"),
+        err
+    )]
+    async fn set_log_filter(
+        &self,
+        request: tonic::Request<SetLogFilterRequest>,
+    ) -> Result<Response<SetLogFilterResponse>, Status> {
+        let filter = request.into_inner().filter;
+        let previous_filter = set_log_filter(&filter)?;
+
+        info!(target: COMPONENT, filter, previous_filter, "Log filter updated");
+
+        Ok(Response::new(SetLogFilterResponse { previous_filter }))
+    }
+
     // TESTING ENDPOINTS
     // --------------------------------------------------------------------------------------------
 
-    /// Returns a list of all nullifiers
+    /// Returns a page of the nullifiers, ordered by nullifier
     #[instrument(
         target = "miden-store",
         name = "store:list_nullifiers",
@@ -412,20 +1254,37 @@ impl api_server::Api for StoreApi {
     )]
     async fn list_nullifiers(
         &self,
-        _request: tonic::Request<ListNullifiersRequest>,
+        request: tonic::Request<ListNullifiersRequest>,
     ) -> Result<Response<ListNullifiersResponse>, Status> {
-        let raw_nullifiers = self.state.list_nullifiers().await.map_err(internal_error)?;
+        const SCOPE: &str = "ListNullifiers";
+        let page = self
+            .page_tokens
+            .decode::<Nullifier>(SCOPE, request.into_inner().page, LIST_PAGE_SIZE)
+            .map_err(|err| Status::invalid_argument(err.to_string()))?;
+        let total_estimate = self.total_estimate(&page, |counts| counts.nullifiers).await?;
+
+        // One more nullifier tells whether there is a next page
+        let mut raw_nullifiers = self
+            .state
+            .list_nullifiers(page.cursor, page.size + 1)
+            .await
+            .map_err(internal_error)?;
+        let next = next_page_cursor(&mut raw_nullifiers, page.size, |(nullifier, _)| *nullifier);
+
         let nullifiers = raw_nullifiers
             .into_iter()
             .map(|(key, block_num)| SmtLeafEntry {
                 key: Some(key.into()),
-                value: Some([Felt::from(block_num), ZERO, ZERO, ZERO].into()),
+                value: Some([Felt::from(block_num.as_u32()), ZERO, ZERO, ZERO].into()),
             })
             .collect();
-        Ok(Response::new(ListNullifiersResponse { nullifiers }))
+        Ok(Response::new(ListNullifiersResponse {
+            nullifiers,
+            page: Some(self.page_tokens.page(SCOPE, next.as_ref(), total_estimate)),
+        }))
     }
 
-    /// Returns a list of all notes
+    /// Returns a page of the notes, ordered by block and position in the block
     #[instrument(
         target = "miden-store",
         name = "store:list_notes",
@@ -435,16 +1294,46 @@ impl api_server::Api for StoreApi {
     )]
     async fn list_notes(
         &self,
-        _request: tonic::Request<ListNotesRequest>,
+        request: tonic::Request<ListNotesRequest>,
     ) -> Result<Response<ListNotesResponse>, Status> {
-        let notes = self
+        let request = request.into_inner();
+        let filter = NoteListFilter {
+            sender: request.sender.map(Into::into),
+            epoch: request.epoch,
+            aux_ranges: aux_ranges(request.aux_ranges)?,
+        };
+
+        // The tokens are only valid for the filters of the first page
+        let scope = list_notes_scope(&filter);
+        let page = self
+            .page_tokens
+            .decode::<NotesListCursor>(&scope, request.page, LIST_PAGE_SIZE)
+            .map_err(|err| Status::invalid_argument(err.to_string()))?;
+        // The filtered notes aren't counted
+        let total_estimate = if filter.is_empty() {
+            self.total_estimate(&page, |counts| counts.notes).await?
+        } else {
+            None
+        };
+
+        let after = page
+            .cursor
+            .map(|cursor| (cursor.block_num, cursor.batch_index, cursor.note_index));
+        let mut notes = self
             .state
-            .list_notes()
+            .list_notes(filter, after, page.size + 1)
             .await
-            .map_err(internal_error)?
+            .map_err(internal_error)?;
+        let next = next_page_cursor(&mut notes, page.size, |note| NotesListCursor {
+            block_num: note.block_num,
+            batch_index: note.note_created.batch_index,
+            note_index: note.note_created.note_index,
+        });
+
+        let notes = notes
             .into_iter()
             .map(|note| generated::note::Note {
-                block_num: note.block_num,
+                block_num: note.block_num.into(),
                 note_index: note.note_created.absolute_note_index(),
                 note_id: Some(note.note_created.note_id.into()),
                 sender: Some(note.note_created.sender.into()),
@@ -452,12 +1341,16 @@ impl api_server::Api for StoreApi {
                 note_type: note.note_created.note_type as u32,
                 merkle_path: Some(note.merkle_path.into()),
                 details: note.note_created.details,
+                aux: note.note_created.aux,
             })
             .collect();
-        Ok(Response::new(ListNotesResponse { notes }))
+        Ok(Response::new(ListNotesResponse {
+            notes,
+            page: Some(self.page_tokens.page(&scope, next.as_ref(), total_estimate)),
+        }))
     }
 
-    /// Returns a list of all accounts
+    /// Returns a page of the accounts matching the request's filters
     #[instrument(
         target = "miden-store",
         name = "store:list_accounts",
@@ -467,20 +1360,222 @@ impl api_server::Api for StoreApi {
     )]
     async fn list_accounts(
         &self,
-        _request: tonic::Request<ListAccountsRequest>,
+        request: tonic::Request<ListAccountsRequest>,
     ) -> Result<Response<ListAccountsResponse>, Status> {
-        let accounts = self
+        let request = request.into_inner();
+        let filter = AccountFilter {
+            is_public: request.is_public,
+            is_faucet: request.is_faucet,
+            updated_after: request.updated_after_block.map(BlockNumber::from),
+            sort_order: account_sort_order(request.sort_order)?,
+        };
+
+        // The tokens are only valid for the filters of the first page
+        let scope = list_accounts_scope(&filter);
+        let page = self
+            .page_tokens
+            .decode::<AccountsListCursor>(&scope, request.page, LIST_PAGE_SIZE)
+            .map_err(|err| Status::invalid_argument(err.to_string()))?;
+        // The filtered accounts aren't counted
+        let total_estimate = match filter {
+            AccountFilter {
+                is_public: None,
+                is_faucet: None,
+                updated_after: None,
+                ..
+            } => self.total_estimate(&page, |counts| counts.accounts).await?,
+            _ => None,
+        };
+
+        let after = page.cursor.map(|cursor| (cursor.block_num, cursor.account_id));
+        let mut accounts = self
             .state
-            .list_accounts()
+            .list_accounts(filter, after, page.size + 1)
+            .await
+            .map_err(internal_error)?;
+        let next = next_page_cursor(&mut accounts, page.size, |account| AccountsListCursor {
+            block_num: account.summary.block_num.into(),
+            account_id: account.summary.account_id.into(),
+        });
+
+        Ok(Response::new(ListAccountsResponse {
+            accounts: accounts.iter().map(Into::into).collect(),
+            page: Some(self.page_tokens.page(&scope, next.as_ref(), total_estimate)),
+        }))
+    }
+
+    type SubscribeBlocksStream = BlockSubscription;
+
+    /// Streams the applied blocks starting from the requested block, followed by the new blocks as
+    /// they are applied.
+    #[instrument(target = "miden-store", name = "store:subscribe_blocks", skip_all, err)]
+    async fn subscribe_blocks(
+        &self,
+        request: tonic::Request<SubscribeBlocksRequest>,
+    ) -> Result<Response<Self::SubscribeBlocksStream>, Status> {
+        let block_num = BlockNumber::from(request.into_inner().block_num);
+        info!(target: COMPONENT, %block_num, "New block subscription");
+
+        Ok(Response::new(subscribe_blocks(self.state.clone(), block_num)))
+    }
+
+    type SubscribeAccountsStream = AccountSubscription;
+
+    /// Streams the new state of the requested accounts each time an applied block updates them.
+    #[instrument(target = "miden-store", name = "store:subscribe_accounts", skip_all, err)]
+    async fn subscribe_accounts(
+        &self,
+        request: tonic::Request<SubscribeAccountsRequest>,
+    ) -> Result<Response<Self::SubscribeAccountsStream>, Status> {
+        let account_ids: BTreeSet<AccountId> =
+            request.into_inner().account_ids.iter().map(|e| e.id.into()).collect();
+
+        if account_ids.is_empty() {
+            return Err(Status::invalid_argument("No account to subscribe to"));
+        }
+        if account_ids.len() > MAX_SUBSCRIBED_ACCOUNTS {
+            return Err(Status::invalid_argument(format!(
+                "Too many accounts, at most {MAX_SUBSCRIBED_ACCOUNTS} can be subscribed to"
+            )));
+        }
+
+        Ok(Response::new(self.state.subscribe_accounts(account_ids)))
+    }
+}
+
+// BLOCK UPDATE
+// ================================================================================================
+
+/// A block received from the block producer or from the followed node, converted to the domain
+/// types.
+pub(crate) struct BlockUpdate {
+    pub block_header: BlockHeader,
+    pub signature: Option<Signature>,
+    /// The encoded [ApplyBlockRequest], streamed to the replica nodes
+    pub block: Vec<u8>,
+    pub proof: Option<Vec<u8>>,
+    pub nullifiers: Vec<Nullifier>,
+    pub accounts: Vec<AccountUpdateDetails>,
+    pub notes: Vec<NoteCreated>,
+    pub transactions: Vec<TransactionReceipt>,
+}
+
+impl BlockUpdate {
+    /// Applies the block to `state`.
+    pub async fn apply(self, state: &State) -> Result<ApplyOutcome, ApplyBlockError> {
+        state
+            .apply_block(
+                self.block_header,
+                self.signature,
+                self.block,
+                self.proof,
+                self.nullifiers,
+                self.accounts,
+                self.notes,
+                self.transactions,
+            )
             .await
-            .map_err(internal_error)?
-            .iter()
-            .map(Into::into)
-            .collect();
-        Ok(Response::new(ListAccountsResponse { accounts }))
     }
 }
 
+/// Validates and converts `request` to the domain types.
+pub(crate) fn block_update(request: ApplyBlockRequest) -> Result<BlockUpdate, Status> {
+    let block = request.encode_to_vec();
+
+    let block_header: BlockHeader = request
+        .block
+        .ok_or(invalid_argument("Apply block missing block header"))?
+        .try_into()
+        .map_err(|err: ConversionError| Status::invalid_argument(err.to_string()))?;
+
+    let nullifiers = validate_nullifiers(&request.nullifiers)?;
+    let accounts = request
+        .accounts
+        .iter()
+        .map(|account_update| {
+            let account_state: AccountState = account_update
+                .try_into()
+                .map_err(|err: ConversionError| Status::invalid_argument(err.to_string()))?;
+
+            match (account_state.account_id.is_on_chain(), account_update.details.is_some()) {
+                (true, false) => {
+                    return Err(Status::invalid_argument("On-chain account must have details"));
+                },
+                (false, true) => {
+                    return Err(Status::invalid_argument(
+                        "Off-chain account must not have details",
+                    ));
+                },
+                _ => (),
+            }
+
+            let details = account_update
+                .details
+                .as_ref()
+                .map(|data| AccountDetails::read_from_bytes(data))
+                .transpose()
+                .map_err(|err| Status::invalid_argument(err.to_string()))?;
+
+            Ok(AccountUpdateDetails {
+                account_id: account_state.account_id,
+                details,
+                final_state_hash: account_state
+                    .account_hash
+                    .ok_or(invalid_argument("Account update missing account hash"))?,
+            })
+        })
+        .collect::<Result<Vec<_>, Status>>()?;
+
+    let notes = request
+        .notes
+        .into_iter()
+        .map(|note| {
+            Ok(NoteCreated {
+                batch_index: note.batch_index,
+                note_index: note.note_index,
+                note_id: note
+                    .note_id
+                    .ok_or(invalid_argument("Note missing id"))?
+                    .try_into()
+                    .map_err(|err: ConversionError| Status::invalid_argument(err.to_string()))?,
+                note_type: NoteType::try_from(note.note_type as u64)
+                    .map_err(|err: NoteError| Status::invalid_argument(err.to_string()))?,
+                sender: note.sender.ok_or(invalid_argument("Note missing sender"))?.into(),
+                tag: note.tag,
+                aux: Felt::try_from(note.aux)
+                    .map_err(|_| invalid_argument("Note aux is not a field element"))?
+                    .as_int(),
+                details: note.details,
+            })
+        })
+        .collect::<Result<Vec<_>, Status>>()?;
+
+    let transactions: Vec<TransactionReceipt> = try_convert(request.transactions)
+        .map_err(|err| Status::invalid_argument(format!("Invalid transaction: {}", err)))?;
+
+    let signature = if request.signature.is_empty() {
+        None
+    } else {
+        Some(
+            Signature::read_from_bytes(&request.signature)
+                .map_err(|err| Status::invalid_argument(format!("Invalid signature: {}", err)))?,
+        )
+    };
+
+    let proof = (!request.proof.is_empty()).then_some(request.proof);
+
+    Ok(BlockUpdate {
+        block_header,
+        signature,
+        block,
+        proof,
+        nullifiers,
+        accounts,
+        notes,
+        transactions,
+    })
+}
+
 // UTILITIES
 // ================================================================================================
 
@@ -493,6 +1588,105 @@ fn invalid_argument<E: core::fmt::Debug>(err: E) -> Status {
     Status::invalid_argument(format!("{:?}", err))
 }
 
+/// Converts the `aux_ranges` field of [SyncNotesRequest] and [ListNotesRequest]
+fn aux_ranges(ranges: Vec<generated::note::AuxRange>) -> Result<Vec<AuxRange>, Status> {
+    if ranges.len() > MAX_AUX_RANGES {
+        return Err(Status::invalid_argument(format!(
+            "Too many aux ranges: {}, the maximum is {MAX_AUX_RANGES}",
+            ranges.len()
+        )));
+    }
+
+    ranges
+        .into_iter()
+        .map(|range| {
+            if range.min > range.max {
+                return Err(Status::invalid_argument(format!(
+                    "Invalid aux range: {} > {}",
+                    range.min, range.max
+                )));
+            }
+            Ok(AuxRange { min: range.min, max: range.max })
+        })
+        .collect()
+}
+
+/// Returns the scope of the page tokens of `GetNullifiersByPrefix` with the filters of the request.
+fn nullifiers_by_prefix_scope(
+    from_block: BlockNumber,
+    prefix_len: u32,
+    prefixes: &[u32],
+) -> String {
+    format!(
+        "GetNullifiersByPrefix from_block={from_block} prefix_len={prefix_len} prefixes={}",
+        prefixes.iter().map(u32::to_string).collect::<Vec<_>>().join(",")
+    )
+}
+
+/// Returns the scope of the page tokens of `GetAccountDeltas` with the filters of the request.
+fn account_deltas_scope(
+    account_id: AccountId,
+    from_block: BlockNumber,
+    to_block: Option<u32>,
+) -> String {
+    format!(
+        "GetAccountDeltas account_id={} from_block={from_block} to_block={}",
+        account_id.as_u64(),
+        scope_value(to_block)
+    )
+}
+
+/// Returns the scope of the page tokens of `ListNotes` with `filter`, each filter encoded
+/// explicitly so that the tokens don't depend on the layout of [NoteListFilter].
+fn list_notes_scope(filter: &NoteListFilter) -> String {
+    format!(
+        "ListNotes sender={} epoch={} aux_ranges={}",
+        scope_value(filter.sender.map(|sender| sender.as_u64())),
+        scope_value(filter.epoch),
+        filter
+            .aux_ranges
+            .iter()
+            .map(|range| format!("{}-{}", range.min, range.max))
+            .collect::<Vec<_>>()
+            .join(",")
+    )
+}
+
+/// Returns the scope of the page tokens of `ListAccounts` with `filter`, each filter encoded
+/// explicitly so that the tokens don't depend on the layout of [AccountFilter].
+fn list_accounts_scope(filter: &AccountFilter) -> String {
+    // The values of the request's `sort_order`
+    let sort_order = match filter.sort_order {
+        AccountSortOrder::BlockNumAsc => 0,
+        AccountSortOrder::BlockNumDesc => 1,
+        AccountSortOrder::AccountIdAsc => 2,
+        AccountSortOrder::AccountIdDesc => 3,
+    };
+
+    format!(
+        "ListAccounts is_public={} is_faucet={} updated_after={} sort_order={sort_order}",
+        scope_value(filter.is_public),
+        scope_value(filter.is_faucet),
+        scope_value(filter.updated_after),
+    )
+}
+
+/// Encodes an optional filter of a page token scope, `-` if it isn't set.
+fn scope_value<T: Display>(value: Option<T>) -> String {
+    value.map_or_else(|| "-".to_string(), |value| value.to_string())
+}
+
+/// Converts the `sort_order` field of [ListAccountsRequest]
+fn account_sort_order(sort_order: u32) -> Result<AccountSortOrder, Status> {
+    match sort_order {
+        0 => Ok(AccountSortOrder::BlockNumAsc),
+        1 => Ok(AccountSortOrder::BlockNumDesc),
+        2 => Ok(AccountSortOrder::AccountIdAsc),
+        3 => Ok(AccountSortOrder::AccountIdDesc),
+        _ => Err(Status::invalid_argument(format!("Invalid account sort order: {sort_order}"))),
+    }
+}
+
 #[instrument(target = "miden-store", skip_all, err)]
 fn validate_nullifiers(nullifiers: &[generated::digest::Digest]) -> Result<Vec<Nullifier>, Status> {
     nullifiers
@@ -502,3 +1696,113 @@ fn validate_nullifiers(nullifiers: &[generated::digest::Digest]) -> Result<Vec<N
         .collect::<Result<_, ConversionError>>()
         .map_err(|_| invalid_argument("Digest field is not in the modulus range"))
 }
+
+/// Returns whether the block `block_num` has at least `min_confirmations` confirmations, the block
+/// at `chain_tip` having one.
+///
+/// The callers read the chain tip after querying the data, so its block is never past the tip.
+fn is_confirmed(block_num: u32, chain_tip: BlockNumber, min_confirmations: u32) -> bool {
+    let depth = chain_tip.as_u32().saturating_sub(block_num);
+    u64::from(depth) + 1 >= u64::from(min_confirmations)
+}
+
+/// Validates the nullifier prefixes requested by a client, and returns their length.
+///
+/// A `prefix_len` of zero selects the default length, other values must be between
+/// [MIN_NULLIFIER_PREFIX_LEN] and [MAX_NULLIFIER_PREFIX_LEN].
+fn validate_nullifier_prefixes(prefix_len: u32, prefixes: &[u32]) -> Result<u32, Status> {
+    let prefix_len = match prefix_len {
+        0 => DEFAULT_NULLIFIER_PREFIX_LEN,
+        MIN_NULLIFIER_PREFIX_LEN..=MAX_NULLIFIER_PREFIX_LEN => prefix_len,
+        _ => {
+            return Err(Status::invalid_argument(format!(
+                "Nullifier prefix length must be between {MIN_NULLIFIER_PREFIX_LEN} and \
+                 {MAX_NULLIFIER_PREFIX_LEN}, got {prefix_len}"
+            )))
+        },
+    };
+
+    if let Some(prefix) = prefixes
+        .iter()
+        .find(|&&prefix| prefix.checked_shr(prefix_len).unwrap_or(0) != 0)
+    {
+        return Err(Status::invalid_argument(format!(
+            "Nullifier prefix {prefix} is longer than {prefix_len} bits"
+        )));
+    }
+
+    Ok(prefix_len)
+}
+
+/// Returns the limits of a state sync response, capped by [MAX_STATE_SYNC_BLOCKS] and
+/// [MAX_STATE_SYNC_NOTES].
+///
+/// A `max_blocks` of zero selects a single block, a `max_notes` of zero selects the cap.
+fn state_sync_limits(max_blocks: u32, max_notes: u32) -> StateSyncLimits {
+    let max_notes = match max_notes {
+        0 => MAX_STATE_SYNC_NOTES,
+        _ => max_notes.min(MAX_STATE_SYNC_NOTES),
+    };
+
+    StateSyncLimits {
+        max_blocks: max_blocks.clamp(1, MAX_STATE_SYNC_BLOCKS),
+        max_notes: max_notes as usize,
+        until: None,
+    }
+}
+
+/// Returns the part of `filter` synced by a state sync request, the values after the `synced` ones
+/// up to `max_len` values, and the number of values synced once the request is served.
+fn filter_slice<T>(filter: &[T], synced: usize, max_len: usize) -> Result<(&[T], usize), Status> {
+    if synced > filter.len() {
+        return Err(Status::invalid_argument(format!(
+            "The continuation token skips {synced} filter values, but the request has {}",
+            filter.len()
+        )));
+    }
+
+    let end = synced.saturating_add(max_len.max(1)).min(filter.len());
+    Ok((&filter[synced..end], end))
+}
+
+/// Converts a note of a state or note sync to its protobuf representation.
+fn note_sync_record(note: Note) -> NoteSyncRecord {
+    NoteSyncRecord {
+        note_index: note.note_created.absolute_note_index(),
+        note_type: note.note_created.note_type as u32,
+        note_id: Some(note.note_created.note_id.into()),
+        sender: Some(note.note_created.sender.into()),
+        tag: note.note_created.tag,
+        merkle_path: Some(note.merkle_path.into()),
+        merkle_path_nodes: Vec::new(),
+        aux: note.note_created.aux,
+    }
+}
+
+/// Returns the sync records of the notes of a block.
+///
+/// With `compact_merkle_paths`, the notes refer to the nodes of their Merkle paths by index, and
+/// the distinct nodes of the block are returned alongside the records.
+fn note_sync_records(
+    notes: Vec<Note>,
+    compact_merkle_paths: bool,
+) -> (Vec<NoteSyncRecord>, Vec<generated::digest::Digest>) {
+    if !compact_merkle_paths {
+        return (notes.into_iter().map(note_sync_record).collect(), Vec::new());
+    }
+
+    let mut path_nodes = MerklePathNodes::default();
+    let notes = notes
+        .into_iter()
+        .map(|note| {
+            let merkle_path_nodes = path_nodes.insert(&note.merkle_path);
+            NoteSyncRecord {
+                merkle_path: None,
+                merkle_path_nodes,
+                ..note_sync_record(note)
+            }
+        })
+        .collect();
+
+    (notes, path_nodes.into_nodes())
+}
@@ -0,0 +1,222 @@
+//! Continuation tokens of the state syncs truncated because their filters exceed the caps of the
+//! store, see [crate::config::SyncFiltersConfig], and cursors of the paginated requests.
+//!
+//! The cursors of the paginated requests are signed in their page tokens, see [PageTokens].
+//!
+//! [PageTokens]: miden_node_proto::domain::pagination::PageTokens
+use miden_objects::utils::{
+    ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable, SliceReader,
+};
+use tonic::Status;
+
+use crate::types::{AccountId, BlockNumber};
+
+// STATE SYNC CONTINUATION
+// ================================================================================================
+
+/// Number of the values of each filter of a state sync request synced so far.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(super) struct SyncedFilters {
+    pub account_ids: usize,
+    pub note_tags: usize,
+    pub nullifiers: usize,
+}
+
+/// Progress of a truncated state sync, sent to the client as an opaque token.
+///
+/// The continuation syncs the next values of the filters over the same blocks as the truncated
+/// update, from `block_num` to `until`, so that the client applies the updates together.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct StateSyncContinuation {
+    /// Block the truncated state sync started from
+    pub block_num: BlockNumber,
+    /// Last block of the truncated update
+    pub until: BlockNumber,
+    pub synced: SyncedFilters,
+}
+
+impl StateSyncContinuation {
+    /// Decodes a token, [None] if it is empty, i.e. the request starts a new state sync.
+    pub fn decode(token: &[u8]) -> Result<Option<Self>, Status> {
+        if token.is_empty() {
+            return Ok(None);
+        }
+
+        let mut reader = SliceReader::new(token);
+        let continuation = Self::read_from(&mut reader)
+            .ok()
+            .filter(|_| !reader.has_more_bytes())
+            .ok_or_else(|| {
+            Status::invalid_argument(format!("Invalid continuation token of {} bytes", token.len()))
+        })?;
+
+        Ok(Some(continuation))
+    }
+}
+
+impl Serializable for StateSyncContinuation {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        // The requests are far too small to hold more than `u32::MAX` filter values
+        target.write_u32(self.block_num.as_u32());
+        target.write_u32(self.until.as_u32());
+        target.write_u32(self.synced.account_ids as u32);
+        target.write_u32(self.synced.note_tags as u32);
+        target.write_u32(self.synced.nullifiers as u32);
+    }
+}
+
+impl Deserializable for StateSyncContinuation {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        Ok(Self {
+            block_num: source.read_u32()?.into(),
+            until: source.read_u32()?.into(),
+            synced: SyncedFilters {
+                account_ids: source.read_u32()? as usize,
+                note_tags: source.read_u32()? as usize,
+                nullifiers: source.read_u32()? as usize,
+            },
+        })
+    }
+}
+
+// BLOCK RANGE CURSOR
+// ================================================================================================
+
+/// Position of a page of the endpoints paging through a range of blocks, `GetNullifiersByPrefix`
+/// and `GetAccountDeltas`, signed in its page token.
+///
+/// The pages cover the blocks up to `until`, fixed when the first page was requested, so that the
+/// pages don't depend on the blocks applied in between.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct BlockRangeCursor {
+    /// Last block of the previous page
+    pub block_num: BlockNumber,
+    /// Last block of the last page
+    pub until: BlockNumber,
+}
+
+impl Serializable for BlockRangeCursor {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u32(self.block_num.as_u32());
+        target.write_u32(self.until.as_u32());
+    }
+}
+
+impl Deserializable for BlockRangeCursor {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        Ok(Self {
+            block_num: source.read_u32()?.into(),
+            until: source.read_u32()?.into(),
+        })
+    }
+}
+
+// LIST CURSORS
+// ================================================================================================
+
+/// Position of a page of `ListNotes`, the last note of the previous page, signed in its page token.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct NotesListCursor {
+    pub block_num: BlockNumber,
+    pub batch_index: u32,
+    pub note_index: u32,
+}
+
+impl Serializable for NotesListCursor {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u32(self.block_num.as_u32());
+        target.write_u32(self.batch_index);
+        target.write_u32(self.note_index);
+    }
+}
+
+impl Deserializable for NotesListCursor {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        Ok(Self {
+            block_num: source.read_u32()?.into(),
+            batch_index: source.read_u32()?,
+            note_index: source.read_u32()?,
+        })
+    }
+}
+
+/// Position of a page of `ListAccounts`, the last account of the previous page, signed in its page
+/// token.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct AccountsListCursor {
+    /// Block of the latest update of the account
+    pub block_num: BlockNumber,
+    pub account_id: AccountId,
+}
+
+impl Serializable for AccountsListCursor {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u32(self.block_num.as_u32());
+        target.write_u64(self.account_id.as_u64());
+    }
+}
+
+impl Deserializable for AccountsListCursor {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        Ok(Self {
+            block_num: source.read_u32()?.into(),
+            account_id: source.read_u64()?.into(),
+        })
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use tonic::Code;
+
+    use super::*;
+
+    #[test]
+    fn test_continuation_round_trip() {
+        let continuation = StateSyncContinuation {
+            block_num: BlockNumber::new(10),
+            until: BlockNumber::new(25),
+            synced: SyncedFilters {
+                account_ids: 1,
+                note_tags: 1000,
+                nullifiers: 0,
+            },
+        };
+
+        let token = continuation.to_bytes();
+        assert_eq!(StateSyncContinuation::decode(&token).unwrap(), Some(continuation));
+
+        // an empty token starts a new sync
+        assert_eq!(StateSyncContinuation::decode(&[]).unwrap(), None);
+
+        let err = StateSyncContinuation::decode(&token[1..]).unwrap_err();
+        assert_eq!(err.code(), Code::InvalidArgument);
+        let err = StateSyncContinuation::decode(&[token.as_slice(), &[0]].concat()).unwrap_err();
+        assert_eq!(err.code(), Code::InvalidArgument);
+    }
+
+    #[test]
+    fn test_list_cursors_round_trip() {
+        let cursor = NotesListCursor {
+            block_num: BlockNumber::new(7),
+            batch_index: 2,
+            note_index: 9,
+        };
+        assert_eq!(NotesListCursor::read_from_bytes(&cursor.to_bytes()), Ok(cursor));
+
+        let cursor = AccountsListCursor {
+            block_num: BlockNumber::new(3),
+            account_id: AccountId::new(u64::MAX - 1),
+        };
+        assert_eq!(AccountsListCursor::read_from_bytes(&cursor.to_bytes()), Ok(cursor));
+
+        let cursor = BlockRangeCursor {
+            block_num: BlockNumber::new(12),
+            until: BlockNumber::new(40),
+        };
+        assert_eq!(BlockRangeCursor::read_from_bytes(&cursor.to_bytes()), Ok(cursor));
+    }
+}
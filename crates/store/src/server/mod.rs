@@ -1,26 +1,40 @@
-use std::{net::ToSocketAddrs, sync::Arc};
+use std::{future::Future, net::ToSocketAddrs, sync::Arc};
 
-use miden_node_proto::generated::store::api_server;
-use miden_node_utils::errors::ApiError;
+use miden_node_proto::{domain::pagination::PageTokens, generated::store::api_server};
+use miden_node_utils::{
+    access_log::AccessLogLayer,
+    errors::ApiError,
+    runtime::{run_named, spawn_named, start_runtime_metrics_sampling},
+};
 use tonic::transport::Server;
 use tracing::info;
 
-use crate::{config::StoreConfig, db::Db, state::State, COMPONENT};
+use crate::{
+    backup::schedule_backups,
+    config::StoreConfig,
+    db::{monitor_size, Db},
+    network::{follow, FollowProgress},
+    state::State,
+    COMPONENT,
+};
 
-mod api;
+pub(crate) mod api;
+mod continuation;
 
 // STORE INITIALIZER
 // ================================================================================================
 
-pub async fn serve(config: StoreConfig, db: Db) -> Result<(), ApiError> {
+/// Serves the store until `shutdown` completes, the in-flight requests are then completed and the
+/// committed blocks are flushed to the database file, see [State::flush].
+pub async fn serve(
+    config: StoreConfig,
+    db: Db,
+    shutdown: impl Future<Output = ()> + Send + 'static,
+) -> Result<(), ApiError> {
     info!(target: COMPONENT, %config, "Initializing server");
 
-    let state = Arc::new(
-        State::load(db)
-            .await
-            .map_err(|err| ApiError::DatabaseConnectionFailed(err.to_string()))?,
-    );
-    let store = api_server::ApiServer::new(api::StoreApi { state });
+    let state = load_state(&config, db).await?;
+    let store = init_with_state(&config, state.clone())?;
 
     info!(target: COMPONENT, "Server initialized");
 
@@ -31,11 +45,77 @@ pub async fn serve(config: StoreConfig, db: Db) -> Result<(), ApiError> {
         .next()
         .ok_or_else(|| ApiError::AddressResolutionFailed(config.endpoint.to_string()))?;
 
-    Server::builder()
+    let server = Server::builder()
+        .layer(AccessLogLayer::new(COMPONENT, config.access_log.as_ref()))
         .add_service(store)
-        .serve(addr)
-        .await
-        .map_err(ApiError::ApiServeFailed)?;
+        .serve_with_shutdown(addr, shutdown);
+    run_named("store-grpc-server", server).await.map_err(ApiError::ApiServeFailed)?;
+
+    if !config.read_only {
+        info!(target: COMPONENT, "Flushing the database before stopping");
+        state
+            .flush()
+            .await
+            .map_err(|err| ApiError::DatabaseFlushFailed(err.to_string()))?;
+    }
+
+    info!(target: COMPONENT, "Server stopped");
 
     Ok(())
 }
+
+/// Loads the state from `db` and builds the store's gRPC service, the database size is sampled and
+/// the database is backed up in the background.
+///
+/// If the store is a replica, the blocks of the followed node are applied in the background. If it
+/// is read-only, the blocks are rejected and the database isn't backed up.
+pub async fn init(
+    config: &StoreConfig,
+    db: Db,
+) -> Result<api_server::ApiServer<impl api_server::Api>, ApiError> {
+    init_with_state(config, load_state(config, db).await?)
+}
+
+async fn load_state(config: &StoreConfig, db: Db) -> Result<Arc<State>, ApiError> {
+    let state = State::load(db, &config.cache)
+        .await
+        .map_err(|err| ApiError::DatabaseConnectionFailed(err.to_string()))?;
+
+    Ok(Arc::new(state))
+}
+
+/// Builds the store's gRPC service serving `state`, see [init].
+fn init_with_state(
+    config: &StoreConfig,
+    state: Arc<State>,
+) -> Result<api_server::ApiServer<impl api_server::Api>, ApiError> {
+    start_runtime_metrics_sampling();
+    spawn_named("database-size-monitor", monitor_size(state.db(), config.database_size));
+    // A read-only database never changes, there is nothing new to back up
+    if let Some(backup_config) = config.backup.as_ref().filter(|_| !config.read_only) {
+        spawn_named("backup-scheduler", schedule_backups(state.clone(), backup_config.clone()));
+    }
+
+    let following = match &config.follow {
+        Some(follow_config) => {
+            let public_key = follow_config
+                .public_key()
+                .map_err(|err| ApiError::InvalidSequencerPublicKey(err.to_string()))?;
+            let progress = Arc::new(FollowProgress::default());
+            spawn_named(
+                "follower",
+                follow(state.clone(), follow_config.rpc_url.clone(), public_key, progress.clone()),
+            );
+            Some(progress)
+        },
+        None => None,
+    };
+
+    Ok(api_server::ApiServer::new(api::StoreApi {
+        state,
+        following,
+        read_only: config.read_only,
+        sync_filters: config.sync_filters,
+        page_tokens: PageTokens::random(),
+    }))
+}
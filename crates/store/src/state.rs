@@ -2,35 +2,51 @@
 //!
 //! The [State] provides data access and modifications methods, its main purpose is to ensure that
 //! data is atomically written, and that reads are consistent.
-use std::{mem, sync::Arc};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    panic,
+    sync::Arc,
+    thread,
+};
 
 use miden_node_proto::{
-    domain::accounts::{AccountInfo, AccountUpdateDetails},
+    domain::{
+        accounts::{AccountInfo, AccountLookup, AccountSummary, AccountUpdateDetails},
+        transactions::{TransactionFee, TransactionReceipt},
+    },
     AccountInputRecord, NullifierWitness,
 };
-use miden_node_utils::formatting::{format_account_id, format_array};
+use miden_node_utils::{chain_params::ACCOUNT_TREE_DEPTH, formatting::format_array};
 use miden_objects::{
-    block::BlockNoteTree,
     crypto::{
+        dsa::rpo_falcon512::Signature,
         hash::rpo::RpoDigest,
-        merkle::{LeafIndex, Mmr, MmrDelta, MmrPeaks, SimpleSmt, SmtProof, ValuePath},
+        merkle::{LeafIndex, MerklePath, Mmr, MmrDelta, MmrPeaks, SimpleSmt, SmtProof, ValuePath},
     },
-    notes::{NoteId, NoteMetadata, Nullifier},
-    AccountError, BlockHeader, ACCOUNT_TREE_DEPTH, ZERO,
+    notes::{NoteId, Nullifier},
+    AccountError, BlockHeader,
 };
 use tokio::{
-    sync::{oneshot, Mutex, RwLock},
+    sync::{oneshot, watch, Mutex, RwLock},
     time::Instant,
 };
 use tracing::{error, info, info_span, instrument};
 
 use crate::{
-    db::{Db, Note, NoteCreated, NullifierInfo, StateSyncUpdate},
+    cache::StateCache,
+    config::CacheConfig,
+    db::{
+        build_note_tree, AccountDeltaInfo, AccountFilter, Db, Note, NoteConsumption, NoteCreated,
+        NoteFilter, NoteListFilter, NoteSyncUpdate, NullifierInfo, RowCounts, StateSyncLimits,
+        StateSyncUpdate,
+    },
     errors::{
-        ApplyBlockError, DatabaseError, GetBlockInputsError, StateInitializationError,
-        StateSyncError,
+        AccountDeltasError, ApplyBlockError, BlockInclusionProofError, CheckNullifiersError,
+        DatabaseError, GetBlockInputsError, NoteAuthenticationInfoError, NoteSyncError,
+        StateInitializationError, StateSyncError,
     },
     nullifier_tree::NullifierTree,
+    subscriptions::{AccountSubscription, AccountSubscriptions},
     types::{AccountId, BlockNumber},
     COMPONENT,
 };
@@ -41,7 +57,57 @@ use crate::{
 #[derive(Debug)]
 pub struct TransactionInputs {
     pub account_hash: RpoDigest,
-    pub nullifiers: Vec<NullifierInfo>,
+    /// The block in which each nullifier was consumed, zero if it wasn't
+    pub nullifiers: Vec<(Nullifier, BlockNumber)>,
+}
+
+/// Number and hash of the latest block in the chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChainTip {
+    pub block_num: BlockNumber,
+    pub block_hash: RpoDigest,
+}
+
+impl From<&BlockHeader> for ChainTip {
+    fn from(block_header: &BlockHeader) -> Self {
+        Self {
+            block_num: block_header.block_num().into(),
+            block_hash: block_header.hash(),
+        }
+    }
+}
+
+/// Outcome of a successful [State::apply_block].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApplyOutcome {
+    /// The block was applied by this request
+    Applied,
+    /// The block was applied before, e.g. by a request whose response was lost, nothing was
+    /// written again
+    AlreadyApplied,
+}
+
+/// Proof that a block is committed to by the chain root of a later block.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlockInclusionProof {
+    pub block_header: BlockHeader,
+    /// The block whose chain root commits to `block_header`
+    pub chain_tip: BlockHeader,
+    /// Path from the hash of `block_header` to one of `mmr_peaks`
+    pub mmr_path: MerklePath,
+    /// Peaks of the chain MMR with `chain_tip.block_num()` leaves, hashing to `chain_tip`'s chain
+    /// root
+    pub mmr_peaks: MmrPeaks,
+}
+
+/// Authentication paths of notes in the note tree of their block.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NoteAuthenticationInfo {
+    /// The block which created the notes, whose note root authenticates them
+    pub block_header: BlockHeader,
+    /// (note_index, note_id, merkle_path) of each note, `note_index` being the absolute index of
+    /// the note in the note tree
+    pub notes: Vec<(u32, RpoDigest, MerklePath)>,
 }
 
 /// Container for state that needs to be updated atomically.
@@ -63,21 +129,62 @@ pub struct State {
     /// To allow readers to access the tree data while an update in being performed, and prevent
     /// TOCTOU issues, there must be no concurrent writers. This locks to serialize the writers.
     writer: Mutex<()>,
+
+    /// Hash of the latest block whose application started, a request sending the same block again
+    /// while it is being applied waits for the outcome of the first one.
+    attempted_block: std::sync::Mutex<Option<RpoDigest>>,
+
+    /// Cache of immutable data, extended with the data of new blocks as they are applied.
+    cache: StateCache,
+
+    /// The latest block, updated once the block is committed to the DB. It is kept outside of
+    /// `inner` so reading it never waits for an in-progress [State::apply_block], and the block
+    /// subscriptions are notified of the new blocks through it.
+    chain_tip: watch::Sender<ChainTip>,
+
+    /// Subscriptions notified of the accounts updated by each applied block.
+    account_subscriptions: AccountSubscriptions,
 }
 
 impl State {
     /// Loads the state from the `db`.
+    ///
+    /// The nullifiers, block headers and account hashes are loaded concurrently, in chunks, and
+    /// their trees are built on blocking threads.
     #[instrument(target = "miden-store", skip_all)]
-    pub async fn load(mut db: Db) -> Result<Self, StateInitializationError> {
-        let nullifier_tree = load_nullifier_tree(&mut db).await?;
-        let chain_mmr = load_mmr(&mut db).await?;
-        let account_tree = load_accounts(&mut db).await?;
+    pub async fn load(
+        db: Db,
+        cache_config: &CacheConfig,
+    ) -> Result<Self, StateInitializationError> {
+        let (nullifier_tree, chain_mmr, account_tree, chain_tip) = tokio::try_join!(
+            load_nullifier_tree(&db),
+            load_mmr(&db),
+            load_accounts(&db),
+            load_chain_tip(&db),
+        )?;
+        check_tree_roots(&db, &chain_tip, &nullifier_tree, &account_tree).await?;
+        let chain_tip = watch::Sender::new(chain_tip);
 
         let inner = RwLock::new(InnerState { nullifier_tree, chain_mmr, account_tree });
 
         let writer = Mutex::new(());
         let db = Arc::new(db);
-        Ok(Self { db, inner, writer })
+        let cache = StateCache::new(cache_config);
+        let account_subscriptions = AccountSubscriptions::default();
+        Ok(Self {
+            db,
+            inner,
+            writer,
+            attempted_block: Default::default(),
+            cache,
+            chain_tip,
+            account_subscriptions,
+        })
+    }
+
+    /// Returns the database backing the state.
+    pub fn db(&self) -> Arc<Db> {
+        self.db.clone()
     }
 
     /// Apply changes of a new block to the DB and in-memory data structures.
@@ -98,17 +205,44 @@ impl State {
     ///   out-of-sync w.r.t. the DB.
     /// - the DB transaction is committed, and requests that read only from the DB can proceed to
     ///   use the fresh data.
-    /// - the in-memory structures are updated, and the lock is released.
+    /// - once the commit succeeded, the in-memory structures are updated, and the lock is
+    ///   released.
+    ///
+    /// `block` is the encoded block, as streamed to the replica nodes. `proof` is the validity
+    /// proof of the block, [None] if the block isn't proven.
+    ///
+    /// ## Note on resubmissions
+    ///
+    /// Applying a block is idempotent, so that the block producer can send a block again whenever
+    /// it doesn't know whether it was applied:
+    ///
+    /// - a block already in the chain isn't written again, [ApplyOutcome::AlreadyApplied] is
+    ///   returned.
+    /// - a block sent again while it is being applied waits for the first request to finish,
+    ///   instead of failing with [ApplyBlockError::ConcurrentWrite].
+    /// - if the DB transaction fails to commit, the in-memory structures are left unchanged and
+    ///   [ApplyBlockError::CommitFailed] is returned, nothing of the block is applied.
     // TODO: This span is logged in a root span, we should connect it to the parent span.
+    #[allow(clippy::too_many_arguments)]
     #[instrument(target = "miden-store", skip_all, err)]
     pub async fn apply_block(
         &self,
         block_header: BlockHeader,
+        signature: Option<Signature>,
+        block: Vec<u8>,
+        proof: Option<Vec<u8>>,
         nullifiers: Vec<Nullifier>,
         accounts: Vec<AccountUpdateDetails>,
         notes: Vec<NoteCreated>,
-    ) -> Result<(), ApplyBlockError> {
-        let _ = self.writer.try_lock().map_err(|_| ApplyBlockError::ConcurrentWrite)?;
+        transactions: Vec<TransactionReceipt>,
+    ) -> Result<ApplyOutcome, ApplyBlockError> {
+        let block_hash = block_header.hash();
+        let _writer = match self.writer.try_lock() {
+            Ok(writer) => writer,
+            Err(_) if self.attempted_block() == Some(block_hash) => self.writer.lock().await,
+            Err(_) => return Err(ApplyBlockError::ConcurrentWrite),
+        };
+        *self.attempted_block.lock().expect("attempted block lock poisoned") = Some(block_hash);
 
         // ensures the right block header is being processed
         let prev_block = self
@@ -117,12 +251,25 @@ impl State {
             .await?
             .ok_or(ApplyBlockError::DbBlockHeaderEmpty)?;
 
+        if block_header.block_num() <= prev_block.block_num() {
+            let applied = self
+                .db
+                .select_block_header_by_block_num(Some(block_header.block_num().into()))
+                .await?;
+            if applied.is_some_and(|applied| applied.hash() == block_hash) {
+                info!(target: COMPONENT, block_num = block_header.block_num(), %block_hash, "Block was already applied");
+                return Ok(ApplyOutcome::AlreadyApplied);
+            }
+        }
         if block_header.block_num() != prev_block.block_num() + 1 {
             return Err(ApplyBlockError::NewBlockInvalidBlockNum);
         }
         if block_header.prev_hash() != prev_block.hash() {
             return Err(ApplyBlockError::NewBlockInvalidPrevHash);
         }
+        if transactions.iter().any(|tx| tx.block_num != block_header.block_num()) {
+            return Err(ApplyBlockError::TransactionInvalidBlockNum);
+        }
 
         // scope to read in-memory data, validate the request, and compute intermediary values
         let (account_tree, chain_mmr, nullifier_tree, notes) = {
@@ -167,7 +314,7 @@ impl State {
                 let mut nullifier_tree = inner.nullifier_tree.clone();
                 for nullifier in nullifiers.iter() {
                     nullifier_tree
-                        .insert(nullifier, block_header.block_num())
+                        .insert(nullifier, block_header.block_num().into())
                         .map_err(ApplyBlockError::FailedToUpdateNullifierTree)?;
                 }
 
@@ -191,7 +338,8 @@ impl State {
             }
 
             // build notes tree
-            let note_tree = build_note_tree(&notes)?;
+            let note_tree =
+                build_note_tree(&notes).map_err(ApplyBlockError::FailedToCreateNoteTree)?;
             if note_tree.root() != block_header.note_root() {
                 return Err(ApplyBlockError::NewBlockInvalidNoteRoot);
             }
@@ -209,7 +357,7 @@ impl State {
                         .map_err(ApplyBlockError::UnableToCreateProofForNote)?;
 
                     Ok(Note {
-                        block_num: block_header.block_num(),
+                        block_num: block_header.block_num().into(),
                         note_created,
                         merkle_path,
                     })
@@ -229,31 +377,71 @@ impl State {
         // in-memory write lock. This requires the DB update to run concurrently, so a new task is
         // spawned.
         let db = self.db.clone();
+        let cached_notes = notes.clone();
+        let account_summaries: Vec<_> = accounts
+            .iter()
+            .map(|update| AccountSummary {
+                account_id: update.account_id,
+                account_hash: update.final_state_hash,
+                block_num: block_header.block_num(),
+                nonce: update.final_nonce(),
+            })
+            .collect();
         let handle = tokio::spawn(async move {
-            db.apply_block(allow_acquire, acquire_done, block_header, notes, nullifiers, accounts)
-                .await
+            db.apply_block(
+                allow_acquire,
+                acquire_done,
+                block_header,
+                signature,
+                block,
+                proof,
+                notes,
+                nullifiers,
+                accounts,
+                transactions,
+            )
+            .await
         });
 
-        acquired_allowed
-            .await
-            .map_err(ApplyBlockError::BlockApplyingBrokenBecauseOfClosedChannel)?;
+        if let Err(err) = acquired_allowed.await {
+            // The DB task failed before the transaction was ready to be committed
+            return Err(match handle.await {
+                Ok(Err(db_err)) => ApplyBlockError::DatabaseError(db_err),
+                _ => ApplyBlockError::BlockApplyingBrokenBecauseOfClosedChannel(err),
+            });
+        }
 
-        // scope to update the in-memory data
-        {
+        // The in-memory data is locked until the DB transaction is committed, and is only updated
+        // once it is, so that the readers never see the changes of an uncommitted block
+        let result = {
             let mut inner = self.inner.write().await;
             let _ = inform_acquire_done.send(());
 
-            let _ = mem::replace(&mut inner.chain_mmr, chain_mmr);
-            let _ = mem::replace(&mut inner.nullifier_tree, nullifier_tree);
-            let _ = mem::replace(&mut inner.account_tree, account_tree);
-        }
+            let result = handle.await;
+            if let Ok(Ok(())) = result {
+                inner.chain_mmr = chain_mmr;
+                inner.nullifier_tree = nullifier_tree;
+                inner.account_tree = account_tree;
+            }
+            result
+        };
 
-        match handle.await {
-            // These errors should never happen. It is unclear if the state of the node would be
-            // valid because the apply_block task may have failed when committing the transaction, so
-            // the in-memory and the DB state would be out-of-sync.
-            //
-            // TODO: shutdown #91
+        let err = match result {
+            Ok(Ok(())) => {
+                self.chain_tip.send_replace((&block_header).into());
+                self.cache.insert_block_header(block_header);
+                self.cache.insert_notes(&cached_notes);
+                self.account_subscriptions.notify(&account_summaries);
+
+                info!(
+                    block_hash = block_header.hash().to_hex(),
+                    block_num = block_header.block_num(),
+                    COMPONENT,
+                    "apply_block sucessfull"
+                );
+
+                return Ok(ApplyOutcome::Applied);
+            },
             Err(err) => {
                 error!(
                     is_cancelled = err.is_cancelled(),
@@ -261,21 +449,56 @@ impl State {
                     COMPONENT,
                     "apply_block task joined with an error"
                 );
+                err.to_string()
             },
             Ok(Err(err)) => {
                 error!(err = err.to_string(), COMPONENT, "apply_block failed with a DB error");
+                err.to_string()
             },
-            Ok(Ok(())) => {
-                info!(
-                    block_hash = block_header.hash().to_hex(),
-                    block_num = block_header.block_num(),
-                    COMPONENT,
-                    "apply_block sucessfull"
-                );
-            },
-        }
+        };
+
+        // The DB transaction wasn't committed, the in-memory structures were left unchanged so
+        // that the block can be applied again
+        Err(ApplyBlockError::CommitFailed(err))
+    }
+
+    /// Returns the hash of the latest block whose application started.
+    fn attempted_block(&self) -> Option<RpoDigest> {
+        *self.attempted_block.lock().expect("attempted block lock poisoned")
+    }
+
+    /// Returns the number and hash of the latest block, without querying the database.
+    pub fn chain_tip(&self) -> ChainTip {
+        *self.chain_tip.borrow()
+    }
+
+    /// Queries the ids of the public accounts authenticated by `public_key`, [None] if the account
+    /// public key index is disabled.
+    pub async fn get_accounts_by_public_key(
+        &self,
+        public_key: RpoDigest,
+    ) -> Result<Option<Vec<AccountId>>, DatabaseError> {
+        self.db.select_accounts_by_public_key(public_key).await
+    }
+
+    /// Returns a receiver notified of the latest block each time a block is applied.
+    pub fn subscribe_chain_tip(&self) -> watch::Receiver<ChainTip> {
+        self.chain_tip.subscribe()
+    }
+
+    /// Returns a stream of the updates of `account_ids`, see [AccountSubscriptions::subscribe].
+    pub fn subscribe_accounts(&self, account_ids: BTreeSet<AccountId>) -> AccountSubscription {
+        self.account_subscriptions.subscribe(account_ids)
+    }
 
-        Ok(())
+    /// Queries at most `limit` encoded blocks from the database, starting from the block
+    /// `block_num`.
+    pub async fn get_blocks(
+        &self,
+        block_num: BlockNumber,
+        limit: usize,
+    ) -> Result<Vec<(BlockNumber, Vec<u8>)>, DatabaseError> {
+        self.db.select_blocks(block_num, limit).await
     }
 
     /// Queries a [BlockHeader] from the database.
@@ -286,17 +509,125 @@ impl State {
         &self,
         block_num: Option<BlockNumber>,
     ) -> Result<Option<BlockHeader>, DatabaseError> {
-        self.db.select_block_header_by_block_num(block_num).await
+        if let Some(block_header) = block_num.and_then(|num| self.cache.get_block_header(num)) {
+            return Ok(Some(block_header));
+        }
+
+        let block_header = self.db.select_block_header_by_block_num(block_num).await?;
+        if let Some(block_header) = block_header {
+            self.cache.insert_block_header(block_header);
+        }
+
+        Ok(block_header)
     }
 
-    /// Generates membership proofs for each one of the `nullifiers` against the latest nullifier
-    /// tree.
+    /// Queries the sequencer's signature of the block `block_num` from the database, [None] if the
+    /// block doesn't exist or is not signed.
+    #[instrument(target = "miden-store", skip_all, ret(level = "debug"), err)]
+    pub async fn get_block_signature(
+        &self,
+        block_num: BlockNumber,
+    ) -> Result<Option<Signature>, DatabaseError> {
+        self.db.select_block_signature(block_num).await
+    }
+
+    /// Queries the validity proof of the block `block_num` from the database, [None] if the block
+    /// doesn't exist, isn't proven or its proof was pruned.
+    #[instrument(target = "miden-store", skip_all, err)]
+    pub async fn get_block_proof(
+        &self,
+        block_num: BlockNumber,
+    ) -> Result<Option<Vec<u8>>, DatabaseError> {
+        self.db.select_block_proof(block_num).await
+    }
+
+    /// Generates membership proofs for each one of the `nullifiers` against the nullifier tree as
+    /// of `block_num`, the latest nullifier tree if [None].
     ///
-    /// Note: these proofs are invalidated once the nullifier tree is modified, i.e. on a new block.
-    #[instrument(target = "miden-store", skip_all, ret(level = "debug"))]
-    pub async fn check_nullifiers(&self, nullifiers: &[Nullifier]) -> Vec<SmtProof> {
-        let inner = self.inner.read().await;
-        nullifiers.iter().map(|n| inner.nullifier_tree.open(n)).collect()
+    /// Each proof is returned with the timestamp of the block which consumed the nullifier, zero if
+    /// it isn't consumed as of `block_num`.
+    ///
+    /// Note: the proofs against the latest tree are invalidated once the nullifier tree is
+    /// modified, i.e. on a new block.
+    #[instrument(target = "miden-store", skip_all, ret(level = "debug"), err)]
+    pub async fn check_nullifiers(
+        &self,
+        nullifiers: &[Nullifier],
+        block_num: Option<BlockNumber>,
+    ) -> Result<Vec<(SmtProof, u64)>, CheckNullifiersError> {
+        // the tree is updated before the chain tip, it always includes the blocks up to it
+        let chain_tip = self.chain_tip().block_num;
+        if let Some(block_num) = block_num.filter(|&block_num| block_num > chain_tip) {
+            return Err(CheckNullifiersError::UnknownBlock(block_num));
+        }
+
+        let (proofs, block_nums): (Vec<_>, Vec<_>) = {
+            let inner = self.inner.read().await;
+            let tree = &inner.nullifier_tree;
+            nullifiers
+                .iter()
+                .map(|n| match block_num {
+                    Some(block_num) => Ok((
+                        tree.open_at(n, block_num)?,
+                        tree.get_block_num(n).filter(|&consumed_in| consumed_in <= block_num),
+                    )),
+                    None => Ok((tree.open(n), tree.get_block_num(n))),
+                })
+                .collect::<Result<Vec<_>, CheckNullifiersError>>()?
+                .into_iter()
+                .unzip()
+        };
+
+        // The consumed nullifiers' blocks are committed, their timestamps are in the database
+        let consumed: BTreeSet<BlockNumber> = block_nums.iter().flatten().copied().collect();
+        let timestamps = if consumed.is_empty() {
+            BTreeMap::new()
+        } else {
+            self.db.select_block_timestamps(consumed.into_iter().collect()).await?
+        };
+
+        Ok(proofs
+            .into_iter()
+            .zip(block_nums)
+            .map(|(proof, block_num)| {
+                let timestamp = block_num
+                    .and_then(|block_num| timestamps.get(&block_num).copied())
+                    .unwrap_or_default();
+                (proof, timestamp)
+            })
+            .collect())
+    }
+
+    /// Returns the nullifiers whose `prefix_len` high bits match one of `nullifier_prefixes`, with
+    /// the block in which they were consumed.
+    pub async fn check_nullifiers_by_prefix(
+        &self,
+        prefix_len: u32,
+        nullifier_prefixes: Vec<u32>,
+    ) -> Result<Vec<NullifierInfo>, DatabaseError> {
+        self.db.select_nullifiers_by_prefix(prefix_len, nullifier_prefixes).await
+    }
+
+    /// Returns a page of the nullifiers consumed in `(block_start, block_end]` whose `prefix_len`
+    /// high bits match one of `nullifier_prefixes`, and the last block of the page if it ends
+    /// before `block_end`.
+    pub async fn get_nullifiers_by_prefix(
+        &self,
+        block_start: BlockNumber,
+        block_end: BlockNumber,
+        prefix_len: u32,
+        nullifier_prefixes: Vec<u32>,
+        max_nullifiers: usize,
+    ) -> Result<(Vec<NullifierInfo>, Option<BlockNumber>), DatabaseError> {
+        self.db
+            .select_nullifiers_page_by_block_range(
+                block_start,
+                block_end,
+                prefix_len,
+                nullifier_prefixes,
+                max_nullifiers,
+            )
+            .await
     }
 
     /// Queries a list of [Note] from the database.
@@ -304,14 +635,51 @@ impl State {
     /// If the provided list of [NoteId] given is empty or no [Note] matches the provided [NoteId]
     /// an empty list is returned.
     pub async fn get_notes_by_id(&self, note_ids: Vec<NoteId>) -> Result<Vec<Note>, DatabaseError> {
-        self.db.select_notes_by_id(note_ids).await
+        let (mut notes, missing) = self.cache.get_notes(note_ids);
+        if !missing.is_empty() {
+            let fetched = self.db.select_notes_by_id(missing).await?;
+            self.cache.insert_notes(&fetched);
+            notes.extend(fetched);
+        }
+
+        Ok(notes)
+    }
+
+    /// Returns the receipt of the transaction with the given id, or [None] if the transaction
+    /// hasn't been included in a block.
+    pub async fn get_transaction_receipt(
+        &self,
+        transaction_id: RpoDigest,
+    ) -> Result<Option<TransactionReceipt>, DatabaseError> {
+        self.db.select_transaction_receipt(transaction_id).await
+    }
+
+    /// Returns the consumption of the note with the given id, or [None] if the note isn't consumed
+    /// or its nullifier isn't known, e.g. for the private notes.
+    pub async fn get_note_consumption(
+        &self,
+        note_id: RpoDigest,
+    ) -> Result<Option<NoteConsumption>, DatabaseError> {
+        self.db.select_note_consumption(note_id).await
+    }
+
+    /// Returns the serialized script with the given root, or [None] if no public note stored by
+    /// the node uses this script.
+    pub async fn get_note_script(
+        &self,
+        script_root: RpoDigest,
+    ) -> Result<Option<Vec<u8>>, DatabaseError> {
+        self.db.select_note_script(script_root).await
     }
 
     /// Loads data to synchronize a client.
     ///
     /// The client's request contains a list of tag prefixes, this method will return the first
-    /// block with a matching tag, or the chain tip. All the other values are filter based on this
-    /// block range.
+    /// blocks with a matching tag, up to `limits`, or the chain tip. All the other values are
+    /// filter based on this block range.
+    ///
+    /// Returns the paths authenticating the preceding blocks of the update in the chain MMR
+    /// updated by the returned delta, in the same order as the blocks.
     ///
     /// # Arguments
     ///
@@ -321,26 +689,44 @@ impl State {
     /// - `note_tag_prefixes`: Only the 16 high bits of the tags the client is interested in, result
     ///   will include notes with matching prefixes, the first block with a matching note determines
     ///   the block range.
-    /// - `nullifier_prefixes`: Only the 16 high bits of the nullifiers the client is interested in,
+    /// - `nullifier_prefix_len`: Number of high bits of the nullifiers in `nullifier_prefixes`.
+    /// - `nullifier_prefixes`: Only the high bits of the nullifiers the client is interested in,
     ///   results will include nullifiers matching prefixes produced in the given block range.
+    /// - `limits`: Maximum number of blocks with matching notes and of notes in the update.
+    /// - `include_account_details`: Include the details of the updated public accounts.
+    #[allow(clippy::too_many_arguments)]
     #[instrument(target = "miden-store", skip_all, ret(level = "debug"), err)]
     pub async fn sync_state(
         &self,
         block_num: BlockNumber,
         account_ids: &[AccountId],
         note_tag_prefixes: &[u32],
+        nullifier_prefix_len: u32,
         nullifier_prefixes: &[u32],
-    ) -> Result<(StateSyncUpdate, MmrDelta), StateSyncError> {
+        limits: StateSyncLimits,
+        include_account_details: bool,
+    ) -> Result<(StateSyncUpdate, MmrDelta, Vec<MerklePath>), StateSyncError> {
         let inner = self.inner.read().await;
 
         let state_sync = self
             .db
-            .get_state_sync(block_num, account_ids, note_tag_prefixes, nullifier_prefixes)
+            .get_state_sync(
+                block_num,
+                account_ids,
+                note_tag_prefixes,
+                nullifier_prefix_len,
+                nullifier_prefixes,
+                limits,
+                include_account_details,
+            )
             .await?;
 
-        let delta = if block_num == state_sync.block_header.block_num() {
+        let delta = if block_num.as_u32() == state_sync.block_header.block_num() {
             // The client is in sync with the chain tip.
-            MmrDelta { forest: block_num as usize, data: vec![] }
+            MmrDelta {
+                forest: block_num.as_u32() as usize,
+                data: vec![],
+            }
         } else {
             // Important notes about the boundary conditions:
             //
@@ -350,7 +736,7 @@ impl State {
             // points cancel out and don't require adjusting.
             // - Mmr::get_delta is inclusive, whereas the sync_state request block_num is defined to be
             // exclusive, so the from_forest has to be adjusted with a +1
-            let from_forest = (block_num + 1) as usize;
+            let from_forest = (block_num.as_u32() + 1) as usize;
             let to_forest = state_sync.block_header.block_num() as usize;
             inner
                 .chain_mmr
@@ -358,7 +744,140 @@ impl State {
                 .map_err(StateSyncError::FailedToBuildMmrDelta)?
         };
 
-        Ok((state_sync, delta))
+        // The preceding blocks are leaves of the MMR with `block_header.block_num` leaves, the
+        // forest of the delta
+        let forest = state_sync.block_header.block_num() as usize;
+        let mmr_paths = state_sync
+            .preceding_blocks
+            .iter()
+            .map(|block| {
+                inner
+                    .chain_mmr
+                    .open(block.block_header.block_num() as usize, forest)
+                    .map(|proof| proof.merkle_path)
+                    .map_err(StateSyncError::FailedToOpenMmr)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok((state_sync, delta, mmr_paths))
+    }
+
+    /// Loads the notes of the first block after `block_num` with a note matching `filter`.
+    ///
+    /// Returns the notes together with the path authenticating the block in the chain MMR, which
+    /// commits to all blocks up to and including the chain tip.
+    #[instrument(target = "miden-store", skip_all, ret(level = "debug"), err)]
+    pub async fn sync_notes(
+        &self,
+        block_num: BlockNumber,
+        filter: NoteFilter,
+    ) -> Result<(NoteSyncUpdate, MerklePath), NoteSyncError> {
+        // The read lock prevents a new block from being committed, keeping the chain MMR and the
+        // DB consistent.
+        let inner = self.inner.read().await;
+
+        let note_sync = self.db.get_note_sync(block_num, filter).await?;
+
+        let mmr_proof = inner
+            .chain_mmr
+            .open(note_sync.block_header.block_num() as usize, inner.chain_mmr.forest())
+            .map_err(NoteSyncError::FailedToOpenMmr)?;
+
+        Ok((note_sync, mmr_proof.merkle_path))
+    }
+
+    /// Returns the proof that the block `block_num` is committed to by the chain root of the block
+    /// `chain_tip`, the latest block if [None].
+    ///
+    /// The chain root of a block commits to all the blocks before it, `block_num` must be lower
+    /// than `chain_tip`.
+    #[instrument(target = "miden-store", skip_all, ret(level = "debug"), err)]
+    pub async fn get_block_inclusion_proof(
+        &self,
+        block_num: BlockNumber,
+        chain_tip: Option<BlockNumber>,
+    ) -> Result<BlockInclusionProof, BlockInclusionProofError> {
+        // The read lock prevents a new block from being committed, keeping the chain MMR and the
+        // DB consistent.
+        let inner = self.inner.read().await;
+
+        let latest = BlockNumber::new(inner.chain_mmr.forest() as u32 - 1);
+        let chain_tip = chain_tip.unwrap_or(latest);
+        if chain_tip > latest {
+            return Err(BlockInclusionProofError::UnknownBlock(chain_tip));
+        }
+        if block_num >= chain_tip {
+            return Err(BlockInclusionProofError::NotCommitted { block_num, chain_tip });
+        }
+
+        let forest = chain_tip.as_u32() as usize;
+        let mmr_proof = inner
+            .chain_mmr
+            .open(block_num.as_u32() as usize, forest)
+            .map_err(BlockInclusionProofError::FailedToOpenMmr)?;
+        let mmr_peaks = inner
+            .chain_mmr
+            .peaks(forest)
+            .map_err(BlockInclusionProofError::FailedToOpenMmr)?;
+
+        let block_header = self
+            .get_block_header(Some(block_num))
+            .await?
+            .ok_or(BlockInclusionProofError::UnknownBlock(block_num))?;
+        let chain_tip = self
+            .get_block_header(Some(chain_tip))
+            .await?
+            .ok_or(BlockInclusionProofError::UnknownBlock(chain_tip))?;
+
+        Ok(BlockInclusionProof {
+            block_header,
+            chain_tip,
+            mmr_path: mmr_proof.merkle_path,
+            mmr_peaks,
+        })
+    }
+
+    /// Returns the authentication paths of the notes at `note_indexes` in the note tree of the
+    /// block `block_num`.
+    ///
+    /// The note tree is rebuilt from the notes of the block stored in the DB, and checked against
+    /// the note root of the block header, so the paths don't depend on the paths stored with the
+    /// notes.
+    #[instrument(target = "miden-store", skip_all, ret(level = "debug"), err)]
+    pub async fn get_note_authentication_info(
+        &self,
+        block_num: BlockNumber,
+        note_indexes: &[u32],
+    ) -> Result<NoteAuthenticationInfo, NoteAuthenticationInfoError> {
+        let block_header = self
+            .get_block_header(Some(block_num))
+            .await?
+            .ok_or(NoteAuthenticationInfoError::UnknownBlock(block_num))?;
+
+        let notes = self.db.select_block_notes(block_num).await?;
+        let note_tree = build_note_tree(&notes)
+            .map_err(|err| NoteAuthenticationInfoError::FailedToBuildNoteTree(block_num, err))?;
+        if note_tree.root() != block_header.note_root() {
+            return Err(NoteAuthenticationInfoError::NoteRootMismatch(block_num));
+        }
+
+        let notes_by_index: BTreeMap<u32, &NoteCreated> =
+            notes.iter().map(|note| (note.absolute_note_index(), note)).collect();
+        let notes = note_indexes
+            .iter()
+            .map(|&note_index| {
+                let note = notes_by_index
+                    .get(&note_index)
+                    .ok_or(NoteAuthenticationInfoError::UnknownNote { block_num, note_index })?;
+                let merkle_path = note_tree
+                    .get_note_path(note.batch_index as usize, note.note_index as usize)
+                    .map_err(NoteAuthenticationInfoError::FailedToOpenNoteTree)?;
+
+                Ok((note_index, note.note_id, merkle_path))
+            })
+            .collect::<Result<_, NoteAuthenticationInfoError>>()?;
+
+        Ok(NoteAuthenticationInfo { block_header, notes })
     }
 
     /// Returns data needed by the block producer to construct and prove the next block.
@@ -372,11 +891,12 @@ impl State {
     > {
         let inner = self.inner.read().await;
 
-        let latest = self
-            .db
-            .select_block_header_by_block_num(None)
-            .await?
-            .ok_or(GetBlockInputsError::DbBlockHeaderEmpty)?;
+        // The trees are opened while the header is queried from the DB
+        let (latest, (account_states, nullifier_input_records)) =
+            tokio::join!(self.db.select_block_header_by_block_num(None), async {
+                open_block_inputs(&inner, account_ids, nullifiers)
+            },);
+        let latest = latest?.ok_or(GetBlockInputsError::DbBlockHeaderEmpty)?;
 
         // sanity check
         if inner.chain_mmr.forest() != latest.block_num() as usize + 1 {
@@ -394,30 +914,8 @@ impl State {
                 error,
             }
         })?;
-        let account_states = account_ids
-            .iter()
-            .cloned()
-            .map(|account_id| {
-                let ValuePath { value: account_hash, path: proof } =
-                    inner.account_tree.open(&LeafIndex::new_max_depth(account_id));
-                Ok(AccountInputRecord {
-                    account_id: account_id.try_into()?,
-                    account_hash,
-                    proof,
-                })
-            })
-            .collect::<Result<_, AccountError>>()?;
-
-        let nullifier_input_records: Vec<NullifierWitness> = nullifiers
-            .iter()
-            .map(|nullifier| {
-                let proof = inner.nullifier_tree.open(nullifier);
-
-                NullifierWitness { nullifier: *nullifier, proof }
-            })
-            .collect();
 
-        Ok((latest, peaks, account_states, nullifier_input_records))
+        Ok((latest, peaks, account_states?, nullifier_input_records))
     }
 
     /// Returns data needed by the block producer to verify transactions validity.
@@ -427,75 +925,167 @@ impl State {
         account_id: AccountId,
         nullifiers: &[Nullifier],
     ) -> TransactionInputs {
-        info!(target: COMPONENT, account_id = %format_account_id(account_id), nullifiers = %format_array(nullifiers));
+        info!(target: COMPONENT, account_id = %account_id, nullifiers = %format_array(nullifiers));
 
         let inner = self.inner.read().await;
 
-        let account_hash = inner.account_tree.open(&LeafIndex::new_max_depth(account_id)).value;
+        let account_hash =
+            inner.account_tree.open(&LeafIndex::new_max_depth(account_id.into())).value;
 
         let nullifiers = nullifiers
             .iter()
-            .map(|nullifier| NullifierInfo {
-                nullifier: *nullifier,
-                block_num: inner.nullifier_tree.get_block_num(nullifier).unwrap_or_default(),
+            .map(|nullifier| {
+                (*nullifier, inner.nullifier_tree.get_block_num(nullifier).unwrap_or_default())
             })
             .collect();
 
         TransactionInputs { account_hash, nullifiers }
     }
 
-    /// Lists all known nullifiers with their inclusion blocks, intended for testing.
-    pub async fn list_nullifiers(&self) -> Result<Vec<(Nullifier, u32)>, DatabaseError> {
-        self.db.select_nullifiers().await
+    /// Lists at most `limit` known nullifiers after the nullifier `after` with their inclusion
+    /// blocks, ordered by nullifier, intended for testing.
+    pub async fn list_nullifiers(
+        &self,
+        after: Option<Nullifier>,
+        limit: usize,
+    ) -> Result<Vec<(Nullifier, BlockNumber)>, DatabaseError> {
+        self.db.select_nullifiers_page(after, limit).await
     }
 
-    /// Lists all known accounts, with their ids, latest state hash, and block at which the account was last
-    /// modified, intended for testing.
-    pub async fn list_accounts(&self) -> Result<Vec<AccountInfo>, DatabaseError> {
-        self.db.select_accounts().await
+    /// Lists at most `limit` known accounts matching `filter` after the account `after`, with
+    /// their ids, latest state hash, and block at which the account was last modified.
+    pub async fn list_accounts(
+        &self,
+        filter: AccountFilter,
+        after: Option<(BlockNumber, AccountId)>,
+        limit: usize,
+    ) -> Result<Vec<AccountInfo>, DatabaseError> {
+        self.db.select_accounts_page(filter, after, limit).await
     }
 
-    /// Lists all known notes, intended for testing.
-    pub async fn list_notes(&self) -> Result<Vec<Note>, DatabaseError> {
-        self.db.select_notes().await
+    /// Lists at most `limit` known notes matching `filter` after the note at `after`, ordered by
+    /// block and position in the block.
+    pub async fn list_notes(
+        &self,
+        filter: NoteListFilter,
+        after: Option<(BlockNumber, u32, u32)>,
+        limit: usize,
+    ) -> Result<Vec<Note>, DatabaseError> {
+        self.db.select_notes_page(filter, after, limit).await
+    }
+
+    /// Returns the number of accounts, notes and nullifiers of the chain, among others.
+    pub async fn row_counts(&self) -> Result<RowCounts, DatabaseError> {
+        self.db.select_row_counts().await
+    }
+
+    /// Returns at most `limit` deltas applied to the account `account_id` by the blocks in
+    /// `(block_start, block_end]`, ordered by block.
+    ///
+    /// Fails if the deltas of some blocks of the range weren't stored.
+    pub async fn get_account_deltas(
+        &self,
+        account_id: AccountId,
+        block_start: BlockNumber,
+        block_end: BlockNumber,
+        limit: usize,
+    ) -> Result<Vec<AccountDeltaInfo>, AccountDeltasError> {
+        let deltas_start = self.db.select_account_deltas_start().await?;
+        // The range is empty if `block_start` is the last block number
+        if block_start.child().is_some_and(|first_block| first_block < deltas_start) {
+            return Err(AccountDeltasError::DeltasNotStored(deltas_start));
+        }
+
+        Ok(self.db.select_account_deltas(account_id, block_start, block_end, limit).await?)
     }
 
-    /// Returns details for public (on-chain) account.
-    pub async fn get_account_details(&self, id: AccountId) -> Result<AccountInfo, DatabaseError> {
+    /// Returns the latest state of the account, with its details if the account is public.
+    pub async fn get_account_details(&self, id: AccountId) -> Result<AccountLookup, DatabaseError> {
         self.db.select_account(id).await
     }
+
+    /// Flushes the committed blocks to the database file, e.g. before taking a snapshot of it.
+    ///
+    /// Waits for the block being applied, if any, and prevents new blocks from being applied until
+    /// the flush completes.
+    #[instrument(target = "miden-store", skip_all, err)]
+    pub async fn flush(&self) -> Result<(), DatabaseError> {
+        let _writer = self.writer.lock().await;
+        self.db.checkpoint_wal().await
+    }
+
+    /// Returns the size of the database file, in bytes.
+    pub async fn database_bytes(&self) -> Result<u64, DatabaseError> {
+        self.db.select_database_bytes().await
+    }
+
+    /// Returns the total fees paid by the transactions of the chain, per faucet.
+    pub async fn collected_fees(&self) -> Result<Vec<TransactionFee>, DatabaseError> {
+        self.db.select_collected_fees().await
+    }
 }
 
 // UTILITIES
 // ================================================================================================
 
-/// Creates a [BlockNoteTree] from the `notes`.
-#[instrument(target = "miden-store", skip_all)]
-pub fn build_note_tree(notes: &[NoteCreated]) -> Result<BlockNoteTree, ApplyBlockError> {
-    // TODO: create SimpleSmt without this allocation
-    let mut entries: Vec<(usize, usize, (RpoDigest, NoteMetadata))> =
-        Vec::with_capacity(notes.len() * 2);
-
-    for note in notes.iter() {
-        let note_metadata =
-            NoteMetadata::new(note.sender.try_into()?, note.note_type, note.tag.into(), ZERO)?;
-        entries.push((
-            note.batch_index as usize,
-            note.note_index as usize,
-            (note.note_id, note_metadata),
-        ));
-    }
-
-    BlockNoteTree::with_entries(entries).map_err(ApplyBlockError::FailedToCreateNoteTree)
+/// Opens the accounts and the nullifiers of a block in the trees of `inner`, each collection in its
+/// own thread.
+fn open_block_inputs(
+    inner: &InnerState,
+    account_ids: &[AccountId],
+    nullifiers: &[Nullifier],
+) -> (Result<Vec<AccountInputRecord>, AccountError>, Vec<NullifierWitness>) {
+    thread::scope(|scope| {
+        let account_states = scope.spawn(|| {
+            account_ids
+                .iter()
+                .map(|&account_id| {
+                    let ValuePath { value: account_hash, path: proof } =
+                        inner.account_tree.open(&LeafIndex::new_max_depth(account_id.into()));
+                    Ok(AccountInputRecord {
+                        account_id: account_id.try_into()?,
+                        account_hash,
+                        proof,
+                    })
+                })
+                .collect()
+        });
+
+        let nullifier_input_records = nullifiers
+            .iter()
+            .map(|nullifier| NullifierWitness {
+                nullifier: *nullifier,
+                proof: inner.nullifier_tree.open(nullifier),
+            })
+            .collect();
+
+        (
+            account_states.join().expect("Opening the accounts panicked"),
+            nullifier_input_records,
+        )
+    })
+}
+
+/// Runs the CPU bound construction of a tree on a blocking thread, so that it doesn't delay the
+/// loading of the other tables, see [State::load].
+async fn build_tree<F, R>(build: F) -> R
+where
+    F: FnOnce() -> R + Send + 'static,
+    R: Send + 'static,
+{
+    tokio::task::spawn_blocking(build)
+        .await
+        .unwrap_or_else(|err| panic::resume_unwind(err.into_panic()))
 }
 
 #[instrument(target = "miden-store", skip_all)]
-async fn load_nullifier_tree(db: &mut Db) -> Result<NullifierTree, StateInitializationError> {
-    let nullifiers = db.select_nullifiers().await?;
+async fn load_nullifier_tree(db: &Db) -> Result<NullifierTree, StateInitializationError> {
+    let nullifiers = db.load_nullifiers().await?;
     let len = nullifiers.len();
 
     let now = Instant::now();
-    let nullifier_tree = NullifierTree::with_entries(nullifiers)
+    let nullifier_tree = build_tree(move || NullifierTree::with_entries(nullifiers))
+        .await
         .map_err(StateInitializationError::FailedToCreateNullifierTree)?;
     let elapsed = now.elapsed().as_secs();
 
@@ -509,24 +1099,83 @@ async fn load_nullifier_tree(db: &mut Db) -> Result<NullifierTree, StateInitiali
 }
 
 #[instrument(target = "miden-store", skip_all)]
-async fn load_mmr(db: &mut Db) -> Result<Mmr, StateInitializationError> {
-    let block_hashes: Vec<RpoDigest> =
-        db.select_block_headers().await?.iter().map(BlockHeader::hash).collect();
+async fn load_mmr(db: &Db) -> Result<Mmr, StateInitializationError> {
+    let block_headers = db.load_block_headers().await?;
+
+    let mmr = build_tree(move || {
+        let block_hashes: Vec<RpoDigest> = block_headers.iter().map(BlockHeader::hash).collect();
+        Mmr::from(block_hashes)
+    })
+    .await;
 
-    Ok(block_hashes.into())
+    Ok(mmr)
 }
 
 #[instrument(target = "miden-store", skip_all)]
-async fn load_accounts(
-    db: &mut Db,
-) -> Result<SimpleSmt<ACCOUNT_TREE_DEPTH>, StateInitializationError> {
-    let account_data: Vec<_> = db
-        .select_account_hashes()
+async fn load_chain_tip(db: &Db) -> Result<ChainTip, StateInitializationError> {
+    let block_header = db
+        .select_block_header_by_block_num(None)
         .await?
-        .into_iter()
-        .map(|(id, account_hash)| (id, account_hash.into()))
-        .collect();
+        .ok_or(StateInitializationError::MissingBlockHeaders)?;
 
-    SimpleSmt::with_leaves(account_data)
-        .map_err(StateInitializationError::FailedToCreateAccountsTree)
+    Ok((&block_header).into())
+}
+
+/// Checks the roots of the trees loaded from the database against the roots of the latest block,
+/// read from their columns rather than from the decoded header.
+#[instrument(target = "miden-store", skip_all)]
+async fn check_tree_roots(
+    db: &Db,
+    chain_tip: &ChainTip,
+    nullifier_tree: &NullifierTree,
+    account_tree: &SimpleSmt<ACCOUNT_TREE_DEPTH>,
+) -> Result<(), StateInitializationError> {
+    let fields = db
+        .select_block_header_fields(chain_tip.block_num, 1)
+        .await?
+        .pop()
+        .ok_or(StateInitializationError::MissingBlockHeaders)?;
+
+    let roots = [
+        ("nullifier", fields.nullifier_root, nullifier_tree.root()),
+        ("account", fields.account_root, account_tree.root()),
+    ];
+    for (tree, expected, actual) in roots {
+        if expected != actual {
+            return Err(StateInitializationError::TreeRootMismatch {
+                tree,
+                block_num: fields.block_num,
+                expected,
+                actual,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+#[instrument(target = "miden-store", skip_all)]
+async fn load_accounts(db: &Db) -> Result<SimpleSmt<ACCOUNT_TREE_DEPTH>, StateInitializationError> {
+    let account_hashes = db.load_account_hashes().await?;
+    let len = account_hashes.len();
+
+    let now = Instant::now();
+    let account_tree = build_tree(move || {
+        SimpleSmt::with_leaves(
+            account_hashes
+                .into_iter()
+                .map(|(id, account_hash)| (id.into(), account_hash.into())),
+        )
+    })
+    .await
+    .map_err(StateInitializationError::FailedToCreateAccountsTree)?;
+    let elapsed = now.elapsed().as_secs();
+
+    info!(
+        num_of_leaves = len,
+        tree_construction = elapsed,
+        COMPONENT,
+        "Loaded account tree"
+    );
+    Ok(account_tree)
 }
@@ -0,0 +1,218 @@
+//! Subscriptions to the updates of accounts, so the clients watching a set of accounts are pushed
+//! their new state instead of listing them in every `SyncState` request.
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    sync::{Arc, Mutex},
+};
+
+use miden_node_proto::{
+    domain::accounts::AccountSummary, generated::responses::SubscribeAccountsResponse,
+};
+use tokio::sync::{
+    mpsc::{self, error::TrySendError, Sender},
+    oneshot,
+};
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::Status;
+use tracing::{debug, warn};
+
+use crate::{types::AccountId, ACCOUNT_SUBSCRIPTION_BUFFER, COMPONENT};
+
+/// Stream of the updates of the subscribed accounts.
+pub type AccountSubscription = ReceiverStream<Result<SubscribeAccountsResponse, Status>>;
+
+type SubscriberId = u64;
+
+// ACCOUNT SUBSCRIPTIONS
+// ================================================================================================
+
+/// Table of the account subscriptions, notified of the accounts updated by each applied block.
+#[derive(Debug, Default, Clone)]
+pub struct AccountSubscriptions {
+    table: Arc<Mutex<SubscriptionTable>>,
+}
+
+#[derive(Debug, Default)]
+struct SubscriptionTable {
+    next_id: SubscriberId,
+    /// Subscribers of each watched account
+    accounts: BTreeMap<AccountId, BTreeSet<SubscriberId>>,
+    subscribers: BTreeMap<SubscriberId, Subscriber>,
+}
+
+#[derive(Debug)]
+struct Subscriber {
+    account_ids: BTreeSet<AccountId>,
+    sender: Sender<Result<SubscribeAccountsResponse, Status>>,
+    /// Dropped when the subscriber is removed for lagging behind, ending its stream
+    _removed: oneshot::Sender<()>,
+}
+
+impl AccountSubscriptions {
+    /// Returns a stream of the updates of `account_ids`, starting with the next applied block.
+    ///
+    /// The subscription is removed when the subscriber disconnects. A subscriber with more than
+    /// [ACCOUNT_SUBSCRIPTION_BUFFER] pending updates is removed, its stream ends with a
+    /// `RESOURCE_EXHAUSTED` status and it should catch up with `SyncState` before subscribing
+    /// again.
+    pub fn subscribe(&self, account_ids: BTreeSet<AccountId>) -> AccountSubscription {
+        let (sender, receiver) = mpsc::channel(ACCOUNT_SUBSCRIPTION_BUFFER);
+        let (removed_sender, removed) = oneshot::channel();
+
+        let id = self.lock().insert(Subscriber {
+            account_ids,
+            sender: sender.clone(),
+            _removed: removed_sender,
+        });
+
+        let table = self.table.clone();
+        tokio::spawn(async move {
+            tokio::select! {
+                _ = sender.closed() => {
+                    table.lock().expect("Poisoned lock").remove(id);
+                },
+                _ = removed => {
+                    let status = Status::resource_exhausted("Account subscription lagged behind");
+                    let _ = sender.send(Err(status)).await;
+                },
+            }
+        });
+
+        ReceiverStream::new(receiver)
+    }
+
+    /// Pushes `updates` to the subscribers of the updated accounts.
+    pub fn notify(&self, updates: &[AccountSummary]) {
+        let mut table = self.lock();
+        if table.subscribers.is_empty() {
+            return;
+        }
+
+        let mut lagging = BTreeSet::new();
+        for update in updates {
+            let Some(subscriber_ids) = table.accounts.get(&update.account_id.into()) else {
+                continue;
+            };
+
+            for id in subscriber_ids {
+                let response = SubscribeAccountsResponse { summary: Some(update.into()) };
+                match table.subscribers[id].sender.try_send(Ok(response)) {
+                    Ok(()) => {},
+                    Err(TrySendError::Full(_)) => {
+                        lagging.insert(*id);
+                    },
+                    // Removed by the task waiting for the subscriber to disconnect
+                    Err(TrySendError::Closed(_)) => {},
+                }
+            }
+        }
+
+        for id in lagging {
+            warn!(target: COMPONENT, subscriber = id, "Removing lagging account subscription");
+            table.remove(id);
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, SubscriptionTable> {
+        self.table.lock().expect("Poisoned lock")
+    }
+}
+
+impl SubscriptionTable {
+    fn insert(&mut self, subscriber: Subscriber) -> SubscriberId {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        for account_id in subscriber.account_ids.iter() {
+            self.accounts.entry(*account_id).or_default().insert(id);
+        }
+        debug!(target: COMPONENT, subscriber = id, accounts = subscriber.account_ids.len(), "New account subscription");
+        self.subscribers.insert(id, subscriber);
+
+        id
+    }
+
+    fn remove(&mut self, id: SubscriberId) {
+        let Some(subscriber) = self.subscribers.remove(&id) else {
+            return;
+        };
+
+        for account_id in subscriber.account_ids.iter() {
+            if let Some(subscriber_ids) = self.accounts.get_mut(account_id) {
+                subscriber_ids.remove(&id);
+                if subscriber_ids.is_empty() {
+                    self.accounts.remove(account_id);
+                }
+            }
+        }
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use miden_objects::{
+        accounts::{AccountId as AccountIdObject, ACCOUNT_ID_OFF_CHAIN_SENDER},
+        Digest,
+    };
+    use tokio_stream::StreamExt;
+    use tonic::Code;
+
+    use super::*;
+
+    fn summary(block_num: u32) -> AccountSummary {
+        AccountSummary {
+            account_id: AccountIdObject::try_from(ACCOUNT_ID_OFF_CHAIN_SENDER).unwrap(),
+            account_hash: Digest::default(),
+            block_num,
+            nonce: None,
+        }
+    }
+
+    /// Waits for the subscription tasks to update the table.
+    async fn wait_for_subscribers(subscriptions: &AccountSubscriptions, count: usize) {
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while subscriptions.lock().subscribers.len() != count {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_account_subscriptions() {
+        let subscriptions = AccountSubscriptions::default();
+        let account_id = summary(0).account_id.into();
+
+        let mut watching = subscriptions.subscribe([account_id].into());
+        let other = subscriptions.subscribe([AccountId::new(1)].into());
+
+        subscriptions.notify(&[summary(1)]);
+        let response = watching.next().await.unwrap().unwrap();
+        assert_eq!(response.summary, Some((&summary(1)).into()));
+
+        // Disconnected subscribers are removed
+        drop(other);
+        wait_for_subscribers(&subscriptions, 1).await;
+        assert_eq!(subscriptions.lock().accounts.len(), 1);
+
+        // Lagging subscribers are removed, and their stream ends with an error
+        for block_num in 0..=ACCOUNT_SUBSCRIPTION_BUFFER as u32 {
+            subscriptions.notify(&[summary(block_num)]);
+        }
+        assert!(subscriptions.lock().subscribers.is_empty());
+        assert!(subscriptions.lock().accounts.is_empty());
+
+        let mut responses = Vec::new();
+        while let Some(response) = watching.next().await {
+            responses.push(response);
+        }
+        assert_eq!(responses.len(), ACCOUNT_SUBSCRIPTION_BUFFER + 1);
+        assert_eq!(responses.pop().unwrap().unwrap_err().code(), Code::ResourceExhausted);
+    }
+}
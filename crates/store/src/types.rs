@@ -1,2 +1,181 @@
-pub type BlockNumber = u32;
-pub type AccountId = u64;
+use std::{
+    fmt::{Display, Formatter},
+    num::TryFromIntError,
+};
+
+use miden_node_proto::generated::account::AccountId as AccountIdPb;
+use miden_objects::{accounts::AccountId as AccountIdObject, AccountError};
+use rusqlite::{
+    types::{FromSql, FromSqlError, FromSqlResult, ToSqlOutput, Value, ValueRef},
+    ToSql,
+};
+
+// BLOCK NUMBER
+// ================================================================================================
+
+/// Number of a block in the chain.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BlockNumber(u32);
+
+impl BlockNumber {
+    pub const GENESIS: Self = Self(0);
+    pub const MAX: Self = Self(u32::MAX);
+
+    pub const fn new(block_num: u32) -> Self {
+        Self(block_num)
+    }
+
+    pub const fn as_u32(self) -> u32 {
+        self.0
+    }
+
+    /// Returns the number of the block following this one, [None] if this is the last block
+    /// number.
+    pub const fn child(self) -> Option<Self> {
+        match self.0.checked_add(1) {
+            Some(block_num) => Some(Self(block_num)),
+            None => None,
+        }
+    }
+}
+
+impl Display for BlockNumber {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+impl From<u32> for BlockNumber {
+    fn from(block_num: u32) -> Self {
+        Self(block_num)
+    }
+}
+
+impl From<BlockNumber> for u32 {
+    fn from(block_num: BlockNumber) -> Self {
+        block_num.0
+    }
+}
+
+impl TryFrom<u64> for BlockNumber {
+    type Error = TryFromIntError;
+
+    fn try_from(block_num: u64) -> Result<Self, Self::Error> {
+        u32::try_from(block_num).map(Self)
+    }
+}
+
+impl ToSql for BlockNumber {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::from(self.0))
+    }
+}
+
+impl From<BlockNumber> for Value {
+    fn from(block_num: BlockNumber) -> Self {
+        Value::Integer(block_num.0.into())
+    }
+}
+
+impl FromSql for BlockNumber {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        let block_num = value.as_i64()?;
+        u32::try_from(block_num)
+            .map(Self)
+            .map_err(|_| FromSqlError::OutOfRange(block_num))
+    }
+}
+
+// ACCOUNT ID
+// ================================================================================================
+
+/// Id of an account, as stored in the database.
+///
+/// Unlike [miden_objects::accounts::AccountId], the id isn't validated, it is only checked when
+/// converted into one.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AccountId(u64);
+
+impl AccountId {
+    pub const fn new(account_id: u64) -> Self {
+        Self(account_id)
+    }
+
+    pub const fn as_u64(self) -> u64 {
+        self.0
+    }
+}
+
+impl Display for AccountId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "0x{:x}", self.0)
+    }
+}
+
+impl From<u64> for AccountId {
+    fn from(account_id: u64) -> Self {
+        Self(account_id)
+    }
+}
+
+impl From<AccountId> for u64 {
+    fn from(account_id: AccountId) -> Self {
+        account_id.0
+    }
+}
+
+impl From<AccountIdObject> for AccountId {
+    fn from(account_id: AccountIdObject) -> Self {
+        Self(account_id.into())
+    }
+}
+
+impl TryFrom<AccountId> for AccountIdObject {
+    type Error = AccountError;
+
+    fn try_from(account_id: AccountId) -> Result<Self, Self::Error> {
+        AccountIdObject::try_from(account_id.0)
+    }
+}
+
+impl From<AccountIdPb> for AccountId {
+    fn from(account_id: AccountIdPb) -> Self {
+        Self(account_id.into())
+    }
+}
+
+impl From<AccountId> for AccountIdPb {
+    fn from(account_id: AccountId) -> Self {
+        account_id.0.into()
+    }
+}
+
+/// SQLite stores integers as `i64`, the ids are stored with the same bits as the `u64` ids.
+impl ToSql for AccountId {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::from(self.0 as i64))
+    }
+}
+
+impl From<AccountId> for Value {
+    fn from(account_id: AccountId) -> Self {
+        Value::Integer(account_id.0 as i64)
+    }
+}
+
+impl FromSql for AccountId {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        Ok(Self(value.as_i64()? as u64))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_last_block_number_has_no_child() {
+        assert_eq!(BlockNumber::GENESIS.child(), Some(BlockNumber::new(1)));
+        assert_eq!(BlockNumber::new(u32::MAX).child(), None);
+    }
+}
@@ -0,0 +1,61 @@
+use miden_objects::{
+    accounts::{
+        AccountId, AccountStorageType, AccountType, ACCOUNT_STORAGE_MASK_SHIFT,
+        ACCOUNT_TYPE_MASK_SHIFT,
+    },
+    Digest, Hasher,
+};
+
+use crate::TransactionBuilder;
+
+// TEST ACCOUNT
+// ================================================================================================
+
+/// A private account whose states are chained hashes, each transaction of the account moves it to
+/// the next state.
+///
+/// The account only exists on chain once a transaction creating it is included in a block.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TestAccount {
+    id: AccountId,
+    hash: Digest,
+}
+
+impl TestAccount {
+    /// Returns a new account, each `seed` giving a different account id.
+    ///
+    /// The id is not derived from an account seed, whose proof of work would dominate the duration
+    /// of the tests. The node doesn't check it.
+    pub fn new(seed: u32) -> Self {
+        let id = (AccountStorageType::OffChain as u64) << ACCOUNT_STORAGE_MASK_SHIFT
+            | (AccountType::RegularAccountUpdatableCode as u64) << ACCOUNT_TYPE_MASK_SHIFT
+            | u64::from(seed) << 8
+            | 0xff;
+
+        Self {
+            id: AccountId::try_from(id).expect("account id is valid"),
+            hash: Digest::default(),
+        }
+    }
+
+    pub fn id(&self) -> AccountId {
+        self.id
+    }
+
+    /// Returns the hash of the current state, [Digest::default] for an account not created yet.
+    pub fn hash(&self) -> Digest {
+        self.hash
+    }
+
+    /// Returns a builder of a transaction moving the account from its current state to the next
+    /// one, and makes the next state current.
+    ///
+    /// The first transaction of an account creates it.
+    pub fn transaction(&mut self) -> TransactionBuilder {
+        let initial_hash = self.hash;
+        let id_hash = Hasher::hash(&u64::from(self.id).to_be_bytes());
+        self.hash = Hasher::merge(&[initial_hash, id_hash]);
+
+        TransactionBuilder::new(self.id, initial_hash, self.hash)
+    }
+}
@@ -0,0 +1,28 @@
+//! In-process harness for the end-to-end tests of the node.
+//!
+//! [TestNode] runs the store, the block producer and the RPC in the current process, connected by
+//! the in-process transport, on a fresh database. Scenarios create accounts, submit dummy proven
+//! transactions, wait for them to be included in blocks and then assert on the state served by
+//! the RPC, e.g. to cover behaviors spanning several components and blocks.
+use std::time::Duration;
+
+mod account;
+mod node;
+mod transaction;
+
+pub use account::TestAccount;
+pub use node::TestNode;
+pub use transaction::{nullifier, TransactionBuilder};
+
+// CONSTANTS
+// =================================================================================================
+
+/// Time between the production of two blocks by the test node.
+pub const BLOCK_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Maximum time waited for the node to reach an expected state, e.g. for a transaction to be
+/// included in a block.
+pub const WAIT_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Time between two checks of the node's state while waiting for it.
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
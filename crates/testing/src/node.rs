@@ -0,0 +1,318 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process,
+    sync::atomic::{AtomicU32, Ordering},
+};
+
+use miden_node_block_producer::{
+    config::{BatchSealingPolicy, BlockProducerConfig, BlockProductionConfig},
+    server as block_producer_server,
+};
+use miden_node_proto::{
+    domain::nullifiers::nullifier_prefix,
+    generated::{
+        block_producer::api_client as block_producer_client,
+        requests::{
+            CheckNullifiersByPrefixRequest, GetAccountDetailsRequest, GetChainTipRequest,
+            GetTransactionReceiptRequest, SubmitProvenTransactionRequest,
+        },
+        rpc::api_client as rpc_client,
+        store::api_client as store_client,
+    },
+};
+use miden_node_rpc::{config::RpcConfig, server as rpc_server};
+use miden_node_store::{
+    config::StoreConfig, db::Db, genesis::GenesisState, server as store_server,
+};
+use miden_node_utils::{
+    chain_params::NULLIFIER_PREFIX_LEN, config::Endpoint, transport::ComponentChannel,
+};
+use miden_objects::{
+    notes::Nullifier,
+    transaction::{ProvenTransaction, TransactionId},
+    utils::Serializable,
+    Digest,
+};
+use tonic::{Code, Status};
+
+use crate::{TestAccount, BLOCK_INTERVAL, POLL_INTERVAL, WAIT_TIMEOUT};
+
+/// Number of the test nodes started by the process, used to name their data directories.
+static NODE_COUNT: AtomicU32 = AtomicU32::new(0);
+
+// TEST NODE
+// ================================================================================================
+
+/// The store, the block producer and the RPC of a node, running in the current process on a fresh
+/// database.
+///
+/// The components run on the tasks of the current tokio runtime and stop with it, the tests must
+/// use a multi-threaded runtime so the blocks are proven while the test waits for them. The
+/// helpers panic on unexpected failures, which fails the test.
+pub struct TestNode {
+    rpc: rpc_client::ApiClient<ComponentChannel>,
+    block_producer: block_producer_client::ApiClient<ComponentChannel>,
+    store: store_client::ApiClient<ComponentChannel>,
+    next_account_seed: u32,
+    store_config: StoreConfig,
+    data_dir: PathBuf,
+}
+
+impl TestNode {
+    /// Starts a node with an empty genesis block, producing a block every [BLOCK_INTERVAL].
+    pub async fn start() -> Self {
+        let data_dir = std::env::temp_dir().join(format!(
+            "miden-node-testing-{}-{}",
+            process::id(),
+            NODE_COUNT.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::create_dir_all(&data_dir).expect("failed to create the data directory");
+
+        let genesis_filepath = data_dir.join("genesis.dat");
+        let genesis = GenesisState::new(Vec::new(), Vec::new(), 1, 0);
+        fs::write(&genesis_filepath, genesis.to_bytes()).expect("failed to write the genesis file");
+
+        // The components are reached in-process, their endpoints are not listened on
+        let endpoint = Endpoint { host: "localhost".to_string(), port: 0 };
+
+        let store_config =
+            StoreConfig::new(endpoint.clone(), data_dir.join("store.sqlite3"), genesis_filepath);
+        let db = Db::setup(store_config.clone()).await.expect("failed to setup the database");
+        let store = store_server::init(&store_config, db)
+            .await
+            .expect("failed to initialize the store");
+        let store = ComponentChannel::in_process(store);
+
+        let block_producer_config = BlockProducerConfig {
+            endpoint: endpoint.clone(),
+            store_url: String::new(),
+            store_client: Default::default(),
+            genesis_hash: None,
+            // The transactions have dummy proofs
+            verify_tx_proofs: false,
+            batch_sealing: BatchSealingPolicy {
+                max_delay_ms: POLL_INTERVAL.as_millis() as u64,
+                ..Default::default()
+            },
+            batch_proving: Default::default(),
+            block_production: BlockProductionConfig {
+                interval_ms: BLOCK_INTERVAL.as_millis() as u64,
+                max_cycles: None,
+                ..Default::default()
+            },
+            clock: Default::default(),
+            signing_key_filepath: None,
+            fees: None,
+            submission_policy: Default::default(),
+            simulation: None,
+            access_log: None,
+        };
+        let block_producer = block_producer_server::init(&block_producer_config, store.clone())
+            .expect("failed to initialize the block producer");
+        let block_producer = ComponentChannel::in_process(block_producer);
+
+        let rpc_config = RpcConfig {
+            endpoint,
+            store_url: String::new(),
+            block_producer_url: String::new(),
+            client: Default::default(),
+            genesis_hash: None,
+            timeouts: Default::default(),
+            cors: Default::default(),
+            admin: None,
+            audit: None,
+            load_shedding: None,
+            access_control: None,
+            testnet_faucet: None,
+            access_log: None,
+            usage: None,
+            store_retry: None,
+        };
+        let rpc = rpc_server::init(&rpc_config, store.clone(), block_producer.clone())
+            .expect("failed to initialize the rpc");
+        let rpc = ComponentChannel::in_process(rpc);
+
+        Self {
+            rpc: rpc_client::ApiClient::new(rpc),
+            block_producer: block_producer_client::ApiClient::new(block_producer),
+            store: store_client::ApiClient::new(store),
+            next_account_seed: 0,
+            store_config,
+            data_dir,
+        }
+    }
+
+    /// Returns a client of the node's RPC.
+    pub fn rpc(&self) -> rpc_client::ApiClient<ComponentChannel> {
+        self.rpc.clone()
+    }
+
+    /// Returns a client of the node's block producer.
+    pub fn block_producer(&self) -> block_producer_client::ApiClient<ComponentChannel> {
+        self.block_producer.clone()
+    }
+
+    /// Returns a client of the node's store.
+    pub fn store(&self) -> store_client::ApiClient<ComponentChannel> {
+        self.store.clone()
+    }
+
+    /// Returns the configuration of the node's store.
+    pub fn store_config(&self) -> &StoreConfig {
+        &self.store_config
+    }
+
+    /// Returns the directory of the node's files, removed when the node is dropped.
+    pub fn data_dir(&self) -> &Path {
+        &self.data_dir
+    }
+
+    // ACCOUNTS
+    // --------------------------------------------------------------------------------------------
+
+    /// Creates a new account, returns once the transaction creating it is included in a block.
+    pub async fn mint_account(&mut self) -> TestAccount {
+        let mut account = TestAccount::new(self.next_account_seed);
+        self.next_account_seed += 1;
+
+        let tx = account.transaction().build();
+        self.submit(&tx).await.expect("account creation was rejected");
+        self.wait_for_transaction(tx.id()).await;
+
+        account
+    }
+
+    /// Returns the hash of the committed state of the account, [None] if it doesn't exist.
+    pub async fn account_hash(&self, account: &TestAccount) -> Option<Digest> {
+        let request = GetAccountDetailsRequest {
+            account_id: Some(account.id().into()),
+            min_confirmations: 0,
+        };
+        match self.rpc().get_account_details(request).await {
+            Ok(response) => {
+                let summary = response
+                    .into_inner()
+                    .account
+                    .and_then(|account| account.summary)
+                    .expect("account summary is missing");
+                let hash = summary.account_hash.expect("account hash is missing");
+
+                Some(hash.try_into().expect("account hash is valid"))
+            },
+            Err(status) if status.code() == Code::NotFound => None,
+            Err(status) => panic!("failed to get the account details: {status}"),
+        }
+    }
+
+    // TRANSACTIONS
+    // --------------------------------------------------------------------------------------------
+
+    /// Submits `tx` to the block producer, returns the rejection status if it isn't accepted.
+    ///
+    /// The transaction is not sent to the RPC, which would reject its dummy proof.
+    pub async fn submit(&self, tx: &ProvenTransaction) -> Result<(), Status> {
+        let request = SubmitProvenTransactionRequest {
+            transaction: tx.to_bytes(),
+            fee_note: Vec::new(),
+        };
+        self.block_producer().submit_proven_transaction(request).await?;
+
+        Ok(())
+    }
+
+    /// Waits for the transaction `tx_id` to be included in a block, returns the block number.
+    pub async fn wait_for_transaction(&self, tx_id: TransactionId) -> u32 {
+        self.wait_for(format!("transaction {tx_id}"), || async move {
+            let request = GetTransactionReceiptRequest {
+                transaction_id: Some(tx_id.into()),
+                min_confirmations: 0,
+            };
+            match self.rpc().get_transaction_receipt(request).await {
+                Ok(response) => Some(
+                    response
+                        .into_inner()
+                        .receipt
+                        .expect("transaction receipt is missing")
+                        .block_num,
+                ),
+                Err(status) if status.code() == Code::NotFound => None,
+                Err(status) => panic!("failed to get the transaction receipt: {status}"),
+            }
+        })
+        .await
+    }
+
+    /// Returns the number of the block which consumed `nullifier`, [None] if it wasn't consumed.
+    pub async fn nullifier_block(&self, nullifier: &Nullifier) -> Option<u32> {
+        let request = CheckNullifiersByPrefixRequest {
+            prefix_len: NULLIFIER_PREFIX_LEN,
+            nullifiers: vec![nullifier_prefix(nullifier)],
+            min_confirmations: 0,
+        };
+        let response = self
+            .rpc()
+            .check_nullifiers_by_prefix(request)
+            .await
+            .expect("failed to check the nullifiers");
+
+        let nullifier = nullifier.inner();
+        response.into_inner().nullifiers.into_iter().find_map(|update| {
+            let update_nullifier: Digest = update
+                .nullifier
+                .expect("nullifier is missing")
+                .try_into()
+                .expect("nullifier is valid");
+            (update_nullifier == nullifier).then_some(update.block_num)
+        })
+    }
+
+    // BLOCKS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of the latest block.
+    pub async fn chain_tip(&self) -> u32 {
+        self.rpc()
+            .get_chain_tip(GetChainTipRequest {})
+            .await
+            .expect("failed to get the chain tip")
+            .into_inner()
+            .block_num
+    }
+
+    /// Waits for `num_blocks` blocks to be produced, returns the number of the latest block.
+    pub async fn advance_blocks(&self, num_blocks: u32) -> u32 {
+        let target = self.chain_tip().await + num_blocks;
+        self.wait_for(format!("block {target}"), || async move {
+            let chain_tip = self.chain_tip().await;
+            (chain_tip >= target).then_some(chain_tip)
+        })
+        .await
+    }
+
+    /// Polls `check` until it returns a value, panics after [WAIT_TIMEOUT].
+    async fn wait_for<T, F, Fut>(&self, what: String, check: F) -> T
+    where
+        F: Fn() -> Fut,
+        Fut: std::future::Future<Output = Option<T>>,
+    {
+        let wait = async {
+            loop {
+                if let Some(value) = check().await {
+                    return value;
+                }
+                tokio::time::sleep(POLL_INTERVAL).await;
+            }
+        };
+
+        tokio::time::timeout(WAIT_TIMEOUT, wait)
+            .await
+            .unwrap_or_else(|_| panic!("timed out waiting for {what}"))
+    }
+}
+
+impl Drop for TestNode {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.data_dir);
+    }
+}
@@ -0,0 +1,114 @@
+use std::sync::OnceLock;
+
+use miden_objects::{
+    accounts::AccountId,
+    assembly::Assembler,
+    notes::{NoteEnvelope, NoteMetadata, NoteType, Nullifier},
+    transaction::{OutputNote, ProvenTransaction, ProvenTransactionBuilder},
+    vm::{ExecutionProof, StackInputs},
+    Digest, Felt, Hasher, ONE, ZERO,
+};
+use miden_processor::DefaultHost;
+use miden_prover::ProvingOptions;
+
+/// Returns a dummy nullifier, each `index` giving a different nullifier.
+///
+/// The store doesn't track the notes consumed by the transactions, any nullifier can be consumed
+/// once.
+pub fn nullifier(index: u64) -> Nullifier {
+    Nullifier::from(Digest::from([ONE, ONE, ONE, Felt::new(index)]))
+}
+
+// TRANSACTION BUILDER
+// ================================================================================================
+
+/// Builder of a transaction with a dummy proof, see [crate::TestAccount::transaction].
+///
+/// The proof is that of a trivial program, it is only accepted by a block producer which doesn't
+/// verify the transaction proofs. The transactions must be submitted with [crate::TestNode::submit]
+/// rather than to the RPC.
+pub struct TransactionBuilder {
+    account_id: AccountId,
+    initial_account_hash: Digest,
+    final_account_hash: Digest,
+    nullifiers: Vec<Nullifier>,
+    output_notes: Vec<OutputNote>,
+}
+
+impl TransactionBuilder {
+    pub(crate) fn new(
+        account_id: AccountId,
+        initial_account_hash: Digest,
+        final_account_hash: Digest,
+    ) -> Self {
+        Self {
+            account_id,
+            initial_account_hash,
+            final_account_hash,
+            nullifiers: Vec::new(),
+            output_notes: Vec::new(),
+        }
+    }
+
+    /// Consumes the notes with the given nullifiers.
+    pub fn consume(mut self, nullifiers: impl IntoIterator<Item = Nullifier>) -> Self {
+        self.nullifiers.extend(nullifiers);
+        self
+    }
+
+    /// Creates `num_notes` private notes, each `seed` giving different notes.
+    pub fn create_private_notes(mut self, seed: u64, num_notes: u64) -> Self {
+        // The store doesn't keep the `aux` of the notes, it must be zero for the note root of the
+        // block to match
+        let metadata = NoteMetadata::new(self.account_id, NoteType::OffChain, 0.into(), ZERO)
+            .expect("note metadata is valid");
+        let notes = (0..num_notes).map(|index| {
+            let note_hash = Hasher::merge(&[
+                Hasher::hash(&seed.to_be_bytes()),
+                Hasher::hash(&index.to_be_bytes()),
+            ]);
+            OutputNote::Private(
+                NoteEnvelope::new(note_hash.into(), metadata).expect("note envelope is valid"),
+            )
+        });
+        self.output_notes.extend(notes);
+        self
+    }
+
+    pub fn build(self) -> ProvenTransaction {
+        ProvenTransactionBuilder::new(
+            self.account_id,
+            self.initial_account_hash,
+            self.final_account_hash,
+            Digest::default(),
+            dummy_proof(),
+        )
+        .add_input_notes(self.nullifiers)
+        .add_output_notes(self.output_notes)
+        .build()
+        .expect("transaction is valid")
+    }
+}
+
+/// Returns the proof of the execution of an empty program, proven once per process.
+///
+/// Unlike `StarkProof::new_dummy`, the proof can be serialized to be sent to the block producer.
+fn dummy_proof() -> ExecutionProof {
+    static PROOF: OnceLock<ExecutionProof> = OnceLock::new();
+
+    PROOF
+        .get_or_init(|| {
+            let program =
+                Assembler::default().compile("begin push.1 drop end").expect("program is valid");
+            let (_, proof) = miden_prover::prove(
+                &program,
+                StackInputs::default(),
+                DefaultHost::default(),
+                ProvingOptions::default(),
+            )
+            .expect("program can be proven");
+
+            proof
+        })
+        .clone()
+}
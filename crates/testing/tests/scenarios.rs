@@ -0,0 +1,327 @@
+use miden_node_proto::{
+    domain::{
+        transactions::TransactionRejection,
+        version::{PROTOCOL_VERSION, PROTO_VERSION},
+    },
+    generated::{
+        requests::{
+            ApplyBlockRequest, GetAccountDetailsRequest, GetNodeStatusRequest,
+            GetNoteAuthenticationInfoRequest, GetNotesByIdRequest, GetTransactionReceiptRequest,
+            GetVersionRequest, SubscribeBlocksRequest,
+        },
+        responses::ApplyBlockResponse,
+        store::api_client as store_client,
+    },
+};
+use miden_node_store::{
+    backfill::backfill,
+    config::StoreConfig,
+    db::{Db, DbInspector},
+    errors::{BackfillError, DatabaseSetupError},
+    server as store_server,
+};
+use miden_node_testing::{nullifier, TestNode};
+use miden_node_utils::transport::ComponentChannel;
+use prost::Message;
+use tonic::{Code, Status};
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_double_spend_across_blocks_is_rejected() {
+    let mut node = TestNode::start().await;
+    let mut alice = node.mint_account().await;
+    let mut bob = node.mint_account().await;
+
+    let spent = nullifier(1);
+    let tx = alice.transaction().consume([spent]).build();
+    node.submit(&tx).await.unwrap();
+    let block_num = node.wait_for_transaction(tx.id()).await;
+    assert_eq!(node.nullifier_block(&spent).await, Some(block_num));
+
+    // The note is spent again once the block consuming it is no longer the latest one
+    node.advance_blocks(1).await;
+    let double_spend = bob.transaction().consume([spent, nullifier(2)]).build();
+    let status = node.submit(&double_spend).await.unwrap_err();
+
+    assert_eq!(
+        TransactionRejection::from_status(&status).unwrap(),
+        Some(TransactionRejection::InputNotesAlreadyConsumed(vec![spent]))
+    );
+    assert_eq!(node.nullifier_block(&nullifier(2)).await, None);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_account_updates_are_chained() {
+    let mut node = TestNode::start().await;
+    let mut account = node.mint_account().await;
+    assert_eq!(node.account_hash(&account).await, Some(account.hash()));
+
+    let mut stale = account;
+    let tx = account.transaction().create_private_notes(0, 2).build();
+    node.submit(&tx).await.unwrap();
+    node.wait_for_transaction(tx.id()).await;
+    assert_eq!(node.account_hash(&account).await, Some(account.hash()));
+
+    // A transaction built on the previous state of the account is rejected
+    let stale_tx = stale.transaction().build();
+    let status = node.submit(&stale_tx).await.unwrap_err();
+
+    assert_eq!(
+        TransactionRejection::from_status(&status).unwrap(),
+        Some(TransactionRejection::IncorrectAccountInitialHash {
+            provided: stale_tx.initial_account_hash(),
+            expected: Some(account.hash()),
+        })
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_transactions_build_on_in_flight_account_states() {
+    let mut node = TestNode::start().await;
+    let mut account = node.mint_account().await;
+
+    // The second transaction starts from the state after the first one, before it is in a block
+    let mut stale = account;
+    let first_tx = account.transaction().create_private_notes(0, 1).build();
+    node.submit(&first_tx).await.unwrap();
+    let second_tx = account.transaction().create_private_notes(1, 1).build();
+    node.submit(&second_tx).await.unwrap();
+
+    // A transaction built on the state in the store is rejected while the account is in flight
+    let stale_tx = stale.transaction().build();
+    let status = node.submit(&stale_tx).await.unwrap_err();
+    assert_eq!(
+        TransactionRejection::from_status(&status).unwrap(),
+        Some(TransactionRejection::IncorrectAccountInitialHash {
+            provided: stale_tx.initial_account_hash(),
+            expected: Some(account.hash()),
+        })
+    );
+
+    node.wait_for_transaction(first_tx.id()).await;
+    node.wait_for_transaction(second_tx.id()).await;
+    assert_eq!(node.account_hash(&account).await, Some(account.hash()));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_node_status_reports_the_chain() {
+    let mut node = TestNode::start().await;
+    let mut account = node.mint_account().await;
+    let tx = account.transaction().build();
+    node.submit(&tx).await.unwrap();
+    let block_num = node.wait_for_transaction(tx.id()).await;
+
+    let status = node.rpc().get_node_status(GetNodeStatusRequest {}).await.unwrap().into_inner();
+
+    let store = status.store.expect("store status is missing");
+    assert!(store.chain_tip >= block_num);
+    assert!(store.genesis_hash.is_some());
+    assert!(store.database_size_bytes > 0);
+    assert_eq!(store.follower, None);
+
+    let block_producer = status.block_producer.expect("block producer status is missing");
+    assert_eq!(block_producer.version, status.version);
+    assert!(!block_producer.block_production_paused);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_node_version_reports_the_components() {
+    let node = TestNode::start().await;
+
+    let version = node.rpc().get_version(GetVersionRequest {}).await.unwrap().into_inner();
+
+    assert_eq!(version.component, "miden-rpc");
+    assert_eq!(version.protocol_version, Some(PROTOCOL_VERSION.into()));
+    assert_eq!(version.proto_version, PROTO_VERSION);
+    assert!(version.genesis_hash.is_some());
+
+    let components: Vec<_> = version
+        .components
+        .iter()
+        .map(|component| component.component.as_str())
+        .collect();
+    assert_eq!(components, ["miden-store", "miden-block-producer"]);
+    assert_eq!(version.components[0].genesis_hash, version.genesis_hash);
+    assert!(version
+        .components
+        .iter()
+        .all(|component| component.protocol_version == version.protocol_version));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_queries_wait_for_confirmations() {
+    let mut node = TestNode::start().await;
+    let mut account = node.mint_account().await;
+    let tx = account.transaction().build();
+    node.submit(&tx).await.unwrap();
+    let block_num = node.wait_for_transaction(tx.id()).await;
+
+    let receipt_request = |min_confirmations| GetTransactionReceiptRequest {
+        transaction_id: Some(tx.id().into()),
+        min_confirmations,
+    };
+    let account_request = |min_confirmations| GetAccountDetailsRequest {
+        account_id: Some(account.id().into()),
+        min_confirmations,
+    };
+
+    // The transaction and the account update are reported once their block is deep enough
+    let status = node.rpc().get_transaction_receipt(receipt_request(1000)).await.unwrap_err();
+    assert_eq!(status.code(), Code::NotFound);
+    let status = node.rpc().get_account_details(account_request(1000)).await.unwrap_err();
+    assert_eq!(status.code(), Code::FailedPrecondition);
+
+    node.advance_blocks(2).await;
+    let receipt = node.rpc().get_transaction_receipt(receipt_request(3)).await.unwrap();
+    assert_eq!(receipt.into_inner().receipt.unwrap().block_num, block_num);
+    assert!(node.rpc().get_account_details(account_request(3)).await.is_ok());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_notes_are_authenticated_by_block() {
+    let mut node = TestNode::start().await;
+    let mut account = node.mint_account().await;
+    let tx = account.transaction().create_private_notes(0, 2).build();
+    node.submit(&tx).await.unwrap();
+    let block_num = node.wait_for_transaction(tx.id()).await;
+
+    let note_ids = tx.output_notes().iter().map(|note| note.id().into()).collect();
+    let notes = node
+        .rpc()
+        .get_notes_by_id(GetNotesByIdRequest { note_ids, min_confirmations: 0 })
+        .await
+        .unwrap()
+        .into_inner()
+        .notes;
+    assert_eq!(notes.len(), 2);
+
+    // The paths in the rebuilt note tree of the block are the paths stored with the notes
+    let request = GetNoteAuthenticationInfoRequest {
+        block_num,
+        note_indexes: notes.iter().rev().map(|note| note.note_index).collect(),
+    };
+    let info = node.rpc().get_note_authentication_info(request).await.unwrap().into_inner();
+
+    assert_eq!(info.block_header.unwrap().block_num, block_num);
+    let paths: Vec<_> =
+        info.notes.into_iter().map(|note| (note.note_id, note.merkle_path)).collect();
+    let expected: Vec<_> =
+        notes.into_iter().rev().map(|note| (note.note_id, note.merkle_path)).collect();
+    assert_eq!(paths, expected);
+
+    // A note index without note isn't authenticated
+    let request = GetNoteAuthenticationInfoRequest { block_num, note_indexes: vec![u32::MAX] };
+    let status = node.rpc().get_note_authentication_info(request).await.unwrap_err();
+    assert_eq!(status.code(), Code::NotFound);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_backfill_rebuilds_the_database_from_the_blocks() {
+    let mut node = TestNode::start().await;
+    let mut account = node.mint_account().await;
+    let spent = nullifier(1);
+    let tx = account.transaction().consume([spent]).create_private_notes(0, 2).build();
+    node.submit(&tx).await.unwrap();
+    let block_num = node.wait_for_transaction(tx.id()).await;
+
+    let output = node.data_dir().join("backfill.sqlite3");
+    let report = backfill(node.store_config().clone(), &output).await.unwrap();
+    assert!(report.chain_tip >= block_num.into());
+    assert_eq!(report.blocks, report.chain_tip.as_u32() as usize);
+
+    // The rows derived from the blocks are those of the node
+    let mut source = DbInspector::open(&node.store_config().database_filepath).unwrap();
+    let mut rebuilt = DbInspector::open(&output).unwrap();
+    let (source_stats, rebuilt_stats) = (source.stats().unwrap(), rebuilt.stats().unwrap());
+    assert_eq!(rebuilt_stats.chain_tip, Some(report.chain_tip));
+    assert_eq!(rebuilt_stats.rows.accounts, source_stats.rows.accounts);
+    assert_eq!(rebuilt_stats.rows.notes, source_stats.rows.notes);
+    assert_eq!(rebuilt_stats.rows.nullifiers, source_stats.rows.nullifiers);
+    assert_eq!(rebuilt_stats.rows.transactions, source_stats.rows.transactions);
+    assert_eq!(rebuilt.nullifier(&spent).unwrap(), Some(block_num.into()));
+
+    // The output is never overwritten
+    let err = backfill(node.store_config().clone(), &output).await.unwrap_err();
+    assert!(matches!(err, BackfillError::OutputExists(_)), "{err}");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_blocks_sent_again_are_not_applied_twice() {
+    let mut node = TestNode::start().await;
+    let mut account = node.mint_account().await;
+    let spent = nullifier(1);
+    let tx = account.transaction().consume([spent]).build();
+    node.submit(&tx).await.unwrap();
+    let block_num = node.wait_for_transaction(tx.id()).await;
+
+    let mut store = node.store();
+    let mut blocks = store
+        .subscribe_blocks(SubscribeBlocksRequest { block_num })
+        .await
+        .unwrap()
+        .into_inner();
+    let block = blocks.message().await.unwrap().unwrap();
+    let request = ApplyBlockRequest::decode(block.block.as_slice()).unwrap();
+
+    // The block is sent again, e.g. because the response to the block producer was lost
+    let response = apply_block(&mut store, request.clone()).await.unwrap();
+    assert!(response.already_applied);
+    assert_eq!(node.nullifier_block(&spent).await, Some(block_num));
+
+    // A block which doesn't follow the chain tip is rejected
+    let mut block_header = request.block.clone().unwrap();
+    block_header.block_num = node.chain_tip().await + 1_000;
+    let request = ApplyBlockRequest { block: Some(block_header), ..request };
+    let status = apply_block(&mut store, request).await.unwrap_err();
+    assert_eq!(status.code(), Code::InvalidArgument);
+}
+
+/// Sends `request` to `store`, again while the store applies a block of the block producer.
+async fn apply_block(
+    store: &mut store_client::ApiClient<ComponentChannel>,
+    request: ApplyBlockRequest,
+) -> Result<ApplyBlockResponse, Status> {
+    loop {
+        match store.apply_block(request.clone()).await {
+            Err(status) if status.code() == Code::Unavailable => {
+                tokio::time::sleep(std::time::Duration::from_millis(10)).await
+            },
+            result => return result.map(tonic::Response::into_inner),
+        }
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_read_only_store_serves_the_reads_and_rejects_the_blocks() {
+    let mut node = TestNode::start().await;
+    let account = node.mint_account().await;
+    let account_hash = node.account_hash(&account).await.unwrap();
+
+    // The database isn't locked by a read-only store, it is read while the node is running
+    let config = StoreConfig {
+        read_only: true,
+        ..node.store_config().clone()
+    };
+    let db = Db::setup(config.clone()).await.unwrap();
+    assert!(db.is_read_only());
+    let store = store_server::init(&config, db).await.unwrap();
+    let mut store = store_client::ApiClient::new(ComponentChannel::in_process(store));
+
+    let request = GetAccountDetailsRequest {
+        account_id: Some(account.id().into()),
+        min_confirmations: 0,
+    };
+    let response = store.get_account_details(request).await.unwrap().into_inner();
+    let summary = response.account.and_then(|account| account.summary).unwrap();
+    assert_eq!(summary.account_hash, Some(account_hash.into()));
+
+    let status = store.apply_block(ApplyBlockRequest::default()).await.unwrap_err();
+    assert_eq!(status.code(), Code::FailedPrecondition);
+
+    // A read-only store doesn't create its database
+    let config = StoreConfig {
+        database_filepath: node.data_dir().join("missing.sqlite3"),
+        ..config
+    };
+    let err = Db::setup(config).await.err().unwrap();
+    assert!(matches!(err, DatabaseSetupError::MissingDatabase { .. }), "{err}");
+}
@@ -0,0 +1,360 @@
+//! Access log of the gRPC servers, one line per request served, comparable to the access log of an
+//! HTTP server.
+//!
+//! Only the method, the peer, the outcome and the size of the requests and responses are logged,
+//! never their content, so the log doesn't leak the details of the notes and transactions.
+use std::{
+    fmt::{Debug, Formatter},
+    future::Future,
+    net::SocketAddr,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    task::{Context, Poll},
+    time::Instant,
+};
+
+use futures_core::Stream;
+use http_body::{Body, SizeHint};
+use tonic::{
+    body::BoxBody,
+    codegen::{http, Bytes, Service},
+    transport::server::TcpConnectInfo,
+    Code, Status,
+};
+use tower::Layer;
+use tracing::info;
+
+use crate::{config::AccessLogConfig, formatting::format_opt};
+
+/// Target of the access log lines, so that they can be filtered and routed separately from the
+/// other logs of the components.
+pub const ACCESS_LOG_TARGET: &str = "miden-access-log";
+
+/// Header of the gRPC status, in the trailers or, for the responses without a body, in the headers.
+const GRPC_STATUS_HEADER: &str = "grpc-status";
+
+/// Size of the prefix of the gRPC messages, a compression flag and the length of the message.
+const GRPC_MESSAGE_PREFIX_SIZE: usize = 5;
+
+// ACCESS LOG
+// ================================================================================================
+
+/// Samples the requests served by a component into the access log.
+struct AccessLog {
+    component: &'static str,
+    config: AccessLogConfig,
+    successes: AtomicU64,
+    errors: AtomicU64,
+}
+
+impl AccessLog {
+    /// Returns true if the request, successful or not, is sampled into the access log.
+    fn is_sampled(&self, success: bool) -> bool {
+        let (counter, one_in) = if success {
+            (&self.successes, self.config.sample_one_in)
+        } else {
+            (&self.errors, self.config.error_sample_one_in)
+        };
+
+        one_in != 0 && counter.fetch_add(1, Ordering::Relaxed) % one_in == 0
+    }
+}
+
+impl Debug for AccessLog {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AccessLog")
+            .field("component", &self.component)
+            .field("config", &self.config)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A request being served, logged once its response has been sent or abandoned.
+struct RequestRecord {
+    log: Arc<AccessLog>,
+    /// Path of the gRPC method, e.g. `/rpc.Api/SyncState`
+    method: String,
+    peer: Option<SocketAddr>,
+    started: Instant,
+    request: Arc<Mutex<MessageCounter>>,
+    response: MessageCounter,
+    /// The status of the response, [None] until it's known
+    code: Option<Code>,
+}
+
+impl Drop for RequestRecord {
+    fn drop(&mut self) {
+        if !self.log.is_sampled(self.code == Some(Code::Ok)) {
+            return;
+        }
+
+        let request = *self.request.lock().expect("Poisoned lock");
+        let code = self.code.map(|code| format!("{code:?}"));
+        info!(
+            target: ACCESS_LOG_TARGET,
+            component = self.log.component,
+            method = %self.method,
+            peer = %format_opt(self.peer.as_ref()),
+            code = %format_opt(code.as_ref()),
+            duration_ms = self.started.elapsed().as_millis() as u64,
+            request_bytes = request.bytes,
+            request_messages = request.messages,
+            response_bytes = self.response.bytes,
+            response_messages = self.response.messages,
+            "Request served"
+        );
+    }
+}
+
+/// Counts the bytes and the gRPC messages of a request or response body, as its chunks are read.
+#[derive(Debug, Default, Clone, Copy)]
+struct MessageCounter {
+    bytes: u64,
+    messages: u64,
+    /// The prefix of the next message, read up to `prefix_len`
+    prefix: [u8; GRPC_MESSAGE_PREFIX_SIZE],
+    prefix_len: usize,
+    /// Bytes of the current message not read yet
+    remaining: u64,
+}
+
+impl MessageCounter {
+    fn update(&mut self, mut chunk: &[u8]) {
+        self.bytes += chunk.len() as u64;
+
+        while !chunk.is_empty() {
+            if self.remaining > 0 {
+                let skipped = chunk.len().min(self.remaining as usize);
+                self.remaining -= skipped as u64;
+                chunk = &chunk[skipped..];
+                continue;
+            }
+
+            let read = chunk.len().min(GRPC_MESSAGE_PREFIX_SIZE - self.prefix_len);
+            self.prefix[self.prefix_len..self.prefix_len + read].copy_from_slice(&chunk[..read]);
+            self.prefix_len += read;
+            chunk = &chunk[read..];
+
+            if self.prefix_len == GRPC_MESSAGE_PREFIX_SIZE {
+                let length = [self.prefix[1], self.prefix[2], self.prefix[3], self.prefix[4]];
+                self.messages += 1;
+                self.remaining = u32::from_be_bytes(length).into();
+                self.prefix_len = 0;
+            }
+        }
+    }
+}
+
+/// Returns the gRPC status code in `headers`, if any.
+fn grpc_code(headers: &http::HeaderMap) -> Option<Code> {
+    headers.get(GRPC_STATUS_HEADER).map(|code| Code::from_bytes(code.as_bytes()))
+}
+
+// ACCESS LOG LAYER
+// ================================================================================================
+
+/// Layer of a gRPC server logging the requests it serves, a pass-through if the access log is
+/// disabled.
+///
+/// A request is logged with its method, its peer, its status code, its duration, and the number
+/// of bytes and messages of the request and of the response. The duration spans until the
+/// response has been fully sent, e.g. including the whole stream of a streaming response.
+#[derive(Debug, Clone)]
+pub struct AccessLogLayer {
+    log: Option<Arc<AccessLog>>,
+}
+
+impl AccessLogLayer {
+    /// Logs the requests served by `component`, if `config` is set.
+    pub fn new(component: &'static str, config: Option<&AccessLogConfig>) -> Self {
+        Self {
+            log: config.map(|config| {
+                Arc::new(AccessLog {
+                    component,
+                    config: config.clone(),
+                    successes: AtomicU64::new(0),
+                    errors: AtomicU64::new(0),
+                })
+            }),
+        }
+    }
+}
+
+impl<S> Layer<S> for AccessLogLayer {
+    type Service = AccessLogService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        AccessLogService { inner, log: self.log.clone() }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AccessLogService<S> {
+    inner: S,
+    log: Option<Arc<AccessLog>>,
+}
+
+impl<S> Service<http::Request<hyper::Body>> for AccessLogService<S>
+where
+    S: Service<http::Request<hyper::Body>, Response = http::Response<BoxBody>>,
+    S::Future: Send + 'static,
+    S::Error: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: http::Request<hyper::Body>) -> Self::Future {
+        let Some(log) = self.log.clone() else {
+            return Box::pin(self.inner.call(request));
+        };
+
+        let request_counter = Arc::new(Mutex::new(MessageCounter::default()));
+        let mut record = RequestRecord {
+            log,
+            method: request.uri().path().to_string(),
+            peer: request
+                .extensions()
+                .get::<TcpConnectInfo>()
+                .and_then(TcpConnectInfo::remote_addr),
+            started: Instant::now(),
+            request: request_counter.clone(),
+            response: MessageCounter::default(),
+            code: None,
+        };
+
+        let request = request.map(|body| {
+            hyper::Body::wrap_stream(CountedRequestBody { inner: body, counter: request_counter })
+        });
+        let response = self.inner.call(request);
+
+        Box::pin(async move {
+            let response = response.await?;
+            // The responses without a body carry their status in the headers
+            record.code = grpc_code(response.headers());
+
+            Ok(response.map(|body| BoxBody::new(LoggedResponseBody { inner: body, record })))
+        })
+    }
+}
+
+/// Request body counting its bytes and messages as they are read.
+struct CountedRequestBody {
+    inner: hyper::Body,
+    counter: Arc<Mutex<MessageCounter>>,
+}
+
+impl Stream for CountedRequestBody {
+    type Item = Result<Bytes, hyper::Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        let chunk = Pin::new(&mut this.inner).poll_data(cx);
+        if let Poll::Ready(Some(Ok(chunk))) = &chunk {
+            this.counter.lock().expect("Poisoned lock").update(chunk);
+        }
+
+        chunk
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, None)
+    }
+}
+
+/// Response body counting its bytes and messages as they are sent, and reading its status from
+/// the trailers. The request is logged when the body is dropped.
+struct LoggedResponseBody {
+    inner: BoxBody,
+    record: RequestRecord,
+}
+
+impl Body for LoggedResponseBody {
+    type Data = Bytes;
+    type Error = Status;
+
+    fn poll_data(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Self::Data, Self::Error>>> {
+        let this = self.get_mut();
+        let chunk = Pin::new(&mut this.inner).poll_data(cx);
+        match &chunk {
+            Poll::Ready(Some(Ok(chunk))) => this.record.response.update(chunk),
+            Poll::Ready(Some(Err(status))) => this.record.code = Some(status.code()),
+            _ => {},
+        }
+
+        chunk
+    }
+
+    fn poll_trailers(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<Option<http::HeaderMap>, Self::Error>> {
+        let this = self.get_mut();
+        let trailers = Pin::new(&mut this.inner).poll_trailers(cx);
+        if let Poll::Ready(Ok(Some(trailers))) = &trailers {
+            this.record.code = grpc_code(trailers).or(this.record.code);
+        }
+
+        trailers
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{atomic::AtomicU64, Arc};
+
+    use super::{AccessLog, MessageCounter};
+    use crate::config::AccessLogConfig;
+
+    /// Returns a gRPC message with a payload of `length` bytes.
+    fn message(length: u8) -> Vec<u8> {
+        let mut message = vec![0, 0, 0, 0, length];
+        message.extend(std::iter::repeat(7).take(length.into()));
+        message
+    }
+
+    #[test]
+    fn messages_are_counted_across_chunks() {
+        let body: Vec<u8> = [message(3), message(0), message(200)].concat();
+
+        for chunk_size in [1, 2, 5, 7, body.len()] {
+            let mut counter = MessageCounter::default();
+            body.chunks(chunk_size).for_each(|chunk| counter.update(chunk));
+
+            assert_eq!(counter.bytes, body.len() as u64);
+            assert_eq!(counter.messages, 3, "chunks of {chunk_size} bytes");
+        }
+    }
+
+    #[test]
+    fn requests_are_sampled_by_outcome() {
+        let log = Arc::new(AccessLog {
+            component: "test",
+            config: AccessLogConfig { sample_one_in: 3, error_sample_one_in: 0 },
+            successes: AtomicU64::new(0),
+            errors: AtomicU64::new(0),
+        });
+
+        let sampled: Vec<_> = (0..6).map(|_| log.is_sampled(true)).collect();
+        assert_eq!(sampled, [true, false, false, true, false, false]);
+        assert!(!log.is_sampled(false));
+    }
+}
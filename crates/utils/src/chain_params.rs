@@ -0,0 +1,273 @@
+//! Parameters of the shape of the chain, shared by the components of the node so they build the
+//! same trees and agree on the encodings they exchange.
+//!
+//! The parameters are those of the protocol version supported by the node. They are re-exported
+//! from `miden-objects` when it defines them, the node must not redefine them:
+//!
+//! - [ACCOUNT_TREE_DEPTH]: depth of the account tree, the account ids are the indexes of its
+//!   leaves.
+//! - [NULLIFIER_TREE_DEPTH]: depth of the nullifier tree, a sparse Merkle tree keyed by the
+//!   nullifiers.
+//! - [BATCH_OUTPUT_NOTES_TREE_DEPTH], [BLOCK_OUTPUT_NOTES_BATCH_TREE_DEPTH] and
+//!   [BLOCK_OUTPUT_NOTES_TREE_DEPTH]: depths of the note trees of the batches and blocks, bounding
+//!   [MAX_NOTES_PER_BATCH] and [MAX_BATCHES_PER_BLOCK].
+//!
+//! The parameters chosen by each chain at genesis are the [ChainParameters].
+use std::fmt::{Display, Formatter};
+
+use miden_objects::{
+    accounts::{AccountId, AccountType},
+    utils::serde::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable},
+    BlockHeader, Digest, Hasher, GENESIS_BLOCK,
+};
+pub use miden_objects::{
+    crypto::merkle::SMT_DEPTH as NULLIFIER_TREE_DEPTH, ACCOUNT_TREE_DEPTH,
+    BATCH_OUTPUT_NOTES_TREE_DEPTH, BLOCK_OUTPUT_NOTES_BATCH_TREE_DEPTH,
+    BLOCK_OUTPUT_NOTES_TREE_DEPTH, MAX_BATCHES_PER_BLOCK, MAX_NOTES_PER_BATCH,
+};
+
+use crate::{
+    errors::{ChainParametersError, GenesisError},
+    formatting::format_opt,
+};
+
+// NULLIFIERS
+// ================================================================================================
+
+/// Number of high bits of the nullifiers by which the store indexes them, and by which the clients
+/// request them in `SyncState`.
+pub const NULLIFIER_PREFIX_LEN: u32 = 16;
+
+// CHAIN PARAMETERS
+// ================================================================================================
+
+/// Parameters of a chain chosen at genesis, unlike the parameters of the protocol version above.
+///
+/// The genesis block header commits to them, and the store and block producer load them from the
+/// genesis file on startup, so the nodes of a chain can't disagree on them through their
+/// configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChainParameters {
+    /// Identifier of the chain, distinguishing the networks running the same protocol version.
+    pub chain_id: u32,
+    /// Maximum number of transactions in a batch.
+    pub max_batch_size: u32,
+    /// Depth of the note tree of the blocks, only [BLOCK_OUTPUT_NOTES_TREE_DEPTH] is supported.
+    pub note_tree_depth: u8,
+    /// Fees charged for the transactions, no fees are charged if not set.
+    pub fees: Option<ChainFees>,
+}
+
+/// Fee charged for each transaction, paid by a pay-to-id note sending at least `min_amount` of the
+/// fungible asset of `faucet_id` to `operator`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChainFees {
+    /// Account the fees are paid to.
+    pub operator: AccountId,
+    /// Faucet of the fungible asset the fees are paid in.
+    pub faucet_id: AccountId,
+    /// Minimum amount of the fee of a transaction.
+    pub min_amount: u64,
+}
+
+impl ChainParameters {
+    /// Checks the parameters are supported by the node.
+    pub fn validate(&self) -> Result<(), ChainParametersError> {
+        if self.max_batch_size == 0 {
+            return Err(ChainParametersError::EmptyBatches);
+        }
+
+        if self.note_tree_depth != BLOCK_OUTPUT_NOTES_TREE_DEPTH {
+            return Err(ChainParametersError::UnsupportedNoteTreeDepth {
+                depth: self.note_tree_depth,
+                expected: BLOCK_OUTPUT_NOTES_TREE_DEPTH,
+            });
+        }
+
+        if let Some(fees) = &self.fees {
+            if fees.faucet_id.account_type() != AccountType::FungibleFaucet {
+                return Err(ChainParametersError::NotFungibleFeeFaucet(fees.faucet_id));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the commitment to the parameters, stored in the genesis block header.
+    pub fn commitment(&self) -> Digest {
+        Hasher::hash(&self.to_bytes())
+    }
+}
+
+// CHAIN GENESIS
+// ================================================================================================
+
+/// Genesis block header of a chain and the parameters it commits to.
+///
+/// Only the store reads the genesis file, the block producer and the RPC fetch the genesis block
+/// from the store on startup and verify it, so they don't need access to the store's data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChainGenesis {
+    pub header: BlockHeader,
+    /// Parameters of the chain, [None] if the genesis file was created before they existed.
+    pub parameters: Option<ChainParameters>,
+}
+
+impl ChainGenesis {
+    /// Returns the hash of the genesis block, identifying the chain.
+    pub fn hash(&self) -> Digest {
+        self.header.hash()
+    }
+
+    /// Checks the header is that of a genesis block committing to the parameters, and that it is
+    /// the block `expected_hash` if set, which pins the chain a component runs on.
+    pub fn verify(&self, expected_hash: Option<Digest>) -> Result<(), GenesisError> {
+        if self.header.block_num() != GENESIS_BLOCK {
+            return Err(GenesisError::NotGenesisBlock(self.header.block_num()));
+        }
+
+        if let Some(expected) = expected_hash {
+            if self.hash() != expected {
+                return Err(GenesisError::UnexpectedGenesisBlock { expected, actual: self.hash() });
+            }
+        }
+
+        // The genesis blocks created before the parameters existed commit to the default digest
+        let commitment =
+            self.parameters.as_ref().map(ChainParameters::commitment).unwrap_or_default();
+        if self.header.proof_hash() != commitment {
+            return Err(GenesisError::ChainParametersMismatch {
+                committed: self.header.proof_hash(),
+                actual: commitment,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for ChainParameters {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!(
+            "{{ chain_id: {}, max_batch_size: {}, note_tree_depth: {}, fees: {} }}",
+            self.chain_id,
+            self.max_batch_size,
+            self.note_tree_depth,
+            format_opt(self.fees.as_ref()),
+        ))
+    }
+}
+
+impl Display for ChainFees {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!(
+            "{{ operator: {}, faucet_id: {}, min_amount: {} }}",
+            self.operator, self.faucet_id, self.min_amount
+        ))
+    }
+}
+
+impl Serializable for ChainParameters {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u32(self.chain_id);
+        target.write_u32(self.max_batch_size);
+        target.write_u8(self.note_tree_depth);
+        self.fees.write_into(target);
+    }
+}
+
+impl Deserializable for ChainParameters {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        Ok(Self {
+            chain_id: source.read_u32()?,
+            max_batch_size: source.read_u32()?,
+            note_tree_depth: source.read_u8()?,
+            fees: Option::read_from(source)?,
+        })
+    }
+}
+
+impl Serializable for ChainFees {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.operator.write_into(target);
+        self.faucet_id.write_into(target);
+        target.write_u64(self.min_amount);
+    }
+}
+
+impl Deserializable for ChainFees {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        Ok(Self {
+            operator: AccountId::read_from(source)?,
+            faucet_id: AccountId::read_from(source)?,
+            min_amount: source.read_u64()?,
+        })
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use miden_objects::{ONE, ZERO};
+
+    use super::*;
+
+    const PARAMETERS: ChainParameters = ChainParameters {
+        chain_id: 7,
+        max_batch_size: 16,
+        note_tree_depth: BLOCK_OUTPUT_NOTES_TREE_DEPTH,
+        fees: None,
+    };
+
+    fn header(block_num: u32, proof_hash: Digest) -> BlockHeader {
+        let digest = Digest::default();
+        BlockHeader::new(
+            digest, block_num, digest, digest, digest, digest, digest, proof_hash, ONE, ZERO,
+        )
+    }
+
+    #[test]
+    fn genesis_commits_to_its_parameters() {
+        let genesis = ChainGenesis {
+            header: header(GENESIS_BLOCK, PARAMETERS.commitment()),
+            parameters: Some(PARAMETERS),
+        };
+        assert_eq!(genesis.verify(None), Ok(()));
+        assert_eq!(genesis.verify(Some(genesis.hash())), Ok(()));
+        assert_eq!(
+            genesis.verify(Some(Digest::default())),
+            Err(GenesisError::UnexpectedGenesisBlock {
+                expected: Digest::default(),
+                actual: genesis.hash()
+            })
+        );
+
+        // parameters of another chain
+        let other = ChainParameters { chain_id: 8, ..PARAMETERS };
+        let tampered = ChainGenesis { parameters: Some(other), ..genesis };
+        assert_eq!(
+            tampered.verify(None),
+            Err(GenesisError::ChainParametersMismatch {
+                committed: PARAMETERS.commitment(),
+                actual: other.commitment()
+            })
+        );
+
+        // the parameters of the chains created without them are missing
+        let without_parameters = ChainGenesis { parameters: None, ..genesis };
+        assert!(without_parameters.verify(None).is_err());
+        let legacy = ChainGenesis {
+            header: header(GENESIS_BLOCK, Digest::default()),
+            parameters: None,
+        };
+        assert_eq!(legacy.verify(None), Ok(()));
+
+        let not_genesis = ChainGenesis {
+            header: header(1, Digest::default()),
+            parameters: None,
+        };
+        assert_eq!(not_genesis.verify(None), Err(GenesisError::NotGenesisBlock(1)));
+    }
+}
@@ -3,11 +3,13 @@ use std::{
     io,
     net::{SocketAddr, ToSocketAddrs},
     path::Path,
+    time::Duration,
     vec,
 };
 
 use figment::{
-    providers::{Format, Toml},
+    providers::{Env, Format, Toml},
+    value::Value,
     Figment,
 };
 use serde::{Deserialize, Serialize};
@@ -34,6 +36,97 @@ impl Display for Endpoint {
     }
 }
 
+/// Connection settings of the gRPC clients a component uses to call the other components.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ClientConfig {
+    /// Interval of the HTTP/2 keep-alive pings sent on the connection, including while it is idle,
+    /// in milliseconds. Zero disables the pings.
+    pub keep_alive_interval_ms: u64,
+    /// Time after which a connection is closed if a keep-alive ping or a health check is not
+    /// answered, in milliseconds.
+    pub keep_alive_timeout_ms: u64,
+    /// Maximum time to establish a connection, in milliseconds.
+    pub connect_timeout_ms: u64,
+    /// Interval of the health checks of the connection, in milliseconds. A connection failing its
+    /// health check is re-established in the background. Zero disables the health checks.
+    pub health_check_interval_ms: u64,
+}
+
+impl ClientConfig {
+    /// Returns the interval of the keep-alive pings, [None] if they are disabled.
+    pub fn keep_alive_interval(&self) -> Option<Duration> {
+        (self.keep_alive_interval_ms != 0)
+            .then(|| Duration::from_millis(self.keep_alive_interval_ms))
+    }
+
+    pub fn keep_alive_timeout(&self) -> Duration {
+        Duration::from_millis(self.keep_alive_timeout_ms)
+    }
+
+    pub fn connect_timeout(&self) -> Duration {
+        Duration::from_millis(self.connect_timeout_ms)
+    }
+
+    /// Returns the interval of the health checks, [None] if they are disabled.
+    pub fn health_check_interval(&self) -> Option<Duration> {
+        (self.health_check_interval_ms != 0)
+            .then(|| Duration::from_millis(self.health_check_interval_ms))
+    }
+}
+
+impl Default for ClientConfig {
+    fn default() -> Self {
+        Self {
+            keep_alive_interval_ms: 30_000,
+            keep_alive_timeout_ms: 10_000,
+            connect_timeout_ms: 5_000,
+            health_check_interval_ms: 60_000,
+        }
+    }
+}
+
+impl Display for ClientConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!(
+            "{{ keep_alive_interval_ms: {}, keep_alive_timeout_ms: {}, connect_timeout_ms: {}, \
+             health_check_interval_ms: {} }}",
+            self.keep_alive_interval_ms,
+            self.keep_alive_timeout_ms,
+            self.connect_timeout_ms,
+            self.health_check_interval_ms
+        ))
+    }
+}
+
+/// Access log of the requests served by a component.
+///
+/// Requests are sampled separately by outcome, one in `sample_one_in` successful requests and one
+/// in `error_sample_one_in` failed requests are logged. One logs every request.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AccessLogConfig {
+    /// Sampling of the successful requests.
+    pub sample_one_in: u64,
+    /// Sampling of the failed requests.
+    pub error_sample_one_in: u64,
+}
+
+impl Default for AccessLogConfig {
+    fn default() -> Self {
+        Self { sample_one_in: 1, error_sample_one_in: 1 }
+    }
+}
+
+impl Display for AccessLogConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!(
+            "{{ sample_one_in: {}, error_sample_one_in: {} }}",
+            self.sample_one_in, self.error_sample_one_in
+        ))
+    }
+}
+
 /// Loads the user configuration.
 ///
 /// This function will look for the configuration file at the provided path. If the path is
@@ -43,3 +136,40 @@ impl Display for Endpoint {
 pub fn load_config(config_file: &Path) -> Figment {
     Figment::from(Toml::file(config_file))
 }
+
+/// Loads the user configuration from `config_file`, overridden by the environment variables
+/// starting with `env_prefix`, themselves overridden by `overrides`.
+///
+/// The environment variables are named after the upper-cased keys, the nested keys being separated
+/// by `__`, e.g. `MIDEN_NODE_STORE__ENDPOINT__PORT` for `store.endpoint.port`. The overrides are
+/// `(key, value)` pairs, the nested keys being separated by `.`. The values of both are parsed as
+/// numbers, booleans, arrays or tables if possible, and as strings otherwise.
+pub fn load_layered_config(
+    config_file: &Path,
+    env_prefix: &str,
+    overrides: &[(String, String)],
+) -> Figment {
+    overrides.iter().fold(
+        load_config(config_file).merge(Env::prefixed(env_prefix).split("__")),
+        |figment, (key, value)| {
+            let value: Value = value.parse().unwrap_or_else(|never| match never {});
+            figment.merge((key.as_str(), value))
+        },
+    )
+}
+
+/// Checks that `url` is the URL of a gRPC server, i.e. `http[s]://<host>[:<port>]`, returning a
+/// description of the problem otherwise.
+pub fn validate_url(url: &str) -> Result<(), String> {
+    let uri: tonic::transport::Uri =
+        url.parse().map_err(|err| format!("`{url}` is not a valid URL: {err}"))?;
+
+    if !matches!(uri.scheme_str(), Some("http" | "https")) {
+        return Err(format!("`{url}` must start with `http://` or `https://`"));
+    }
+    if uri.host().map_or(true, str::is_empty) {
+        return Err(format!("`{url}` has no host"));
+    }
+
+    Ok(())
+}
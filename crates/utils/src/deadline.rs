@@ -0,0 +1,126 @@
+use std::time::{Duration, Instant};
+
+use tonic::{metadata::MetadataMap, Request, Status};
+
+/// Metadata key used by gRPC to transmit the request's timeout.
+pub const GRPC_TIMEOUT_HEADER: &str = "grpc-timeout";
+
+/// Returns the timeout set by the client in the `grpc-timeout` header, if any.
+///
+/// The header is formatted as a positive integer of at most 8 digits followed by a unit, as
+/// defined by the [gRPC spec](https://github.com/grpc/grpc/blob/master/doc/PROTOCOL-HTTP2.md).
+/// Malformed headers are ignored.
+pub fn grpc_timeout(metadata: &MetadataMap) -> Option<Duration> {
+    parse_grpc_timeout(metadata.get(GRPC_TIMEOUT_HEADER)?.to_str().ok()?)
+}
+
+/// Parses the value of a `grpc-timeout` header, see [grpc_timeout].
+pub(crate) fn parse_grpc_timeout(value: &str) -> Option<Duration> {
+    if value.len() < 2 || value.len() > 9 {
+        return None;
+    }
+
+    let (amount, unit) = value.split_at(value.len() - 1);
+    let amount: u64 = amount.parse().ok()?;
+
+    let timeout = match unit {
+        "H" => Duration::from_secs(amount * 60 * 60),
+        "M" => Duration::from_secs(amount * 60),
+        "S" => Duration::from_secs(amount),
+        "m" => Duration::from_millis(amount),
+        "u" => Duration::from_micros(amount),
+        "n" => Duration::from_nanos(amount),
+        _ => return None,
+    };
+
+    Some(timeout)
+}
+
+/// Returns the deadline of `request`, which is the earliest of the client's deadline and `timeout`
+/// from now.
+pub fn request_deadline<T>(request: &Request<T>, timeout: Duration) -> Instant {
+    let timeout = match grpc_timeout(request.metadata()) {
+        Some(client_timeout) => client_timeout.min(timeout),
+        None => timeout,
+    };
+
+    Instant::now() + timeout
+}
+
+/// Sets the time remaining until `deadline` as the timeout of `request`, so the deadline is
+/// enforced by the server handling the request.
+///
+/// Returns a `DEADLINE_EXCEEDED` error if the deadline has already passed.
+pub fn propagate_deadline<T>(request: &mut Request<T>, deadline: Instant) -> Result<(), Status> {
+    let remaining = deadline
+        .checked_duration_since(Instant::now())
+        .filter(|remaining| !remaining.is_zero())
+        .ok_or_else(|| Status::deadline_exceeded("Request deadline exceeded"))?;
+
+    request.set_timeout(remaining);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use tonic::{metadata::MetadataMap, Request};
+
+    use super::{grpc_timeout, propagate_deadline, request_deadline, GRPC_TIMEOUT_HEADER};
+
+    fn metadata(timeout: &str) -> MetadataMap {
+        let mut metadata = MetadataMap::new();
+        metadata.insert(GRPC_TIMEOUT_HEADER, timeout.parse().unwrap());
+        metadata
+    }
+
+    #[test]
+    fn grpc_timeout_is_parsed() {
+        assert_eq!(grpc_timeout(&metadata("2H")), Some(Duration::from_secs(7200)));
+        assert_eq!(grpc_timeout(&metadata("3M")), Some(Duration::from_secs(180)));
+        assert_eq!(grpc_timeout(&metadata("10S")), Some(Duration::from_secs(10)));
+        assert_eq!(grpc_timeout(&metadata("250m")), Some(Duration::from_millis(250)));
+        assert_eq!(grpc_timeout(&metadata("7u")), Some(Duration::from_micros(7)));
+        assert_eq!(grpc_timeout(&metadata("99999999n")), Some(Duration::from_nanos(99999999)));
+    }
+
+    #[test]
+    fn malformed_grpc_timeout_is_ignored() {
+        assert_eq!(grpc_timeout(&MetadataMap::new()), None);
+        assert_eq!(grpc_timeout(&metadata("S")), None);
+        assert_eq!(grpc_timeout(&metadata("10")), None);
+        assert_eq!(grpc_timeout(&metadata("10s")), None);
+        assert_eq!(grpc_timeout(&metadata("123456789S")), None);
+        assert_eq!(grpc_timeout(&metadata("-1S")), None);
+    }
+
+    #[test]
+    fn deadline_is_the_earliest_of_client_and_server() {
+        let mut request = Request::new(());
+        request.set_timeout(Duration::from_millis(100));
+
+        let deadline = request_deadline(&request, Duration::from_secs(60));
+        let mut forwarded = Request::new(());
+        propagate_deadline(&mut forwarded, deadline).unwrap();
+
+        let timeout = grpc_timeout(forwarded.metadata()).unwrap();
+        assert!(timeout <= Duration::from_millis(100));
+
+        let deadline = request_deadline(&Request::new(()), Duration::from_millis(100));
+        let mut forwarded = Request::new(());
+        propagate_deadline(&mut forwarded, deadline).unwrap();
+
+        let timeout = grpc_timeout(forwarded.metadata()).unwrap();
+        assert!(timeout <= Duration::from_millis(100));
+    }
+
+    #[test]
+    fn expired_deadline_is_rejected() {
+        let deadline = request_deadline(&Request::new(()), Duration::ZERO);
+
+        let status = propagate_deadline(&mut Request::new(()), deadline).unwrap_err();
+        assert_eq!(status.code(), tonic::Code::DeadlineExceeded);
+    }
+}
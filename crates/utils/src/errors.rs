@@ -1,5 +1,6 @@
+use miden_objects::{accounts::AccountId, Digest};
 use thiserror::Error;
-use tonic::transport::Error as TransportError;
+use tonic::{transport::Error as TransportError, Status};
 
 #[derive(Debug, Error)]
 pub enum ApiError {
@@ -18,4 +19,90 @@ pub enum ApiError {
 
     #[error("Connection to the database has failed: {0}")]
     DatabaseConnectionFailed(String),
+
+    #[error("Flushing the database has failed: {0}")]
+    DatabaseFlushFailed(String),
+
+    #[error("Invalid CORS origin: {0}")]
+    InvalidCorsOrigin(String),
+
+    #[error("Loading the signing key has failed: {0}")]
+    InvalidSigningKey(String),
+
+    #[error("Invalid sequencer public key: {0}")]
+    InvalidSequencerPublicKey(String),
+
+    #[error("Invalid fee configuration: {0}")]
+    InvalidFeeConfig(String),
+
+    #[error("Invalid submission policy: {0}")]
+    InvalidSubmissionPolicy(String),
+
+    #[error("Loading the testnet faucet has failed: {0}")]
+    InvalidTestnetFaucet(String),
+
+    #[error("Loading the genesis block from the store has failed: {0}")]
+    GenesisLoadFailed(String),
+
+    #[error("Getting the version of the {0} has failed: {1}")]
+    VersionHandshakeFailed(&'static str, String),
+
+    #[error(
+        "The {component} implements version {version} of the protocol, incompatible with version {expected}"
+    )]
+    IncompatibleProtocolVersion {
+        component: &'static str,
+        version: String,
+        expected: String,
+    },
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ChainParametersError {
+    #[error("The maximum batch size must not be zero")]
+    EmptyBatches,
+
+    #[error("Unsupported note tree depth {depth}, the node only supports depth {expected}")]
+    UnsupportedNoteTreeDepth { depth: u8, expected: u8 },
+
+    #[error("The fees must be paid in a fungible asset, account {0} is not a fungible faucet")]
+    NotFungibleFeeFaucet(AccountId),
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum GenesisError {
+    #[error("Block {0} is not the genesis block")]
+    NotGenesisBlock(u32),
+
+    #[error(
+        "The genesis block of the store is {actual}, not the configured genesis block {expected}"
+    )]
+    UnexpectedGenesisBlock { expected: Digest, actual: Digest },
+
+    #[error(
+        "The genesis block commits to the chain parameters {committed}, the parameters of the store hash to {actual}"
+    )]
+    ChainParametersMismatch { committed: Digest, actual: Digest },
+}
+
+#[derive(Debug, Error)]
+pub enum LogFilterError {
+    #[error("Invalid log filter: {0}")]
+    InvalidFilter(String),
+
+    #[error("Logging is not set up, the log filter can't be replaced")]
+    LoggingNotSetUp,
+
+    #[error("Replacing the log filter has failed: {0}")]
+    ReloadFailed(String),
+}
+
+impl From<LogFilterError> for Status {
+    fn from(err: LogFilterError) -> Self {
+        match err {
+            LogFilterError::InvalidFilter(_) => Status::invalid_argument(err.to_string()),
+            LogFilterError::LoggingNotSetUp => Status::failed_precondition(err.to_string()),
+            LogFilterError::ReloadFailed(_) => Status::internal(err.to_string()),
+        }
+    }
 }
@@ -0,0 +1,16 @@
+//! Build options of the node, reported by the components with their versions.
+
+/// Returns the build options the node is built with: the enabled cargo features and
+/// `tokio_unstable`, which enables the runtime metrics.
+pub fn build_features() -> Vec<String> {
+    let features = [
+        ("tracing-forest", cfg!(feature = "tracing-forest")),
+        ("tokio_unstable", cfg!(tokio_unstable)),
+    ];
+
+    features
+        .into_iter()
+        .filter(|(_, enabled)| *enabled)
+        .map(|(feature, _)| feature.to_string())
+        .collect()
+}
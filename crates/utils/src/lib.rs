@@ -1,4 +1,10 @@
+pub mod access_log;
+pub mod chain_params;
 pub mod config;
+pub mod deadline;
 pub mod errors;
+pub mod features;
 pub mod formatting;
 pub mod logging;
+pub mod runtime;
+pub mod transport;
@@ -1,44 +1,122 @@
+use std::sync::{Mutex, OnceLock};
+
 use anyhow::Result;
 use tracing::{
     level_filters::LevelFilter,
     subscriber::{self, Subscriber},
 };
-use tracing_subscriber::EnvFilter;
+use tracing_subscriber::{reload, EnvFilter};
+
+use crate::errors::LogFilterError;
+
+/// Filter of the global subscriber, set by [setup_logging].
+static LOG_FILTER: OnceLock<LogFilter> = OnceLock::new();
+
+/// Replaces the filter of a subscriber.
+type ReloadFilter = Box<dyn Fn(EnvFilter) -> Result<(), reload::Error> + Send + Sync>;
+
+/// Filter of the global subscriber, which can be replaced at runtime.
+struct LogFilter {
+    reload: ReloadFilter,
+    /// Directives of the current filter
+    current: Mutex<String>,
+    /// Directives of the filter set on startup, from the `RUST_LOG` environment variable
+    initial: String,
+}
 
 pub fn setup_logging() -> Result<()> {
-    subscriber::set_global_default(subscriber())?;
+    let filter = env_filter();
+    let initial = filter.to_string();
+    let (subscriber, reload) = reloadable_subscriber(filter);
+    subscriber::set_global_default(subscriber)?;
+
+    let current = Mutex::new(initial.clone());
+    // The global subscriber is only set once, so is its filter
+    let _ = LOG_FILTER.set(LogFilter { reload, current, initial });
 
     Ok(())
 }
 
-#[cfg(not(feature = "tracing-forest"))]
+/// Replaces the filter of the logs with the `filter` directives, in the syntax of the `RUST_LOG`
+/// environment variable, e.g. `info,miden-store=trace`. An empty `filter` restores the filter set
+/// on startup.
+///
+/// Returns the directives of the replaced filter.
+pub fn set_log_filter(filter: &str) -> Result<String, LogFilterError> {
+    let log_filter = LOG_FILTER.get().ok_or(LogFilterError::LoggingNotSetUp)?;
+
+    let directives = match filter.trim() {
+        "" => log_filter.initial.as_str(),
+        directives => directives,
+    };
+    let filter = EnvFilter::builder()
+        .with_default_directive(LevelFilter::INFO.into())
+        .parse(directives)
+        .map_err(|err| LogFilterError::InvalidFilter(err.to_string()))?;
+    let directives = filter.to_string();
+
+    let mut current = log_filter.current.lock().expect("Poisoned lock");
+    (log_filter.reload)(filter).map_err(|err| LogFilterError::ReloadFailed(err.to_string()))?;
+
+    Ok(std::mem::replace(&mut current, directives))
+}
+
+/// Returns the filter of the logs, from the `RUST_LOG` environment variable.
+fn env_filter() -> EnvFilter {
+    EnvFilter::builder()
+        .with_default_directive(LevelFilter::INFO.into())
+        .from_env_lossy()
+}
+
 pub fn subscriber() -> impl Subscriber + core::fmt::Debug {
+    reloadable_subscriber(env_filter()).0
+}
+
+#[cfg(not(feature = "tracing-forest"))]
+fn reloadable_subscriber(filter: EnvFilter) -> (impl Subscriber + core::fmt::Debug, ReloadFilter) {
     use tracing_subscriber::fmt::format::FmtSpan;
 
-    tracing_subscriber::fmt()
+    let builder = tracing_subscriber::fmt()
         .pretty()
         .compact()
         .with_level(true)
         .with_file(true)
         .with_line_number(true)
         .with_target(true)
-        .with_env_filter(
-            EnvFilter::builder()
-                .with_default_directive(LevelFilter::INFO.into())
-                .from_env_lossy(),
-        )
+        .with_env_filter(filter)
         .with_span_events(FmtSpan::NEW | FmtSpan::CLOSE)
-        .finish()
+        .with_filter_reloading();
+    let handle = builder.reload_handle();
+
+    (builder.finish(), Box::new(move |filter| handle.reload(filter)))
 }
 
 #[cfg(feature = "tracing-forest")]
-pub fn subscriber() -> impl Subscriber + core::fmt::Debug {
+fn reloadable_subscriber(filter: EnvFilter) -> (impl Subscriber + core::fmt::Debug, ReloadFilter) {
     pub use tracing_forest::ForestLayer;
     pub use tracing_subscriber::{layer::SubscriberExt, Registry};
 
-    Registry::default().with(ForestLayer::default()).with(
-        EnvFilter::builder()
-            .with_default_directive(LevelFilter::INFO.into())
-            .from_env_lossy(),
-    )
+    let (filter, handle) = reload::Layer::new(filter);
+    let subscriber = Registry::default().with(ForestLayer::default()).with(filter);
+
+    (subscriber, Box::new(move |filter| handle.reload(filter)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{set_log_filter, setup_logging};
+    use crate::errors::LogFilterError;
+
+    #[test]
+    fn log_filter_is_replaced_and_restored() {
+        setup_logging().unwrap();
+        let initial = set_log_filter("").unwrap();
+
+        assert!(matches!(set_log_filter("info,="), Err(LogFilterError::InvalidFilter(_))));
+
+        assert_eq!(set_log_filter("warn,miden-store=trace").unwrap(), initial);
+        let replaced = set_log_filter("").unwrap();
+        assert!(replaced.contains("miden-store=trace"), "{replaced}");
+        assert_eq!(set_log_filter("").unwrap(), initial);
+    }
 }
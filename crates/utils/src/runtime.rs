@@ -0,0 +1,156 @@
+//! Named tasks and metrics of the tokio runtime, to diagnose the stalls of the async pipeline.
+//!
+//! The task names and the runtime metrics rely on unstable tokio APIs, available only when the
+//! node is built with `RUSTFLAGS="--cfg tokio_unstable"`. Otherwise the tasks are spawned unnamed
+//! and no runtime metrics are reported.
+#[cfg(tokio_unstable)]
+use std::time::Duration;
+use std::{
+    future::Future,
+    sync::{Once, RwLock},
+};
+
+use tokio::task::JoinHandle;
+use tracing::debug;
+
+const COMPONENT: &str = "miden-runtime";
+
+/// Interval between two samples of the runtime metrics.
+#[cfg(tokio_unstable)]
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Latest sample of the runtime metrics, shared by all the components of the process.
+static RUNTIME_METRICS: RwLock<Option<RuntimeMetrics>> = RwLock::new(None);
+
+// NAMED TASKS
+// ================================================================================================
+
+/// Spawns `future` as a task named `name`, shown by the tools inspecting the runtime, e.g.
+/// `tokio-console`.
+pub fn spawn_named<F>(name: &'static str, future: F) -> JoinHandle<F::Output>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    debug!(target: COMPONENT, task = name, "Spawning task");
+
+    #[cfg(tokio_unstable)]
+    {
+        tokio::task::Builder::new()
+            .name(name)
+            .spawn(future)
+            .expect("Spawning a task outside of a runtime")
+    }
+    #[cfg(not(tokio_unstable))]
+    {
+        tokio::spawn(future)
+    }
+}
+
+/// Runs `future` to completion as a task named `name`, resuming its panic if it panics.
+pub async fn run_named<F>(name: &'static str, future: F) -> F::Output
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    match spawn_named(name, future).await {
+        Ok(output) => output,
+        Err(err) => match err.try_into_panic() {
+            Ok(panic) => std::panic::resume_unwind(panic),
+            Err(err) => panic!("Task {name} was cancelled: {err}"),
+        },
+    }
+}
+
+// RUNTIME METRICS
+// ================================================================================================
+
+/// Sample of the metrics of the tokio runtime.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RuntimeMetrics {
+    /// Number of worker threads
+    pub workers: usize,
+    /// Fraction of the time the workers were busy since the previous sample, from 0 to 1
+    pub worker_utilization: f64,
+    /// Number of tasks waiting in the global queue, spawned from outside of the workers
+    pub global_queue_depth: usize,
+    /// Number of tasks waiting in the local queues of the workers
+    pub local_queue_depth: usize,
+    /// Number of blocking threads running a blocking task, e.g. a database query
+    pub busy_blocking_threads: usize,
+    /// Number of blocking threads waiting for a blocking task
+    pub idle_blocking_threads: usize,
+    /// Number of blocking tasks waiting for a blocking thread
+    pub blocking_queue_depth: usize,
+    /// Number of tasks alive
+    pub active_tasks: usize,
+}
+
+/// Returns the latest sample of the runtime metrics, [None] before the first one or if the node
+/// isn't built with `tokio_unstable`.
+pub fn runtime_metrics() -> Option<RuntimeMetrics> {
+    *RUNTIME_METRICS.read().expect("Poisoned lock")
+}
+
+/// Starts sampling the runtime metrics in the background, once per process however many
+/// components call it.
+pub fn start_runtime_metrics_sampling() {
+    static STARTED: Once = Once::new();
+
+    STARTED.call_once(|| {
+        #[cfg(tokio_unstable)]
+        spawn_named("runtime-metrics", sample_runtime_metrics());
+        #[cfg(not(tokio_unstable))]
+        debug!(target: COMPONENT, "Runtime metrics require building with tokio_unstable");
+    });
+}
+
+/// Samples the runtime metrics every [SAMPLE_INTERVAL].
+#[cfg(tokio_unstable)]
+async fn sample_runtime_metrics() {
+    use std::time::Instant;
+
+    let metrics = tokio::runtime::Handle::current().metrics();
+    let busy_duration = |metrics: &tokio::runtime::RuntimeMetrics| -> Duration {
+        (0..metrics.num_workers())
+            .map(|worker| metrics.worker_total_busy_duration(worker))
+            .sum()
+    };
+
+    let mut interval = tokio::time::interval(SAMPLE_INTERVAL);
+    // The first tick completes immediately
+    interval.tick().await;
+    let mut previous = (Instant::now(), busy_duration(&metrics));
+    loop {
+        interval.tick().await;
+
+        let now = Instant::now();
+        let busy = busy_duration(&metrics);
+        let workers = metrics.num_workers();
+        let elapsed = now.duration_since(previous.0).as_secs_f64() * workers as f64;
+        let worker_utilization = if elapsed > 0.0 {
+            (busy.saturating_sub(previous.1).as_secs_f64() / elapsed).min(1.0)
+        } else {
+            0.0
+        };
+        previous = (now, busy);
+
+        let blocking_threads = metrics.num_blocking_threads();
+        let idle_blocking_threads = metrics.num_idle_blocking_threads();
+        let sample = RuntimeMetrics {
+            workers,
+            worker_utilization,
+            global_queue_depth: metrics.injection_queue_depth(),
+            local_queue_depth: (0..workers)
+                .map(|worker| metrics.worker_local_queue_depth(worker))
+                .sum(),
+            busy_blocking_threads: blocking_threads.saturating_sub(idle_blocking_threads),
+            idle_blocking_threads,
+            blocking_queue_depth: metrics.blocking_queue_depth(),
+            active_tasks: metrics.active_tasks_count(),
+        };
+        debug!(target: COMPONENT, ?sample, "Runtime metrics sampled");
+
+        *RUNTIME_METRICS.write().expect("Poisoned lock") = Some(sample);
+    }
+}
@@ -0,0 +1,414 @@
+use std::{
+    convert::Infallible,
+    fmt::{Debug, Formatter},
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, RwLock, Weak,
+    },
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use http_body::Body;
+use tokio::sync::Notify;
+use tonic::{
+    body::{empty_body, BoxBody},
+    codegen::{http, Service, StdError},
+    transport::{Channel, Endpoint},
+    Status,
+};
+use tower::ServiceExt;
+use tracing::{info, warn};
+
+use crate::{
+    config::ClientConfig,
+    deadline::{parse_grpc_timeout, GRPC_TIMEOUT_HEADER},
+};
+
+type ResponseFuture =
+    Pin<Box<dyn Future<Output = Result<http::Response<BoxBody>, StdError>> + Send>>;
+
+/// Method called by the health checks. The components don't serve it, but any gRPC response shows
+/// that the connection works.
+const HEALTH_CHECK_PATH: &str = "/grpc.health.v1.Health/Check";
+
+const COMPONENT: &str = "miden-transport";
+
+// COMPONENT CHANNEL
+// ================================================================================================
+
+/// Transport used by a component to send requests to another component.
+///
+/// The requests are either sent over the network to a remote gRPC server, or handed directly to a
+/// gRPC service running in the same process. The latter avoids the network stack, but requests and
+/// responses are still encoded to protobuf.
+///
+/// Can be used with any generated gRPC client, e.g. `ApiClient::new(channel)`.
+#[derive(Clone)]
+pub struct ComponentChannel {
+    call: Arc<dyn Fn(http::Request<BoxBody>) -> ResponseFuture + Send + Sync>,
+    /// The monitored connection to the remote server, if any
+    connection: Option<Arc<RemoteConnection>>,
+}
+
+impl ComponentChannel {
+    /// Connects to the gRPC server at `url`, in the format `http://<host>[:<port>]`, with the
+    /// default [ClientConfig].
+    pub async fn connect(url: impl Into<String>) -> Result<Self, tonic::transport::Error> {
+        Self::connect_with(url, &ClientConfig::default()).await
+    }
+
+    /// Connects to the gRPC server at `url`, in the format `http://<host>[:<port>]`.
+    ///
+    /// The connection is kept alive with HTTP/2 pings, so a broken connection is detected and
+    /// replaced before the next request, instead of failing it after a long idle period. If health
+    /// checks are enabled, a background task also checks the connection periodically and
+    /// re-establishes it when it fails. A request failing with a transport error, e.g. because the
+    /// server went away, triggers an immediate health check.
+    pub async fn connect_with(
+        url: impl Into<String>,
+        config: &ClientConfig,
+    ) -> Result<Self, tonic::transport::Error> {
+        let mut endpoint = Endpoint::from_shared(url.into())?
+            .connect_timeout(config.connect_timeout())
+            .keep_alive_timeout(config.keep_alive_timeout())
+            .keep_alive_while_idle(true)
+            .tcp_keepalive(config.keep_alive_interval());
+        if let Some(interval) = config.keep_alive_interval() {
+            endpoint = endpoint.http2_keep_alive_interval(interval);
+        }
+        let channel = endpoint.connect().await?;
+
+        let Some(interval) = config.health_check_interval() else {
+            return Ok(Self::remote(channel));
+        };
+
+        let connection = Arc::new(RemoteConnection {
+            endpoint,
+            channel: RwLock::new(channel),
+            healthy: AtomicBool::new(true),
+            check_now: Arc::new(Notify::new()),
+            timeout: config.keep_alive_timeout(),
+        });
+        tokio::spawn(monitor_health(
+            Arc::downgrade(&connection),
+            connection.check_now.clone(),
+            interval,
+        ));
+
+        let call = {
+            let connection = connection.clone();
+            move |request| -> ResponseFuture {
+                let response = send(connection.channel(), request);
+                let check_now = connection.check_now.clone();
+                Box::pin(async move {
+                    let response = response.await;
+                    if response.is_err() {
+                        check_now.notify_one();
+                    }
+                    response
+                })
+            }
+        };
+
+        Ok(Self {
+            call: Arc::new(call),
+            connection: Some(connection),
+        })
+    }
+
+    /// Returns a transport sending the requests over an already connected channel.
+    pub fn remote(channel: Channel) -> Self {
+        let call = move |request| send(channel.clone(), request);
+
+        Self { call: Arc::new(call), connection: None }
+    }
+
+    /// Returns a transport handing the requests to `service`, e.g. a generated `ApiServer`.
+    ///
+    /// The timeout set by the client in the `grpc-timeout` header is enforced, the same way as
+    /// it is enforced by a tonic server.
+    pub fn in_process<S>(service: S) -> Self
+    where
+        S: Service<http::Request<BoxBody>, Response = http::Response<BoxBody>, Error = Infallible>
+            + Clone
+            + Send
+            + Sync
+            + 'static,
+        S::Future: Send + 'static,
+    {
+        let call = move |request: http::Request<BoxBody>| -> ResponseFuture {
+            let timeout = request
+                .headers()
+                .get(GRPC_TIMEOUT_HEADER)
+                .and_then(|value| value.to_str().ok())
+                .and_then(parse_grpc_timeout);
+            let response = service.clone().oneshot(request);
+
+            Box::pin(async move {
+                let response = match timeout {
+                    Some(timeout) => match tokio::time::timeout(timeout, response).await {
+                        Ok(response) => response,
+                        Err(_) => return Ok(Status::deadline_exceeded("Timeout expired").to_http()),
+                    },
+                    None => response.await,
+                };
+
+                Ok(response.unwrap_or_else(|never| match never {}))
+            })
+        };
+
+        Self { call: Arc::new(call), connection: None }
+    }
+
+    /// Returns `false` if the last health check of the connection failed and it was not
+    /// re-established yet. The transports without health checks are always considered healthy.
+    pub fn is_healthy(&self) -> bool {
+        self.connection
+            .as_ref()
+            .map_or(true, |connection| connection.healthy.load(Ordering::Acquire))
+    }
+}
+
+/// Sends `request` over `channel`.
+fn send(channel: Channel, request: http::Request<BoxBody>) -> ResponseFuture {
+    let response = channel.oneshot(request);
+    Box::pin(async move {
+        let response = response.await?;
+        Ok(response
+            .map(|body| body.map_err(|err| Status::from_error(Box::new(err))).boxed_unsync()))
+    })
+}
+
+// CONNECTION HEALTH
+// ================================================================================================
+
+/// A connection to a remote gRPC server, replaced by its health monitor when it fails.
+struct RemoteConnection {
+    endpoint: Endpoint,
+    channel: RwLock<Channel>,
+    healthy: AtomicBool,
+    /// Wakes up the health monitor to check the connection immediately
+    check_now: Arc<Notify>,
+    /// Time allowed to a health check
+    timeout: Duration,
+}
+
+impl RemoteConnection {
+    fn channel(&self) -> Channel {
+        self.channel.read().expect("channel lock is not poisoned").clone()
+    }
+
+    /// Sends a request to the server, any gRPC response shows that the connection works.
+    async fn check(&self) -> Result<(), String> {
+        let request = http::Request::builder()
+            .method(http::Method::POST)
+            .uri(HEALTH_CHECK_PATH)
+            .header(http::header::CONTENT_TYPE, "application/grpc")
+            .header(http::header::TE, "trailers")
+            .body(empty_body())
+            .expect("health check request is valid");
+
+        match tokio::time::timeout(self.timeout, send(self.channel(), request)).await {
+            Ok(Ok(_)) => Ok(()),
+            Ok(Err(err)) => Err(err.to_string()),
+            Err(_) => Err("health check timed out".to_string()),
+        }
+    }
+
+    /// Establishes a new connection to the server, replacing the current one.
+    async fn reconnect(&self) -> Result<(), tonic::transport::Error> {
+        let channel = self.endpoint.connect().await?;
+        *self.channel.write().expect("channel lock is not poisoned") = channel;
+
+        Ok(())
+    }
+}
+
+/// Checks the connection every `interval`, or when woken up by `check_now`, and re-establishes it
+/// if it fails. Stops once the connection is dropped.
+async fn monitor_health(
+    connection: Weak<RemoteConnection>,
+    check_now: Arc<Notify>,
+    interval: Duration,
+) {
+    loop {
+        tokio::select! {
+            _ = tokio::time::sleep(interval) => {},
+            _ = check_now.notified() => {},
+        }
+
+        let Some(connection) = connection.upgrade() else {
+            return;
+        };
+        let url = connection.endpoint.uri().to_string();
+
+        let Err(err) = connection.check().await else {
+            connection.healthy.store(true, Ordering::Release);
+            continue;
+        };
+
+        connection.healthy.store(false, Ordering::Release);
+        warn!(target: COMPONENT, url, %err, "Connection health check failed, reconnecting");
+
+        match connection.reconnect().await {
+            Ok(()) => {
+                connection.healthy.store(true, Ordering::Release);
+                info!(target: COMPONENT, url, "Connection re-established");
+            },
+            Err(err) => warn!(target: COMPONENT, url, %err, "Reconnection failed"),
+        }
+    }
+}
+
+impl Service<http::Request<BoxBody>> for ComponentChannel {
+    type Response = http::Response<BoxBody>;
+    type Error = StdError;
+    type Future = ResponseFuture;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: http::Request<BoxBody>) -> Self::Future {
+        (self.call)(request)
+    }
+}
+
+impl Debug for ComponentChannel {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ComponentChannel")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        convert::Infallible,
+        future::{ready, Ready},
+        net::SocketAddr,
+        task::{Context, Poll},
+        time::Duration,
+    };
+
+    use tokio::sync::oneshot;
+    use tonic::{
+        body::{empty_body, BoxBody},
+        codegen::{http, Service},
+        server::NamedService,
+        transport::{Body, Server},
+        Code, Status,
+    };
+    use tower::{service_fn, ServiceExt};
+
+    use super::ComponentChannel;
+    use crate::{config::ClientConfig, deadline::GRPC_TIMEOUT_HEADER};
+
+    /// Service answering every request with `UNIMPLEMENTED`.
+    #[derive(Clone)]
+    struct Unimplemented;
+
+    impl NamedService for Unimplemented {
+        const NAME: &'static str = "test.Unimplemented";
+    }
+
+    impl Service<http::Request<Body>> for Unimplemented {
+        type Response = http::Response<BoxBody>;
+        type Error = Infallible;
+        type Future = Ready<Result<Self::Response, Infallible>>;
+
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, _request: http::Request<Body>) -> Self::Future {
+            ready(Ok(Status::unimplemented("").to_http()))
+        }
+    }
+
+    /// Serves [Unimplemented] on `addr` until the returned sender is dropped.
+    fn serve(addr: SocketAddr) -> oneshot::Sender<()> {
+        let (shutdown, stopped) = oneshot::channel::<()>();
+        tokio::spawn(Server::builder().add_service(Unimplemented).serve_with_shutdown(
+            addr,
+            async {
+                let _ = stopped.await;
+            },
+        ));
+        shutdown
+    }
+
+    fn grpc_request() -> http::Request<BoxBody> {
+        http::Request::builder()
+            .method(http::Method::POST)
+            .uri("/test.Unimplemented/Call")
+            .header(http::header::CONTENT_TYPE, "application/grpc")
+            .body(empty_body())
+            .unwrap()
+    }
+
+    async fn wait_until(channel: &ComponentChannel, healthy: bool) {
+        for _ in 0..100 {
+            if channel.is_healthy() == healthy {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        panic!("connection health did not become {healthy}");
+    }
+
+    #[tokio::test]
+    async fn remote_connection_is_re_established() {
+        let addr = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap();
+        let config = ClientConfig {
+            keep_alive_interval_ms: 100,
+            keep_alive_timeout_ms: 200,
+            connect_timeout_ms: 200,
+            health_check_interval_ms: 50,
+        };
+
+        let server = serve(addr);
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let channel =
+            ComponentChannel::connect_with(format!("http://{addr}"), &config).await.unwrap();
+        assert!(channel.clone().oneshot(grpc_request()).await.is_ok());
+
+        drop(server);
+        wait_until(&channel, false).await;
+
+        let _server = serve(addr);
+        wait_until(&channel, true).await;
+        assert!(channel.clone().oneshot(grpc_request()).await.is_ok());
+    }
+
+    fn slow_service(delay: Duration) -> ComponentChannel {
+        ComponentChannel::in_process(service_fn(move |_request| async move {
+            tokio::time::sleep(delay).await;
+            Ok::<_, Infallible>(http::Response::new(empty_body()))
+        }))
+    }
+
+    fn request(timeout: &str) -> http::Request<tonic::body::BoxBody> {
+        http::Request::builder()
+            .header(GRPC_TIMEOUT_HEADER, timeout)
+            .body(empty_body())
+            .unwrap()
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn in_process_request_is_served() {
+        let response = slow_service(Duration::from_secs(1)).oneshot(request("2S")).await.unwrap();
+
+        assert!(Status::from_header_map(response.headers()).is_none());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn in_process_request_times_out() {
+        let response = slow_service(Duration::from_secs(2)).oneshot(request("1S")).await.unwrap();
+
+        let status = Status::from_header_map(response.headers()).unwrap();
+        assert_eq!(status.code(), Code::DeadlineExceeded);
+    }
+}